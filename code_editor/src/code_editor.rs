@@ -248,6 +248,8 @@ pub struct CodeEditor {
     #[animator] animator: Animator,
 
     #[rust] blink_timer: Timer,
+    #[rust(LiveId::unique())] primary_paste_id: LiveId,
+    #[rust] primary_selection: String,
 }
 
 enum KeepCursorInView {
@@ -538,6 +540,18 @@ impl CodeEditor {
         }
     }
 
+    /// Hands a changed selection to the primary selection, the one middle click pastes on X11
+    fn update_primary_selection(&mut self, cx: &mut Cx, session: &Session) {
+        let selected = session.copy();
+        if selected == self.primary_selection {
+            return
+        }
+        if !selected.is_empty() {
+            cx.copy_to_clipboard(ClipboardSelection::Primary, vec![ClipboardItem::text(&selected)]);
+        }
+        self.primary_selection = selected;
+    }
+
     pub fn reset_cursor_blinker(&mut self, cx: &mut Cx) {
         self.animator_cut(cx, id!(blink.off));
         cx.stop_timer(self.blink_timer);
@@ -568,7 +582,17 @@ impl CodeEditor {
             }
             self.blink_timer = cx.start_timeout(self.blink_speed)
         }
+        if let Event::ClipboardRead(cr) = event {
+            if cr.request_id == self.primary_paste_id {
+                if let Some(text) = cr.item.as_ref().and_then( | item | item.as_text()).filter( | text | !text.is_empty()) {
+                    session.paste(text.into());
+                    self.redraw(cx);
+                    actions.push(CodeEditorAction::TextDidChange);
+                }
+            }
+        }
         let mut keyboard_moved_cursor = false;
+        let mut mouse_selected = false;
         match event.hits(cx, self.scroll_bars.area()) {
             Hit::KeyFocusLost(_) => {
                 self.animator_play(cx, id!(focus.off));
@@ -853,6 +877,24 @@ impl CodeEditor {
                     keyboard_moved_cursor = true;
                 }
             }
+            Hit::FingerDown(FingerDownEvent {
+                abs,
+                device,
+                ..
+            }) if device.mouse_button() == Some(2) => {
+                // middle click pastes the primary selection where it lands
+                self.animator_play(cx, id!(focus.on));
+                cx.set_key_focus(self.scroll_bars.area());
+                let ((cursor, affinity), _) = self.pick(session, abs);
+                session.set_selection(cursor, affinity, SelectionMode::Simple);
+                cx.read_clipboard(self.primary_paste_id, ClipboardSelection::Primary, &[ClipboardItem::TEXT]);
+                self.reset_cursor_blinker(cx);
+                self.redraw(cx);
+            }
+            Hit::FingerMove(FingerMoveEvent {
+                device,
+                ..
+            }) if device.mouse_button() == Some(2) => {}
             Hit::FingerDown(FingerDownEvent {
                 abs,
                 tap_count,
@@ -920,6 +962,7 @@ impl CodeEditor {
             Hit::FingerUp(_) => {
                 self.reset_cursor_blinker(cx);
                 self.keep_cursor_in_view = KeepCursorInView::Off;
+                mouse_selected = true;
             }
             Hit::FingerHoverIn(_) | Hit::FingerHoverOver(_) => {
                 cx.set_cursor(MouseCursor::Text);
@@ -946,6 +989,9 @@ impl CodeEditor {
             self.keep_cursor_in_view = KeepCursorInView::Once;
             self.reset_cursor_blinker(cx);
         }
+        if keyboard_moved_cursor || mouse_selected {
+            self.update_primary_selection(cx, session);
+        }
         if let KeepCursorInView::Always(abs, next) = &mut self.keep_cursor_in_view {
             if next.is_event(event).is_some() {
                 *next = cx.new_next_frame();
@@ -13,6 +13,7 @@ use {
             Pass,
             CxPassParent,
             CxPassRect,
+            WindowId,
             Cx
         },
         nav::CxNavTreeRc,
@@ -99,6 +100,11 @@ impl<'a> Cx2d<'a> {
         self.pass_stack.last().unwrap().dpi_factor
     }
     
+    /// The window the pass we are drawing into ends up on
+    pub fn current_window_id(&self) -> Option<WindowId> {
+        self.pass_stack.last().and_then( | item | self.cx.get_pass_window_id(item.pass_id))
+    }
+    
     pub fn inside_pass(&self) -> bool {
         self.pass_stack.len()>0
     }
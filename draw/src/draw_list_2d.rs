@@ -2,6 +2,7 @@ use {
     crate::{
         makepad_platform::*,
         nav::*,
+        overlay::{OverlayLayer, CxOverlayStack},
         cx_2d::{Cx2d},
         turtle::{Walk,AlignEntry}
     }
@@ -92,6 +93,13 @@ impl DrawList2d {
         self.begin_overlay_inner(cx, false)
     }
     
+    /// Begins an overlay that stacks in `layer` of the overlay stack, see `OverlayLayer`
+    pub fn begin_overlay_layer(&mut self, cx: &mut Cx2d, layer: OverlayLayer) {
+        let overlay_id = cx.overlay_id.unwrap();
+        cx.cx.global::<CxOverlayStack>().push(overlay_id, self.draw_list.id(), layer);
+        self.begin_overlay_inner(cx, false)
+    }
+    
     pub fn begin_overlay_inner(&mut self, cx: &mut Cx2d, always_last:bool) {
         let pass_id = cx.pass_stack.last().unwrap().pass_id;
         let redraw_id = cx.cx.redraw_id;
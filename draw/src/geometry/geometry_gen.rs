@@ -12,6 +12,8 @@ live_design!{
         x2: 1.0;
         y2: 1.0;
     }
+    
+    GeometryMesh3D = {{GeometryMesh3D}} {}
 }
 
 impl LiveHook for GeometryQuad2D {
@@ -61,6 +63,29 @@ pub struct GeometryQuad2D {
     #[live(1.0)] pub y2: f32,
 }
 
+/// The vertex layout of `add_cube_3d` and loaded meshes, without a geometry of its own:
+/// the geometry is picked per draw with `DrawVars::set_geometry`.
+#[derive(Live, LiveHook, LiveRegister)]
+pub struct GeometryMesh3D {
+}
+
+impl GeometryFields for GeometryMesh3D {
+    fn geometry_fields(&self, fields: &mut Vec<GeometryField>) {
+        fields.push(GeometryField {id: live_id!(geom_pos), ty: ShaderTy::Vec3});
+        fields.push(GeometryField {id: live_id!(geom_id), ty: ShaderTy::Float});
+        fields.push(GeometryField {id: live_id!(geom_normal), ty: ShaderTy::Vec3});
+        fields.push(GeometryField {id: live_id!(geom_uv), ty: ShaderTy::Vec2});
+    }
+    
+    fn get_geometry_id(&self) -> Option<GeometryId> {
+        None
+    }
+    
+    fn live_type_check(&self) -> LiveType {
+        LiveType::of::<Self>()
+    }
+}
+
 #[derive(Clone, Debug, Default, PartialEq)]
 pub struct GeometryGen {
     pub vertices: Vec<f32>, // vec4 pos, vec3 normal, vec2 uv
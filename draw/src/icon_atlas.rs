@@ -424,7 +424,8 @@ impl<'a> Cx2d<'a> {
     
 }
 
-fn parse_svg_path(path: &[u8]) -> Result<Vec<PathCommand>, String> {
+/// Parses SVG path data (the `d` attribute) into path commands.
+pub fn parse_svg_path(path: &[u8]) -> Result<Vec<PathCommand>, String> {
     #[derive(Debug)]
     enum Cmd {
         Unknown,
@@ -434,7 +435,9 @@ fn parse_svg_path(path: &[u8]) -> Result<Vec<PathCommand>, String> {
         Line(bool),
         Arc(bool),
         Cubic(bool),
+        SmoothCubic(bool),
         Quadratic(bool),
+        SmoothQuadratic(bool),
         Close
     }
     impl Default for Cmd {fn default() -> Self {Self::Unknown}}
@@ -447,6 +450,8 @@ fn parse_svg_path(path: &[u8]) -> Result<Vec<PathCommand>, String> {
         nums: [f64; 7],
         num_count: usize,
         last_pt: Point,
+        // the second control point of the previous curve, mirrored by S and T
+        last_ctrl: Option<Point>,
         out: Vec<PathCommand>,
         num_state: Option<NumState>
     }
@@ -456,13 +461,21 @@ fn parse_svg_path(path: &[u8]) -> Result<Vec<PathCommand>, String> {
         num: f64,
         mul: f64,
         has_dot: bool,
+        exp: Option<(i32, i32)>,
     }
     
     impl NumState {
-        fn new_pos(v: f64) -> Self {Self {num: v, mul: 1.0, has_dot: false}}
-        fn new_min() -> Self {Self {num: 0.0, mul: -1.0, has_dot: false}}
-        fn finalize(self) -> f64 {self.num * self.mul}
+        fn new_pos(v: f64) -> Self {Self {num: v, mul: 1.0, has_dot: false, exp: None}}
+        fn new_min() -> Self {Self {num: 0.0, mul: -1.0, has_dot: false, exp: None}}
+        fn finalize(self) -> f64 {
+            let (exp_sign, exp) = self.exp.unwrap_or((1, 0));
+            self.num * self.mul * 10f64.powi(exp_sign * exp)
+        }
         fn add_digit(&mut self, digit: f64) {
+            if let Some((_, exp)) = &mut self.exp {
+                *exp = *exp * 10 + digit as i32;
+                return
+            }
             self.num *= 10.0;
             self.num += digit;
             if self.has_dot {
@@ -482,6 +495,8 @@ fn parse_svg_path(path: &[u8]) -> Result<Vec<PathCommand>, String> {
                 Cmd::Vert(_) => 1,
                 Cmd::Line(_) => 2,
                 Cmd::Cubic(_) => 6,
+                Cmd::SmoothCubic(_) => 4,
+                Cmd::SmoothQuadratic(_) => 2,
                 Cmd::Arc(_) => 7,
                 Cmd::Quadratic(_) => 4,
                 Cmd::Close => 0
@@ -491,6 +506,10 @@ fn parse_svg_path(path: &[u8]) -> Result<Vec<PathCommand>, String> {
         }
         
         fn add_min(&mut self) -> Result<(), String> {
+            if let Some(NumState {exp: Some((exp_sign, 0)), ..}) = &mut self.num_state {
+                *exp_sign = -1;
+                return Ok(())
+            }
             if self.num_state.is_some() {
                 self.finalize_num();
             }
@@ -520,6 +539,16 @@ fn parse_svg_path(path: &[u8]) -> Result<Vec<PathCommand>, String> {
             Ok(())
         }
         
+        fn add_exp(&mut self) -> Result<(), String> {
+            match &mut self.num_state {
+                Some(num_state) if num_state.exp.is_none() => {
+                    num_state.exp = Some((1, 0));
+                    Ok(())
+                }
+                _ => Err(format!("Unexpected exponent"))
+            }
+        }
+        
         fn add_dot(&mut self) -> Result<(), String> {
             if let Some(num_state) = &mut self.num_state {
                 if num_state.has_dot {
@@ -560,6 +589,11 @@ fn parse_svg_path(path: &[u8]) -> Result<Vec<PathCommand>, String> {
             if self.expect_nums != self.num_count {
                 return Err(format!("SVG Path command {:?} expected {} points, got {}", self.cmd, self.expect_nums, self.num_count));
             }
+            let last_ctrl = self.last_ctrl.take();
+            let reflect = | ctrl: Option<Point>, pt: Point | match ctrl {
+                Some(ctrl) => pt + (pt - ctrl),
+                None => pt
+            };
             match self.cmd {
                 Cmd::Unknown => (),
                 Cmd::Move(abs) => {
@@ -599,21 +633,23 @@ fn parse_svg_path(path: &[u8]) -> Result<Vec<PathCommand>, String> {
                     self.out.push(PathCommand::LineTo(self.last_pt));
                 },
                 Cmd::Cubic(abs) => {
-                    if abs {
-                        self.last_pt = Point {x: self.nums[4], y: self.nums[5]};
-                        self.out.push(PathCommand::CubicTo(
-                            Point {x: self.nums[0], y: self.nums[1]},
-                            Point {x: self.nums[2], y: self.nums[3]},
-                            self.last_pt,
-                        ));
-                    } else {
-                        self.out.push(PathCommand::CubicTo(
-                            self.last_pt + Vector {x: self.nums[0], y: self.nums[1]},
-                            self.last_pt + Vector {x: self.nums[2], y: self.nums[3]},
-                            self.last_pt + Vector {x: self.nums[4], y: self.nums[5]},
-                        ));
-                        self.last_pt += Vector {x: self.nums[4], y: self.nums[5]};
-                    }
+                    let base = if abs {Point::origin()} else {self.last_pt};
+                    let ctrl = base + Vector {x: self.nums[2], y: self.nums[3]};
+                    self.last_pt = base + Vector {x: self.nums[4], y: self.nums[5]};
+                    self.out.push(PathCommand::CubicTo(
+                        base + Vector {x: self.nums[0], y: self.nums[1]},
+                        ctrl,
+                        self.last_pt,
+                    ));
+                    self.last_ctrl = Some(ctrl);
+                },
+                Cmd::SmoothCubic(abs) => {
+                    let base = if abs {Point::origin()} else {self.last_pt};
+                    let ctrl1 = reflect(last_ctrl.filter( | _ | matches!(self.out.last(), Some(PathCommand::CubicTo(..)))), self.last_pt);
+                    let ctrl = base + Vector {x: self.nums[0], y: self.nums[1]};
+                    self.last_pt = base + Vector {x: self.nums[2], y: self.nums[3]};
+                    self.out.push(PathCommand::CubicTo(ctrl1, ctrl, self.last_pt));
+                    self.last_ctrl = Some(ctrl);
                 },
                 Cmd::Arc(abs) => {
                     if abs {
@@ -638,20 +674,18 @@ fn parse_svg_path(path: &[u8]) -> Result<Vec<PathCommand>, String> {
                     }
                 },
                 Cmd::Quadratic(abs) => {
-                    if abs {
-                        self.last_pt = Point {x: self.nums[2], y: self.nums[3]};
-                        self.out.push(PathCommand::QuadraticTo(
-                            Point {x: self.nums[0], y: self.nums[1]},
-                            self.last_pt
-                        ));
-                    }
-                    else {
-                        self.out.push(PathCommand::QuadraticTo(
-                            self.last_pt + Vector {x: self.nums[0], y: self.nums[1]},
-                            self.last_pt + Vector {x: self.nums[2], y: self.nums[3]},
-                        ));
-                        self.last_pt += Vector {x: self.nums[2], y: self.nums[3]};
-                    }
+                    let base = if abs {Point::origin()} else {self.last_pt};
+                    let ctrl = base + Vector {x: self.nums[0], y: self.nums[1]};
+                    self.last_pt = base + Vector {x: self.nums[2], y: self.nums[3]};
+                    self.out.push(PathCommand::QuadraticTo(ctrl, self.last_pt));
+                    self.last_ctrl = Some(ctrl);
+                }
+                Cmd::SmoothQuadratic(abs) => {
+                    let base = if abs {Point::origin()} else {self.last_pt};
+                    let ctrl = reflect(last_ctrl.filter( | _ | matches!(self.out.last(), Some(PathCommand::QuadraticTo(..)))), self.last_pt);
+                    self.last_pt = base + Vector {x: self.nums[0], y: self.nums[1]};
+                    self.out.push(PathCommand::QuadraticTo(ctrl, self.last_pt));
+                    self.last_ctrl = Some(ctrl);
                 }
                 Cmd::Close => {
                     self.out.push(PathCommand::Close);
@@ -672,6 +706,10 @@ fn parse_svg_path(path: &[u8]) -> Result<Vec<PathCommand>, String> {
             b'q' => state.next_cmd(Cmd::Quadratic(false)) ?,
             b'C' => state.next_cmd(Cmd::Cubic(true)) ?,
             b'c' => state.next_cmd(Cmd::Cubic(false)) ?,
+            b'S' => state.next_cmd(Cmd::SmoothCubic(true)) ?,
+            b's' => state.next_cmd(Cmd::SmoothCubic(false)) ?,
+            b'T' => state.next_cmd(Cmd::SmoothQuadratic(true)) ?,
+            b't' => state.next_cmd(Cmd::SmoothQuadratic(false)) ?,
             b'H' => state.next_cmd(Cmd::Hor(true)) ?,
             b'h' => state.next_cmd(Cmd::Hor(false)) ?,
             b'V' => state.next_cmd(Cmd::Vert(true)) ?,
@@ -684,6 +722,7 @@ fn parse_svg_path(path: &[u8]) -> Result<Vec<PathCommand>, String> {
             b'-' => state.add_min() ?,
             b'0'..=b'9' => state.add_digit((path[i] - b'0') as f64) ?,
             b'.' => state.add_dot() ?,
+            b'e' | b'E' => state.add_exp() ?,
             b',' | b' ' | b'\r' | b'\n' | b'\t' => state.whitespace() ?,
             x => {
                 return Err(format!("Unexpected character {} - {}", x, x as char))
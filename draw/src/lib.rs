@@ -12,6 +12,7 @@ pub mod font_atlas;
 pub mod geometry;
 pub mod nav;
 pub mod icon_atlas;
+pub mod mesh;
 mod owned_font_face;
  
 pub use crate::{
@@ -28,7 +29,9 @@ pub use crate::{
         DeferWalk
     },
     overlay::{
-        Overlay
+        Overlay,
+        OverlayLayer,
+        CxOverlayStack
     },
     nav::{
         NavRole,
@@ -51,12 +54,14 @@ pub use crate::{
         draw_icon::DrawIcon,
         draw_quad::DrawQuad,
         draw_line::DrawLine,
-        draw_text::DrawText,
+        draw_text::{DrawText, TextWrap},
         draw_color::DrawColor,
+        draw_mesh::DrawMesh,
     },
     geometry::{
         GeometryGen,
         GeometryQuad2D,
+        GeometryMesh3D,
     },
 };
 
@@ -69,4 +74,5 @@ pub fn live_design(cx: &mut Cx) {
     crate::geometry::geometry_gen::live_design(cx);
     crate::shader::std::live_design(cx);
     crate::shader::draw_trapezoid::live_design(cx);
+    crate::shader::draw_mesh::live_design(cx);
 }
\ No newline at end of file
@@ -0,0 +1,543 @@
+use {
+    crate::{
+        makepad_platform::*,
+        makepad_micro_serde::*,
+        geometry::GeometryGen,
+        mesh::*,
+    },
+    makepad_base64::base64_decode,
+};
+
+const GLB_MAGIC: &[u8] = b"glTF";
+const GLB_CHUNK_JSON: u32 = 0x4E4F534A;
+const GLB_CHUNK_BIN: u32 = 0x004E4942;
+
+const MODE_TRIANGLES: u64 = 4;
+const MODE_TRIANGLE_STRIP: u64 = 5;
+const MODE_TRIANGLE_FAN: u64 = 6;
+
+impl MeshScene {
+    /// Loads a glTF 2.0 model, either as `.gltf` JSON or as a binary `.glb`. External buffers
+    /// and images are loaded through `load_file`, `data:` URIs are decoded in place.
+    ///
+    /// Triangle meshes with positions, normals, the first uv set and metallic-roughness
+    /// materials are supported; skins, morph targets and animations are ignored.
+    pub fn from_gltf(data: &[u8], load_file: &mut dyn FnMut(&str) -> Option<Vec<u8>>) -> Result<Self, MeshError> {
+        let (json, bin) = if data.starts_with(GLB_MAGIC) {
+            split_glb(data)?
+        }
+        else {
+            (data, None)
+        };
+        let json = std::str::from_utf8(json).map_err( | e | MeshError::Parse(e.to_string()))?;
+        let root = JsonValue::deserialize_json(json).map_err( | e | MeshError::Parse(format!("{:?}", e)))?;
+        let version = root.get("asset").and_then( | a | a.get("version")).and_then( | v | v.as_str()).unwrap_or("");
+        if !version.starts_with('2') {
+            return Err(MeshError::Unsupported(format!("glTF version {}", version)))
+        }
+
+        let mut buffers = Vec::new();
+        for buffer in root.array("buffers") {
+            buffers.push(match buffer.get("uri").and_then( | v | v.as_str()) {
+                Some(uri) => load_uri(uri, load_file)?,
+                None => bin.map( | b | b.to_vec()).ok_or_else( || MeshError::MissingData("glb binary chunk".to_string()))?,
+            });
+        }
+        let gltf = Gltf {root: &root, buffers};
+
+        let mut scene = MeshScene::default();
+        for image in root.array("images") {
+            let name = image.get("name").and_then( | v | v.as_str()).unwrap_or("").to_string();
+            let data = if let Some(uri) = image.get("uri").and_then( | v | v.as_str()) {
+                load_uri(uri, load_file)?
+            }
+            else {
+                let view = image.get("bufferView").and_then( | v | v.as_usize())
+                    .ok_or_else( || MeshError::Parse("image without uri or bufferView".to_string()))?;
+                gltf.buffer_view(view)?.to_vec()
+            };
+            scene.images.push(MeshImage {name, data});
+        }
+        let textures = root.array("textures");
+        for material in root.array("materials") {
+            let mut m = MeshMaterial {
+                name: material.get("name").and_then( | v | v.as_str()).unwrap_or("").to_string(),
+                metallic: 1.0,
+                roughness: 1.0,
+                ..Default::default()
+            };
+            if let Some(pbr) = material.get("pbrMetallicRoughness") {
+                if let Some(c) = pbr.get("baseColorFactor").map( | v | v.floats()).filter( | c | c.len() == 4) {
+                    m.base_color = vec4(c[0], c[1], c[2], c[3]);
+                }
+                m.metallic = pbr.get("metallicFactor").and_then( | v | v.as_f64()).unwrap_or(1.0) as f32;
+                m.roughness = pbr.get("roughnessFactor").and_then( | v | v.as_f64()).unwrap_or(1.0) as f32;
+                m.base_color_texture = pbr.get("baseColorTexture")
+                    .and_then( | t | t.get("index")).and_then( | v | v.as_usize())
+                    .and_then( | t | textures.get(t)).and_then( | t | t.get("source")).and_then( | v | v.as_usize())
+                    .filter( | i | *i < scene.images.len());
+            }
+            if let Some(e) = material.get("emissiveFactor").map( | v | v.floats()).filter( | e | e.len() == 3) {
+                m.emissive = vec3(e[0], e[1], e[2]);
+            }
+            scene.materials.push(m);
+        }
+        for mesh in root.array("meshes") {
+            let mut out = Mesh {
+                name: mesh.get("name").and_then( | v | v.as_str()).unwrap_or("").to_string(),
+                primitives: Vec::new()
+            };
+            for primitive in mesh.array("primitives") {
+                if let Some(primitive) = gltf.primitive(primitive)? {
+                    out.primitives.push(primitive);
+                }
+            }
+            scene.meshes.push(out);
+        }
+
+        // walk the node hierarchy of the default scene, or every root node if there is none
+        let nodes = root.array("nodes");
+        let roots: Vec<usize> = match root.get("scene").and_then( | v | v.as_usize()).or(if root.array("scenes").is_empty() {None} else {Some(0)}) {
+            Some(s) => root.array("scenes").get(s).map( | s | s.array("nodes").iter().filter_map( | v | v.as_usize()).collect()).unwrap_or_default(),
+            None => {
+                let mut is_child = vec![false; nodes.len()];
+                for node in nodes {
+                    for child in node.array("children").iter().filter_map( | v | v.as_usize()) {
+                        if let Some(c) = is_child.get_mut(child) {
+                            *c = true;
+                        }
+                    }
+                }
+                (0..nodes.len()).filter( | i | !is_child[*i]).collect()
+            }
+        };
+        let mut stack: Vec<(usize, Mat4, usize)> = roots.into_iter().map( | n | (n, Mat4::identity(), 0)).collect();
+        while let Some((index, parent, depth)) = stack.pop() {
+            let Some(node) = nodes.get(index) else {continue};
+            // guard against cycles in malformed files
+            if depth > 64 {
+                continue;
+            }
+            let transform = Mat4::mul(&node_transform(node), &parent);
+            if let Some(mesh) = node.get("mesh").and_then( | v | v.as_usize()).filter( | m | *m < scene.meshes.len()) {
+                scene.instances.push(MeshInstance {mesh, transform});
+            }
+            for child in node.array("children").iter().filter_map( | v | v.as_usize()) {
+                stack.push((child, transform, depth + 1));
+            }
+        }
+        Ok(scene)
+    }
+}
+
+fn split_glb(data: &[u8]) -> Result<(&[u8], Option<&[u8]>), MeshError> {
+    let u32_at = | at: usize | -> Result<u32, MeshError> {
+        data.get(at..at + 4).map( | b | u32::from_le_bytes([b[0], b[1], b[2], b[3]])).ok_or_else( || MeshError::Parse("glb truncated".to_string()))
+    };
+    if u32_at(4)? != 2 {
+        return Err(MeshError::Unsupported(format!("glb version {}", u32_at(4)?)))
+    }
+    let length = (u32_at(8)? as usize).min(data.len());
+    let mut json = None;
+    let mut bin = None;
+    let mut pos = 12;
+    while pos + 8 <= length {
+        let chunk_len = u32_at(pos)? as usize;
+        let chunk_type = u32_at(pos + 4)?;
+        let chunk = data.get(pos + 8..pos + 8 + chunk_len).ok_or_else( || MeshError::Parse("glb chunk truncated".to_string()))?;
+        match chunk_type {
+            GLB_CHUNK_JSON => json = Some(chunk),
+            GLB_CHUNK_BIN => bin = Some(chunk),
+            _ => ()
+        }
+        pos += 8 + chunk_len;
+    }
+    Ok((json.ok_or_else( || MeshError::Parse("glb without json chunk".to_string()))?, bin))
+}
+
+fn load_uri(uri: &str, load_file: &mut dyn FnMut(&str) -> Option<Vec<u8>>) -> Result<Vec<u8>, MeshError> {
+    if let Some(data) = uri.strip_prefix("data:") {
+        let (header, payload) = data.split_once(',').ok_or_else( || MeshError::Parse("bad data uri".to_string()))?;
+        if !header.ends_with(";base64") {
+            return Err(MeshError::Unsupported("data uri that is not base64".to_string()))
+        }
+        return base64_decode(payload.as_bytes()).map_err( | e | MeshError::Parse(format!("data uri {:?}", e)))
+    }
+    let path = percent_decode(uri);
+    load_file(&path).ok_or(MeshError::MissingData(path))
+}
+
+fn percent_decode(uri: &str) -> String {
+    let bytes = uri.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            if let Ok(v) = u8::from_str_radix(&uri[i + 1..i + 3], 16) {
+                out.push(v);
+                i += 3;
+                continue;
+            }
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// The node's local transform, from `matrix` or translation/rotation/scale.
+fn node_transform(node: &JsonValue) -> Mat4 {
+    if let Some(m) = node.get("matrix").map( | v | v.floats()).filter( | m | m.len() == 16) {
+        let mut v = [0.0; 16];
+        v.copy_from_slice(&m);
+        return Mat4 {v}
+    }
+    let t = node.get("translation").map( | v | v.floats()).filter( | v | v.len() == 3).unwrap_or(vec![0.0, 0.0, 0.0]);
+    let r = node.get("rotation").map( | v | v.floats()).filter( | v | v.len() == 4).unwrap_or(vec![0.0, 0.0, 0.0, 1.0]);
+    let s = node.get("scale").map( | v | v.floats()).filter( | v | v.len() == 3).unwrap_or(vec![1.0, 1.0, 1.0]);
+    let (x, y, z, w) = (r[0], r[1], r[2], r[3]);
+    let (x2, y2, z2) = (x + x, y + y, z + z);
+    let (xx, xy, xz) = (x * x2, x * y2, x * z2);
+    let (yy, yz, zz) = (y * y2, y * z2, z * z2);
+    let (wx, wy, wz) = (w * x2, w * y2, w * z2);
+    Mat4 {v: [
+        (1.0 - (yy + zz)) * s[0],
+        (xy + wz) * s[0],
+        (xz - wy) * s[0],
+        0.0,
+        (xy - wz) * s[1],
+        (1.0 - (xx + zz)) * s[1],
+        (yz + wx) * s[1],
+        0.0,
+        (xz + wy) * s[2],
+        (yz - wx) * s[2],
+        (1.0 - (xx + yy)) * s[2],
+        0.0,
+        t[0],
+        t[1],
+        t[2],
+        1.0
+    ]}
+}
+
+struct Gltf<'a> {
+    root: &'a JsonValue,
+    buffers: Vec<Vec<u8>>,
+}
+
+impl<'a> Gltf<'a> {
+    fn buffer_view(&self, index: usize) -> Result<&[u8], MeshError> {
+        let view = self.root.array("bufferViews").get(index).ok_or_else( || MeshError::Parse(format!("bufferView {} missing", index)))?;
+        let buffer = view.get("buffer").and_then( | v | v.as_usize()).and_then( | b | self.buffers.get(b))
+            .ok_or_else( || MeshError::Parse(format!("bufferView {} has no buffer", index)))?;
+        let offset = view.get("byteOffset").and_then( | v | v.as_usize()).unwrap_or(0);
+        let length = view.get("byteLength").and_then( | v | v.as_usize()).unwrap_or(0);
+        buffer.get(offset..offset + length).ok_or_else( || MeshError::Parse(format!("bufferView {} out of range", index)))
+    }
+
+    /// Reads an accessor as floats, `components` per element, converting (and normalizing) integer data.
+    fn accessor(&self, index: usize) -> Result<(Vec<f32>, usize), MeshError> {
+        let err = | what: &str | MeshError::Parse(format!("accessor {}: {}", index, what));
+        let accessor = self.root.array("accessors").get(index).ok_or_else( || err("missing"))?;
+        if accessor.get("sparse").is_some() {
+            return Err(MeshError::Unsupported("sparse accessors".to_string()))
+        }
+        let count = accessor.get("count").and_then( | v | v.as_usize()).ok_or_else( || err("no count"))?;
+        let components = match accessor.get("type").and_then( | v | v.as_str()).unwrap_or("") {
+            "SCALAR" => 1,
+            "VEC2" => 2,
+            "VEC3" => 3,
+            "VEC4" => 4,
+            "MAT4" => 16,
+            other => return Err(MeshError::Unsupported(format!("accessor type {}", other)))
+        };
+        let component_type = accessor.get("componentType").and_then( | v | v.as_usize()).ok_or_else( || err("no componentType"))?;
+        let normalized = accessor.get("normalized").and_then( | v | v.as_bool()).unwrap_or(false);
+        let size = match component_type {
+            5120 | 5121 => 1,
+            5122 | 5123 => 2,
+            5125 | 5126 => 4,
+            other => return Err(MeshError::Unsupported(format!("component type {}", other)))
+        };
+        let Some(view_index) = accessor.get("bufferView").and_then( | v | v.as_usize()) else {
+            // no buffer view means all zeros
+            return Ok((vec![0.0; count * components], components))
+        };
+        let view = self.buffer_view(view_index)?;
+        let stride = self.root.array("bufferViews")[view_index].get("byteStride").and_then( | v | v.as_usize()).unwrap_or(size * components);
+        let offset = accessor.get("byteOffset").and_then( | v | v.as_usize()).unwrap_or(0);
+        if count > 0 && offset + (count - 1) * stride + size * components > view.len() {
+            return Err(err("out of range"))
+        }
+        let mut out = Vec::with_capacity(count * components);
+        for i in 0..count {
+            for c in 0..components {
+                let at = offset + i * stride + c * size;
+                let b = &view[at..at + size];
+                out.push(match (component_type, normalized) {
+                    (5120, false) => b[0] as i8 as f32,
+                    (5120, true) => (b[0] as i8 as f32 / 127.0).max(-1.0),
+                    (5121, false) => b[0] as f32,
+                    (5121, true) => b[0] as f32 / 255.0,
+                    (5122, false) => i16::from_le_bytes([b[0], b[1]]) as f32,
+                    (5122, true) => (i16::from_le_bytes([b[0], b[1]]) as f32 / 32767.0).max(-1.0),
+                    (5123, false) => u16::from_le_bytes([b[0], b[1]]) as f32,
+                    (5123, true) => u16::from_le_bytes([b[0], b[1]]) as f32 / 65535.0,
+                    (5125, _) => u32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f32,
+                    _ => f32::from_le_bytes([b[0], b[1], b[2], b[3]]),
+                });
+            }
+        }
+        Ok((out, components))
+    }
+
+    /// Reads an index accessor without going through f32, so large indices stay exact.
+    fn indices(&self, index: usize) -> Result<Vec<u32>, MeshError> {
+        let accessor = self.root.array("accessors").get(index).ok_or_else( || MeshError::Parse(format!("accessor {} missing", index)))?;
+        if accessor.get("componentType").and_then( | v | v.as_usize()) != Some(5125) {
+            let (values, _) = self.accessor(index)?;
+            return Ok(values.iter().map( | v | *v as u32).collect())
+        }
+        let count = accessor.get("count").and_then( | v | v.as_usize()).unwrap_or(0);
+        let view_index = accessor.get("bufferView").and_then( | v | v.as_usize()).ok_or_else( || MeshError::Parse("indices without bufferView".to_string()))?;
+        let view = self.buffer_view(view_index)?;
+        let offset = accessor.get("byteOffset").and_then( | v | v.as_usize()).unwrap_or(0);
+        let bytes = view.get(offset..offset + count * 4).ok_or_else( || MeshError::Parse("indices out of range".to_string()))?;
+        Ok(bytes.chunks_exact(4).map( | b | u32::from_le_bytes([b[0], b[1], b[2], b[3]])).collect())
+    }
+
+    fn primitive(&self, primitive: &JsonValue) -> Result<Option<MeshPrimitive>, MeshError> {
+        let mode = primitive.get("mode").and_then( | v | v.as_u64()).unwrap_or(MODE_TRIANGLES);
+        if mode != MODE_TRIANGLES && mode != MODE_TRIANGLE_STRIP && mode != MODE_TRIANGLE_FAN {
+            // points and lines have no surface to shade
+            return Ok(None)
+        }
+        let Some(attributes) = primitive.get("attributes") else {return Ok(None)};
+        let Some(position) = attributes.get("POSITION").and_then( | v | v.as_usize()) else {return Ok(None)};
+        let (positions, _) = self.accessor(position)?;
+        let positions: Vec<Vec3> = positions.chunks_exact(3).map( | p | vec3(p[0], p[1], p[2])).collect();
+        let vertex_count = positions.len();
+
+        let mut indices = match primitive.get("indices").and_then( | v | v.as_usize()) {
+            Some(accessor) => self.indices(accessor)?,
+            None => (0..vertex_count as u32).collect()
+        };
+        if indices.iter().any( | i | *i as usize >= vertex_count) {
+            return Err(MeshError::Parse("index out of range".to_string()))
+        }
+        indices = match mode {
+            MODE_TRIANGLE_STRIP => (2..indices.len()).flat_map( | i | {
+                // keep the winding consistent on every other triangle
+                if i % 2 == 0 {[indices[i - 2], indices[i - 1], indices[i]]} else {[indices[i - 1], indices[i - 2], indices[i]]}
+            }).collect(),
+            MODE_TRIANGLE_FAN => (2..indices.len()).flat_map( | i | [indices[0], indices[i - 1], indices[i]]).collect(),
+            _ => indices
+        };
+
+        let normals: Vec<Vec3> = match attributes.get("NORMAL").and_then( | v | v.as_usize()) {
+            Some(accessor) => self.accessor(accessor)?.0.chunks_exact(3).map( | n | vec3(n[0], n[1], n[2])).collect(),
+            None => compute_normals(&positions, &indices)
+        };
+        let uvs: Vec<Vec2> = match attributes.get("TEXCOORD_0").and_then( | v | v.as_usize()) {
+            Some(accessor) => self.accessor(accessor)?.0.chunks_exact(2).map( | t | vec2(t[0], t[1])).collect(),
+            None => Vec::new()
+        };
+
+        let mut geometry = GeometryGen::default();
+        geometry.vertices.reserve(vertex_count * MESH_VERTEX_SIZE);
+        for (i, pos) in positions.iter().enumerate() {
+            push_vertex(
+                &mut geometry,
+                *pos,
+                normals.get(i).copied().unwrap_or_default(),
+                uvs.get(i).copied().unwrap_or_default()
+            );
+        }
+        geometry.indices = indices;
+        Ok(Some(MeshPrimitive {
+            geometry,
+            material: primitive.get("material").and_then( | v | v.as_usize()),
+        }))
+    }
+}
+
+/// Lenient accessors for walking the glTF document.
+trait JsonExt {
+    fn array(&self, key: &str) -> &[JsonValue];
+    fn as_usize(&self) -> Option<usize>;
+    fn floats(&self) -> Vec<f32>;
+}
+
+impl JsonExt for JsonValue {
+    fn array(&self, key: &str) -> &[JsonValue] {
+        match self.get(key) {
+            Some(JsonValue::Array(items)) => items,
+            _ => &[]
+        }
+    }
+
+    fn as_usize(&self) -> Option<usize> {
+        self.as_u64().map( | v | v as usize)
+    }
+
+    fn floats(&self) -> Vec<f32> {
+        match self {
+            JsonValue::Array(items) => items.iter().filter_map( | v | v.as_f64()).map( | v | v as f32).collect(),
+            _ => Vec::new()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        crate::{
+            makepad_platform::*,
+            mesh::*,
+        },
+        makepad_base64::{base64_encode, BASE64_STANDARD},
+    };
+
+    fn no_files(_: &str) -> Option<Vec<u8>> {
+        None
+    }
+
+    // three float positions followed by three u16 indices, 42 bytes
+    fn triangle_buffer() -> Vec<u8> {
+        let mut buffer = Vec::new();
+        for v in [0.0f32, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0] {
+            buffer.extend_from_slice(&v.to_le_bytes());
+        }
+        for i in [0u16, 1, 2] {
+            buffer.extend_from_slice(&i.to_le_bytes());
+        }
+        buffer
+    }
+
+    // a triangle scaled by a child node and moved by its parent, `buffer` is the one buffers entry
+    fn triangle_gltf(buffer: &str) -> String {
+        format!(r#"{{
+            "asset": {{"version": "2.0"}},
+            "scene": 0,
+            "scenes": [{{"nodes": [0]}}],
+            "nodes": [
+                {{"children": [1], "translation": [10, 0, 0]}},
+                {{"mesh": 0, "scale": [2, 2, 2]}}
+            ],
+            "meshes": [{{"name": "tri", "primitives": [{{"attributes": {{"POSITION": 0}}, "indices": 1, "material": 0}}]}}],
+            "materials": [{{"name": "red", "pbrMetallicRoughness": {{"baseColorFactor": [1, 0, 0, 1], "metallicFactor": 0.5}}}}],
+            "buffers": [{}],
+            "bufferViews": [
+                {{"buffer": 0, "byteOffset": 0, "byteLength": 36}},
+                {{"buffer": 0, "byteOffset": 36, "byteLength": 6}}
+            ],
+            "accessors": [
+                {{"bufferView": 0, "componentType": 5126, "count": 3, "type": "VEC3"}},
+                {{"bufferView": 1, "componentType": 5123, "count": 3, "type": "SCALAR"}}
+            ]
+        }}"#, buffer)
+    }
+
+    fn glb(json: &str, bin: &[u8]) -> Vec<u8> {
+        let mut json = json.as_bytes().to_vec();
+        json.resize(json.len().next_multiple_of(4), b' ');
+        let mut bin = bin.to_vec();
+        bin.resize(bin.len().next_multiple_of(4), 0);
+        let mut out = b"glTF".to_vec();
+        out.extend_from_slice(&2u32.to_le_bytes());
+        out.extend_from_slice(&((12 + 8 + json.len() + 8 + bin.len()) as u32).to_le_bytes());
+        out.extend_from_slice(&(json.len() as u32).to_le_bytes());
+        out.extend_from_slice(b"JSON");
+        out.extend_from_slice(&json);
+        out.extend_from_slice(&(bin.len() as u32).to_le_bytes());
+        out.extend_from_slice(b"BIN\0");
+        out.extend_from_slice(&bin);
+        out
+    }
+
+    fn assert_triangle_scene(scene: &MeshScene) {
+        assert_eq!(scene.meshes.len(), 1);
+        assert_eq!(scene.meshes[0].name, "tri");
+        let primitive = &scene.meshes[0].primitives[0];
+        assert_eq!(primitive.material, Some(0));
+        assert_eq!(primitive.geometry.indices, vec![0, 1, 2]);
+        // pos, id, normal computed from the winding, no uvs
+        let vertices = &primitive.geometry.vertices;
+        assert_eq!(vertices.len(), 3 * MESH_VERTEX_SIZE);
+        assert_eq!(&vertices[MESH_VERTEX_SIZE..2 * MESH_VERTEX_SIZE], &[1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0]);
+
+        let material = &scene.materials[0];
+        assert_eq!(material.name, "red");
+        assert_eq!(material.base_color, vec4(1.0, 0.0, 0.0, 1.0));
+        assert_eq!(material.metallic, 0.5);
+        // glTF defaults, not the OBJ ones
+        assert_eq!(material.roughness, 1.0);
+
+        assert_eq!(scene.instances.len(), 1);
+        assert_eq!(scene.bounds(), Some((vec3(10.0, 0.0, 0.0), vec3(12.0, 2.0, 0.0))));
+    }
+
+    #[test]
+    fn embedded_buffer() {
+        let uri = String::from_utf8(base64_encode(&triangle_buffer(), &BASE64_STANDARD)).unwrap();
+        let json = triangle_gltf(&format!(r#"{{"byteLength": 42, "uri": "data:application/octet-stream;base64,{}"}}"#, uri));
+        let scene = MeshScene::from_gltf(json.as_bytes(), &mut no_files).unwrap();
+        assert_triangle_scene(&scene);
+        // leading whitespace doesn't hide the format
+        let scene = MeshScene::from_data(format!("\n  {}", json).as_bytes(), &mut no_files).unwrap();
+        assert_triangle_scene(&scene);
+    }
+
+    #[test]
+    fn external_buffer() {
+        let json = triangle_gltf(r#"{"byteLength": 42, "uri": "tri%20angle.bin"}"#);
+        let mut requested = Vec::new();
+        let scene = MeshScene::from_gltf(json.as_bytes(), &mut | path | {
+            requested.push(path.to_string());
+            Some(triangle_buffer())
+        }).unwrap();
+        assert_triangle_scene(&scene);
+        assert_eq!(requested, vec!["tri angle.bin".to_string()]);
+
+        assert!(matches!(MeshScene::from_gltf(json.as_bytes(), &mut no_files), Err(MeshError::MissingData(path)) if path == "tri angle.bin"));
+    }
+
+    #[test]
+    fn binary_glb() {
+        let data = glb(&triangle_gltf(r#"{"byteLength": 42}"#), &triangle_buffer());
+        let scene = MeshScene::from_gltf(&data, &mut no_files).unwrap();
+        assert_triangle_scene(&scene);
+        let scene = MeshScene::from_data(&data, &mut no_files).unwrap();
+        assert_triangle_scene(&scene);
+    }
+
+    #[test]
+    fn glb_errors() {
+        let data = glb(&triangle_gltf(r#"{"byteLength": 42}"#), &triangle_buffer());
+
+        let mut version_1 = data.clone();
+        version_1[4] = 1;
+        assert!(matches!(MeshScene::from_gltf(&version_1, &mut no_files), Err(MeshError::Unsupported(_))));
+        assert!(matches!(MeshScene::from_gltf(&data[..10], &mut no_files), Err(MeshError::Parse(_))));
+        // the length in the header still promises the whole binary chunk
+        assert!(matches!(MeshScene::from_gltf(&data[..data.len() - 8], &mut no_files), Err(MeshError::Parse(_))));
+
+        // a buffer without uri needs the binary chunk
+        let json = triangle_gltf(r#"{"byteLength": 42}"#);
+        assert!(matches!(MeshScene::from_gltf(json.as_bytes(), &mut no_files), Err(MeshError::MissingData(_))));
+    }
+
+    #[test]
+    fn gltf_errors() {
+        let json = r#"{"asset": {"version": "1.0"}}"#;
+        assert!(matches!(MeshScene::from_gltf(json.as_bytes(), &mut no_files), Err(MeshError::Unsupported(_))));
+        assert!(matches!(MeshScene::from_gltf(b"{\"asset\":", &mut no_files), Err(MeshError::Parse(_))));
+
+        // indices that point past the positions
+        let mut buffer = triangle_buffer();
+        buffer[40] = 7;
+        let uri = String::from_utf8(base64_encode(&buffer, &BASE64_STANDARD)).unwrap();
+        let json = triangle_gltf(&format!(r#"{{"byteLength": 42, "uri": "data:application/octet-stream;base64,{}"}}"#, uri));
+        assert!(matches!(MeshScene::from_gltf(json.as_bytes(), &mut no_files), Err(MeshError::Parse(_))));
+    }
+}
@@ -0,0 +1,151 @@
+pub mod obj;
+pub mod gltf;
+
+use {
+    std::fmt,
+    crate::{
+        makepad_platform::*,
+        geometry::GeometryGen,
+    }
+};
+
+/// The errors that can occur while loading a mesh file.
+#[derive(Debug)]
+pub enum MeshError {
+    /// The file is malformed; the message says where.
+    Parse(String),
+    /// The file uses a feature the loader does not implement.
+    Unsupported(String),
+    /// An external buffer, image or material file could not be loaded.
+    MissingData(String),
+}
+
+impl fmt::Display for MeshError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Parse(msg) => write!(f, "parse error: {msg}"),
+            Self::Unsupported(msg) => write!(f, "unsupported: {msg}"),
+            Self::MissingData(msg) => write!(f, "missing data: {msg}"),
+        }
+    }
+}
+
+/// A metallic-roughness material, as used by glTF and mapped onto by OBJ.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MeshMaterial {
+    pub name: String,
+    pub base_color: Vec4,
+    pub metallic: f32,
+    pub roughness: f32,
+    pub emissive: Vec3,
+    /// Index into `MeshScene::images`.
+    pub base_color_texture: Option<usize>,
+}
+
+impl Default for MeshMaterial {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            base_color: vec4(1.0, 1.0, 1.0, 1.0),
+            metallic: 0.0,
+            roughness: 0.5,
+            emissive: vec3(0.0, 0.0, 0.0),
+            base_color_texture: None,
+        }
+    }
+}
+
+/// An encoded (PNG, JPEG, ..) image referenced by a material. Decoding is left to the caller.
+#[derive(Clone, Debug)]
+pub struct MeshImage {
+    pub name: String,
+    pub data: Vec<u8>,
+}
+
+/// A triangle list in the `GeometryMesh3D` vertex layout, drawn with a single material.
+#[derive(Clone, Debug)]
+pub struct MeshPrimitive {
+    pub geometry: GeometryGen,
+    /// Index into `MeshScene::materials`, or the default material.
+    pub material: Option<usize>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Mesh {
+    pub name: String,
+    pub primitives: Vec<MeshPrimitive>,
+}
+
+/// A mesh placed in the scene. Meshes used by many instances can be drawn instanced.
+#[derive(Clone, Debug)]
+pub struct MeshInstance {
+    pub mesh: usize,
+    pub transform: Mat4,
+}
+
+/// A loaded model: meshes, their materials and images, and where the meshes are placed.
+#[derive(Clone, Debug, Default)]
+pub struct MeshScene {
+    pub meshes: Vec<Mesh>,
+    pub materials: Vec<MeshMaterial>,
+    pub images: Vec<MeshImage>,
+    pub instances: Vec<MeshInstance>,
+}
+
+impl MeshScene {
+    /// Loads an `.obj`, `.gltf` or `.glb` file by looking at its contents. `load_file`
+    /// is called for files it references (material libraries, buffers, images) by relative path.
+    pub fn from_data(data: &[u8], load_file: &mut dyn FnMut(&str) -> Option<Vec<u8>>) -> Result<Self, MeshError> {
+        let start = data.iter().position( | c | !c.is_ascii_whitespace()).unwrap_or(0);
+        if data.starts_with(b"glTF") || data[start..].starts_with(b"{") {
+            Self::from_gltf(data, load_file)
+        }
+        else {
+            let source = std::str::from_utf8(data).map_err( | e | MeshError::Parse(e.to_string()))?;
+            Self::from_obj(source, load_file)
+        }
+    }
+
+    /// The axis aligned bounding box of all instances, as `(min, max)`.
+    pub fn bounds(&self) -> Option<(Vec3, Vec3)> {
+        let mut min = Vec3::all(f32::INFINITY);
+        let mut max = Vec3::all(-f32::INFINITY);
+        for instance in &self.instances {
+            let Some(mesh) = self.meshes.get(instance.mesh) else {continue};
+            for primitive in &mesh.primitives {
+                for v in primitive.geometry.vertices.chunks_exact(MESH_VERTEX_SIZE) {
+                    let p = instance.transform.transform_vec4(vec4(v[0], v[1], v[2], 1.0));
+                    min = vec3(min.x.min(p.x), min.y.min(p.y), min.z.min(p.z));
+                    max = vec3(max.x.max(p.x), max.y.max(p.y), max.z.max(p.z));
+                }
+            }
+        }
+        if min.x > max.x {
+            return None
+        }
+        Some((min, max))
+    }
+}
+
+/// Floats per vertex: pos vec3, id float, normal vec3, uv vec2.
+pub const MESH_VERTEX_SIZE: usize = 9;
+
+fn push_vertex(geometry: &mut GeometryGen, pos: Vec3, normal: Vec3, uv: Vec2) {
+    geometry.vertices.extend_from_slice(&[pos.x, pos.y, pos.z, 0.0, normal.x, normal.y, normal.z, uv.x, uv.y]);
+}
+
+/// Smooth vertex normals from triangle `indices`, weighted by triangle area.
+fn compute_normals(positions: &[Vec3], indices: &[u32]) -> Vec<Vec3> {
+    let mut normals = vec![Vec3::default(); positions.len()];
+    for tri in indices.chunks_exact(3) {
+        let (a, b, c) = (tri[0] as usize, tri[1] as usize, tri[2] as usize);
+        if a >= positions.len() || b >= positions.len() || c >= positions.len() {
+            continue;
+        }
+        let n = Vec3::cross(positions[b] - positions[a], positions[c] - positions[a]);
+        normals[a] += n;
+        normals[b] += n;
+        normals[c] += n;
+    }
+    normals.iter().map( | n | n.normalize()).collect()
+}
@@ -0,0 +1,344 @@
+use {
+    std::collections::HashMap,
+    crate::{
+        makepad_platform::*,
+        geometry::GeometryGen,
+        mesh::*,
+    }
+};
+
+impl MeshScene {
+    /// Loads a Wavefront OBJ. Faces are triangulated as fans, and each material used
+    /// becomes one primitive of a single mesh. Materials come from `mtllib` files
+    /// loaded through `load_file`; a missing material library is not an error.
+    pub fn from_obj(source: &str, load_file: &mut dyn FnMut(&str) -> Option<Vec<u8>>) -> Result<Self, MeshError> {
+        let mut scene = MeshScene::default();
+        let mut positions = Vec::new();
+        let mut normals = Vec::new();
+        let mut uvs = Vec::new();
+        let mut material_map: HashMap<String, usize> = HashMap::new();
+        // faces per material, as (position, uv, normal) index triples
+        let mut groups: Vec<(Option<usize>, Vec<[(usize, Option<usize>, Option<usize>); 3]>)> = vec![(None, Vec::new())];
+
+        for (line_no, line) in source.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or("").trim();
+            let mut parts = line.split_whitespace();
+            let Some(keyword) = parts.next() else {continue};
+            let err = | what: &str | MeshError::Parse(format!("obj line {}: {}", line_no + 1, what));
+            match keyword {
+                "v" => positions.push(parse_vec3(&mut parts).ok_or_else( || err("bad vertex"))?),
+                "vn" => normals.push(parse_vec3(&mut parts).ok_or_else( || err("bad normal"))?),
+                "vt" => {
+                    let u = parts.next().and_then( | v | v.parse::<f32>().ok()).ok_or_else( || err("bad uv"))?;
+                    let v = parts.next().and_then( | v | v.parse::<f32>().ok()).unwrap_or(0.0);
+                    // OBJ has v going up, textures are addressed top down
+                    uvs.push(vec2(u, 1.0 - v));
+                }
+                "f" => {
+                    let mut face = Vec::new();
+                    for corner in parts {
+                        let mut idx = corner.split('/');
+                        let resolve = | v: Option<&str>, len: usize | -> Result<Option<usize>, MeshError> {
+                            match v {
+                                None | Some("") => Ok(None),
+                                Some(v) => {
+                                    let i: i64 = v.parse().map_err( | _ | err("bad face index"))?;
+                                    let i = if i < 0 {len as i64 + i} else {i - 1};
+                                    if i < 0 || i as usize >= len {
+                                        return Err(err("face index out of range"))
+                                    }
+                                    Ok(Some(i as usize))
+                                }
+                            }
+                        };
+                        let p = resolve(idx.next(), positions.len())?.ok_or_else( || err("face without position"))?;
+                        let t = resolve(idx.next(), uvs.len())?;
+                        let n = resolve(idx.next(), normals.len())?;
+                        face.push((p, t, n));
+                    }
+                    let faces = &mut groups.last_mut().unwrap().1;
+                    for i in 2..face.len() {
+                        faces.push([face[0], face[i - 1], face[i]]);
+                    }
+                }
+                "mtllib" => {
+                    let file = line[keyword.len()..].trim();
+                    if let Some(data) = load_file(file) {
+                        parse_mtl(&String::from_utf8_lossy(&data), &mut scene, &mut material_map, load_file);
+                    }
+                    else {
+                        log!("obj: cannot load material library {}", file);
+                    }
+                }
+                "usemtl" => {
+                    let name = line[keyword.len()..].trim();
+                    let material = match material_map.get(name) {
+                        Some(index) => *index,
+                        None => {
+                            // keep unknown materials apart, so they can still be told apart
+                            scene.materials.push(MeshMaterial {name: name.to_string(), ..Default::default()});
+                            material_map.insert(name.to_string(), scene.materials.len() - 1);
+                            scene.materials.len() - 1
+                        }
+                    };
+                    match groups.iter().position( | (m, _) | *m == Some(material)) {
+                        Some(index) => {
+                            let group = groups.remove(index);
+                            groups.push(group);
+                        }
+                        None => groups.push((Some(material), Vec::new()))
+                    }
+                }
+                _ => ()
+            }
+        }
+
+        let mut mesh = Mesh::default();
+        for (material, faces) in groups {
+            if faces.is_empty() {
+                continue;
+            }
+            mesh.primitives.push(build_primitive(&positions, &normals, &uvs, &faces, material));
+        }
+        if !mesh.primitives.is_empty() {
+            scene.meshes.push(mesh);
+            scene.instances.push(MeshInstance {mesh: 0, transform: Mat4::identity()});
+        }
+        Ok(scene)
+    }
+}
+
+fn parse_vec3<'a>(parts: &mut impl Iterator<Item = &'a str>) -> Option<Vec3> {
+    let mut next = || parts.next().and_then( | v | v.parse::<f32>().ok());
+    Some(vec3(next()?, next()?, next()?))
+}
+
+fn build_primitive(
+    positions: &[Vec3],
+    normals: &[Vec3],
+    uvs: &[Vec2],
+    faces: &[[(usize, Option<usize>, Option<usize>); 3]],
+    material: Option<usize>
+) -> MeshPrimitive {
+    let mut geometry = GeometryGen::default();
+    let mut vertex_map = HashMap::new();
+    let mut vertex_positions = Vec::new();
+    let mut missing_normals = false;
+    for corner in faces.iter().flatten() {
+        let next_index = vertex_positions.len() as u32;
+        let index = *vertex_map.entry(*corner).or_insert_with( || {
+            let (p, t, n) = *corner;
+            missing_normals |= n.is_none();
+            let normal = n.map( | n | normals[n]).unwrap_or_default();
+            let uv = t.map( | t | uvs[t]).unwrap_or_default();
+            push_vertex(&mut geometry, positions[p], normal, uv);
+            vertex_positions.push(positions[p]);
+            next_index
+        });
+        geometry.indices.push(index);
+    }
+    if missing_normals {
+        let computed = compute_normals(&vertex_positions, &geometry.indices);
+        for (v, n) in geometry.vertices.chunks_exact_mut(MESH_VERTEX_SIZE).zip(computed) {
+            if v[4] == 0.0 && v[5] == 0.0 && v[6] == 0.0 {
+                v[4..7].copy_from_slice(&[n.x, n.y, n.z]);
+            }
+        }
+    }
+    MeshPrimitive {geometry, material}
+}
+
+fn parse_mtl(
+    source: &str,
+    scene: &mut MeshScene,
+    material_map: &mut HashMap<String, usize>,
+    load_file: &mut dyn FnMut(&str) -> Option<Vec<u8>>
+) {
+    let mut current: Option<usize> = None;
+    for line in source.lines() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        let mut parts = line.split_whitespace();
+        let Some(keyword) = parts.next() else {continue};
+        let rest = line[keyword.len()..].trim();
+        if keyword == "newmtl" {
+            scene.materials.push(MeshMaterial {name: rest.to_string(), ..Default::default()});
+            material_map.insert(rest.to_string(), scene.materials.len() - 1);
+            current = Some(scene.materials.len() - 1);
+            continue;
+        }
+        let Some(index) = current else {continue};
+        match keyword {
+            "Kd" => if let Some(c) = parse_vec3(&mut parts) {
+                let m = &mut scene.materials[index];
+                m.base_color = vec4(c.x, c.y, c.z, m.base_color.w);
+            }
+            "Ke" => if let Some(c) = parse_vec3(&mut parts) {
+                scene.materials[index].emissive = c;
+            }
+            "d" => if let Ok(d) = rest.parse::<f32>() {
+                scene.materials[index].base_color.w = d;
+            }
+            "Tr" => if let Ok(tr) = rest.parse::<f32>() {
+                scene.materials[index].base_color.w = 1.0 - tr;
+            }
+            // map the phong exponent onto roughness the usual way
+            "Ns" => if let Ok(ns) = rest.parse::<f32>() {
+                scene.materials[index].roughness = (2.0 / (ns + 2.0)).sqrt().clamp(0.0, 1.0);
+            }
+            "Pr" => if let Ok(v) = rest.parse::<f32>() {
+                scene.materials[index].roughness = v;
+            }
+            "Pm" => if let Ok(v) = rest.parse::<f32>() {
+                scene.materials[index].metallic = v;
+            }
+            "map_Kd" => {
+                // options like -s 1 1 1 come before the file name
+                let file = rest.split_whitespace().last().unwrap_or("");
+                if let Some(data) = load_file(file) {
+                    scene.images.push(MeshImage {name: file.to_string(), data});
+                    scene.materials[index].base_color_texture = Some(scene.images.len() - 1);
+                }
+                else {
+                    log!("obj: cannot load texture {}", file);
+                }
+            }
+            _ => ()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        std::collections::HashMap,
+        crate::{
+            makepad_platform::*,
+            mesh::*,
+        },
+    };
+
+    fn no_files(_: &str) -> Option<Vec<u8>> {
+        None
+    }
+
+    fn vertex(primitive: &MeshPrimitive, index: usize) -> &[f32] {
+        &primitive.geometry.vertices[index * MESH_VERTEX_SIZE..(index + 1) * MESH_VERTEX_SIZE]
+    }
+
+    #[test]
+    fn quad_with_uvs_and_normals() {
+        let source = "
+            # a unit quad facing +z
+            v 0 0 0
+            v 1 0 0
+            v 1 1 0
+            v 0 1 0
+            vt 0 0
+            vt 1 0
+            vt 1 1
+            vt 0 1
+            vn 0 0 1
+            f 1/1/1 2/2/1 3/3/1 4/4/1
+        ";
+        let scene = MeshScene::from_obj(source, &mut no_files).unwrap();
+        assert_eq!(scene.meshes.len(), 1);
+        assert_eq!(scene.instances.len(), 1);
+        assert_eq!(scene.instances[0].transform, Mat4::identity());
+        let primitive = &scene.meshes[0].primitives[0];
+        assert_eq!(primitive.material, None);
+        // the quad is a fan of two triangles sharing the four corners
+        assert_eq!(primitive.geometry.indices, vec![0, 1, 2, 0, 2, 3]);
+        assert_eq!(primitive.geometry.vertices.len(), 4 * MESH_VERTEX_SIZE);
+        // pos, id, normal, uv with v flipped
+        assert_eq!(vertex(primitive, 1), &[1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 1.0, 1.0, 1.0]);
+        assert_eq!(vertex(primitive, 3), &[0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0]);
+        assert_eq!(scene.bounds(), Some((vec3(0.0, 0.0, 0.0), vec3(1.0, 1.0, 0.0))));
+    }
+
+    #[test]
+    fn negative_indices_and_computed_normals() {
+        let source = "v 0 0 0\nv 0 0 1\nv 1 0 0\nf -3 -2 -1\n";
+        let scene = MeshScene::from_obj(source, &mut no_files).unwrap();
+        let primitive = &scene.meshes[0].primitives[0];
+        assert_eq!(primitive.geometry.indices, vec![0, 1, 2]);
+        // counter clockwise seen from above, so the normal points up
+        for i in 0..3 {
+            assert_eq!(&vertex(primitive, i)[4..7], &[0.0, 1.0, 0.0]);
+        }
+    }
+
+    #[test]
+    fn materials_split_primitives() {
+        let mut files = HashMap::new();
+        files.insert("scene.mtl", b"
+            newmtl red
+            Kd 1 0 0
+            d 0.5
+            Ns 0
+            Pm 0.25
+            newmtl textured
+            map_Kd -s 1 1 1 wood.png
+        ".to_vec());
+        files.insert("wood.png", vec![1, 2, 3]);
+        let source = "
+            mtllib scene.mtl
+            v 0 0 0
+            v 1 0 0
+            v 0 1 0
+            usemtl red
+            f 1 2 3
+            usemtl textured
+            f 1 3 2
+            usemtl red
+            f 2 3 1
+            usemtl missing
+            f 3 2 1
+        ";
+        let scene = MeshScene::from_obj(source, &mut | path | files.get(path).cloned()).unwrap();
+        assert_eq!(scene.materials.len(), 3);
+        let red = &scene.materials[0];
+        assert_eq!(red.name, "red");
+        assert_eq!(red.base_color, vec4(1.0, 0.0, 0.0, 0.5));
+        assert_eq!(red.roughness, 1.0);
+        assert_eq!(red.metallic, 0.25);
+        assert_eq!(scene.materials[1].base_color_texture, Some(0));
+        assert_eq!(scene.images[0].name, "wood.png");
+        assert_eq!(scene.images[0].data, vec![1, 2, 3]);
+        // a material that isn't in the library still gets its own entry
+        assert_eq!(scene.materials[2].name, "missing");
+        assert_eq!(scene.materials[2].base_color, vec4(1.0, 1.0, 1.0, 1.0));
+
+        // one primitive per material, faces of a material gathered together
+        let primitives = &scene.meshes[0].primitives;
+        let materials: Vec<_> = primitives.iter().map( | p | p.material).collect();
+        assert_eq!(materials, vec![Some(1), Some(0), Some(2)]);
+        assert_eq!(primitives[1].geometry.indices.len(), 6);
+    }
+
+    #[test]
+    fn missing_material_library_is_not_an_error() {
+        let scene = MeshScene::from_obj("mtllib gone.mtl\nv 0 0 0\nv 1 0 0\nv 0 1 0\nusemtl a\nf 1 2 3\n", &mut no_files).unwrap();
+        assert_eq!(scene.meshes[0].primitives.len(), 1);
+        assert_eq!(scene.materials[0].name, "a");
+    }
+
+    #[test]
+    fn errors() {
+        let parse_error = | source: &str | matches!(MeshScene::from_obj(source, &mut no_files), Err(MeshError::Parse(_)));
+        assert!(parse_error("v 0 0\n"));
+        assert!(parse_error("v 0 0 0\nf 1 2 3\n"));
+        assert!(parse_error("v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1/4 2 3\n"));
+        assert!(parse_error("v 0 0 0\nv 1 0 0\nv 0 1 0\nf 0 1 2\n"));
+        assert!(parse_error("v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 x 3\n"));
+        // nothing but vertices is an empty scene
+        let scene = MeshScene::from_obj("v 0 0 0\n", &mut no_files).unwrap();
+        assert!(scene.meshes.is_empty() && scene.instances.is_empty());
+        assert_eq!(scene.bounds(), None);
+    }
+
+    #[test]
+    fn from_data_detects_obj() {
+        let scene = MeshScene::from_data(b"v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 3\n", &mut no_files).unwrap();
+        assert_eq!(scene.meshes[0].primitives[0].geometry.indices, vec![0, 1, 2]);
+    }
+}
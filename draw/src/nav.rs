@@ -4,10 +4,7 @@ use {
     std::cell::RefCell,
     crate::{
         cx_2d::Cx2d,
-        makepad_platform::Area,
-        makepad_platform::DrawListId,
-        makepad_platform::Margin,
-        makepad_platform::Cx,
+        makepad_platform::*,
     }
 };
 
@@ -37,12 +34,26 @@ impl std::ops::IndexMut<DrawListId> for CxNavTree {
     }
 }
 
-#[derive(Debug, Clone)]
+/// Where a stop sits in the tab order. Top stops come first and Bottom stops last,
+/// each sorted by their index. Default stops keep their draw order and sort as Middle(0)
+#[derive(Copy, Clone, Debug, Live, LiveHook)]
+#[live_ignore]
 pub enum NavOrder {
-    Default,
-    Top(u64),
-    Middle(u64),
-    Bottom(u64),
+    #[pick] Default,
+    #[live(0)] Top(u64),
+    #[live(0)] Middle(u64),
+    #[live(0)] Bottom(u64),
+}
+
+impl NavOrder {
+    fn sort_key(&self) -> (usize, u64) {
+        match self {
+            Self::Top(index) => (0, *index),
+            Self::Default => (1, 0),
+            Self::Middle(index) => (1, *index),
+            Self::Bottom(index) => (2, *index),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -61,11 +72,14 @@ pub enum NavItem {
     EndScroll(Area)
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum NavRole {
     TextInput,
     DropDown,
     Slider,
+    Button,
+    CheckBox,
+    RadioButton,
 }
 
 impl<'a> Cx2d<'a> {
@@ -114,6 +128,41 @@ impl<'a> Cx2d<'a> {
         }
     }
     
+    /// All the stops below `root` in tab order, together with the scroll areas each one sits in.
+    /// Stops that weren't drawn since their draw list last redrew are left out
+    pub fn collect_nav_stops(cx: &mut Cx, root: DrawListId) -> Vec<(NavStop, Vec<Area>)> {
+        let nav_tree_rc = cx.get_global::<CxNavTreeRc>().clone();
+        let nav_tree = &*nav_tree_rc.0.borrow();
+        fn collect_nav_stops(cx: &Cx, scroll_stack: &mut Vec<Area>, nav_tree: &CxNavTree, draw_list_id: DrawListId, out: &mut Vec<(NavStop, Vec<Area>)>) {
+            if draw_list_id.index() >= nav_tree.nav_lists.len() {
+                return
+            }
+            for nav_item in &nav_tree[draw_list_id].nav_list {
+                match nav_item {
+                    NavItem::Child(draw_list_id) => {
+                        collect_nav_stops(cx, scroll_stack, nav_tree, *draw_list_id, out);
+                    }
+                    NavItem::Stop(stop) => if stop.area.is_valid(cx) {
+                        let mut stack = scroll_stack.clone();
+                        stack.push(stop.area);
+                        out.push((stop.clone(), stack));
+                    }
+                    NavItem::BeginScroll(area) => {
+                        scroll_stack.push(*area);
+                    }
+                    NavItem::EndScroll(_) => {
+                        scroll_stack.pop();
+                    }
+                }
+            }
+        }
+        let mut stops = Vec::new();
+        collect_nav_stops(cx, &mut Vec::new(), nav_tree, root, &mut stops);
+        // a stable sort, so stops with the same order stay in draw order
+        stops.sort_by_key( | (stop, _) | stop.order.sort_key());
+        stops
+    }
+    
     pub fn nav_list_clear(&mut self, draw_list_id: DrawListId) {
         let mut nav_tree = self.nav_tree_rc.0.borrow_mut();
         if draw_list_id.index() >= nav_tree.nav_lists.len() {
@@ -128,11 +177,15 @@ impl<'a> Cx2d<'a> {
     }
     
     pub fn add_nav_stop(&mut self, area: Area, role: NavRole, margin: Margin) {
+        self.add_nav_stop_ordered(area, role, margin, NavOrder::Default)
+    }
+    
+    pub fn add_nav_stop_ordered(&mut self, area: Area, role: NavRole, margin: Margin, order: NavOrder) {
         let draw_list_id = *self.draw_list_stack.last().unwrap();
         self.nav_list_item_push(draw_list_id, NavItem::Stop(NavStop {
             role,
             area,
-            order: NavOrder::Default,
+            order,
             margin
         }));
     }
@@ -9,6 +9,59 @@ use {
     }
 };
 
+/// The layers of the overlay stack. Higher layers draw over lower ones and inside
+/// a layer the overlay that opened last is on top, so a dropdown inside a modal
+/// pops up over it and a second modal covers the first
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Live, LiveHook)]
+#[live_ignore]
+pub enum OverlayLayer {
+    Modal,
+    #[pick] Popup,
+    Toast,
+    Tooltip,
+}
+
+#[derive(Clone, Copy)]
+struct OverlayStackEntry {
+    overlay_id: DrawListId,
+    draw_list_id: DrawListId,
+    layer: OverlayLayer,
+}
+
+/// Every layered overlay that is open, in the order they opened. Overlays join
+/// when they first draw with `DrawList2d::begin_overlay_layer` and leave once they stop drawing
+#[derive(Default)]
+pub struct CxOverlayStack {
+    stack: Vec<OverlayStackEntry>
+}
+
+impl CxOverlayStack {
+    pub (crate) fn push(&mut self, overlay_id: DrawListId, draw_list_id: DrawListId, layer: OverlayLayer) {
+        if !self.contains(draw_list_id) {
+            self.stack.push(OverlayStackEntry {overlay_id, draw_list_id, layer});
+        }
+    }
+    
+    pub fn contains(&self, draw_list_id: DrawListId) -> bool {
+        self.stack.iter().any( | entry | entry.draw_list_id == draw_list_id)
+    }
+    
+    /// The overlay on top of `layer` in the overlay of one window
+    pub fn top(&self, overlay_id: DrawListId, layer: OverlayLayer) -> Option<DrawListId> {
+        self.stack.iter().rev()
+            .find( | entry | entry.overlay_id == overlay_id && entry.layer == layer)
+            .map( | entry | entry.draw_list_id)
+    }
+    
+    /// Whether no other overlay of the same layer opened after this one
+    pub fn is_top(&self, draw_list_id: DrawListId) -> bool {
+        if let Some(entry) = self.stack.iter().find( | entry | entry.draw_list_id == draw_list_id) {
+            return self.top(entry.overlay_id, entry.layer) == Some(draw_list_id)
+        }
+        false
+    }
+}
+
 #[derive(Debug)]
 pub struct Overlay { // draw info per UI element
     pub (crate) draw_list: DrawList,
@@ -45,6 +98,10 @@ impl LiveApply for Overlay {
 }
 
 impl Overlay {
+    pub fn draw_list_id(&self) -> DrawListId {
+        self.draw_list.id()
+    }
+    
     pub fn handle_event(&self, _cx:&Cx, _event:&Event){
         /*let area = self.sweep_lock.borrow().clone();
         if !area.is_empty(){
@@ -80,6 +137,35 @@ impl Overlay {
                 }
             }
         }
+        
+        // overlays that didn't draw are closed, the rest go in stacking order
+        let overlay_id = self.draw_list.id();
+        let mut stack = std::mem::take(&mut cx.cx.global::<CxOverlayStack>().stack);
+        let draw_list = &mut cx.cx.draw_lists[overlay_id];
+        let mut slots = Vec::new();
+        for i in 0..draw_list.draw_items.len(){
+            if let Some(sub_id) = draw_list.draw_items[i].sub_list(){
+                if let Some(pos) = stack.iter().position(|entry| entry.draw_list_id == sub_id){
+                    slots.push((i, stack[pos].layer, pos, sub_id, draw_list.draw_items[i].redraw_id));
+                }
+            }
+        }
+        stack.retain(|entry| entry.overlay_id != overlay_id || slots.iter().any(|slot| slot.3 == entry.draw_list_id));
+        let mut sorted = slots.clone();
+        sorted.sort_by_key(|slot| (slot.1, slot.2));
+        for (slot, sorted) in slots.iter().zip(sorted.iter()){
+            let item = &mut draw_list.draw_items[slot.0];
+            item.kind = CxDrawKind::SubList(sorted.3);
+            item.redraw_id = sorted.4;
+        }
+        
+        // the top modal takes all the finger input of the window
+        let top_modal = stack.iter().rev()
+            .find(|entry| entry.overlay_id == overlay_id && entry.layer == OverlayLayer::Modal)
+            .map(|entry| entry.draw_list_id);
+        cx.cx.global::<CxOverlayStack>().stack = stack;
+        let pass_id = cx.pass_stack.last().unwrap().pass_id;
+        cx.cx.set_input_lock(pass_id, top_modal);
     }
 }
 
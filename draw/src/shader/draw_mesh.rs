@@ -0,0 +1,198 @@
+use {
+    crate::{
+        makepad_platform::*,
+        geometry::GeometryMesh3D,
+        cx_2d::Cx2d,
+    },
+};
+
+live_design!{
+    import makepad_draw::shader::std::*;
+
+    DrawMesh = {{DrawMesh}} {
+        texture base_color_texture: texture2d
+
+        uniform camera_pos: vec3(0.0, 0.0, 1.0)
+        uniform light_dir: vec3(-0.4, 0.8, 0.6)
+        uniform light_color: vec3(1.0, 1.0, 1.0)
+        uniform ambient_sky: vec3(0.35, 0.38, 0.45)
+        uniform ambient_ground: vec3(0.15, 0.13, 0.12)
+
+        varying world_pos: vec3
+        varying world_normal: vec3
+        varying uv: vec2
+
+        fn transform_point(self, p: vec3) -> vec4 {
+            return self.transform_x * p.x + self.transform_y * p.y + self.transform_z * p.z + self.transform_w
+        }
+
+        fn vertex(self) -> vec4 {
+            let world = self.transform_point(self.geom_pos);
+            self.world_pos = world.xyz;
+            self.world_normal = (self.transform_x * self.geom_normal.x + self.transform_y * self.geom_normal.y + self.transform_z * self.geom_normal.z).xyz;
+            self.uv = self.geom_uv;
+            return self.camera_projection * (self.camera_view * world)
+        }
+
+        fn get_base_color(self) -> vec4 {
+            if self.has_texture > 0.5 {
+                return self.base_color * sample2d(self.base_color_texture, self.uv)
+            }
+            return self.base_color
+        }
+
+        // Cook-Torrance with a GGX distribution, Schlick fresnel and a hemisphere ambient
+        fn shade(self, albedo: vec3) -> vec3 {
+            let n = normalize(self.world_normal);
+            let v = normalize(self.camera_pos - self.world_pos);
+            if dot(n, v) < 0.0 {
+                n = -n;
+            }
+            let l = normalize(self.light_dir);
+            let h = normalize(l + v);
+            let n_dot_l = max(dot(n, l), 0.0);
+            let n_dot_v = max(dot(n, v), 0.001);
+            let n_dot_h = max(dot(n, h), 0.0);
+            let v_dot_h = max(dot(v, h), 0.0);
+
+            let rough = clamp(self.roughness, 0.04, 1.0);
+            let a = rough * rough;
+            let a2 = a * a;
+            let d = n_dot_h * n_dot_h * (a2 - 1.0) + 1.0;
+            let distribution = a2 / (3.14159 * d * d);
+            let k = (rough + 1.0) * (rough + 1.0) / 8.0;
+            let geometry = (n_dot_l / (n_dot_l * (1.0 - k) + k)) * (n_dot_v / (n_dot_v * (1.0 - k) + k));
+            let f0 = mix(vec3(0.04, 0.04, 0.04), albedo, self.metallic);
+            let fresnel = f0 + (vec3(1.0, 1.0, 1.0) - f0) * pow(1.0 - v_dot_h, 5.0);
+            let specular = fresnel * (distribution * geometry / (4.0 * n_dot_v * max(n_dot_l, 0.001)));
+            let diffuse = (vec3(1.0, 1.0, 1.0) - fresnel) * (1.0 - self.metallic) * albedo / 3.14159;
+
+            let ambient = mix(self.ambient_ground, self.ambient_sky, n.y * 0.5 + 0.5) * albedo;
+            return ambient + (diffuse + specular) * self.light_color * n_dot_l * 3.14159 + self.emissive
+        }
+
+        fn pixel(self) -> vec4 {
+            let color = self.get_base_color();
+            return Pal::premul(vec4(self.shade(color.xyz), color.w))
+        }
+    }
+}
+
+/// Draws triangle meshes in the `GeometryMesh3D` layout with a metallic-roughness
+/// material. Meant for a pass in `PassMatrixMode::Projection`; instances that share
+/// a geometry and texture are batched into one instanced draw call.
+#[derive(Live, LiveRegister)]
+#[repr(C)]
+pub struct DrawMesh {
+    #[live] pub geometry: GeometryMesh3D,
+    #[deref] pub draw_vars: DrawVars,
+    #[calc] pub transform_x: Vec4,
+    #[calc] pub transform_y: Vec4,
+    #[calc] pub transform_z: Vec4,
+    #[calc] pub transform_w: Vec4,
+    #[live(vec4(1.0, 1.0, 1.0, 1.0))] pub base_color: Vec4,
+    #[live(0.0)] pub metallic: f32,
+    #[live(0.5)] pub roughness: f32,
+    #[live] pub emissive: Vec3,
+    #[calc] pub has_texture: f32,
+}
+
+impl LiveHook for DrawMesh {
+    fn before_apply(&mut self, cx: &mut Cx, apply: &mut Apply, index: usize, nodes: &[LiveNode]) {
+        self.draw_vars.before_apply_init_shader(cx, apply, index, nodes, &self.geometry);
+    }
+    fn after_apply(&mut self, cx: &mut Cx, apply: &mut Apply, index: usize, nodes: &[LiveNode]) {
+        self.draw_vars.after_apply_update_self(cx, apply, index, nodes, &self.geometry);
+    }
+}
+
+impl DrawMesh {
+    pub fn set_transform(&mut self, transform: &Mat4) {
+        let v = &transform.v;
+        self.transform_x = vec4(v[0], v[1], v[2], v[3]);
+        self.transform_y = vec4(v[4], v[5], v[6], v[7]);
+        self.transform_z = vec4(v[8], v[9], v[10], v[11]);
+        self.transform_w = vec4(v[12], v[13], v[14], v[15]);
+    }
+
+    pub fn set_texture(&mut self, texture: Option<&Texture>) {
+        match texture {
+            Some(texture) => {
+                self.draw_vars.set_texture(0, texture);
+                self.has_texture = 1.0;
+            }
+            None => {
+                self.draw_vars.empty_texture(0);
+                self.has_texture = 0.0;
+            }
+        }
+    }
+
+    pub fn set_camera_pos(&mut self, cx: &Cx, pos: Vec3) {
+        self.draw_vars.set_uniform(cx, id!(camera_pos), &[pos.x, pos.y, pos.z]);
+    }
+
+    /// Draws one instance of `geometry` with the current transform and material.
+    pub fn draw(&mut self, cx: &mut Cx2d, geometry: &Geometry) {
+        if self.draw_vars.can_instance() {
+            self.draw_vars.set_geometry(geometry);
+            let new_area = cx.add_instance(&self.draw_vars);
+            self.draw_vars.area = cx.update_area_refs(self.draw_vars.area, new_area);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        crate::{
+            makepad_platform::*,
+            cx_2d::Cx2d,
+            draw_list_2d::DrawList2d,
+            geometry::GeometryGen,
+        },
+        super::DrawMesh,
+    };
+
+    #[test]
+    fn texture_presence_splits_draw_calls() {
+        let mut cx = Cx::new(Box::new( | _, _ | {}));
+        crate::live_design(&mut cx);
+        cx.live_expand();
+        let mut draw_mesh = DrawMesh::new_local(&mut cx);
+        let geometry = Geometry::new(&mut cx);
+        GeometryGen::from_cube_3d(1.0, 1.0, 1.0, 1, 1, 1).to_geometry(&mut cx, &geometry);
+        let texture = Texture::new_with_format(&mut cx, TextureFormat::VecBGRAu8_32 {
+            width: 1,
+            height: 1,
+            data: vec![0xffffffff]
+        });
+        let pass = Pass::new(&mut cx);
+        let mut draw_list = DrawList2d::new(&mut cx);
+
+        let draw_event = DrawEvent::default();
+        let cx = &mut Cx2d::new(&mut cx, &draw_event);
+        cx.begin_pass(&pass, Some(1.0));
+        draw_list.begin_always(cx);
+        draw_mesh.set_texture(None);
+        draw_mesh.draw(cx, &geometry);
+        draw_mesh.draw(cx, &geometry);
+        draw_mesh.set_texture(Some(&texture));
+        draw_mesh.draw(cx, &geometry);
+        draw_mesh.draw(cx, &geometry);
+        draw_mesh.set_texture(None);
+        draw_mesh.draw(cx, &geometry);
+        draw_list.end(cx);
+        cx.end_pass(&pass);
+
+        let draw_items = &cx.draw_lists[draw_list.draw_list.id()].draw_items;
+        let instances: Vec<_> = (0..draw_items.len()).filter_map( | i | {
+            let draw_item = &draw_items[i];
+            let draw_call = draw_item.draw_call()?;
+            Some((draw_call.texture_slots[0].is_some(), draw_item.instances.as_ref().unwrap().len()))
+        }).collect();
+        // untextured instances batch together, but never with textured ones
+        let slots = instances.iter().map( | (_, len) | len).sum::<usize>() / 5;
+        assert_eq!(instances, vec![(false, 3 * slots), (true, 2 * slots)]);
+    }
+}
@@ -6,3 +6,4 @@ pub mod draw_line;
 pub mod draw_text;
 pub mod std;
 pub mod draw_trapezoid;
+pub mod draw_mesh;
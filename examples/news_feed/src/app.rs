@@ -208,6 +208,7 @@ live_design!{
                 padding: 0.0
                 profile_img = <Image> {
                     source: (IMG_PROFILE_A)
+                    async_load: true
                     margin: 0,
                     width: 50.,
                     height: 50.
@@ -270,6 +271,7 @@ live_design!{
         
         hero = <Image> {
             source: (IMG_A),
+            async_load: true
             //image_scale: 1.0,
             margin: 0,
             width: Fill,
@@ -372,9 +372,11 @@ impl App {
             let value = JsonValue::deserialize_json(&data).unwrap();
             // lets push these ids into a vec
             let mut lights = Vec::new();
-            for (id,light) in value.key("lights").object(){
-                let id = id.parse::<u64>().unwrap();
-                lights.push((id, light.key("name").string(), light.key("uniqueid").string()));
+            if let Some(lights_obj) = value["lights"].as_object(){
+                for (id,light) in lights_obj{
+                    let id = id.parse::<u64>().unwrap();
+                    lights.push((id, light["name"].as_str().unwrap_or(""), light["uniqueid"].as_str().unwrap_or("")));
+                }
             }
             lights.sort_by(|a,b| a.0.cmp(&b.0));
             for (id, name, _unique) in lights{
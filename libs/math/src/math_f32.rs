@@ -653,6 +653,31 @@ impl Mat4 {
         ]}
     }
     
+    /// A view matrix for a camera at `eye` looking at `target`.
+    pub fn look_at(eye: Vec3, target: Vec3, up: Vec3) -> Mat4 {
+        let z = (eye - target).normalize();
+        let x = Vec3::cross(up, z).normalize();
+        let y = Vec3::cross(z, x);
+        Mat4 {v: [
+            x.x,
+            y.x,
+            z.x,
+            0.0,
+            x.y,
+            y.y,
+            z.y,
+            0.0,
+            x.z,
+            y.z,
+            z.z,
+            0.0,
+            -x.dot(eye),
+            -y.dot(eye),
+            -z.dot(eye),
+            1.0
+        ]}
+    }
+
     pub fn translation(x: f32, y: f32, z: f32) -> Mat4 {
         Mat4 {v: [
             1.0,
@@ -1,5 +1,19 @@
 use proc_macro::{TokenStream};
 use makepad_micro_proc_macro::*;
+use crate::serde_attrs::*;
+
+// binary data has no keys, so only #[skip] changes the layout
+fn de_fields(tb: &mut TokenBuilder, fields: &[SerdeField]) {
+    for field in fields {
+        tb.ident(&field.name).add(":");
+        if field.skip {
+            tb.stream(Some(field.default_value())).add(",");
+        }
+        else {
+            tb.add("DeBin :: de_bin ( o , d ) ? ,");
+        }
+    }
+}
 
 pub fn derive_ser_bin_impl(input: TokenStream) -> TokenStream {
     let mut parser = TokenParser::new(input);
@@ -24,7 +38,8 @@ pub fn derive_ser_bin_impl(input: TokenStream) -> TokenStream {
                 }
             }
             else if let Some(fields) = parser.eat_all_struct_fields(){ 
-                for field in fields{
+                let fields = match parse_fields(fields) {Ok(fields) => fields, Err(err) => return err};
+                for field in fields.iter().filter(| field | !field.skip){
                     tb.add("self .").ident(&field.name).add(". ser_bin ( s ) ;");
                 }
             }
@@ -65,12 +80,13 @@ pub fn derive_ser_bin_impl(input: TokenStream) -> TokenStream {
                         tb.add("}");
                     }
                     else if let Some(fields) = parser.eat_all_struct_fields(){ // named variant
+                        let fields = match parse_fields(fields) {Ok(fields) => fields, Err(err) => return err};
                         tb.add("Self ::").ident(&variant).add("{");
-                        for field in fields.iter(){
+                        for field in fields.iter().filter(| field | !field.skip){
                             tb.ident(&field.name).add(",");
                         }
-                        tb.add("} => {").suf_u16(index).add(". ser_bin ( s ) ;");
-                        for field in fields{
+                        tb.add(".. } => {").suf_u16(index).add(". ser_bin ( s ) ;");
+                        for field in fields.iter().filter(| field | !field.skip){
                             tb.ident(&field.name).add(". ser_bin ( s ) ;");
                         }
                         tb.add("}");
@@ -122,10 +138,9 @@ pub fn derive_de_bin_impl(input: TokenStream) -> TokenStream {
                 tb.add(")");
             }
             else if let Some(fields) = parser.eat_all_struct_fields(){ 
+                let fields = match parse_fields(fields) {Ok(fields) => fields, Err(err) => return err};
                 tb.add("{");
-                for field in fields{
-                    tb.ident(&field.name).add(": DeBin :: de_bin ( o , d ) ? ,");
-                }
+                de_fields(&mut tb, &fields);
                 tb.add("}");
             }
             else{
@@ -165,10 +180,9 @@ pub fn derive_de_bin_impl(input: TokenStream) -> TokenStream {
                         tb.add(")");
                     }
                     else if let Some(fields) = parser.eat_all_struct_fields(){ // named variant
+                        let fields = match parse_fields(fields) {Ok(fields) => fields, Err(err) => return err};
                         tb.ident(&variant).add("{");
-                        for field in fields.iter(){
-                            tb.ident(&field.name).add(": DeBin :: de_bin ( o , d ) ? ,");
-                        }
+                        de_fields(&mut tb, &fields);
                         tb.add("}");
                     }
                     else if parser.is_punct_alone(',') || parser.is_eot(){ // bare variant
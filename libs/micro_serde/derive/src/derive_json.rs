@@ -1,16 +1,129 @@
 use proc_macro::TokenStream;
 use makepad_micro_proc_macro::*;
+use crate::serde_attrs::*;
+
+// fields are reached through self in structs and through bindings in enum variants
+fn field_ref(tb: &mut TokenBuilder, bound: bool, name: &str) {
+    if !bound {
+        tb.add("& self .");
+    }
+    tb.ident(name);
+}
+
+fn bind_fields(tb: &mut TokenBuilder, fields: &[SerdeField]) {
+    tb.add("{");
+    for field in fields.iter().filter(| field | !field.skip) {
+        tb.ident(&field.name).add(",");
+    }
+    tb.add(".. }");
+}
+
+fn ser_fields(tb: &mut TokenBuilder, fields: &[SerdeField], bound: bool, d: &str) {
+    for field in fields {
+        if field.skip {
+            continue;
+        }
+        if field.flatten {
+            tb.add("s . flat (").add(d).add(",");
+            field_ref(tb, bound, &field.name);
+            tb.add(") ;");
+        }
+        else if field.is_option {
+            tb.add("if let Some ( t ) =");
+            field_ref(tb, bound, &field.name);
+            tb.add("{ s . field_sep ( ) ;");
+            tb.add("s . field (").add(d).add("+ 1 ,").string(&field.json_key()).add(") ;");
+            tb.add("t . ser_json (").add(d).add("+ 1 , s ) ; }");
+        }
+        else {
+            tb.add("s . field_sep ( ) ;");
+            tb.add("s . field (").add(d).add("+ 1 ,").string(&field.json_key()).add(") ;");
+            tb.add("(");
+            field_ref(tb, bound, &field.name);
+            tb.add(") . ser_json (").add(d).add("+ 1 , s ) ;");
+        }
+    }
+}
+
+fn ser_tuple(tb: &mut TokenBuilder, len: usize) {
+    tb.add("s . out . push (").chr('[').add(") ;");
+    for i in 0..len {
+        tb.ident(&format!("n{}", i)).add(". ser_json ( d , s ) ;");
+        if i != len - 1 {
+            tb.add("s . out . push (").chr(',').add(") ;");
+        }
+    }
+    tb.add("s . out . push (").chr(']').add(") ;");
+}
+
+fn de_fields(tb: &mut TokenBuilder, fields: &[SerdeField], variant: Option<&str>) {
+    let flatten = fields.iter().any(| field | field.flatten);
+    tb.add("{ s . curly_open ( i ) ? ;");
+    for field in fields.iter().filter(| field | !field.skip && !field.flatten) {
+        tb.add("let mut").ident(&format!("_{}", field.name)).add("= None ;");
+    }
+    if flatten {
+        tb.add("let mut flatten = SerJsonState { out : String :: new ( ) } ; flatten . st_pre ( ) ;");
+    }
+    tb.add("while let Some ( _ ) = s . next_str ( ) {");
+    tb.add("match s . strbuf . as_str ( ) {");
+    for field in fields.iter().filter(| field | !field.skip && !field.flatten) {
+        tb.string(&field.json_key()).add("=> { s . next_colon ( i ) ? ;");
+        tb.ident(&format!("_{}", field.name)).add("= Some ( DeJson :: de_json ( s , i ) ? ) ; } ,");
+    }
+    if flatten {
+        tb.add("_ => s . capture_field ( i , & mut flatten ) ?");
+    }
+    else {
+        tb.add("_ => return std :: result :: Result :: Err ( s . err_exp ( & s . strbuf ) )");
+    }
+    tb.add("} ; s . eat_comma_curly ( i ) ? ;");
+    tb.add("} ; s . curly_close ( i ) ? ;");
+    if flatten {
+        tb.add("flatten . st_post ( 0 ) ;");
+    }
+
+    tb.add("Self");
+    if let Some(variant) = variant {
+        tb.add("::").ident(variant);
+    }
+    tb.add("{");
+    for field in fields {
+        tb.ident(&field.name).add(":");
+        if field.skip {
+            tb.stream(Some(field.default_value())).add(",");
+        }
+        else if field.flatten {
+            tb.add("s . de_captured ( & flatten . out , DeJson :: de_json ) ? ,");
+        }
+        else if field.is_option && field.default.is_none() {
+            // a missing option is None, a present one holds its own Option
+            tb.ident(&format!("_{}", field.name)).add(". flatten ( ) ,");
+        }
+        else {
+            tb.add("if let Some ( t ) =").ident(&format!("_{}", field.name)).add("{ t } else {");
+            if let Some(default) = &field.default {
+                tb.stream(Some(default.clone()));
+            }
+            else {
+                tb.add("return std :: result :: Result :: Err ( s . err_nf (").string(&field.json_key()).add(") )");
+            }
+            tb.add("} ,");
+        }
+    }
+    tb.add("} }");
+}
 
 pub fn derive_ser_json_impl(input: TokenStream) -> TokenStream {
 
     let mut parser = TokenParser::new(input);
     let mut tb = TokenBuilder::new();
-    
-    parser.eat_attributes();
+
+    let attrs = parser.eat_attributes();
     parser.eat_ident("pub");
     if parser.eat_ident("struct"){
         if let Some(name) = parser.eat_any_ident(){
-            
+
             let generic = parser.eat_generic();
             let types = parser.eat_all_types();
             let where_clause = parser.eat_where_clause(Some("SerJson"));
@@ -18,7 +131,7 @@ pub fn derive_ser_json_impl(input: TokenStream) -> TokenStream {
             tb.add("impl").stream(generic.clone());
             tb.add("SerJson for").ident(&name).stream(generic).stream(where_clause);
             tb.add("{ fn ser_json ( & self , d : usize , s : & mut SerJsonState ) {");
-            
+
             if let Some(types) = types{
                 tb.add("s . out . push (").chr('[').add(") ;");
                 for i in 0..types.len(){
@@ -30,27 +143,10 @@ pub fn derive_ser_json_impl(input: TokenStream) -> TokenStream {
                 tb.add("s . out . push (").chr(']').add(") ;");
             }
             else if let Some(fields) = parser.eat_all_struct_fields(){
-                let fields_len = fields.len();
-                tb.add("s . st_pre ( ) ;");
+                let fields = match parse_fields(fields) {Ok(fields) => fields, Err(err) => return err};
                 // named struct
-                for (i, field) in fields.into_iter().enumerate() {
-                    if field.ty.into_iter().next().unwrap().to_string() == "Option"{
-                        tb.add("if let Some ( t ) = ").add("& self .").ident(&field.name).add("{");
-                        tb.add("s . field ( d + 1 ,").string(&field.name).add(") ;");
-                        tb.add("t . ser_json ( d + 1 , s ) ;");
-                        if i != fields_len - 1 {
-                            tb.add("s . conl ( ) ;");
-                        }
-                        tb.add("} ;");
-                    }
-                    else{
-                        tb.add("s . field ( d + 1 ,").string(&field.name).add(" ) ;");
-                        tb.add("self .").ident(&field.name).add(". ser_json ( d + 1 , s ) ;");
-                        if i != fields_len - 1 {
-                            tb.add("s . conl ( ) ;");
-                        }
-                    }
-                }
+                tb.add("s . st_pre ( ) ;");
+                ser_fields(&mut tb, &fields, false, "d");
                 tb.add("s . st_post ( d ) ;");
             }
             else{
@@ -62,80 +158,106 @@ pub fn derive_ser_json_impl(input: TokenStream) -> TokenStream {
     }
     else if parser.eat_ident("enum"){
         if let Some(name) = parser.eat_any_ident(){
+            let repr = match EnumRepr::parse(&attrs) {Ok(repr) => repr, Err(err) => return err};
             let generic = parser.eat_generic();
             let where_clause = parser.eat_where_clause(Some("SerJson"));
 
             tb.add("impl").stream(generic.clone());
             tb.add("SerJson for").ident(&name).stream(generic).stream(where_clause);
             tb.add("{ fn ser_json ( & self , d : usize , s : & mut SerJsonState ) {");
-            tb.add("s . out . push (").chr('{').add(") ;");
+            if let EnumRepr::External = repr {
+                tb.add("s . out . push (").chr('{').add(") ;");
+            }
             tb.add("match self {");
-            
+
             if !parser.open_brace(){
                 return parser.unexpected()
             }
 
             while !parser.eat_eot(){
                 // parse ident
-                parser.eat_attributes();
+                let rename = match parse_variant_rename(&parser.eat_attributes()) {Ok(rename) => rename, Err(err) => return err};
                 if let Some(variant) = parser.eat_any_ident(){
+                    let key = rename.unwrap_or(variant.clone());
                     if let Some(types) = parser.eat_all_types(){
-                        
+
                         tb.add("Self ::").ident(&variant).add("(");
                         for i in 0..types.len(){
                             tb.ident(&format!("n{}", i)).add(",");
                         }
                         tb.add(") => {");
-                        tb.add("s . label (").string(&variant).add(") ;");
-                        tb.add("s . out . push (").chr(':').add(") ;");
-                        tb.add("s . out . push (").chr('[').add(") ;");
-                        
-                        for i in 0..types.len(){
-                            tb.ident(&format!("n{}", i)).add(". ser_json ( d , s ) ;");
-                            if i != types.len() - 1{
-                                tb.add("s . out . push (").chr(',').add(") ;");
+                        match &repr {
+                            EnumRepr::External => {
+                                tb.add("s . label (").string(&key).add(") ;");
+                                tb.add("s . out . push (").chr(':').add(") ;");
+                                ser_tuple(&mut tb, types.len());
+                            }
+                            EnumRepr::Internal(tag) => {
+                                if types.len() != 1 {
+                                    return error("Internally tagged enums only support tuple variants with a single field")
+                                }
+                                tb.add("s . st_pre ( ) ; s . field ( d + 1 ,").string(tag).add(") ;");
+                                tb.add("s . label (").string(&key).add(") ;");
+                                tb.add("s . flat ( d , n0 ) ; s . st_post ( d ) ;");
+                            }
+                            EnumRepr::Adjacent(tag, content) => {
+                                tb.add("s . st_pre ( ) ; s . field ( d + 1 ,").string(tag).add(") ;");
+                                tb.add("s . label (").string(&key).add(") ;");
+                                tb.add("s . conl ( ) ; s . field ( d + 1 ,").string(content).add(") ;");
+                                if types.len() == 1 {
+                                    tb.add("n0 . ser_json ( d + 1 , s ) ;");
+                                }
+                                else {
+                                    ser_tuple(&mut tb, types.len());
+                                }
+                                tb.add("s . st_post ( d ) ;");
                             }
                         }
-                        tb.add("s . out . push (").chr(']').add(") ;");
                         tb.add("}");
                     }
                     else if let Some(fields) = parser.eat_all_struct_fields(){ // named variant
-                        tb.add("Self ::").ident(&variant).add("{");
-                        for field in fields.iter(){
-                            tb.ident(&field.name).add(",");
-                        }
-                        tb.add("} => {");
-                        
-                        tb.add("s . label (").string(&variant).add(") ;");
-                        tb.add("s . out . push (").chr(':').add(") ;");
-                        tb.add("s . st_pre ( ) ;");
-
-                        let fields_len = fields.len();
-                        for (i, field) in fields.into_iter().enumerate() {
-                            let field_strip = if let Some(v) = field.name.strip_prefix("_"){v}else{&field.name};
-                            if field.ty.into_iter().next().unwrap().to_string() == "Option"{
-                                tb.add("if let Some ( t ) = ").ident(&field.name).add("{");
-                                tb.add("s . field ( d + 1 ,").string(&field_strip).add(") ;");
-                                tb.add("t . ser_json ( d + 1 , s ) ;");
-                                if i != fields_len - 1 {
-                                    tb.add("s . conl ( ) ;");
-                                }
-                                tb.add("} ;");
+                        let fields = match parse_fields(fields) {Ok(fields) => fields, Err(err) => return err};
+                        tb.add("Self ::").ident(&variant);
+                        bind_fields(&mut tb, &fields);
+                        tb.add("=> {");
+                        match &repr {
+                            EnumRepr::External => {
+                                tb.add("s . label (").string(&key).add(") ;");
+                                tb.add("s . out . push (").chr(':').add(") ;");
+                                tb.add("s . st_pre ( ) ;");
+                                ser_fields(&mut tb, &fields, true, "d");
+                                tb.add("s . st_post ( d ) ;");
                             }
-                            else{
-                                tb.add("s . field ( d + 1 ,").string(&field_strip).add(" ) ;");
-                                tb.ident(&field.name).add(". ser_json ( d + 1 , s ) ;");
-                                if i != fields_len - 1 {
-                                    tb.add("s . conl ( ) ;");
-                                }
+                            EnumRepr::Internal(tag) => {
+                                tb.add("s . st_pre ( ) ; s . field ( d + 1 ,").string(tag).add(") ;");
+                                tb.add("s . label (").string(&key).add(") ;");
+                                ser_fields(&mut tb, &fields, true, "d");
+                                tb.add("s . st_post ( d ) ;");
+                            }
+                            EnumRepr::Adjacent(tag, content) => {
+                                tb.add("s . st_pre ( ) ; s . field ( d + 1 ,").string(tag).add(") ;");
+                                tb.add("s . label (").string(&key).add(") ;");
+                                tb.add("s . conl ( ) ; s . field ( d + 1 ,").string(content).add(") ;");
+                                tb.add("s . st_pre ( ) ;");
+                                ser_fields(&mut tb, &fields, true, "d + 1");
+                                tb.add("s . st_post ( d + 1 ) ; s . st_post ( d ) ;");
                             }
                         }
-                        tb.add("s . st_post ( d ) ; }");
+                        tb.add("}");
                     }
                     else if parser.is_punct_alone(',') || parser.is_eot(){ // bare variant
                         tb.add("Self ::").ident(&variant).add("=> {");
-                        tb.add("s . label (").string(&variant).add(") ;");
-                        tb.add("s . out . push_str (").string(":[]").add(") ; }");
+                        match &repr {
+                            EnumRepr::External => {
+                                tb.add("s . label (").string(&key).add(") ;");
+                                tb.add("s . out . push_str (").string(":[]").add(") ;");
+                            }
+                            EnumRepr::Internal(tag) | EnumRepr::Adjacent(tag, _) => {
+                                tb.add("s . st_pre ( ) ; s . field ( d + 1 ,").string(tag).add(") ;");
+                                tb.add("s . label (").string(&key).add(") ; s . st_post ( d ) ;");
+                            }
+                        }
+                        tb.add("}");
                     }
                     else{
                         return parser.unexpected();
@@ -147,7 +269,9 @@ pub fn derive_ser_json_impl(input: TokenStream) -> TokenStream {
                 }
             }
             tb.add("}");
-            tb.add("s . out . push (").chr('}').add(") ;");
+            if let EnumRepr::External = repr {
+                tb.add("s . out . push (").chr('}').add(") ;");
+            }
             tb.add("} } ;");
             return tb.end();
         }
@@ -158,8 +282,8 @@ pub fn derive_ser_json_impl(input: TokenStream) -> TokenStream {
 pub fn derive_de_json_impl(input: TokenStream) -> TokenStream {
     let mut parser = TokenParser::new(input);
     let mut tb = TokenBuilder::new();
-    
-    parser.eat_attributes();
+
+    let attrs = parser.eat_attributes();
     parser.eat_ident("pub");
     if parser.eat_ident("struct"){
         if let Some(name) = parser.eat_any_ident(){
@@ -183,47 +307,23 @@ pub fn derive_de_json_impl(input: TokenStream) -> TokenStream {
                 tb.add("s . block_close ( i ) ? ;");
                 tb.add("std :: result :: Result :: Ok ( r )");
             }
-            else if let Some(fields) = parser.eat_all_struct_fields(){ 
-                tb.add("s . curly_open ( i ) ? ;");
-                for field in &fields{
-                    tb.add("let mut").ident(&format!("_{}",field.name)).add("= None ;");
-                }
-                tb.add("while let Some ( _ ) = s . next_str ( ) {");
-                tb.add("match s . strbuf . as_ref ( ) {");
-                for field in &fields{
-                    let field_strip = if let Some(v) = field.name.strip_prefix("_"){v}else{&field.name};
-                    tb.string(&field_strip).add("=> { s . next_colon ( i ) ? ;");
-                    tb.ident(&format!("_{}",field.name)).add("= Some (DeJson :: de_json ( s , i ) ? ) ; } ,");
-                }
-                tb.add("_ => return std :: result :: Result :: Err ( s . err_exp ( & s . strbuf ) )");
-                tb.add("} ; s . eat_comma_curly ( i ) ? ;");
-                tb.add("} ; s . curly_close ( i ) ? ;");
-                
-                tb.add("std :: result :: Result :: Ok ( Self {");
-                for field in fields{
-                    tb.ident(&field.name).add(":");
-                    if field.ty.into_iter().next().unwrap().to_string() == "Option"{
-                        tb.add("if let Some ( t ) =").ident(&format!("_{}",field.name));
-                        tb.add("{ t } else { None } ,");
-                    }
-                    else{
-                        tb.add("if let Some ( t ) =").ident(&format!("_{}",field.name));
-                        tb.add("{ t } else { return Err ( s . err_nf (");
-                        tb.string(&field.name).add(") ) } ,");
-                    }
-                }
-                tb.add("} )");
+            else if let Some(fields) = parser.eat_all_struct_fields(){
+                let fields = match parse_fields(fields) {Ok(fields) => fields, Err(err) => return err};
+                tb.add("std :: result :: Result :: Ok (");
+                de_fields(&mut tb, &fields, None);
+                tb.add(")");
             }
             else{
                 return parser.unexpected()
             }
-            tb.add("} } ;"); 
+            tb.add("} } ;");
             return tb.end();
         }
     }
     else if parser.eat_ident("enum"){
-        
+
         if let Some(name) = parser.eat_any_ident(){
+            let repr = match EnumRepr::parse(&attrs) {Ok(repr) => repr, Err(err) => return err};
             let generic = parser.eat_generic();
             let where_clause = parser.eat_where_clause(Some("DeJson"));
 
@@ -231,75 +331,131 @@ pub fn derive_de_json_impl(input: TokenStream) -> TokenStream {
             tb.add("DeJson for").ident(&name).stream(generic).stream(where_clause);
             tb.add("{ fn de_json ( s : & mut  DeJsonState , i : & mut std :: str :: Chars )");
             tb.add("-> std :: result :: Result < Self , DeJsonErr > { ");
-            tb.add("s . curly_open ( i ) ? ;");
-            tb.add("let _ = s . string ( i ) ? ;");
-            tb.add("s . colon ( i ) ? ;");
-            tb.add("let r = std :: result :: Result :: Ok ( match s . strbuf . as_ref ( ) {");
-            
+
             if !parser.open_brace(){
                 return parser.unexpected()
             }
+
+            // the tagged forms are read into a buffer first, the tag can come after the fields
+            match &repr {
+                EnumRepr::External => {
+                    tb.add("s . curly_open ( i ) ? ;");
+                    tb.add("let _ = s . string ( i ) ? ;");
+                    tb.add("s . colon ( i ) ? ;");
+                    tb.add("let r = std :: result :: Result :: Ok ( match s . strbuf . as_ref ( ) {");
+                }
+                EnumRepr::Internal(tag) => {
+                    tb.add("s . curly_open ( i ) ? ;");
+                    tb.add("let mut tag = None ;");
+                    tb.add("let mut rest = SerJsonState { out : String :: new ( ) } ; rest . st_pre ( ) ;");
+                    tb.add("while let Some ( _ ) = s . next_str ( ) {");
+                    tb.add("if s . strbuf ==").string(tag).add("{ s . next_colon ( i ) ? ; tag = Some ( s . as_string ( ) ? ) ; s . next_tok ( i ) ? ; }");
+                    tb.add("else { s . capture_field ( i , & mut rest ) ? ; }");
+                    tb.add("s . eat_comma_curly ( i ) ? ; }");
+                    tb.add("s . curly_close ( i ) ? ; rest . st_post ( 0 ) ;");
+                    tb.add("let tag : String = if let Some ( tag ) = tag { tag } else { return std :: result :: Result :: Err ( s . err_nf (").string(tag).add(") ) } ;");
+                    tb.add("s . de_captured ( & rest . out , | s , i | std :: result :: Result :: Ok ( match tag . as_str ( ) {");
+                }
+                EnumRepr::Adjacent(tag, content) => {
+                    tb.add("s . curly_open ( i ) ? ;");
+                    tb.add("let mut tag = None ;");
+                    tb.add("let mut content = None ;");
+                    tb.add("while let Some ( _ ) = s . next_str ( ) {");
+                    tb.add("if s . strbuf ==").string(tag).add("{ s . next_colon ( i ) ? ; tag = Some ( s . as_string ( ) ? ) ; s . next_tok ( i ) ? ; }");
+                    tb.add("else if s . strbuf ==").string(content).add("{");
+                    tb.add("s . next_colon ( i ) ? ; let mut c = SerJsonState { out : String :: new ( ) } ;");
+                    tb.add("s . capture_value ( i , & mut c ) ? ; content = Some ( c . out ) ; }");
+                    tb.add("else { return std :: result :: Result :: Err ( s . err_exp ( & s . strbuf ) ) }");
+                    tb.add("s . eat_comma_curly ( i ) ? ; }");
+                    tb.add("s . curly_close ( i ) ? ;");
+                    tb.add("let tag : String = if let Some ( tag ) = tag { tag } else { return std :: result :: Result :: Err ( s . err_nf (").string(tag).add(") ) } ;");
+                    tb.add("match tag . as_str ( ) {");
+                }
+            }
+
+            // adjacently tagged bare variants have no content, they are matched before it is parsed
+            let mut content_tb = TokenBuilder::new();
+            let mut has_content = false;
             while !parser.eat_eot(){
                 // parse ident
-                parser.eat_attributes();
+                let rename = match parse_variant_rename(&parser.eat_attributes()) {Ok(rename) => rename, Err(err) => return err};
                 if let Some(variant) = parser.eat_any_ident(){
-                    tb.string(&variant).add("=> {");
+                    let key = rename.unwrap_or(variant.clone());
                     if let Some(types) = parser.eat_all_types(){
-                        
-                        tb.add("s . block_open ( i ) ? ;");
-                        tb.add("let r = Self ::").ident(&variant).add("(");
-                        for _ in 0..types.len(){
-                            tb.add("{ let r = DeJson :: de_json ( s , i ) ? ; s . eat_comma_block ( i ) ? ; r } ,");
+                        has_content = true;
+                        let tb = if let EnumRepr::Adjacent(..) = repr {&mut content_tb} else {&mut tb};
+                        tb.string(&key).add("=> {");
+                        match &repr {
+                            EnumRepr::Internal(_) if types.len() != 1 => {
+                                return error("Internally tagged enums only support tuple variants with a single field")
+                            }
+                            EnumRepr::Internal(_) | EnumRepr::Adjacent(..) if types.len() == 1 => {
+                                tb.add("Self ::").ident(&variant).add("( DeJson :: de_json ( s , i ) ? )");
+                            }
+                            _ => {
+                                tb.add("s . block_open ( i ) ? ;");
+                                tb.add("let r = Self ::").ident(&variant).add("(");
+                                for _ in 0..types.len(){
+                                    tb.add("{ let r = DeJson :: de_json ( s , i ) ? ; s . eat_comma_block ( i ) ? ; r } ,");
+                                }
+                                tb.add(") ;");
+                                tb.add("s . block_close ( i ) ? ; r");
+                            }
                         }
-                        tb.add(") ;");
-                        tb.add("s . block_close ( i ) ? ; r");
+                        tb.add("}");
                     }
                     else if let Some(fields) = parser.eat_all_struct_fields(){ // named variant
-                        tb.add("s . curly_open ( i ) ? ;");
-                        for field in &fields{
-                            tb.add("let mut").ident(&format!("_{}",field.name)).add("= None ;");
-                        }
-                        tb.add("while let Some ( _ ) = s . next_str ( ) {");
-                        tb.add("match s . strbuf . as_ref ( ) {");
-                        for field in &fields{
-                            tb.string(&field.name).add("=> { s . next_colon ( i ) ? ;");
-                            tb.ident(&format!("_{}",field.name)).add("= Some (DeJson :: de_json ( s , i ) ? ) ; } ,");
-                        }
-                        tb.add("_ => return std :: result :: Result :: Err ( s . err_exp ( & s . strbuf ) )");
-                        tb.add("} s . eat_comma_curly ( i ) ? ;");
-                        tb.add("} s . curly_close ( i ) ? ;");
-                        
-                        tb.add("Self ::").ident(&variant).add("{");
-                        for field in fields{
-                            tb.ident(&field.name).add(":");
-                            if field.ty.into_iter().next().unwrap().to_string() == "Option"{
-                                tb.add("if let Some ( t ) =").ident(&format!("_{}",field.name));
-                                tb.add("{ t } else { None } ,");
+                        let fields = match parse_fields(fields) {Ok(fields) => fields, Err(err) => return err};
+                        has_content = true;
+                        let tb = if let EnumRepr::Adjacent(..) = repr {&mut content_tb} else {&mut tb};
+                        tb.string(&key).add("=>");
+                        de_fields(tb, &fields, Some(&variant));
+                    }
+                    else if parser.is_punct_alone(',') || parser.is_eot(){ // bare variant
+                        tb.string(&key).add("=> {");
+                        match &repr {
+                            EnumRepr::External => {
+                                tb.add("s . block_open ( i ) ? ; s . block_close ( i ) ? ; Self ::").ident(&variant);
+                            }
+                            EnumRepr::Internal(_) => {
+                                tb.add("s . curly_open ( i ) ? ; s . curly_close ( i ) ? ; Self ::").ident(&variant);
                             }
-                            else{
-                                tb.add("if let Some ( t ) =").ident(&format!("_{}",field.name));
-                                tb.add("{ t } else { return Err ( s . err_nf (");
-                                tb.string(&field.name).add(") ) } ,");
+                            EnumRepr::Adjacent(..) => {
+                                tb.add("std :: result :: Result :: Ok ( Self ::").ident(&variant).add(")");
                             }
                         }
                         tb.add("}");
                     }
-                    else if parser.is_punct_alone(',') || parser.is_eot(){ // bare variant
-                        tb.add("s . block_open ( i ) ? ; s . block_close ( i ) ? ; Self ::").ident(&variant);
-                    }
                     else{
                         return parser.unexpected();
                     }
-                    
-                    tb.add("}");
                     parser.eat_punct_alone(',');
                 }
                 else{
                     return parser.unexpected()
                 }
             }
-            tb.add("_ => return std :: result :: Result :: Err ( s . err_exp ( & s . strbuf ) )");
-            tb.add("} ) ; s . curly_close ( i ) ? ; r } }");
+            match &repr {
+                EnumRepr::External => {
+                    tb.add("_ => return std :: result :: Result :: Err ( s . err_exp ( & s . strbuf ) )");
+                    tb.add("} ) ; s . curly_close ( i ) ? ; r } }");
+                }
+                EnumRepr::Internal(_) => {
+                    tb.add("_ => return std :: result :: Result :: Err ( s . err_enum ( & tag ) )");
+                    tb.add("} ) ) } }");
+                }
+                EnumRepr::Adjacent(..) if !has_content => {
+                    tb.add("_ => { let _ = content ; std :: result :: Result :: Err ( s . err_enum ( & tag ) ) } } } }");
+                }
+                EnumRepr::Adjacent(_, content) => {
+                    tb.add("_ => {");
+                    tb.add("let content : String = if let Some ( content ) = content { content } else { return std :: result :: Result :: Err ( s . err_nf (").string(content).add(") ) } ;");
+                    tb.add("s . de_captured ( & content , | s , i | std :: result :: Result :: Ok ( match tag . as_str ( ) {");
+                    tb.stream(Some(content_tb.end()));
+                    tb.add("_ => return std :: result :: Result :: Err ( s . err_enum ( & tag ) )");
+                    tb.add("} ) ) } } } }");
+                }
+            }
             return tb.end();
         }
     }
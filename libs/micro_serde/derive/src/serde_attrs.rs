@@ -0,0 +1,113 @@
+use proc_macro::{TokenStream, TokenTree};
+use makepad_micro_proc_macro::*;
+
+pub struct SerdeField {
+    pub name: String,
+    pub is_option: bool,
+    pub rename: Option<String>,
+    pub default: Option<TokenStream>,
+    pub skip: bool,
+    pub flatten: bool,
+}
+
+impl SerdeField {
+    // json keys drop a leading _ so fields can be named after keywords
+    pub fn json_key(&self) -> String {
+        if let Some(rename) = &self.rename {
+            return rename.clone()
+        }
+        self.name.strip_prefix('_').unwrap_or(&self.name).to_string()
+    }
+
+    pub fn ron_key(&self) -> String {
+        self.rename.clone().unwrap_or(self.name.clone())
+    }
+
+    // the value used for skipped fields and for keys missing from the input
+    pub fn default_value(&self) -> TokenStream {
+        self.default.clone().unwrap_or_else(default_default)
+    }
+}
+
+fn default_default() -> TokenStream {
+    let mut tb = TokenBuilder::new();
+    tb.add("Default :: default ( )");
+    tb.end()
+}
+
+pub fn parse_fields(fields: Vec<StructField>) -> Result<Vec<SerdeField>, TokenStream> {
+    let mut out = Vec::new();
+    for field in fields {
+        let mut serde_field = SerdeField {
+            is_option: field.ty.into_iter().next().map(| t | t.to_string() == "Option").unwrap_or(false),
+            name: field.name,
+            rename: None,
+            default: None,
+            skip: false,
+            flatten: false,
+        };
+        for attr in &field.attrs {
+            match attr.name.as_ref() {
+                "rename" => serde_field.rename = Some(attr_string(attr) ?),
+                "default" | "default_on_missing" => serde_field.default = Some(attr.args.clone().filter(| args | !args.is_empty()).unwrap_or_else(default_default)),
+                "skip" => serde_field.skip = true,
+                "flatten" => serde_field.flatten = true,
+                _ => ()
+            }
+        }
+        out.push(serde_field);
+    }
+    if out.iter().filter(| field | field.flatten).count() > 1 {
+        return Err(error("Only one #[flatten] field is supported per struct"))
+    }
+    Ok(out)
+}
+
+pub fn parse_variant_rename(attrs: &[Attribute]) -> Result<Option<String>, TokenStream> {
+    for attr in attrs {
+        if attr.name == "rename" {
+            return Ok(Some(attr_string(attr) ?))
+        }
+    }
+    Ok(None)
+}
+
+// how an enum is written, #[tag("type")] puts the variant name next to its fields
+// and #[tag("type")] #[content("value")] puts the fields under a key of their own
+pub enum EnumRepr {
+    External,
+    Internal(String),
+    Adjacent(String, String),
+}
+
+impl EnumRepr {
+    pub fn parse(attrs: &[Attribute]) -> Result<Self, TokenStream> {
+        let mut tag = None;
+        let mut content = None;
+        for attr in attrs {
+            match attr.name.as_ref() {
+                "tag" => tag = Some(attr_string(attr) ?),
+                "content" => content = Some(attr_string(attr) ?),
+                _ => ()
+            }
+        }
+        match (tag, content) {
+            (None, None) => Ok(EnumRepr::External),
+            (Some(tag), None) => Ok(EnumRepr::Internal(tag)),
+            (Some(tag), Some(content)) => Ok(EnumRepr::Adjacent(tag, content)),
+            (None, Some(_)) => Err(error("#[content] needs a #[tag] as well")),
+        }
+    }
+}
+
+fn attr_string(attr: &Attribute) -> Result<String, TokenStream> {
+    if let Some(args) = &attr.args {
+        if let Some(TokenTree::Literal(lit)) = args.clone().into_iter().next() {
+            let lit = lit.to_string();
+            if let Some(value) = lit.strip_prefix('"').and_then(| v | v.strip_suffix('"')) {
+                return Ok(value.to_string())
+            }
+        }
+    }
+    Err(error(&format!("#[{0}] needs a string, like #[{0}(\"name\")]", attr.name)))
+}
@@ -25,6 +25,22 @@ enum MyEnum<T> where T: Clone {
     Four {z: Option<u32>, w: T},
 }
 
+// attributes map rust names onto the keys of external apis
+#[derive(SerJson, DeJson, SerRon, DeRon, PartialEq)]
+struct Request {
+    #[rename("maxTokens")] max_tokens: u32,
+    #[default_on_missing(1.0)] temperature: f64,
+    #[skip] retries: u32,
+    #[flatten] message: Message,
+}
+
+#[derive(SerJson, DeJson, SerRon, DeRon, PartialEq)]
+#[tag("type")]
+enum Message {
+    #[rename("text")] Text {text: String},
+    #[rename("image")] Image {url: String, width: Option<u32>},
+}
+
 fn main() {
     //let a = MyStruct{step1:1,step2:None};
     //let x = MyStruct2(1,2);
@@ -55,4 +71,23 @@ fn main() {
     println!("RON Output {}", ron);
     let y:MyStruct<usize> = DeRon::deserialize_ron(&ron).unwrap();
     println!("RON roundtrip equality {}", x == y);
+    
+    let x = Request {
+        max_tokens: 100,
+        temperature: 0.5,
+        retries: 0,
+        message: Message::Image {url: "cat.png".to_string(), width: None}
+    };
+    let json = x.serialize_json();
+    println!("JSON Output {}", json);
+    let y: Request = DeJson::deserialize_json(&json).unwrap();
+    println!("JSON roundtrip equality {}", x == y);
+    
+    let ron = x.serialize_ron();
+    println!("RON Output {}", ron);
+    let y: Request = DeRon::deserialize_ron(&ron).unwrap();
+    println!("RON roundtrip equality {}", x == y);
+    
+    let y: Request = DeJson::deserialize_json(r#"{"text":"hello","type":"text","maxTokens":10}"#).unwrap();
+    println!("JSON default and late tag {}", y.temperature == 1.0 && y.message == Message::Text {text: "hello".to_string()});
 }
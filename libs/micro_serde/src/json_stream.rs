@@ -0,0 +1,774 @@
+use std::collections::HashMap;
+use crate::serde_json::*;
+
+// Push based json parsing for data that arrives in pieces, like http bodies
+// and server-sent events. Bytes go in with push, events and values come out
+// as soon as they are complete, a chunk can end anywhere, even inside a utf8 character.
+
+#[derive(Default)]
+struct Utf8Decoder {
+    pending: Vec<u8>
+}
+
+impl Utf8Decoder {
+    // appends the complete characters of chunk to out, keeps a split character for the next chunk
+    fn decode(&mut self, chunk: &[u8], out: &mut String) {
+        self.pending.extend_from_slice(chunk);
+        let mut start = 0;
+        loop {
+            match std::str::from_utf8(&self.pending[start..]) {
+                Ok(s) => {
+                    out.push_str(s);
+                    start = self.pending.len();
+                    break;
+                }
+                Err(e) => {
+                    let valid = start + e.valid_up_to();
+                    out.push_str(std::str::from_utf8(&self.pending[start..valid]).unwrap());
+                    if let Some(len) = e.error_len() {
+                        out.push(char::REPLACEMENT_CHARACTER);
+                        start = valid + len;
+                    }
+                    else {
+                        start = valid;
+                        break;
+                    }
+                }
+            }
+        }
+        self.pending.drain(..start);
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum JsonEvent {
+    ObjectStart,
+    ObjectEnd,
+    ArrayStart,
+    ArrayEnd,
+    Key(String),
+    String(String),
+    U64(u64),
+    I64(i64),
+    F64(f64),
+    Bool(bool),
+    Null,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Container {
+    Object,
+    Array
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Expect {
+    Value,
+    FirstValue,
+    FirstKey,
+    Key,
+    Colon,
+    Comma
+}
+
+enum Partial {
+    Object(HashMap<String, JsonValue>, Option<String>),
+    Array(Vec<JsonValue>)
+}
+
+// How far the string or number at the cursor got before the input ran out, so the
+// next push carries on from there instead of lexing a long token from its start again
+#[derive(Default)]
+struct PartialToken {
+    // bytes lexed so far, counted from the start of the token
+    len: usize,
+    // the decoded characters of a string up to len
+    out: String,
+}
+
+// Any number of values separated by whitespace, so newline-delimited json works as is.
+// After an error the rest of the line is skipped and parsing starts over with the
+// next one, a broken line doesn't end the stream.
+pub struct JsonStreamParser {
+    decoder: Utf8Decoder,
+    buf: String,
+    pos: usize,
+    finished: bool,
+    skip_line: bool,
+    stack: Vec<Container>,
+    expect: Expect,
+    partial: Vec<Partial>,
+    token: PartialToken,
+    line: usize,
+    col: usize,
+}
+
+impl Default for JsonStreamParser {
+    fn default() -> Self {
+        Self {
+            decoder: Utf8Decoder::default(),
+            buf: String::new(),
+            pos: 0,
+            finished: false,
+            skip_line: false,
+            stack: Vec::new(),
+            expect: Expect::Value,
+            partial: Vec::new(),
+            token: PartialToken::default(),
+            line: 0,
+            col: 0,
+        }
+    }
+}
+
+enum Lex<T> {
+    Done(T, usize),
+    NeedMore,
+    Err(String)
+}
+
+impl JsonStreamParser {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, chunk: &[u8]) {
+        self.decoder.decode(chunk, &mut self.buf);
+    }
+
+    pub fn push_str(&mut self, chunk: &str) {
+        self.push(chunk.as_bytes());
+    }
+
+    // no more input, a number at the very end is complete now and anything unterminated is an error
+    pub fn finish(&mut self) {
+        self.finished = true;
+    }
+
+    // true when no value is halfway parsed
+    pub fn is_idle(&self) -> bool {
+        self.stack.is_empty() && self.buf[self.pos..].trim().is_empty()
+    }
+
+    pub fn next_event(&mut self) -> Option<Result<JsonEvent, DeJsonErr>> {
+        match self.lex_event() {
+            Some(Err(msg)) => {
+                let err = DeJsonErr {msg, line: self.line, col: self.col};
+                self.stack.clear();
+                self.partial.clear();
+                self.token = PartialToken::default();
+                self.expect = Expect::Value;
+                self.skip_line = true;
+                Some(Err(err))
+            }
+            Some(Ok(event)) => Some(Ok(event)),
+            None => {
+                self.buf.drain(..self.pos);
+                self.pos = 0;
+                None
+            }
+        }
+    }
+
+    // the next complete top level value, don't mix this with next_event on the same parser
+    pub fn next_value(&mut self) -> Option<Result<JsonValue, DeJsonErr>> {
+        while let Some(event) = self.next_event() {
+            match event {
+                Ok(event) => if let Some(value) = self.build(event) {
+                    return Some(Ok(value))
+                }
+                Err(err) => return Some(Err(err))
+            }
+        }
+        None
+    }
+
+    pub fn next_de_json<T: DeJson>(&mut self) -> Option<Result<T, DeJsonErr>> {
+        self.next_value().map( | value | value.and_then( | value | value.to_de_json()))
+    }
+
+    fn build(&mut self, event: JsonEvent) -> Option<JsonValue> {
+        let value = match event {
+            JsonEvent::ObjectStart => {
+                self.partial.push(Partial::Object(HashMap::new(), None));
+                return None
+            }
+            JsonEvent::ArrayStart => {
+                self.partial.push(Partial::Array(Vec::new()));
+                return None
+            }
+            JsonEvent::Key(key) => {
+                if let Some(Partial::Object(_, pending)) = self.partial.last_mut() {
+                    *pending = Some(key);
+                }
+                return None
+            }
+            JsonEvent::ObjectEnd | JsonEvent::ArrayEnd => match self.partial.pop() {
+                Some(Partial::Object(obj, _)) => JsonValue::Object(obj),
+                Some(Partial::Array(arr)) => JsonValue::Array(arr),
+                None => return None
+            }
+            JsonEvent::String(v) => JsonValue::String(v),
+            JsonEvent::U64(v) => JsonValue::U64(v),
+            JsonEvent::I64(v) => JsonValue::I64(v),
+            JsonEvent::F64(v) => JsonValue::F64(v),
+            JsonEvent::Bool(v) => JsonValue::Bool(v),
+            JsonEvent::Null => JsonValue::Null,
+        };
+        match self.partial.last_mut() {
+            None => Some(value),
+            Some(Partial::Object(obj, pending)) => {
+                if let Some(key) = pending.take() {
+                    obj.insert(key, value);
+                }
+                None
+            }
+            Some(Partial::Array(arr)) => {
+                arr.push(value);
+                None
+            }
+        }
+    }
+
+    fn consume(&mut self, len: usize) {
+        for c in self.buf[self.pos..self.pos + len].chars() {
+            if c == '\n' {
+                self.line += 1;
+                self.col = 0;
+            }
+            else {
+                self.col += 1;
+            }
+        }
+        self.pos += len;
+    }
+
+    fn after_value(&mut self) {
+        self.expect = if self.stack.is_empty() {Expect::Value} else {Expect::Comma};
+    }
+
+    fn lex_event(&mut self) -> Option<Result<JsonEvent, String>> {
+        loop {
+            if self.skip_line {
+                if let Some(end) = self.buf[self.pos..].find('\n') {
+                    self.consume(end + 1);
+                    self.skip_line = false;
+                }
+                else {
+                    let len = self.buf.len() - self.pos;
+                    self.consume(len);
+                    return None
+                }
+            }
+            let ws = self.buf[self.pos..].len() - self.buf[self.pos..].trim_start().len();
+            self.consume(ws);
+            let rest = &self.buf[self.pos..];
+            let c = match rest.chars().next() {
+                Some(c) => c,
+                None => {
+                    if self.finished && !self.stack.is_empty() {
+                        return Some(Err("Unexpected end of input".to_string()))
+                    }
+                    return None
+                }
+            };
+            let top = self.stack.last().copied();
+            let event = match (self.expect, c) {
+                (Expect::Value | Expect::FirstValue, '{') => {
+                    self.stack.push(Container::Object);
+                    self.expect = Expect::FirstKey;
+                    self.consume(1);
+                    JsonEvent::ObjectStart
+                }
+                (Expect::Value | Expect::FirstValue, '[') => {
+                    self.stack.push(Container::Array);
+                    self.expect = Expect::FirstValue;
+                    self.consume(1);
+                    JsonEvent::ArrayStart
+                }
+                (Expect::FirstValue | Expect::Comma, ']') if top == Some(Container::Array) => {
+                    self.stack.pop();
+                    self.after_value();
+                    self.consume(1);
+                    JsonEvent::ArrayEnd
+                }
+                (Expect::FirstKey | Expect::Comma, '}') if top == Some(Container::Object) => {
+                    self.stack.pop();
+                    self.after_value();
+                    self.consume(1);
+                    JsonEvent::ObjectEnd
+                }
+                (Expect::Colon, ':') => {
+                    self.expect = Expect::Value;
+                    self.consume(1);
+                    continue;
+                }
+                (Expect::Comma, ',') => {
+                    self.expect = if top == Some(Container::Object) {Expect::Key} else {Expect::Value};
+                    self.consume(1);
+                    continue;
+                }
+                (Expect::FirstKey | Expect::Key, '"') => match lex_string(rest, &mut self.token) {
+                    Lex::Done(key, len) => {
+                        self.expect = Expect::Colon;
+                        self.consume(len);
+                        JsonEvent::Key(key)
+                    }
+                    Lex::NeedMore => return self.need_more(),
+                    Lex::Err(msg) => return Some(Err(msg))
+                }
+                (Expect::Value | Expect::FirstValue, _) => {
+                    let lexed = match c {
+                        '"' => match lex_string(rest, &mut self.token) {
+                            Lex::Done(v, len) => Lex::Done(JsonEvent::String(v), len),
+                            Lex::NeedMore => Lex::NeedMore,
+                            Lex::Err(msg) => Lex::Err(msg)
+                        },
+                        '-' | '0'..='9' => lex_number(rest, self.finished, &mut self.token),
+                        't' => lex_literal(rest, "true", JsonEvent::Bool(true)),
+                        'f' => lex_literal(rest, "false", JsonEvent::Bool(false)),
+                        'n' => lex_literal(rest, "null", JsonEvent::Null),
+                        _ => Lex::Err(format!("Unexpected {:?}, expected a value", c))
+                    };
+                    match lexed {
+                        Lex::Done(event, len) => {
+                            self.after_value();
+                            self.consume(len);
+                            event
+                        }
+                        Lex::NeedMore => return self.need_more(),
+                        Lex::Err(msg) => return Some(Err(msg))
+                    }
+                }
+                (expect, c) => {
+                    let what = match expect {
+                        Expect::FirstKey | Expect::Key => "a key",
+                        Expect::Colon => ":",
+                        _ => ", or a closing bracket"
+                    };
+                    return Some(Err(format!("Unexpected {:?}, expected {}", c, what)))
+                }
+            };
+            return Some(Ok(event))
+        }
+    }
+
+    fn need_more(&self) -> Option<Result<JsonEvent, String>> {
+        if self.finished {
+            return Some(Err("Unexpected end of input".to_string()))
+        }
+        None
+    }
+}
+
+fn lex_literal(rest: &str, literal: &str, event: JsonEvent) -> Lex<JsonEvent> {
+    if rest.starts_with(literal) {
+        return Lex::Done(event, literal.len())
+    }
+    if literal.starts_with(rest) {
+        return Lex::NeedMore
+    }
+    Lex::Err(format!("Unexpected identifier, expected {}", literal))
+}
+
+fn lex_number(rest: &str, finished: bool, token: &mut PartialToken) -> Lex<JsonEvent> {
+    let len = rest[token.len..].find( | c: char | !matches!(c, '0'..='9' | '-' | '+' | '.' | 'e' | 'E')).map( | len | token.len + len).unwrap_or(rest.len());
+    // more digits can follow in the next chunk
+    if len == rest.len() && !finished {
+        token.len = len;
+        return Lex::NeedMore
+    }
+    token.len = 0;
+    let num = &rest[..len];
+    let event = if num.contains(['.', 'e', 'E']) {
+        num.parse().ok().map(JsonEvent::F64)
+    }
+    else if num.starts_with('-') {
+        num.parse().ok().map(JsonEvent::I64).or_else( | | num.parse().ok().map(JsonEvent::F64))
+    }
+    else {
+        num.parse().ok().map(JsonEvent::U64).or_else( | | num.parse().ok().map(JsonEvent::F64))
+    };
+    match event {
+        Some(event) => Lex::Done(event, len),
+        None => Lex::Err(format!("Cannot parse number {}", num))
+    }
+}
+
+fn lex_string(rest: &str, token: &mut PartialToken) -> Lex<String> {
+    // past the opening quote
+    let mut pos = token.len.max(1);
+    while let Some(c) = rest[pos..].chars().next() {
+        match c {
+            '"' => {
+                token.len = 0;
+                return Lex::Done(std::mem::take(&mut token.out), pos + 1)
+            }
+            '\\' => match lex_escape(&rest[pos..]) {
+                Lex::Done(c, len) => {
+                    token.out.push(c);
+                    pos += len;
+                }
+                // an escape split over two chunks is lexed again from its backslash
+                Lex::NeedMore => break,
+                Lex::Err(msg) => return Lex::Err(msg)
+            },
+            c => {
+                token.out.push(c);
+                pos += c.len_utf8();
+            }
+        }
+    }
+    token.len = pos;
+    Lex::NeedMore
+}
+
+// rest starts at the backslash
+fn lex_escape(rest: &str) -> Lex<char> {
+    let c = match rest[1..].chars().next() {
+        Some('n') => '\n',
+        Some('r') => '\r',
+        Some('t') => '\t',
+        Some('b') => '\u{8}',
+        Some('f') => '\u{c}',
+        Some('0') => '\0',
+        Some('u') => return lex_unicode_escape(rest),
+        Some(c) => return Lex::Done(c, 1 + c.len_utf8()),
+        None => return Lex::NeedMore
+    };
+    Lex::Done(c, 2)
+}
+
+fn lex_unicode_escape(rest: &str) -> Lex<char> {
+    let code = match lex_hex4(rest, 2) {
+        Lex::Done(code, _) => code,
+        Lex::NeedMore => return Lex::NeedMore,
+        Lex::Err(msg) => return Lex::Err(msg)
+    };
+    if !(0xd800..0xdc00).contains(&code) {
+        return Lex::Done(char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER), 6)
+    }
+    // a high surrogate needs the low surrogate escape that follows it
+    if !rest[6..].starts_with("\\u") {
+        if "\\u".starts_with(&rest[6..]) {
+            return Lex::NeedMore
+        }
+        return Lex::Err("Invalid surrogate pair".to_string())
+    }
+    match lex_hex4(rest, 8) {
+        Lex::Done(low, _) if (0xdc00..0xe000).contains(&low) => {
+            Lex::Done(char::from_u32(0x10000 + ((code - 0xd800) << 10) + (low - 0xdc00)).unwrap(), 12)
+        }
+        Lex::Done(_, _) => Lex::Err("Invalid surrogate pair".to_string()),
+        Lex::NeedMore => Lex::NeedMore,
+        Lex::Err(msg) => Lex::Err(msg)
+    }
+}
+
+fn lex_hex4(rest: &str, at: usize) -> Lex<u32> {
+    if rest.len() < at + 4 {
+        return Lex::NeedMore
+    }
+    match rest.get(at..at + 4).and_then( | hex | u32::from_str_radix(hex, 16).ok()) {
+        Some(code) => Lex::Done(code, at + 4),
+        None => Lex::Err(format!("Invalid unicode escape {}", rest.chars().skip(at).take(4).collect::<String>()))
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SseEvent {
+    pub event: Option<String>,
+    pub id: Option<String>,
+    pub data: String,
+}
+
+impl SseEvent {
+    pub fn json(&self) -> Result<JsonValue, DeJsonErr> {
+        JsonValue::deserialize_json(&self.data)
+    }
+
+    pub fn de_json<T: DeJson>(&self) -> Result<T, DeJsonErr> {
+        T::deserialize_json(&self.data)
+    }
+}
+
+// Splits a text/event-stream into events, the way llm apis stream their tokens.
+// The data of an event is usually json, see SseEvent::json.
+#[derive(Default)]
+pub struct SseParser {
+    decoder: Utf8Decoder,
+    buf: String,
+    // how much of buf is known to hold no line end, a long line isn't searched again on every push
+    scanned: usize,
+    skip_lf: bool,
+    event: SseEvent,
+    has_data: bool,
+}
+
+impl SseParser {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, chunk: &[u8]) {
+        self.decoder.decode(chunk, &mut self.buf);
+    }
+
+    pub fn push_str(&mut self, chunk: &str) {
+        self.push(chunk.as_bytes());
+    }
+
+    pub fn next_event(&mut self) -> Option<SseEvent> {
+        loop {
+            // lines end in \n, \r\n or \r, and a chunk can end between the \r and the \n
+            if self.skip_lf && !self.buf.is_empty() {
+                if self.buf.starts_with('\n') {
+                    self.buf.remove(0);
+                }
+                self.skip_lf = false;
+            }
+            let Some(end) = self.buf[self.scanned..].find(['\n', '\r']) else {
+                self.scanned = self.buf.len();
+                return None
+            };
+            let end = self.scanned + end;
+            self.scanned = 0;
+            self.skip_lf = self.buf[end..].starts_with('\r');
+            let line: String = self.buf.drain(..end + 1).collect();
+            let line = &line[..end];
+            if line.is_empty() {
+                if self.has_data {
+                    self.has_data = false;
+                    let mut event = std::mem::take(&mut self.event);
+                    if event.data.ends_with('\n') {
+                        event.data.pop();
+                    }
+                    return Some(event)
+                }
+                self.event = SseEvent::default();
+                continue;
+            }
+            if line.starts_with(':') { // comment, used as keep alive
+                continue;
+            }
+            let (field, value) = match line.split_once(':') {
+                Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+                None => (line, "")
+            };
+            match field {
+                "data" => {
+                    self.event.data.push_str(value);
+                    self.event.data.push('\n');
+                    self.has_data = true;
+                }
+                "event" => self.event.event = Some(value.to_string()),
+                "id" => self.event.id = Some(value.to_string()),
+                _ => ()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values_from_chunks(chunks: &[&[u8]]) -> Vec<Result<JsonValue, DeJsonErr>> {
+        let mut parser = JsonStreamParser::new();
+        let mut out = Vec::new();
+        for chunk in chunks {
+            parser.push(chunk);
+            while let Some(value) = parser.next_value() {
+                out.push(value);
+            }
+        }
+        parser.finish();
+        while let Some(value) = parser.next_value() {
+            out.push(value);
+        }
+        out
+    }
+
+    // every way of cutting the input in two gives the same values as parsing it whole
+    fn assert_split_anywhere(json: &str, expected: &[JsonValue]) {
+        let bytes = json.as_bytes();
+        for split in 0..=bytes.len() {
+            let values: Vec<JsonValue> = values_from_chunks(&[&bytes[..split], &bytes[split..]]).into_iter().map( | v | v.unwrap()).collect();
+            assert_eq!(values, expected, "split at {} of {:?}", split, json);
+        }
+        let bytes: Vec<&[u8]> = bytes.chunks(1).collect();
+        let values: Vec<JsonValue> = values_from_chunks(&bytes).into_iter().map( | v | v.unwrap()).collect();
+        assert_eq!(values, expected, "byte by byte {:?}", json);
+    }
+
+    fn string(v: &str) -> JsonValue {
+        JsonValue::String(v.to_string())
+    }
+
+    #[test]
+    fn strings_split_anywhere() {
+        assert_split_anywhere(r#""hello world""#, &[string("hello world")]);
+        assert_split_anywhere(r#"["a","","bc"]"#, &[JsonValue::Array(vec![string("a"), string(""), string("bc")])]);
+        assert_split_anywhere("\"h\u{e9}llo \u{1f600}\"", &[string("h\u{e9}llo \u{1f600}")]);
+        assert_split_anywhere(r#"{"key with \"quotes\"":"v"}"#, &[JsonValue::deserialize_json(r#"{"key with \"quotes\"":"v"}"#).unwrap()]);
+    }
+
+    #[test]
+    fn escapes_split_anywhere() {
+        assert_split_anywhere(r#""a\nb\tc\\d\/e\"f\r\b\f""#, &[string("a\nb\tc\\d/e\"f\r\u{8}\u{c}")]);
+        assert_split_anywhere(r#""é中""#, &[string("\u{e9}\u{4e2d}")]);
+        // a surrogate pair is one character
+        assert_split_anywhere(r#""x😀y""#, &[string("x\u{1f600}y")]);
+    }
+
+    #[test]
+    fn bad_escapes() {
+        let values = values_from_chunks(&[br#""\ud83dx" 1"#]);
+        assert!(values[0].is_err());
+        let values = values_from_chunks(&[br#""\ud83dA""#]);
+        assert!(values[0].is_err());
+        let values = values_from_chunks(&[br#""\uzz12""#]);
+        assert!(values[0].is_err());
+    }
+
+    #[test]
+    fn numbers_split_anywhere() {
+        assert_split_anywhere("[12345,-678,3.25,1e3,-2.5E-2]", &[JsonValue::Array(vec![
+            JsonValue::U64(12345),
+            JsonValue::I64(-678),
+            JsonValue::F64(3.25),
+            JsonValue::F64(1000.0),
+            JsonValue::F64(-0.025),
+        ])]);
+        assert_split_anywhere("18446744073709551615 -9", &[JsonValue::U64(u64::MAX), JsonValue::I64(-9)]);
+    }
+
+    #[test]
+    fn number_at_the_end_waits_for_finish() {
+        let mut parser = JsonStreamParser::new();
+        parser.push_str("12");
+        assert!(parser.next_value().is_none());
+        parser.push_str("34");
+        assert!(parser.next_value().is_none());
+        parser.finish();
+        assert_eq!(parser.next_value().unwrap().unwrap(), JsonValue::U64(1234));
+    }
+
+    #[test]
+    fn long_string_is_lexed_once() {
+        let mut parser = JsonStreamParser::new();
+        parser.push_str("\"");
+        for _ in 0..1000 {
+            parser.push_str("abcd");
+            assert!(parser.next_event().is_none());
+        }
+        // everything pushed so far is decoded and won't be looked at again
+        assert_eq!(parser.token.len, 4001);
+        assert_eq!(parser.token.out.len(), 4000);
+        parser.push_str("\"");
+        assert_eq!(parser.next_event().unwrap().unwrap(), JsonEvent::String("abcd".repeat(1000)));
+        assert_eq!(parser.token.len, 0);
+    }
+
+    #[test]
+    fn utf8_split_inside_a_character() {
+        let json = "\"\u{1f600}\"".as_bytes();
+        for split in 1..json.len() {
+            let values = values_from_chunks(&[&json[..split], &json[split..]]);
+            assert_eq!(values[0].as_ref().unwrap(), &string("\u{1f600}"));
+        }
+    }
+
+    #[test]
+    fn newline_delimited_values_and_recovery() {
+        let values = values_from_chunks(&[b"{\"a\":1}\n{\"a\":}\n[true,null]\n"]);
+        assert_eq!(values.len(), 3);
+        assert_eq!(values[0].as_ref().unwrap()["a"].as_u64(), Some(1));
+        // a broken line is reported and skipped
+        assert!(values[1].is_err());
+        assert_eq!(values[2].as_ref().unwrap(), &JsonValue::Array(vec![JsonValue::Bool(true), JsonValue::Null]));
+    }
+
+    #[test]
+    fn finish_on_truncated_input() {
+        for truncated in [r#"{"a":1"#, r#"["abc"#, r#""abc"#, r#"{"key"#, r#""\u00"#, r#""\ud83d"#, "tru", "[1,"] {
+            let values = values_from_chunks(&[truncated.as_bytes()]);
+            assert_eq!(values.len(), 1, "{:?}", truncated);
+            assert!(values[0].is_err(), "{:?}", truncated);
+        }
+        // nothing at all is not an error
+        assert!(values_from_chunks(&[b"  \n"]).is_empty());
+    }
+
+    #[test]
+    fn events() {
+        let mut parser = JsonStreamParser::new();
+        parser.push_str(r#"{"a":[1,"x"]}"#);
+        let mut events = Vec::new();
+        while let Some(event) = parser.next_event() {
+            events.push(event.unwrap());
+        }
+        assert_eq!(events, vec![
+            JsonEvent::ObjectStart,
+            JsonEvent::Key("a".to_string()),
+            JsonEvent::ArrayStart,
+            JsonEvent::U64(1),
+            JsonEvent::String("x".to_string()),
+            JsonEvent::ArrayEnd,
+            JsonEvent::ObjectEnd,
+        ]);
+        assert!(parser.is_idle());
+    }
+
+    fn sse_events(chunks: &[&str]) -> Vec<SseEvent> {
+        let mut parser = SseParser::new();
+        let mut out = Vec::new();
+        for chunk in chunks {
+            parser.push_str(chunk);
+            while let Some(event) = parser.next_event() {
+                out.push(event);
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn sse_multi_line_data() {
+        let events = sse_events(&["data: first\ndata: second\n\ndata:third\n\n"]);
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].data, "first\nsecond");
+        assert_eq!(events[1].data, "third");
+    }
+
+    #[test]
+    fn sse_comments_and_fields() {
+        let events = sse_events(&[": keep alive\n\nevent: delta\nid: 7\n: in between\ndata: {\"t\":1}\nretry: 10\n\n"]);
+        assert_eq!(events, vec![SseEvent {
+            event: Some("delta".to_string()),
+            id: Some("7".to_string()),
+            data: "{\"t\":1}".to_string(),
+        }]);
+        assert_eq!(events[0].json().unwrap()["t"].as_u64(), Some(1));
+    }
+
+    #[test]
+    fn sse_line_endings() {
+        let expected = vec!["a".to_string(), "b\nc".to_string()];
+        for text in ["data: a\r\n\r\ndata: b\r\ndata: c\r\n\r\n", "data: a\r\rdata: b\rdata: c\r\r", "data: a\n\ndata: b\r\ndata: c\r\r"] {
+            // also with the stream cut between every two characters, including a \r and its \n
+            for split in 0..=text.len() {
+                let events = sse_events(&[&text[..split], &text[split..]]);
+                let data: Vec<String> = events.into_iter().map( | e | e.data).collect();
+                assert_eq!(data, expected, "split at {} of {:?}", split, text);
+            }
+        }
+    }
+
+    #[test]
+    fn sse_event_without_data_is_dropped() {
+        let events = sse_events(&["event: ping\n\ndata: x\n\n"]);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event, None);
+        assert_eq!(events[0].data, "x");
+    }
+}
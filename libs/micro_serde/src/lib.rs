@@ -6,5 +6,8 @@ pub use crate::serde_bin::*;
 mod serde_json;
 pub use crate::serde_json::*;
 
+mod json_stream;
+pub use crate::json_stream::*;
+
 mod serde_ron;
 pub use crate::serde_ron::*;
\ No newline at end of file
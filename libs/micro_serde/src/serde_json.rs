@@ -1,4 +1,5 @@
 use std::collections::{HashMap};
+use std::ops::{Index, IndexMut};
 use std::hash::Hash;
 use std::str::Chars;
 
@@ -31,6 +32,27 @@ impl SerJsonState {
         self.out.push(',')
     }
     
+    // a comma between fields, but not in front of the first one
+    pub fn field_sep(&mut self) {
+        if !self.out.ends_with('{') {
+            self.conl();
+        }
+    }
+    
+    // writes the fields of value into the object that is being written, for #[flatten]
+    pub fn flat<T: SerJson + ?Sized>(&mut self, d: usize, value: &T) {
+        let mut s = SerJsonState {
+            out: String::new()
+        };
+        value.ser_json(d, &mut s);
+        if let Some(fields) = s.out.strip_prefix('{').and_then( | v | v.strip_suffix('}')) {
+            if !fields.is_empty() {
+                self.field_sep();
+                self.out.push_str(fields);
+            }
+        }
+    }
+    
     pub fn st_pre(&mut self) {
         self.out.push('{');
     }
@@ -236,6 +258,56 @@ impl DeJsonState {
         }
     }
     
+    // copies the key and value at the cursor to out as json, so the fields of
+    // #[flatten] and tagged enums can be parsed once the whole object is read
+    pub fn capture_field(&mut self, i: &mut Chars, out: &mut SerJsonState) -> Result<(), DeJsonErr> {
+        out.field_sep();
+        self.strbuf.ser_json(0, out);
+        out.out.push(':');
+        self.next_colon(i) ?;
+        self.capture_value(i, out)
+    }
+    
+    pub fn capture_value(&mut self, i: &mut Chars, out: &mut SerJsonState) -> Result<(), DeJsonErr> {
+        let mut depth = 0;
+        loop {
+            match self.tok {
+                DeJsonTok::Str => self.strbuf.ser_json(0, out),
+                DeJsonTok::U64(_) | DeJsonTok::I64(_) | DeJsonTok::F64(_) => out.out.push_str(&self.numbuf),
+                DeJsonTok::Bool(v) => v.ser_json(0, out),
+                DeJsonTok::Null => out.out.push_str("null"),
+                DeJsonTok::Colon => out.out.push(':'),
+                DeJsonTok::Comma => out.out.push(','),
+                DeJsonTok::CurlyOpen | DeJsonTok::BlockOpen => {
+                    out.out.push(if self.tok == DeJsonTok::CurlyOpen {'{'} else {'['});
+                    depth += 1;
+                }
+                DeJsonTok::CurlyClose | DeJsonTok::BlockClose if depth > 0 => {
+                    out.out.push(if self.tok == DeJsonTok::CurlyClose {'}'} else {']'});
+                    depth -= 1;
+                }
+                _ => return Err(self.err_token("value"))
+            }
+            self.next_tok(i) ?;
+            if depth == 0 {
+                return Ok(())
+            }
+        }
+    }
+    
+    // parses json that was copied with capture_field, errors point at the current position
+    pub fn de_captured<T, F>(&self, json: &str, f: F) -> Result<T, DeJsonErr>
+    where F: FnOnce(&mut DeJsonState, &mut Chars) -> Result<T, DeJsonErr> {
+        let mut state = DeJsonState::default();
+        let mut chars = json.chars();
+        state.next(&mut chars);
+        state.next_tok(&mut chars).and_then( | _ | f(&mut state, &mut chars)).map_err( | e | DeJsonErr {
+            msg: e.msg,
+            line: self.line,
+            col: self.col
+        })
+    }
+    
     pub fn block_open(&mut self, i: &mut Chars) -> Result<(), DeJsonErr> {
         if self.tok == DeJsonTok::BlockOpen {
             self.next_tok(i) ?;
@@ -652,7 +724,7 @@ impl<T> DeJson for Vec<T> where T: DeJson {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum JsonValue{
     String(String),
     Char(char),
@@ -667,24 +739,277 @@ pub enum JsonValue{
     Array(Vec<JsonValue>)
 }
 
+static JSON_NULL: JsonValue = JsonValue::Null;
+
 impl JsonValue{
-    pub fn object(&self)->&HashMap<String, JsonValue>{
-        if let JsonValue::Object(obj) = self{
-            return obj
+    pub fn as_object(&self) -> Option<&HashMap<String, JsonValue>> {
+        if let JsonValue::Object(obj) = self {Some(obj)} else {None}
+    }
+    
+    pub fn as_object_mut(&mut self) -> Option<&mut HashMap<String, JsonValue>> {
+        if let JsonValue::Object(obj) = self {Some(obj)} else {None}
+    }
+    
+    pub fn as_array(&self) -> Option<&Vec<JsonValue>> {
+        if let JsonValue::Array(arr) = self {Some(arr)} else {None}
+    }
+    
+    pub fn as_array_mut(&mut self) -> Option<&mut Vec<JsonValue>> {
+        if let JsonValue::Array(arr) = self {Some(arr)} else {None}
+    }
+    
+    pub fn as_str(&self) -> Option<&str> {
+        if let JsonValue::String(v) = self {Some(v)} else {None}
+    }
+    
+    pub fn as_char(&self) -> Option<char> {
+        if let JsonValue::Char(v) = self {Some(*v)} else {None}
+    }
+    
+    pub fn as_bare_ident(&self) -> Option<&str> {
+        if let JsonValue::BareIdent(v) = self {Some(v)} else {None}
+    }
+    
+    // integers convert between u64 and i64 when they fit
+    pub fn as_u64(&self) -> Option<u64> {
+        match self {
+            JsonValue::U64(v) => Some(*v),
+            JsonValue::I64(v) => u64::try_from(*v).ok(),
+            _ => None
+        }
+    }
+    
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            JsonValue::I64(v) => Some(*v),
+            JsonValue::U64(v) => i64::try_from(*v).ok(),
+            _ => None
+        }
+    }
+    
+    // any number, json doesn't tell 1.0 and 1 apart
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            JsonValue::F64(v) => Some(*v),
+            JsonValue::U64(v) => Some(*v as f64),
+            JsonValue::I64(v) => Some(*v as f64),
+            _ => None
+        }
+    }
+    
+    pub fn as_bool(&self) -> Option<bool> {
+        if let JsonValue::Bool(v) = self {Some(*v)} else {None}
+    }
+    
+    pub fn is_null(&self) -> bool {
+        matches!(self, JsonValue::Null)
+    }
+    
+    pub fn is_undefined(&self) -> bool {
+        matches!(self, JsonValue::Undefined)
+    }
+    
+    // an object key (&str) or an array index (usize)
+    pub fn get<I: JsonIndex>(&self, index: I) -> Option<&JsonValue> {
+        index.index_into(self)
+    }
+    
+    pub fn get_mut<I: JsonIndex>(&mut self, index: I) -> Option<&mut JsonValue> {
+        index.index_into_mut(self)
+    }
+    
+    // looks up a json pointer like "/choices/0/message", ~1 escapes / and ~0 escapes ~
+    pub fn pointer(&self, pointer: &str) -> Option<&JsonValue> {
+        if pointer.is_empty() {
+            return Some(self)
+        }
+        let mut target = self;
+        for token in pointer.strip_prefix('/')?.split('/') {
+            target = match target {
+                JsonValue::Object(obj) => obj.get(&unescape_pointer(token))?,
+                JsonValue::Array(arr) => arr.get(pointer_index(token)?)?,
+                _ => return None
+            };
+        }
+        Some(target)
+    }
+    
+    pub fn pointer_mut(&mut self, pointer: &str) -> Option<&mut JsonValue> {
+        if pointer.is_empty() {
+            return Some(self)
+        }
+        let mut target = self;
+        for token in pointer.strip_prefix('/')?.split('/') {
+            target = match target {
+                JsonValue::Object(obj) => obj.get_mut(&unescape_pointer(token))?,
+                JsonValue::Array(arr) => arr.get_mut(pointer_index(token)?)?,
+                _ => return None
+            };
         }
-        panic!()
+        Some(target)
     }
-    pub fn string(&self)->&String{
-        if let JsonValue::String(obj) = self{
-            return obj
+    
+    // goes through the json text, so T sees exactly what it would see on the wire
+    pub fn from_ser_json<T: SerJson + ?Sized>(value: &T) -> Result<JsonValue, DeJsonErr> {
+        JsonValue::deserialize_json(&value.serialize_json())
+    }
+    
+    pub fn to_de_json<T: DeJson>(&self) -> Result<T, DeJsonErr> {
+        T::deserialize_json(&self.serialize_json())
+    }
+    
+    #[deprecated(note = "panics on anything but an object, use as_object")]
+    pub fn object(&self) -> &HashMap<String, JsonValue> {
+        self.as_object().expect("JsonValue is not an object")
+    }
+    
+    #[deprecated(note = "panics on anything but a string, use as_str")]
+    pub fn string(&self) -> &String {
+        if let JsonValue::String(v) = self {v} else {panic!("JsonValue is not a string")}
+    }
+    
+    #[deprecated(note = "panics on a missing key, use get or indexing")]
+    pub fn key(&self, key: &str) -> &JsonValue {
+        self.as_object().expect("JsonValue is not an object").get(key).expect("JsonValue has no such key")
+    }
+}
+
+fn unescape_pointer(token: &str) -> String {
+    token.replace("~1", "/").replace("~0", "~")
+}
+
+fn pointer_index(token: &str) -> Option<usize> {
+    // json pointers don't allow leading zeros or signs in array indices
+    if token.is_empty() || token.len() > 1 && token.starts_with('0') || !token.bytes().all( | b | b.is_ascii_digit()) {
+        return None
+    }
+    token.parse().ok()
+}
+
+pub trait JsonIndex {
+    fn index_into<'a>(&self, value: &'a JsonValue) -> Option<&'a JsonValue>;
+    fn index_into_mut<'a>(&self, value: &'a mut JsonValue) -> Option<&'a mut JsonValue>;
+    fn index_or_insert<'a>(&self, value: &'a mut JsonValue) -> &'a mut JsonValue;
+}
+
+impl JsonIndex for usize {
+    fn index_into<'a>(&self, value: &'a JsonValue) -> Option<&'a JsonValue> {
+        value.as_array()?.get(*self)
+    }
+    
+    fn index_into_mut<'a>(&self, value: &'a mut JsonValue) -> Option<&'a mut JsonValue> {
+        value.as_array_mut()?.get_mut(*self)
+    }
+    
+    fn index_or_insert<'a>(&self, value: &'a mut JsonValue) -> &'a mut JsonValue {
+        match value {
+            JsonValue::Array(arr) => {
+                let len = arr.len();
+                arr.get_mut(*self).unwrap_or_else( | | panic!("JsonValue index {} out of bounds for array of length {}", self, len))
+            }
+            _ => panic!("JsonValue can't be indexed with {}, it is not an array", self)
         }
-        panic!()
     }
-    pub fn key(&self, key:&str)->&JsonValue{
-        if let JsonValue::Object(obj) = self{
-            return obj.get(key).unwrap()
+}
+
+impl JsonIndex for str {
+    fn index_into<'a>(&self, value: &'a JsonValue) -> Option<&'a JsonValue> {
+        value.as_object()?.get(self)
+    }
+    
+    fn index_into_mut<'a>(&self, value: &'a mut JsonValue) -> Option<&'a mut JsonValue> {
+        value.as_object_mut()?.get_mut(self)
+    }
+    
+    // assigning to a key of null turns it into an object, like a missing key would
+    fn index_or_insert<'a>(&self, value: &'a mut JsonValue) -> &'a mut JsonValue {
+        if value.is_null() || value.is_undefined() {
+            *value = JsonValue::Object(HashMap::new());
+        }
+        match value {
+            JsonValue::Object(obj) => obj.entry(self.to_string()).or_insert(JsonValue::Null),
+            _ => panic!("JsonValue can't be indexed with {:?}, it is not an object", self)
+        }
+    }
+}
+
+impl JsonIndex for String {
+    fn index_into<'a>(&self, value: &'a JsonValue) -> Option<&'a JsonValue> {
+        self.as_str().index_into(value)
+    }
+    
+    fn index_into_mut<'a>(&self, value: &'a mut JsonValue) -> Option<&'a mut JsonValue> {
+        self.as_str().index_into_mut(value)
+    }
+    
+    fn index_or_insert<'a>(&self, value: &'a mut JsonValue) -> &'a mut JsonValue {
+        self.as_str().index_or_insert(value)
+    }
+}
+
+impl<T> JsonIndex for &T where T: JsonIndex + ?Sized {
+    fn index_into<'a>(&self, value: &'a JsonValue) -> Option<&'a JsonValue> {
+        (**self).index_into(value)
+    }
+    
+    fn index_into_mut<'a>(&self, value: &'a mut JsonValue) -> Option<&'a mut JsonValue> {
+        (**self).index_into_mut(value)
+    }
+    
+    fn index_or_insert<'a>(&self, value: &'a mut JsonValue) -> &'a mut JsonValue {
+        (**self).index_or_insert(value)
+    }
+}
+
+// reading a missing key or index gives null instead of panicking
+impl<I> Index<I> for JsonValue where I: JsonIndex {
+    type Output = JsonValue;
+    fn index(&self, index: I) -> &JsonValue {
+        index.index_into(self).unwrap_or(&JSON_NULL)
+    }
+}
+
+impl<I> IndexMut<I> for JsonValue where I: JsonIndex {
+    fn index_mut(&mut self, index: I) -> &mut JsonValue {
+        index.index_or_insert(self)
+    }
+}
+
+impl SerJson for JsonValue {
+    fn ser_json(&self, d: usize, s: &mut SerJsonState) {
+        match self {
+            JsonValue::String(v) => v.ser_json(d, s),
+            JsonValue::Char(v) => v.to_string().ser_json(d, s),
+            JsonValue::U64(v) => v.ser_json(d, s),
+            JsonValue::I64(v) => v.ser_json(d, s),
+            JsonValue::F64(v) => {
+                // keep the fraction so the value reads back as a float
+                if !v.is_finite() {
+                    s.out.push_str("null");
+                }
+                else if v.fract() == 0.0 {
+                    s.out.push_str(&format!("{:.1}", v));
+                }
+                else {
+                    v.ser_json(d, s);
+                }
+            }
+            JsonValue::Bool(v) => v.ser_json(d, s),
+            JsonValue::BareIdent(v) => v.ser_json(d, s),
+            JsonValue::Null | JsonValue::Undefined => s.out.push_str("null"),
+            JsonValue::Object(obj) => {
+                s.st_pre();
+                for (key, value) in obj {
+                    s.field_sep();
+                    s.indent(d + 1);
+                    key.ser_json(d + 1, s);
+                    s.out.push(':');
+                    value.ser_json(d + 1, s);
+                }
+                s.st_post(d);
+            }
+            JsonValue::Array(arr) => arr.ser_json(d, s),
         }
-        panic!()
     }
 }
 
@@ -23,6 +23,17 @@ impl SerRonState {
         self.out.push_str(",\n")
     }
     
+    // writes the fields of value into the struct that is being written, for #[flatten]
+    pub fn flat<T: SerRon + ?Sized>(&mut self, d: usize, value: &T) {
+        let mut s = SerRonState {
+            out: String::new()
+        };
+        value.ser_ron(d, &mut s);
+        if let Some(fields) = s.out.strip_prefix("(\n").and_then( | v | v.strip_suffix(')')) {
+            self.out.push_str(fields.trim_end_matches(' '));
+        }
+    }
+    
     pub fn st_pre(&mut self) {
         self.out.push_str("(\n");
     }
@@ -239,6 +250,67 @@ impl DeRonState {
         }
     }
     
+    // copies the key and value at the cursor to out as ron, so the fields of
+    // #[flatten] and tagged enums can be parsed once the whole struct is read
+    pub fn capture_field(&mut self, i: &mut Chars, out: &mut SerRonState) -> Result<(), DeRonErr> {
+        out.out.push_str(&self.identbuf);
+        out.out.push(':');
+        self.next_colon(i) ?;
+        self.capture_value(i, out) ?;
+        out.conl();
+        Ok(())
+    }
+    
+    pub fn capture_value(&mut self, i: &mut Chars, out: &mut SerRonState) -> Result<(), DeRonErr> {
+        let mut depth = 0;
+        loop {
+            match self.tok {
+                DeRonTok::Ident => out.out.push_str(&self.identbuf),
+                DeRonTok::Str => self.strbuf.ser_ron(0, out),
+                DeRonTok::U64(_) | DeRonTok::I64(_) | DeRonTok::F64(_) => out.out.push_str(&self.numbuf),
+                DeRonTok::Bool(v) => v.ser_ron(0, out),
+                DeRonTok::Char(c) => {
+                    out.out.push('\'');
+                    if c == '\'' || c == '\\' {
+                        out.out.push('\\');
+                    }
+                    out.out.push(c);
+                    out.out.push('\'');
+                }
+                DeRonTok::Colon => out.out.push(':'),
+                DeRonTok::Comma => out.out.push(','),
+                DeRonTok::ParenOpen | DeRonTok::BlockOpen | DeRonTok::CurlyOpen => {
+                    out.out.push(match self.tok {DeRonTok::ParenOpen => '(', DeRonTok::BlockOpen => '[', _ => '{'});
+                    depth += 1;
+                }
+                DeRonTok::ParenClose | DeRonTok::BlockClose | DeRonTok::CurlyClose if depth > 0 => {
+                    out.out.push(match self.tok {DeRonTok::ParenClose => ')', DeRonTok::BlockClose => ']', _ => '}'});
+                    depth -= 1;
+                }
+                _ => return Err(self.err_token("value"))
+            }
+            let was_ident = self.tok == DeRonTok::Ident;
+            self.next_tok(i) ?;
+            // an identifier followed by parens is an enum variant or a named struct
+            if depth == 0 && !(was_ident && self.tok == DeRonTok::ParenOpen) {
+                return Ok(())
+            }
+        }
+    }
+    
+    // parses ron that was copied with capture_field, errors point at the current position
+    pub fn de_captured<T, F>(&self, ron: &str, f: F) -> Result<T, DeRonErr>
+    where F: FnOnce(&mut DeRonState, &mut Chars) -> Result<T, DeRonErr> {
+        let mut state = DeRonState::default();
+        let mut chars = ron.chars();
+        state.next(&mut chars);
+        state.next_tok(&mut chars).and_then( | _ | f(&mut state, &mut chars)).map_err( | e | DeRonErr {
+            msg: e.msg,
+            line: self.line,
+            col: self.col
+        })
+    }
+    
     pub fn paren_open(&mut self, i: &mut Chars) -> Result<(), DeRonErr> {
         if self.tok == DeRonTok::ParenOpen {
             self.next_tok(i) ?;
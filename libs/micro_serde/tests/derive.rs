@@ -0,0 +1,204 @@
+// Round trips through the derived serializers, one attribute and format at a time.
+
+use {
+    std::collections::HashMap,
+    makepad_micro_serde::*,
+};
+
+#[derive(SerJson, DeJson, SerRon, DeRon, SerBin, DeBin, Debug, PartialEq)]
+struct Renamed {
+    #[rename("maxTokens")] max_tokens: u32,
+    name: String,
+}
+
+#[derive(SerJson, DeJson, SerRon, DeRon, Debug, PartialEq)]
+struct Defaults {
+    id: u32,
+    #[default_on_missing(1.5)] temperature: f64,
+    #[default] tags: Vec<String>,
+    note: Option<String>,
+}
+
+#[derive(SerJson, DeJson, SerRon, DeRon, SerBin, DeBin, Debug, PartialEq)]
+struct Skipped {
+    id: u32,
+    #[skip] cache: Vec<u32>,
+    #[skip] #[default(7)] retries: u32,
+    name: String,
+}
+
+#[derive(SerBin, DeBin, Debug, PartialEq)]
+struct NotSkipped {
+    id: u32,
+    name: String,
+}
+
+#[derive(SerJson, DeJson, SerRon, DeRon, Debug, PartialEq)]
+struct Inner {
+    width: u32,
+    height: u32,
+}
+
+#[derive(SerJson, DeJson, SerRon, DeRon, Debug, PartialEq)]
+struct Outer {
+    name: String,
+    #[flatten] size: Inner,
+}
+
+#[derive(SerJson, DeJson, Debug, PartialEq)]
+struct WithExtra {
+    id: u32,
+    #[flatten] extra: HashMap<String, String>,
+}
+
+#[derive(SerJson, DeJson, SerRon, DeRon, Debug, PartialEq)]
+#[tag("type")]
+enum Internal {
+    #[rename("text")] Text {text: String},
+    #[rename("image")] Image {url: String, width: Option<u32>},
+    Empty,
+}
+
+#[derive(SerJson, DeJson, SerRon, DeRon, Debug, PartialEq)]
+#[tag("t")]
+#[content("c")]
+enum Adjacent {
+    Point {x: i32, y: i32},
+    Pair(u32, String),
+    Single(String),
+    Empty,
+}
+
+fn json_round_trip<T: SerJson + DeJson + PartialEq + std::fmt::Debug>(value: &T) -> String {
+    let json = value.serialize_json();
+    let back = T::deserialize_json(&json).unwrap_or_else( | e | panic!("{} in {}", e.msg, json));
+    assert_eq!(&back, value, "json: {}", json);
+    json
+}
+
+fn ron_round_trip<T: SerRon + DeRon + PartialEq + std::fmt::Debug>(value: &T) -> String {
+    let ron = value.serialize_ron();
+    let back = T::deserialize_ron(&ron).unwrap_or_else( | e | panic!("{} in {}", e.msg, ron));
+    assert_eq!(&back, value, "ron: {}", ron);
+    ron
+}
+
+fn bin_round_trip<T: SerBin + DeBin + PartialEq + std::fmt::Debug>(value: &T) -> Vec<u8> {
+    let bin = value.serialize_bin();
+    assert_eq!(&T::deserialize_bin(&bin).unwrap(), value);
+    bin
+}
+
+#[test]
+fn rename() {
+    let value = Renamed {max_tokens: 100, name: "a".to_string()};
+    let json = json_round_trip(&value);
+    assert!(json.contains("\"maxTokens\":100"), "{}", json);
+    assert!(!json.contains("max_tokens"), "{}", json);
+    let ron = ron_round_trip(&value);
+    assert!(ron.contains("maxTokens:"), "{}", ron);
+    bin_round_trip(&value);
+}
+
+#[test]
+fn default_on_missing_fields() {
+    let value: Defaults = DeJson::deserialize_json(r#"{"id":3}"#).unwrap();
+    assert_eq!(value, Defaults {id: 3, temperature: 1.5, tags: Vec::new(), note: None});
+    let value: Defaults = DeRon::deserialize_ron("(id:3)").unwrap();
+    assert_eq!(value, Defaults {id: 3, temperature: 1.5, tags: Vec::new(), note: None});
+
+    // fields that are there win over the default
+    let value: Defaults = DeJson::deserialize_json(r#"{"id":3,"temperature":0.5,"tags":["x"]}"#).unwrap();
+    assert_eq!(value.temperature, 0.5);
+    assert_eq!(value.tags, vec!["x".to_string()]);
+
+    // without a default a missing field is still an error
+    assert!(Defaults::deserialize_json(r#"{"temperature":0.5}"#).is_err());
+    assert!(Defaults::deserialize_ron("(temperature:0.5)").is_err());
+
+    let value = Defaults {id: 1, temperature: 2.0, tags: vec!["a".to_string()], note: Some("n".to_string())};
+    json_round_trip(&value);
+    ron_round_trip(&value);
+}
+
+#[test]
+fn skip() {
+    let value = Skipped {id: 1, cache: vec![1, 2, 3], retries: 3, name: "a".to_string()};
+    let expected = Skipped {id: 1, cache: Vec::new(), retries: 7, name: "a".to_string()};
+
+    let json = value.serialize_json();
+    assert!(!json.contains("cache") && !json.contains("retries"), "{}", json);
+    assert_eq!(Skipped::deserialize_json(&json).unwrap(), expected);
+
+    let ron = value.serialize_ron();
+    assert!(!ron.contains("cache") && !ron.contains("retries"), "{}", ron);
+    assert_eq!(Skipped::deserialize_ron(&ron).unwrap(), expected);
+
+    // skipped fields take no room in the binary layout
+    let bin = value.serialize_bin();
+    assert_eq!(bin, NotSkipped {id: 1, name: "a".to_string()}.serialize_bin());
+    assert_eq!(Skipped::deserialize_bin(&bin).unwrap(), expected);
+}
+
+#[test]
+fn flatten_struct() {
+    let value = Outer {name: "a".to_string(), size: Inner {width: 10, height: 20}};
+    let json = json_round_trip(&value);
+    assert_eq!(json, r#"{"name":"a","width":10,"height":20}"#);
+    ron_round_trip(&value);
+
+    // flattened fields may come in any order around the others
+    let value: Outer = DeJson::deserialize_json(r#"{"height":2,"name":"b","width":1}"#).unwrap();
+    assert_eq!(value, Outer {name: "b".to_string(), size: Inner {width: 1, height: 2}});
+    let value: Outer = DeRon::deserialize_ron("(height:2, name:\"b\", width:1)").unwrap();
+    assert_eq!(value, Outer {name: "b".to_string(), size: Inner {width: 1, height: 2}});
+}
+
+#[test]
+fn flatten_unknown_fields() {
+    let value: WithExtra = DeJson::deserialize_json(r#"{"a":"x","id":1,"b":"y"}"#).unwrap();
+    assert_eq!(value.id, 1);
+    assert_eq!(value.extra.len(), 2);
+    assert_eq!(value.extra["a"], "x");
+    assert_eq!(value.extra["b"], "y");
+    json_round_trip(&value);
+
+    // without a flatten field unknown keys are an error
+    assert!(Renamed::deserialize_json(r#"{"maxTokens":1,"name":"a","other":2}"#).is_err());
+    // and a flattened struct rejects the keys it doesn't know either
+    assert!(Outer::deserialize_json(r#"{"name":"a","width":1,"height":2,"depth":3}"#).is_err());
+}
+
+#[test]
+fn internally_tagged() {
+    let value = Internal::Image {url: "cat.png".to_string(), width: Some(3)};
+    let json = json_round_trip(&value);
+    assert_eq!(json, r#"{"type":"image","url":"cat.png","width":3}"#);
+    ron_round_trip(&value);
+    json_round_trip(&Internal::Text {text: "hi".to_string()});
+    ron_round_trip(&Internal::Text {text: "hi".to_string()});
+    json_round_trip(&Internal::Empty);
+    ron_round_trip(&Internal::Empty);
+
+    // the tag doesn't have to come first
+    let value: Internal = DeJson::deserialize_json(r#"{"text":"hello","type":"text"}"#).unwrap();
+    assert_eq!(value, Internal::Text {text: "hello".to_string()});
+
+    assert!(Internal::deserialize_json(r#"{"type":"video","url":"a"}"#).is_err());
+    assert!(Internal::deserialize_json(r#"{"url":"a"}"#).is_err());
+}
+
+#[test]
+fn adjacently_tagged() {
+    let value = Adjacent::Point {x: 1, y: -2};
+    let json = json_round_trip(&value);
+    assert_eq!(json, r#"{"t":"Point","c":{"x":1,"y":-2}}"#);
+    ron_round_trip(&value);
+    for value in [Adjacent::Pair(1, "a".to_string()), Adjacent::Single("s".to_string()), Adjacent::Empty] {
+        json_round_trip(&value);
+        ron_round_trip(&value);
+    }
+
+    let value: Adjacent = DeJson::deserialize_json(r#"{"c":"s","t":"Single"}"#).unwrap();
+    assert_eq!(value, Adjacent::Single("s".to_string()));
+}
@@ -0,0 +1,106 @@
+// Lookups, indexing and serialization of the dynamic JsonValue.
+
+use makepad_micro_serde::*;
+
+fn parse(json: &str) -> JsonValue {
+    JsonValue::deserialize_json(json).unwrap()
+}
+
+#[test]
+fn pointer() {
+    let value = parse(r#"{"choices":[{"message":{"content":"hi"}},{"message":null}],"a/b":1,"m~n":2,"":3}"#);
+    assert_eq!(value.pointer(""), Some(&value));
+    assert_eq!(value.pointer("/choices/0/message/content").and_then( | v | v.as_str()), Some("hi"));
+    assert_eq!(value.pointer("/choices/1/message"), Some(&JsonValue::Null));
+    // ~1 is a / in a key and ~0 a ~
+    assert_eq!(value.pointer("/a~1b").and_then( | v | v.as_u64()), Some(1));
+    assert_eq!(value.pointer("/m~0n").and_then( | v | v.as_u64()), Some(2));
+    // the empty key is a key like any other
+    assert_eq!(value.pointer("/").and_then( | v | v.as_u64()), Some(3));
+
+    assert_eq!(value.pointer("/choices/2"), None);
+    assert_eq!(value.pointer("/choices/01"), None);
+    assert_eq!(value.pointer("/choices/-1"), None);
+    assert_eq!(value.pointer("/choices/x"), None);
+    assert_eq!(value.pointer("/missing/0"), None);
+    assert_eq!(value.pointer("/choices/0/message/content/deeper"), None);
+    // pointers start with a slash
+    assert_eq!(value.pointer("choices"), None);
+}
+
+#[test]
+fn pointer_mut() {
+    let mut value = parse(r#"{"list":[1,2],"x~/y":{"z":false}}"#);
+    *value.pointer_mut("/list/1").unwrap() = JsonValue::String("two".to_string());
+    *value.pointer_mut("/x~0~1y/z").unwrap() = JsonValue::Bool(true);
+    assert_eq!(value["list"][1].as_str(), Some("two"));
+    assert_eq!(value["x~/y"]["z"].as_bool(), Some(true));
+    assert!(value.pointer_mut("/list/5").is_none());
+}
+
+#[test]
+fn index_missing_is_null() {
+    let value = parse(r#"{"a":{"b":[10,20]},"s":"text"}"#);
+    assert_eq!(value["a"]["b"][1].as_u64(), Some(20));
+    assert!(value["missing"].is_null());
+    assert!(value["missing"]["deeper"][3].is_null());
+    assert!(value["a"]["b"][2].is_null());
+    // indexing something that isn't a container is null too
+    assert!(value["s"]["key"].is_null());
+    assert!(value["s"][0].is_null());
+    assert!(value[0].is_null());
+    assert_eq!(value.get("a").and_then( | a | a.get("b")).and_then( | b | b.get(0)).and_then( | v | v.as_i64()), Some(10));
+    assert_eq!(value.get("missing"), None);
+}
+
+#[test]
+fn index_mut_inserts() {
+    let mut value = JsonValue::Null;
+    value["model"] = JsonValue::String("m".to_string());
+    value["options"]["stream"] = JsonValue::Bool(true);
+    assert_eq!(value.pointer("/options/stream"), Some(&JsonValue::Bool(true)));
+    assert_eq!(value["model"].as_str(), Some("m"));
+
+    let mut value = parse("[1,2,3]");
+    value[2] = JsonValue::U64(30);
+    assert_eq!(value[2].as_u64(), Some(30));
+}
+
+#[test]
+#[should_panic]
+fn index_mut_out_of_bounds_panics() {
+    let mut value = parse("[1]");
+    value[3] = JsonValue::Null;
+}
+
+#[test]
+fn ser_json_round_trip() {
+    let json = r#"{"s":"a \"quoted\" line\n","u":18446744073709551615,"i":-5,"f":1.5,"whole":2.0,"t":true,"n":null,"list":[1,[],{}],"nested":{"k":"v"}}"#;
+    let value = parse(json);
+    let back = parse(&value.serialize_json());
+    assert_eq!(back, value);
+    // floats stay floats even without a fraction
+    assert_eq!(back["whole"], JsonValue::F64(2.0));
+    assert_eq!(back["u"].as_u64(), Some(u64::MAX));
+    assert_eq!(back["i"].as_i64(), Some(-5));
+}
+
+#[test]
+fn typed_conversions() {
+    #[derive(SerJson, DeJson, Debug, PartialEq)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+    let value = JsonValue::from_ser_json(&Point {x: 1, y: -2}).unwrap();
+    assert_eq!(value["y"].as_i64(), Some(-2));
+    assert_eq!(value.to_de_json::<Point>().unwrap(), Point {x: 1, y: -2});
+}
+
+#[test]
+#[allow(deprecated)]
+fn deprecated_accessors() {
+    let value = parse(r#"{"lights":{"1":{"name":"desk"}}}"#);
+    let light = value.key("lights").object().get("1").unwrap();
+    assert_eq!(light.key("name").string(), "desk");
+}
@@ -116,6 +116,23 @@ impl ZReaderTrait for &Vec<u8> {
     }
 }
 
+impl ZReaderTrait for alloc::rc::Rc<Vec<u8>> {
+    #[inline(always)]
+    fn get_byte(&self, index: usize) -> Option<&u8> {
+        self.get(index)
+    }
+
+    #[inline(always)]
+    fn get_slice(&self, index: Range<usize>) -> Option<&[u8]> {
+        self.get(index)
+    }
+
+    #[inline(always)]
+    fn get_len(&self) -> usize {
+        self.len()
+    }
+}
+
 impl<const N: usize> ZReaderTrait for &[u8; N] {
     fn get_byte(&self, index: usize) -> Option<&u8> {
         self.get(index)
@@ -173,6 +173,7 @@ where
     pub(crate) seen_headers:    bool,
     pub(crate) seen_trns:       bool,
     pub(crate) seen_iend:       bool,
+    pub(crate) seen_idat_fctl:  bool,
     pub(crate) current_frame:   usize
 }
 
@@ -216,6 +217,7 @@ impl<T: ZReaderTrait> PngDecoder<T> {
             seen_trns:       false,
             seen_headers:    false,
             seen_iend:       false,
+            seen_idat_fctl:  false,
             trns_bytes:      [0; 4],
             current_frame:   0
         }
@@ -316,6 +318,30 @@ impl<T: ZReaderTrait> PngDecoder<T> {
         self.frames.len() > self.current_frame
     }
 
+    /// Return the animation control chunk (`acTL`) if present
+    ///
+    /// Unlike [`is_animated`](Self::is_animated) this is available right after
+    /// the headers are decoded, before the following frames have been read.
+    pub const fn get_animation_info(&self) -> Option<&ActlChunk> {
+        self.actl_info.as_ref()
+    }
+
+    /// Return the frame control information of the frame most recently
+    /// returned by [`decode`](Self::decode) or [`decode_into`](Self::decode_into)
+    ///
+    /// The frame pixels occupy the first `width*height*components` samples of
+    /// the output, and are to be placed at `x_offset`,`y_offset` of the canvas.
+    ///
+    /// Returns `None` for the default image of an APNG that has no `fcTL` chunk
+    /// before its `IDAT`, that image is not part of the animation.
+    pub fn get_last_frame_info(&self) -> Option<FrameInfo> {
+        let index = self.current_frame.checked_sub(1)?;
+        if index == 0 && self.actl_info.is_some() && !self.seen_idat_fctl {
+            return None;
+        }
+        self.frames.get(index).and_then(|frame| frame.fctl_info)
+    }
+
     pub(crate) fn read_chunk_header(&mut self) -> Result<PngChunk, PngDecodeErrors> {
         // Format is length - chunk type - [data] -  crc chunk, load crc chunk now
         let chunk_length = self.stream.get_u32_be_err()? as usize;
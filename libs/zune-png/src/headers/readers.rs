@@ -497,6 +497,7 @@ impl<T: ZReaderTrait> PngDecoder<T> {
                 self.parse_idat(next_header)?;
                 // set fctl information
                 self.frames[0].set_fctl(fctl_info);
+                self.seen_idat_fctl = true;
             } else if next_header.chunk_type == PngChunkType::fcTL {
                 // next frame, stop and go back
                 //
@@ -120,6 +120,7 @@ macro_rules!warn {
     ( $ ( $ t: tt) *) => {}
 }
 
+pub use apng::{ActlChunk, BlendOp, DisposeOp, FrameInfo};
 pub use decoder::{ItxtChunk, PngDecoder, PngInfo, TextChunk, TimeInfo, ZtxtChunk};
 pub use encoder::PngEncoder;
 pub use enums::InterlaceMethod;
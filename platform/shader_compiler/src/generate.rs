@@ -77,6 +77,51 @@ pub trait BackendWriter {
     fn write_ty_lit(&self, string: &mut String, ty_lit: TyLit);
     fn write_builtin_call_ident(&self, string: &mut String, ident: Ident, arg_exprs: &[Expr]);
     
+    // the methods below have defaults for the C-like backends (GLSL, HLSL, Metal)
+    // and are overridden by backends with a Rust-like syntax such as WGSL
+    
+    fn cond_expr_is_select(&self) -> bool {false}
+    fn inout_params_are_pointers(&self) -> bool {false}
+    fn assignment_is_stmt(&self) -> bool {false}
+    fn needs_discard_for_unused_values(&self) -> bool {false}
+    
+    fn write_let_prefix(&self, _string: &mut String) {
+    }
+    
+    fn write_fn_def_start(&self, string: &mut String, ident: &dyn fmt::Display, return_ty: &Ty) {
+        self.write_var_decl(string, "", false, false, ident, return_ty);
+    }
+    
+    fn write_fn_def_return_ty(&self, _string: &mut String, _return_ty: &Ty) {
+    }
+    
+    fn write_fn_def_param(
+        &self,
+        string: &mut String,
+        sep: &'static str,
+        is_inout: bool,
+        ident: &dyn fmt::Display,
+        ty: &Ty,
+    ) -> bool {
+        self.write_var_decl(string, sep, is_inout, false, ident, ty)
+    }
+    
+    fn write_fn_def_param_prologue(&self, _string: &mut String, _is_inout: bool, _ident: &dyn fmt::Display, _ty: &Ty) {
+    }
+    
+    fn write_for_stmt_head(&self, string: &mut String, ident: Ident, from: i32, to: i32, step: i32) {
+        write!(
+            string,
+            "for (int {0} = {1}; {0} {2} {3}; {0} {4} {5}) ",
+            ident,
+            if from <= to {from} else {from - 1},
+            if from <= to {"<"} else {">="},
+            to,
+            if step > 0 {"+="} else {"-="},
+            step.abs()
+        )
+            .unwrap();
+    }
 }
 
 pub struct BlockGenerator<'a> {
@@ -183,7 +228,12 @@ pub fn generate_cons_fn(backend_writer:&dyn BackendWriter, string: &mut String,
 
 impl<'a> BlockGenerator<'a> {
     pub fn generate_block(&mut self, block: &Block) {
+        self.generate_block_with_prologue(block, "")
+    }
+    
+    pub fn generate_block_with_prologue(&mut self, block: &Block, prologue: &str) {
         write!(self.string, "{{\n").unwrap();
+        write!(self.string, "{}", prologue).unwrap();
         self.write_indent();
         if !block.stmts.is_empty() {
             self.indent_level += 1;
@@ -285,17 +335,7 @@ impl<'a> BlockGenerator<'a> {
         } else {
             -1
         };
-        write!(
-            self.string,
-            "for (int {0} = {1}; {0} {2} {3}; {0} {4} {5}) ",
-            ident,
-            if from <= to {from} else {from - 1},
-            if from <= to {"<"} else {">="},
-            to,
-            if step > 0 {"+="} else {"-="},
-            step.abs()
-        )
-            .unwrap();
+        self.backend_writer.write_for_stmt_head(self.string, ident, from as i32, to as i32, step as i32);
         self.generate_block(block);
         writeln!(self.string).unwrap();
     }
@@ -358,6 +398,7 @@ impl<'a> BlockGenerator<'a> {
         expr: &Option<Expr>,
         shadow: &Cell<Option<ScopeSymShadow >>
     ) {
+        self.backend_writer.write_let_prefix(self.string);
         self.backend_writer.write_var_decl(
             &mut self.string,
             "",
@@ -388,6 +429,32 @@ impl<'a> BlockGenerator<'a> {
     }
     
     fn generate_expr_stmt(&mut self, _span: TokenSpan, expr: &Expr) {
+        if self.backend_writer.assignment_is_stmt() {
+            // split chained assignments like a = b = c into b = c; a = b;
+            if let ExprKind::Bin {span, op, ref left_expr, ref right_expr} = expr.kind {
+                if let ExprKind::Bin {op: right_op, left_expr: ref right_left_expr, ..} = right_expr.kind {
+                    if op.is_assign() && right_op.is_assign() {
+                        self.generate_expr_stmt(span, right_expr);
+                        self.write_indent();
+                        self.generate_expr(left_expr);
+                        write!(self.string, " {} ", op).unwrap();
+                        self.generate_expr(right_left_expr);
+                        writeln!(self.string, ";").unwrap();
+                        return
+                    }
+                }
+            }
+        }
+        if self.backend_writer.needs_discard_for_unused_values() {
+            let is_used = match &expr.kind {
+                ExprKind::Bin {op, ..} => op.is_assign(),
+                ExprKind::MethodCall {..} | ExprKind::PlainCall {..} => true,
+                _ => false
+            };
+            if !is_used {
+                write!(self.string, "_ = ").unwrap();
+            }
+        }
         self.generate_expr(expr);
         writeln!(self.string, ";").unwrap();
     }
@@ -566,6 +633,16 @@ impl<'a> ExprGenerator<'a> {
         expr_if_true: &Expr,
         expr_if_false: &Expr,
     ) {
+        if self.backend_writer.cond_expr_is_select() {
+            write!(self.string, "select(").unwrap();
+            self.generate_expr(expr_if_false);
+            write!(self.string, ", ").unwrap();
+            self.generate_expr(expr_if_true);
+            write!(self.string, ", ").unwrap();
+            self.generate_expr(expr);
+            write!(self.string, ")").unwrap();
+            return
+        }
         write!(self.string, "(").unwrap();
         self.generate_expr(expr);
         write!(self.string, " ? ").unwrap();
@@ -650,6 +727,13 @@ impl<'a> ExprGenerator<'a> {
             }
         }
         
+        if op.is_assign() && self.backend_writer.assignment_is_stmt() {
+            self.generate_expr(left_expr);
+            write!(self.string, " {} ", op).unwrap();
+            self.generate_expr(right_expr);
+            return
+        }
+        
         write!(self.string, "(").unwrap();
         self.generate_expr(left_expr);
         write!(self.string, " {} ", op).unwrap();
@@ -711,7 +795,7 @@ impl<'a> ExprGenerator<'a> {
             )).unwrap();
             
             let mut sep = "";
-            for arg_expr in arg_exprs {
+            for (arg_expr, is_inout) in arg_exprs.iter().zip(Self::inout_params(fn_def)) {
                 // check if the args is a closure, ifso skip it
                 match arg_expr.ty.borrow().as_ref().unwrap(){
                     Ty::ClosureDef(_)=>{
@@ -721,7 +805,7 @@ impl<'a> ExprGenerator<'a> {
                 }
                 
                 write!(self.string, "{}", sep).unwrap();
-                self.generate_expr(arg_expr);
+                self.generate_call_arg(arg_expr, is_inout);
                 sep = ", ";
             }
             // and now the closed over values
@@ -748,9 +832,9 @@ impl<'a> ExprGenerator<'a> {
         else {
             write!(self.string, "{}_{} (", fn_def.fn_ptr, fn_def.ident).unwrap();
             let mut sep = "";
-            for arg_expr in arg_exprs {
+            for (arg_expr, is_inout) in arg_exprs.iter().zip(Self::inout_params(fn_def)) {
                 write!(self.string, "{}", sep).unwrap();
-                self.generate_expr(arg_expr);
+                self.generate_call_arg(arg_expr, is_inout);
                 sep = ", ";
             }
 
@@ -760,6 +844,20 @@ impl<'a> ExprGenerator<'a> {
         }
     }
     
+    // the inout flags of the params that take call arguments, draw shader self params are implicit
+    fn inout_params(fn_def: &FnDef) -> impl Iterator<Item = bool> + '_ {
+        fn_def.params.iter().filter( | param | {
+            !matches!(param.ty_expr.ty.borrow().as_ref(), Some(Ty::DrawShader(_)))
+        }).map( | param | param.is_inout)
+    }
+    
+    fn generate_call_arg(&mut self, arg_expr: &Expr, is_inout: bool) {
+        if is_inout && self.backend_writer.inout_params_are_pointers() {
+            write!(self.string, "&").unwrap();
+        }
+        self.generate_expr(arg_expr);
+    }
+    
     fn generate_field_expr(&mut self, _span: TokenSpan, expr: &Expr, field_ident: Ident, ty:&Ty) {
         match expr.ty.borrow().as_ref() {
             Some(Ty::DrawShader(_)) => {
@@ -926,7 +1024,15 @@ impl<'a> ExprGenerator<'a> {
                 write!(self.string, "{}", DisplayVarName(ident, shadow)).unwrap();
             }
             VarKind::MutLocal {ident, shadow} => {
-                write!(self.string, "{}", DisplayVarName(ident, shadow)).unwrap();
+                let is_inout_param = self.backend_writer.inout_params_are_pointers() && self.fn_def.map_or(false, | fn_def | {
+                    fn_def.params.iter().any( | param | param.is_inout && param.ident == ident && param.shadow.get() == Some(shadow))
+                });
+                if is_inout_param {
+                    write!(self.string, "(*{})", DisplayVarName(ident, shadow)).unwrap();
+                }
+                else {
+                    write!(self.string, "{}", DisplayVarName(ident, shadow)).unwrap();
+                }
             }
             VarKind::LiveValue(value_node_ptr) => {
                 // this is a live value.. also prefix needed
@@ -960,38 +1066,34 @@ pub struct FnDefGenerator<'a> {
 impl<'a> FnDefGenerator<'a> {
     pub fn generate_fn_def(&mut self) {
         
-        self.backend_writer.write_var_decl(
+        self.backend_writer.write_fn_def_start(
             &mut self.string,
-            "",
-            false,
-            false,
             &DisplayFnName(self.fn_def.fn_ptr, self.fn_def.ident), // here we must expand IdentPath to something
             self.fn_def.return_ty.borrow().as_ref().unwrap()
         );
         write!(self.string, "(").unwrap();
         let mut sep = "";
+        let mut prologue = String::new();
         for param in &self.fn_def.params {
             if !param.shadow.get().is_none() {
-                if self.backend_writer.write_var_decl(
-                    &mut self.string,
-                    sep,
-                    param.is_inout,
-                    false,
-                    &DisplayVarName(param.ident, param.shadow.get().unwrap()),
-                    param.ty_expr.ty.borrow().as_ref().unwrap(),
-                ) {
+                let ident = DisplayVarName(param.ident, param.shadow.get().unwrap());
+                let ty = param.ty_expr.ty.borrow();
+                if self.backend_writer.write_fn_def_param(&mut self.string, sep, param.is_inout, &ident, ty.as_ref().unwrap()) {
+                    self.backend_writer.write_fn_def_param_prologue(&mut prologue, param.is_inout, &ident, ty.as_ref().unwrap());
                     sep = ", ";
                 }
             }
         }
         self.backend_writer.write_fn_def_hidden_params(self.string, self.fn_def.hidden_args.borrow().as_ref().unwrap(), sep);
-        write!(self.string, ") ").unwrap();
-        self.generate_block(&self.fn_def.block);
+        write!(self.string, ")").unwrap();
+        self.backend_writer.write_fn_def_return_ty(self.string, self.fn_def.return_ty.borrow().as_ref().unwrap());
+        write!(self.string, " ").unwrap();
+        self.generate_block(&self.fn_def.block, &prologue);
         writeln!(self.string).unwrap();
         //self.visited.insert(self.decl.ident_path);
     }
     
-    fn generate_block(&mut self, block: &Block) {
+    fn generate_block(&mut self, block: &Block, prologue: &str) {
         BlockGenerator {
             shader_registry: self.shader_registry,
             closure_site_info: None,
@@ -1002,7 +1104,7 @@ impl<'a> FnDefGenerator<'a> {
             indent_level: 0,
             string: self.string,
         }
-        .generate_block(block)
+        .generate_block_with_prologue(block, prologue)
     }
 }
 
@@ -1077,11 +1179,8 @@ impl<'a> FnDefWithClosureArgsGenerator<'a> {
     
     pub fn generate_fn_def_with_closure_args(&mut self) {
         
-        self.backend_writer.write_var_decl(
+        self.backend_writer.write_fn_def_start(
             &mut self.string,
-            "",
-            false,
-            false,
             &DisplayFnNameWithClosureArgs(
                 self.closure_site_info.site_index,
                 self.call_def.fn_ptr,
@@ -1091,16 +1190,13 @@ impl<'a> FnDefWithClosureArgsGenerator<'a> {
         );
         write!(self.string, "(").unwrap();
         let mut sep = "";
+        let mut prologue = String::new();
         for param in &self.fn_def.params {
             if !param.shadow.get().is_none() {
-                if self.backend_writer.write_var_decl(
-                    &mut self.string,
-                    sep,
-                    param.is_inout,
-                    false,
-                    &DisplayVarName(param.ident, param.shadow.get().unwrap()),
-                    param.ty_expr.ty.borrow().as_ref().unwrap(),
-                ) {
+                let ident = DisplayVarName(param.ident, param.shadow.get().unwrap());
+                let ty = param.ty_expr.ty.borrow();
+                if self.backend_writer.write_fn_def_param(&mut self.string, sep, param.is_inout, &ident, ty.as_ref().unwrap()) {
+                    self.backend_writer.write_fn_def_param_prologue(&mut prologue, param.is_inout, &ident, ty.as_ref().unwrap());
                     sep = ", ";
                 }
             }
@@ -1125,17 +1221,19 @@ impl<'a> FnDefWithClosureArgsGenerator<'a> {
         merged_hidden_args.extend(self.call_def.hidden_args.borrow().as_ref().unwrap().iter().cloned());
         self.backend_writer.write_fn_def_hidden_params(self.string, &merged_hidden_args, sep);
         
-        write!(self.string, ") ").unwrap();
+        write!(self.string, ")").unwrap();
+        self.backend_writer.write_fn_def_return_ty(self.string, self.fn_def.return_ty.borrow().as_ref().unwrap());
+        write!(self.string, " ").unwrap();
         // alright so here the block is generated.. however
         // we need to know the names and the closed-over-args passthrough
-        self.generate_block(&self.fn_def.block);
+        self.generate_block(&self.fn_def.block, &prologue);
         
         
         writeln!(self.string).unwrap();
         //self.visited.insert(self.decl.ident_path);
     }
     
-    fn generate_block(&mut self, block: &Block) {
+    fn generate_block(&mut self, block: &Block, prologue: &str) {
         BlockGenerator {
             shader_registry: self.shader_registry,
             closure_site_info: Some(self.closure_site_info.clone()),
@@ -1146,7 +1244,7 @@ impl<'a> FnDefWithClosureArgsGenerator<'a> {
             indent_level: 0,
             string: self.string,
         }
-        .generate_block(block)
+        .generate_block_with_prologue(block, prologue)
     }
 }
 
@@ -1167,14 +1265,13 @@ impl<'a> ClosureDefGenerator<'a> {
         let fn_param = &self.fn_def.params[self.closure_site_arg.param_index];
         
         let mut sep = "";
+        let mut prologue = String::new();
+        let mut return_ty_str = String::new();
         
         if let TyExprKind::ClosureDecl {params, return_ty, ..} = &fn_param.ty_expr.kind {
             
-            self.backend_writer.write_var_decl(
+            self.backend_writer.write_fn_def_start(
                 &mut self.string,
-                "",
-                false,
-                false,
                 &DisplayClosureName(self.call_def.fn_ptr, self.closure_site_arg.closure_def_index), // here we must expand IdentPath to something
                 return_ty.borrow().as_ref().unwrap(),
             );
@@ -1185,17 +1282,14 @@ impl<'a> ClosureDefGenerator<'a> {
                 // lets fetch the name of this thing
                 let closure_param = &self.closure_def.params[param_index];
                 let shadow = closure_param.shadow.get().unwrap();
-                if self.backend_writer.write_var_decl(
-                    &mut self.string,
-                    sep,
-                    param.is_inout,
-                    false,
-                    &DisplayVarName(closure_param.ident, shadow),
-                    param.ty_expr.ty.borrow().as_ref().unwrap(),
-                ) {
+                let ident = DisplayVarName(closure_param.ident, shadow);
+                let ty = param.ty_expr.ty.borrow();
+                if self.backend_writer.write_fn_def_param(&mut self.string, sep, param.is_inout, &ident, ty.as_ref().unwrap()) {
+                    self.backend_writer.write_fn_def_param_prologue(&mut prologue, param.is_inout, &ident, ty.as_ref().unwrap());
                     sep = ", ";
                 }
             }
+            self.backend_writer.write_fn_def_return_ty(&mut return_ty_str, return_ty.borrow().as_ref().unwrap());
         }
         else {
             panic!()
@@ -1219,24 +1313,27 @@ impl<'a> ClosureDefGenerator<'a> {
         merged_hidden_args.extend(self.call_def.hidden_args.borrow().as_ref().unwrap().iter().cloned());
         self.backend_writer.write_fn_def_hidden_params(self.string, &merged_hidden_args, sep);
         
-        writeln!(self.string, ") {{").unwrap();
+        write!(self.string, "){}", return_ty_str).unwrap();
         
         match &self.closure_def.kind {
             ClosureDefKind::Expr(expr) => {
+                writeln!(self.string, " {{").unwrap();
+                write!(self.string, "{}", prologue).unwrap();
                 write!(self.string, "    return ").unwrap();
                 self.generate_expr(expr);
                 writeln!(self.string, ";").unwrap();
                 writeln!(self.string, "}}").unwrap();
             }
             ClosureDefKind::Block(block) => {
-                self.generate_block(block);
+                write!(self.string, " ").unwrap();
+                self.generate_block(block, &prologue);
                 writeln!(self.string).unwrap();
             }
         }
         //self.visited.insert(self.decl.ident_path);
     }
     
-    fn generate_block(&mut self, block: &Block) {
+    fn generate_block(&mut self, block: &Block, prologue: &str) {
         BlockGenerator {
             shader_registry: self.shader_registry,
             closure_site_info: None,
@@ -1247,7 +1344,7 @@ impl<'a> ClosureDefGenerator<'a> {
             indent_level: 0,
             string: self.string,
         }
-        .generate_block(block)
+        .generate_block_with_prologue(block, prologue)
     }
     
     
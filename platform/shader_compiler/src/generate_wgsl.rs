@@ -0,0 +1,953 @@
+// WGSL backend, generates a single module with a vertex_main and fragment_main entrypoint.
+//
+// All resources live in bind group 0 and get sequential bindings in this order:
+// const_table, live_table, one <block>_table per uniform block (sorted by name),
+// the textures in field order, and finally default_sampler.
+// Tables that would be empty are left out. All tables are arrays of vec4<f32>
+// using the same tight packing as the GLSL backend (slot n is table[n >> 2][n & 3]).
+//
+// Geometries and instances are passed in as packed vec4 vertex attributes,
+// geometries first, exactly like packed_geometry_N / packed_instance_N in GLSL.
+
+use {
+    std::{
+        cell::RefCell,
+        fmt,
+        fmt::Write,
+        collections::{BTreeMap, BTreeSet},
+    },
+    crate::{
+        makepad_live_id::{
+            live_id,
+            LiveId,
+        },
+        generate::*,
+        shader_ast::*,
+        shader_registry::ShaderRegistry
+    }
+};
+
+pub fn generate_shader(draw_shader_def: &DrawShaderDef, const_table: &DrawShaderConstTable, shader_registry: &ShaderRegistry) -> String {
+    let mut string = String::new();
+    let mut cons_fns = BTreeMap::new();
+    for fn_iter in draw_shader_def.all_fns.borrow().iter() {
+        let fn_def = shader_registry.all_fns.get(fn_iter).unwrap();
+        for (ty_lit, param_tys) in fn_def.constructor_fn_deps.borrow().as_ref().unwrap().iter() {
+            if cons_fn_is_needed(*ty_lit, param_tys) {
+                cons_fns.insert(cons_fn_name(*ty_lit, param_tys), (*ty_lit, param_tys.clone()));
+            }
+        }
+    }
+    let backend_writer = WgslBackendWriter {
+        shader_registry,
+        const_table,
+        cons_fns: cons_fns.keys().cloned().collect(),
+        helpers: RefCell::new(BTreeMap::new())
+    };
+    DrawShaderGenerator {
+        draw_shader_def,
+        shader_registry,
+        const_table,
+        string: &mut string,
+        cons_fns: &cons_fns,
+        wgsl_writer: &backend_writer,
+        backend_writer: &backend_writer
+    }
+    .generate_shader();
+    string
+}
+
+struct DrawShaderGenerator<'a> {
+    draw_shader_def: &'a DrawShaderDef,
+    shader_registry: &'a ShaderRegistry,
+    string: &'a mut String,
+    const_table: &'a DrawShaderConstTable,
+    cons_fns: &'a BTreeMap<String, (TyLit, Vec<Ty>)>,
+    wgsl_writer: &'a WgslBackendWriter<'a>,
+    backend_writer: &'a dyn BackendWriter
+}
+
+impl<'a> DrawShaderGenerator<'a> {
+    fn generate_shader(&mut self) {
+        // the function bodies go first so we know which builtin helpers are used
+        let mut body = String::new();
+        self.generate_fn_defs(&mut body);
+
+        self.generate_bindings();
+        self.generate_private_decls();
+        self.generate_struct_defs();
+
+        for (name, (ty_lit, param_tys)) in self.cons_fns.iter() {
+            self.generate_cons_fn(name, *ty_lit, param_tys);
+        }
+        for helper in self.wgsl_writer.helpers.borrow().values() {
+            writeln!(self.string, "{}", helper).unwrap();
+        }
+
+        write!(self.string, "{}", body).unwrap();
+
+        self.generate_io_structs();
+        self.generate_vertex_main();
+        self.generate_fragment_main();
+    }
+
+    fn generate_fn_defs(&mut self, string: &mut String) {
+        let all_fns = self.draw_shader_def.all_fns.borrow();
+        for fn_iter in all_fns.iter().rev() {
+            let const_table_offset = self.const_table.offsets.get(fn_iter).cloned();
+            let fn_def = self.shader_registry.all_fns.get(fn_iter).unwrap();
+            if fn_def.has_closure_args() {
+                for call_iter in all_fns.iter().rev() {
+                    // any function that depends on us, will have the closures we need
+                    let call_def = self.shader_registry.all_fns.get(call_iter).unwrap();
+                    if call_def.callees.borrow().as_ref().unwrap().contains(&fn_iter) {
+                        FnDefWithClosureArgsGenerator::generate_fn_def_with_all_closures(
+                            string,
+                            self.shader_registry,
+                            fn_def,
+                            call_def,
+                            self.backend_writer,
+                            const_table_offset
+                        );
+                    }
+                }
+                continue
+            }
+            FnDefGenerator {
+                fn_def,
+                const_table_offset,
+                shader_registry: self.shader_registry,
+                backend_writer: self.backend_writer,
+                string,
+            }
+            .generate_fn_def();
+            writeln!(string).unwrap();
+        }
+    }
+
+    fn generate_bindings(&mut self) {
+        let mut binding = 0;
+        if !self.const_table.table.is_empty() {
+            writeln!(
+                self.string,
+                "@group(0) @binding({}) var<uniform> const_table: array<vec4<f32>, {}>;",
+                binding,
+                table_len(self.const_table.table.len())
+            ).unwrap();
+            binding += 1;
+        }
+        let live_slots = self.calc_live_slots();
+        if live_slots > 0 {
+            writeln!(
+                self.string,
+                "@group(0) @binding({}) var<uniform> live_table: array<vec4<f32>, {}>;",
+                binding,
+                table_len(live_slots)
+            ).unwrap();
+            binding += 1;
+        }
+        for (ident, vec) in self.draw_shader_def.fields_as_uniform_blocks() {
+            let mut slots = 0;
+            for (index, _item) in &vec {
+                let field = &self.draw_shader_def.fields[*index];
+                slots += field.ty_expr.ty.borrow().as_ref().unwrap().slots();
+            }
+            writeln!(
+                self.string,
+                "@group(0) @binding({}) var<uniform> {}_table: array<vec4<f32>, {}>;",
+                binding,
+                ident,
+                table_len(slots)
+            ).unwrap();
+            binding += 1;
+        }
+        let mut has_textures = false;
+        for field in &self.draw_shader_def.fields {
+            if let DrawShaderFieldKind::Texture {..} = field.kind {
+                write!(self.string, "@group(0) @binding({}) var ", binding).unwrap();
+                self.write_var_decl(&DisplayDsIdent(field.ident), field.ty_expr.ty.borrow().as_ref().unwrap());
+                writeln!(self.string, ";").unwrap();
+                binding += 1;
+                has_textures = true;
+            }
+        }
+        if has_textures {
+            writeln!(self.string, "@group(0) @binding({}) var default_sampler: sampler;", binding).unwrap();
+        }
+        writeln!(self.string).unwrap();
+    }
+
+    fn generate_private_decls(&mut self) {
+        for (live_ref, ty) in self.draw_shader_def.all_live_refs.borrow().iter() {
+            write!(self.string, "var<private> ").unwrap();
+            self.write_var_decl(live_ref, ty);
+            writeln!(self.string, ";").unwrap();
+        }
+        for field in &self.draw_shader_def.fields {
+            match field.kind {
+                DrawShaderFieldKind::Texture {..} => (),
+                _ => {
+                    write!(self.string, "var<private> ").unwrap();
+                    self.write_var_decl(&DisplayDsIdent(field.ident), field.ty_expr.ty.borrow().as_ref().unwrap());
+                    writeln!(self.string, ";").unwrap();
+                }
+            }
+        }
+        writeln!(self.string).unwrap();
+    }
+
+    fn generate_struct_defs(&mut self) {
+        for struct_ptr in self.draw_shader_def.all_structs.borrow().iter().rev() {
+            let struct_def = self.shader_registry.structs.get(struct_ptr).unwrap();
+            writeln!(self.string, "struct {} {{", struct_ptr).unwrap();
+            for field in &struct_def.fields {
+                write!(self.string, "    ").unwrap();
+                self.write_var_decl(&DisplayStructField(field.ident), field.ty_expr.ty.borrow().as_ref().unwrap());
+                writeln!(self.string, ",").unwrap();
+            }
+            writeln!(self.string, "}}\n").unwrap();
+        }
+    }
+
+    fn generate_cons_fn(&mut self, name: &str, ty_lit: TyLit, param_tys: &[Ty]) {
+        let ty = ty_lit.to_ty();
+        write!(self.string, "fn {}(", name).unwrap();
+        let param_names: Vec<String> = if param_tys.len() == 1 {
+            vec!["x".to_string()]
+        }
+        else {
+            (0..param_tys.len()).map( | index | DisplaConstructorArg(index).to_string()).collect()
+        };
+        let mut sep = "";
+        for (param_name, param_ty) in param_names.iter().zip(param_tys) {
+            write!(self.string, "{}", sep).unwrap();
+            self.write_var_decl(param_name, param_ty);
+            sep = ", ";
+        }
+        write!(self.string, ") -> ").unwrap();
+        self.write_ty_lit(ty_lit);
+        writeln!(self.string, " {{").unwrap();
+
+        let scalar = scalar_name(&ty);
+        let components = match (mat_dim(&ty), param_tys) {
+            (Some(dst), [Ty::Float]) => {
+                (0..dst * dst).map( | index | {
+                    if index / dst == index % dst {"x".to_string()} else {"0.0".to_string()}
+                }).collect()
+            }
+            (Some(dst), [param_ty]) if mat_dim(param_ty).is_some() => {
+                let src = mat_dim(param_ty).unwrap();
+                let mut components = Vec::new();
+                for col_index in 0..dst {
+                    for row_index in 0..dst {
+                        components.push(if row_index < src && col_index < src {
+                            format!("x[{}][{}]", col_index, row_index)
+                        }
+                        else if col_index == row_index {
+                            "1.0".to_string()
+                        }
+                        else {
+                            "0.0".to_string()
+                        })
+                    }
+                }
+                components
+            }
+            _ => {
+                let mut components = Vec::new();
+                for (param_name, param_ty) in param_names.iter().zip(param_tys) {
+                    for component in ty_components(param_name, param_ty) {
+                        if scalar_name(param_ty) != scalar {
+                            components.push(format!("{}({})", scalar, component));
+                        }
+                        else {
+                            components.push(component);
+                        }
+                    }
+                }
+                components.truncate(ty.slots());
+                components
+            }
+        };
+        write!(self.string, "    return ").unwrap();
+        self.write_ty_lit(ty_lit);
+        writeln!(self.string, "({});", components.join(", ")).unwrap();
+        writeln!(self.string, "}}\n").unwrap();
+    }
+
+    fn generate_io_structs(&mut self) {
+        writeln!(self.string, "struct VertexInput {{").unwrap();
+        let mut location = 0;
+        for (name, slots) in [
+            ("packed_geometry", self.compute_packed_slots( | kind | matches!(kind, DrawShaderFieldKind::Geometry {..}))),
+            ("packed_instance", self.compute_packed_slots( | kind | matches!(kind, DrawShaderFieldKind::Instance {..}))),
+        ] {
+            for (index, size) in packed_var_sizes(slots).enumerate() {
+                writeln!(self.string, "    @location({}) {}_{}: {},", location, name, index, packed_ty(size)).unwrap();
+                location += 1;
+            }
+        }
+        writeln!(self.string, "}}\n").unwrap();
+
+        writeln!(self.string, "struct Varyings {{").unwrap();
+        writeln!(self.string, "    @builtin(position) position: vec4<f32>,").unwrap();
+        for (index, size) in packed_var_sizes(self.compute_packed_slots(is_varying)).enumerate() {
+            writeln!(self.string, "    @location({}) packed_varying_{}: {},", index, index, packed_ty(size)).unwrap();
+        }
+        writeln!(self.string, "}}\n").unwrap();
+    }
+
+    fn generate_vertex_main(&mut self) {
+        let packed_geometries_slots = self.compute_packed_slots( | kind | matches!(kind, DrawShaderFieldKind::Geometry {..}));
+        let packed_instances_slots = self.compute_packed_slots( | kind | matches!(kind, DrawShaderFieldKind::Instance {..}));
+
+        writeln!(self.string, "@vertex").unwrap();
+        if packed_geometries_slots + packed_instances_slots > 0 {
+            writeln!(self.string, "fn vertex_main(input: VertexInput) -> Varyings {{").unwrap();
+        }
+        else {
+            writeln!(self.string, "fn vertex_main() -> Varyings {{").unwrap();
+        }
+        self.generate_uniform_block_unpack();
+        self.generate_live_unpack();
+
+        let mut geometry_slot = 0;
+        let mut instance_slot = 0;
+        for field in &self.draw_shader_def.fields {
+            let ty = field.ty_expr.ty.borrow();
+            match field.kind {
+                DrawShaderFieldKind::Geometry {..} => {
+                    self.unpack_var(field.ident, ty.as_ref().unwrap(), "input.packed_geometry", packed_geometries_slots, &mut geometry_slot);
+                }
+                DrawShaderFieldKind::Instance {..} => {
+                    self.unpack_var(field.ident, ty.as_ref().unwrap(), "input.packed_instance", packed_instances_slots, &mut instance_slot);
+                }
+                _ => ()
+            }
+        }
+        writeln!(self.string).unwrap();
+
+        let vertex_def = self.shader_registry.draw_shader_method_decl_from_ident(self.draw_shader_def, Ident(live_id!(vertex))).unwrap();
+        writeln!(self.string, "    var output: Varyings;").unwrap();
+        writeln!(self.string, "    output.position = {}();", DisplayFnName(vertex_def.fn_ptr, vertex_def.ident)).unwrap();
+        writeln!(self.string).unwrap();
+
+        let packed_varyings_slots = self.compute_packed_slots(is_varying);
+        let mut varying_slot = 0;
+        for field in &self.draw_shader_def.fields {
+            if is_varying(&field.kind) {
+                self.pack_var(field.ident, field.ty_expr.ty.borrow().as_ref().unwrap(), "output.packed_varying", packed_varyings_slots, &mut varying_slot);
+            }
+        }
+        writeln!(self.string, "    return output;").unwrap();
+        writeln!(self.string, "}}\n").unwrap();
+    }
+
+    fn generate_fragment_main(&mut self) {
+        writeln!(self.string, "@fragment").unwrap();
+        writeln!(self.string, "fn fragment_main(input: Varyings) -> @location(0) vec4<f32> {{").unwrap();
+        self.generate_uniform_block_unpack();
+        self.generate_live_unpack();
+
+        let packed_varyings_slots = self.compute_packed_slots(is_varying);
+        let mut varying_slot = 0;
+        for field in &self.draw_shader_def.fields {
+            if is_varying(&field.kind) {
+                self.unpack_var(field.ident, field.ty_expr.ty.borrow().as_ref().unwrap(), "input.packed_varying", packed_varyings_slots, &mut varying_slot);
+            }
+        }
+        writeln!(self.string).unwrap();
+
+        let pixel_def = self.shader_registry.draw_shader_method_decl_from_ident(self.draw_shader_def, Ident(live_id!(pixel))).unwrap();
+        writeln!(self.string, "    return {}();", DisplayFnName(pixel_def.fn_ptr, pixel_def.ident)).unwrap();
+        writeln!(self.string, "}}").unwrap();
+    }
+
+    fn generate_uniform_block_unpack(&mut self) {
+        for (ident, vec) in self.draw_shader_def.fields_as_uniform_blocks() {
+            let table = format!("{}_table", ident);
+            let mut slots = 0;
+            for (index, _item) in vec {
+                let field = &self.draw_shader_def.fields[index];
+                let ty = field.ty_expr.ty.borrow();
+                write!(self.string, "    {} = ", &DisplayDsIdent(field.ident)).unwrap();
+                self.write_uniform_ty_unpack(ty.as_ref().unwrap(), &table, slots);
+                writeln!(self.string, ";").unwrap();
+                slots += ty.as_ref().unwrap().slots();
+            }
+        }
+    }
+
+    fn generate_live_unpack(&mut self) {
+        let mut slots = 0;
+        for (live_ref, ty) in self.draw_shader_def.all_live_refs.borrow().iter() {
+            write!(self.string, "    {} = ", &live_ref).unwrap();
+            self.write_uniform_ty_unpack(ty, "live_table", slots);
+            writeln!(self.string, ";").unwrap();
+            slots += ty.slots();
+        }
+    }
+
+    fn write_uniform_ty_unpack(&mut self, ty: &Ty, table: &str, s: usize) {
+        let slot = | index: usize | format!("{}[{}].{}", table, index >> 2, component_name(index & 3));
+        match ty {
+            Ty::Bool => write!(self.string, "{} > 0.5", slot(s)).unwrap(),
+            Ty::Int => write!(self.string, "i32({})", slot(s)).unwrap(),
+            Ty::Float | Ty::Enum(_) => write!(self.string, "{}", slot(s)).unwrap(),
+            Ty::Bvec2 | Ty::Bvec3 | Ty::Bvec4 => {
+                let components: Vec<String> = (s..s + ty.slots()).map( | index | format!("{} > 0.5", slot(index))).collect();
+                self.write_ty_lit(ty.maybe_ty_lit().unwrap());
+                write!(self.string, "({})", components.join(", ")).unwrap();
+            }
+            Ty::Ivec2 | Ty::Ivec3 | Ty::Ivec4 => {
+                let components: Vec<String> = (s..s + ty.slots()).map( | index | format!("i32({})", slot(index))).collect();
+                self.write_ty_lit(ty.maybe_ty_lit().unwrap());
+                write!(self.string, "({})", components.join(", ")).unwrap();
+            }
+            Ty::Vec2 | Ty::Vec3 | Ty::Vec4 | Ty::Mat2 | Ty::Mat3 | Ty::Mat4 => {
+                let components: Vec<String> = (s..s + ty.slots()).map(slot).collect();
+                self.write_ty_lit(ty.maybe_ty_lit().unwrap());
+                write!(self.string, "({})", components.join(", ")).unwrap();
+            }
+            _ => panic!("unexpected as initializeable type {:?}", ty),
+        }
+    }
+
+    // WGSL has no multi component swizzle assignment, so packing and unpacking goes per slot
+    fn unpack_var(&mut self, ident: Ident, ty: &Ty, packed_var_name: &str, packed_vars_size: usize, packed_slot: &mut usize) {
+        let scalar = scalar_name(ty);
+        for component in ty_components(&DisplayDsIdent(ident).to_string(), ty) {
+            let packed = packed_slot_expr(packed_var_name, packed_vars_size, *packed_slot);
+            if scalar != "f32" {
+                writeln!(self.string, "    {} = {}({});", component, scalar, packed).unwrap();
+            }
+            else {
+                writeln!(self.string, "    {} = {};", component, packed).unwrap();
+            }
+            *packed_slot += 1;
+        }
+    }
+
+    fn pack_var(&mut self, ident: Ident, ty: &Ty, packed_var_name: &str, packed_vars_size: usize, packed_slot: &mut usize) {
+        let scalar = scalar_name(ty);
+        for component in ty_components(&DisplayDsIdent(ident).to_string(), ty) {
+            let packed = packed_slot_expr(packed_var_name, packed_vars_size, *packed_slot);
+            if scalar != "f32" {
+                writeln!(self.string, "    {} = f32({});", packed, component).unwrap();
+            }
+            else {
+                writeln!(self.string, "    {} = {};", packed, component).unwrap();
+            }
+            *packed_slot += 1;
+        }
+    }
+
+    fn compute_packed_slots(&self, filter: impl Fn(&DrawShaderFieldKind) -> bool) -> usize {
+        let mut slots = 0;
+        for field in &self.draw_shader_def.fields {
+            if filter(&field.kind) {
+                slots += field.ty_expr.ty.borrow().as_ref().unwrap().slots();
+            }
+        }
+        slots
+    }
+
+    pub fn calc_live_slots(&self) -> usize {
+        let mut slots = 0;
+        for (_, ty) in self.draw_shader_def.all_live_refs.borrow().iter() {
+            slots += ty.slots();
+        }
+        slots
+    }
+
+    fn write_var_decl(&mut self, ident: &dyn fmt::Display, ty: &Ty) {
+        self.backend_writer.write_var_decl(&mut self.string, "", false, false, ident, ty);
+    }
+
+    fn write_ty_lit(&mut self, ty_lit: TyLit) {
+        self.backend_writer.write_ty_lit(&mut self.string, ty_lit);
+    }
+}
+
+// geometries and instances used in the pixel shader are forwarded as varyings
+fn is_varying(kind: &DrawShaderFieldKind) -> bool {
+    match kind {
+        DrawShaderFieldKind::Geometry {is_used_in_pixel_shader, ..} => is_used_in_pixel_shader.get(),
+        DrawShaderFieldKind::Instance {is_used_in_pixel_shader, ..} => is_used_in_pixel_shader.get(),
+        DrawShaderFieldKind::Varying {..} => true,
+        _ => false
+    }
+}
+
+fn table_len(slots: usize) -> usize {
+    (slots + 3) >> 2
+}
+
+fn component_name(index: usize) -> &'static str {
+    ["x", "y", "z", "w"][index]
+}
+
+fn packed_var_sizes(mut slots: usize) -> impl Iterator<Item = usize> {
+    std::iter::from_fn(move || {
+        let size = slots.min(4);
+        slots -= size;
+        if size > 0 {Some(size)} else {None}
+    })
+}
+
+fn packed_ty(size: usize) -> &'static str {
+    match size {
+        1 => "f32",
+        2 => "vec2<f32>",
+        3 => "vec3<f32>",
+        _ => "vec4<f32>",
+    }
+}
+
+fn packed_slot_expr(packed_var_name: &str, packed_vars_size: usize, slot: usize) -> String {
+    let index = slot >> 2;
+    if packed_vars_size - (index << 2) == 1 {
+        format!("{}_{}", packed_var_name, index)
+    }
+    else {
+        format!("{}_{}.{}", packed_var_name, index, component_name(slot & 3))
+    }
+}
+
+fn mat_dim(ty: &Ty) -> Option<usize> {
+    match ty {
+        Ty::Mat2 => Some(2),
+        Ty::Mat3 => Some(3),
+        Ty::Mat4 => Some(4),
+        _ => None
+    }
+}
+
+fn scalar_name(ty: &Ty) -> &'static str {
+    match ty {
+        Ty::Bool | Ty::Bvec2 | Ty::Bvec3 | Ty::Bvec4 => "bool",
+        Ty::Int | Ty::Ivec2 | Ty::Ivec3 | Ty::Ivec4 => "i32",
+        _ => "f32"
+    }
+}
+
+// the scalar components of a value in column major order
+fn ty_components(name: &str, ty: &Ty) -> Vec<String> {
+    if let Some(dim) = mat_dim(ty) {
+        return (0..dim * dim).map( | index | format!("{}[{}][{}]", name, index / dim, index % dim)).collect()
+    }
+    match ty.slots() {
+        1 => vec![name.to_string()],
+        slots => (0..slots).map( | index | format!("{}.{}", name, component_name(index))).collect()
+    }
+}
+
+fn cons_fn_name(ty_lit: TyLit, param_tys: &[Ty]) -> String {
+    let mut cons_name = format!("consfn_{}", ty_lit);
+    for param_ty in param_tys {
+        write!(cons_name, "_{}", param_ty).unwrap();
+    }
+    cons_name
+}
+
+// WGSL constructors don't truncate, don't build matrices from a scalar or another size matrix,
+// and don't mix scalars and vectors for matrices, those go through a generated function
+fn cons_fn_is_needed(ty_lit: TyLit, param_tys: &[Ty]) -> bool {
+    let ty = ty_lit.to_ty();
+    let total_slots: usize = param_tys.iter().map( | param_ty | param_ty.slots()).sum();
+    if let Some(dim) = mat_dim(&ty) {
+        return match param_tys {
+            [param_ty] => *param_ty != ty,
+            _ => !(param_tys.iter().all( | param_ty | *param_ty == Ty::Float) && total_slots == dim * dim
+                || param_tys.len() == dim && param_tys.iter().all( | param_ty | param_ty.slots() == dim && mat_dim(param_ty).is_none()))
+        }
+    }
+    match param_tys {
+        [param_ty] => param_ty.slots() != 1 && param_ty.slots() != ty.slots() || mat_dim(param_ty).is_some(),
+        _ => total_slots != ty.slots() || param_tys.iter().any( | param_ty | {
+            mat_dim(param_ty).is_some() || scalar_name(param_ty) != scalar_name(&ty)
+        })
+    }
+}
+
+struct WgslBackendWriter<'a> {
+    pub shader_registry: &'a ShaderRegistry,
+    pub const_table: &'a DrawShaderConstTable,
+    pub cons_fns: BTreeSet<String>,
+    // builtins WGSL lacks or spells differently are emitted as helper functions
+    pub helpers: RefCell<BTreeMap<String, String>>,
+}
+
+impl<'a> WgslBackendWriter<'a> {
+    fn ty_string(&self, ty: &Ty) -> String {
+        let mut string = String::new();
+        match ty {
+            Ty::Struct(struct_ptr) => write!(string, "{}", struct_ptr).unwrap(),
+            Ty::Enum(_) => write!(string, "f32").unwrap(),
+            Ty::Array {elem_ty, len} => write!(string, "array<{}, {}>", self.ty_string(elem_ty), len).unwrap(),
+            Ty::Texture2D => self.write_ty_lit(&mut string, TyLit::Texture2D),
+            Ty::TextureOES => self.write_ty_lit(&mut string, TyLit::TextureOES),
+            _ => self.write_ty_lit(&mut string, ty.maybe_ty_lit().unwrap()),
+        }
+        string
+    }
+
+    fn bool_ty_string(&self, ty: &Ty) -> &'static str {
+        match ty.slots() {
+            1 => "bool",
+            2 => "vec2<bool>",
+            3 => "vec3<bool>",
+            _ => "vec4<bool>",
+        }
+    }
+
+    fn add_helper(&self, name: &str, def: impl FnOnce() -> String) {
+        let mut helpers = self.helpers.borrow_mut();
+        if !helpers.contains_key(name) {
+            helpers.insert(name.to_string(), def());
+        }
+    }
+
+    fn helper_name(ident: Ident, arg_tys: &[Ty]) -> String {
+        let mut name = format!("{}", ident);
+        for arg_ty in arg_tys {
+            write!(name, "_{}", arg_ty).unwrap();
+        }
+        name
+    }
+
+    fn helper_params(&self, arg_tys: &[Ty]) -> String {
+        let mut params = Vec::new();
+        for (index, arg_ty) in arg_tys.iter().enumerate() {
+            params.push(format!("{}: {}", ["x", "y", "z"][index], self.ty_string(arg_ty)));
+        }
+        params.join(", ")
+    }
+
+    fn write_inverse_helper(&self, name: &str, ty: &Ty) {
+        self.add_helper(name, || match ty {
+            Ty::Mat2 => format!(concat!(
+                "fn {}(m: mat2x2<f32>) -> mat2x2<f32> {{\n",
+                "    let det = m[0][0] * m[1][1] - m[1][0] * m[0][1];\n",
+                "    return mat2x2<f32>(m[1][1], -m[0][1], -m[1][0], m[0][0]) * (1.0 / det);\n",
+                "}}\n"
+            ), name),
+            Ty::Mat3 => format!(concat!(
+                "fn {}(m: mat3x3<f32>) -> mat3x3<f32> {{\n",
+                "    let r0 = cross(m[1], m[2]);\n",
+                "    let r1 = cross(m[2], m[0]);\n",
+                "    let r2 = cross(m[0], m[1]);\n",
+                "    return transpose(mat3x3<f32>(r0, r1, r2)) * (1.0 / dot(m[0], r0));\n",
+                "}}\n"
+            ), name),
+            _ => format!(concat!(
+                "fn {}(m: mat4x4<f32>) -> mat4x4<f32> {{\n",
+                "    let b00 = m[0][0] * m[1][1] - m[0][1] * m[1][0];\n",
+                "    let b01 = m[0][0] * m[1][2] - m[0][2] * m[1][0];\n",
+                "    let b02 = m[0][0] * m[1][3] - m[0][3] * m[1][0];\n",
+                "    let b03 = m[0][1] * m[1][2] - m[0][2] * m[1][1];\n",
+                "    let b04 = m[0][1] * m[1][3] - m[0][3] * m[1][1];\n",
+                "    let b05 = m[0][2] * m[1][3] - m[0][3] * m[1][2];\n",
+                "    let b06 = m[2][0] * m[3][1] - m[2][1] * m[3][0];\n",
+                "    let b07 = m[2][0] * m[3][2] - m[2][2] * m[3][0];\n",
+                "    let b08 = m[2][0] * m[3][3] - m[2][3] * m[3][0];\n",
+                "    let b09 = m[2][1] * m[3][2] - m[2][2] * m[3][1];\n",
+                "    let b10 = m[2][1] * m[3][3] - m[2][3] * m[3][1];\n",
+                "    let b11 = m[2][2] * m[3][3] - m[2][3] * m[3][2];\n",
+                "    let det = b00 * b11 - b01 * b10 + b02 * b09 + b03 * b08 - b04 * b07 + b05 * b06;\n",
+                "    return mat4x4<f32>(\n",
+                "        m[1][1] * b11 - m[1][2] * b10 + m[1][3] * b09,\n",
+                "        m[0][2] * b10 - m[0][1] * b11 - m[0][3] * b09,\n",
+                "        m[3][1] * b05 - m[3][2] * b04 + m[3][3] * b03,\n",
+                "        m[2][2] * b04 - m[2][1] * b05 - m[2][3] * b03,\n",
+                "        m[1][2] * b08 - m[1][0] * b11 - m[1][3] * b07,\n",
+                "        m[0][0] * b11 - m[0][2] * b08 + m[0][3] * b07,\n",
+                "        m[3][2] * b02 - m[3][0] * b05 - m[3][3] * b01,\n",
+                "        m[2][0] * b05 - m[2][2] * b02 + m[2][3] * b01,\n",
+                "        m[1][0] * b10 - m[1][1] * b08 + m[1][3] * b06,\n",
+                "        m[0][1] * b08 - m[0][0] * b10 - m[0][3] * b06,\n",
+                "        m[3][0] * b04 - m[3][1] * b02 + m[3][3] * b00,\n",
+                "        m[2][1] * b02 - m[2][0] * b04 - m[2][3] * b00,\n",
+                "        m[1][1] * b07 - m[1][0] * b09 - m[1][2] * b06,\n",
+                "        m[0][0] * b09 - m[0][1] * b07 + m[0][2] * b06,\n",
+                "        m[3][1] * b01 - m[3][0] * b03 - m[3][2] * b00,\n",
+                "        m[2][0] * b03 - m[2][1] * b01 + m[2][2] * b00\n",
+                "    ) * (1.0 / det);\n",
+                "}}\n"
+            ), name),
+        });
+    }
+}
+
+impl<'a> BackendWriter for WgslBackendWriter<'a> {
+    fn get_struct_cons_type(&self) -> StructConsType {
+        StructConsType::Paren
+    }
+
+    fn enum_is_float(&self) -> bool {
+        true
+    }
+
+    fn needs_mul_fn_for_matrix_multiplication(&self) -> bool {
+        false
+    }
+
+    fn needs_unpack_for_matrix_multiplication(&self) -> bool {
+        false
+    }
+
+    fn const_table_is_vec4(&self) -> bool {
+        true
+    }
+
+    fn use_cons_fn(&self, what: &str) -> bool {
+        self.cons_fns.contains(what)
+    }
+
+    fn cond_expr_is_select(&self) -> bool {
+        true
+    }
+
+    fn inout_params_are_pointers(&self) -> bool {
+        true
+    }
+
+    fn assignment_is_stmt(&self) -> bool {
+        true
+    }
+
+    fn needs_discard_for_unused_values(&self) -> bool {
+        true
+    }
+
+    fn write_let_prefix(&self, string: &mut String) {
+        write!(string, "var ").unwrap();
+    }
+
+    fn write_fn_def_start(&self, string: &mut String, ident: &dyn fmt::Display, _return_ty: &Ty) {
+        write!(string, "fn {}", ident).unwrap();
+    }
+
+    fn write_fn_def_return_ty(&self, string: &mut String, return_ty: &Ty) {
+        if *return_ty != Ty::Void {
+            write!(string, " -> {}", self.ty_string(return_ty)).unwrap();
+        }
+    }
+
+    // function parameters are immutable in WGSL, so they are copied into a local in the prologue
+    fn write_fn_def_param(
+        &self,
+        string: &mut String,
+        sep: &'static str,
+        is_inout: bool,
+        ident: &dyn fmt::Display,
+        ty: &Ty,
+    ) -> bool {
+        match ty {
+            Ty::DrawShader(_) | Ty::ClosureDef {..} | Ty::ClosureDecl => false,
+            Ty::Texture2D | Ty::TextureOES => self.write_var_decl(string, sep, false, false, ident, ty),
+            _ if is_inout => self.write_var_decl(string, sep, true, false, ident, ty),
+            _ => self.write_var_decl(string, sep, false, false, &format!("p_{}", ident), ty),
+        }
+    }
+
+    fn write_fn_def_param_prologue(&self, string: &mut String, is_inout: bool, ident: &dyn fmt::Display, ty: &Ty) {
+        match ty {
+            Ty::Texture2D | Ty::TextureOES => (),
+            _ if is_inout => (),
+            _ => writeln!(string, "    var {0} = p_{0};", ident).unwrap(),
+        }
+    }
+
+    fn write_for_stmt_head(&self, string: &mut String, ident: Ident, from: i32, to: i32, step: i32) {
+        write!(
+            string,
+            "for (var {0}: i32 = {1}; {0} {2} {3}; {0} {4} {5}) ",
+            ident,
+            if from <= to {from} else {from - 1},
+            if from <= to {"<"} else {">="},
+            to,
+            if step > 0 {"+="} else {"-="},
+            step.abs()
+        )
+            .unwrap();
+    }
+
+    fn write_var_decl(
+        &self,
+        string: &mut String,
+        sep: &'static str,
+        is_inout: bool,
+        _is_packed: bool,
+        ident: &dyn fmt::Display,
+        ty: &Ty,
+    ) -> bool {
+        match ty {
+            Ty::DrawShader(_) | Ty::ClosureDef {..} | Ty::ClosureDecl => false,
+            Ty::Void => {
+                write!(string, "{}{}", sep, ident).unwrap();
+                true
+            }
+            _ if is_inout => {
+                write!(string, "{}{}: ptr<function, {}>", sep, ident, self.ty_string(ty)).unwrap();
+                true
+            }
+            _ => {
+                write!(string, "{}{}: {}", sep, ident, self.ty_string(ty)).unwrap();
+                true
+            }
+        }
+    }
+
+    fn write_call_expr_hidden_args(&self, _string: &mut String, _hidden_args: &BTreeSet<HiddenArgKind >, _sep: &str) {
+    }
+
+    fn write_fn_def_hidden_params(&self, _string: &mut String, _hidden_args: &BTreeSet<HiddenArgKind >, _sep: &str) {
+    }
+
+    fn generate_live_value_prefix(&self, _string: &mut String) {
+    }
+
+    fn generate_draw_shader_field_expr(&self, string: &mut String, field_ident: Ident, _ty: &Ty) {
+        write!(string, "{}", &DisplayDsIdent(field_ident)).unwrap();
+    }
+
+    fn write_ty_lit(&self, string: &mut String, ty_lit: TyLit) {
+        write!(
+            string,
+            "{}",
+            match ty_lit {
+                TyLit::Bool => "bool",
+                TyLit::Int => "i32",
+                TyLit::Float => "f32",
+                TyLit::Bvec2 => "vec2<bool>",
+                TyLit::Bvec3 => "vec3<bool>",
+                TyLit::Bvec4 => "vec4<bool>",
+                TyLit::Ivec2 => "vec2<i32>",
+                TyLit::Ivec3 => "vec3<i32>",
+                TyLit::Ivec4 => "vec4<i32>",
+                TyLit::Vec2 => "vec2<f32>",
+                TyLit::Vec3 => "vec3<f32>",
+                TyLit::Vec4 => "vec4<f32>",
+                TyLit::Mat2 => "mat2x2<f32>",
+                TyLit::Mat3 => "mat3x3<f32>",
+                TyLit::Mat4 => "mat4x4<f32>",
+                TyLit::Texture2D => "texture_2d<f32>",
+                TyLit::TextureOES => "texture_external",
+            }
+        )
+            .unwrap();
+    }
+
+    fn write_builtin_call_ident(&self, string: &mut String, ident: Ident, arg_exprs: &[Expr]) {
+        let arg_tys: Vec<Ty> = arg_exprs.iter().map( | arg_expr | arg_expr.ty.borrow().clone().unwrap()).collect();
+        match ident.0 {
+            live_id!(atan) if arg_tys.len() == 2 => write!(string, "atan2").unwrap(),
+            live_id!(dFdx) => write!(string, "dpdx").unwrap(),
+            live_id!(dFdy) => write!(string, "dpdy").unwrap(),
+            live_id!(inversesqrt) => write!(string, "inverseSqrt").unwrap(),
+            live_id!(faceforward) => write!(string, "faceForward").unwrap(),
+            live_id!(mod) => {
+                let name = Self::helper_name(ident, &arg_tys);
+                self.add_helper(&name, || format!(
+                    "fn {}({}) -> {} {{\n    return x - y * floor(x / y);\n}}\n",
+                    name,
+                    self.helper_params(&arg_tys),
+                    self.ty_string(&arg_tys[0])
+                ));
+                write!(string, "{}", name).unwrap();
+            }
+            live_id!(lessThan) | live_id!(lessThanEqual) | live_id!(greaterThan) |
+            live_id!(greaterThanEqual) | live_id!(equal) | live_id!(notEqual) => {
+                let op = match ident.0 {
+                    live_id!(lessThan) => "<",
+                    live_id!(lessThanEqual) => "<=",
+                    live_id!(greaterThan) => ">",
+                    live_id!(greaterThanEqual) => ">=",
+                    live_id!(equal) => "==",
+                    _ => "!=",
+                };
+                let name = Self::helper_name(ident, &arg_tys);
+                self.add_helper(&name, || format!(
+                    "fn {}({}) -> {} {{\n    return x {} y;\n}}\n",
+                    name,
+                    self.helper_params(&arg_tys),
+                    self.bool_ty_string(&arg_tys[0]),
+                    op
+                ));
+                write!(string, "{}", name).unwrap();
+            }
+            live_id!(not) => {
+                let name = Self::helper_name(ident, &arg_tys);
+                self.add_helper(&name, || format!(
+                    "fn {}({}) -> {} {{\n    return !x;\n}}\n",
+                    name,
+                    self.helper_params(&arg_tys),
+                    self.ty_string(&arg_tys[0])
+                ));
+                write!(string, "{}", name).unwrap();
+            }
+            live_id!(matrixCompMult) => {
+                let name = Self::helper_name(ident, &arg_tys);
+                let dim = mat_dim(&arg_tys[0]).unwrap();
+                self.add_helper(&name, || {
+                    let columns: Vec<String> = (0..dim).map( | col | format!("x[{0}] * y[{0}]", col)).collect();
+                    format!(
+                        "fn {0}({1}) -> {2} {{\n    return {2}({3});\n}}\n",
+                        name,
+                        self.helper_params(&arg_tys),
+                        self.ty_string(&arg_tys[0]),
+                        columns.join(", ")
+                    )
+                });
+                write!(string, "{}", name).unwrap();
+            }
+            live_id!(inverse) => {
+                let name = Self::helper_name(ident, &arg_tys);
+                self.write_inverse_helper(&name, &arg_tys[0]);
+                write!(string, "{}", name).unwrap();
+            }
+            live_id!(clamp) | live_id!(min) | live_id!(max) | live_id!(step) | live_id!(smoothstep)
+                if arg_tys.iter().any( | arg_ty | *arg_ty != arg_tys[0]) => {
+                // GLSL allows mixing scalars with vectors here, WGSL wants them splatted
+                let name = Self::helper_name(ident, &arg_tys);
+                let ret_ty = arg_tys.iter().max_by_key( | arg_ty | arg_ty.slots()).unwrap().clone();
+                self.add_helper(&name, || {
+                    let args: Vec<String> = arg_tys.iter().enumerate().map( | (index, arg_ty) | {
+                        let arg = ["x", "y", "z"][index];
+                        if *arg_ty != ret_ty {
+                            format!("{}({})", self.ty_string(&ret_ty), arg)
+                        }
+                        else {
+                            arg.to_string()
+                        }
+                    }).collect();
+                    format!(
+                        "fn {}({}) -> {} {{\n    return {}({});\n}}\n",
+                        name,
+                        self.helper_params(&arg_tys),
+                        self.ty_string(&ret_ty),
+                        ident,
+                        args.join(", ")
+                    )
+                });
+                write!(string, "{}", name).unwrap();
+            }
+            live_id!(sample2d) | live_id!(sample2d_rt) => {
+                self.add_helper(&ident.to_string(), || format!(
+                    "fn {}(tex: texture_2d<f32>, pos: vec2<f32>) -> vec4<f32> {{\n    return textureSampleLevel(tex, default_sampler, pos, 0.0);\n}}\n",
+                    ident
+                ));
+                write!(string, "{}", ident).unwrap();
+            }
+            live_id!(sample2dOES) => {
+                self.add_helper(&ident.to_string(), || format!(
+                    "fn {}(tex: texture_external, pos: vec2<f32>) -> vec4<f32> {{\n    return textureSampleBaseClampToEdge(tex, default_sampler, pos);\n}}\n",
+                    ident
+                ));
+                write!(string, "{}", ident).unwrap();
+            }
+            _ => write!(string, "{}", ident).unwrap()
+        }
+    }
+}
@@ -14,6 +14,7 @@ pub mod lhs_check;
 pub mod swizzle;
 pub mod util;
 pub mod generate;
+pub mod generate_wgsl;
 
 #[cfg(any(target_os = "android", target_os = "linux", target_arch = "wasm32"))]
 pub mod generate_glsl;
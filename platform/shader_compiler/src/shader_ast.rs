@@ -858,6 +858,10 @@ impl DrawShaderDef {
 }
 
 impl BinOp {
+    pub fn is_assign(&self) -> bool {
+        matches!(self, BinOp::Assign | BinOp::AddAssign | BinOp::SubAssign | BinOp::MulAssign | BinOp::DivAssign)
+    }
+    
     pub fn from_assign_op(token: LiveToken) -> Option<BinOp> {
         match token {
             LiveToken::Punct(live_id!( =)) => Some(BinOp::Assign),
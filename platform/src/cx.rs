@@ -35,6 +35,7 @@ use {
         cx_api::CxOsOp,
         area::Area,
         gpu_info::GpuInfo,
+        monitor::MonitorInfo,
         window::CxWindowPool,
         draw_list::CxDrawListPool,
         web_socket::WebSocket,
@@ -58,6 +59,7 @@ pub struct Cx {
     pub (crate) gpu_info: GpuInfo,
     pub (crate) xr_capabilities: XrCapabilities,
     pub (crate) cpu_cores: usize,
+    pub (crate) monitors: Vec<MonitorInfo>,
     pub null_texture: Texture,
     pub windows: CxWindowPool,
     pub passes: CxPassPool,
@@ -213,6 +215,7 @@ impl Cx {
         Self {
             null_texture,
             cpu_cores: 8,
+            monitors: Vec::new(),
             in_makepad_studio: false,
             os_type: OsType::Unknown,
             gpu_info: Default::default(),
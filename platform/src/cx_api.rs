@@ -6,8 +6,9 @@ use {
         cursor::MouseCursor,
         cx::{Cx, CxRef, OsType, XrCapabilities},
         draw_list::DrawListId,
-        event::{DragItem, HttpRequest, NextFrame, Timer, Trigger, VideoSource},
+        event::{ClipboardItem, ClipboardSelection, DragItem, HttpRequest, NextFrame, Timer, Trigger, VideoSource},
         gpu_info::GpuInfo,
+        monitor::MonitorInfo,
         macos_menu::MacosMenu,
         makepad_futures::executor::Spawner,
         makepad_live_id::*,
@@ -67,6 +68,12 @@ pub enum CxOsOp {
     StartDragging(Vec<DragItem>),
     UpdateMacosMenu(MacosMenu),
     ShowClipboardActions(String),
+    CopyToClipboard(ClipboardSelection, Vec<ClipboardItem>),
+    ReadClipboard {
+        request_id: LiveId,
+        selection: ClipboardSelection,
+        mime_types: Vec<String>,
+    },
 
     HttpRequest {
         request_id: LiveId,
@@ -128,6 +135,10 @@ impl Cx {
     pub fn gpu_info(&self) -> &GpuInfo {
         &self.gpu_info
     }
+    /// The monitors the platform reported, empty on platforms that don't enumerate them
+    pub fn monitors(&self) -> &[MonitorInfo] {
+        &self.monitors
+    }
 
     pub fn update_macos_menu(&mut self, menu: MacosMenu) {
         self.platform_ops.push(CxOsOp::UpdateMacosMenu(menu));
@@ -165,6 +176,21 @@ impl Cx {
             .push(CxOsOp::ShowClipboardActions(selected));
     }
 
+    /// Puts the items on the clipboard, each one is a representation of the same contents
+    pub fn copy_to_clipboard(&mut self, selection: ClipboardSelection, items: Vec<ClipboardItem>) {
+        self.platform_ops.push(CxOsOp::CopyToClipboard(selection, items));
+    }
+    
+    /// Asks for the clipboard contents, answered with an Event::ClipboardRead holding the first
+    /// of the mime types that is available
+    pub fn read_clipboard(&mut self, request_id: LiveId, selection: ClipboardSelection, mime_types: &[&str]) {
+        self.platform_ops.push(CxOsOp::ReadClipboard {
+            request_id,
+            selection,
+            mime_types: mime_types.iter().map( | m | m.to_string()).collect(),
+        });
+    }
+    
     pub fn start_dragging(&mut self, items: Vec<DragItem>) {
         self.platform_ops.iter().for_each(|p| {
             if let CxOsOp::StartDragging { .. } = p {
@@ -194,6 +220,36 @@ impl Cx {
         self.fingers.sweep_unlock(value);
     }
 
+    /// Keeps finger input in a pass to `draw_list_id` and the draw lists drawn into it,
+    /// modal overlays use this to block everything below them. `None` lifts the lock
+    pub fn set_input_lock(&mut self, pass_id: PassId, draw_list_id: Option<DrawListId>) {
+        self.fingers.input_locks.retain( | (lock_pass_id, _) | *lock_pass_id != pass_id);
+        if let Some(draw_list_id) = draw_list_id {
+            self.fingers.input_locks.push((pass_id, draw_list_id));
+        }
+    }
+
+    pub fn is_input_locked_out(&self, area: Area) -> bool {
+        let Some(mut draw_list_id) = area.draw_list_id() else {
+            return false
+        };
+        let Some(pass_id) = self.draw_lists[draw_list_id].pass_id else {
+            return false
+        };
+        let Some((_, lock)) = self.fingers.input_locks.iter().find( | (lock_pass_id, _) | *lock_pass_id == pass_id) else {
+            return false
+        };
+        loop {
+            if draw_list_id == *lock {
+                return false
+            }
+            match self.draw_lists[draw_list_id].codeflow_parent_id {
+                Some(parent_id) => draw_list_id = parent_id,
+                None => return true
+            }
+        }
+    }
+
     pub fn start_timeout(&mut self, interval: f64) -> Timer {
         self.timer_id += 1;
         self.platform_ops.push(CxOsOp::StartTimer {
@@ -257,6 +313,38 @@ impl Cx {
         1.0
     }
 
+    /// The window a pass ends up on, walking up through parent passes
+    pub fn get_pass_window_id(&self, pass_id: PassId) -> Option<WindowId> {
+        let mut pass_id_walk = pass_id;
+        for _ in 0..25 {
+            match self.passes[pass_id_walk].parent {
+                CxPassParent::Window(window_id) => {
+                    return Some(window_id)
+                }
+                CxPassParent::Pass(next_pass_id) => {
+                    pass_id_walk = next_pass_id;
+                }
+                _ => {
+                    break;
+                }
+            }
+        }
+        None
+    }
+    
+    /// Returns true if `window_id` was given `owner` (or a window owned by it) through `WindowHandle::set_owner`
+    pub fn window_is_owned_by(&self, window_id: WindowId, owner: WindowId) -> bool {
+        let mut window_id_walk = window_id;
+        for _ in 0..25 {
+            match self.windows[window_id_walk].owner_window {
+                Some(owner_id) if owner_id == owner => return true,
+                Some(owner_id) => window_id_walk = owner_id,
+                None => break
+            }
+        }
+        false
+    }
+    
     pub fn redraw_pass_and_parent_passes(&mut self, pass_id: PassId) {
         let mut walk_pass_id = pass_id;
         loop {
@@ -322,14 +322,13 @@ impl CxDrawList {
                             }
                             if diff {continue}
                             for i in 0..sh.mapping.textures.len() {
+                                // a drawcall without a texture never gets one bound later on
                                 fn neq(a:&Option<Texture>, b:&Option<Texture>)->bool{
-                                    if let Some(a) = a{
-                                        if let Some(b) = b{
-                                            return a.texture_id() != b.texture_id()
-                                        }
-                                        return true
+                                    match (a, b) {
+                                        (Some(a), Some(b)) => a.texture_id() != b.texture_id(),
+                                        (None, None) => false,
+                                        _ => true
                                     }
-                                    return false
                                 }
                                 if neq(&draw_call.texture_slots[i], &draw_vars.texture_slots[i]) {
                                     diff = true;
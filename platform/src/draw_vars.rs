@@ -18,7 +18,7 @@ use {
         makepad_math::*,
         cx::Cx,
         texture::{Texture},
-        geometry::GeometryId,
+        geometry::{Geometry, GeometryId},
         area::Area,
         geometry::{GeometryFields},
         live_traits::*,
@@ -132,6 +132,12 @@ impl DrawVars {
     pub fn empty_texture(&mut self, slot: usize) {
         self.texture_slots[slot] = None;
     }
+    
+    /// Draws with `geometry` instead of the one from the shader's geometry field,
+    /// for shaders that draw many different meshes.
+    pub fn set_geometry(&mut self, geometry: &Geometry) {
+        self.geometry_id = Some(geometry.geometry_id());
+    }
 
     pub fn redraw(&self, cx: &mut Cx) {
         self.area.redraw(cx);
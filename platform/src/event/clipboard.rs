@@ -0,0 +1,69 @@
+use {
+    std::rc::Rc,
+    crate::makepad_live_id::*,
+};
+
+/// Which system selection a clipboard operation works on
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ClipboardSelection {
+    #[default]
+    Clipboard,
+    /// The X11 selection that follows whatever is selected and pastes on middle click,
+    /// platforms without one ignore it
+    Primary,
+}
+
+/// One representation of the clipboard contents, a copy usually offers a few of these
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClipboardItem {
+    pub mime_type: String,
+    pub data: Rc<Vec<u8>>,
+}
+
+impl ClipboardItem {
+    pub const TEXT: &'static str = "text/plain;charset=utf-8";
+    pub const HTML: &'static str = "text/html";
+    pub const PNG: &'static str = "image/png";
+    
+    pub fn new(mime_type: &str, data: Vec<u8>) -> Self {
+        Self {mime_type: mime_type.to_string(), data: Rc::new(data)}
+    }
+    
+    pub fn text(text: &str) -> Self {
+        Self::new(Self::TEXT, text.as_bytes().to_vec())
+    }
+    
+    pub fn html(html: &str) -> Self {
+        Self::new(Self::HTML, html.as_bytes().to_vec())
+    }
+    
+    /// Takes encoded png data, decoding is up to the reader
+    pub fn png(data: Vec<u8>) -> Self {
+        Self::new(Self::PNG, data)
+    }
+    
+    pub fn is_text(&self) -> bool {
+        is_text_mime_type(&self.mime_type)
+    }
+    
+    /// The data as a string, for the text/ types
+    pub fn as_text(&self) -> Option<String> {
+        if !self.is_text() {
+            return None
+        }
+        Some(String::from_utf8_lossy(&self.data).to_string())
+    }
+}
+
+/// Mime types that plain text can be served as and read from, the X11 names included
+pub fn is_text_mime_type(mime_type: &str) -> bool {
+    mime_type.starts_with("text/plain") || mime_type == "UTF8_STRING" || mime_type == "STRING" || mime_type == "TEXT"
+}
+
+#[derive(Clone, Debug)]
+pub struct ClipboardReadEvent {
+    pub request_id: LiveId,
+    pub selection: ClipboardSelection,
+    /// The first of the requested mime types the clipboard had, None if it had none of them
+    pub item: Option<ClipboardItem>,
+}
@@ -11,12 +11,14 @@ use {
         },
         cx::Cx,
         area::Area,
+        window::WindowId,
     },
 };
 
 
 #[derive(Clone, Debug)]
 pub struct DragEvent {
+    pub window_id: WindowId,
     pub modifiers: KeyModifiers,
     pub handled: Cell<bool>,
     pub abs: DVec2,
@@ -26,6 +28,7 @@ pub struct DragEvent {
 
 #[derive(Clone, Debug)]
 pub struct DropEvent {
+    pub window_id: WindowId,
     pub modifiers: KeyModifiers,
     pub handled: Cell<bool>,
     pub abs: DVec2,
@@ -34,6 +37,7 @@ pub struct DropEvent {
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct DragHitEvent {
+    pub window_id: WindowId,
     pub modifiers: KeyModifiers,
     pub abs: DVec2,
     pub rect: Rect,
@@ -44,12 +48,20 @@ pub struct DragHitEvent {
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct DropHitEvent {
+    pub window_id: WindowId,
     pub modifiers: KeyModifiers,
     pub abs: DVec2,
     pub rect: Rect,
     pub items: Rc<Vec<DragItem >>,
 }
 
+/// A drag we started ended over a spot where none of our windows could take it,
+/// `position` is in the same screen space as window positions so it can be used to place a new window.
+#[derive(Clone, Debug)]
+pub struct DropOutsideEvent {
+    pub position: DVec2,
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum DragState {
     In,
@@ -117,6 +129,7 @@ impl Event {
                         cx.drag_drop.next_drag_area = area;
                         event.handled.set(true);
                         DragHit::Drag(DragHitEvent {
+                            window_id: event.window_id,
                             rect,
                             modifiers: event.modifiers,
                             abs: event.abs,
@@ -127,6 +140,7 @@ impl Event {
                     } else {
                         //log!("drag_hist_with_options: Drag, in drag area, event not handled or rect ({:?}) doesn't contain ({},{}) with margin {:?}",rect,event.abs.x,event.abs.y,options.margin);
                         DragHit::Drag(DragHitEvent {
+                            window_id: event.window_id,
                             rect,
                             modifiers: event.modifiers,
                             state: DragState::Out,
@@ -141,6 +155,7 @@ impl Event {
                         cx.drag_drop.next_drag_area = area;
                         event.handled.set(true);
                         DragHit::Drag(DragHitEvent {
+                            window_id: event.window_id,
                             modifiers: event.modifiers,
                             rect,
                             state: DragState::In,
@@ -161,6 +176,7 @@ impl Event {
                     cx.drag_drop.next_drag_area = Area::default();
                     event.handled.set(true);
                     DragHit::Drop(DropHitEvent {
+                        window_id: event.window_id,
                         modifiers: event.modifiers,
                         rect,
                         abs: event.abs,
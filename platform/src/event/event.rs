@@ -16,6 +16,7 @@ use {
             drag_drop::*,
             network::*,
             video_playback::*,
+            clipboard::*,
         },
         action::ActionsBuf,
         animator::Ease,
@@ -23,6 +24,7 @@ use {
         midi::MidiPortsEvent,
         video::VideoInputsEvent,
         draw_list::DrawListId,
+        window::WindowId,
     },
 };
 
@@ -154,12 +156,15 @@ pub enum Event {
     KeyDown(KeyEvent),
     KeyUp(KeyEvent),
     TextInput(TextInputEvent),
+    TextPreedit(TextPreeditEvent),
     TextCopy(TextClipboardEvent),
     TextCut(TextClipboardEvent),
+    ClipboardRead(ClipboardReadEvent),
 
     Drag(DragEvent),
     Drop(DropEvent),
     DragEnd,
+    DropOutside(DropOutsideEvent),
 
     Actions(ActionsBuf),
     AudioDevices(AudioDevicesEvent),
@@ -249,6 +254,9 @@ impl Event{
 
             #[cfg(target_arch = "wasm32")]
             51=>"ToWasmMsg",
+            52=>"TextPreedit",
+            53=>"ClipboardRead",
+            54=>"DropOutside",
             _=>panic!()
         }
     }
@@ -318,6 +326,9 @@ impl Event{
 
             #[cfg(target_arch = "wasm32")]
             Self::ToWasmMsg(_)=>51,
+            Self::TextPreedit(_)=>52,
+            Self::ClipboardRead(_)=>53,
+            Self::DropOutside(_)=>54,
         }
     }
 }
@@ -330,6 +341,7 @@ pub enum Hit{
     KeyUp(KeyEvent),
     Trigger(TriggerHitEvent),
     TextInput(TextInputEvent),
+    TextPreedit(TextPreeditEvent),
     TextCopy(TextClipboardEvent),
     TextCut(TextClipboardEvent),
 
@@ -365,6 +377,24 @@ impl Event{
             _=>false
         }
     }
+    
+    /// The window an input or window event is about, None for events that go to all windows
+    pub fn window_id(&self)->Option<WindowId>{
+        match self{
+            Self::WindowCloseRequested(e)=>Some(e.window_id),
+            Self::WindowClosed(e)=>Some(e.window_id),
+            Self::WindowGeomChange(e)=>Some(e.window_id),
+            Self::WindowDragQuery(e)=>Some(e.window_id),
+            Self::MouseDown(e)=>Some(e.window_id),
+            Self::MouseMove(e)=>Some(e.window_id),
+            Self::MouseUp(e)=>Some(e.window_id),
+            Self::TouchUpdate(e)=>Some(e.window_id),
+            Self::Scroll(e)=>Some(e.window_id),
+            Self::Drag(e)=>Some(e.window_id),
+            Self::Drop(e)=>Some(e.window_id),
+            _=>None
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
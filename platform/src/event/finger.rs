@@ -26,6 +26,8 @@ use {
         window::WindowId,
         cx::Cx,
         area::Area,
+        pass::PassId,
+        draw_list::DrawListId,
     },
 };
 
@@ -217,6 +219,7 @@ pub struct CxFingers {
     tap: CxDigitTap,
     hovers: Vec<CxDigitHover>,
     sweep_lock: Option<Area>,
+    pub (crate) input_locks: Vec<(PassId, DrawListId)>,
 }
 
 impl CxFingers {
@@ -632,6 +635,14 @@ impl Event {
         if !area.is_valid(cx) {
             return Hit::Nothing
         }
+        if cx.is_input_locked_out(area) {
+            match self {
+                Event::MouseDown(_) | Event::MouseMove(_) | Event::MouseUp(_) | Event::Scroll(_) | Event::TouchUpdate(_) => {
+                    return Hit::Nothing
+                }
+                _ => ()
+            }
+        }
         match self {
             Event::KeyFocus(kf) => {
                 if area == kf.prev {
@@ -656,6 +667,11 @@ impl Event {
                     return Hit::TextInput(ti.clone())
                 }
             },
+            Event::TextPreedit(tp) => {
+                if cx.keyboard.has_key_focus(area) {
+                    return Hit::TextPreedit(tp.clone())
+                }
+            },
             Event::TextCopy(tc) => {
                 if cx.keyboard.has_key_focus(area) {
                     return Hit::TextCopy(tc.clone());
@@ -120,6 +120,15 @@ pub struct TextInputEvent {
     pub was_paste: bool
 }
 
+/// Composition text from an input method that has not been committed yet.
+/// An empty `text` means the composition ended; the result arrives as a `TextInputEvent`.
+#[derive(Clone, Debug, Default, SerBin, DeBin, SerJson, DeJson, PartialEq)]
+pub struct TextPreeditEvent {
+    pub text: String,
+    /// caret position inside `text`, in chars
+    pub cursor: usize,
+}
+
 #[derive(Clone, Debug)]
 pub struct TextClipboardEvent {
     pub response: Rc<RefCell<Option<String>>>
@@ -38,6 +38,7 @@ mod cursor;
 mod macos_menu;
 mod animator;
 mod gpu_info;
+mod monitor;
 mod geometry;
 mod debug;
 mod component_map;
@@ -129,6 +130,7 @@ pub use {
         scope::*,
         draw_list::{
             CxDrawItem,
+            CxDrawKind,
             CxRectArea,
             CxDrawCall,
             DrawList,
@@ -169,6 +171,7 @@ pub use {
             KeyModifiers,
             DrawEvent,
             DigitDevice,
+            DigitId,
             MouseDownEvent,
             MouseMoveEvent,
             MouseUpEvent,
@@ -185,7 +188,11 @@ pub use {
             KeyEvent,
             KeyFocusEvent,
             TextInputEvent,
+            TextPreeditEvent,
             TextClipboardEvent,
+            ClipboardSelection,
+            ClipboardItem,
+            ClipboardReadEvent,
             WindowCloseRequestedEvent,
             WindowClosedEvent,
             WindowDragQueryResponse,
@@ -195,6 +202,7 @@ pub use {
             XRUpdateEvent,
             DragEvent,
             DropEvent,
+            DropOutsideEvent,
             DragState,
             DragItem,
             DragResponse,
@@ -212,14 +220,15 @@ pub use {
         cursor::MouseCursor,
         macos_menu::MacosMenu,
         draw_matrix::DrawMatrix,
-        window::WindowHandle,
+        window::{WindowHandle, WindowId},
         pass::{
             PassId,
             CxPassParent,
             CxPassRect,
             Pass,
             PassClearColor,
-            PassClearDepth
+            PassClearDepth,
+            PassMatrixMode
         },
         texture::{
             Texture,
@@ -265,6 +274,7 @@ pub use {
             Geometry,
         },
         gpu_info::GpuPerformance,       
+        monitor::MonitorInfo,
     },
 };
 
@@ -221,6 +221,15 @@ impl Cx {
         });
     }
     
+    /// Queues new source for a registered live file, it gets picked up by
+    /// `handle_live_edit` on the next poll just like a change from the file watcher
+    pub fn send_live_file_change(&mut self, file_name: &str, content: String){
+        let _ = self.live_file_change_sender.send(vec![LiveFileChange{
+            file_name: file_name.to_string(),
+            content
+        }]);
+    }
+
     pub fn handle_live_edit(&mut self)->bool{
         // ok so we have a life filechange
         // now what. now we need to 'reload' our entire live system.. how.
@@ -0,0 +1,34 @@
+use crate::makepad_math::{DVec2, dvec2};
+
+/// A display attached to the system, as reported by the platform
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MonitorInfo {
+    pub name: String,
+    pub is_primary: bool,
+    /// Top left of the monitor on the virtual desktop, in physical pixels
+    pub position: DVec2,
+    /// Size of the monitor in physical pixels
+    pub size: DVec2,
+    /// Physical size in millimeters, zero when the monitor doesn't report it
+    pub physical_size_mm: DVec2,
+    pub dpi_factor: f64,
+    /// Refresh rate in Hz, zero when unknown
+    pub refresh_rate: f64,
+}
+
+impl MonitorInfo {
+    pub fn contains(&self, pos: DVec2) -> bool {
+        pos.x >= self.position.x && pos.y >= self.position.y &&
+        pos.x < self.position.x + self.size.x && pos.y < self.position.y + self.size.y
+    }
+    
+    /// Area in pixels that a rect on the virtual desktop shares with this monitor
+    pub fn overlap_area(&self, pos: DVec2, size: DVec2) -> f64 {
+        let min = dvec2(pos.x.max(self.position.x), pos.y.max(self.position.y));
+        let max = dvec2(
+            (pos.x + size.x).min(self.position.x + self.size.x),
+            (pos.y + size.y).min(self.position.y + self.size.y)
+        );
+        (max.x - min.x).max(0.0) * (max.y - min.y).max(0.0)
+    }
+}
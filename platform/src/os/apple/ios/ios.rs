@@ -29,6 +29,8 @@ use {
         window::CxWindowPool,
         event::{
             Event,
+            ClipboardReadEvent,
+            ClipboardSelection,
             NetworkResponseChannel
         },
         cx_api::{CxOsApi, CxOsOp},
@@ -311,6 +313,15 @@ impl Cx {
                 CxOsOp::ShowClipboardActions(_request) => {
                     crate::log!("Show clipboard actions not supported yet");
                 }
+                // there is no primary selection, it follows every selection made so don't log it
+                CxOsOp::CopyToClipboard(ClipboardSelection::Primary, _) => {
+                }
+                CxOsOp::CopyToClipboard(_, _) => {
+                    crate::log!("Clipboard items not supported yet");
+                }
+                CxOsOp::ReadClipboard {request_id, selection, ..} => {
+                    self.call_event_handler(&Event::ClipboardRead(ClipboardReadEvent {request_id, selection, item: None}));
+                }
                 CxOsOp::PrepareVideoPlayback(_, _, _, _, _) => todo!(),
                 CxOsOp::BeginVideoPlayback(_) => todo!(),
                 CxOsOp::PauseVideoPlayback(_) => todo!(),
@@ -39,6 +39,8 @@ use {
             WindowGeom,
             MouseUpEvent,
             Event,
+            ClipboardReadEvent,
+            ClipboardSelection,
             NetworkResponseChannel
         },
         window::CxWindowPool,
@@ -382,6 +384,9 @@ impl Cx {
                 self.call_event_handler(&Event::Drop(e));
                 self.drag_drop.cycle_drag();
             }
+            MacosEvent::DropOutside(e) => {
+                self.call_event_handler(&Event::DropOutside(e));
+            }
             MacosEvent::DragEnd => {
                 // lets send mousebutton ups to fix missing it.
                 // TODO! make this more resilient
@@ -517,6 +522,15 @@ impl Cx {
                 CxOsOp::ShowClipboardActions(_request) => {
                     crate::log!("Show clipboard actions not supported yet");
                 }
+                // there is no primary selection, it follows every selection made so don't log it
+                CxOsOp::CopyToClipboard(ClipboardSelection::Primary, _) => {
+                }
+                CxOsOp::CopyToClipboard(_, _) => {
+                    crate::log!("Clipboard items not supported yet");
+                }
+                CxOsOp::ReadClipboard {request_id, selection, ..} => {
+                    self.call_event_handler(&Event::ClipboardRead(ClipboardReadEvent {request_id, selection, item: None}));
+                }
                 /*CxOsOp::WebSocketOpen {request_id, request} => {
                     web_socket_open(request_id, request, self.os.network_response.sender.clone());
                 }
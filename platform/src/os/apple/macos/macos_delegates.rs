@@ -38,6 +38,7 @@ use {
         event::{
             DragEvent,
             DropEvent,
+            DropOutsideEvent,
             DragItem,
             DragResponse
         },
@@ -630,8 +631,19 @@ pub fn define_cocoa_view_class() -> *const Class {
         cw.send_change_event();
     }
     
-    extern fn dragging_session_ended_at_point_operation(this: &Object, _: Sel, _session: ObjcId, _point: NSPoint, _operation: NSDragOperation) {
+    extern fn dragging_session_ended_at_point_operation(this: &Object, _: Sel, _session: ObjcId, point: NSPoint, operation: NSDragOperation) {
         let window = get_cocoa_window(this);
+        // nobody took the drag, tell the app when it ended outside of our window
+        if operation as u64 == NSDragOperation::None as u64 {
+            let frame: NSRect = unsafe {msg_send![window.window, frame]};
+            let inside = point.x >= frame.origin.x && point.x <= frame.origin.x + frame.size.width
+                && point.y >= frame.origin.y && point.y <= frame.origin.y + frame.size.height;
+            if !inside {
+                window.do_callback(MacosEvent::DropOutside(DropOutsideEvent {
+                    position: DVec2 {x: point.x, y: point.y}
+                }));
+            }
+        }
         window.do_callback(MacosEvent::DragEnd);
     }
     
@@ -667,6 +679,7 @@ pub fn define_cocoa_view_class() -> *const Class {
         };
         
         window.do_callback(MacosEvent::Drag(DragEvent {
+            window_id: window.window_id,
             modifiers,
             handled: Cell::new(false),
             abs: pos,
@@ -762,6 +775,7 @@ pub fn define_cocoa_view_class() -> *const Class {
         let window = get_cocoa_window(this);
         let (items, pos) = get_drag_items_from_pasteboard(this, sender);
         window.do_callback(MacosEvent::Drop(DropEvent {
+            window_id: window.window_id,
             modifiers,
             handled: Cell::new(false),
             abs: pos,
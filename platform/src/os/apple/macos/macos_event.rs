@@ -15,6 +15,7 @@ use crate::{
         KeyEvent,
         DragEvent,
         DropEvent,
+        DropOutsideEvent,
         TextClipboardEvent,
         TimerEvent,
     },
@@ -41,6 +42,7 @@ pub enum MacosEvent {
     Drag(DragEvent),
     Drop(DropEvent),
     DragEnd,
+    DropOutside(DropOutsideEvent),
     KeyDown(KeyEvent),
     KeyUp(KeyEvent),
     TextCopy(TextClipboardEvent),
@@ -21,6 +21,16 @@ pub enum EventFlow{
 
 impl Cx {
     
+    // packaged builds (cargo makepad linux package) set MAKEPAD_PACKAGE_DIR to the resource
+    // directory relative to the executable, so resources are found wherever the package is installed
+    pub fn native_set_package_root(&mut self){
+        if let Some(package_dir) = option_env!("MAKEPAD_PACKAGE_DIR"){
+            if let Some(exe_dir) = std::env::current_exe().ok().as_ref().and_then(|exe| exe.parent()){
+                self.live_registry.borrow_mut().package_root = Some(exe_dir.join(package_dir).display().to_string());
+            }
+        }
+    }
+    
     pub fn native_load_dependencies(&mut self){
         for (path,dep) in &mut self.dependencies{
             if let Ok(mut file_handle) = File::open(path) {
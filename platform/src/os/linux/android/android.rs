@@ -37,6 +37,7 @@ use {
             TimerEvent,
             TextInputEvent,
             TextClipboardEvent,
+            ClipboardReadEvent,
             KeyEvent,
             KeyModifiers,
             KeyCode,
@@ -732,6 +733,11 @@ impl Cx {
                 CxOsOp::ShowClipboardActions(_selected) => {
                     //to_java.show_clipboard_actions(selected.as_str());
                 },
+                CxOsOp::CopyToClipboard(_, _) => {
+                },
+                CxOsOp::ReadClipboard {request_id, selection, ..} => {
+                    self.call_event_handler(&Event::ClipboardRead(ClipboardReadEvent {request_id, selection, item: None}));
+                },
                 CxOsOp::HttpRequest {request_id, request} => {
                     unsafe {android_jni::to_java_http_request(request_id, request);}
                 },
@@ -18,6 +18,7 @@ use {
         thread::Signal,
         event::{
             TimerEvent,
+            ClipboardReadEvent,
             Event,
             WindowGeom,
         },
@@ -297,6 +298,9 @@ impl Cx {
                 CxOsOp::StopTimer(timer_id) => {
                     direct_app.timers.stop_timer(timer_id);
                 },
+                CxOsOp::ReadClipboard {request_id, selection, ..} => {
+                    self.call_event_handler(&Event::ClipboardRead(ClipboardReadEvent {request_id, selection, item: None}));
+                },
                 _ => ()
             }
         }
@@ -306,6 +310,7 @@ impl Cx {
 
 impl CxOsApi for Cx {
     fn init_cx_os(&mut self) {
+        self.native_set_package_root();
         self.live_expand();
         self.live_scan_dependencies();
         self.native_load_dependencies();
@@ -32,6 +32,7 @@ pub const EGL_OPENGL_ES_API: u32 = 12448;
 pub const EGL_GL_TEXTURE_2D_KHR: u32 = 12465;
 
 pub const EGL_PLATFORM_X11_EXT: u32 = 12757;
+pub const EGL_PLATFORM_WAYLAND_EXT: u32 = 12760;
 pub const EGL_PLATFORM_GBM_KHR: u32 = 12759;
 
 pub const EGL_LINUX_DMA_BUF_EXT: u32 = 12912;
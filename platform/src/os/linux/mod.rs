@@ -1,6 +1,12 @@
 #[cfg(not(any(linux_direct, target_os="android")))]
 pub mod x11; 
 
+#[cfg(not(any(linux_direct, target_os="android")))]
+pub mod wayland;
+
+#[cfg(not(any(linux_direct, target_os="android")))]
+pub mod uri_list;
+
 #[cfg(linux_direct)]
 pub mod direct;
 
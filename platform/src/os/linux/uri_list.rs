@@ -0,0 +1,99 @@
+use crate::{
+    makepad_live_id::LiveId,
+    event::DragItem,
+};
+
+// text/uri-list is what file managers exchange on both X11 and Wayland. Internal ids ride along
+// in the fragment, the same way the macos backend puts them on the pasteboard.
+
+const INTERNAL_ID_MARKER: &str = "#makepad_internal_id=";
+const INTERNAL_EMPTY_PATH: &str = "makepad_internal_empty";
+
+/// Encodes the file paths as a text/uri-list, other items are left out
+pub fn drag_items_to_uri_list(items: &[DragItem]) -> String {
+    let mut uri_list = String::new();
+    for item in items {
+        if let DragItem::FilePath {path, internal_id} = item {
+            uri_list.push_str("file://");
+            if path.is_empty() {
+                uri_list.push('/');
+                uri_list.push_str(INTERNAL_EMPTY_PATH);
+            }
+            else {
+                uri_list.push_str(&percent_encode(path));
+            }
+            if let Some(internal_id) = internal_id {
+                uri_list.push_str(&format!("{}{}", INTERNAL_ID_MARKER, internal_id.0));
+            }
+            uri_list.push_str("\r\n");
+        }
+    }
+    uri_list
+}
+
+/// Joins the string items into the plain text flavor of a drag
+pub fn drag_items_to_text(items: &[DragItem]) -> String {
+    let mut text = String::new();
+    for item in items {
+        if let DragItem::String {value, ..} = item {
+            if !text.is_empty() {
+                text.push('\n');
+            }
+            text.push_str(value);
+        }
+    }
+    text
+}
+
+/// Decodes a text/uri-list, local files become file paths and anything else a string
+pub fn uri_list_to_drag_items(uri_list: &str) -> Vec<DragItem> {
+    uri_list.lines()
+        .map( | line | line.trim())
+        .filter( | line | !line.is_empty() && !line.starts_with('#'))
+        .map( | line | {
+            let Some(path) = line.strip_prefix("file://") else {
+                return DragItem::String {value: line.to_string(), internal_id: None}
+            };
+            // skip the host part, it is empty or localhost for local files
+            let path = path.find('/').map_or(path, | i | &path[i..]);
+            let (path, internal_id) = match path.split_once(INTERNAL_ID_MARKER) {
+                Some((path, id)) => (path, id.parse::<u64>().ok().map(LiveId)),
+                None => (path, None)
+            };
+            let path = percent_decode(path);
+            DragItem::FilePath {
+                path: if path.strip_prefix('/') == Some(INTERNAL_EMPTY_PATH) {String::new()} else {path},
+                internal_id
+            }
+        })
+        .collect()
+}
+
+fn percent_encode(path: &str) -> String {
+    let mut out = String::with_capacity(path.len());
+    for byte in path.bytes() {
+        match byte {
+            b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'/' | b'-' | b'_' | b'.' | b'~' => out.push(byte as char),
+            _ => out.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    out
+}
+
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            if let Some(byte) = std::str::from_utf8(&bytes[i + 1..i + 3]).ok().and_then( | hex | u8::from_str_radix(hex, 16).ok()) {
+                out.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).to_string()
+}
@@ -0,0 +1,508 @@
+use {
+    std::cell::RefCell,
+    std::rc::Rc,
+    self::super::{
+        opengl_wayland::OpenglWindow,
+        wayland_event::*,
+        wayland_app::*,
+    },
+    self::super::super::{
+        egl_sys,
+        x11::opengl_x11::OpenglCx,
+    },
+    crate::{
+        cx_api::CxOsOp,
+        makepad_math::dvec2,
+        makepad_live_id::*,
+        thread::SignalToUI,
+        event::{Event, NetworkResponse, NetworkResponseItem},
+        pass::CxPassParent,
+        cx::{Cx, OsType, LinuxWindowParams},
+        gpu_info::GpuPerformance,
+        os::cx_native::EventFlow,
+    }
+};
+
+impl Cx {
+    /// Runs the app on a Wayland compositor, returns false when there is none so the caller can fall back to X11
+    pub(crate) fn wayland_event_loop(cx: Rc<RefCell<Cx>>) -> bool {
+        let opengl_windows = Rc::new(RefCell::new(Vec::new()));
+        let ok = init_wayland_app_global(Box::new({
+            let cx = cx.clone();
+            move | wayland_app,
+            event | {
+                let mut cx = cx.borrow_mut();
+                let mut opengl_windows = opengl_windows.borrow_mut();
+                cx.wayland_event_callback(wayland_app, event, &mut *opengl_windows)
+            }
+        }));
+        if !ok {
+            return false
+        }
+
+        cx.borrow_mut().self_ref = Some(cx.clone());
+        // there are no server side decorations on wayland we can count on, so we draw our own
+        cx.borrow_mut().os_type = OsType::LinuxWindow(LinuxWindowParams{
+            custom_window_chrome: true
+        });
+        cx.borrow_mut().gpu_info.performance = GpuPerformance::Tier1;
+
+        cx.borrow_mut().os.opengl_cx = Some(unsafe {
+            OpenglCx::from_egl_platform_display(
+                egl_sys::EGL_PLATFORM_WAYLAND_EXT,
+                get_wayland_app_global().display,
+            )
+        });
+
+        cx.borrow_mut().call_event_handler(&Event::Startup);
+        cx.borrow_mut().redraw_all();
+        get_wayland_app_global().start_timer(0, 0.008, true);
+        get_wayland_app_global().event_loop();
+        true
+    }
+
+    fn wayland_event_callback(
+        &mut self,
+        wayland_app: &mut WaylandApp,
+        event: WaylandEvent,
+        opengl_windows: &mut Vec<OpenglWindow>
+    ) -> EventFlow {
+        if let EventFlow::Exit = self.handle_wayland_platform_ops(opengl_windows, wayland_app) {
+            return EventFlow::Exit
+        }
+
+        let mut paint_dirty = false;
+
+        match event {
+            WaylandEvent::AppGotFocus => {
+                for window in opengl_windows.iter_mut() {
+                    if let Some(main_pass_id) = self.windows[window.window_id].main_pass_id {
+                        self.repaint_pass(main_pass_id);
+                    }
+                }
+                paint_dirty = true;
+                self.call_event_handler(&Event::AppGotFocus);
+            }
+            WaylandEvent::AppLostFocus => {
+                self.call_event_handler(&Event::AppLostFocus);
+            }
+            WaylandEvent::WindowGeomChange(mut re) => {
+                if let Some(window) = opengl_windows.iter_mut().find( | w | w.window_id == re.window_id) {
+                    if let Some(dpi_override) = self.windows[re.window_id].dpi_override {
+                        re.new_geom.inner_size *= re.new_geom.dpi_factor / dpi_override;
+                        re.new_geom.dpi_factor = dpi_override;
+                    }
+
+                    window.window_geom = re.new_geom.clone();
+                    self.windows[re.window_id].window_geom = re.new_geom.clone();
+                    // a scale change needs a redraw as much as a resize does
+                    if re.old_geom.inner_size != re.new_geom.inner_size || re.old_geom.dpi_factor != re.new_geom.dpi_factor {
+                        if let Some(main_pass_id) = self.windows[re.window_id].main_pass_id {
+                            self.redraw_pass_and_child_passes(main_pass_id);
+                        }
+                    }
+                }
+                self.call_event_handler(&Event::WindowGeomChange(re));
+            }
+            WaylandEvent::WindowClosed(wc) => {
+                let window_id = wc.window_id;
+                self.call_event_handler(&Event::WindowClosed(wc));
+                self.windows[window_id].is_created = false;
+                if let Some(index) = opengl_windows.iter().position( | w | w.window_id == window_id) {
+                    opengl_windows[index].destroy_egl_surface(self.os.opengl_cx.as_ref().unwrap());
+                    opengl_windows.remove(index);
+                    if opengl_windows.len() == 0 {
+                        wayland_app.terminate_event_loop();
+                        self.call_event_handler(&Event::Shutdown);
+                        return EventFlow::Exit
+                    }
+                }
+            }
+            WaylandEvent::Paint => {
+                if self.new_next_frames.len() != 0 {
+                    self.call_next_frame_event(wayland_app.time_now());
+                }
+                if self.need_redrawing() {
+                    self.call_draw_event();
+                    self.os.opengl_cx.as_ref().unwrap().make_current();
+                    self.opengl_compile_shaders();
+                }
+                self.handle_wayland_repaint(opengl_windows);
+            }
+            WaylandEvent::MouseDown(e) => {
+                self.fingers.process_tap_count(
+                    e.abs,
+                    e.time
+                );
+                self.fingers.mouse_down(e.button);
+                self.call_event_handler(&Event::MouseDown(e.into()))
+            }
+            WaylandEvent::MouseMove(e) => {
+                self.call_event_handler(&Event::MouseMove(e.into()));
+                self.fingers.cycle_hover_area(live_id!(mouse).into());
+                self.fingers.switch_captures();
+            }
+            WaylandEvent::MouseUp(e) => {
+                let button = e.button;
+                self.call_event_handler(&Event::MouseUp(e.into()));
+                self.fingers.mouse_up(button);
+                self.fingers.cycle_hover_area(live_id!(mouse).into());
+            }
+            WaylandEvent::Scroll(e) => {
+                self.call_event_handler(&Event::Scroll(e.into()))
+            }
+            WaylandEvent::TouchUpdate(e) => {
+                self.fingers.process_touch_update_start(e.time, &e.touches);
+                let e = Event::TouchUpdate(e);
+                self.call_event_handler(&e);
+                let e = if let Event::TouchUpdate(e) = e {e} else {panic!()};
+                self.fingers.process_touch_update_end(&e.touches);
+            }
+            WaylandEvent::WindowDragQuery(e) => {
+                self.call_event_handler(&Event::WindowDragQuery(e))
+            }
+            WaylandEvent::WindowCloseRequested(e) => {
+                self.call_event_handler(&Event::WindowCloseRequested(e))
+            }
+            WaylandEvent::TextInput(e) => {
+                self.call_event_handler(&Event::TextInput(e))
+            }
+            WaylandEvent::TextPreedit(e) => {
+                self.call_event_handler(&Event::TextPreedit(e))
+            }
+            WaylandEvent::Drag(e) => {
+                self.call_event_handler(&Event::Drag(e))
+            }
+            WaylandEvent::Drop(e) => {
+                self.call_event_handler(&Event::Drop(e))
+            }
+            WaylandEvent::DragEnd => {
+                self.call_event_handler(&Event::DragEnd)
+            }
+            WaylandEvent::KeyDown(e) => {
+                self.keyboard.process_key_down(e.clone());
+                self.call_event_handler(&Event::KeyDown(e))
+            }
+            WaylandEvent::KeyUp(e) => {
+                self.keyboard.process_key_up(e.clone());
+                self.call_event_handler(&Event::KeyUp(e))
+            }
+            WaylandEvent::TextCopy(e) => {
+                self.call_event_handler(&Event::TextCopy(e))
+            }
+            WaylandEvent::TextCut(e) => {
+                self.call_event_handler(&Event::TextCut(e))
+            }
+            WaylandEvent::ClipboardRead(e) => {
+                self.call_event_handler(&Event::ClipboardRead(e))
+            }
+            WaylandEvent::Timer(e) => {
+                if e.timer_id == 0 {
+                    if SignalToUI::check_and_clear_ui_signal() {
+                        self.handle_media_signals();
+                        self.call_event_handler(&Event::Signal);
+                    }
+                    if self.handle_live_edit() {
+                        self.call_event_handler(&Event::LiveEdit);
+                        self.redraw_all();
+                    }
+                }
+                else {
+                    self.call_event_handler(&Event::Timer(e))
+                }
+            }
+        }
+
+        if self.need_redrawing() || self.new_next_frames.len() != 0 || paint_dirty || self.wayland_can_paint(opengl_windows) {
+            EventFlow::Poll
+        } else {
+            // windows waiting on the compositor get woken up by their frame callback
+            EventFlow::Wait
+        }
+    }
+
+    /// True when there is a dirty pass that isn't held back by a window waiting for its frame callback
+    fn wayland_can_paint(&self, opengl_windows: &[OpenglWindow]) -> bool {
+        for pass_id in self.passes.id_iter() {
+            if !self.passes[pass_id].paint_dirty {
+                continue
+            }
+            if let CxPassParent::Window(window_id) = self.passes[pass_id].parent {
+                if opengl_windows.iter().any( | w | w.window_id == window_id && w.wayland_window.is_waiting_for_frame()) {
+                    continue
+                }
+            }
+            return true
+        }
+        false
+    }
+
+    fn handle_wayland_repaint(&mut self, opengl_windows: &mut Vec<OpenglWindow>) {
+        self.os.opengl_cx.as_ref().unwrap().make_current();
+        let mut passes_todo = Vec::new();
+        self.compute_pass_repaint_order(&mut passes_todo);
+        self.repaint_id += 1;
+        for pass_id in &passes_todo {
+            self.passes[*pass_id].set_time(get_wayland_app_global().time_now() as f32);
+            match self.passes[*pass_id].parent.clone() {
+                CxPassParent::Window(window_id) => {
+                    if let Some(window) = opengl_windows.iter_mut().find( | w | w.window_id == window_id) {
+                        // the pass stays dirty and gets drawn when the compositor asks for the next frame
+                        if window.wayland_window.is_waiting_for_frame() {
+                            continue;
+                        }
+                        window.wayland_window.resize_buffers();
+                        let wayland_window = &mut window.wayland_window;
+                        self.draw_pass_to_egl_surface(
+                            *pass_id,
+                            window.egl_surface,
+                            dvec2(wayland_window.buffer_size.0 as f64, wayland_window.buffer_size.1 as f64),
+                            Some(&mut || wayland_window.request_frame())
+                        );
+                    }
+                }
+                CxPassParent::Pass(_) => {
+                    self.draw_pass_to_magic_texture(*pass_id);
+                },
+                CxPassParent::None => {
+                    self.draw_pass_to_magic_texture(*pass_id);
+                }
+            }
+        }
+    }
+
+    fn handle_wayland_platform_ops(&mut self, opengl_windows: &mut Vec<OpenglWindow>, wayland_app: &mut WaylandApp) -> EventFlow {
+        let mut ret = EventFlow::Poll;
+        let mut network_responses = Vec::new();
+        while let Some(op) = self.platform_ops.pop() {
+            match op {
+                CxOsOp::CreateWindow(window_id) => {
+                    let window = &mut self.windows[window_id];
+                    // the compositor places toplevels, so create_position has nothing to map onto
+                    let opengl_window = OpenglWindow::new(
+                        window_id,
+                        self.os.opengl_cx.as_ref().unwrap(),
+                        window.create_inner_size.unwrap_or(dvec2(800., 600.)),
+                        &window.create_title,
+                    );
+                    window.window_geom = opengl_window.window_geom.clone();
+                    opengl_windows.push(opengl_window);
+                    window.is_created = true;
+                },
+                CxOsOp::CloseWindow(window_id) => {
+                    if let Some(index) = opengl_windows.iter().position( | w | w.window_id == window_id) {
+                        self.windows[window_id].is_created = false;
+                        opengl_windows[index].destroy_egl_surface(self.os.opengl_cx.as_ref().unwrap());
+                        opengl_windows[index].wayland_window.close_window();
+                        opengl_windows.remove(index);
+                        if opengl_windows.len() == 0 {
+                            ret = EventFlow::Exit
+                        }
+                    }
+                },
+                CxOsOp::Quit => {
+                    ret = EventFlow::Exit
+                }
+                CxOsOp::MinimizeWindow(window_id) => {
+                    if let Some(window) = opengl_windows.iter_mut().find( | w | w.window_id == window_id) {
+                        window.wayland_window.minimize();
+                    }
+                },
+                CxOsOp::MaximizeWindow(window_id) => {
+                    if let Some(window) = opengl_windows.iter_mut().find( | w | w.window_id == window_id) {
+                        window.wayland_window.maximize();
+                    }
+                },
+                CxOsOp::RestoreWindow(window_id) => {
+                    if let Some(window) = opengl_windows.iter_mut().find( | w | w.window_id == window_id) {
+                        window.wayland_window.restore();
+                    }
+                },
+                CxOsOp::FullscreenWindow(window_id) => {
+                    if let Some(window) = opengl_windows.iter_mut().find( | w | w.window_id == window_id) {
+                        window.wayland_window.fullscreen();
+                    }
+                },
+                CxOsOp::NormalizeWindow(window_id) => {
+                    if let Some(window) = opengl_windows.iter_mut().find( | w | w.window_id == window_id) {
+                        window.wayland_window.normalize();
+                    }
+                }
+                CxOsOp::SetTopmost(_window_id, _is_topmost) => {
+                    // xdg-shell has no way to ask for this
+                }
+                CxOsOp::ShowClipboardActions(_) => {
+                }
+                CxOsOp::CopyToClipboard(selection, items) => {
+                    wayland_app.copy_to_clipboard(selection, items);
+                }
+                CxOsOp::ReadClipboard {request_id, selection, mime_types} => {
+                    wayland_app.read_clipboard(request_id, selection, mime_types);
+                }
+                CxOsOp::XrStartPresenting => {
+                },
+                CxOsOp::XrStopPresenting => {
+                },
+                CxOsOp::ShowTextIME(area, pos) => {
+                    let pos = area.clipped_rect(self).pos + pos;
+                    opengl_windows.iter_mut().for_each( | w | {
+                        w.wayland_window.set_ime_spot(pos);
+                    });
+                }
+                CxOsOp::HideTextIME => {
+                    opengl_windows.iter_mut().for_each( | w | {
+                        w.wayland_window.hide_ime();
+                    });
+                },
+                CxOsOp::SetCursor(cursor) => {
+                    wayland_app.set_mouse_cursor(cursor);
+                },
+                CxOsOp::StartTimer {timer_id, interval, repeats} => {
+                    wayland_app.start_timer(timer_id, interval, repeats);
+                },
+                CxOsOp::StopTimer(timer_id) => {
+                    wayland_app.stop_timer(timer_id);
+                },
+                CxOsOp::StartDragging(items) => {
+                    wayland_app.start_dragging(items);
+                },
+                CxOsOp::UpdateMacosMenu(_menu) => {
+                },
+                CxOsOp::HttpRequest{request_id, request} => {
+                    // there is no http client on linux yet, so the request fails instead of never answering
+                    crate::error!("http requests are not supported on linux, dropping request to {}", request.url);
+                    network_responses.push(NetworkResponseItem {
+                        request_id,
+                        response: NetworkResponse::HttpRequestError("http requests are not supported on linux".to_string())
+                    });
+                },
+                CxOsOp::PrepareVideoPlayback(video_id, _, _, _, _) |
+                CxOsOp::BeginVideoPlayback(video_id) |
+                CxOsOp::PauseVideoPlayback(video_id) |
+                CxOsOp::ResumeVideoPlayback(video_id) |
+                CxOsOp::MuteVideoPlayback(video_id) |
+                CxOsOp::UnmuteVideoPlayback(video_id) |
+                CxOsOp::CleanupVideoPlaybackResources(video_id) |
+                CxOsOp::UpdateVideoSurfaceTexture(video_id) => {
+                    crate::error!("video playback is not supported on wayland, ignoring op for video {}", video_id);
+                },
+                CxOsOp::SaveFileDialog(_) |
+                CxOsOp::SelectFileDialog(_) |
+                CxOsOp::SaveFolderDialog(_) |
+                CxOsOp::SelectFolderDialog(_) => {
+                    crate::error!("file dialogs are not supported on wayland, ignoring the request");
+                },
+            }
+        }
+        if !network_responses.is_empty() {
+            self.call_event_handler(&Event::NetworkResponses(network_responses));
+        }
+        ret
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        std::{
+            cell::RefCell,
+            rc::Rc,
+            fs,
+            path::PathBuf,
+            process::{Child, Command, Stdio},
+            time::{Duration, Instant},
+        },
+        super::super::wayland_app::*,
+        crate::{
+            cx_api::CxOsOp,
+            makepad_live_id::*,
+            event::{Event, HttpRequest, HttpMethod, NetworkResponse},
+            file_dialogs::FileDialog,
+            cx::Cx,
+            os::cx_native::EventFlow,
+        },
+    };
+
+    const SOCKET: &str = "makepad-test";
+
+    // weston with its headless backend, so the backend can be tested without a display
+    struct HeadlessWeston {
+        child: Child,
+        runtime_dir: PathBuf,
+    }
+
+    impl HeadlessWeston {
+        fn start() -> Option<Self> {
+            let runtime_dir = std::env::temp_dir().join(format!("makepad-wayland-test-{}", std::process::id()));
+            fs::create_dir_all(&runtime_dir).ok()?;
+            let child = Command::new("weston")
+                .args(["--backend=headless-backend.so", &format!("--socket={}", SOCKET), "--idle-time=0"])
+                .env("XDG_RUNTIME_DIR", &runtime_dir)
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .spawn()
+                .ok()?;
+            let mut weston = HeadlessWeston {child, runtime_dir};
+            let started = Instant::now();
+            while !weston.runtime_dir.join(SOCKET).exists() {
+                if started.elapsed() > Duration::from_secs(10) || weston.child.try_wait().ok()?.is_some() {
+                    return None
+                }
+                std::thread::sleep(Duration::from_millis(20));
+            }
+            std::env::set_var("XDG_RUNTIME_DIR", &weston.runtime_dir);
+            std::env::set_var("WAYLAND_DISPLAY", SOCKET);
+            Some(weston)
+        }
+    }
+
+    impl Drop for HeadlessWeston {
+        fn drop(&mut self) {
+            let _ = self.child.kill();
+            let _ = self.child.wait();
+            let _ = fs::remove_dir_all(&self.runtime_dir);
+        }
+    }
+
+    #[test]
+    fn headless_compositor() {
+        let Some(_weston) = HeadlessWeston::start() else {
+            eprintln!("skipping the wayland backend test, weston isn't installed or didn't start");
+            return
+        };
+        assert!(init_wayland_app_global(Box::new( | _, _ | EventFlow::Poll)), "couldn't connect to the headless compositor");
+        let wayland_app = get_wayland_app_global();
+        assert!(!wayland_app.compositor.is_null());
+        assert!(!wayland_app.xdg_wm_base.is_null());
+        assert!(!wayland_app.shm.is_null());
+        // the headless backend has one output and reports its scale on the second roundtrip
+        assert!(!wayland_app.outputs.is_empty());
+        assert!(wayland_app.outputs.iter().all( | output | output.scale >= 1));
+
+        let responses = Rc::new(RefCell::new(Vec::new()));
+        let mut cx = Cx::new(Box::new({
+            let responses = responses.clone();
+            move | _, event | if let Event::NetworkResponses(items) = event {
+                responses.borrow_mut().extend(items.iter().cloned());
+            }
+        }));
+
+        // ops the backend can't do are reported or dropped, they don't take the app down
+        cx.http_request(live_id!(request), HttpRequest::new("http://127.0.0.1/".to_string(), HttpMethod::GET));
+        cx.platform_ops.push(CxOsOp::BeginVideoPlayback(live_id!(video)));
+        cx.platform_ops.push(CxOsOp::CleanupVideoPlaybackResources(live_id!(video)));
+        cx.platform_ops.push(CxOsOp::SelectFileDialog(FileDialog::new()));
+        cx.platform_ops.push(CxOsOp::SaveFolderDialog(FileDialog::new()));
+        cx.platform_ops.push(CxOsOp::StartTimer {timer_id: 1, interval: 1.0, repeats: false});
+        cx.platform_ops.push(CxOsOp::StopTimer(1));
+        assert_eq!(cx.handle_wayland_platform_ops(&mut Vec::new(), wayland_app), EventFlow::Poll);
+        assert!(cx.platform_ops.is_empty());
+
+        let responses = responses.borrow();
+        assert_eq!(responses.len(), 1);
+        assert_eq!(responses[0].request_id, live_id!(request));
+        assert!(matches!(responses[0].response, NetworkResponse::HttpRequestError(_)));
+
+        wayland_app.flush();
+    }
+}
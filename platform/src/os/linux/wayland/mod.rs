@@ -0,0 +1,7 @@
+pub mod wayland_sys;
+pub mod wayland_protocols;
+pub mod wayland_app;
+pub mod wayland_window;
+pub mod wayland_event;
+pub mod opengl_wayland;
+pub mod linux_wayland;
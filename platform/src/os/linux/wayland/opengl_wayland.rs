@@ -0,0 +1,77 @@
+use {
+    self::super::{
+        wayland_window::WaylandWindow,
+        super::{
+            egl_sys,
+            x11::opengl_x11::OpenglCx,
+        },
+    },
+    crate::{
+        window::WindowId,
+        makepad_math::{DVec2, dvec2},
+        event::*,
+    },
+};
+
+pub struct OpenglWindow {
+    pub window_id: WindowId,
+    pub window_geom: WindowGeom,
+    pub wayland_window: Box<WaylandWindow>,
+    pub egl_surface: egl_sys::EGLSurface,
+}
+
+impl OpenglWindow {
+    pub fn new(
+        window_id: WindowId,
+        opengl_cx: &OpenglCx,
+        inner_size: DVec2,
+        title: &str
+    ) -> OpenglWindow {
+        assert_eq!(opengl_cx.egl_platform, egl_sys::EGL_PLATFORM_WAYLAND_EXT);
+
+        // boxed before init, the protocol objects point back at it
+        let mut wayland_window = Box::new(WaylandWindow::new(window_id));
+        wayland_window.init(title, inner_size);
+
+        let egl_surface = unsafe {
+            (opengl_cx.libegl.eglCreateWindowSurface.unwrap())(
+                opengl_cx.egl_display,
+                opengl_cx.egl_config,
+                wayland_window.egl_window as egl_sys::EGLNativeWindowType,
+                std::ptr::null(),
+            )
+        };
+        assert!(!egl_surface.is_null(), "eglCreateWindowSurface failed");
+
+        unsafe {
+            // pacing comes from frame callbacks, a blocking swap would stall the whole event loop on hidden windows
+            (opengl_cx.libegl.eglMakeCurrent.unwrap())(opengl_cx.egl_display, egl_surface, egl_surface, opengl_cx.egl_context);
+            (opengl_cx.libegl.eglSwapInterval.unwrap())(opengl_cx.egl_display, 0);
+        }
+        opengl_cx.make_current();
+
+        OpenglWindow {
+            window_id,
+            window_geom: wayland_window.get_window_geom(),
+            wayland_window,
+            egl_surface,
+        }
+    }
+
+    /// Size of the EGL buffer in pixels
+    pub fn pix_size(&self) -> DVec2 {
+        dvec2(self.wayland_window.buffer_size.0 as f64, self.wayland_window.buffer_size.1 as f64)
+    }
+
+    /// Has to happen before the window is dropped, EGL must let go of the wl_egl_window first
+    pub fn destroy_egl_surface(&mut self, opengl_cx: &OpenglCx) {
+        if self.egl_surface.is_null() {
+            return
+        }
+        unsafe {
+            opengl_cx.make_current();
+            (opengl_cx.libegl.eglDestroySurface.unwrap())(opengl_cx.egl_display, self.egl_surface);
+        }
+        self.egl_surface = std::ptr::null_mut();
+    }
+}
@@ -0,0 +1,1498 @@
+use {
+    std::{
+        collections::HashMap,
+        cell::{Cell, RefCell},
+        rc::Rc,
+        ffi::{CStr, CString},
+        fs::File,
+        io::{Read, Write},
+        os::{
+            fd::FromRawFd,
+            raw::{c_char, c_int, c_void},
+        },
+        ptr,
+    },
+    self::super::{
+        wayland_sys::*,
+        wayland_protocols::*,
+        wayland_event::WaylandEvent,
+        wayland_window::*,
+        super::{
+            libc_sys,
+            select_timer::SelectTimers,
+            uri_list::*,
+            x11::xlib_app::keysym_to_keycode,
+        },
+    },
+    crate::{
+        area::Area,
+        makepad_math::{DVec2, dvec2},
+        makepad_live_id::LiveId,
+        event::*,
+        cursor::MouseCursor,
+        os::cx_native::EventFlow,
+    },
+};
+
+static mut WAYLAND_APP: *mut WaylandApp = 0 as *mut _;
+
+pub fn get_wayland_app_global() -> &'static mut WaylandApp {
+    unsafe {
+        &mut *(WAYLAND_APP)
+    }
+}
+
+/// Connects to the compositor, returns false when there is no usable Wayland session
+pub fn init_wayland_app_global(event_callback: Box<dyn FnMut(&mut WaylandApp, WaylandEvent) -> EventFlow>) -> bool {
+    let Some(app) = WaylandApp::try_new(event_callback) else {
+        return false
+    };
+    unsafe {
+        WAYLAND_APP = Box::into_raw(Box::new(app));
+    }
+    // the globals dispatch into the app, so they can only be bound once it has its final address
+    if get_wayland_app_global().init_globals() {
+        return true
+    }
+    unsafe {
+        let app = Box::from_raw(WAYLAND_APP);
+        WAYLAND_APP = ptr::null_mut();
+        (app.wl.wl_display_disconnect)(app.display);
+    }
+    false
+}
+
+// timer ids from Cx count up from 1, so the top of the range is free for our own use
+const KEY_REPEAT_TIMER_ID: u64 = u64::MAX;
+
+const TEXT_MIME_TYPES: [&str; 3] = ["text/plain;charset=utf-8", "UTF8_STRING", "text/plain"];
+const URI_LIST_MIME_TYPE: &str = "text/uri-list";
+
+pub struct WaylandOutput {
+    pub output: *mut wl_proxy,
+    pub name: u32,
+    pub scale: i32,
+}
+
+#[derive(Default)]
+pub struct WaylandScroll {
+    pub value: DVec2,
+    pub discrete: DVec2,
+    pub is_wheel: bool,
+    pub has_value: bool,
+}
+
+pub struct WaylandApp {
+    pub wl: LibWaylandClient,
+    pub egl: LibWaylandEgl,
+    pub xkb: LibXkbCommon,
+    pub cursor_lib: Option<LibWaylandCursor>,
+
+    pub display: *mut wl_display,
+    pub display_fd: c_int,
+    pub app_id: CString,
+    event_loop_running: bool,
+    pub event_callback: Option<Box<dyn FnMut(&mut WaylandApp, WaylandEvent) -> EventFlow >>,
+    pub event_flow: EventFlow,
+    pub timers: SelectTimers,
+
+    pub registry: *mut wl_proxy,
+    pub compositor: *mut wl_proxy,
+    pub shm: *mut wl_proxy,
+    pub xdg_wm_base: *mut wl_proxy,
+    pub seat: *mut wl_proxy,
+    pub data_device_manager: *mut wl_proxy,
+    pub fractional_scale_manager: *mut wl_proxy,
+    pub viewporter: *mut wl_proxy,
+    pub text_input_manager: *mut wl_proxy,
+    pub outputs: Vec<WaylandOutput>,
+    pub window_map: HashMap<*mut wl_proxy, *mut WaylandWindow>,
+
+    pub pointer: *mut wl_proxy,
+    pub pointer_surface: *mut wl_proxy,
+    pub pointer_serial: u32,
+    pub button_serial: u32,
+    pub scroll: WaylandScroll,
+    pub last_scroll_time: f64,
+    pub last_click_time: f64,
+    pub last_click_pos: DVec2,
+    pub current_cursor: MouseCursor,
+    pub cursor_theme: *mut wl_cursor_theme,
+    pub cursor_theme_scale: i32,
+    pub cursor_surface: *mut wl_proxy,
+
+    pub keyboard: *mut wl_proxy,
+    pub keyboard_surface: *mut wl_proxy,
+    pub keyboard_serial: u32,
+    pub xkb_context: *mut xkb_context,
+    pub xkb_keymap: *mut xkb_keymap,
+    pub xkb_state: *mut xkb_state,
+    pub modifiers: KeyModifiers,
+    pub repeat_rate: i32,
+    pub repeat_delay: i32,
+    pub repeat_key: Option<u32>,
+
+    pub touch: *mut wl_proxy,
+    pub touch_surface: *mut wl_proxy,
+    pub touches: Vec<TouchPoint>,
+
+    pub data_device: *mut wl_proxy,
+    pub offers: HashMap<*mut wl_proxy, Vec<String>>,
+    pub selection_offer: *mut wl_proxy,
+    pub clipboard: Vec<ClipboardItem>,
+    pub clipboard_source: *mut wl_proxy,
+    pub dnd_offer: *mut wl_proxy,
+    pub dnd_surface: *mut wl_proxy,
+    pub dnd_serial: u32,
+    pub dnd_pos: DVec2,
+    pub drag_source: *mut wl_proxy,
+    pub drag_data: HashMap<String, String>,
+
+    pub text_input: *mut wl_proxy,
+    pub text_input_surface: *mut wl_proxy,
+    pub pending_preedit: Option<(String, usize)>,
+    pub pending_commit: Option<String>,
+    pub ime_preedit_visible: bool,
+}
+
+impl WaylandApp {
+    fn try_new(event_callback: Box<dyn FnMut(&mut WaylandApp, WaylandEvent) -> EventFlow>) -> Option<WaylandApp> {
+        // an empty WAYLAND_DISPLAY is the usual way to force X11
+        if std::env::var("WAYLAND_DISPLAY").map_or(true, | v | v.is_empty()) {
+            return None
+        }
+        let wl = LibWaylandClient::try_load()?;
+        let egl = LibWaylandEgl::try_load()?;
+        let xkb = LibXkbCommon::try_load()?;
+        let display = unsafe {(wl.wl_display_connect)(ptr::null())};
+        if display.is_null() {
+            return None
+        }
+        let display_fd = unsafe {(wl.wl_display_get_fd)(display)};
+        let xkb_context = unsafe {(xkb.xkb_context_new)(XKB_CONTEXT_NO_FLAGS)};
+        let app_id = std::env::current_exe().ok()
+            .and_then( | path | path.file_stem().map( | stem | stem.to_string_lossy().to_string()))
+            .unwrap_or_else( || "makepad".to_string());
+
+        Some(WaylandApp {
+            wl,
+            egl,
+            xkb,
+            cursor_lib: LibWaylandCursor::try_load(),
+            display,
+            display_fd,
+            app_id: CString::new(app_id).unwrap_or_default(),
+            event_loop_running: true,
+            event_callback: Some(event_callback),
+            event_flow: EventFlow::Poll,
+            timers: SelectTimers::new(),
+            registry: ptr::null_mut(),
+            compositor: ptr::null_mut(),
+            shm: ptr::null_mut(),
+            xdg_wm_base: ptr::null_mut(),
+            seat: ptr::null_mut(),
+            data_device_manager: ptr::null_mut(),
+            fractional_scale_manager: ptr::null_mut(),
+            viewporter: ptr::null_mut(),
+            text_input_manager: ptr::null_mut(),
+            outputs: Vec::new(),
+            window_map: HashMap::new(),
+            pointer: ptr::null_mut(),
+            pointer_surface: ptr::null_mut(),
+            pointer_serial: 0,
+            button_serial: 0,
+            scroll: WaylandScroll::default(),
+            last_scroll_time: 0.0,
+            last_click_time: 0.0,
+            last_click_pos: DVec2::default(),
+            current_cursor: MouseCursor::Default,
+            cursor_theme: ptr::null_mut(),
+            cursor_theme_scale: 0,
+            cursor_surface: ptr::null_mut(),
+            keyboard: ptr::null_mut(),
+            keyboard_surface: ptr::null_mut(),
+            keyboard_serial: 0,
+            xkb_context,
+            xkb_keymap: ptr::null_mut(),
+            xkb_state: ptr::null_mut(),
+            modifiers: KeyModifiers::default(),
+            repeat_rate: 25,
+            repeat_delay: 600,
+            repeat_key: None,
+            touch: ptr::null_mut(),
+            touch_surface: ptr::null_mut(),
+            touches: Vec::new(),
+            data_device: ptr::null_mut(),
+            offers: HashMap::new(),
+            selection_offer: ptr::null_mut(),
+            clipboard: Vec::new(),
+            clipboard_source: ptr::null_mut(),
+            dnd_offer: ptr::null_mut(),
+            dnd_surface: ptr::null_mut(),
+            dnd_serial: 0,
+            dnd_pos: DVec2::default(),
+            drag_source: ptr::null_mut(),
+            drag_data: HashMap::new(),
+            text_input: ptr::null_mut(),
+            text_input_surface: ptr::null_mut(),
+            pending_preedit: None,
+            pending_commit: None,
+            ime_preedit_visible: false,
+        })
+    }
+
+    fn init_globals(&mut self) -> bool {
+        unsafe {
+            self.registry = self.request_new(self.display as *mut wl_proxy, WL_DISPLAY_GET_REGISTRY, self.wl.wl_registry_interface, &mut [
+                wl_argument::new_id()
+            ]);
+            self.add_dispatcher(self.registry, registry_dispatch, ptr::null_mut());
+            // first roundtrip gets the globals, the second one their initial state
+            (self.wl.wl_display_roundtrip)(self.display);
+            if self.compositor.is_null() || self.xdg_wm_base.is_null() {
+                return false
+            }
+            if !self.seat.is_null() {
+                if !self.data_device_manager.is_null() {
+                    self.data_device = self.request_new(self.data_device_manager, WL_DATA_DEVICE_MANAGER_GET_DATA_DEVICE, self.wl.wl_data_device_interface, &mut [
+                        wl_argument::new_id(),
+                        wl_argument::object(self.seat)
+                    ]);
+                    self.add_dispatcher(self.data_device, data_device_dispatch, ptr::null_mut());
+                }
+                if !self.text_input_manager.is_null() {
+                    self.text_input = self.request_new(self.text_input_manager, ZWP_TEXT_INPUT_MANAGER_V3_GET_TEXT_INPUT, &zwp_text_input_v3, &mut [
+                        wl_argument::new_id(),
+                        wl_argument::object(self.seat)
+                    ]);
+                    self.add_dispatcher(self.text_input, text_input_dispatch, ptr::null_mut());
+                }
+            }
+            (self.wl.wl_display_roundtrip)(self.display);
+        }
+        true
+    }
+
+    pub unsafe fn request(&self, proxy: *mut wl_proxy, opcode: u32, args: &mut [wl_argument]) {
+        (self.wl.wl_proxy_marshal_array_flags)(proxy, opcode, ptr::null(), (self.wl.wl_proxy_get_version)(proxy), 0, args.as_mut_ptr());
+    }
+
+    pub unsafe fn request_new(&self, proxy: *mut wl_proxy, opcode: u32, interface: *const wl_interface, args: &mut [wl_argument]) -> *mut wl_proxy {
+        (self.wl.wl_proxy_marshal_array_flags)(proxy, opcode, interface, (self.wl.wl_proxy_get_version)(proxy), 0, args.as_mut_ptr())
+    }
+
+    pub unsafe fn destroy(&self, proxy: *mut wl_proxy, opcode: u32) {
+        (self.wl.wl_proxy_marshal_array_flags)(proxy, opcode, ptr::null(), (self.wl.wl_proxy_get_version)(proxy), WL_MARSHAL_FLAG_DESTROY, [].as_mut_ptr());
+    }
+
+    pub unsafe fn add_dispatcher(&self, proxy: *mut wl_proxy, dispatcher: wl_dispatcher_func_t, data: *mut c_void) {
+        (self.wl.wl_proxy_add_dispatcher)(proxy, dispatcher, ptr::null(), data);
+    }
+
+    unsafe fn bind(&self, name: u32, interface: *const wl_interface, version: u32) -> *mut wl_proxy {
+        (self.wl.wl_proxy_marshal_array_flags)(
+            self.registry,
+            WL_REGISTRY_BIND,
+            interface,
+            version,
+            0,
+            [
+                wl_argument::uint(name),
+                wl_argument::string((*interface).name),
+                wl_argument::uint(version),
+                wl_argument::new_id()
+            ].as_mut_ptr()
+        )
+    }
+
+    pub fn flush(&self) {
+        unsafe {(self.wl.wl_display_flush)(self.display)};
+    }
+
+    pub fn output_scale(&self, output: *mut wl_proxy) -> Option<i32> {
+        self.outputs.iter().find( | o | o.output == output).map( | o | o.scale)
+    }
+
+    /// Drops all references to a surface that is about to be destroyed
+    pub fn forget_surface(&mut self, surface: *mut wl_proxy) {
+        if self.pointer_surface == surface {
+            self.pointer_surface = ptr::null_mut();
+        }
+        if self.keyboard_surface == surface {
+            self.keyboard_surface = ptr::null_mut();
+            self.stop_key_repeat();
+        }
+        if self.touch_surface == surface {
+            self.touch_surface = ptr::null_mut();
+            self.touches.clear();
+        }
+        if self.dnd_surface == surface {
+            self.dnd_surface = ptr::null_mut();
+        }
+        if self.text_input_surface == surface {
+            self.text_input_surface = ptr::null_mut();
+        }
+    }
+
+    fn window(&self, surface: *mut wl_proxy) -> Option<&'static mut WaylandWindow> {
+        self.window_map.get(&surface).map( | window | unsafe {&mut **window})
+    }
+
+    unsafe fn event_loop_poll(&mut self, block: bool) {
+        while (self.wl.wl_display_prepare_read)(self.display) != 0 {
+            (self.wl.wl_display_dispatch_pending)(self.display);
+        }
+        (self.wl.wl_display_flush)(self.display);
+        if block {
+            self.timers.select(self.display_fd);
+        }
+        // libwayland reads without blocking, so this is fine even when nothing arrived
+        (self.wl.wl_display_read_events)(self.display);
+        (self.wl.wl_display_dispatch_pending)(self.display);
+        (self.wl.wl_display_flush)(self.display);
+        self.do_callback(WaylandEvent::Paint);
+    }
+
+    pub fn event_loop(&mut self) {
+        unsafe {
+            self.do_callback(WaylandEvent::Paint);
+
+            let mut timer_ids = Vec::new();
+            while self.event_loop_running {
+                match self.event_flow {
+                    EventFlow::Exit => {
+                        break;
+                    }
+                    EventFlow::Wait => {
+                        self.fire_timers(&mut timer_ids);
+                        self.event_loop_poll(true);
+                        self.event_flow = EventFlow::Poll;
+                    }
+                    EventFlow::Poll => {
+                        self.fire_timers(&mut timer_ids);
+                        self.event_loop_poll(false);
+                    }
+                }
+            }
+        }
+    }
+
+    fn fire_timers(&mut self, timer_ids: &mut Vec<u64>) {
+        let time = self.time_now();
+        self.timers.update_timers(timer_ids);
+        for timer_id in timer_ids.iter() {
+            if *timer_id == KEY_REPEAT_TIMER_ID {
+                self.repeat_key_press();
+                continue;
+            }
+            self.do_callback(
+                WaylandEvent::Timer(TimerEvent {
+                    timer_id: *timer_id,
+                    time: Some(time)
+                })
+            );
+        }
+    }
+
+    pub fn do_callback(&mut self, event: WaylandEvent) {
+        if let Some(mut callback) = self.event_callback.take() {
+            self.event_flow = callback(self, event);
+            if let EventFlow::Exit = self.event_flow {
+                self.terminate_event_loop();
+            }
+            self.event_callback = Some(callback);
+        }
+    }
+
+    pub fn terminate_event_loop(&mut self) {
+        self.event_loop_running = false;
+    }
+
+    pub fn start_timer(&mut self, id: u64, timeout: f64, repeats: bool) {
+        self.timers.start_timer(id, timeout, repeats);
+    }
+
+    pub fn stop_timer(&mut self, id: u64) {
+        self.timers.stop_timer(id);
+    }
+
+    pub fn time_now(&self) -> f64 {
+        self.timers.time_now()
+    }
+
+    // Cursor
+
+    pub fn set_mouse_cursor(&mut self, cursor: MouseCursor) {
+        if self.current_cursor != cursor {
+            self.current_cursor = cursor;
+            self.apply_mouse_cursor();
+        }
+    }
+
+    fn apply_mouse_cursor(&mut self) {
+        if self.pointer.is_null() || self.pointer_surface.is_null() {
+            return
+        }
+        let names: &[&str] = match self.current_cursor {
+            MouseCursor::Hidden => &[],
+            MouseCursor::EResize => &["e-resize", "right_side"],
+            MouseCursor::NResize => &["n-resize", "top_side"],
+            MouseCursor::NeResize => &["ne-resize", "top_right_corner"],
+            MouseCursor::NwResize => &["nw-resize", "top_left_corner"],
+            MouseCursor::SResize => &["s-resize", "bottom_side"],
+            MouseCursor::SeResize => &["se-resize", "bottom_right_corner"],
+            MouseCursor::SwResize => &["sw-resize", "bottom_left_corner"],
+            MouseCursor::WResize => &["w-resize", "left_side"],
+            MouseCursor::Default => &["default", "left_ptr"],
+            MouseCursor::Crosshair => &["crosshair"],
+            MouseCursor::Hand => &["pointer", "hand2", "hand1"],
+            MouseCursor::Arrow => &["default", "left_ptr"],
+            MouseCursor::Move => &["move", "fleur"],
+            MouseCursor::NotAllowed => &["not-allowed", "crossed_circle"],
+            MouseCursor::Text => &["text", "xterm"],
+            MouseCursor::Wait => &["wait", "watch"],
+            MouseCursor::Help => &["help", "question_arrow"],
+            MouseCursor::NsResize => &["ns-resize", "v_double_arrow"],
+            MouseCursor::NeswResize => &["nesw-resize", "fd_double_arrow"],
+            MouseCursor::EwResize => &["ew-resize", "h_double_arrow"],
+            MouseCursor::NwseResize => &["nwse-resize", "bd_double_arrow"],
+            MouseCursor::ColResize => &["col-resize", "split_h", "h_double_arrow"],
+            MouseCursor::RowResize => &["row-resize", "split_v", "v_double_arrow"],
+        };
+        unsafe {
+            if names.is_empty() {
+                self.request(self.pointer, WL_POINTER_SET_CURSOR, &mut [
+                    wl_argument::uint(self.pointer_serial),
+                    wl_argument::object(ptr::null_mut()),
+                    wl_argument::int(0),
+                    wl_argument::int(0)
+                ]);
+                return
+            }
+            let scale = self.window(self.pointer_surface).map_or(1.0, | w | w.scale).ceil() as i32;
+            let Some(cursor) = self.load_cursor(names, scale) else {
+                return
+            };
+            let image = *(*cursor).images;
+            let buffer = (self.cursor_lib.as_ref().unwrap().wl_cursor_image_get_buffer)(image);
+            if buffer.is_null() {
+                return
+            }
+            if self.cursor_surface.is_null() {
+                self.cursor_surface = self.request_new(self.compositor, WL_COMPOSITOR_CREATE_SURFACE, self.wl.wl_surface_interface, &mut [
+                    wl_argument::new_id()
+                ]);
+            }
+            self.request(self.pointer, WL_POINTER_SET_CURSOR, &mut [
+                wl_argument::uint(self.pointer_serial),
+                wl_argument::object(self.cursor_surface),
+                wl_argument::int((*image).hotspot_x as i32 / scale),
+                wl_argument::int((*image).hotspot_y as i32 / scale)
+            ]);
+            self.request(self.cursor_surface, WL_SURFACE_SET_BUFFER_SCALE, &mut [wl_argument::int(scale)]);
+            self.request(self.cursor_surface, WL_SURFACE_ATTACH, &mut [
+                wl_argument::object(buffer),
+                wl_argument::int(0),
+                wl_argument::int(0)
+            ]);
+            self.request(self.cursor_surface, WL_SURFACE_DAMAGE, &mut [
+                wl_argument::int(0),
+                wl_argument::int(0),
+                wl_argument::int(i32::MAX),
+                wl_argument::int(i32::MAX)
+            ]);
+            self.request(self.cursor_surface, WL_SURFACE_COMMIT, &mut []);
+        }
+    }
+
+    unsafe fn load_cursor(&mut self, names: &[&str], scale: i32) -> Option<*mut wl_cursor> {
+        let cursor_lib = self.cursor_lib.as_ref()?;
+        if self.shm.is_null() {
+            return None
+        }
+        if self.cursor_theme.is_null() || self.cursor_theme_scale != scale {
+            if !self.cursor_theme.is_null() {
+                (cursor_lib.wl_cursor_theme_destroy)(self.cursor_theme);
+            }
+            let size = std::env::var("XCURSOR_SIZE").ok().and_then( | s | s.parse().ok()).unwrap_or(24);
+            let theme = std::env::var("XCURSOR_THEME").ok().and_then( | s | CString::new(s).ok());
+            self.cursor_theme = (cursor_lib.wl_cursor_theme_load)(
+                theme.as_ref().map_or(ptr::null(), | t | t.as_ptr()),
+                size * scale,
+                self.shm
+            );
+            self.cursor_theme_scale = scale;
+        }
+        if self.cursor_theme.is_null() {
+            return None
+        }
+        for name in names {
+            let name = CString::new(*name).unwrap();
+            let cursor = (cursor_lib.wl_cursor_theme_get_cursor)(self.cursor_theme, name.as_ptr());
+            if !cursor.is_null() && (*cursor).image_count > 0 {
+                return Some(cursor)
+            }
+        }
+        None
+    }
+
+    // Keyboard
+
+    fn update_modifiers(&mut self) {
+        if self.xkb_state.is_null() {
+            return
+        }
+        let active = | name: &[u8] | unsafe {
+            (self.xkb.xkb_state_mod_name_is_active)(self.xkb_state, name.as_ptr() as *const c_char, XKB_STATE_MODS_EFFECTIVE) > 0
+        };
+        self.modifiers = KeyModifiers {
+            shift: active(b"Shift\0"),
+            control: active(b"Control\0"),
+            alt: active(b"Mod1\0"),
+            logo: active(b"Mod4\0"),
+        };
+    }
+
+    fn key_code(&self, key: u32) -> KeyCode {
+        if self.xkb_state.is_null() {
+            return KeyCode::Unknown
+        }
+        // xkb keycodes are the evdev ones offset by 8
+        let keysym = unsafe {(self.xkb.xkb_state_key_get_one_sym)(self.xkb_state, key + 8)};
+        keysym_to_keycode(keysym)
+    }
+
+    fn key_text(&self, key: u32) -> String {
+        if self.xkb_state.is_null() {
+            return String::new()
+        }
+        let mut buffer = [0u8; 64];
+        let len = unsafe {
+            (self.xkb.xkb_state_key_get_utf8)(self.xkb_state, key + 8, buffer.as_mut_ptr() as *mut c_char, buffer.len())
+        };
+        String::from_utf8_lossy(&buffer[..(len.max(0) as usize).min(buffer.len() - 1)]).to_string()
+    }
+
+    fn send_key_press(&mut self, key: u32, is_repeat: bool) {
+        let key_code = self.key_code(key);
+        let modifiers = self.modifiers;
+        if !is_repeat && (modifiers.control || modifiers.logo) {
+            match key_code {
+                KeyCode::KeyV => self.paste(),
+                KeyCode::KeyC => {
+                    let response = Rc::new(RefCell::new(None));
+                    self.do_callback(WaylandEvent::TextCopy(TextClipboardEvent {
+                        response: response.clone()
+                    }));
+                    let response = response.borrow();
+                    if let Some(response) = response.as_ref() {
+                        self.copy_to_clipboard(ClipboardSelection::Clipboard, vec![ClipboardItem::text(response)]);
+                    }
+                }
+                KeyCode::KeyX => {
+                    let response = Rc::new(RefCell::new(None));
+                    self.do_callback(WaylandEvent::TextCut(TextClipboardEvent {
+                        response: response.clone()
+                    }));
+                    let response = response.borrow();
+                    if let Some(response) = response.as_ref() {
+                        self.copy_to_clipboard(ClipboardSelection::Clipboard, vec![ClipboardItem::text(response)]);
+                    }
+                }
+                _ => ()
+            }
+        }
+        self.do_callback(WaylandEvent::KeyDown(KeyEvent {
+            key_code,
+            is_repeat,
+            modifiers,
+            time: self.time_now()
+        }));
+        if !(modifiers.control || modifiers.logo || modifiers.alt) {
+            let text = self.key_text(key);
+            let char_code = text.chars().next().unwrap_or('\0');
+            if char_code >= ' ' && char_code != 127 as char {
+                self.do_callback(WaylandEvent::TextInput(TextInputEvent {
+                    input: text,
+                    was_paste: false,
+                    replace_last: false
+                }));
+            }
+        }
+    }
+
+    fn repeat_key_press(&mut self) {
+        if let Some(key) = self.repeat_key {
+            self.start_timer(KEY_REPEAT_TIMER_ID, 1.0 / self.repeat_rate.max(1) as f64, false);
+            self.send_key_press(key, true);
+        }
+    }
+
+    fn stop_key_repeat(&mut self) {
+        if self.repeat_key.take().is_some() {
+            self.stop_timer(KEY_REPEAT_TIMER_ID);
+        }
+    }
+
+    unsafe fn load_keymap(&mut self, fd: c_int, size: u32) {
+        let mut file = File::from_raw_fd(fd);
+        let mut keymap = Vec::with_capacity(size as usize);
+        if Read::by_ref(&mut file).take(size as u64).read_to_end(&mut keymap).is_err() {
+            return
+        }
+        if let Some(end) = keymap.iter().position( | b | *b == 0) {
+            keymap.truncate(end);
+        }
+        let Ok(keymap) = CString::new(keymap) else {
+            return
+        };
+        let xkb_keymap = (self.xkb.xkb_keymap_new_from_string)(
+            self.xkb_context,
+            keymap.as_ptr(),
+            XKB_KEYMAP_FORMAT_TEXT_V1,
+            XKB_KEYMAP_COMPILE_NO_FLAGS
+        );
+        if xkb_keymap.is_null() {
+            return
+        }
+        if !self.xkb_state.is_null() {
+            (self.xkb.xkb_state_unref)(self.xkb_state);
+        }
+        if !self.xkb_keymap.is_null() {
+            (self.xkb.xkb_keymap_unref)(self.xkb_keymap);
+        }
+        self.xkb_keymap = xkb_keymap;
+        self.xkb_state = (self.xkb.xkb_state_new)(xkb_keymap);
+    }
+
+    // Clipboard and drag and drop
+
+    /// Only the regular clipboard, the primary selection needs a protocol we don't bind
+    pub fn copy_to_clipboard(&mut self, selection: ClipboardSelection, items: Vec<ClipboardItem>) {
+        if selection != ClipboardSelection::Clipboard || self.data_device.is_null() {
+            return
+        }
+        let mut mime_types: Vec<&str> = Vec::new();
+        for item in &items {
+            let item_types = if item.is_text() {&TEXT_MIME_TYPES[..]} else {&[][..]};
+            for mime_type in item_types.iter().copied().chain(Some(item.mime_type.as_str())) {
+                if !mime_types.contains(&mime_type) {
+                    mime_types.push(mime_type);
+                }
+            }
+        }
+        unsafe {
+            if !self.clipboard_source.is_null() {
+                self.destroy(self.clipboard_source, WL_DATA_SOURCE_DESTROY);
+            }
+            self.clipboard_source = self.create_data_source(&mime_types);
+            self.request(self.data_device, WL_DATA_DEVICE_SET_SELECTION, &mut [
+                wl_argument::object(self.clipboard_source),
+                wl_argument::uint(self.keyboard_serial)
+            ]);
+        }
+        self.clipboard = items;
+        self.flush();
+    }
+
+    /// Data for a mime type a client asked our clipboard source for
+    fn clipboard_data(&self, mime_type: &str) -> Option<Rc<Vec<u8>>> {
+        if let Some(item) = self.clipboard.iter().find( | item | item.mime_type == mime_type) {
+            return Some(item.data.clone())
+        }
+        if is_text_mime_type(mime_type) {
+            return self.clipboard.iter().find( | item | item.is_text()).map( | item | item.data.clone())
+        }
+        None
+    }
+
+    /// The first of the mime types the clipboard has, reading an offer is synchronous here
+    fn read_clipboard_item(&self, mime_types: &[String]) -> Option<ClipboardItem> {
+        // we own the selection, reading from our own pipe would block forever
+        if !self.clipboard_source.is_null() {
+            return mime_types.iter().find_map( | mime_type | {
+                self.clipboard_data(mime_type).map( | data | ClipboardItem {mime_type: mime_type.clone(), data})
+            })
+        }
+        let offered = self.offers.get(&self.selection_offer)?;
+        for mime_type in mime_types {
+            let offer_type = if is_text_mime_type(mime_type) {
+                TEXT_MIME_TYPES.iter().find( | t | offered.iter().any( | o | o == *t)).map( | t | t.to_string())
+            }
+            else {
+                offered.iter().find( | o | *o == mime_type).cloned()
+            };
+            if let Some(offer_type) = offer_type {
+                let data = self.receive_offer(self.selection_offer, &offer_type)?;
+                return Some(ClipboardItem::new(mime_type, data))
+            }
+        }
+        None
+    }
+
+    pub fn read_clipboard(&mut self, request_id: LiveId, selection: ClipboardSelection, mime_types: Vec<String>) {
+        let item = if selection == ClipboardSelection::Clipboard {
+            self.read_clipboard_item(&mime_types)
+        }
+        else {
+            None
+        };
+        self.do_callback(WaylandEvent::ClipboardRead(ClipboardReadEvent {request_id, selection, item}));
+    }
+
+    unsafe fn create_data_source(&self, mime_types: &[&str]) -> *mut wl_proxy {
+        let source = self.request_new(self.data_device_manager, WL_DATA_DEVICE_MANAGER_CREATE_DATA_SOURCE, self.wl.wl_data_source_interface, &mut [
+            wl_argument::new_id()
+        ]);
+        self.add_dispatcher(source, data_source_dispatch, ptr::null_mut());
+        for mime_type in mime_types {
+            let mime_type = CString::new(*mime_type).unwrap();
+            self.request(source, WL_DATA_SOURCE_OFFER, &mut [wl_argument::string(mime_type.as_ptr())]);
+        }
+        source
+    }
+
+    fn paste(&mut self) {
+        let text = self.read_clipboard_item(&[ClipboardItem::TEXT.to_string()]).and_then( | item | item.as_text());
+        if let Some(text) = text {
+            if !text.is_empty() {
+                self.do_callback(WaylandEvent::TextInput(TextInputEvent {
+                    input: text,
+                    was_paste: true,
+                    replace_last: false
+                }));
+            }
+        }
+    }
+
+    fn offer_mime_type(&self, offer: *mut wl_proxy, mime_types: &[&'static str]) -> Option<&'static str> {
+        let offered = self.offers.get(&offer)?;
+        mime_types.iter().find( | mime_type | offered.iter().any( | o | o == *mime_type)).copied()
+    }
+
+    fn receive_offer(&self, offer: *mut wl_proxy, mime_type: &str) -> Option<Vec<u8>> {
+        unsafe {
+            let mut fds = [0 as c_int; 2];
+            if libc_sys::pipe(fds.as_mut_ptr()) != 0 {
+                return None
+            }
+            let mime_type = CString::new(mime_type).unwrap();
+            self.request(offer, WL_DATA_OFFER_RECEIVE, &mut [
+                wl_argument::string(mime_type.as_ptr()),
+                wl_argument::fd(fds[1])
+            ]);
+            // libwayland sends a dup of the write end, ours has to be closed to ever see the end of the data
+            self.flush();
+            libc_sys::close(fds[1]);
+            let mut data = Vec::new();
+            File::from_raw_fd(fds[0]).read_to_end(&mut data).ok()?;
+            Some(data)
+        }
+    }
+
+    fn destroy_offer(&mut self, offer: *mut wl_proxy) {
+        if offer.is_null() {
+            return
+        }
+        self.offers.remove(&offer);
+        unsafe {self.destroy(offer, WL_DATA_OFFER_DESTROY)};
+    }
+
+    fn send_drag(&mut self) {
+        let Some(window) = self.window(self.dnd_surface) else {
+            return
+        };
+        let response = Rc::new(Cell::new(DragResponse::None));
+        window.do_callback(WaylandEvent::Drag(DragEvent {
+            window_id: window.window_id,
+            modifiers: self.modifiers,
+            handled: Cell::new(false),
+            abs: self.dnd_pos,
+            items: Rc::new(Vec::new()),
+            response: response.clone(),
+        }));
+        let (mime_type, action) = match response.get() {
+            DragResponse::None => (None, WL_DATA_DEVICE_MANAGER_DND_ACTION_NONE),
+            DragResponse::Move => (Some(URI_LIST_MIME_TYPE), WL_DATA_DEVICE_MANAGER_DND_ACTION_MOVE),
+            _ => (Some(URI_LIST_MIME_TYPE), WL_DATA_DEVICE_MANAGER_DND_ACTION_COPY),
+        };
+        let mime_type = mime_type.filter( | _ | self.offer_mime_type(self.dnd_offer, &[URI_LIST_MIME_TYPE]).is_some());
+        unsafe {
+            let mime_type = mime_type.map( | m | CString::new(m).unwrap());
+            self.request(self.dnd_offer, WL_DATA_OFFER_ACCEPT, &mut [
+                wl_argument::uint(self.dnd_serial),
+                wl_argument::string(mime_type.as_ref().map_or(ptr::null(), | m | m.as_ptr()))
+            ]);
+            if (self.wl.wl_proxy_get_version)(self.dnd_offer) >= 3 {
+                self.request(self.dnd_offer, WL_DATA_OFFER_SET_ACTIONS, &mut [
+                    wl_argument::uint(WL_DATA_DEVICE_MANAGER_DND_ACTION_COPY | WL_DATA_DEVICE_MANAGER_DND_ACTION_MOVE),
+                    wl_argument::uint(action)
+                ]);
+            }
+        }
+    }
+
+    fn send_drop(&mut self) {
+        let offer = self.dnd_offer;
+        self.dnd_offer = ptr::null_mut();
+        let items = if self.offer_mime_type(offer, &[URI_LIST_MIME_TYPE]).is_some() {
+            self.receive_offer(offer, URI_LIST_MIME_TYPE).map( | uri_list | uri_list_to_drag_items(&String::from_utf8_lossy(&uri_list))).unwrap_or_default()
+        }
+        else {
+            Vec::new()
+        };
+        if (unsafe {(self.wl.wl_proxy_get_version)(offer)}) >= 3 {
+            unsafe {self.request(offer, WL_DATA_OFFER_FINISH, &mut [])};
+        }
+        self.destroy_offer(offer);
+        if let Some(window) = self.window(self.dnd_surface) {
+            window.do_callback(WaylandEvent::Drop(DropEvent {
+                window_id: window.window_id,
+                modifiers: self.modifiers,
+                handled: Cell::new(false),
+                abs: self.dnd_pos,
+                items: Rc::new(items),
+            }));
+        }
+    }
+
+    pub fn start_dragging(&mut self, items: Vec<DragItem>) {
+        if self.data_device.is_null() || self.pointer_surface.is_null() {
+            return
+        }
+        let uri_list = drag_items_to_uri_list(&items);
+        let text = drag_items_to_text(&items);
+        self.drag_data.clear();
+        if !uri_list.is_empty() {
+            self.drag_data.insert(URI_LIST_MIME_TYPE.to_string(), uri_list);
+        }
+        if !text.is_empty() {
+            for mime_type in TEXT_MIME_TYPES {
+                self.drag_data.insert(mime_type.to_string(), text.clone());
+            }
+        }
+        unsafe {
+            if !self.drag_source.is_null() {
+                self.destroy(self.drag_source, WL_DATA_SOURCE_DESTROY);
+            }
+            let mime_types: Vec<&str> = self.drag_data.keys().map( | k | k.as_str()).collect();
+            self.drag_source = self.create_data_source(&mime_types);
+            if (self.wl.wl_proxy_get_version)(self.drag_source) >= 3 {
+                self.request(self.drag_source, WL_DATA_SOURCE_SET_ACTIONS, &mut [
+                    wl_argument::uint(WL_DATA_DEVICE_MANAGER_DND_ACTION_COPY | WL_DATA_DEVICE_MANAGER_DND_ACTION_MOVE)
+                ]);
+            }
+            self.request(self.data_device, WL_DATA_DEVICE_START_DRAG, &mut [
+                wl_argument::object(self.drag_source),
+                wl_argument::object(self.pointer_surface),
+                wl_argument::object(ptr::null_mut()),
+                wl_argument::uint(self.button_serial)
+            ]);
+        }
+        self.flush();
+    }
+
+    // Text input
+
+    pub fn update_text_input(&mut self, window: &WaylandWindow, enable: bool) {
+        if self.text_input.is_null() || self.text_input_surface != window.surface {
+            return
+        }
+        unsafe {
+            if window.ime_active {
+                if enable {
+                    self.request(self.text_input, ZWP_TEXT_INPUT_V3_ENABLE, &mut []);
+                    self.request(self.text_input, ZWP_TEXT_INPUT_V3_SET_CONTENT_TYPE, &mut [
+                        wl_argument::uint(ZWP_TEXT_INPUT_V3_CONTENT_HINT_NONE),
+                        wl_argument::uint(ZWP_TEXT_INPUT_V3_CONTENT_PURPOSE_NORMAL)
+                    ]);
+                }
+                self.request(self.text_input, ZWP_TEXT_INPUT_V3_SET_CURSOR_RECTANGLE, &mut [
+                    wl_argument::int(window.ime_spot.x as i32),
+                    wl_argument::int(window.ime_spot.y as i32),
+                    wl_argument::int(1),
+                    wl_argument::int(20)
+                ]);
+            }
+            else {
+                self.request(self.text_input, ZWP_TEXT_INPUT_V3_DISABLE, &mut []);
+            }
+            self.request(self.text_input, ZWP_TEXT_INPUT_V3_COMMIT, &mut []);
+        }
+        if !window.ime_active && self.ime_preedit_visible {
+            self.ime_preedit_visible = false;
+            self.do_callback(WaylandEvent::TextPreedit(TextPreeditEvent {
+                text: String::new(),
+                cursor: 0
+            }));
+        }
+    }
+
+    fn apply_text_input(&mut self) {
+        if let Some(text) = self.pending_commit.take() {
+            if self.ime_preedit_visible {
+                self.ime_preedit_visible = false;
+                self.do_callback(WaylandEvent::TextPreedit(TextPreeditEvent {
+                    text: String::new(),
+                    cursor: 0
+                }));
+            }
+            if !text.is_empty() {
+                self.do_callback(WaylandEvent::TextInput(TextInputEvent {
+                    input: text,
+                    was_paste: false,
+                    replace_last: false
+                }));
+            }
+        }
+        match self.pending_preedit.take() {
+            Some((text, cursor)) if !text.is_empty() => {
+                self.ime_preedit_visible = true;
+                // the compositor gives the caret as a byte offset
+                let cursor = text.get(..cursor.min(text.len())).map_or(0, | t | t.chars().count());
+                self.do_callback(WaylandEvent::TextPreedit(TextPreeditEvent {text, cursor}));
+            }
+            _ => if self.ime_preedit_visible {
+                self.ime_preedit_visible = false;
+                self.do_callback(WaylandEvent::TextPreedit(TextPreeditEvent {
+                    text: String::new(),
+                    cursor: 0
+                }));
+            }
+        }
+    }
+
+    // Pointer
+
+    fn send_scroll(&mut self) {
+        let scroll = std::mem::take(&mut self.scroll);
+        if !scroll.has_value {
+            return
+        }
+        let Some(window) = self.window(self.pointer_surface) else {
+            return
+        };
+        let time_now = self.time_now();
+        let delta = if scroll.is_wheel {
+            let last_scroll_time = self.last_scroll_time;
+            self.last_scroll_time = time_now;
+            // same completely arbitrary acceleration curve as the X11 backend
+            let speed = 1200.0 * (0.2 - 2. * (time_now - last_scroll_time)).max(0.01);
+            let steps = if scroll.discrete != DVec2::default() {scroll.discrete} else {
+                dvec2(scroll.value.x.signum() * (scroll.value.x != 0.0) as i32 as f64, scroll.value.y.signum() * (scroll.value.y != 0.0) as i32 as f64)
+            };
+            steps * speed
+        }
+        else {
+            scroll.value
+        };
+        window.do_callback(WaylandEvent::Scroll(ScrollEvent {
+            window_id: window.window_id,
+            scroll: delta,
+            abs: window.last_mouse_pos,
+            modifiers: self.modifiers,
+            is_mouse: scroll.is_wheel,
+            handled_x: Cell::new(false),
+            handled_y: Cell::new(false),
+            time: time_now
+        }));
+    }
+
+    fn pointer_button(&mut self, serial: u32, button: u32, pressed: bool) {
+        // linux input codes, mapped to the numbering used by the other platforms
+        let button = match button {
+            0x110 => 0,
+            0x111 => 1,
+            0x112 => 2,
+            button => (button as usize).saturating_sub(0x110),
+        };
+        let Some(window) = self.window(self.pointer_surface) else {
+            return
+        };
+        if !pressed {
+            window.send_mouse_up(button, self.modifiers);
+            return
+        }
+        self.button_serial = serial;
+        let time_now = self.time_now();
+        let pos = window.last_mouse_pos;
+        match window.last_nc_mode {
+            Some(WaylandNcMode::Move) if button == 0 => {
+                if time_now - self.last_click_time < 0.35 && (pos - self.last_click_pos).length() < 5.0 {
+                    if window.is_maximized {
+                        window.restore();
+                    }
+                    else {
+                        window.maximize();
+                    }
+                }
+                else {
+                    window.start_move(serial);
+                }
+            }
+            Some(WaylandNcMode::Move) if button == 1 => {
+                window.show_window_menu(serial, pos);
+            }
+            Some(WaylandNcMode::Resize(edge)) if button == 0 => {
+                window.start_resize(serial, edge);
+            }
+            _ => {
+                window.send_mouse_down(button, self.modifiers);
+            }
+        }
+        self.last_click_time = time_now;
+        self.last_click_pos = pos;
+    }
+
+    fn pointer_motion(&mut self, pos: DVec2) {
+        let Some(window) = self.window(self.pointer_surface) else {
+            return
+        };
+        let nc_mode = window.update_nc_mode(pos);
+        window.send_mouse_move(pos, self.modifiers);
+        if let Some(WaylandNcMode::Resize(edge)) = nc_mode {
+            self.set_mouse_cursor(match edge {
+                XDG_TOPLEVEL_RESIZE_EDGE_TOP_LEFT => MouseCursor::NwResize,
+                XDG_TOPLEVEL_RESIZE_EDGE_TOP_RIGHT => MouseCursor::NeResize,
+                XDG_TOPLEVEL_RESIZE_EDGE_BOTTOM_LEFT => MouseCursor::SwResize,
+                XDG_TOPLEVEL_RESIZE_EDGE_BOTTOM_RIGHT => MouseCursor::SeResize,
+                XDG_TOPLEVEL_RESIZE_EDGE_LEFT => MouseCursor::WResize,
+                XDG_TOPLEVEL_RESIZE_EDGE_RIGHT => MouseCursor::EResize,
+                XDG_TOPLEVEL_RESIZE_EDGE_TOP => MouseCursor::NResize,
+                _ => MouseCursor::SResize,
+            });
+        }
+    }
+
+    // Touch
+
+    fn send_touch_update(&mut self) {
+        let Some(window) = self.window(self.touch_surface) else {
+            self.touches.clear();
+            return
+        };
+        window.do_callback(WaylandEvent::TouchUpdate(TouchUpdateEvent {
+            time: self.time_now(),
+            window_id: window.window_id,
+            modifiers: self.modifiers,
+            touches: self.touches.clone(),
+        }));
+        self.touches.retain( | t | !matches!(t.state, TouchState::Stop));
+        for touch in &mut self.touches {
+            touch.state = TouchState::Stable;
+        }
+    }
+}
+
+unsafe fn args_slice<'a>(args: *mut wl_argument, msg: *const wl_message) -> &'a [wl_argument] {
+    // count the arguments from the signature, skipping version and nullability markers
+    let count = CStr::from_ptr((*msg).signature).to_bytes().iter().filter( | c | c.is_ascii_alphabetic()).count();
+    if count == 0 {
+        return &[]
+    }
+    std::slice::from_raw_parts(args, count)
+}
+
+unsafe extern "C" fn registry_dispatch(_: *const c_void, _: *mut c_void, opcode: u32, msg: *const wl_message, args: *mut wl_argument) -> c_int {
+    let app = get_wayland_app_global();
+    let args = args_slice(args, msg);
+    match opcode {
+        WL_REGISTRY_EVENT_GLOBAL => {
+            let name = args[0].u;
+            let interface = args[1].to_string().unwrap_or_default();
+            let version = args[2].u;
+            match interface.as_str() {
+                "wl_compositor" => {
+                    app.compositor = app.bind(name, app.wl.wl_compositor_interface, version.min(4));
+                }
+                "wl_shm" => {
+                    app.shm = app.bind(name, app.wl.wl_shm_interface, 1);
+                }
+                "xdg_wm_base" => {
+                    app.xdg_wm_base = app.bind(name, &xdg_wm_base, version.min(2));
+                    app.add_dispatcher(app.xdg_wm_base, xdg_wm_base_dispatch, ptr::null_mut());
+                }
+                "wl_seat" if app.seat.is_null() => {
+                    app.seat = app.bind(name, app.wl.wl_seat_interface, version.min(5));
+                    app.add_dispatcher(app.seat, seat_dispatch, ptr::null_mut());
+                }
+                "wl_output" => {
+                    let output = app.bind(name, app.wl.wl_output_interface, version.min(2));
+                    app.add_dispatcher(output, output_dispatch, ptr::null_mut());
+                    app.outputs.push(WaylandOutput {output, name, scale: 1});
+                }
+                "wl_data_device_manager" => {
+                    app.data_device_manager = app.bind(name, app.wl.wl_data_device_manager_interface, version.min(3));
+                }
+                "wp_fractional_scale_manager_v1" => {
+                    app.fractional_scale_manager = app.bind(name, &wp_fractional_scale_manager_v1, 1);
+                }
+                "wp_viewporter" => {
+                    app.viewporter = app.bind(name, &wp_viewporter, 1);
+                }
+                "zwp_text_input_manager_v3" => {
+                    app.text_input_manager = app.bind(name, &zwp_text_input_manager_v3, 1);
+                }
+                _ => ()
+            }
+        }
+        WL_REGISTRY_EVENT_GLOBAL_REMOVE => {
+            let name = args[0].u;
+            if let Some(index) = app.outputs.iter().position( | o | o.name == name) {
+                let output = app.outputs.remove(index).output;
+                for window in app.window_map.values() {
+                    let window = &mut **window;
+                    window.outputs.retain( | o | *o != output);
+                    window.update_scale();
+                }
+                (app.wl.wl_proxy_destroy)(output);
+            }
+        }
+        _ => ()
+    }
+    0
+}
+
+unsafe extern "C" fn xdg_wm_base_dispatch(_: *const c_void, _: *mut c_void, opcode: u32, _: *const wl_message, args: *mut wl_argument) -> c_int {
+    let app = get_wayland_app_global();
+    if opcode == XDG_WM_BASE_EVENT_PING {
+        app.request(app.xdg_wm_base, XDG_WM_BASE_PONG, &mut [*args]);
+    }
+    0
+}
+
+unsafe extern "C" fn output_dispatch(_: *const c_void, target: *mut c_void, opcode: u32, _: *const wl_message, args: *mut wl_argument) -> c_int {
+    let app = get_wayland_app_global();
+    if opcode == WL_OUTPUT_EVENT_SCALE {
+        let output = target as *mut wl_proxy;
+        if let Some(o) = app.outputs.iter_mut().find( | o | o.output == output) {
+            o.scale = (*args).i;
+        }
+        for window in app.window_map.values() {
+            let window = &mut **window;
+            if window.outputs.contains(&output) {
+                window.update_scale();
+            }
+        }
+    }
+    0
+}
+
+unsafe extern "C" fn seat_dispatch(_: *const c_void, _: *mut c_void, opcode: u32, _: *const wl_message, args: *mut wl_argument) -> c_int {
+    let app = get_wayland_app_global();
+    if opcode != WL_SEAT_EVENT_CAPABILITIES {
+        return 0
+    }
+    let caps = (*args).u;
+    if caps & WL_SEAT_CAPABILITY_POINTER != 0 && app.pointer.is_null() {
+        app.pointer = app.request_new(app.seat, WL_SEAT_GET_POINTER, app.wl.wl_pointer_interface, &mut [wl_argument::new_id()]);
+        app.add_dispatcher(app.pointer, pointer_dispatch, ptr::null_mut());
+    }
+    if caps & WL_SEAT_CAPABILITY_KEYBOARD != 0 && app.keyboard.is_null() {
+        app.keyboard = app.request_new(app.seat, WL_SEAT_GET_KEYBOARD, app.wl.wl_keyboard_interface, &mut [wl_argument::new_id()]);
+        app.add_dispatcher(app.keyboard, keyboard_dispatch, ptr::null_mut());
+    }
+    if caps & WL_SEAT_CAPABILITY_TOUCH != 0 && app.touch.is_null() {
+        app.touch = app.request_new(app.seat, WL_SEAT_GET_TOUCH, app.wl.wl_touch_interface, &mut [wl_argument::new_id()]);
+        app.add_dispatcher(app.touch, touch_dispatch, ptr::null_mut());
+    }
+    0
+}
+
+unsafe extern "C" fn pointer_dispatch(_: *const c_void, _: *mut c_void, opcode: u32, msg: *const wl_message, args: *mut wl_argument) -> c_int {
+    let app = get_wayland_app_global();
+    let args = args_slice(args, msg);
+    match opcode {
+        WL_POINTER_EVENT_ENTER => {
+            app.pointer_serial = args[0].u;
+            app.pointer_surface = args[1].o;
+            // the compositor may have changed the cursor while we were away
+            app.apply_mouse_cursor();
+            app.pointer_motion(dvec2(wl_fixed_to_f64(args[2].f), wl_fixed_to_f64(args[3].f)));
+        }
+        WL_POINTER_EVENT_LEAVE => {
+            app.pointer_surface = ptr::null_mut();
+        }
+        WL_POINTER_EVENT_MOTION => {
+            app.pointer_motion(dvec2(wl_fixed_to_f64(args[1].f), wl_fixed_to_f64(args[2].f)));
+        }
+        WL_POINTER_EVENT_BUTTON => {
+            app.pointer_button(args[0].u, args[2].u, args[3].u == WL_POINTER_BUTTON_STATE_PRESSED);
+        }
+        WL_POINTER_EVENT_AXIS => {
+            let value = wl_fixed_to_f64(args[2].f);
+            match args[1].u {
+                WL_POINTER_AXIS_VERTICAL_SCROLL => app.scroll.value.y += value,
+                WL_POINTER_AXIS_HORIZONTAL_SCROLL => app.scroll.value.x += value,
+                _ => ()
+            }
+            app.scroll.has_value = true;
+            // before version 5 there are no frames to group the axis events
+            if (app.wl.wl_proxy_get_version)(app.pointer) < 5 {
+                app.scroll.is_wheel = true;
+                app.send_scroll();
+            }
+        }
+        WL_POINTER_EVENT_AXIS_SOURCE => {
+            app.scroll.is_wheel = args[0].u == WL_POINTER_AXIS_SOURCE_WHEEL;
+        }
+        WL_POINTER_EVENT_AXIS_DISCRETE => {
+            let steps = args[1].i as f64;
+            match args[0].u {
+                WL_POINTER_AXIS_VERTICAL_SCROLL => app.scroll.discrete.y += steps,
+                WL_POINTER_AXIS_HORIZONTAL_SCROLL => app.scroll.discrete.x += steps,
+                _ => ()
+            }
+        }
+        WL_POINTER_EVENT_FRAME => {
+            app.send_scroll();
+        }
+        _ => ()
+    }
+    0
+}
+
+unsafe extern "C" fn keyboard_dispatch(_: *const c_void, _: *mut c_void, opcode: u32, msg: *const wl_message, args: *mut wl_argument) -> c_int {
+    let app = get_wayland_app_global();
+    let args = args_slice(args, msg);
+    match opcode {
+        WL_KEYBOARD_EVENT_KEYMAP => {
+            let fd = args[1].h;
+            if args[0].u == WL_KEYBOARD_KEYMAP_FORMAT_XKB_V1 {
+                app.load_keymap(fd, args[2].u);
+            }
+            else {
+                libc_sys::close(fd);
+            }
+        }
+        WL_KEYBOARD_EVENT_ENTER => {
+            app.keyboard_serial = args[0].u;
+            app.keyboard_surface = args[1].o;
+            if let Some(window) = app.window(app.keyboard_surface) {
+                window.send_focus_event();
+            }
+        }
+        WL_KEYBOARD_EVENT_LEAVE => {
+            app.stop_key_repeat();
+            if let Some(window) = app.window(app.keyboard_surface) {
+                window.send_focus_lost_event();
+            }
+            app.keyboard_surface = ptr::null_mut();
+        }
+        WL_KEYBOARD_EVENT_KEY => {
+            app.keyboard_serial = args[0].u;
+            let key = args[2].u;
+            if args[3].u == WL_KEYBOARD_KEY_STATE_PRESSED {
+                app.stop_key_repeat();
+                if !app.xkb_keymap.is_null() && app.repeat_rate > 0 && (app.xkb.xkb_keymap_key_repeats)(app.xkb_keymap, key + 8) != 0 {
+                    app.repeat_key = Some(key);
+                    app.start_timer(KEY_REPEAT_TIMER_ID, app.repeat_delay as f64 / 1000.0, false);
+                }
+                app.send_key_press(key, false);
+            }
+            else {
+                if app.repeat_key == Some(key) {
+                    app.stop_key_repeat();
+                }
+                let key_code = app.key_code(key);
+                app.do_callback(WaylandEvent::KeyUp(KeyEvent {
+                    key_code,
+                    is_repeat: false,
+                    modifiers: app.modifiers,
+                    time: app.time_now()
+                }));
+            }
+        }
+        WL_KEYBOARD_EVENT_MODIFIERS => {
+            if !app.xkb_state.is_null() {
+                (app.xkb.xkb_state_update_mask)(app.xkb_state, args[1].u, args[2].u, args[3].u, 0, 0, args[4].u);
+            }
+            app.update_modifiers();
+        }
+        WL_KEYBOARD_EVENT_REPEAT_INFO => {
+            app.repeat_rate = args[0].i;
+            app.repeat_delay = args[1].i;
+        }
+        _ => ()
+    }
+    0
+}
+
+unsafe extern "C" fn touch_dispatch(_: *const c_void, _: *mut c_void, opcode: u32, msg: *const wl_message, args: *mut wl_argument) -> c_int {
+    let app = get_wayland_app_global();
+    let args = args_slice(args, msg);
+    let time = app.time_now();
+    match opcode {
+        WL_TOUCH_EVENT_DOWN => {
+            app.touch_surface = args[2].o;
+            app.touches.push(TouchPoint {
+                state: TouchState::Start,
+                abs: dvec2(wl_fixed_to_f64(args[4].f), wl_fixed_to_f64(args[5].f)),
+                time,
+                uid: args[3].i as u64,
+                rotation_angle: 0.0,
+                force: 0.0,
+                radius: dvec2(1.0, 1.0),
+                handled: Cell::new(Area::Empty),
+                sweep_lock: Cell::new(Area::Empty),
+            });
+        }
+        WL_TOUCH_EVENT_UP => {
+            let uid = args[2].i as u64;
+            if let Some(touch) = app.touches.iter_mut().find( | t | t.uid == uid) {
+                touch.state = TouchState::Stop;
+                touch.time = time;
+            }
+        }
+        WL_TOUCH_EVENT_MOTION => {
+            let uid = args[1].i as u64;
+            if let Some(touch) = app.touches.iter_mut().find( | t | t.uid == uid) {
+                if !matches!(touch.state, TouchState::Start) {
+                    touch.state = TouchState::Move;
+                }
+                touch.abs = dvec2(wl_fixed_to_f64(args[2].f), wl_fixed_to_f64(args[3].f));
+                touch.time = time;
+            }
+        }
+        WL_TOUCH_EVENT_FRAME => {
+            app.send_touch_update();
+        }
+        WL_TOUCH_EVENT_CANCEL => {
+            for touch in &mut app.touches {
+                touch.state = TouchState::Stop;
+            }
+            app.send_touch_update();
+        }
+        _ => ()
+    }
+    0
+}
+
+unsafe extern "C" fn data_device_dispatch(_: *const c_void, _: *mut c_void, opcode: u32, msg: *const wl_message, args: *mut wl_argument) -> c_int {
+    let app = get_wayland_app_global();
+    let args = args_slice(args, msg);
+    match opcode {
+        WL_DATA_DEVICE_EVENT_DATA_OFFER => {
+            let offer = args[0].o;
+            app.add_dispatcher(offer, data_offer_dispatch, ptr::null_mut());
+            app.offers.insert(offer, Vec::new());
+        }
+        WL_DATA_DEVICE_EVENT_ENTER => {
+            app.dnd_serial = args[0].u;
+            app.dnd_surface = args[1].o;
+            app.dnd_pos = dvec2(wl_fixed_to_f64(args[2].f), wl_fixed_to_f64(args[3].f));
+            app.dnd_offer = args[4].o;
+            if !app.dnd_offer.is_null() {
+                app.send_drag();
+            }
+        }
+        WL_DATA_DEVICE_EVENT_MOTION => {
+            app.dnd_pos = dvec2(wl_fixed_to_f64(args[1].f), wl_fixed_to_f64(args[2].f));
+            if !app.dnd_offer.is_null() {
+                app.send_drag();
+            }
+        }
+        WL_DATA_DEVICE_EVENT_LEAVE => {
+            if !app.dnd_offer.is_null() {
+                let offer = app.dnd_offer;
+                app.dnd_offer = ptr::null_mut();
+                app.destroy_offer(offer);
+                app.do_callback(WaylandEvent::DragEnd);
+            }
+            app.dnd_surface = ptr::null_mut();
+        }
+        WL_DATA_DEVICE_EVENT_DROP => {
+            if !app.dnd_offer.is_null() {
+                app.send_drop();
+            }
+        }
+        WL_DATA_DEVICE_EVENT_SELECTION => {
+            let offer = args[0].o;
+            if app.selection_offer != offer {
+                app.destroy_offer(app.selection_offer);
+            }
+            app.selection_offer = offer;
+        }
+        _ => ()
+    }
+    0
+}
+
+unsafe extern "C" fn data_offer_dispatch(_: *const c_void, target: *mut c_void, opcode: u32, msg: *const wl_message, args: *mut wl_argument) -> c_int {
+    let app = get_wayland_app_global();
+    let args = args_slice(args, msg);
+    if opcode == WL_DATA_OFFER_EVENT_OFFER {
+        if let Some(mime_types) = app.offers.get_mut(&(target as *mut wl_proxy)) {
+            mime_types.push(args[0].to_string().unwrap_or_default());
+        }
+    }
+    0
+}
+
+unsafe extern "C" fn data_source_dispatch(_: *const c_void, target: *mut c_void, opcode: u32, msg: *const wl_message, args: *mut wl_argument) -> c_int {
+    let app = get_wayland_app_global();
+    let args = args_slice(args, msg);
+    let source = target as *mut wl_proxy;
+    match opcode {
+        WL_DATA_SOURCE_EVENT_SEND => {
+            let mime_type = args[0].to_string().unwrap_or_default();
+            let mut file = File::from_raw_fd(args[1].h);
+            if source == app.clipboard_source {
+                if let Some(data) = app.clipboard_data(&mime_type) {
+                    let _ = file.write_all(&data);
+                }
+            }
+            else if let Some(data) = app.drag_data.get(&mime_type) {
+                let _ = file.write_all(data.as_bytes());
+            }
+        }
+        WL_DATA_SOURCE_EVENT_CANCELLED | WL_DATA_SOURCE_EVENT_DND_FINISHED => {
+            app.destroy(source, WL_DATA_SOURCE_DESTROY);
+            if source == app.clipboard_source {
+                app.clipboard_source = ptr::null_mut();
+            }
+            if source == app.drag_source {
+                app.drag_source = ptr::null_mut();
+                app.drag_data.clear();
+                app.do_callback(WaylandEvent::DragEnd);
+            }
+        }
+        _ => ()
+    }
+    0
+}
+
+unsafe extern "C" fn text_input_dispatch(_: *const c_void, _: *mut c_void, opcode: u32, msg: *const wl_message, args: *mut wl_argument) -> c_int {
+    let app = get_wayland_app_global();
+    let args = args_slice(args, msg);
+    match opcode {
+        ZWP_TEXT_INPUT_V3_EVENT_ENTER => {
+            app.text_input_surface = args[0].o;
+            if let Some(window) = app.window(app.text_input_surface) {
+                if window.ime_active {
+                    app.update_text_input(window, true);
+                }
+            }
+        }
+        ZWP_TEXT_INPUT_V3_EVENT_LEAVE => {
+            if let Some(window) = app.window(app.text_input_surface) {
+                if window.ime_active {
+                    app.request(app.text_input, ZWP_TEXT_INPUT_V3_DISABLE, &mut []);
+                    app.request(app.text_input, ZWP_TEXT_INPUT_V3_COMMIT, &mut []);
+                }
+            }
+            app.text_input_surface = ptr::null_mut();
+        }
+        ZWP_TEXT_INPUT_V3_EVENT_PREEDIT_STRING => {
+            app.pending_preedit = Some((args[0].to_string().unwrap_or_default(), args[1].i.max(0) as usize));
+        }
+        ZWP_TEXT_INPUT_V3_EVENT_COMMIT_STRING => {
+            app.pending_commit = Some(args[0].to_string().unwrap_or_default());
+        }
+        ZWP_TEXT_INPUT_V3_EVENT_DONE => {
+            app.apply_text_input();
+        }
+        _ => ()
+    }
+    0
+}
@@ -0,0 +1,52 @@
+use {
+    crate::{
+        event::{
+            MouseDownEvent,
+            MouseUpEvent,
+            MouseMoveEvent,
+            ScrollEvent,
+            WindowGeomChangeEvent,
+            WindowDragQueryEvent,
+            WindowCloseRequestedEvent,
+            WindowClosedEvent,
+            TextInputEvent,
+            TextPreeditEvent,
+            KeyEvent,
+            DragEvent,
+            DropEvent,
+            TextClipboardEvent,
+            ClipboardReadEvent,
+            TimerEvent,
+            TouchUpdateEvent,
+        },
+    }
+};
+
+#[derive(Debug)]
+pub enum WaylandEvent {
+    AppGotFocus,
+    AppLostFocus,
+    WindowGeomChange(WindowGeomChangeEvent),
+    WindowClosed(WindowClosedEvent),
+    Paint,
+    
+    MouseDown(MouseDownEvent),
+    MouseUp(MouseUpEvent),
+    MouseMove(MouseMoveEvent),
+    Scroll(ScrollEvent),
+    TouchUpdate(TouchUpdateEvent),
+    
+    WindowDragQuery(WindowDragQueryEvent),
+    WindowCloseRequested(WindowCloseRequestedEvent),
+    TextInput(TextInputEvent),
+    TextPreedit(TextPreeditEvent),
+    Drag(DragEvent),
+    Drop(DropEvent),
+    DragEnd,
+    KeyDown(KeyEvent),
+    KeyUp(KeyEvent),
+    TextCopy(TextClipboardEvent),
+    TextCut(TextClipboardEvent),
+    ClipboardRead(ClipboardReadEvent),
+    Timer(TimerEvent),
+}
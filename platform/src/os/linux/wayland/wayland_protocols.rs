@@ -0,0 +1,260 @@
+#![allow(non_upper_case_globals)]
+
+// Interface tables for the protocols that don't ship with libwayland-client, written out by hand
+// instead of running wayland-scanner. Only the message names and signatures matter, the object
+// types are left out which turns off libwayland's (optional) object type checking for them.
+
+use {
+    std::os::raw::c_char,
+    self::super::wayland_sys::{wl_interface, wl_message},
+};
+
+static NULL_TYPES: [Option<&'static wl_interface>; 8] = [None; 8];
+
+macro_rules! wl_message {
+    ($name:literal, $signature:literal) => {
+        wl_message {
+            name: concat!($name, "\0").as_ptr() as *const c_char,
+            signature: concat!($signature, "\0").as_ptr() as *const c_char,
+            types: NULL_TYPES.as_ptr() as *const *const wl_interface,
+        }
+    }
+}
+
+macro_rules! wl_interface {
+    ($name:ident, $version:literal, $requests:ident, $events:ident) => {
+        pub static $name: wl_interface = wl_interface {
+            name: concat!(stringify!($name), "\0").as_ptr() as *const c_char,
+            version: $version,
+            method_count: $requests.len() as i32,
+            methods: $requests.as_ptr(),
+            event_count: $events.len() as i32,
+            events: $events.as_ptr(),
+        };
+    }
+}
+
+// xdg-shell
+
+pub const XDG_WM_BASE_DESTROY: u32 = 0;
+pub const XDG_WM_BASE_GET_XDG_SURFACE: u32 = 2;
+pub const XDG_WM_BASE_PONG: u32 = 3;
+pub const XDG_WM_BASE_EVENT_PING: u32 = 0;
+
+static xdg_wm_base_requests: [wl_message; 4] = [
+    wl_message!("destroy", ""),
+    wl_message!("create_positioner", "n"),
+    wl_message!("get_xdg_surface", "no"),
+    wl_message!("pong", "u"),
+];
+static xdg_wm_base_events: [wl_message; 1] = [
+    wl_message!("ping", "u"),
+];
+wl_interface!(xdg_wm_base, 5, xdg_wm_base_requests, xdg_wm_base_events);
+
+pub const XDG_SURFACE_DESTROY: u32 = 0;
+pub const XDG_SURFACE_GET_TOPLEVEL: u32 = 1;
+pub const XDG_SURFACE_SET_WINDOW_GEOMETRY: u32 = 3;
+pub const XDG_SURFACE_ACK_CONFIGURE: u32 = 4;
+pub const XDG_SURFACE_EVENT_CONFIGURE: u32 = 0;
+
+static xdg_surface_requests: [wl_message; 5] = [
+    wl_message!("destroy", ""),
+    wl_message!("get_toplevel", "n"),
+    wl_message!("get_popup", "n?oo"),
+    wl_message!("set_window_geometry", "iiii"),
+    wl_message!("ack_configure", "u"),
+];
+static xdg_surface_events: [wl_message; 1] = [
+    wl_message!("configure", "u"),
+];
+wl_interface!(xdg_surface, 5, xdg_surface_requests, xdg_surface_events);
+
+pub const XDG_TOPLEVEL_DESTROY: u32 = 0;
+pub const XDG_TOPLEVEL_SET_TITLE: u32 = 2;
+pub const XDG_TOPLEVEL_SET_APP_ID: u32 = 3;
+pub const XDG_TOPLEVEL_SHOW_WINDOW_MENU: u32 = 4;
+pub const XDG_TOPLEVEL_MOVE: u32 = 5;
+pub const XDG_TOPLEVEL_RESIZE: u32 = 6;
+pub const XDG_TOPLEVEL_SET_MIN_SIZE: u32 = 8;
+pub const XDG_TOPLEVEL_SET_MAXIMIZED: u32 = 9;
+pub const XDG_TOPLEVEL_UNSET_MAXIMIZED: u32 = 10;
+pub const XDG_TOPLEVEL_SET_FULLSCREEN: u32 = 11;
+pub const XDG_TOPLEVEL_UNSET_FULLSCREEN: u32 = 12;
+pub const XDG_TOPLEVEL_SET_MINIMIZED: u32 = 13;
+pub const XDG_TOPLEVEL_EVENT_CONFIGURE: u32 = 0;
+pub const XDG_TOPLEVEL_EVENT_CLOSE: u32 = 1;
+
+static xdg_toplevel_requests: [wl_message; 14] = [
+    wl_message!("destroy", ""),
+    wl_message!("set_parent", "?o"),
+    wl_message!("set_title", "s"),
+    wl_message!("set_app_id", "s"),
+    wl_message!("show_window_menu", "ouii"),
+    wl_message!("move", "ou"),
+    wl_message!("resize", "ouu"),
+    wl_message!("set_max_size", "ii"),
+    wl_message!("set_min_size", "ii"),
+    wl_message!("set_maximized", ""),
+    wl_message!("unset_maximized", ""),
+    wl_message!("set_fullscreen", "?o"),
+    wl_message!("unset_fullscreen", ""),
+    wl_message!("set_minimized", ""),
+];
+static xdg_toplevel_events: [wl_message; 4] = [
+    wl_message!("configure", "iia"),
+    wl_message!("close", ""),
+    wl_message!("configure_bounds", "4ii"),
+    wl_message!("wm_capabilities", "5a"),
+];
+wl_interface!(xdg_toplevel, 5, xdg_toplevel_requests, xdg_toplevel_events);
+
+// fractional-scale-v1
+
+pub const WP_FRACTIONAL_SCALE_MANAGER_V1_GET_FRACTIONAL_SCALE: u32 = 1;
+pub const WP_FRACTIONAL_SCALE_V1_DESTROY: u32 = 0;
+pub const WP_FRACTIONAL_SCALE_V1_EVENT_PREFERRED_SCALE: u32 = 0;
+
+static wp_fractional_scale_manager_v1_requests: [wl_message; 2] = [
+    wl_message!("destroy", ""),
+    wl_message!("get_fractional_scale", "no"),
+];
+static wp_fractional_scale_manager_v1_events: [wl_message; 0] = [];
+wl_interface!(wp_fractional_scale_manager_v1, 1, wp_fractional_scale_manager_v1_requests, wp_fractional_scale_manager_v1_events);
+
+static wp_fractional_scale_v1_requests: [wl_message; 1] = [
+    wl_message!("destroy", ""),
+];
+static wp_fractional_scale_v1_events: [wl_message; 1] = [
+    wl_message!("preferred_scale", "u"),
+];
+wl_interface!(wp_fractional_scale_v1, 1, wp_fractional_scale_v1_requests, wp_fractional_scale_v1_events);
+
+// viewporter
+
+pub const WP_VIEWPORTER_GET_VIEWPORT: u32 = 1;
+pub const WP_VIEWPORT_DESTROY: u32 = 0;
+pub const WP_VIEWPORT_SET_DESTINATION: u32 = 2;
+
+static wp_viewporter_requests: [wl_message; 2] = [
+    wl_message!("destroy", ""),
+    wl_message!("get_viewport", "no"),
+];
+static wp_viewporter_events: [wl_message; 0] = [];
+wl_interface!(wp_viewporter, 1, wp_viewporter_requests, wp_viewporter_events);
+
+static wp_viewport_requests: [wl_message; 3] = [
+    wl_message!("destroy", ""),
+    wl_message!("set_source", "ffff"),
+    wl_message!("set_destination", "ii"),
+];
+static wp_viewport_events: [wl_message; 0] = [];
+wl_interface!(wp_viewport, 1, wp_viewport_requests, wp_viewport_events);
+
+// text-input-unstable-v3
+
+pub const ZWP_TEXT_INPUT_MANAGER_V3_GET_TEXT_INPUT: u32 = 1;
+pub const ZWP_TEXT_INPUT_V3_DESTROY: u32 = 0;
+pub const ZWP_TEXT_INPUT_V3_ENABLE: u32 = 1;
+pub const ZWP_TEXT_INPUT_V3_DISABLE: u32 = 2;
+pub const ZWP_TEXT_INPUT_V3_SET_CONTENT_TYPE: u32 = 5;
+pub const ZWP_TEXT_INPUT_V3_SET_CURSOR_RECTANGLE: u32 = 6;
+pub const ZWP_TEXT_INPUT_V3_COMMIT: u32 = 7;
+pub const ZWP_TEXT_INPUT_V3_EVENT_ENTER: u32 = 0;
+pub const ZWP_TEXT_INPUT_V3_EVENT_LEAVE: u32 = 1;
+pub const ZWP_TEXT_INPUT_V3_EVENT_PREEDIT_STRING: u32 = 2;
+pub const ZWP_TEXT_INPUT_V3_EVENT_COMMIT_STRING: u32 = 3;
+pub const ZWP_TEXT_INPUT_V3_EVENT_DELETE_SURROUNDING_TEXT: u32 = 4;
+pub const ZWP_TEXT_INPUT_V3_EVENT_DONE: u32 = 5;
+
+static zwp_text_input_manager_v3_requests: [wl_message; 2] = [
+    wl_message!("destroy", ""),
+    wl_message!("get_text_input", "no"),
+];
+static zwp_text_input_manager_v3_events: [wl_message; 0] = [];
+wl_interface!(zwp_text_input_manager_v3, 1, zwp_text_input_manager_v3_requests, zwp_text_input_manager_v3_events);
+
+static zwp_text_input_v3_requests: [wl_message; 8] = [
+    wl_message!("destroy", ""),
+    wl_message!("enable", ""),
+    wl_message!("disable", ""),
+    wl_message!("set_surrounding_text", "sii"),
+    wl_message!("set_text_change_cause", "u"),
+    wl_message!("set_content_type", "uu"),
+    wl_message!("set_cursor_rectangle", "iiii"),
+    wl_message!("commit", ""),
+];
+static zwp_text_input_v3_events: [wl_message; 6] = [
+    wl_message!("enter", "o"),
+    wl_message!("leave", "o"),
+    wl_message!("preedit_string", "?sii"),
+    wl_message!("commit_string", "?s"),
+    wl_message!("delete_surrounding_text", "uu"),
+    wl_message!("done", "u"),
+];
+wl_interface!(zwp_text_input_v3, 1, zwp_text_input_v3_requests, zwp_text_input_v3_events);
+
+// opcodes of the core protocol objects whose interfaces we get from libwayland-client
+
+pub const WL_DISPLAY_SYNC: u32 = 0;
+pub const WL_DISPLAY_GET_REGISTRY: u32 = 1;
+pub const WL_REGISTRY_BIND: u32 = 0;
+pub const WL_REGISTRY_EVENT_GLOBAL: u32 = 0;
+pub const WL_REGISTRY_EVENT_GLOBAL_REMOVE: u32 = 1;
+pub const WL_CALLBACK_EVENT_DONE: u32 = 0;
+pub const WL_COMPOSITOR_CREATE_SURFACE: u32 = 0;
+pub const WL_SURFACE_DESTROY: u32 = 0;
+pub const WL_SURFACE_ATTACH: u32 = 1;
+pub const WL_SURFACE_DAMAGE: u32 = 2;
+pub const WL_SURFACE_FRAME: u32 = 3;
+pub const WL_SURFACE_COMMIT: u32 = 6;
+pub const WL_SURFACE_SET_BUFFER_SCALE: u32 = 8;
+pub const WL_SURFACE_EVENT_ENTER: u32 = 0;
+pub const WL_SURFACE_EVENT_LEAVE: u32 = 1;
+pub const WL_SEAT_GET_POINTER: u32 = 0;
+pub const WL_SEAT_GET_KEYBOARD: u32 = 1;
+pub const WL_SEAT_GET_TOUCH: u32 = 2;
+pub const WL_SEAT_EVENT_CAPABILITIES: u32 = 0;
+pub const WL_POINTER_SET_CURSOR: u32 = 0;
+pub const WL_POINTER_EVENT_ENTER: u32 = 0;
+pub const WL_POINTER_EVENT_LEAVE: u32 = 1;
+pub const WL_POINTER_EVENT_MOTION: u32 = 2;
+pub const WL_POINTER_EVENT_BUTTON: u32 = 3;
+pub const WL_POINTER_EVENT_AXIS: u32 = 4;
+pub const WL_POINTER_EVENT_FRAME: u32 = 5;
+pub const WL_POINTER_EVENT_AXIS_SOURCE: u32 = 6;
+pub const WL_POINTER_EVENT_AXIS_DISCRETE: u32 = 8;
+pub const WL_KEYBOARD_EVENT_KEYMAP: u32 = 0;
+pub const WL_KEYBOARD_EVENT_ENTER: u32 = 1;
+pub const WL_KEYBOARD_EVENT_LEAVE: u32 = 2;
+pub const WL_KEYBOARD_EVENT_KEY: u32 = 3;
+pub const WL_KEYBOARD_EVENT_MODIFIERS: u32 = 4;
+pub const WL_KEYBOARD_EVENT_REPEAT_INFO: u32 = 5;
+pub const WL_TOUCH_EVENT_DOWN: u32 = 0;
+pub const WL_TOUCH_EVENT_UP: u32 = 1;
+pub const WL_TOUCH_EVENT_MOTION: u32 = 2;
+pub const WL_TOUCH_EVENT_FRAME: u32 = 3;
+pub const WL_TOUCH_EVENT_CANCEL: u32 = 4;
+pub const WL_OUTPUT_EVENT_SCALE: u32 = 3;
+pub const WL_DATA_DEVICE_MANAGER_CREATE_DATA_SOURCE: u32 = 0;
+pub const WL_DATA_DEVICE_MANAGER_GET_DATA_DEVICE: u32 = 1;
+pub const WL_DATA_DEVICE_START_DRAG: u32 = 0;
+pub const WL_DATA_DEVICE_SET_SELECTION: u32 = 1;
+pub const WL_DATA_DEVICE_EVENT_DATA_OFFER: u32 = 0;
+pub const WL_DATA_DEVICE_EVENT_ENTER: u32 = 1;
+pub const WL_DATA_DEVICE_EVENT_LEAVE: u32 = 2;
+pub const WL_DATA_DEVICE_EVENT_MOTION: u32 = 3;
+pub const WL_DATA_DEVICE_EVENT_DROP: u32 = 4;
+pub const WL_DATA_DEVICE_EVENT_SELECTION: u32 = 5;
+pub const WL_DATA_OFFER_ACCEPT: u32 = 0;
+pub const WL_DATA_OFFER_RECEIVE: u32 = 1;
+pub const WL_DATA_OFFER_DESTROY: u32 = 2;
+pub const WL_DATA_OFFER_FINISH: u32 = 3;
+pub const WL_DATA_OFFER_SET_ACTIONS: u32 = 4;
+pub const WL_DATA_OFFER_EVENT_OFFER: u32 = 0;
+pub const WL_DATA_SOURCE_OFFER: u32 = 0;
+pub const WL_DATA_SOURCE_DESTROY: u32 = 1;
+pub const WL_DATA_SOURCE_SET_ACTIONS: u32 = 2;
+pub const WL_DATA_SOURCE_EVENT_SEND: u32 = 1;
+pub const WL_DATA_SOURCE_EVENT_CANCELLED: u32 = 2;
+pub const WL_DATA_SOURCE_EVENT_DND_FINISHED: u32 = 4;
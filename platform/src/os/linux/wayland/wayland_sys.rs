@@ -0,0 +1,369 @@
+#![allow(non_camel_case_types, non_snake_case, non_upper_case_globals, dead_code)]
+
+// libwayland-client, libwayland-egl, libwayland-cursor and libxkbcommon are all loaded at runtime
+// so the same binary still starts on an X11-only system.
+
+use {
+    std::{
+        ffi::CString,
+        os::raw::{c_char, c_int, c_void},
+        ptr::NonNull,
+    },
+    self::super::super::libc_sys::{dlclose, dlopen, dlsym, RTLD_LAZY, RTLD_LOCAL},
+};
+
+pub enum wl_proxy {}
+pub enum wl_display {}
+pub enum wl_egl_window {}
+pub enum wl_cursor_theme {}
+
+pub enum xkb_context {}
+pub enum xkb_keymap {}
+pub enum xkb_state {}
+
+pub type wl_fixed_t = i32;
+
+pub fn wl_fixed_to_f64(f: wl_fixed_t) -> f64 {
+    f as f64 / 256.0
+}
+
+pub fn wl_fixed_from_f64(f: f64) -> wl_fixed_t {
+    (f * 256.0).round() as wl_fixed_t
+}
+
+#[repr(C)]
+pub struct wl_message {
+    pub name: *const c_char,
+    pub signature: *const c_char,
+    pub types: *const *const wl_interface,
+}
+
+#[repr(C)]
+pub struct wl_interface {
+    pub name: *const c_char,
+    pub version: c_int,
+    pub method_count: c_int,
+    pub methods: *const wl_message,
+    pub event_count: c_int,
+    pub events: *const wl_message,
+}
+
+// the protocol tables are immutable statics, libwayland only ever reads them
+unsafe impl Sync for wl_message {}
+unsafe impl Sync for wl_interface {}
+
+#[repr(C)]
+pub struct wl_array {
+    pub size: usize,
+    pub alloc: usize,
+    pub data: *mut c_void,
+}
+
+impl wl_array {
+    pub unsafe fn as_u32_slice(&self) -> &[u32] {
+        if self.data.is_null() {
+            return &[]
+        }
+        std::slice::from_raw_parts(self.data as *const u32, self.size / 4)
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub union wl_argument {
+    pub i: i32,
+    pub u: u32,
+    pub f: wl_fixed_t,
+    pub s: *const c_char,
+    pub o: *mut wl_proxy,
+    pub n: u32,
+    pub a: *mut wl_array,
+    pub h: i32,
+}
+
+impl wl_argument {
+    pub fn int(i: i32) -> Self {Self {i}}
+    pub fn uint(u: u32) -> Self {Self {u}}
+    pub fn fixed(f: f64) -> Self {Self {f: wl_fixed_from_f64(f)}}
+    pub fn string(s: *const c_char) -> Self {Self {s}}
+    pub fn object(o: *mut wl_proxy) -> Self {Self {o}}
+    pub fn new_id() -> Self {Self {o: std::ptr::null_mut()}}
+    pub fn fd(h: i32) -> Self {Self {h}}
+
+    pub unsafe fn to_string(&self) -> Option<String> {
+        if self.s.is_null() {
+            return None
+        }
+        Some(std::ffi::CStr::from_ptr(self.s).to_string_lossy().to_string())
+    }
+}
+
+pub type wl_dispatcher_func_t = unsafe extern "C" fn(
+    implementation: *const c_void,
+    target: *mut c_void,
+    opcode: u32,
+    msg: *const wl_message,
+    args: *mut wl_argument,
+) -> c_int;
+
+pub const WL_MARSHAL_FLAG_DESTROY: u32 = 1;
+
+pub const WL_SEAT_CAPABILITY_POINTER: u32 = 1;
+pub const WL_SEAT_CAPABILITY_KEYBOARD: u32 = 2;
+pub const WL_SEAT_CAPABILITY_TOUCH: u32 = 4;
+
+pub const WL_POINTER_BUTTON_STATE_PRESSED: u32 = 1;
+pub const WL_POINTER_AXIS_VERTICAL_SCROLL: u32 = 0;
+pub const WL_POINTER_AXIS_HORIZONTAL_SCROLL: u32 = 1;
+pub const WL_POINTER_AXIS_SOURCE_WHEEL: u32 = 0;
+
+pub const WL_KEYBOARD_KEYMAP_FORMAT_XKB_V1: u32 = 1;
+pub const WL_KEYBOARD_KEY_STATE_PRESSED: u32 = 1;
+
+pub const WL_DATA_DEVICE_MANAGER_DND_ACTION_NONE: u32 = 0;
+pub const WL_DATA_DEVICE_MANAGER_DND_ACTION_COPY: u32 = 1;
+pub const WL_DATA_DEVICE_MANAGER_DND_ACTION_MOVE: u32 = 2;
+
+pub const XDG_TOPLEVEL_STATE_MAXIMIZED: u32 = 1;
+pub const XDG_TOPLEVEL_STATE_FULLSCREEN: u32 = 2;
+pub const XDG_TOPLEVEL_STATE_ACTIVATED: u32 = 4;
+
+pub const XDG_TOPLEVEL_RESIZE_EDGE_TOP: u32 = 1;
+pub const XDG_TOPLEVEL_RESIZE_EDGE_BOTTOM: u32 = 2;
+pub const XDG_TOPLEVEL_RESIZE_EDGE_LEFT: u32 = 4;
+pub const XDG_TOPLEVEL_RESIZE_EDGE_TOP_LEFT: u32 = 5;
+pub const XDG_TOPLEVEL_RESIZE_EDGE_BOTTOM_LEFT: u32 = 6;
+pub const XDG_TOPLEVEL_RESIZE_EDGE_RIGHT: u32 = 8;
+pub const XDG_TOPLEVEL_RESIZE_EDGE_TOP_RIGHT: u32 = 9;
+pub const XDG_TOPLEVEL_RESIZE_EDGE_BOTTOM_RIGHT: u32 = 10;
+
+pub const ZWP_TEXT_INPUT_V3_CONTENT_HINT_NONE: u32 = 0;
+pub const ZWP_TEXT_INPUT_V3_CONTENT_PURPOSE_NORMAL: u32 = 0;
+
+pub const XKB_CONTEXT_NO_FLAGS: c_int = 0;
+pub const XKB_KEYMAP_FORMAT_TEXT_V1: c_int = 1;
+pub const XKB_KEYMAP_COMPILE_NO_FLAGS: c_int = 0;
+pub const XKB_STATE_MODS_EFFECTIVE: c_int = 1 << 3;
+
+#[repr(C)]
+pub struct wl_cursor_image {
+    pub width: u32,
+    pub height: u32,
+    pub hotspot_x: u32,
+    pub hotspot_y: u32,
+    pub delay: u32,
+}
+
+#[repr(C)]
+pub struct wl_cursor {
+    pub image_count: u32,
+    pub images: *mut *mut wl_cursor_image,
+    pub name: *mut c_char,
+}
+
+struct Module(NonNull<c_void>);
+
+impl Module {
+    fn load(paths: &[&str]) -> Option<Self> {
+        for path in paths {
+            let path = CString::new(*path).unwrap();
+            let module = unsafe {dlopen(path.as_ptr(), RTLD_LAZY | RTLD_LOCAL)};
+            if let Some(module) = NonNull::new(module) {
+                return Some(Module(module))
+            }
+        }
+        None
+    }
+
+    fn get_symbol<F: Sized>(&self, name: &str) -> Option<F> {
+        let name = CString::new(name).unwrap();
+        let symbol = unsafe {dlsym(self.0.as_ptr(), name.as_ptr())};
+        if symbol.is_null() {
+            return None
+        }
+        Some(unsafe {std::mem::transmute_copy::<_, F>(&symbol)})
+    }
+}
+
+impl Drop for Module {
+    fn drop(&mut self) {
+        unsafe {dlclose(self.0.as_ptr())};
+    }
+}
+
+pub struct LibWaylandClient {
+    pub wl_display_connect: unsafe extern "C" fn(name: *const c_char) -> *mut wl_display,
+    pub wl_display_disconnect: unsafe extern "C" fn(display: *mut wl_display),
+    pub wl_display_get_fd: unsafe extern "C" fn(display: *mut wl_display) -> c_int,
+    pub wl_display_dispatch_pending: unsafe extern "C" fn(display: *mut wl_display) -> c_int,
+    pub wl_display_flush: unsafe extern "C" fn(display: *mut wl_display) -> c_int,
+    pub wl_display_roundtrip: unsafe extern "C" fn(display: *mut wl_display) -> c_int,
+    pub wl_display_prepare_read: unsafe extern "C" fn(display: *mut wl_display) -> c_int,
+    pub wl_display_read_events: unsafe extern "C" fn(display: *mut wl_display) -> c_int,
+    pub wl_display_cancel_read: unsafe extern "C" fn(display: *mut wl_display),
+
+    pub wl_proxy_marshal_array_flags: unsafe extern "C" fn(
+        proxy: *mut wl_proxy,
+        opcode: u32,
+        interface: *const wl_interface,
+        version: u32,
+        flags: u32,
+        args: *mut wl_argument
+    ) -> *mut wl_proxy,
+    pub wl_proxy_add_dispatcher: unsafe extern "C" fn(
+        proxy: *mut wl_proxy,
+        dispatcher: wl_dispatcher_func_t,
+        implementation: *const c_void,
+        data: *mut c_void
+    ) -> c_int,
+    pub wl_proxy_destroy: unsafe extern "C" fn(proxy: *mut wl_proxy),
+    pub wl_proxy_get_version: unsafe extern "C" fn(proxy: *mut wl_proxy) -> u32,
+    pub wl_proxy_get_user_data: unsafe extern "C" fn(proxy: *mut wl_proxy) -> *mut c_void,
+
+    pub wl_registry_interface: *const wl_interface,
+    pub wl_callback_interface: *const wl_interface,
+    pub wl_compositor_interface: *const wl_interface,
+    pub wl_surface_interface: *const wl_interface,
+    pub wl_region_interface: *const wl_interface,
+    pub wl_shm_interface: *const wl_interface,
+    pub wl_seat_interface: *const wl_interface,
+    pub wl_pointer_interface: *const wl_interface,
+    pub wl_keyboard_interface: *const wl_interface,
+    pub wl_touch_interface: *const wl_interface,
+    pub wl_output_interface: *const wl_interface,
+    pub wl_data_device_manager_interface: *const wl_interface,
+    pub wl_data_device_interface: *const wl_interface,
+    pub wl_data_source_interface: *const wl_interface,
+    pub wl_data_offer_interface: *const wl_interface,
+
+    _keep_module_alive: Module,
+}
+
+impl LibWaylandClient {
+    pub fn try_load() -> Option<Self> {
+        let module = Module::load(&["libwayland-client.so.0", "libwayland-client.so"])?;
+        Some(Self {
+            wl_display_connect: module.get_symbol("wl_display_connect")?,
+            wl_display_disconnect: module.get_symbol("wl_display_disconnect")?,
+            wl_display_get_fd: module.get_symbol("wl_display_get_fd")?,
+            wl_display_dispatch_pending: module.get_symbol("wl_display_dispatch_pending")?,
+            wl_display_flush: module.get_symbol("wl_display_flush")?,
+            wl_display_roundtrip: module.get_symbol("wl_display_roundtrip")?,
+            wl_display_prepare_read: module.get_symbol("wl_display_prepare_read")?,
+            wl_display_read_events: module.get_symbol("wl_display_read_events")?,
+            wl_display_cancel_read: module.get_symbol("wl_display_cancel_read")?,
+            // needs libwayland 1.20, everything older is long out of support
+            wl_proxy_marshal_array_flags: module.get_symbol("wl_proxy_marshal_array_flags")?,
+            wl_proxy_add_dispatcher: module.get_symbol("wl_proxy_add_dispatcher")?,
+            wl_proxy_destroy: module.get_symbol("wl_proxy_destroy")?,
+            wl_proxy_get_version: module.get_symbol("wl_proxy_get_version")?,
+            wl_proxy_get_user_data: module.get_symbol("wl_proxy_get_user_data")?,
+
+            wl_registry_interface: module.get_symbol("wl_registry_interface")?,
+            wl_callback_interface: module.get_symbol("wl_callback_interface")?,
+            wl_compositor_interface: module.get_symbol("wl_compositor_interface")?,
+            wl_surface_interface: module.get_symbol("wl_surface_interface")?,
+            wl_region_interface: module.get_symbol("wl_region_interface")?,
+            wl_shm_interface: module.get_symbol("wl_shm_interface")?,
+            wl_seat_interface: module.get_symbol("wl_seat_interface")?,
+            wl_pointer_interface: module.get_symbol("wl_pointer_interface")?,
+            wl_keyboard_interface: module.get_symbol("wl_keyboard_interface")?,
+            wl_touch_interface: module.get_symbol("wl_touch_interface")?,
+            wl_output_interface: module.get_symbol("wl_output_interface")?,
+            wl_data_device_manager_interface: module.get_symbol("wl_data_device_manager_interface")?,
+            wl_data_device_interface: module.get_symbol("wl_data_device_interface")?,
+            wl_data_source_interface: module.get_symbol("wl_data_source_interface")?,
+            wl_data_offer_interface: module.get_symbol("wl_data_offer_interface")?,
+
+            _keep_module_alive: module,
+        })
+    }
+}
+
+pub struct LibWaylandEgl {
+    pub wl_egl_window_create: unsafe extern "C" fn(surface: *mut wl_proxy, width: c_int, height: c_int) -> *mut wl_egl_window,
+    pub wl_egl_window_destroy: unsafe extern "C" fn(window: *mut wl_egl_window),
+    pub wl_egl_window_resize: unsafe extern "C" fn(window: *mut wl_egl_window, width: c_int, height: c_int, dx: c_int, dy: c_int),
+    _keep_module_alive: Module,
+}
+
+impl LibWaylandEgl {
+    pub fn try_load() -> Option<Self> {
+        let module = Module::load(&["libwayland-egl.so.1", "libwayland-egl.so"])?;
+        Some(Self {
+            wl_egl_window_create: module.get_symbol("wl_egl_window_create")?,
+            wl_egl_window_destroy: module.get_symbol("wl_egl_window_destroy")?,
+            wl_egl_window_resize: module.get_symbol("wl_egl_window_resize")?,
+            _keep_module_alive: module,
+        })
+    }
+}
+
+pub struct LibWaylandCursor {
+    pub wl_cursor_theme_load: unsafe extern "C" fn(name: *const c_char, size: c_int, shm: *mut wl_proxy) -> *mut wl_cursor_theme,
+    pub wl_cursor_theme_destroy: unsafe extern "C" fn(theme: *mut wl_cursor_theme),
+    pub wl_cursor_theme_get_cursor: unsafe extern "C" fn(theme: *mut wl_cursor_theme, name: *const c_char) -> *mut wl_cursor,
+    pub wl_cursor_image_get_buffer: unsafe extern "C" fn(image: *mut wl_cursor_image) -> *mut wl_proxy,
+    _keep_module_alive: Module,
+}
+
+impl LibWaylandCursor {
+    pub fn try_load() -> Option<Self> {
+        let module = Module::load(&["libwayland-cursor.so.0", "libwayland-cursor.so"])?;
+        Some(Self {
+            wl_cursor_theme_load: module.get_symbol("wl_cursor_theme_load")?,
+            wl_cursor_theme_destroy: module.get_symbol("wl_cursor_theme_destroy")?,
+            wl_cursor_theme_get_cursor: module.get_symbol("wl_cursor_theme_get_cursor")?,
+            wl_cursor_image_get_buffer: module.get_symbol("wl_cursor_image_get_buffer")?,
+            _keep_module_alive: module,
+        })
+    }
+}
+
+pub struct LibXkbCommon {
+    pub xkb_context_new: unsafe extern "C" fn(flags: c_int) -> *mut xkb_context,
+    pub xkb_context_unref: unsafe extern "C" fn(context: *mut xkb_context),
+    pub xkb_keymap_new_from_string: unsafe extern "C" fn(
+        context: *mut xkb_context,
+        string: *const c_char,
+        format: c_int,
+        flags: c_int
+    ) -> *mut xkb_keymap,
+    pub xkb_keymap_unref: unsafe extern "C" fn(keymap: *mut xkb_keymap),
+    pub xkb_keymap_key_repeats: unsafe extern "C" fn(keymap: *mut xkb_keymap, key: u32) -> c_int,
+    pub xkb_state_new: unsafe extern "C" fn(keymap: *mut xkb_keymap) -> *mut xkb_state,
+    pub xkb_state_unref: unsafe extern "C" fn(state: *mut xkb_state),
+    pub xkb_state_update_mask: unsafe extern "C" fn(
+        state: *mut xkb_state,
+        depressed_mods: u32,
+        latched_mods: u32,
+        locked_mods: u32,
+        depressed_layout: u32,
+        latched_layout: u32,
+        locked_layout: u32
+    ) -> c_int,
+    pub xkb_state_key_get_one_sym: unsafe extern "C" fn(state: *mut xkb_state, key: u32) -> u32,
+    pub xkb_state_key_get_utf8: unsafe extern "C" fn(state: *mut xkb_state, key: u32, buffer: *mut c_char, size: usize) -> c_int,
+    pub xkb_state_mod_name_is_active: unsafe extern "C" fn(state: *mut xkb_state, name: *const c_char, type_: c_int) -> c_int,
+    _keep_module_alive: Module,
+}
+
+impl LibXkbCommon {
+    pub fn try_load() -> Option<Self> {
+        let module = Module::load(&["libxkbcommon.so.0", "libxkbcommon.so"])?;
+        Some(Self {
+            xkb_context_new: module.get_symbol("xkb_context_new")?,
+            xkb_context_unref: module.get_symbol("xkb_context_unref")?,
+            xkb_keymap_new_from_string: module.get_symbol("xkb_keymap_new_from_string")?,
+            xkb_keymap_unref: module.get_symbol("xkb_keymap_unref")?,
+            xkb_keymap_key_repeats: module.get_symbol("xkb_keymap_key_repeats")?,
+            xkb_state_new: module.get_symbol("xkb_state_new")?,
+            xkb_state_unref: module.get_symbol("xkb_state_unref")?,
+            xkb_state_update_mask: module.get_symbol("xkb_state_update_mask")?,
+            xkb_state_key_get_one_sym: module.get_symbol("xkb_state_key_get_one_sym")?,
+            xkb_state_key_get_utf8: module.get_symbol("xkb_state_key_get_utf8")?,
+            xkb_state_mod_name_is_active: module.get_symbol("xkb_state_mod_name_is_active")?,
+            _keep_module_alive: module,
+        })
+    }
+}
@@ -0,0 +1,522 @@
+use {
+    std::{
+        cell::Cell,
+        rc::Rc,
+        ffi::CString,
+        os::raw::{c_int, c_void},
+        ptr,
+    },
+    self::super::{
+        wayland_sys::*,
+        wayland_protocols::*,
+        wayland_event::WaylandEvent,
+        wayland_app::*,
+    },
+    crate::{
+        area::Area,
+        window::WindowId,
+        makepad_math::{DVec2, dvec2},
+        event::*,
+    },
+};
+
+/// What a left click does when it lands on the client side decorations
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum WaylandNcMode {
+    Move,
+    Resize(u32),
+}
+
+pub struct WaylandWindow {
+    pub window_id: WindowId,
+    pub surface: *mut wl_proxy,
+    pub xdg_surface: *mut wl_proxy,
+    pub xdg_toplevel: *mut wl_proxy,
+    pub egl_window: *mut wl_egl_window,
+    pub fractional_scale: *mut wl_proxy,
+    pub viewport: *mut wl_proxy,
+    pub frame_callback: *mut wl_proxy,
+
+    pub configured: bool,
+    pub pending_size: Option<DVec2>,
+    pub pending_states: Vec<u32>,
+    pub is_maximized: bool,
+    pub is_fullscreen: bool,
+
+    pub inner_size: DVec2,
+    pub buffer_size: (i32, i32),
+    pub outputs: Vec<*mut wl_proxy>,
+    pub preferred_scale: Option<f64>,
+    pub scale: f64,
+    pub last_window_geom: WindowGeom,
+
+    pub last_nc_mode: Option<WaylandNcMode>,
+    pub last_mouse_pos: DVec2,
+    pub ime_spot: DVec2,
+    pub ime_active: bool,
+}
+
+impl WaylandWindow {
+    pub fn new(window_id: WindowId) -> WaylandWindow {
+        WaylandWindow {
+            window_id,
+            surface: ptr::null_mut(),
+            xdg_surface: ptr::null_mut(),
+            xdg_toplevel: ptr::null_mut(),
+            egl_window: ptr::null_mut(),
+            fractional_scale: ptr::null_mut(),
+            viewport: ptr::null_mut(),
+            frame_callback: ptr::null_mut(),
+            configured: false,
+            pending_size: None,
+            pending_states: Vec::new(),
+            is_maximized: false,
+            is_fullscreen: false,
+            inner_size: DVec2::default(),
+            buffer_size: (0, 0),
+            outputs: Vec::new(),
+            preferred_scale: None,
+            scale: 1.0,
+            last_window_geom: WindowGeom::default(),
+            last_nc_mode: None,
+            last_mouse_pos: DVec2::default(),
+            ime_spot: DVec2::default(),
+            ime_active: false,
+        }
+    }
+
+    pub fn init(&mut self, title: &str, size: DVec2) {
+        let app = get_wayland_app_global();
+        unsafe {
+            let data = self as *mut WaylandWindow as *mut c_void;
+
+            self.surface = app.request_new(app.compositor, WL_COMPOSITOR_CREATE_SURFACE, app.wl.wl_surface_interface, &mut [
+                wl_argument::new_id()
+            ]);
+            app.add_dispatcher(self.surface, surface_dispatch, data);
+
+            self.xdg_surface = app.request_new(app.xdg_wm_base, XDG_WM_BASE_GET_XDG_SURFACE, &xdg_surface, &mut [
+                wl_argument::new_id(),
+                wl_argument::object(self.surface)
+            ]);
+            app.add_dispatcher(self.xdg_surface, xdg_surface_dispatch, data);
+
+            self.xdg_toplevel = app.request_new(self.xdg_surface, XDG_SURFACE_GET_TOPLEVEL, &xdg_toplevel, &mut [
+                wl_argument::new_id()
+            ]);
+            app.add_dispatcher(self.xdg_toplevel, xdg_toplevel_dispatch, data);
+
+            let title = CString::new(title).unwrap_or_default();
+            app.request(self.xdg_toplevel, XDG_TOPLEVEL_SET_TITLE, &mut [wl_argument::string(title.as_ptr())]);
+            app.request(self.xdg_toplevel, XDG_TOPLEVEL_SET_APP_ID, &mut [wl_argument::string(app.app_id.as_ptr())]);
+            app.request(self.xdg_toplevel, XDG_TOPLEVEL_SET_MIN_SIZE, &mut [wl_argument::int(100), wl_argument::int(50)]);
+
+            // fractional scaling renders at the exact pixel size and lets the viewport map it back
+            if !app.fractional_scale_manager.is_null() && !app.viewporter.is_null() {
+                self.fractional_scale = app.request_new(
+                    app.fractional_scale_manager,
+                    WP_FRACTIONAL_SCALE_MANAGER_V1_GET_FRACTIONAL_SCALE,
+                    &wp_fractional_scale_v1,
+                    &mut [wl_argument::new_id(), wl_argument::object(self.surface)]
+                );
+                app.add_dispatcher(self.fractional_scale, fractional_scale_dispatch, data);
+                self.viewport = app.request_new(app.viewporter, WP_VIEWPORTER_GET_VIEWPORT, &wp_viewport, &mut [
+                    wl_argument::new_id(),
+                    wl_argument::object(self.surface)
+                ]);
+            }
+
+            self.inner_size = size;
+            self.buffer_size = (size.x as i32, size.y as i32);
+            self.egl_window = (app.egl.wl_egl_window_create)(self.surface, self.buffer_size.0, self.buffer_size.1);
+            assert!(!self.egl_window.is_null(), "wl_egl_window_create failed");
+
+            // the initial commit without a buffer asks the compositor for the first configure
+            app.request(self.surface, WL_SURFACE_COMMIT, &mut []);
+            app.flush();
+
+            app.window_map.insert(self.surface, self);
+            self.last_window_geom = self.get_window_geom();
+        }
+    }
+
+    pub fn close_window(&mut self) {
+        let app = get_wayland_app_global();
+        if self.surface.is_null() {
+            return
+        }
+        app.window_map.remove(&self.surface);
+        app.forget_surface(self.surface);
+        unsafe {
+            if !self.frame_callback.is_null() {
+                (app.wl.wl_proxy_destroy)(self.frame_callback);
+                self.frame_callback = ptr::null_mut();
+            }
+            if !self.viewport.is_null() {
+                app.destroy(self.viewport, WP_VIEWPORT_DESTROY);
+                self.viewport = ptr::null_mut();
+            }
+            if !self.fractional_scale.is_null() {
+                app.destroy(self.fractional_scale, WP_FRACTIONAL_SCALE_V1_DESTROY);
+                self.fractional_scale = ptr::null_mut();
+            }
+            app.destroy(self.xdg_toplevel, XDG_TOPLEVEL_DESTROY);
+            app.destroy(self.xdg_surface, XDG_SURFACE_DESTROY);
+            app.destroy(self.surface, WL_SURFACE_DESTROY);
+            app.flush();
+        }
+        self.xdg_toplevel = ptr::null_mut();
+        self.xdg_surface = ptr::null_mut();
+        self.surface = ptr::null_mut();
+    }
+
+    pub fn minimize(&self) {
+        let app = get_wayland_app_global();
+        unsafe {app.request(self.xdg_toplevel, XDG_TOPLEVEL_SET_MINIMIZED, &mut [])};
+    }
+
+    pub fn maximize(&self) {
+        let app = get_wayland_app_global();
+        unsafe {app.request(self.xdg_toplevel, XDG_TOPLEVEL_SET_MAXIMIZED, &mut [])};
+    }
+
+    pub fn restore(&self) {
+        let app = get_wayland_app_global();
+        unsafe {app.request(self.xdg_toplevel, XDG_TOPLEVEL_UNSET_MAXIMIZED, &mut [])};
+    }
+
+    pub fn fullscreen(&self) {
+        let app = get_wayland_app_global();
+        unsafe {app.request(self.xdg_toplevel, XDG_TOPLEVEL_SET_FULLSCREEN, &mut [wl_argument::object(ptr::null_mut())])};
+    }
+
+    pub fn normalize(&self) {
+        let app = get_wayland_app_global();
+        unsafe {app.request(self.xdg_toplevel, XDG_TOPLEVEL_UNSET_FULLSCREEN, &mut [])};
+    }
+
+    pub fn start_move(&self, serial: u32) {
+        let app = get_wayland_app_global();
+        unsafe {app.request(self.xdg_toplevel, XDG_TOPLEVEL_MOVE, &mut [
+            wl_argument::object(app.seat),
+            wl_argument::uint(serial)
+        ])};
+    }
+
+    pub fn start_resize(&self, serial: u32, edge: u32) {
+        let app = get_wayland_app_global();
+        unsafe {app.request(self.xdg_toplevel, XDG_TOPLEVEL_RESIZE, &mut [
+            wl_argument::object(app.seat),
+            wl_argument::uint(serial),
+            wl_argument::uint(edge)
+        ])};
+    }
+
+    pub fn show_window_menu(&self, serial: u32, pos: DVec2) {
+        let app = get_wayland_app_global();
+        unsafe {app.request(self.xdg_toplevel, XDG_TOPLEVEL_SHOW_WINDOW_MENU, &mut [
+            wl_argument::object(app.seat),
+            wl_argument::uint(serial),
+            wl_argument::int(pos.x as i32),
+            wl_argument::int(pos.y as i32)
+        ])};
+    }
+
+    pub fn get_window_geom(&self) -> WindowGeom {
+        WindowGeom {
+            xr_is_presenting: false,
+            can_fullscreen: true,
+            is_topmost: false,
+            // like on X11 a maximized window reports as fullscreen so the caption buttons toggle correctly
+            is_fullscreen: self.is_maximized || self.is_fullscreen,
+            inner_size: self.inner_size,
+            outer_size: self.inner_size,
+            dpi_factor: self.scale,
+            // wayland clients don't get to know where they are
+            position: DVec2::default(),
+        }
+    }
+
+    /// True while a presented frame hasn't been picked up by the compositor yet, or before the first configure
+    pub fn is_waiting_for_frame(&self) -> bool {
+        !self.configured || !self.frame_callback.is_null()
+    }
+
+    /// Asks for a frame callback, has to happen right before the buffer swap commits the surface
+    pub fn request_frame(&mut self) {
+        let app = get_wayland_app_global();
+        unsafe {
+            self.frame_callback = app.request_new(self.surface, WL_SURFACE_FRAME, app.wl.wl_callback_interface, &mut [
+                wl_argument::new_id()
+            ]);
+            app.add_dispatcher(self.frame_callback, frame_callback_dispatch, self as *mut WaylandWindow as *mut c_void);
+        }
+    }
+
+    /// Resizes the EGL window to the current size and scale, returns true when it changed
+    pub fn resize_buffers(&mut self) -> bool {
+        let buffer_size = (
+            (self.inner_size.x * self.scale).round() as i32,
+            (self.inner_size.y * self.scale).round() as i32
+        );
+        if buffer_size == self.buffer_size {
+            return false
+        }
+        self.buffer_size = buffer_size;
+        let app = get_wayland_app_global();
+        unsafe {
+            (app.egl.wl_egl_window_resize)(self.egl_window, buffer_size.0, buffer_size.1, 0, 0);
+            if !self.viewport.is_null() {
+                app.request(self.viewport, WP_VIEWPORT_SET_DESTINATION, &mut [
+                    wl_argument::int(self.inner_size.x as i32),
+                    wl_argument::int(self.inner_size.y as i32)
+                ]);
+            }
+            else {
+                app.request(self.surface, WL_SURFACE_SET_BUFFER_SCALE, &mut [wl_argument::int(self.scale as c_int)]);
+            }
+        }
+        true
+    }
+
+    /// Recomputes the scale from the fractional scale or the outputs we are on
+    pub fn update_scale(&mut self) {
+        let app = get_wayland_app_global();
+        let scale = if let Some(scale) = self.preferred_scale {
+            scale
+        }
+        else {
+            self.outputs.iter().filter_map( | output | app.output_scale(*output)).max().unwrap_or(1).max(1) as f64
+        };
+        if scale != self.scale {
+            self.scale = scale;
+            if self.configured {
+                self.send_change_event();
+            }
+        }
+    }
+
+    pub fn set_ime_spot(&mut self, spot: DVec2) {
+        if self.ime_active && self.ime_spot == spot {
+            return
+        }
+        let was_active = self.ime_active;
+        self.ime_spot = spot;
+        self.ime_active = true;
+        get_wayland_app_global().update_text_input(self, !was_active);
+    }
+
+    pub fn hide_ime(&mut self) {
+        if self.ime_active {
+            self.ime_active = false;
+            get_wayland_app_global().update_text_input(self, false);
+        }
+    }
+
+    pub fn time_now(&self) -> f64 {
+        get_wayland_app_global().time_now()
+    }
+
+    pub fn do_callback(&mut self, event: WaylandEvent) {
+        get_wayland_app_global().do_callback(event);
+    }
+
+    pub fn send_change_event(&mut self) {
+        let new_geom = self.get_window_geom();
+        let old_geom = self.last_window_geom.clone();
+        self.last_window_geom = new_geom.clone();
+
+        self.do_callback(WaylandEvent::WindowGeomChange(WindowGeomChangeEvent {
+            window_id: self.window_id,
+            old_geom,
+            new_geom
+        }));
+        self.do_callback(WaylandEvent::Paint);
+    }
+
+    pub fn send_focus_event(&mut self) {
+        self.do_callback(WaylandEvent::AppGotFocus);
+    }
+
+    pub fn send_focus_lost_event(&mut self) {
+        self.do_callback(WaylandEvent::AppLostFocus);
+    }
+
+    pub fn send_mouse_down(&mut self, button: usize, modifiers: KeyModifiers) {
+        self.do_callback(WaylandEvent::MouseDown(MouseDownEvent {
+            button,
+            modifiers,
+            window_id: self.window_id,
+            abs: self.last_mouse_pos,
+            time: self.time_now(),
+            handled: Cell::new(Area::Empty),
+        }));
+    }
+
+    pub fn send_mouse_up(&mut self, button: usize, modifiers: KeyModifiers) {
+        self.do_callback(WaylandEvent::MouseUp(MouseUpEvent {
+            button,
+            modifiers,
+            window_id: self.window_id,
+            abs: self.last_mouse_pos,
+            time: self.time_now()
+        }));
+    }
+
+    pub fn send_mouse_move(&mut self, pos: DVec2, modifiers: KeyModifiers) {
+        self.last_mouse_pos = pos;
+        self.do_callback(WaylandEvent::MouseMove(MouseMoveEvent {
+            window_id: self.window_id,
+            abs: pos,
+            modifiers,
+            time: self.time_now(),
+            handled: Cell::new(Area::Empty),
+        }));
+    }
+
+    pub fn send_close_requested_event(&mut self) -> bool {
+        let accept_close = Rc::new(Cell::new(true));
+        self.do_callback(WaylandEvent::WindowCloseRequested(WindowCloseRequestedEvent {
+            window_id: self.window_id,
+            accept_close: accept_close.clone()
+        }));
+        accept_close.get()
+    }
+
+    /// Figures out if the pointer is on a resize border or on the caption, like the X11 backend does
+    pub fn update_nc_mode(&mut self, pos: DVec2) -> Option<WaylandNcMode> {
+        let response = Rc::new(Cell::new(WindowDragQueryResponse::NoAnswer));
+        self.do_callback(WaylandEvent::WindowDragQuery(WindowDragQueryEvent {
+            window_id: self.window_id,
+            abs: pos,
+            response: response.clone()
+        }));
+        let size = self.inner_size;
+        let edge = if self.is_maximized || self.is_fullscreen {
+            0
+        }
+        else {
+            let mut edge = 0;
+            if pos.x < 5.0 {edge |= XDG_TOPLEVEL_RESIZE_EDGE_LEFT}
+            if pos.x >= size.x - 5.0 {edge |= XDG_TOPLEVEL_RESIZE_EDGE_RIGHT}
+            if pos.y < 5.0 {edge |= XDG_TOPLEVEL_RESIZE_EDGE_TOP}
+            if pos.y >= size.y - 5.0 {edge |= XDG_TOPLEVEL_RESIZE_EDGE_BOTTOM}
+            edge
+        };
+        self.last_nc_mode = if edge != 0 {
+            Some(WaylandNcMode::Resize(edge))
+        }
+        else if let WindowDragQueryResponse::Caption = response.get() {
+            Some(WaylandNcMode::Move)
+        }
+        else {
+            None
+        };
+        self.last_nc_mode
+    }
+
+    fn apply_configure(&mut self) {
+        if let Some(size) = self.pending_size.take() {
+            self.inner_size = size;
+        }
+        self.is_maximized = self.pending_states.contains(&XDG_TOPLEVEL_STATE_MAXIMIZED);
+        self.is_fullscreen = self.pending_states.contains(&XDG_TOPLEVEL_STATE_FULLSCREEN);
+        self.configured = true;
+        let new_geom = self.get_window_geom();
+        if new_geom != self.last_window_geom {
+            self.send_change_event();
+        }
+        else {
+            self.do_callback(WaylandEvent::Paint);
+        }
+    }
+}
+
+impl Drop for WaylandWindow {
+    fn drop(&mut self) {
+        self.close_window();
+        if !self.egl_window.is_null() {
+            unsafe {(get_wayland_app_global().egl.wl_egl_window_destroy)(self.egl_window)};
+        }
+    }
+}
+
+unsafe fn window_from_data(target: *mut c_void) -> &'static mut WaylandWindow {
+    let app = get_wayland_app_global();
+    &mut *(( app.wl.wl_proxy_get_user_data)(target as *mut wl_proxy) as *mut WaylandWindow)
+}
+
+unsafe extern "C" fn surface_dispatch(_: *const c_void, target: *mut c_void, opcode: u32, _: *const wl_message, args: *mut wl_argument) -> c_int {
+    let window = window_from_data(target);
+    let output = (*args).o;
+    match opcode {
+        WL_SURFACE_EVENT_ENTER => {
+            if !window.outputs.contains(&output) {
+                window.outputs.push(output);
+            }
+            window.update_scale();
+        }
+        WL_SURFACE_EVENT_LEAVE => {
+            window.outputs.retain( | o | *o != output);
+            window.update_scale();
+        }
+        _ => ()
+    }
+    0
+}
+
+unsafe extern "C" fn xdg_surface_dispatch(_: *const c_void, target: *mut c_void, opcode: u32, _: *const wl_message, args: *mut wl_argument) -> c_int {
+    let window = window_from_data(target);
+    if opcode == XDG_SURFACE_EVENT_CONFIGURE {
+        let app = get_wayland_app_global();
+        app.request(window.xdg_surface, XDG_SURFACE_ACK_CONFIGURE, &mut [*args]);
+        window.apply_configure();
+    }
+    0
+}
+
+unsafe extern "C" fn xdg_toplevel_dispatch(_: *const c_void, target: *mut c_void, opcode: u32, _: *const wl_message, args: *mut wl_argument) -> c_int {
+    let window = window_from_data(target);
+    match opcode {
+        XDG_TOPLEVEL_EVENT_CONFIGURE => {
+            let args = std::slice::from_raw_parts(args, 3);
+            let (width, height) = (args[0].i, args[1].i);
+            // zero means we get to pick, so keep the size we have
+            if width > 0 && height > 0 {
+                window.pending_size = Some(dvec2(width as f64, height as f64));
+            }
+            window.pending_states = (*args[2].a).as_u32_slice().to_vec();
+        }
+        XDG_TOPLEVEL_EVENT_CLOSE => {
+            if window.send_close_requested_event() {
+                let window_id = window.window_id;
+                // Cx drops the window, which has to tear down its EGL surface before the wl_surface goes
+                get_wayland_app_global().do_callback(WaylandEvent::WindowClosed(WindowClosedEvent {
+                    window_id
+                }));
+            }
+        }
+        _ => ()
+    }
+    0
+}
+
+unsafe extern "C" fn fractional_scale_dispatch(_: *const c_void, target: *mut c_void, opcode: u32, _: *const wl_message, args: *mut wl_argument) -> c_int {
+    let window = window_from_data(target);
+    if opcode == WP_FRACTIONAL_SCALE_V1_EVENT_PREFERRED_SCALE {
+        // the scale comes as a multiple of 1/120
+        window.preferred_scale = Some((*args).u as f64 / 120.0);
+        window.update_scale();
+    }
+    0
+}
+
+unsafe extern "C" fn frame_callback_dispatch(_: *const c_void, target: *mut c_void, opcode: u32, _: *const wl_message, _: *mut wl_argument) -> c_int {
+    let window = window_from_data(target);
+    if opcode == WL_CALLBACK_EVENT_DONE {
+        (get_wayland_app_global().wl.wl_proxy_destroy)(window.frame_callback);
+        window.frame_callback = ptr::null_mut();
+        window.do_callback(WaylandEvent::Paint);
+    }
+    0
+}
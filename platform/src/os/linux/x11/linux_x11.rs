@@ -27,6 +27,11 @@ use {
 
 impl Cx {
     pub fn event_loop(cx:Rc<RefCell<Cx>>) {
+        let is_stdin_loop = std::env::args().find(|v| v=="--stdin-loop").is_some();
+        // prefer a native wayland session, xwayland stays the fallback
+        if !is_stdin_loop && Cx::wayland_event_loop(cx.clone()) {
+            return
+        }
         cx.borrow_mut().self_ref = Some(cx.clone());
         cx.borrow_mut().os_type = OsType::LinuxWindow(LinuxWindowParams{
             custom_window_chrome: false
@@ -34,7 +39,6 @@ impl Cx {
         cx.borrow_mut().gpu_info.performance = GpuPerformance::Tier1;
 
         let opengl_windows = Rc::new(RefCell::new(Vec::new()));
-        let is_stdin_loop = std::env::args().find(|v| v=="--stdin-loop").is_some();
         init_xlib_app_global(Box::new({
             let cx = cx.clone();
             move | xlib_app,
@@ -48,6 +52,7 @@ impl Cx {
             }
         }));
         
+        cx.borrow_mut().monitors = get_xlib_app_global().monitors.clone();
         cx.borrow_mut().os.opengl_cx = Some(unsafe {
             OpenglCx::from_egl_platform_display(
                 egl_sys::EGL_PLATFORM_X11_EXT,
@@ -101,7 +106,7 @@ impl Cx {
                     window.window_geom = re.new_geom.clone();
                     self.windows[re.window_id].window_geom = re.new_geom.clone();
                     // redraw just this windows root draw list
-                    if re.old_geom.inner_size != re.new_geom.inner_size {
+                    if re.old_geom.inner_size != re.new_geom.inner_size || re.old_geom.dpi_factor != re.new_geom.dpi_factor {
                         if let Some(main_pass_id) = self.windows[re.window_id].main_pass_id {
                             self.redraw_pass_and_child_passes(main_pass_id);
                         }
@@ -168,12 +173,18 @@ impl Cx {
             XlibEvent::TextInput(e) => {
                 self.call_event_handler(&Event::TextInput(e))
             }
+            XlibEvent::TextPreedit(e) => {
+                self.call_event_handler(&Event::TextPreedit(e))
+            }
             XlibEvent::Drag(e) => {
                 self.call_event_handler(&Event::Drag(e))
             }
             XlibEvent::Drop(e) => {
                 self.call_event_handler(&Event::Drop(e))
             }
+            XlibEvent::DropOutside(e) => {
+                self.call_event_handler(&Event::DropOutside(e))
+            }
             XlibEvent::DragEnd => {
                 self.call_event_handler(&Event::DragEnd)
             }
@@ -191,6 +202,9 @@ impl Cx {
             XlibEvent::TextCut(e) => {
                 self.call_event_handler(&Event::TextCut(e))
             }
+            XlibEvent::ClipboardRead(e) => {
+                self.call_event_handler(&Event::ClipboardRead(e))
+            }
             XlibEvent::Timer(e) => {
                 //println!("TIMER! {:?}", std::time::Instant::now());
                 if e.timer_id == 0{
@@ -198,11 +212,18 @@ impl Cx {
                         self.handle_media_signals();
                         self.call_event_handler(&Event::Signal);
                     }
+                    if self.handle_live_edit() {
+                        self.call_event_handler(&Event::LiveEdit);
+                        self.redraw_all();
+                    }
                 }
                 else{
                     self.call_event_handler(&Event::Timer(e))
                 }
             }
+            XlibEvent::MonitorsChanged(monitors) => {
+                self.monitors = monitors;
+            }
         }
         
         if self.any_passes_dirty() || self.need_redrawing() || self.new_next_frames.len() != 0 || paint_dirty {
@@ -289,14 +310,26 @@ impl Cx {
                 },
                 CxOsOp::ShowClipboardActions(_) =>{
                 }
-                CxOsOp::FullscreenWindow(_window_id) => {
-                    todo!()
+                CxOsOp::CopyToClipboard(selection, items) => {
+                    xlib_app.copy_to_clipboard(selection, items);
+                }
+                CxOsOp::ReadClipboard {request_id, selection, mime_types} => {
+                    xlib_app.read_clipboard(request_id, selection, mime_types);
+                }
+                CxOsOp::FullscreenWindow(window_id) => {
+                    if let Some(window) = opengl_windows.iter_mut().find( | w | w.window_id == window_id) {
+                        window.xlib_window.fullscreen();
+                    }
                 },
-                CxOsOp::NormalizeWindow(_window_id) => {
-                    todo!()
+                CxOsOp::NormalizeWindow(window_id) => {
+                    if let Some(window) = opengl_windows.iter_mut().find( | w | w.window_id == window_id) {
+                        window.xlib_window.normalize();
+                    }
                 }
-                CxOsOp::SetTopmost(_window_id, _is_topmost) => {
-                    todo!()
+                CxOsOp::SetTopmost(window_id, is_topmost) => {
+                    if let Some(window) = opengl_windows.iter_mut().find( | w | w.window_id == window_id) {
+                        window.xlib_window.set_topmost(is_topmost);
+                    }
                 }
                 CxOsOp::XrStartPresenting => {
                     //todo!()
@@ -304,11 +337,16 @@ impl Cx {
                 CxOsOp::XrStopPresenting => {
                     //todo!()
                 },
-                CxOsOp::ShowTextIME(_area, _pos) => {
-                    //todo!()
+                CxOsOp::ShowTextIME(area, pos) => {
+                    let pos = area.clipped_rect(self).pos + pos;
+                    opengl_windows.iter_mut().for_each( | w | {
+                        w.xlib_window.set_ime_spot(pos);
+                    });
                 }
                 CxOsOp::HideTextIME => {
-                    //todo!()
+                    opengl_windows.iter_mut().for_each( | w | {
+                        w.xlib_window.hide_ime();
+                    });
                 },
                 CxOsOp::SetCursor(cursor) => {
                     xlib_app.set_mouse_cursor(cursor);
@@ -319,7 +357,8 @@ impl Cx {
                 CxOsOp::StopTimer(timer_id) => {
                     xlib_app.stop_timer(timer_id);
                 },
-                CxOsOp::StartDragging(_dragged_item) => {
+                CxOsOp::StartDragging(items) => {
+                    xlib_app.start_dragging(items);
                 },
                 CxOsOp::UpdateMacosMenu(_menu) => {
                 },
@@ -347,6 +386,7 @@ impl Cx {
 
 impl CxOsApi for Cx {
     fn init_cx_os(&mut self) {
+        self.native_set_package_root();
         self.live_expand();
         self.live_scan_dependencies();
         self.native_load_dependencies();
@@ -363,6 +403,6 @@ pub struct CxOs {
     pub (crate) stdin_timers: PollTimers,
 
     // HACK(eddyb) generalize this to EGL, properly.
-    pub(super) opengl_cx: Option<OpenglCx>,
+    pub(crate) opengl_cx: Option<OpenglCx>,
 }
 
@@ -30,21 +30,29 @@ impl Cx {
         &mut self,
         pass_id: PassId,
         opengl_window: &mut OpenglWindow,
+    ) {
+        let pix_size = opengl_window.window_geom.inner_size * opengl_window.window_geom.dpi_factor;
+        self.draw_pass_to_egl_surface(pass_id, opengl_window.egl_surface, pix_size, None);
+    }
+    
+    /// Draws a window pass into any EGL window surface, `before_swap` runs right before the buffers are swapped
+    pub(crate) fn draw_pass_to_egl_surface(
+        &mut self,
+        pass_id: PassId,
+        egl_surface: egl_sys::EGLSurface,
+        pix_size: DVec2,
+        before_swap: Option<&mut dyn FnMut()>,
     ) {
         let draw_list_id = self.passes[pass_id].main_draw_list_id.unwrap();
         
         self.setup_render_pass(pass_id);
         
-        let egl_surface = opengl_window.egl_surface;
-        
         self.passes[pass_id].paint_dirty = false;
 
-        let pix_width = opengl_window.window_geom.inner_size.x * opengl_window.window_geom.dpi_factor;
-        let pix_height = opengl_window.window_geom.inner_size.y * opengl_window.window_geom.dpi_factor;
         unsafe {
             let opengl_cx = self.os.opengl_cx.as_ref().unwrap();
             (opengl_cx.libegl.eglMakeCurrent.unwrap())(opengl_cx.egl_display, egl_surface, egl_surface, opengl_cx.egl_context);
-            gl_sys::Viewport(0, 0, pix_width.floor() as i32, pix_height.floor() as i32);
+            gl_sys::Viewport(0, 0, pix_size.x.floor() as i32, pix_size.y.floor() as i32);
         }
         
         let clear_color = if self.passes[pass_id].color_textures.len() == 0 {
@@ -81,6 +89,9 @@ impl Cx {
             zbias_step,
         );
 
+        if let Some(before_swap) = before_swap {
+            before_swap();
+        }
         unsafe {
             let opengl_cx = self.os.opengl_cx.as_ref().unwrap();
             (opengl_cx.libegl.eglSwapBuffers.unwrap())(opengl_cx.egl_display, egl_surface);
@@ -278,13 +289,13 @@ impl CxTexture {
 
 // FIXME(eddyb) move this out of `linux::x11`, since it's mostly generic EGL.
 pub struct OpenglCx {
-    libegl: LibEgl,
-    egl_display: egl_sys::EGLDisplay,
-    egl_config: egl_sys::EGLConfig,
-    egl_context: egl_sys::EGLContext,
+    pub(crate) libegl: LibEgl,
+    pub(crate) egl_display: egl_sys::EGLDisplay,
+    pub(crate) egl_config: egl_sys::EGLConfig,
+    pub(crate) egl_context: egl_sys::EGLContext,
 
-    egl_platform: egl_sys::EGLenum,
-    egl_platform_display: *mut c_void,
+    pub(crate) egl_platform: egl_sys::EGLenum,
+    pub(crate) egl_platform_display: *mut c_void,
 }
 
 impl OpenglCx {
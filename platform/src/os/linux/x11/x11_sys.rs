@@ -1,9 +1,11 @@
 #![allow(non_upper_case_globals)]
+#![allow(non_snake_case)]
 
 use std::os::raw::{
     c_int,
     c_uint,
     c_short,
+    c_ushort,
     c_long,
     c_ulong,
     c_void,
@@ -34,6 +36,13 @@ pub type XrmDatabase = *mut _XrmHashBucketRec;
 pub type XKeyPressedEvent = XKeyEvent;
 pub type XComposeStatus = _XComposeStatus;
 pub type GC = *mut _XGC;
+pub type RROutput = XID;
+pub type RRCrtc = XID;
+pub type RRMode = XID;
+pub type Rotation = c_ushort;
+pub type Connection = c_ushort;
+pub type SubpixelOrder = c_ushort;
+pub type XRRModeFlags = c_ulong;
 
 pub const None: u32 = 0;
 pub const True: u32 = 1;
@@ -45,9 +54,16 @@ pub const CurrentTime: u32 = 0;
 pub const SelectionNotify: u32 = 31;
 pub const AnyPropertyType: u32 = 0;
 pub const SelectionRequest: u32 = 30;
+pub const SelectionClear: u32 = 29;
+pub const PropertyNewValue: u32 = 0;
+pub const PropertyDelete: u32 = 1;
+pub const XA_PRIMARY: u32 = 1;
+pub const XA_ATOM: u32 = 4;
+pub const XA_STRING: u32 = 31;
 pub const PropModeReplace: u32 = 0;
 pub const DestroyNotify: u32 = 17;
 pub const ConfigureNotify: u32 = 22;
+pub const PropertyNotify: u32 = 28;
 pub const EnterNotify: u32 = 7;
 pub const LeaveNotify: u32 = 8;
 pub const MotionNotify: u32 = 6;
@@ -59,6 +75,8 @@ pub const KeyRelease: u32 = 3;
 pub const ButtonPress: u32 = 4;
 pub const ButtonRelease: u32 = 5;
 pub const Expose: u32 = 12;
+pub const FocusIn: u32 = 9;
+pub const FocusOut: u32 = 10;
 
 pub const CWBorderPixel: u32 = 8;
 pub const CWColormap: u32 = 8192;
@@ -80,14 +98,43 @@ pub const VisibilityChangeMask: u32 = 65536;
 pub const FocusChangeMask: u32 = 2097152;
 pub const EnterWindowMask: u32 = 16;
 pub const LeaveWindowMask: u32 = 32;
+pub const PropertyChangeMask: u32 = 4194304;
+pub const GrabModeAsync: u32 = 1;
+pub const GrabSuccess: u32 = 0;
+pub const RRScreenChangeNotify: u32 = 0;
+pub const RRScreenChangeNotifyMask: u32 = 1;
+pub const RR_Connected: u32 = 0;
+pub const RR_Interlace: u32 = 16;
+pub const RR_DoubleScan: u32 = 32;
 pub const XBufferOverflow: i32 = -1;
+pub const XLookupNone: i32 = 1;
+pub const XLookupChars: i32 = 2;
+pub const XLookupKeySym: i32 = 3;
+pub const XLookupBoth: i32 = 4;
 
+pub const XIMPreeditCallbacks: u32 = 2;
+pub const XIMPreeditPosition: u32 = 4;
 pub const XIMPreeditNothing: u32 = 8;
 pub const XIMStatusNothing: u32 = 1024;
 
+pub const XIMForwardChar: c_int = 0;
+pub const XIMBackwardChar: c_int = 1;
+pub const XIMLineStart: c_int = 8;
+pub const XIMLineEnd: c_int = 9;
+pub const XIMAbsolutePosition: c_int = 10;
+
 pub const XNInputStyle: &'static [u8; 11usize] = b"inputStyle\0";
 pub const XNClientWindow: &'static [u8; 13usize] = b"clientWindow\0";
 pub const XNFocusWindow: &'static [u8; 12usize] = b"focusWindow\0";
+pub const XNQueryInputStyle: &'static [u8; 16usize] = b"queryInputStyle\0";
+pub const XNPreeditAttributes: &'static [u8; 18usize] = b"preeditAttributes\0";
+pub const XNSpotLocation: &'static [u8; 13usize] = b"spotLocation\0";
+pub const XNPreeditStartCallback: &'static [u8; 21usize] = b"preeditStartCallback\0";
+pub const XNPreeditDoneCallback: &'static [u8; 20usize] = b"preeditDoneCallback\0";
+pub const XNPreeditDrawCallback: &'static [u8; 20usize] = b"preeditDrawCallback\0";
+pub const XNPreeditCaretCallback: &'static [u8; 21usize] = b"preeditCaretCallback\0";
+
+pub const LC_CTYPE: c_int = 0;
 
 pub const Mod1Mask: u32 = 8;
 pub const ShiftMask: u32 = 1;
@@ -228,6 +275,10 @@ pub const XK_Down: u32 = 65364;
 pub const XK_Up: u32 = 65362;
     
 
+extern "C" {
+    pub fn setlocale(category: c_int, locale: *const c_char) -> *mut c_char;
+}
+
 #[link(name = "Xcursor")]
 extern "C" {
     pub fn XcursorLibraryLoadCursor(
@@ -236,6 +287,41 @@ extern "C" {
     ) -> Cursor;
 }
 
+#[link(name = "Xrandr")]
+extern "C" {
+    pub fn XRRQueryExtension(
+        dpy: *mut Display,
+        event_base_return: *mut c_int,
+        error_base_return: *mut c_int,
+    ) -> c_int;
+    
+    pub fn XRRSelectInput(dpy: *mut Display, window: Window, mask: c_int);
+    
+    pub fn XRRUpdateConfiguration(event: *mut XEvent) -> c_int;
+    
+    pub fn XRRGetScreenResourcesCurrent(dpy: *mut Display, window: Window) -> *mut XRRScreenResources;
+    
+    pub fn XRRFreeScreenResources(resources: *mut XRRScreenResources);
+    
+    pub fn XRRGetOutputInfo(
+        dpy: *mut Display,
+        resources: *mut XRRScreenResources,
+        output: RROutput,
+    ) -> *mut XRROutputInfo;
+    
+    pub fn XRRFreeOutputInfo(output_info: *mut XRROutputInfo);
+    
+    pub fn XRRGetCrtcInfo(
+        dpy: *mut Display,
+        resources: *mut XRRScreenResources,
+        crtc: RRCrtc,
+    ) -> *mut XRRCrtcInfo;
+    
+    pub fn XRRFreeCrtcInfo(crtc_info: *mut XRRCrtcInfo);
+    
+    pub fn XRRGetOutputPrimary(dpy: *mut Display, window: Window) -> RROutput;
+}
+
 #[link(name = "X11")]
 extern "C" {
     pub fn XOpenDisplay(arg1: *const c_char) -> *mut Display;
@@ -303,6 +389,14 @@ extern "C" {
     
     pub fn XDefaultScreen(arg1: *mut Display) -> c_int;
     
+    pub fn XDisplayWidth(arg1: *mut Display, arg2: c_int) -> c_int;
+    
+    pub fn XDisplayHeight(arg1: *mut Display, arg2: c_int) -> c_int;
+    
+    pub fn XDisplayWidthMM(arg1: *mut Display, arg2: c_int) -> c_int;
+    
+    pub fn XDisplayHeightMM(arg1: *mut Display, arg2: c_int) -> c_int;
+    
     pub fn XRootWindow(arg1: *mut Display, arg2: c_int) -> Window;
 
     pub fn XGetVisualInfo(
@@ -353,6 +447,24 @@ extern "C" {
     
     pub fn XCreateIC(arg1: XIM, ...) -> XIC;
     
+    pub fn XDestroyIC(arg1: XIC);
+    
+    pub fn XSetICValues(arg1: XIC, ...) -> *mut c_char;
+    
+    pub fn XGetIMValues(arg1: XIM, ...) -> *mut c_char;
+    
+    pub fn XVaCreateNestedList(arg1: c_int, ...) -> *mut c_void;
+    
+    pub fn XSetICFocus(arg1: XIC);
+    
+    pub fn XUnsetICFocus(arg1: XIC);
+    
+    pub fn XFilterEvent(arg1: *mut XEvent, arg2: Window) -> c_int;
+    
+    pub fn XSetLocaleModifiers(arg1: *const c_char) -> *mut c_char;
+    
+    pub fn XSupportsLocale() -> c_int;
+    
     pub fn XDestroyWindow(arg1: *mut Display, arg2: Window) -> c_int;
     
     pub fn XIconifyWindow(
@@ -361,6 +473,26 @@ extern "C" {
         arg3: c_int,
     ) -> c_int;
     
+    pub fn XMoveResizeWindow(
+        arg1: *mut Display,
+        arg2: Window,
+        arg3: c_int,
+        arg4: c_int,
+        arg5: c_uint,
+        arg6: c_uint,
+    ) -> c_int;
+    
+    pub fn XTranslateCoordinates(
+        arg1: *mut Display,
+        arg2: Window,
+        arg3: Window,
+        arg4: c_int,
+        arg5: c_int,
+        arg6: *mut c_int,
+        arg7: *mut c_int,
+        arg8: *mut Window,
+    ) -> c_int;
+    
     pub fn XGetWindowAttributes(
         arg1: *mut Display,
         arg2: Window,
@@ -395,6 +527,18 @@ extern "C" {
         arg4: Time,
     ) -> c_int;
     
+    pub fn XGrabPointer(
+        arg1: *mut Display,
+        arg2: Window,
+        arg3: c_int,
+        arg4: c_uint,
+        arg5: c_int,
+        arg6: c_int,
+        arg7: Window,
+        arg8: Cursor,
+        arg9: Time,
+    ) -> c_int;
+    
     pub fn XUngrabPointer(arg1: *mut Display, arg2: Time) -> c_int;
     
     pub fn XSetSelectionOwner(
@@ -404,6 +548,16 @@ extern "C" {
         arg4: Time,
     ) -> c_int;
     
+    pub fn XGetSelectionOwner(arg1: *mut Display, arg2: Atom) -> Window;
+    
+    pub fn XDeleteProperty(arg1: *mut Display, arg2: Window, arg3: Atom) -> c_int;
+    
+    pub fn XGetAtomName(arg1: *mut Display, arg2: Atom) -> *mut c_char;
+    
+    pub fn XSelectInput(arg1: *mut Display, arg2: Window, arg3: c_long) -> c_int;
+    
+    pub fn XMaxRequestSize(arg1: *mut Display) -> c_long;
+    
     pub fn Xutf8LookupString(
         arg1: XIC,
         arg2: *mut XKeyPressedEvent,
@@ -448,6 +602,70 @@ pub struct _XIC {
     _unused: [u8; 0],
 }
 
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct XPoint {
+    pub x: c_short,
+    pub y: c_short,
+}
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct XIMStyles {
+    pub count_styles: c_ushort,
+    pub supported_styles: *mut c_ulong,
+}
+
+pub type XIMProc = Option<unsafe extern "C" fn(ic: XIC, client_data: XPointer, call_data: XPointer)>;
+pub type XICProc = Option<unsafe extern "C" fn(ic: XIC, client_data: XPointer, call_data: XPointer) -> c_int>;
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct XIMCallback {
+    pub client_data: XPointer,
+    pub callback: XIMProc,
+}
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct XICCallback {
+    pub client_data: XPointer,
+    pub callback: XICProc,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub union XIMTextString {
+    pub multi_byte: *mut c_char,
+    pub wide_char: *mut i32,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct XIMText {
+    pub length: c_ushort,
+    pub feedback: *mut c_ulong,
+    pub encoding_is_wchar: c_int,
+    pub string: XIMTextString,
+}
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct XIMPreeditDrawCallbackStruct {
+    pub caret: c_int,
+    pub chg_first: c_int,
+    pub chg_length: c_int,
+    pub text: *mut XIMText,
+}
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct XIMPreeditCaretCallbackStruct {
+    pub position: c_int,
+    pub direction: c_int,
+    pub style: c_int,
+}
+
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
 pub struct _XComposeStatus {
@@ -1101,3 +1319,72 @@ pub union _XEvent {
     _bindgen_union_align: [u64; 24usize],
 }
 
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct XRRModeInfo {
+    pub id: RRMode,
+    pub width: c_uint,
+    pub height: c_uint,
+    pub dotClock: c_ulong,
+    pub hSyncStart: c_uint,
+    pub hSyncEnd: c_uint,
+    pub hTotal: c_uint,
+    pub hSkew: c_uint,
+    pub vSyncStart: c_uint,
+    pub vSyncEnd: c_uint,
+    pub vTotal: c_uint,
+    pub name: *mut c_char,
+    pub nameLength: c_uint,
+    pub modeFlags: XRRModeFlags,
+}
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct XRRScreenResources {
+    pub timestamp: Time,
+    pub configTimestamp: Time,
+    pub ncrtc: c_int,
+    pub crtcs: *mut RRCrtc,
+    pub noutput: c_int,
+    pub outputs: *mut RROutput,
+    pub nmode: c_int,
+    pub modes: *mut XRRModeInfo,
+}
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct XRROutputInfo {
+    pub timestamp: Time,
+    pub crtc: RRCrtc,
+    pub name: *mut c_char,
+    pub nameLen: c_int,
+    pub mm_width: c_ulong,
+    pub mm_height: c_ulong,
+    pub connection: Connection,
+    pub subpixel_order: SubpixelOrder,
+    pub ncrtc: c_int,
+    pub crtcs: *mut RRCrtc,
+    pub nclone: c_int,
+    pub clones: *mut RROutput,
+    pub nmode: c_int,
+    pub npreferred: c_int,
+    pub modes: *mut RRMode,
+}
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct XRRCrtcInfo {
+    pub timestamp: Time,
+    pub x: c_int,
+    pub y: c_int,
+    pub width: c_uint,
+    pub height: c_uint,
+    pub mode: RRMode,
+    pub rotation: Rotation,
+    pub noutput: c_int,
+    pub outputs: *mut RROutput,
+    pub rotations: Rotation,
+    pub npossible: c_int,
+    pub possible: *mut RROutput,
+}
@@ -4,24 +4,55 @@ use {
         mem,
         rc::Rc,
         cell::{Cell, RefCell},
-        os::raw::{c_char, c_int, c_uint, c_ulong, c_void, c_uchar, c_long},
+        os::raw::{c_char, c_int, c_uint, c_ulong, c_long},
         ptr,
     },
     self::super::{
         x11_sys,
         xlib_event::XlibEvent,
         xlib_window::*,
+        xlib_clipboard::*,
         super::select_timer::SelectTimers,
     },
     crate::{
         makepad_math::DVec2,
+        makepad_live_id::LiveId,
         event::*,
         cursor::MouseCursor,
         os::cx_native::EventFlow,
-        x11::x11_sys::XEvent
+        monitor::MonitorInfo,
     },
 };
 
+/// Picks a scale from the pixel density, snapped to quarter steps. Projectors and some virtual outputs
+/// report a nonsense physical size, those stay at 1.0
+fn dpi_factor_from_physical_size(size: DVec2, physical_size_mm: DVec2) -> f64 {
+    if physical_size_mm.x < 10.0 || physical_size_mm.y < 10.0 {
+        return 1.0
+    }
+    let dpi = size.x / (physical_size_mm.x / 25.4);
+    let dpi_y = size.y / (physical_size_mm.y / 25.4);
+    // tv's report their aspect ratio in centimeters and such, a big mismatch means the numbers are made up
+    if (dpi / dpi_y - 1.0).abs() > 0.2 || dpi > 600.0 {
+        return 1.0
+    }
+    ((dpi / 96.0) * 4.0).round().max(4.0) / 4.0
+}
+
+fn mode_refresh_rate(mode: &x11_sys::XRRModeInfo) -> f64 {
+    let mut v_total = mode.vTotal as f64;
+    if mode.modeFlags & x11_sys::RR_DoubleScan as c_ulong != 0 {
+        v_total *= 2.0;
+    }
+    if mode.modeFlags & x11_sys::RR_Interlace as c_ulong != 0 {
+        v_total /= 2.0;
+    }
+    if mode.hTotal == 0 || v_total == 0.0 {
+        return 0.0
+    }
+    mode.dotClock as f64 / (mode.hTotal as f64 * v_total)
+}
+
 static mut XLIB_APP: *mut XlibApp = 0 as *mut _;
 
 pub fn get_xlib_app_global() -> &'static mut XlibApp {
@@ -40,7 +71,7 @@ pub struct XlibApp {
     pub display: *mut x11_sys::Display,
     event_loop_running: bool,
     pub xim: x11_sys::XIM,
-    pub clipboard: String,
+    pub clipboard: XlibClipboard,
     pub display_fd: c_int,
     //pub signal_fds: [c_int; 2],
     pub window_map: HashMap<c_ulong, *mut XlibWindow>,
@@ -56,6 +87,8 @@ pub struct XlibApp {
     pub current_cursor: MouseCursor,
     pub atoms: XlibAtoms,
     pub dnd: Dnd,
+    pub randr_event_base: Option<c_int>,
+    pub monitors: Vec<MonitorInfo>,
 }
 
 impl XlibApp {
@@ -63,11 +96,18 @@ impl XlibApp {
         unsafe {
             let display = x11_sys::XOpenDisplay(ptr::null());
             let display_fd = x11_sys::XConnectionNumber(display);
+            // XIM servers (ibus, fcitx) only talk to us in a proper locale, XMODIFIERS picks the server
+            x11_sys::setlocale(x11_sys::LC_CTYPE, "\0".as_ptr() as *const _);
+            if x11_sys::XSupportsLocale() == 0 {
+                x11_sys::setlocale(x11_sys::LC_CTYPE, "C.UTF-8\0".as_ptr() as *const _);
+            }
+            x11_sys::XSetLocaleModifiers("\0".as_ptr() as *const _);
             let xim = x11_sys::XOpenIM(display, ptr::null_mut(), ptr::null_mut(), ptr::null_mut());
             //let mut signal_fds = [0, 0];
             //libc_sys::pipe(signal_fds.as_mut_ptr());
             x11_sys::XrmInitialize();
-            XlibApp {
+            let randr_event_base = Self::init_randr(display);
+            let mut xlib_app = XlibApp {
                 event_loop_running: true,
                 event_callback: Some(event_callback),
                 atoms: XlibAtoms::new(display),
@@ -75,7 +115,7 @@ impl XlibApp {
                 display,
                 display_fd,
                 //signal_fds,
-                clipboard: String::new(),
+                clipboard: XlibClipboard::new(display),
                 last_scroll_time: 0.0,
                 last_click_time: 0.0,
                 last_click_pos: (0, 0),
@@ -85,7 +125,146 @@ impl XlibApp {
                 //free_timers: Vec::new(),
                 current_cursor: MouseCursor::Default,
                 dnd: Dnd::new(display),
+                randr_event_base,
+                monitors: Vec::new(),
+            };
+            xlib_app.monitors = xlib_app.enumerate_monitors();
+            xlib_app
+        }
+    }
+    
+    /// Subscribes to monitor changes, returns the event base or None without the RandR extension
+    unsafe fn init_randr(display: *mut x11_sys::Display) -> Option<c_int> {
+        let mut event_base = 0;
+        let mut error_base = 0;
+        if x11_sys::XRRQueryExtension(display, &mut event_base, &mut error_base) == 0 {
+            return None
+        }
+        let root_window = x11_sys::XRootWindow(display, x11_sys::XDefaultScreen(display));
+        x11_sys::XRRSelectInput(display, root_window, x11_sys::RRScreenChangeNotifyMask as c_int);
+        Some(event_base)
+    }
+    
+    /// Lists the active outputs through XRandR, or the whole screen as one monitor without it
+    pub fn enumerate_monitors(&self) -> Vec<MonitorInfo> {
+        let xft_dpi_factor = self.get_xft_dpi_factor();
+        let mut monitors = Vec::new();
+        unsafe {
+            let root_window = x11_sys::XRootWindow(self.display, x11_sys::XDefaultScreen(self.display));
+            if self.randr_event_base.is_some() {
+                let resources = x11_sys::XRRGetScreenResourcesCurrent(self.display, root_window);
+                if !resources.is_null() {
+                    let primary = x11_sys::XRRGetOutputPrimary(self.display, root_window);
+                    let outputs = std::slice::from_raw_parts((*resources).outputs, (*resources).noutput.max(0) as usize);
+                    let modes = std::slice::from_raw_parts((*resources).modes, (*resources).nmode.max(0) as usize);
+                    for output in outputs {
+                        let output_info = x11_sys::XRRGetOutputInfo(self.display, resources, *output);
+                        if output_info.is_null() {
+                            continue;
+                        }
+                        // disconnected or switched off outputs have no crtc
+                        if (*output_info).connection as u32 == x11_sys::RR_Connected && (*output_info).crtc != 0 {
+                            let crtc_info = x11_sys::XRRGetCrtcInfo(self.display, resources, (*output_info).crtc);
+                            if !crtc_info.is_null() {
+                                let name = std::slice::from_raw_parts((*output_info).name as *const u8, (*output_info).nameLen.max(0) as usize);
+                                let size = DVec2 {x: (*crtc_info).width as f64, y: (*crtc_info).height as f64};
+                                let physical_size_mm = DVec2 {x: (*output_info).mm_width as f64, y: (*output_info).mm_height as f64};
+                                monitors.push(MonitorInfo {
+                                    name: String::from_utf8_lossy(name).to_string(),
+                                    is_primary: *output == primary,
+                                    position: DVec2 {x: (*crtc_info).x as f64, y: (*crtc_info).y as f64},
+                                    size,
+                                    physical_size_mm,
+                                    dpi_factor: xft_dpi_factor.unwrap_or_else( || dpi_factor_from_physical_size(size, physical_size_mm)),
+                                    refresh_rate: modes.iter().find( | mode | mode.id == (*crtc_info).mode).map_or(0.0, mode_refresh_rate),
+                                });
+                                x11_sys::XRRFreeCrtcInfo(crtc_info);
+                            }
+                        }
+                        x11_sys::XRRFreeOutputInfo(output_info);
+                    }
+                    x11_sys::XRRFreeScreenResources(resources);
+                }
+            }
+            if monitors.is_empty() {
+                let screen = x11_sys::XDefaultScreen(self.display);
+                let size = DVec2 {
+                    x: x11_sys::XDisplayWidth(self.display, screen) as f64,
+                    y: x11_sys::XDisplayHeight(self.display, screen) as f64
+                };
+                let physical_size_mm = DVec2 {
+                    x: x11_sys::XDisplayWidthMM(self.display, screen) as f64,
+                    y: x11_sys::XDisplayHeightMM(self.display, screen) as f64
+                };
+                monitors.push(MonitorInfo {
+                    name: "default".to_string(),
+                    is_primary: true,
+                    position: DVec2::default(),
+                    size,
+                    physical_size_mm,
+                    dpi_factor: xft_dpi_factor.unwrap_or(1.0),
+                    refresh_rate: 0.0,
+                });
+            }
+            // make sure exactly one monitor is the primary, xrandr leaves it unset on many setups
+            if !monitors.iter().any( | monitor | monitor.is_primary) {
+                monitors[0].is_primary = true;
+            }
+        }
+        monitors
+    }
+    
+    /// The desktop wide scale configured through the Xft.dpi resource, it wins over what the monitors report
+    pub fn get_xft_dpi_factor(&self) -> Option<f64> {
+        unsafe {
+            let resource_string = x11_sys::XResourceManagerString(self.display);
+            if resource_string == std::ptr::null_mut() {
+                return None
             }
+            let db = x11_sys::XrmGetStringDatabase(resource_string);
+            let mut ty = mem::MaybeUninit::uninit();
+            let mut value = mem::MaybeUninit::uninit();
+            x11_sys::XrmGetResource(
+                db,
+                "Xft.dpi\0".as_ptr() as * const _,
+                "String\0".as_ptr() as * const _,
+                ty.as_mut_ptr(),
+                value.as_mut_ptr()
+            );
+            let value = value.assume_init();
+            if value.addr == std::ptr::null_mut() {
+                return None
+            }
+            let dpi: f64 = std::ffi::CStr::from_ptr(value.addr).to_str().ok()?.trim().parse().ok()?;
+            if dpi > 0.0 {Some(dpi / 96.0)} else {None}
+        }
+    }
+    
+    /// Scale of the monitor a rect on the root window mostly sits on
+    pub fn dpi_factor_for_rect(&self, pos: DVec2, size: DVec2) -> f64 {
+        let center = pos + size * 0.5;
+        if let Some(monitor) = self.monitors.iter().find( | monitor | monitor.contains(center)) {
+            return monitor.dpi_factor
+        }
+        self.monitors.iter()
+            .map( | monitor | (monitor.overlap_area(pos, size), monitor))
+            .filter( | (area, _) | *area > 0.0)
+            .max_by( | a, b | a.0.total_cmp(&b.0))
+            .map( | (_, monitor) | monitor.dpi_factor)
+            .unwrap_or_else( || self.primary_dpi_factor())
+    }
+    
+    pub fn primary_dpi_factor(&self) -> f64 {
+        self.monitors.iter().find( | monitor | monitor.is_primary).map_or(1.0, | monitor | monitor.dpi_factor)
+    }
+    
+    /// The monitor layout changed, windows that ended up on a monitor with a different scale get a geom change
+    unsafe fn handle_screen_change(&mut self) {
+        self.monitors = self.enumerate_monitors();
+        self.do_callback(XlibEvent::MonitorsChanged(self.monitors.clone()));
+        let window_ptrs: Vec<*mut XlibWindow> = self.window_map.values().cloned().collect();
+        for window_ptr in window_ptrs {
+            (*window_ptr).update_window_geom();
         }
     }
     
@@ -96,69 +275,43 @@ impl XlibApp {
             let mut event = mem::MaybeUninit::uninit();
             x11_sys::XNextEvent(self.display, event.as_mut_ptr());
             let mut event = event.assume_init();
+            // the input method swallows the keys it uses for composing
+            if x11_sys::XFilterEvent(&mut event, 0) != 0 {
+                continue;
+            }
+            // randr events are numbered from a base handed out at runtime, so they can't be a match arm
+            if let Some(randr_event_base) = self.randr_event_base {
+                if event.type_ == randr_event_base + x11_sys::RRScreenChangeNotify as c_int {
+                    x11_sys::XRRUpdateConfiguration(&mut event);
+                    self.handle_screen_change();
+                    continue;
+                }
+            }
             match event.type_ as u32 {
                 x11_sys::SelectionNotify => {
                     let selection = event.xselection;
-                    if selection.property == self.dnd.atoms.selection {
-                        self.dnd.handle_selection_event(&selection);
-                    } else {
-                        // first get the size of the thing
-                        let mut actual_type = mem::MaybeUninit::uninit();
-                        let mut actual_format = mem::MaybeUninit::uninit();
-                        let mut n_items = mem::MaybeUninit::uninit();
-                        let mut bytes_to_read = mem::MaybeUninit::uninit();
-                        let mut ret = mem::MaybeUninit::uninit();
-                        x11_sys::XGetWindowProperty(
-                            self.display,
-                            selection.requestor,
-                            selection.property,
-                            0,
-                            0,
-                            0,
-                            x11_sys::AnyPropertyType as c_ulong,
-                            actual_type.as_mut_ptr(),
-                            actual_format.as_mut_ptr(),
-                            n_items.as_mut_ptr(),
-                            bytes_to_read.as_mut_ptr(),
-                            ret.as_mut_ptr()
-                        );
-                        //let actual_type = actual_type.assume_init();
-                        //let actual_format = actual_format.assume_init();
-                        //let n_items = n_items.assume_init();
-                        let bytes_to_read = bytes_to_read.assume_init();
-                        //let mut ret = ret.assume_init();
-                        let mut bytes_after = mem::MaybeUninit::uninit();
-                        x11_sys::XGetWindowProperty(
-                            self.display,
-                            selection.requestor,
-                            selection.property,
-                            0,
-                            bytes_to_read as c_long,
-                            0,
-                            x11_sys::AnyPropertyType as c_ulong,
-                            actual_type.as_mut_ptr(),
-                            actual_format.as_mut_ptr(),
-                            n_items.as_mut_ptr(),
-                            bytes_after.as_mut_ptr(),
-                            ret.as_mut_ptr()
-                        );
-                        let ret = ret.assume_init();
-                        //let bytes_after = bytes_after.assume_init();
-                        if ret != ptr::null_mut() && bytes_to_read > 0 {
-                            let utf8_slice = std::slice::from_raw_parts::<u8>(ret as *const _ as *const u8, bytes_to_read as usize);
-                            if let Ok(utf8_string) = String::from_utf8(utf8_slice.to_vec()) {
-                                self.do_callback(XlibEvent::TextInput(TextInputEvent {
-                                    input: utf8_string,
-                                    was_paste: true,
-                                    replace_last: false
-                                }));
-                            }
-                            x11_sys::XFree(ret as *mut _ as *mut c_void);
+                    if selection.selection == self.dnd.atoms.selection {
+                        // the data of a drag hovering us arrived, or of a drop that waited for it
+                        if self.dnd.handle_selection_event(&selection) {
+                            self.dnd_send_drop();
+                        }
+                        else {
+                            self.dnd_send_drag();
                         }
                     }
+                    else if let Some(result) = self.clipboard.handle_selection_notify(&selection) {
+                        self.do_clipboard_callback(result);
+                    }
                 },
                 x11_sys::SelectionRequest => {
                     let request = event.xselectionrequest;
+                    if self.dnd.handle_selection_request(&request) {
+                        continue;
+                    }
+                    if self.clipboard.handle_selection_request(&request) {
+                        continue;
+                    }
+                    // not a selection we ever own, refuse
                     let mut response = x11_sys::XSelectionEvent {
                         type_: x11_sys::SelectionNotify as i32,
                         serial: 0,
@@ -168,40 +321,18 @@ impl XlibApp {
                         selection: request.selection,
                         target: request.target,
                         time: request.time,
-                        property: request.property,
+                        property: 0,
                     };
-                    if request.target == self.atoms.targets {
-                        let mut targets = [self.atoms.utf8_string];
-                        x11_sys::XChangeProperty(
-                            self.display,
-                            request.requestor,
-                            request.property,
-                            4,
-                            32,
-                            x11_sys::PropModeReplace as i32,
-                            targets.as_mut() as *mut _ as *mut c_uchar,
-                            targets.len() as i32
-                        );
-                    }
-                    else if request.target == self.atoms.utf8_string {
-                        x11_sys::XChangeProperty(
-                            self.display,
-                            request.requestor,
-                            request.property,
-                            self.atoms.utf8_string,
-                            8,
-                            x11_sys::PropModeReplace as i32,
-                            self.clipboard.as_ptr() as *const _ as *const c_uchar,
-                            self.clipboard.len() as i32
-                        );
-                    }
-                    else {
-                        response.property = 0;
-                    }
                     x11_sys::XSendEvent(self.display, request.requestor, 1, 0, &mut response as *mut _ as *mut x11_sys::XEvent);
                 },
+                x11_sys::SelectionClear => {
+                    self.clipboard.handle_selection_clear(&event.xselectionclear);
+                },
                 x11_sys::DestroyNotify => { // our window got destroyed
                     let destroy_window = event.xdestroywindow;
+                    for result in self.clipboard.window_destroyed(destroy_window.window) {
+                        self.do_clipboard_callback(result);
+                    }
                     if let Some(window_ptr) = self.window_map.get(&destroy_window.window) {
                         let window = &mut (**window_ptr);
                         window.do_callback(XlibEvent::WindowClosed(WindowClosedEvent {
@@ -218,6 +349,24 @@ impl XlibApp {
                         }
                     }
                 },
+                x11_sys::PropertyNotify => {
+                    let prop = event.xproperty;
+                    if let Some(result) = self.clipboard.handle_property_notify(&prop) {
+                        self.do_clipboard_callback(result);
+                    }
+                    if prop.atom == self.atoms.net_wm_state {
+                        if let Some(window_ptr) = self.window_map.get(&prop.window) {
+                            let window = &mut (**window_ptr);
+                            window.update_window_geom();
+                        }
+                    }
+                },
+                x11_sys::FocusIn | x11_sys::FocusOut => {
+                    if let Some(window_ptr) = self.window_map.get(&event.xfocus.window) {
+                        let window = &mut (**window_ptr);
+                        window.set_focus(event.type_ as u32 == x11_sys::FocusIn);
+                    }
+                },
                 x11_sys::EnterNotify => {},
                 x11_sys::LeaveNotify => {
                     let crossing = event.xcrossing;
@@ -244,6 +393,11 @@ impl XlibApp {
                 },
                 x11_sys::MotionNotify => { // mousemove
                     let motion = event.xmotion;
+                    // while dragging out, the pointer is grabbed and the motion belongs to the drag
+                    if self.dnd.source.is_some() {
+                        self.dnd.source_motion(motion.x_root, motion.y_root, motion.time);
+                        continue;
+                    }
                     if let Some(window_ptr) = self.window_map.get(&motion.window) {
                         let window = &mut (**window_ptr);
                         let x = motion.x;
@@ -323,6 +477,7 @@ impl XlibApp {
                 x11_sys::ButtonPress => { // mouse down
                     let button = event.xbutton;
                     let time_now = self.time_now();
+                    self.dnd.last_press = (button.window, button.time);
                     if let Some(window_ptr) = self.window_map.get(&button.window) {
                         let window = &mut (**window_ptr);
                         x11_sys::XSetInputFocus(
@@ -399,7 +554,7 @@ impl XlibApp {
                                 }
                             }
                             else {
-                                window.send_mouse_down(button.button as usize, self.xkeystate_to_modifiers(button.state))
+                                window.send_mouse_down(xbutton_to_button(button.button), self.xkeystate_to_modifiers(button.state))
                             }
                         }
                     }
@@ -408,12 +563,32 @@ impl XlibApp {
                 },
                 x11_sys::ButtonRelease => { // mouse up
                     let button = event.xbutton;
+                    if let Some(source) = &self.dnd.source {
+                        let over_our_window = source.target.map_or(false, | (target, _) | self.window_map.contains_key(&target));
+                        if self.dnd.source_release(button.time) {
+                            if !over_our_window {
+                                self.do_callback(XlibEvent::DropOutside(DropOutsideEvent {
+                                    position: DVec2 {x: button.x_root as f64, y: button.y_root as f64}
+                                }));
+                            }
+                            self.do_callback(XlibEvent::DragEnd);
+                        }
+                    }
+                    // the scroll wheel buttons never sent a mouse down
+                    if button.button >= 4 && button.button <= 7 {
+                        continue;
+                    }
                     if let Some(window_ptr) = self.window_map.get(&button.window) {
                         let window = &mut (**window_ptr);
-                        window.send_mouse_up(button.button as usize, self.xkeystate_to_modifiers(button.state))
+                        window.send_mouse_up(xbutton_to_button(button.button), self.xkeystate_to_modifiers(button.state))
                     }
                 },
                 x11_sys::KeyPress => {
+                    if self.dnd.source.is_some() && self.xkeyevent_to_keycode(&mut event.xkey) == KeyCode::Escape {
+                        self.dnd.cancel_drag();
+                        self.do_callback(XlibEvent::DragEnd);
+                        continue;
+                    }
                     if let Some(window_ptr) = self.window_map.get(&event.xkey.window) {
                         let window = &mut (**window_ptr);
                         let block_text = if event.xkey.keycode != 0 {
@@ -424,23 +599,12 @@ impl XlibApp {
                                 match key_code {
                                     KeyCode::KeyV => { // paste
                                         // request the pasteable text from the other side
-                                        x11_sys::XConvertSelection(
-                                            self.display,
-                                            self.atoms.clipboard,
-                                            self.atoms.utf8_string,
-                                            self.atoms.clipboard,
-                                            window.window.unwrap(),
-                                            event.xkey.time
+                                        self.clipboard.read(
+                                            ClipboardReadKind::Paste,
+                                            ClipboardSelection::Clipboard,
+                                            vec![ClipboardItem::TEXT.to_string()],
+                                            window.window.unwrap()
                                         );
-                                        /*
-                                        self.do_callback(&mut vec![
-                                            Event::TextInput(TextInputEvent {
-                                                input: String::new(),
-                                                was_paste: true,
-                                                replace_last: false
-                                            })
-                                        ]);
-                                        */
                                     }
                                     KeyCode::KeyC => {
                                         let response = Rc::new(RefCell::new(None));
@@ -449,7 +613,7 @@ impl XlibApp {
                                         }));
                                         let response = response.borrow();
                                         if let Some(response) = response.as_ref() {
-                                            self.copy_to_clipboard(response, &window, &event);
+                                            self.clipboard.set_items(ClipboardSelection::Clipboard, vec![ClipboardItem::text(response)], window.window.unwrap(), event.xkey.time);
                                         }
                                     }
                                     KeyCode::KeyX => {
@@ -459,7 +623,7 @@ impl XlibApp {
                                         }));
                                         let response = response.borrow();
                                         if let Some(response) = response.as_ref() {
-                                            self.copy_to_clipboard(response, &window, &event);
+                                            self.clipboard.set_items(ClipboardSelection::Clipboard, vec![ClipboardItem::text(response)], window.window.unwrap(), event.xkey.time);
                                         }
                                     }
                                     _ => ()
@@ -478,21 +642,46 @@ impl XlibApp {
                         
                         if !block_text {
                             // decode the character
-                            let mut buffer = [0u8; 32];
+                            let mut buffer = vec![0u8; 32];
                             let mut keysym = mem::MaybeUninit::uninit();
-                            let mut status = mem::MaybeUninit::uninit();
-                            let count = x11_sys::Xutf8LookupString(
-                                window.xic.unwrap(),
-                                &mut event.xkey,
-                                buffer.as_mut_ptr() as *mut c_char,
-                                buffer.len() as c_int,
-                                keysym.as_mut_ptr(),
-                                status.as_mut_ptr(),
-                            );
-                            //let keysym = keysym.assume_init();
-                            let status = status.assume_init();
-                            if status != x11_sys::XBufferOverflow {
-                                let utf8 = std::str::from_utf8(&buffer[..count as usize]).unwrap_or("").to_string();
+                            let mut status = x11_sys::XLookupNone;
+                            let count = if let Some(xic) = window.xic {
+                                let count = x11_sys::Xutf8LookupString(
+                                    xic,
+                                    &mut event.xkey,
+                                    buffer.as_mut_ptr() as *mut c_char,
+                                    buffer.len() as c_int,
+                                    keysym.as_mut_ptr(),
+                                    &mut status,
+                                );
+                                // committed IME text can be longer than a single key
+                                if status == x11_sys::XBufferOverflow {
+                                    buffer.resize(count as usize + 1, 0);
+                                    x11_sys::Xutf8LookupString(
+                                        xic,
+                                        &mut event.xkey,
+                                        buffer.as_mut_ptr() as *mut c_char,
+                                        buffer.len() as c_int,
+                                        keysym.as_mut_ptr(),
+                                        &mut status,
+                                    )
+                                }
+                                else {
+                                    count
+                                }
+                            }
+                            else {
+                                status = x11_sys::XLookupChars;
+                                x11_sys::XLookupString(
+                                    &mut event.xkey,
+                                    buffer.as_mut_ptr() as *mut c_char,
+                                    buffer.len() as c_int,
+                                    keysym.as_mut_ptr(),
+                                    ptr::null_mut(),
+                                )
+                            };
+                            if status == x11_sys::XLookupChars || status == x11_sys::XLookupBoth {
+                                let utf8 = String::from_utf8_lossy(&buffer[..count.max(0) as usize]).to_string();
                                 let char_code = utf8.chars().next().unwrap_or('\0');
                                 if char_code >= ' ' && char_code != 127 as char {
                                     self.do_callback(XlibEvent::TextInput(TextInputEvent {
@@ -524,11 +713,21 @@ impl XlibApp {
                     if event.message_type == self.dnd.atoms.enter {
                         self.dnd.handle_enter_event(&event);
                     } else if event.message_type == self.dnd.atoms.drop {
-                        self.dnd.handle_drop_event(&event);
+                        if self.dnd.handle_drop_event(&event) {
+                            self.dnd_send_drop();
+                        }
                     } else if event.message_type == self.dnd.atoms.leave {
                         self.dnd.handle_leave_event(&event);
+                        self.do_callback(XlibEvent::DragEnd);
                     } else if event.message_type == self.dnd.atoms.position {
                         self.dnd.handle_position_event(&event);
+                        self.dnd_send_drag();
+                    } else if event.message_type == self.dnd.atoms.status {
+                        self.dnd.handle_status_event(&event);
+                    } else if event.message_type == self.dnd.atoms.finished {
+                        if self.dnd.handle_finished_event(&event) {
+                            self.do_callback(XlibEvent::DragEnd);
+                        }
                     }
                 },
                 x11_sys::Expose => {
@@ -637,6 +836,60 @@ impl XlibApp {
         return None
     }
     
+    /// Lets the window under a drag know about it, and tells the source what it answered
+    unsafe fn dnd_send_drag(&mut self) {
+        let target_window = self.dnd.target_window;
+        let Some(window_ptr) = self.window_map.get(&target_window) else {
+            return
+        };
+        let window = &mut (**window_ptr);
+        let (x_root, y_root) = self.dnd.last_root_pos;
+        let response = Rc::new(Cell::new(DragResponse::None));
+        window.do_callback(XlibEvent::Drag(DragEvent {
+            window_id: window.window_id,
+            modifiers: KeyModifiers::default(),
+            handled: Cell::new(false),
+            abs: window.root_to_local(x_root, y_root),
+            items: self.dnd.items.clone().unwrap_or_default(),
+            response: response.clone(),
+        }));
+        let action = self.dnd.response_to_action(response.get());
+        self.dnd.send_status_event(self.dnd.source_window, target_window, action);
+    }
+    
+    /// Delivers a drop once its data is there, a drop the app didn't accept ends the drag instead
+    unsafe fn dnd_send_drop(&mut self) {
+        let accepted = self.dnd.action != self.dnd.atoms.none && self.dnd.action != 0;
+        match self.window_map.get(&self.dnd.target_window) {
+            Some(window_ptr) if accepted => {
+                let window = &mut (**window_ptr);
+                let (x_root, y_root) = self.dnd.last_root_pos;
+                window.do_callback(XlibEvent::Drop(DropEvent {
+                    window_id: window.window_id,
+                    modifiers: KeyModifiers::default(),
+                    handled: Cell::new(false),
+                    abs: window.root_to_local(x_root, y_root),
+                    items: self.dnd.items.clone().unwrap_or_default(),
+                }));
+            }
+            _ => {
+                self.do_callback(XlibEvent::DragEnd);
+            }
+        }
+        self.dnd.send_finished_event();
+    }
+    
+    pub fn start_dragging(&mut self, items: Vec<DragItem>) {
+        unsafe {
+            if self.dnd.source.is_some() {
+                self.dnd.cancel_drag();
+            }
+            if !self.dnd.start_drag(&items) {
+                self.do_callback(XlibEvent::DragEnd);
+            }
+        }
+    }
+    
     pub fn set_mouse_cursor(&mut self, cursor: MouseCursor) {
         if self.current_cursor != cursor {
             self.current_cursor = cursor.clone();
@@ -702,154 +955,192 @@ impl XlibApp {
                 ptr::null_mut(),
             );
         }
-        match keysym as u32 {
-            x11_sys::XK_a => KeyCode::KeyA,
-            x11_sys::XK_A => KeyCode::KeyA,
-            x11_sys::XK_b => KeyCode::KeyB,
-            x11_sys::XK_B => KeyCode::KeyB,
-            x11_sys::XK_c => KeyCode::KeyC,
-            x11_sys::XK_C => KeyCode::KeyC,
-            x11_sys::XK_d => KeyCode::KeyD,
-            x11_sys::XK_D => KeyCode::KeyD,
-            x11_sys::XK_e => KeyCode::KeyE,
-            x11_sys::XK_E => KeyCode::KeyE,
-            x11_sys::XK_f => KeyCode::KeyF,
-            x11_sys::XK_F => KeyCode::KeyF,
-            x11_sys::XK_g => KeyCode::KeyG,
-            x11_sys::XK_G => KeyCode::KeyG,
-            x11_sys::XK_h => KeyCode::KeyH,
-            x11_sys::XK_H => KeyCode::KeyH,
-            x11_sys::XK_i => KeyCode::KeyI,
-            x11_sys::XK_I => KeyCode::KeyI,
-            x11_sys::XK_j => KeyCode::KeyJ,
-            x11_sys::XK_J => KeyCode::KeyJ,
-            x11_sys::XK_k => KeyCode::KeyK,
-            x11_sys::XK_K => KeyCode::KeyK,
-            x11_sys::XK_l => KeyCode::KeyL,
-            x11_sys::XK_L => KeyCode::KeyL,
-            x11_sys::XK_m => KeyCode::KeyM,
-            x11_sys::XK_M => KeyCode::KeyM,
-            x11_sys::XK_n => KeyCode::KeyN,
-            x11_sys::XK_N => KeyCode::KeyN,
-            x11_sys::XK_o => KeyCode::KeyO,
-            x11_sys::XK_O => KeyCode::KeyO,
-            x11_sys::XK_p => KeyCode::KeyP,
-            x11_sys::XK_P => KeyCode::KeyP,
-            x11_sys::XK_q => KeyCode::KeyQ,
-            x11_sys::XK_Q => KeyCode::KeyQ,
-            x11_sys::XK_r => KeyCode::KeyR,
-            x11_sys::XK_R => KeyCode::KeyR,
-            x11_sys::XK_s => KeyCode::KeyS,
-            x11_sys::XK_S => KeyCode::KeyS,
-            x11_sys::XK_t => KeyCode::KeyT,
-            x11_sys::XK_T => KeyCode::KeyT,
-            x11_sys::XK_u => KeyCode::KeyU,
-            x11_sys::XK_U => KeyCode::KeyU,
-            x11_sys::XK_v => KeyCode::KeyV,
-            x11_sys::XK_V => KeyCode::KeyV,
-            x11_sys::XK_w => KeyCode::KeyW,
-            x11_sys::XK_W => KeyCode::KeyW,
-            x11_sys::XK_x => KeyCode::KeyX,
-            x11_sys::XK_X => KeyCode::KeyX,
-            x11_sys::XK_y => KeyCode::KeyY,
-            x11_sys::XK_Y => KeyCode::KeyY,
-            x11_sys::XK_z => KeyCode::KeyZ,
-            x11_sys::XK_Z => KeyCode::KeyZ,
-            
-            x11_sys::XK_0 => KeyCode::Key0,
-            x11_sys::XK_1 => KeyCode::Key1,
-            x11_sys::XK_2 => KeyCode::Key2,
-            x11_sys::XK_3 => KeyCode::Key3,
-            x11_sys::XK_4 => KeyCode::Key4,
-            x11_sys::XK_5 => KeyCode::Key5,
-            x11_sys::XK_6 => KeyCode::Key6,
-            x11_sys::XK_7 => KeyCode::Key7,
-            x11_sys::XK_8 => KeyCode::Key8,
-            x11_sys::XK_9 => KeyCode::Key9,
-            
-            x11_sys::XK_Alt_L => KeyCode::Alt,
-            x11_sys::XK_Alt_R => KeyCode::Alt,
-            x11_sys::XK_Meta_L => KeyCode::Logo,
-            x11_sys::XK_Meta_R => KeyCode::Logo,
-            x11_sys::XK_Shift_L => KeyCode::Shift,
-            x11_sys::XK_Shift_R => KeyCode::Shift,
-            x11_sys::XK_Control_L => KeyCode::Control,
-            x11_sys::XK_Control_R => KeyCode::Control,
-            
-            x11_sys::XK_equal => KeyCode::Equals,
-            x11_sys::XK_minus => KeyCode::Minus,
-            x11_sys::XK_bracketright => KeyCode::RBracket,
-            x11_sys::XK_bracketleft => KeyCode::LBracket,
-            x11_sys::XK_Return => KeyCode::ReturnKey,
-            x11_sys::XK_grave => KeyCode::Backtick,
-            x11_sys::XK_semicolon => KeyCode::Semicolon,
-            x11_sys::XK_backslash => KeyCode::Backslash,
-            x11_sys::XK_comma => KeyCode::Comma,
-            x11_sys::XK_slash => KeyCode::Slash,
-            x11_sys::XK_period => KeyCode::Period,
-            x11_sys::XK_Tab => KeyCode::Tab,
-            x11_sys::XK_ISO_Left_Tab => KeyCode::Tab,
-            x11_sys::XK_space => KeyCode::Space,
-            x11_sys::XK_BackSpace => KeyCode::Backspace,
-            x11_sys::XK_Escape => KeyCode::Escape,
-            x11_sys::XK_Caps_Lock => KeyCode::Capslock,
-            x11_sys::XK_KP_Decimal => KeyCode::NumpadDecimal,
-            x11_sys::XK_KP_Multiply => KeyCode::NumpadMultiply,
-            x11_sys::XK_KP_Add => KeyCode::NumpadAdd,
-            x11_sys::XK_Num_Lock => KeyCode::Numlock,
-            x11_sys::XK_KP_Divide => KeyCode::NumpadDivide,
-            x11_sys::XK_KP_Enter => KeyCode::NumpadEnter,
-            x11_sys::XK_KP_Subtract => KeyCode::NumpadSubtract,
-            //keysim::XK_9 => KeyCode::NumpadEquals,
-            x11_sys::XK_KP_0 => KeyCode::Numpad0,
-            x11_sys::XK_KP_1 => KeyCode::Numpad1,
-            x11_sys::XK_KP_2 => KeyCode::Numpad2,
-            x11_sys::XK_KP_3 => KeyCode::Numpad3,
-            x11_sys::XK_KP_4 => KeyCode::Numpad4,
-            x11_sys::XK_KP_5 => KeyCode::Numpad5,
-            x11_sys::XK_KP_6 => KeyCode::Numpad6,
-            x11_sys::XK_KP_7 => KeyCode::Numpad7,
-            x11_sys::XK_KP_8 => KeyCode::Numpad8,
-            x11_sys::XK_KP_9 => KeyCode::Numpad9,
-            
-            x11_sys::XK_F1 => KeyCode::F1,
-            x11_sys::XK_F2 => KeyCode::F2,
-            x11_sys::XK_F3 => KeyCode::F3,
-            x11_sys::XK_F4 => KeyCode::F4,
-            x11_sys::XK_F5 => KeyCode::F5,
-            x11_sys::XK_F6 => KeyCode::F6,
-            x11_sys::XK_F7 => KeyCode::F7,
-            x11_sys::XK_F8 => KeyCode::F8,
-            x11_sys::XK_F9 => KeyCode::F9,
-            x11_sys::XK_F10 => KeyCode::F10,
-            x11_sys::XK_F11 => KeyCode::F11,
-            x11_sys::XK_F12 => KeyCode::F12,
-            
-            x11_sys::XK_Print => KeyCode::PrintScreen,
-            x11_sys::XK_Home => KeyCode::Home,
-            x11_sys::XK_Page_Up => KeyCode::PageUp,
-            x11_sys::XK_Delete => KeyCode::Delete,
-            x11_sys::XK_End => KeyCode::End,
-            x11_sys::XK_Page_Down => KeyCode::PageDown,
-            x11_sys::XK_Left => KeyCode::ArrowLeft,
-            x11_sys::XK_Right => KeyCode::ArrowRight,
-            x11_sys::XK_Down => KeyCode::ArrowDown,
-            x11_sys::XK_Up => KeyCode::ArrowUp,
-            _ => KeyCode::Unknown,
+        keysym_to_keycode(keysym as u32)
+    }
+
+    /// Any of our windows can own a selection, the first one will do
+    fn selection_window(&self) -> Option<x11_sys::Window> {
+        self.window_map.keys().next().copied()
+    }
+    
+    pub fn copy_to_clipboard(&mut self, selection: ClipboardSelection, items: Vec<ClipboardItem>) {
+        if let Some(window) = self.selection_window() {
+            unsafe {self.clipboard.set_items(selection, items, window, x11_sys::CurrentTime as x11_sys::Time)};
+        }
+    }
+    
+    pub fn read_clipboard(&mut self, request_id: LiveId, selection: ClipboardSelection, mime_types: Vec<String>) {
+        if let Some(window) = self.selection_window() {
+            unsafe {self.clipboard.read(ClipboardReadKind::Request(request_id), selection, mime_types, window)};
+        }
+        else {
+            self.do_callback(XlibEvent::ClipboardRead(ClipboardReadEvent {request_id, selection, item: None}));
         }
     }
+    
+    fn do_clipboard_callback(&mut self, result: ClipboardReadResult) {
+        match result {
+            ClipboardReadResult::Paste(text) => if !text.is_empty() {
+                self.do_callback(XlibEvent::TextInput(TextInputEvent {
+                    input: text,
+                    was_paste: true,
+                    replace_last: false
+                }));
+            }
+            ClipboardReadResult::Read(event) => self.do_callback(XlibEvent::ClipboardRead(event)),
+        }
+    }
+}
+
+/// X11 numbers middle before right and puts back/forward after the scroll buttons,
+/// this maps them to the numbering used by the other platforms
+fn xbutton_to_button(button: c_uint) -> usize {
+    match button {
+        1 => 0,
+        2 => 2,
+        3 => 1,
+        8 => 3,
+        9 => 4,
+        button => button as usize
+    }
+}
 
-    unsafe fn copy_to_clipboard(&mut self, text: &String, window: &XlibWindow, event: &XEvent) {
-        // store the text on the clipboard
-        self.clipboard = text.clone();
-        // lets set the owner
-        x11_sys::XSetSelectionOwner(
-            self.display,
-            self.atoms.clipboard,
-            window.window.unwrap(),
-            event.xkey.time
-        );
-        x11_sys::XFlush(self.display);
+/// Maps a keysym to a key code, xkbcommon uses the same keysyms so the Wayland backend shares this
+pub fn keysym_to_keycode(keysym: u32) -> KeyCode {
+    match keysym {
+        x11_sys::XK_a => KeyCode::KeyA,
+        x11_sys::XK_A => KeyCode::KeyA,
+        x11_sys::XK_b => KeyCode::KeyB,
+        x11_sys::XK_B => KeyCode::KeyB,
+        x11_sys::XK_c => KeyCode::KeyC,
+        x11_sys::XK_C => KeyCode::KeyC,
+        x11_sys::XK_d => KeyCode::KeyD,
+        x11_sys::XK_D => KeyCode::KeyD,
+        x11_sys::XK_e => KeyCode::KeyE,
+        x11_sys::XK_E => KeyCode::KeyE,
+        x11_sys::XK_f => KeyCode::KeyF,
+        x11_sys::XK_F => KeyCode::KeyF,
+        x11_sys::XK_g => KeyCode::KeyG,
+        x11_sys::XK_G => KeyCode::KeyG,
+        x11_sys::XK_h => KeyCode::KeyH,
+        x11_sys::XK_H => KeyCode::KeyH,
+        x11_sys::XK_i => KeyCode::KeyI,
+        x11_sys::XK_I => KeyCode::KeyI,
+        x11_sys::XK_j => KeyCode::KeyJ,
+        x11_sys::XK_J => KeyCode::KeyJ,
+        x11_sys::XK_k => KeyCode::KeyK,
+        x11_sys::XK_K => KeyCode::KeyK,
+        x11_sys::XK_l => KeyCode::KeyL,
+        x11_sys::XK_L => KeyCode::KeyL,
+        x11_sys::XK_m => KeyCode::KeyM,
+        x11_sys::XK_M => KeyCode::KeyM,
+        x11_sys::XK_n => KeyCode::KeyN,
+        x11_sys::XK_N => KeyCode::KeyN,
+        x11_sys::XK_o => KeyCode::KeyO,
+        x11_sys::XK_O => KeyCode::KeyO,
+        x11_sys::XK_p => KeyCode::KeyP,
+        x11_sys::XK_P => KeyCode::KeyP,
+        x11_sys::XK_q => KeyCode::KeyQ,
+        x11_sys::XK_Q => KeyCode::KeyQ,
+        x11_sys::XK_r => KeyCode::KeyR,
+        x11_sys::XK_R => KeyCode::KeyR,
+        x11_sys::XK_s => KeyCode::KeyS,
+        x11_sys::XK_S => KeyCode::KeyS,
+        x11_sys::XK_t => KeyCode::KeyT,
+        x11_sys::XK_T => KeyCode::KeyT,
+        x11_sys::XK_u => KeyCode::KeyU,
+        x11_sys::XK_U => KeyCode::KeyU,
+        x11_sys::XK_v => KeyCode::KeyV,
+        x11_sys::XK_V => KeyCode::KeyV,
+        x11_sys::XK_w => KeyCode::KeyW,
+        x11_sys::XK_W => KeyCode::KeyW,
+        x11_sys::XK_x => KeyCode::KeyX,
+        x11_sys::XK_X => KeyCode::KeyX,
+        x11_sys::XK_y => KeyCode::KeyY,
+        x11_sys::XK_Y => KeyCode::KeyY,
+        x11_sys::XK_z => KeyCode::KeyZ,
+        x11_sys::XK_Z => KeyCode::KeyZ,
+        
+        x11_sys::XK_0 => KeyCode::Key0,
+        x11_sys::XK_1 => KeyCode::Key1,
+        x11_sys::XK_2 => KeyCode::Key2,
+        x11_sys::XK_3 => KeyCode::Key3,
+        x11_sys::XK_4 => KeyCode::Key4,
+        x11_sys::XK_5 => KeyCode::Key5,
+        x11_sys::XK_6 => KeyCode::Key6,
+        x11_sys::XK_7 => KeyCode::Key7,
+        x11_sys::XK_8 => KeyCode::Key8,
+        x11_sys::XK_9 => KeyCode::Key9,
+        
+        x11_sys::XK_Alt_L => KeyCode::Alt,
+        x11_sys::XK_Alt_R => KeyCode::Alt,
+        x11_sys::XK_Meta_L => KeyCode::Logo,
+        x11_sys::XK_Meta_R => KeyCode::Logo,
+        x11_sys::XK_Shift_L => KeyCode::Shift,
+        x11_sys::XK_Shift_R => KeyCode::Shift,
+        x11_sys::XK_Control_L => KeyCode::Control,
+        x11_sys::XK_Control_R => KeyCode::Control,
+        
+        x11_sys::XK_equal => KeyCode::Equals,
+        x11_sys::XK_minus => KeyCode::Minus,
+        x11_sys::XK_bracketright => KeyCode::RBracket,
+        x11_sys::XK_bracketleft => KeyCode::LBracket,
+        x11_sys::XK_Return => KeyCode::ReturnKey,
+        x11_sys::XK_grave => KeyCode::Backtick,
+        x11_sys::XK_semicolon => KeyCode::Semicolon,
+        x11_sys::XK_backslash => KeyCode::Backslash,
+        x11_sys::XK_comma => KeyCode::Comma,
+        x11_sys::XK_slash => KeyCode::Slash,
+        x11_sys::XK_period => KeyCode::Period,
+        x11_sys::XK_Tab => KeyCode::Tab,
+        x11_sys::XK_ISO_Left_Tab => KeyCode::Tab,
+        x11_sys::XK_space => KeyCode::Space,
+        x11_sys::XK_BackSpace => KeyCode::Backspace,
+        x11_sys::XK_Escape => KeyCode::Escape,
+        x11_sys::XK_Caps_Lock => KeyCode::Capslock,
+        x11_sys::XK_KP_Decimal => KeyCode::NumpadDecimal,
+        x11_sys::XK_KP_Multiply => KeyCode::NumpadMultiply,
+        x11_sys::XK_KP_Add => KeyCode::NumpadAdd,
+        x11_sys::XK_Num_Lock => KeyCode::Numlock,
+        x11_sys::XK_KP_Divide => KeyCode::NumpadDivide,
+        x11_sys::XK_KP_Enter => KeyCode::NumpadEnter,
+        x11_sys::XK_KP_Subtract => KeyCode::NumpadSubtract,
+        //keysim::XK_9 => KeyCode::NumpadEquals,
+        x11_sys::XK_KP_0 => KeyCode::Numpad0,
+        x11_sys::XK_KP_1 => KeyCode::Numpad1,
+        x11_sys::XK_KP_2 => KeyCode::Numpad2,
+        x11_sys::XK_KP_3 => KeyCode::Numpad3,
+        x11_sys::XK_KP_4 => KeyCode::Numpad4,
+        x11_sys::XK_KP_5 => KeyCode::Numpad5,
+        x11_sys::XK_KP_6 => KeyCode::Numpad6,
+        x11_sys::XK_KP_7 => KeyCode::Numpad7,
+        x11_sys::XK_KP_8 => KeyCode::Numpad8,
+        x11_sys::XK_KP_9 => KeyCode::Numpad9,
+        
+        x11_sys::XK_F1 => KeyCode::F1,
+        x11_sys::XK_F2 => KeyCode::F2,
+        x11_sys::XK_F3 => KeyCode::F3,
+        x11_sys::XK_F4 => KeyCode::F4,
+        x11_sys::XK_F5 => KeyCode::F5,
+        x11_sys::XK_F6 => KeyCode::F6,
+        x11_sys::XK_F7 => KeyCode::F7,
+        x11_sys::XK_F8 => KeyCode::F8,
+        x11_sys::XK_F9 => KeyCode::F9,
+        x11_sys::XK_F10 => KeyCode::F10,
+        x11_sys::XK_F11 => KeyCode::F11,
+        x11_sys::XK_F12 => KeyCode::F12,
+        
+        x11_sys::XK_Print => KeyCode::PrintScreen,
+        x11_sys::XK_Home => KeyCode::Home,
+        x11_sys::XK_Page_Up => KeyCode::PageUp,
+        x11_sys::XK_Delete => KeyCode::Delete,
+        x11_sys::XK_End => KeyCode::End,
+        x11_sys::XK_Page_Down => KeyCode::PageDown,
+        x11_sys::XK_Left => KeyCode::ArrowLeft,
+        x11_sys::XK_Right => KeyCode::ArrowRight,
+        x11_sys::XK_Down => KeyCode::ArrowDown,
+        x11_sys::XK_Up => KeyCode::ArrowUp,
+        _ => KeyCode::Unknown,
     }
 }
 
@@ -862,6 +1153,8 @@ pub struct XlibAtoms {
     pub net_wm_state: x11_sys::Atom,
     pub new_wm_state_maximized_horz: x11_sys::Atom,
     pub new_wm_state_maximized_vert: x11_sys::Atom,
+    pub net_wm_state_fullscreen: x11_sys::Atom,
+    pub net_wm_state_above: x11_sys::Atom,
     pub targets: x11_sys::Atom,
     pub utf8_string: x11_sys::Atom,
     pub text: x11_sys::Atom,
@@ -881,6 +1174,8 @@ impl XlibAtoms {
             net_wm_state: x11_sys::XInternAtom(display, "_NET_WM_STATE\0".as_ptr() as *const _, 0),
             new_wm_state_maximized_horz: x11_sys::XInternAtom(display, "_NET_WM_STATE_MAXIMIZED_HORZ\0".as_ptr() as *const _, 0),
             new_wm_state_maximized_vert: x11_sys::XInternAtom(display, "_NET_WM_STATE_MAXIMIZED_VERT\0".as_ptr() as *const _, 0),
+            net_wm_state_fullscreen: x11_sys::XInternAtom(display, "_NET_WM_STATE_FULLSCREEN\0".as_ptr() as *const _, 0),
+            net_wm_state_above: x11_sys::XInternAtom(display, "_NET_WM_STATE_ABOVE\0".as_ptr() as *const _, 0),
             targets: x11_sys::XInternAtom(display, "TARGETS\0".as_ptr() as *const _, 0),
             utf8_string: x11_sys::XInternAtom(display, "UTF8_STRING\0".as_ptr() as *const _, 1),
             atom: x11_sys::XInternAtom(display, "ATOM\0".as_ptr() as *const _, 0),
@@ -0,0 +1,489 @@
+use {
+    std::{
+        collections::VecDeque,
+        ffi::{CStr, CString},
+        mem,
+        os::raw::{c_int, c_long, c_uchar, c_void},
+        ptr,
+        rc::Rc,
+    },
+    self::super::x11_sys,
+    crate::{
+        makepad_live_id::LiveId,
+        event::*,
+    },
+};
+
+// X11 selections are owned by a window and the data is pulled by the requestor through a
+// property on its own window. Reads are asynchronous: first we ask for the TARGETS to pick
+// a type, then for the data itself. Transfers bigger than a request go through the INCR
+// protocol, where the data comes in chunks every time the receiver deletes the property.
+
+/// What a finished read turns into
+pub enum ClipboardReadResult {
+    /// Ctrl+V, the text goes out as a TextInput
+    Paste(String),
+    Read(ClipboardReadEvent),
+}
+
+pub enum ClipboardReadKind {
+    Paste,
+    Request(LiveId),
+}
+
+enum ReadState {
+    Targets,
+    Data {mime_type: String},
+    Incr {mime_type: String, data: Vec<u8>},
+}
+
+struct ClipboardRead {
+    kind: ClipboardReadKind,
+    selection: ClipboardSelection,
+    mime_types: Vec<String>,
+    window: x11_sys::Window,
+    state: ReadState,
+}
+
+impl ClipboardRead {
+    fn into_result(self, item: Option<ClipboardItem>) -> Option<ClipboardReadResult> {
+        match self.kind {
+            ClipboardReadKind::Paste => {
+                let text = item.and_then( | item | item.as_text())?;
+                Some(ClipboardReadResult::Paste(text))
+            }
+            ClipboardReadKind::Request(request_id) => Some(ClipboardReadResult::Read(ClipboardReadEvent {
+                request_id,
+                selection: self.selection,
+                item,
+            }))
+        }
+    }
+}
+
+/// A transfer we serve in chunks to another client
+struct IncrTransfer {
+    requestor: x11_sys::Window,
+    property: x11_sys::Atom,
+    target: x11_sys::Atom,
+    data: Rc<Vec<u8>>,
+    offset: usize,
+}
+
+pub struct ClipboardAtoms {
+    pub clipboard: x11_sys::Atom,
+    pub primary: x11_sys::Atom,
+    pub targets: x11_sys::Atom,
+    pub incr: x11_sys::Atom,
+    pub utf8_string: x11_sys::Atom,
+    pub string: x11_sys::Atom,
+    pub text: x11_sys::Atom,
+    pub text_plain: x11_sys::Atom,
+    pub text_plain_utf8: x11_sys::Atom,
+    /// Property on our window that the conversions are stored in
+    pub transfer: x11_sys::Atom,
+}
+
+impl ClipboardAtoms {
+    fn new(display: *mut x11_sys::Display) -> Self {
+        unsafe {Self {
+            clipboard: x11_sys::XInternAtom(display, "CLIPBOARD\0".as_ptr() as *const _, 0),
+            primary: x11_sys::XA_PRIMARY as x11_sys::Atom,
+            targets: x11_sys::XInternAtom(display, "TARGETS\0".as_ptr() as *const _, 0),
+            incr: x11_sys::XInternAtom(display, "INCR\0".as_ptr() as *const _, 0),
+            utf8_string: x11_sys::XInternAtom(display, "UTF8_STRING\0".as_ptr() as *const _, 0),
+            string: x11_sys::XA_STRING as x11_sys::Atom,
+            text: x11_sys::XInternAtom(display, "TEXT\0".as_ptr() as *const _, 0),
+            text_plain: x11_sys::XInternAtom(display, "text/plain\0".as_ptr() as *const _, 0),
+            text_plain_utf8: x11_sys::XInternAtom(display, "text/plain;charset=utf-8\0".as_ptr() as *const _, 0),
+            transfer: x11_sys::XInternAtom(display, "MAKEPAD_SELECTION\0".as_ptr() as *const _, 0),
+        }}
+    }
+}
+
+pub struct XlibClipboard {
+    pub atoms: ClipboardAtoms,
+    pub display: *mut x11_sys::Display,
+    /// What we serve while owning the CLIPBOARD selection
+    pub clipboard_items: Vec<ClipboardItem>,
+    /// Same for PRIMARY
+    pub primary_items: Vec<ClipboardItem>,
+    reads: VecDeque<ClipboardRead>,
+    transfers: Vec<IncrTransfer>,
+    chunk_size: usize,
+}
+
+impl XlibClipboard {
+    pub fn new(display: *mut x11_sys::Display) -> Self {
+        // anything that doesn't fit in a single request has to go through INCR
+        let max_request_bytes = unsafe {x11_sys::XMaxRequestSize(display)} as usize * 4;
+        Self {
+            atoms: ClipboardAtoms::new(display),
+            display,
+            clipboard_items: Vec::new(),
+            primary_items: Vec::new(),
+            reads: VecDeque::new(),
+            transfers: Vec::new(),
+            chunk_size: max_request_bytes.saturating_sub(1024).clamp(4096, 262144),
+        }
+    }
+
+    fn selection_atom(&self, selection: ClipboardSelection) -> x11_sys::Atom {
+        match selection {
+            ClipboardSelection::Clipboard => self.atoms.clipboard,
+            ClipboardSelection::Primary => self.atoms.primary,
+        }
+    }
+
+    fn items(&self, selection: x11_sys::Atom) -> Option<&Vec<ClipboardItem >> {
+        if selection == self.atoms.clipboard {
+            Some(&self.clipboard_items)
+        }
+        else if selection == self.atoms.primary {
+            Some(&self.primary_items)
+        }
+        else {
+            None
+        }
+    }
+
+    /// Takes ownership of the selection with the given items, no items gives it up
+    pub unsafe fn set_items(&mut self, selection: ClipboardSelection, items: Vec<ClipboardItem>, window: x11_sys::Window, time: x11_sys::Time) {
+        let owner = if items.is_empty() {x11_sys::None as x11_sys::Window} else {window};
+        match selection {
+            ClipboardSelection::Clipboard => self.clipboard_items = items,
+            ClipboardSelection::Primary => self.primary_items = items,
+        }
+        x11_sys::XSetSelectionOwner(self.display, self.selection_atom(selection), owner, time);
+        x11_sys::XFlush(self.display);
+    }
+
+    /// Another client took the selection over
+    pub fn handle_selection_clear(&mut self, event: &x11_sys::XSelectionClearEvent) {
+        if event.selection == self.atoms.clipboard {
+            self.clipboard_items.clear();
+        }
+        else if event.selection == self.atoms.primary {
+            self.primary_items.clear();
+        }
+    }
+
+    // Reading
+
+    /// Queues a read, the answer comes back from handle_selection_notify or handle_property_notify
+    pub unsafe fn read(&mut self, kind: ClipboardReadKind, selection: ClipboardSelection, mime_types: Vec<String>, window: x11_sys::Window) {
+        self.reads.push_back(ClipboardRead {
+            kind,
+            selection,
+            mime_types,
+            window,
+            state: ReadState::Targets,
+        });
+        if self.reads.len() == 1 {
+            self.start_read();
+        }
+    }
+
+    unsafe fn start_read(&mut self) {
+        if let Some(read) = self.reads.front() {
+            x11_sys::XConvertSelection(
+                self.display,
+                self.selection_atom(read.selection),
+                self.atoms.targets,
+                self.atoms.transfer,
+                read.window,
+                x11_sys::CurrentTime as x11_sys::Time
+            );
+            x11_sys::XFlush(self.display);
+        }
+    }
+
+    unsafe fn finish_read(&mut self, item: Option<ClipboardItem>) -> Option<ClipboardReadResult> {
+        let read = self.reads.pop_front()?;
+        self.start_read();
+        read.into_result(item)
+    }
+
+    /// Reads waiting on a window that went away never get their answer, they end empty
+    pub unsafe fn window_destroyed(&mut self, window: x11_sys::Window) -> Vec<ClipboardReadResult> {
+        let head_lost = self.reads.front().map_or(false, | read | read.window == window);
+        let (lost, kept): (VecDeque<_>, VecDeque<_>) = self.reads.drain(..).partition( | read | read.window == window);
+        self.reads = kept;
+        if head_lost {
+            self.start_read();
+        }
+        lost.into_iter().filter_map( | read | read.into_result(None)).collect()
+    }
+
+    /// Handles the answer to one of our conversion requests
+    pub unsafe fn handle_selection_notify(&mut self, event: &x11_sys::XSelectionEvent) -> Option<ClipboardReadResult> {
+        let read = self.reads.front()?;
+        if event.selection != self.selection_atom(read.selection) || event.requestor != read.window {
+            return None
+        }
+        let window = read.window;
+        // a property of None means the owner couldn't convert, or there is no owner
+        if event.property == x11_sys::None as x11_sys::Atom {
+            return self.finish_read(None)
+        }
+        let Some((ty, data)) = self.read_property(window, event.property) else {
+            return self.finish_read(None)
+        };
+        match &self.reads[0].state {
+            ReadState::Targets => {
+                let targets: Vec<x11_sys::Atom> = data
+                    .chunks_exact(mem::size_of::<x11_sys::Atom>())
+                    .map( | chunk | x11_sys::Atom::from_ne_bytes(chunk.try_into().unwrap()))
+                    .collect();
+                let Some((target, mime_type)) = self.pick_target(&self.reads[0].mime_types, &targets) else {
+                    return self.finish_read(None)
+                };
+                x11_sys::XConvertSelection(
+                    self.display,
+                    event.selection,
+                    target,
+                    self.atoms.transfer,
+                    window,
+                    x11_sys::CurrentTime as x11_sys::Time
+                );
+                x11_sys::XFlush(self.display);
+                self.reads[0].state = ReadState::Data {mime_type};
+                None
+            }
+            ReadState::Data {mime_type} => {
+                let mime_type = mime_type.clone();
+                if ty == self.atoms.incr {
+                    // deleting the property, which read_property did, asks for the first chunk
+                    self.reads[0].state = ReadState::Incr {mime_type, data: Vec::new()};
+                    return None
+                }
+                self.finish_read(Some(ClipboardItem::new(&mime_type, data)))
+            }
+            ReadState::Incr {..} => None
+        }
+    }
+
+    /// Picks the first of the wanted mime types the owner offers. Text can come in as any of
+    /// the text targets, UTF8_STRING is what most toolkits offer
+    fn pick_target(&self, mime_types: &[String], targets: &[x11_sys::Atom]) -> Option<(x11_sys::Atom, String)> {
+        for mime_type in mime_types {
+            if is_text_mime_type(mime_type) {
+                for text_target in [self.atoms.utf8_string, self.atoms.text_plain_utf8, self.atoms.text_plain, self.atoms.string] {
+                    if targets.contains(&text_target) {
+                        return Some((text_target, mime_type.clone()))
+                    }
+                }
+            }
+            let Ok(name) = CString::new(mime_type.as_str()) else {
+                continue
+            };
+            let atom = unsafe {x11_sys::XInternAtom(self.display, name.as_ptr(), 1)};
+            if atom != 0 && targets.contains(&atom) {
+                return Some((atom, mime_type.clone()))
+            }
+        }
+        None
+    }
+
+    /// Reads and deletes a property, returns its type and raw data
+    unsafe fn read_property(&self, window: x11_sys::Window, property: x11_sys::Atom) -> Option<(x11_sys::Atom, Vec<u8>)> {
+        let mut data = Vec::new();
+        let mut offset = 0;
+        let length = 65536;
+        let mut actual_type = 0;
+        loop {
+            let mut actual_format = 0;
+            let mut nitems = 0;
+            let mut bytes_after = 0;
+            let mut prop = ptr::null_mut();
+            x11_sys::XGetWindowProperty(
+                self.display,
+                window,
+                property,
+                offset,
+                length,
+                x11_sys::False as c_int,
+                x11_sys::AnyPropertyType as x11_sys::Atom,
+                &mut actual_type,
+                &mut actual_format,
+                &mut nitems,
+                &mut bytes_after,
+                &mut prop,
+            );
+            if prop.is_null() {
+                break;
+            }
+            // Xlib hands out 32 bit items as longs
+            let item_size = match actual_format {
+                16 => mem::size_of::<i16>(),
+                32 => mem::size_of::<c_long>(),
+                _ => 1
+            };
+            data.extend_from_slice(std::slice::from_raw_parts(prop as *const u8, nitems as usize * item_size));
+            x11_sys::XFree(prop as *mut c_void);
+            if bytes_after == 0 {
+                break;
+            }
+            offset += length;
+        }
+        x11_sys::XDeleteProperty(self.display, window, property);
+        if actual_type == 0 {
+            return None
+        }
+        Some((actual_type, data))
+    }
+
+    // Serving
+
+    /// Answers another client asking for our selection, returns false for selections we don't serve
+    pub unsafe fn handle_selection_request(&mut self, request: &x11_sys::XSelectionRequestEvent) -> bool {
+        let Some(items) = self.items(request.selection) else {
+            return false
+        };
+        // obsolete clients leave the property out, ICCCM says to use the target then
+        let property = if request.property == x11_sys::None as x11_sys::Atom {request.target} else {request.property};
+        let mut response = x11_sys::XSelectionEvent {
+            type_: x11_sys::SelectionNotify as i32,
+            serial: 0,
+            send_event: 0,
+            display: self.display,
+            requestor: request.requestor,
+            selection: request.selection,
+            target: request.target,
+            time: request.time,
+            property,
+        };
+        if items.is_empty() {
+            response.property = x11_sys::None as x11_sys::Atom;
+        }
+        else if request.target == self.atoms.targets {
+            let mut targets = vec![self.atoms.targets];
+            for item in items {
+                if item.is_text() {
+                    targets.extend_from_slice(&[self.atoms.utf8_string, self.atoms.text_plain_utf8, self.atoms.text_plain, self.atoms.string, self.atoms.text]);
+                }
+                if let Ok(name) = CString::new(item.mime_type.as_str()) {
+                    targets.push(x11_sys::XInternAtom(self.display, name.as_ptr(), 0));
+                }
+            }
+            let mut seen = Vec::new();
+            targets.retain( | target | if seen.contains(target) {false} else {seen.push(*target); true});
+            x11_sys::XChangeProperty(
+                self.display,
+                request.requestor,
+                property,
+                x11_sys::XA_ATOM as x11_sys::Atom,
+                32,
+                x11_sys::PropModeReplace as i32,
+                targets.as_ptr() as *const c_uchar,
+                targets.len() as i32
+            );
+        }
+        else if let Some(data) = self.find_item_data(items, request.target) {
+            if data.len() > self.chunk_size {
+                // too big for one request, announce the size and hand it out as the requestor deletes the property
+                x11_sys::XSelectInput(self.display, request.requestor, x11_sys::PropertyChangeMask as c_long);
+                let size = [data.len() as c_long];
+                x11_sys::XChangeProperty(
+                    self.display,
+                    request.requestor,
+                    property,
+                    self.atoms.incr,
+                    32,
+                    x11_sys::PropModeReplace as i32,
+                    size.as_ptr() as *const c_uchar,
+                    1
+                );
+                self.transfers.retain( | t | t.requestor != request.requestor || t.property != property);
+                self.transfers.push(IncrTransfer {
+                    requestor: request.requestor,
+                    property,
+                    target: request.target,
+                    data,
+                    offset: 0,
+                });
+            }
+            else {
+                x11_sys::XChangeProperty(
+                    self.display,
+                    request.requestor,
+                    property,
+                    request.target,
+                    8,
+                    x11_sys::PropModeReplace as i32,
+                    data.as_ptr(),
+                    data.len() as i32
+                );
+            }
+        }
+        else {
+            response.property = x11_sys::None as x11_sys::Atom;
+        }
+        x11_sys::XSendEvent(self.display, request.requestor, 1, 0, &mut response as *mut _ as *mut x11_sys::XEvent);
+        x11_sys::XFlush(self.display);
+        true
+    }
+
+    /// Finds the item for a target, any text item serves the text targets
+    unsafe fn find_item_data(&self, items: &[ClipboardItem], target: x11_sys::Atom) -> Option<Rc<Vec<u8>>> {
+        let name = x11_sys::XGetAtomName(self.display, target);
+        if name.is_null() {
+            return None
+        }
+        let target_name = CStr::from_ptr(name).to_string_lossy().to_string();
+        x11_sys::XFree(name as *mut c_void);
+        if let Some(item) = items.iter().find( | item | item.mime_type == target_name) {
+            return Some(item.data.clone())
+        }
+        if is_text_mime_type(&target_name) {
+            return items.iter().find( | item | item.is_text()).map( | item | item.data.clone())
+        }
+        None
+    }
+
+    /// Property changes drive both directions of an INCR transfer
+    pub unsafe fn handle_property_notify(&mut self, event: &x11_sys::XPropertyEvent) -> Option<ClipboardReadResult> {
+        if event.state == x11_sys::PropertyDelete as c_int {
+            // the receiver took the last chunk, send the next one. An empty one ends the transfer
+            let index = self.transfers.iter().position( | t | t.requestor == event.window && t.property == event.atom)?;
+            let transfer = &mut self.transfers[index];
+            let end = (transfer.offset + self.chunk_size).min(transfer.data.len());
+            let chunk = &transfer.data[transfer.offset..end];
+            x11_sys::XChangeProperty(
+                self.display,
+                transfer.requestor,
+                transfer.property,
+                transfer.target,
+                8,
+                x11_sys::PropModeReplace as i32,
+                chunk.as_ptr(),
+                chunk.len() as i32
+            );
+            x11_sys::XFlush(self.display);
+            if chunk.is_empty() {
+                self.transfers.remove(index);
+            }
+            else {
+                transfer.offset = end;
+            }
+            return None
+        }
+        if event.state == x11_sys::PropertyNewValue as c_int && event.atom == self.atoms.transfer {
+            let read = self.reads.front()?;
+            if read.window != event.window || !matches!(read.state, ReadState::Incr {..}) {
+                return None
+            }
+            let (_, chunk) = self.read_property(event.window, event.atom)?;
+            let ReadState::Incr {mime_type, data} = &mut self.reads[0].state else {
+                return None
+            };
+            if !chunk.is_empty() {
+                data.extend_from_slice(&chunk);
+                return None
+            }
+            let item = ClipboardItem::new(mime_type, mem::take(data));
+            return self.finish_read(Some(item))
+        }
+        None
+    }
+}
@@ -10,12 +10,16 @@ use {
             WindowCloseRequestedEvent,
             WindowClosedEvent,
             TextInputEvent,
+            TextPreeditEvent,
             KeyEvent,
             DragEvent,
             DropEvent,
+            DropOutsideEvent,
             TextClipboardEvent,
+            ClipboardReadEvent,
             TimerEvent,
         },
+        monitor::MonitorInfo,
     }
 };
 
@@ -35,12 +39,16 @@ pub enum XlibEvent {
     WindowDragQuery(WindowDragQueryEvent),
     WindowCloseRequested(WindowCloseRequestedEvent),
     TextInput(TextInputEvent),
+    TextPreedit(TextPreeditEvent),
     Drag(DragEvent),
     Drop(DropEvent),
     DragEnd,
+    DropOutside(DropOutsideEvent),
     KeyDown(KeyEvent),
     KeyUp(KeyEvent),
     TextCopy(TextClipboardEvent),
     TextCut(TextClipboardEvent),
+    ClipboardRead(ClipboardReadEvent),
     Timer(TimerEvent),
+    MonitorsChanged(Vec<MonitorInfo>),
 }
@@ -3,14 +3,15 @@ use {
         mem,
         cell::Cell,
         rc::Rc,
-        os::raw::{c_ulong, c_long, c_void, c_char},
+        os::raw::{c_ulong, c_long, c_void, c_char, c_int, c_uint},
         ptr,
-        ffi::{CStr,CString}, 
+        ffi::CStr, 
     },
     self::super::{
         x11_sys,
         xlib_event::XlibEvent,
         xlib_app::*,
+        super::uri_list::*,
     },
     crate::{
         area::Area,
@@ -32,8 +33,14 @@ pub struct XlibWindow {
     pub last_nc_mode: Option<c_long>,
     pub window_id: WindowId,
     pub last_window_geom: WindowGeom,
+    // frame position and pixel size to go back to when leaving fullscreen
+    pub normal_rect: Option<(DVec2, DVec2)>,
     
     pub ime_spot: DVec2,
+    pub ime_active: bool,
+    pub ime_preedit: Vec<char>,
+    pub ime_preedit_cursor: usize,
+    pub has_focus: bool,
     pub current_cursor: MouseCursor,
     pub last_mouse_pos: DVec2,
 }
@@ -60,8 +67,13 @@ impl XlibWindow {
             //child_windows: Vec::new(),
             window_id,
             last_window_geom: WindowGeom::default(),
+            normal_rect: None,
             last_nc_mode: None,
             ime_spot: DVec2::default(),
+            ime_active: false,
+            ime_preedit: Vec::new(),
+            ime_preedit_cursor: 0,
+            has_focus: false,
             current_cursor: MouseCursor::Default,
             last_mouse_pos: DVec2::default(),
         }
@@ -96,6 +108,7 @@ impl XlibWindow {
                     | x11_sys::FocusChangeMask
                     | x11_sys::EnterWindowMask
                     | x11_sys::LeaveWindowMask
+                    | x11_sys::PropertyChangeMask
             ) as c_long;
             
             let dpi_factor = self.get_dpi_factor();
@@ -150,16 +163,7 @@ impl XlibWindow {
             let title_bytes = format!("{}\0", title);
             x11_sys::XStoreName(display, window, title_bytes.as_bytes().as_ptr() as *const c_char);
             
-            let xic = x11_sys::XCreateIC(
-                get_xlib_app_global().xim,
-                x11_sys::XNInputStyle.as_ptr(),
-                (x11_sys::XIMPreeditNothing | x11_sys::XIMStatusNothing) as i32,
-                x11_sys::XNClientWindow.as_ptr(),
-                window,
-                x11_sys::XNFocusWindow.as_ptr(),
-                window,
-                ptr::null_mut() as *mut c_void
-            );
+            let xic = Self::create_ic(window);
             
             // Create a window
             get_xlib_app_global().window_map.insert(window, self);
@@ -167,7 +171,7 @@ impl XlibWindow {
             self.attributes = Some(attributes);
             self.visual_info = Some(visual_info);
             self.window = Some(window);
-            self.xic = Some(xic);
+            self.xic = xic;
             self.last_window_geom = self.get_window_geom();
             
             let new_geom = self.get_window_geom();
@@ -180,7 +184,7 @@ impl XlibWindow {
         }
     }
     
-    fn restore_or_maximize(&self, add_remove: c_long) {
+    fn send_net_wm_state(&self, add_remove: c_long, first: x11_sys::Atom, second: x11_sys::Atom) {
         unsafe {
             let default_screen = x11_sys::XDefaultScreen(get_xlib_app_global().display);
             let root_window = x11_sys::XRootWindow(get_xlib_app_global().display, default_screen);
@@ -195,8 +199,10 @@ impl XlibWindow {
                 data: {
                     let mut msg = mem::zeroed::<x11_sys::XClientMessageEvent__bindgen_ty_1>();
                     msg.l[0] = add_remove;
-                    msg.l[1] = get_xlib_app_global().atoms.new_wm_state_maximized_horz as c_long;
-                    msg.l[2] = get_xlib_app_global().atoms.new_wm_state_maximized_vert as c_long;
+                    msg.l[1] = first as c_long;
+                    msg.l[2] = second as c_long;
+                    // source indication, we are a normal application
+                    msg.l[3] = 1;
                     msg
                 }
             };
@@ -207,9 +213,15 @@ impl XlibWindow {
                 (x11_sys::SubstructureNotifyMask | x11_sys::SubstructureRedirectMask) as c_long,
                 &mut xclient as *mut _ as *mut x11_sys::XEvent
             );
+            x11_sys::XFlush(get_xlib_app_global().display);
         }
     }
     
+    fn restore_or_maximize(&self, add_remove: c_long) {
+        let atoms = &get_xlib_app_global().atoms;
+        self.send_net_wm_state(add_remove, atoms.new_wm_state_maximized_horz, atoms.new_wm_state_maximized_vert);
+    }
+    
     pub fn restore(&self) {
         self.restore_or_maximize(_NET_WM_STATE_REMOVE);
     }
@@ -218,6 +230,42 @@ impl XlibWindow {
         self.restore_or_maximize(_NET_WM_STATE_ADD);
     }
     
+    pub fn fullscreen(&mut self) {
+        let state = self.get_net_wm_state();
+        let atoms = &get_xlib_app_global().atoms;
+        if state.contains(&atoms.net_wm_state_fullscreen) {
+            return
+        }
+        // maximized windows already have their normal geometry remembered by the window manager
+        if !state.contains(&atoms.new_wm_state_maximized_horz) && !state.contains(&atoms.new_wm_state_maximized_vert) {
+            // with a reparenting window manager our position is the offset inside the frame,
+            // and a move request positions the frame, so take the frame origin
+            self.normal_rect = Some((self.get_root_position() - self.get_position(), self.get_outer_size()));
+        }
+        self.send_net_wm_state(_NET_WM_STATE_ADD, atoms.net_wm_state_fullscreen, 0);
+    }
+    
+    /// Leaves fullscreen and maximized, and puts the window back where it was before going fullscreen
+    pub fn normalize(&mut self) {
+        let atoms = &get_xlib_app_global().atoms;
+        self.send_net_wm_state(_NET_WM_STATE_REMOVE, atoms.net_wm_state_fullscreen, 0);
+        self.restore_or_maximize(_NET_WM_STATE_REMOVE);
+        if let Some((pos, size)) = self.normal_rect.take() {
+            unsafe {
+                let display = get_xlib_app_global().display;
+                x11_sys::XMoveResizeWindow(
+                    display,
+                    self.window.unwrap(),
+                    pos.x as c_int,
+                    pos.y as c_int,
+                    size.x.max(1.0) as c_uint,
+                    size.y.max(1.0) as c_uint
+                );
+                x11_sys::XFlush(display);
+            }
+        }
+    }
+    
     pub fn close_window(&mut self) {
         unsafe {
             x11_sys::XDestroyWindow(get_xlib_app_global().display, self.window.unwrap());
@@ -235,19 +283,25 @@ impl XlibWindow {
         }
     }
     
-    pub fn set_topmost(&self, _topmost: bool) {
+    pub fn set_topmost(&self, topmost: bool) {
+        let add_remove = if topmost {_NET_WM_STATE_ADD} else {_NET_WM_STATE_REMOVE};
+        self.send_net_wm_state(add_remove, get_xlib_app_global().atoms.net_wm_state_above, 0);
     }
     
     pub fn get_is_topmost(&self) -> bool {
-        false
+        self.get_net_wm_state().contains(&get_xlib_app_global().atoms.net_wm_state_above)
+    }
+    
+    pub fn get_is_fullscreen(&self) -> bool {
+        self.get_net_wm_state().contains(&get_xlib_app_global().atoms.net_wm_state_fullscreen)
     }
     
     pub fn get_window_geom(&self) -> WindowGeom {
         WindowGeom {
             xr_is_presenting: false,
-            can_fullscreen: false,
+            can_fullscreen: true,
             is_topmost: self.get_is_topmost(),
-            is_fullscreen: self.get_is_maximized(),
+            is_fullscreen: self.get_is_maximized() || self.get_is_fullscreen(),
             inner_size: self.get_inner_size(),
             outer_size: self.get_outer_size(),
             dpi_factor: self.get_dpi_factor(),
@@ -256,7 +310,15 @@ impl XlibWindow {
     }
     
     pub fn get_is_maximized(&self) -> bool {
-        let mut maximized = false;
+        let atoms = &get_xlib_app_global().atoms;
+        self.get_net_wm_state().iter().any( | atom | {
+            *atom == atoms.new_wm_state_maximized_horz || *atom == atoms.new_wm_state_maximized_vert
+        })
+    }
+    
+    /// The atoms currently set in the _NET_WM_STATE property of the window
+    pub fn get_net_wm_state(&self) -> Vec<x11_sys::Atom> {
+        let mut state = Vec::new();
         unsafe {
             let mut prop_type = mem::MaybeUninit::uninit();
             let mut format = mem::MaybeUninit::uninit();
@@ -277,28 +339,172 @@ impl XlibWindow {
                 bytes_after.as_mut_ptr(),
                 properties.as_mut_ptr()
             );
-            //let prop_type = prop_type.assume_init();
-            //let format = format.assume_init();
             let n_item = n_item.assume_init();
-            //let bytes_after = bytes_after.assume_init();
             let properties = properties.assume_init();
             if result == 0 && properties != ptr::null_mut() {
                 let items = std::slice::from_raw_parts::<c_ulong>(properties as *mut _, n_item as usize);
-                for item in items {
-                    if *item == get_xlib_app_global().atoms.new_wm_state_maximized_horz
-                        || *item == get_xlib_app_global().atoms.new_wm_state_maximized_vert {
-                        maximized = true;
-                        break;
-                    }
-                }
+                state.extend_from_slice(items);
                 x11_sys::XFree(properties as *mut _);
             }
         }
-        maximized
+        state
+    }
+    
+    // creates the input context, preferring on-the-spot composition so we can render the preedit text ourselves
+    unsafe fn create_ic(window: c_ulong) -> Option<x11_sys::XIC> {
+        let xim = get_xlib_app_global().xim;
+        if xim.is_null() {
+            return None
+        }
+        let mut styles: *mut x11_sys::XIMStyles = ptr::null_mut();
+        let mut supported = Vec::new();
+        if x11_sys::XGetIMValues(
+            xim,
+            x11_sys::XNQueryInputStyle.as_ptr(),
+            &mut styles as *mut _,
+            ptr::null_mut() as *mut c_void
+        ).is_null() && !styles.is_null() {
+            let styles_ref = &*styles;
+            for i in 0..styles_ref.count_styles as usize {
+                supported.push(*styles_ref.supported_styles.add(i) as u32);
+            }
+            x11_sys::XFree(styles as *mut c_void);
+        }
+        
+        let client_data = window as x11_sys::XPointer;
+        let start = x11_sys::XICCallback {client_data, callback: Some(xim_preedit_start)};
+        let done = x11_sys::XIMCallback {client_data, callback: Some(xim_preedit_done)};
+        let draw = x11_sys::XIMCallback {client_data, callback: Some(xim_preedit_draw)};
+        let caret = x11_sys::XIMCallback {client_data, callback: Some(xim_preedit_caret)};
+        let spot = x11_sys::XPoint {x: 0, y: 0};
+        
+        for preedit in [x11_sys::XIMPreeditCallbacks, x11_sys::XIMPreeditPosition] {
+            let style = preedit | x11_sys::XIMStatusNothing;
+            if !supported.contains(&style) {
+                continue;
+            }
+            let attributes = if preedit == x11_sys::XIMPreeditCallbacks {
+                x11_sys::XVaCreateNestedList(
+                    0,
+                    x11_sys::XNPreeditStartCallback.as_ptr(),
+                    &start as *const _,
+                    x11_sys::XNPreeditDoneCallback.as_ptr(),
+                    &done as *const _,
+                    x11_sys::XNPreeditDrawCallback.as_ptr(),
+                    &draw as *const _,
+                    x11_sys::XNPreeditCaretCallback.as_ptr(),
+                    &caret as *const _,
+                    x11_sys::XNSpotLocation.as_ptr(),
+                    &spot as *const _,
+                    ptr::null_mut() as *mut c_void
+                )
+            }
+            else {
+                x11_sys::XVaCreateNestedList(
+                    0,
+                    x11_sys::XNSpotLocation.as_ptr(),
+                    &spot as *const _,
+                    ptr::null_mut() as *mut c_void
+                )
+            };
+            let xic = x11_sys::XCreateIC(
+                xim,
+                x11_sys::XNInputStyle.as_ptr(),
+                style as c_ulong,
+                x11_sys::XNClientWindow.as_ptr(),
+                window,
+                x11_sys::XNFocusWindow.as_ptr(),
+                window,
+                x11_sys::XNPreeditAttributes.as_ptr(),
+                attributes,
+                ptr::null_mut() as *mut c_void
+            );
+            x11_sys::XFree(attributes);
+            if !xic.is_null() {
+                // the IM only gets our keys while a text input has focus, see set_ime_spot
+                x11_sys::XUnsetICFocus(xic);
+                return Some(xic)
+            }
+        }
+        let xic = x11_sys::XCreateIC(
+            xim,
+            x11_sys::XNInputStyle.as_ptr(),
+            (x11_sys::XIMPreeditNothing | x11_sys::XIMStatusNothing) as c_ulong,
+            x11_sys::XNClientWindow.as_ptr(),
+            window,
+            x11_sys::XNFocusWindow.as_ptr(),
+            window,
+            ptr::null_mut() as *mut c_void
+        );
+        if xic.is_null() {None} else {Some(xic)}
     }
     
     pub fn set_ime_spot(&mut self, spot: DVec2) {
+        if self.ime_active && self.ime_spot == spot {
+            return
+        }
         self.ime_spot = spot;
+        self.ime_active = true;
+        if let Some(xic) = self.xic {
+            unsafe {
+                let dpi_factor = self.get_dpi_factor();
+                let spot = x11_sys::XPoint {
+                    x: (spot.x * dpi_factor) as i16,
+                    y: (spot.y * dpi_factor) as i16
+                };
+                let attributes = x11_sys::XVaCreateNestedList(
+                    0,
+                    x11_sys::XNSpotLocation.as_ptr(),
+                    &spot as *const _,
+                    ptr::null_mut() as *mut c_void
+                );
+                x11_sys::XSetICValues(
+                    xic,
+                    x11_sys::XNPreeditAttributes.as_ptr(),
+                    attributes,
+                    ptr::null_mut() as *mut c_void
+                );
+                x11_sys::XFree(attributes);
+            }
+        }
+        self.update_ic_focus();
+    }
+    
+    pub fn hide_ime(&mut self) {
+        if self.ime_active {
+            self.ime_active = false;
+            self.update_ic_focus();
+            if !self.ime_preedit.is_empty() {
+                self.send_preedit(Vec::new(), 0);
+            }
+        }
+    }
+    
+    pub fn set_focus(&mut self, has_focus: bool) {
+        self.has_focus = has_focus;
+        self.update_ic_focus();
+    }
+    
+    fn update_ic_focus(&mut self) {
+        if let Some(xic) = self.xic {
+            unsafe {
+                if self.ime_active && self.has_focus {
+                    x11_sys::XSetICFocus(xic);
+                }
+                else {
+                    x11_sys::XUnsetICFocus(xic);
+                }
+            }
+        }
+    }
+    
+    fn send_preedit(&mut self, text: Vec<char>, cursor: usize) {
+        self.ime_preedit_cursor = cursor.min(text.len());
+        self.ime_preedit = text;
+        self.do_callback(XlibEvent::TextPreedit(TextPreeditEvent {
+            text: self.ime_preedit.iter().collect(),
+            cursor: self.ime_preedit_cursor
+        }));
     }
     
     pub fn get_position(&self) -> DVec2 {
@@ -323,6 +529,35 @@ impl XlibWindow {
         }
     }
     
+    /// Position of the window contents in root window coordinates, unlike get_position this ignores reparenting
+    pub fn get_root_position(&self) -> DVec2 {
+        unsafe {
+            let display = get_xlib_app_global().display;
+            let default_screen = x11_sys::XDefaultScreen(display);
+            let root_window = x11_sys::XRootWindow(display, default_screen);
+            let mut x = 0;
+            let mut y = 0;
+            let mut child = 0;
+            x11_sys::XTranslateCoordinates(display, self.window.unwrap(), root_window, 0, 0, &mut x, &mut y, &mut child);
+            DVec2 {x: x as f64, y: y as f64}
+        }
+    }
+    
+    /// Converts a position on the root window to logical coordinates inside this window
+    pub fn root_to_local(&self, x_root: c_int, y_root: c_int) -> DVec2 {
+        unsafe {
+            let display = get_xlib_app_global().display;
+            let default_screen = x11_sys::XDefaultScreen(display);
+            let root_window = x11_sys::XRootWindow(display, default_screen);
+            let mut x = 0;
+            let mut y = 0;
+            let mut child = 0;
+            x11_sys::XTranslateCoordinates(display, root_window, self.window.unwrap(), x_root, y_root, &mut x, &mut y, &mut child);
+            let dpi_factor = self.last_window_geom.dpi_factor;
+            DVec2 {x: x as f64 / dpi_factor, y: y as f64 / dpi_factor}
+        }
+    }
+    
     pub fn get_inner_size(&self) -> DVec2 {
         let dpi_factor = self.get_dpi_factor();
         unsafe {
@@ -353,34 +588,13 @@ impl XlibWindow {
     pub fn set_inner_size(&self, _size: DVec2) {
     }
     
+    /// Scale of the monitor the window sits on, or of the primary monitor before the window exists
     pub fn get_dpi_factor(&self) -> f64 {
-        unsafe {
-            //return 2.0;
-            let display = get_xlib_app_global().display;
-            let resource_string = x11_sys::XResourceManagerString(display);
-            if resource_string == std::ptr::null_mut() {
-                return 1.0
-            }
-            let db = x11_sys::XrmGetStringDatabase(resource_string);
-            let mut ty = mem::MaybeUninit::uninit();
-            let mut value = mem::MaybeUninit::uninit();
-            x11_sys::XrmGetResource(
-                db,
-                "Xft.dpi\0".as_ptr() as * const _,
-                "String\0".as_ptr() as * const _,
-                ty.as_mut_ptr(),
-                value.as_mut_ptr()
-            );
-            //let ty = ty.assume_init();
-            let value = value.assume_init();
-            if value.addr == std::ptr::null_mut() {
-                return 1.0; // TODO find some other way to figure it out
-            }
-            else {
-                let dpi: f64 = CStr::from_ptr(value.addr).to_str().unwrap().parse().unwrap();
-                return dpi / 96.0;
-            }
+        let xlib_app = get_xlib_app_global();
+        if self.window.is_none() {
+            return xlib_app.primary_dpi_factor()
         }
+        xlib_app.dpi_factor_for_rect(self.get_root_position(), self.get_outer_size())
     }
     
     pub fn time_now(&self) -> f64 {
@@ -392,12 +606,8 @@ impl XlibWindow {
     }
     
     pub fn send_change_event(&mut self) {
-        
-        let mut new_geom = self.get_window_geom();
-        if new_geom.inner_size.x < self.last_window_geom.inner_size.x ||
-        new_geom.inner_size.y < self.last_window_geom.inner_size.y {
-            new_geom.is_fullscreen = false;
-        }
+        // the fullscreen and maximized flags come from _NET_WM_STATE, which is tracked through update_wm_state
+        let new_geom = self.get_window_geom();
         let old_geom = self.last_window_geom.clone();
         self.last_window_geom = new_geom.clone();
         
@@ -409,6 +619,17 @@ impl XlibWindow {
         self.do_callback(XlibEvent::Paint);
     }
     
+    /// Only sends a change when the geometry moved, for when _NET_WM_STATE or the monitor layout changed
+    pub fn update_window_geom(&mut self) {
+        if self.window.is_none() {
+            return
+        }
+        let new_geom = self.get_window_geom();
+        if new_geom != self.last_window_geom {
+            self.send_change_event();
+        }
+    }
+    
     pub fn send_focus_event(&mut self) {
         self.do_callback(XlibEvent::AppGotFocus);
     }
@@ -473,6 +694,72 @@ impl XlibWindow {
 }
 
 
+unsafe fn xim_window(client_data: x11_sys::XPointer) -> Option<&'static mut XlibWindow> {
+    get_xlib_app_global().window_map.get(&(client_data as c_ulong)).map( | window | &mut **window)
+}
+
+unsafe extern "C" fn xim_preedit_start(_ic: x11_sys::XIC, client_data: x11_sys::XPointer, _call_data: x11_sys::XPointer) -> c_int {
+    if let Some(window) = xim_window(client_data) {
+        window.ime_preedit.clear();
+        window.ime_preedit_cursor = 0;
+    }
+    // no limit on the preedit length
+    -1
+}
+
+unsafe extern "C" fn xim_preedit_done(_ic: x11_sys::XIC, client_data: x11_sys::XPointer, _call_data: x11_sys::XPointer) {
+    if let Some(window) = xim_window(client_data) {
+        window.send_preedit(Vec::new(), 0);
+    }
+}
+
+unsafe extern "C" fn xim_preedit_draw(_ic: x11_sys::XIC, client_data: x11_sys::XPointer, call_data: x11_sys::XPointer) {
+    let window = if let Some(window) = xim_window(client_data) {window} else {return};
+    let draw = &*(call_data as *const x11_sys::XIMPreeditDrawCallbackStruct);
+    // the IM sends the range of chars to replace plus the new chars
+    let mut new_chars = Vec::new();
+    if !draw.text.is_null() {
+        let text = &*draw.text;
+        if text.encoding_is_wchar != 0 {
+            if !text.string.wide_char.is_null() {
+                for i in 0..text.length as usize {
+                    if let Some(c) = char::from_u32(*text.string.wide_char.add(i) as u32) {
+                        new_chars.push(c);
+                    }
+                }
+            }
+        }
+        else if !text.string.multi_byte.is_null() {
+            new_chars.extend(CStr::from_ptr(text.string.multi_byte).to_string_lossy().chars());
+        }
+    }
+    let mut text = window.ime_preedit.clone();
+    let first = (draw.chg_first.max(0) as usize).min(text.len());
+    let end = (first + draw.chg_length.max(0) as usize).min(text.len());
+    text.splice(first..end, new_chars);
+    window.send_preedit(text, draw.caret.max(0) as usize);
+}
+
+unsafe extern "C" fn xim_preedit_caret(_ic: x11_sys::XIC, client_data: x11_sys::XPointer, call_data: x11_sys::XPointer) {
+    let window = if let Some(window) = xim_window(client_data) {window} else {return};
+    let caret = &mut *(call_data as *mut x11_sys::XIMPreeditCaretCallbackStruct);
+    let len = window.ime_preedit.len();
+    let cursor = window.ime_preedit_cursor;
+    let cursor = match caret.direction {
+        x11_sys::XIMForwardChar => (cursor + 1).min(len),
+        x11_sys::XIMBackwardChar => cursor.saturating_sub(1),
+        x11_sys::XIMLineStart => 0,
+        x11_sys::XIMLineEnd => len,
+        x11_sys::XIMAbsolutePosition => (caret.position.max(0) as usize).min(len),
+        _ => cursor
+    };
+    caret.position = cursor as c_int;
+    if cursor != window.ime_preedit_cursor {
+        let text = window.ime_preedit.clone();
+        window.send_preedit(text, cursor);
+    }
+}
+
 #[derive(Clone, Copy, PartialEq)]
 #[repr(C)]
 struct MwmHints {
@@ -510,11 +797,35 @@ pub const _NET_WM_STATE_TOGGLE: c_long = 2;/* toggle property  */
 
 /* move via keyboard */
 
+/// A drag that started in one of our windows, with the data it offers
+pub struct DndSource {
+    pub window: x11_sys::Window,
+    pub uri_list: String,
+    pub text: String,
+    pub types: Vec<x11_sys::Atom>,
+    pub target: Option<(x11_sys::Window, c_long)>,
+    pub accepted: bool,
+    pub waiting_for_status: bool,
+    pub pending_position: Option<(c_int, c_int, x11_sys::Time)>,
+    pub dropped: bool,
+}
+
 pub struct Dnd {
     pub atoms: DndAtoms,
     pub display: *mut x11_sys::Display,
     pub type_list: Option<Vec<x11_sys::Atom >>,
-    pub selection: Option<CString>,
+    // target side, the drag currently hovering one of our windows
+    pub source_window: x11_sys::Window,
+    pub target_window: x11_sys::Window,
+    pub data_type: Option<x11_sys::Atom>,
+    pub data_requested: bool,
+    pub items: Option<Rc<Vec<DragItem>>>,
+    pub drop_pending: bool,
+    pub last_root_pos: (c_int, c_int),
+    pub action: x11_sys::Atom,
+    // source side
+    pub source: Option<DndSource>,
+    pub last_press: (x11_sys::Window, x11_sys::Time),
 }
 
 impl Dnd {
@@ -523,7 +834,16 @@ impl Dnd {
             atoms: DndAtoms::new(display),
             display,
             type_list: None,
-            selection: None,
+            source_window: 0,
+            target_window: 0,
+            data_type: None,
+            data_requested: false,
+            items: None,
+            drop_pending: false,
+            last_root_pos: (0, 0),
+            action: 0,
+            source: None,
+            last_press: (0, 0),
         }
     }
     
@@ -534,7 +854,7 @@ impl Dnd {
         
         // I took this value from the Winit source code. Apparently, this is the latest version, and
         // hasn't changed since 2002.
-        let version = 5 as c_ulong;
+        let version = XDND_VERSION as c_ulong;
         
         x11_sys::XChangeProperty(
             self.display,
@@ -560,29 +880,45 @@ impl Dnd {
         // If the has_more_types flags is set, we have to obtain the list of supported types from
         // the XDndTypeList property. Otherwise, we can obtain the list of supported types from the
         // event itself.
-        self.type_list = Some(if has_more_types {
+        let type_list = if has_more_types {
             self.get_type_list_property(source_window)
         } else {
-            event.data.l[2..4]
+            event.data.l[2..5]
                 .iter()
                 .map( | &l | l as x11_sys::Atom)
                 .filter( | &atom | atom != x11_sys::None as x11_sys::Atom)
                 .collect()
-        });
+        };
+        // we take files over text, and text over nothing
+        self.data_type = [self.atoms.uri_list, self.atoms.utf8_string, self.atoms.text_plain]
+            .into_iter()
+            .find( | atom | type_list.contains(atom));
+        self.type_list = Some(type_list);
+        self.source_window = source_window;
+        self.target_window = event.window;
+        self.data_requested = false;
+        self.items = None;
+        self.drop_pending = false;
     }
     
-    /// Handles a XDndDrop event.
-    pub unsafe fn handle_drop_event(&mut self, event: &x11_sys::XClientMessageEvent) {
-        // The XDndLeave event is sent by the source window when a drag is confirmed. That is, the
+    /// Handles a XDndDrop event, returns true when the data is already there so the drop can be delivered right away.
+    pub unsafe fn handle_drop_event(&mut self, event: &x11_sys::XClientMessageEvent) -> bool {
+        // The XDndDrop event is sent by the source window when a drag is confirmed. That is, the
         // mouse button is released while the mouse is inside the client rectangle of the target
-        // window. The target window is supposed to respond to this by requesting that the selection
-        // representing the thing being dragged is converted to the appropriate data type (in our
-        // case, a URI list). The source window, in turn, is supposed to respond this by sending a
-        // selection event containing the data to the source window.
+        // window. If we didn't get the data while hovering, we request it now and deliver the
+        // drop when the selection event comes in.
         
         let target_window = event.window as x11_sys::Window;
-        self.convert_selection(target_window);
         self.type_list = None;
+        // nothing we can read means nothing to wait for, the drop just gets turned down
+        if self.items.is_some() || self.data_type.is_none() {
+            return true
+        }
+        if !self.data_requested {
+            self.convert_selection(target_window, event.data.l[2] as x11_sys::Time);
+        }
+        self.drop_pending = true;
+        false
     }
     
     /// Handles a XDndLeave event.
@@ -591,53 +927,67 @@ impl Dnd {
         // mouse leaves the client rectangle of the target window. The target window is supposed to
         // repsond this this by pretending the drag never happened.
         
+        self.reset_target();
+    }
+    
+    pub fn reset_target(&mut self) {
         self.type_list = None;
+        self.data_type = None;
+        self.data_requested = false;
+        self.items = None;
+        self.drop_pending = false;
+        self.source_window = 0;
+        self.target_window = 0;
     }
     
-    /// Handles a XDndPosition event.
-    pub unsafe fn handle_position_event(&mut self, event: &x11_sys::XClientMessageEvent) {
+    /// Handles a XDndPosition event, returns the pointer position in root coordinates.
+    pub unsafe fn handle_position_event(&mut self, event: &x11_sys::XClientMessageEvent) -> (c_int, c_int) {
         // The XDndPosition event is sent by the source window after the XDndEnter event, every time
         // the mouse is moved. The target window is supposed to respond to this by sending a status
-        // event to the source window notifying whether it can accept the drag at this position.
+        // event to the source window, which happens once the app had a look at the drag.
         
         let target_window = event.window as x11_sys::Window;
-        let source_window = event.data.l[0] as x11_sys::Window;
-        
-        // For now we accept te drag if and only if the list of types supported by the source
-        // includes a uri list.
-        //
-        // TODO: Extend this test by taking into account the position of the mouse as well.
-        let accepted = self.type_list.as_ref().map_or(false, | type_list | type_list.contains(&self.atoms.uri_list));
-        
-        // Notify the source window whether we can accept the drag at this position.
-        self.send_status_event(source_window, target_window, accepted);
-        
-        // If this is the first time we've accepted the drag, request that the drag-and-drop
-        // selection be converted to a URI list. The target window is supposed to respond to this by
-        // sending a XSelectionEvent containing the URI list.
+        self.source_window = event.data.l[0] as x11_sys::Window;
+        self.target_window = target_window;
+        let packed = event.data.l[2];
+        self.last_root_pos = (((packed >> 16) & 0xffff) as i16 as c_int, (packed & 0xffff) as i16 as c_int);
         
-        // Since this is an asynchronous operation, its possible for another XDndPosition event to
-        // come in before the response to the first conversion request has been received. In this
-        // case, a second conversion request will be sent, the response to which will be ignored.
-        if accepted && self.selection.is_none() {
+        // Request the data on the first position, so the drag events can carry the items. The
+        // answer comes in asynchronously as a XSelectionEvent.
+        if self.data_type.is_some() && !self.data_requested {
+            self.convert_selection(target_window, event.data.l[3] as x11_sys::Time);
         }
+        self.last_root_pos
     }
     
-    /// Handles a XSelectionEvent.
-    pub unsafe fn handle_selection_event(&mut self, _event: &x11_sys::XSelectionEvent) {
-        // The XSelectionEvent is sent by the source window in response to a request by the source
-        // window to convert the selection representing the thing being dragged to the appropriate
-        // data type. This request is always sent in response to a XDndDrop event, so this event
-        // should only be received after a drop operation has completed.
+    /// Handles a XSelectionEvent for the drag data, returns true when a drop was waiting on it.
+    pub unsafe fn handle_selection_event(&mut self, event: &x11_sys::XSelectionEvent) -> bool {
+        // The XSelectionEvent is sent by the X server in response to our conversion request, the
+        // data is in the XdndSelection property of our window. A property of None means the
+        // source couldn't convert.
         
-        //let source_window = event.requestor;
-        //let selection = CString::new(self.get_selection_property(source_window)).unwrap();
-        
-        // TODO: Actually use the selection
+        let Some(data_type) = self.data_type else {
+            return false
+        };
+        let items = if event.property == x11_sys::None as x11_sys::Atom {
+            Vec::new()
+        }
+        else {
+            let data = self.get_selection_property(event.requestor, data_type);
+            let data = String::from_utf8_lossy(&data);
+            if data_type == self.atoms.uri_list {
+                uri_list_to_drag_items(&data)
+            }
+            else {
+                vec![DragItem::String {value: data.to_string(), internal_id: None}]
+            }
+        };
+        self.items = Some(Rc::new(items));
+        self.drop_pending
     }
     
-    /// Gets the XDndSelection property from the source window.
-    pub unsafe fn get_selection_property(&mut self, source_window: x11_sys::Window) -> Vec< std::os::raw::c_uchar> {
+    /// Gets the XDndSelection property from the given window.
+    pub unsafe fn get_selection_property(&mut self, window: x11_sys::Window, data_type: x11_sys::Atom) -> Vec< std::os::raw::c_uchar> {
         let mut selection = Vec::new();
         let mut offset = 0;
         let length = 1024;
@@ -649,18 +999,21 @@ impl Dnd {
         loop {
             x11_sys::XGetWindowProperty(
                 self.display,
-                source_window,
+                window,
                 self.atoms.selection,
                 offset,
                 length,
                 x11_sys::False as std::os::raw::c_int,
-                self.atoms.uri_list,
+                data_type,
                 &mut actual_type,
                 &mut actual_format,
                 &mut nitems,
                 &mut bytes_after,
                 &mut prop,
             );
+            if prop.is_null() {
+                break;
+            }
             selection.extend_from_slice(std::slice::from_raw_parts(prop as *mut  std::os::raw::c_uchar, nitems as usize));
             x11_sys::XFree(prop as *mut c_void);
             if bytes_after == 0 {
@@ -696,6 +1049,9 @@ impl Dnd {
                 &mut bytes_after,
                 &mut prop,
             );
+            if prop.is_null() {
+                break;
+            }
             type_list.extend_from_slice(std::slice::from_raw_parts(prop as *mut x11_sys::Atom, nitems as usize));
             x11_sys::XFree(prop as *mut c_void);
             if bytes_after == 0 {
@@ -706,54 +1062,373 @@ impl Dnd {
         type_list
     }
     
-    /// Sends a XDndStatus event to the target window.
-    pub unsafe fn send_status_event(&mut self, source_window: x11_sys::Window, target_window: x11_sys::Window, accepted: bool) {
+    /// Maps the answer of the app to the XDND action it stands for.
+    pub fn response_to_action(&self, response: DragResponse) -> Option<x11_sys::Atom> {
+        match response {
+            DragResponse::None => None,
+            DragResponse::Copy => Some(self.atoms.action_copy),
+            DragResponse::Link => Some(self.atoms.action_link),
+            DragResponse::Move => Some(self.atoms.action_move),
+        }
+    }
+    
+    /// Sends a XDndStatus event to the source window.
+    pub unsafe fn send_status_event(&mut self, source_window: x11_sys::Window, target_window: x11_sys::Window, action: Option<x11_sys::Atom>) {
+        // without data there is nothing to drop, whatever the app thinks
+        let action = action.filter( | _ | self.data_type.is_some());
+        self.action = action.unwrap_or(self.atoms.none);
+        self.send_client_message(source_window, self.atoms.status, [
+            target_window as c_long,
+            // bit 1 asks for position updates even when the pointer doesn't leave a rectangle
+            if action.is_some() {(1 << 0) | (1 << 1)} else {1 << 1},
+            0,
+            0,
+            self.action as c_long,
+        ]);
+    }
+    
+    /// Sends a XDndFinished event to the source window, after a drop was handled.
+    pub unsafe fn send_finished_event(&mut self) {
+        let accepted = self.action != self.atoms.none && self.action != 0;
+        self.send_client_message(self.source_window, self.atoms.finished, [
+            self.target_window as c_long,
+            if accepted {1 << 0} else {0},
+            if accepted {self.action as c_long} else {0},
+            0,
+            0,
+        ]);
+        self.reset_target();
+    }
+    
+    // Requests that the selection representing the thing being dragged is converted to the
+    // data type we picked when the drag entered.
+    pub unsafe fn convert_selection(&mut self, target_window: x11_sys::Window, time: x11_sys::Time) {
+        let Some(data_type) = self.data_type else {
+            return
+        };
+        self.data_requested = true;
+        x11_sys::XConvertSelection(
+            self.display,
+            self.atoms.selection,
+            data_type,
+            self.atoms.selection,
+            target_window,
+            time,
+        );
+        x11_sys::XFlush(self.display);
+    }
+    
+    unsafe fn send_client_message(&self, window: x11_sys::Window, message_type: x11_sys::Atom, data: [c_long; 5]) {
+        let mut xclient = x11_sys::XClientMessageEvent {
+            type_: x11_sys::ClientMessage as  std::os::raw::c_int,
+            serial: 0,
+            send_event: 0,
+            display: self.display,
+            window,
+            message_type,
+            format: 32,
+            data: {
+                let mut msg = mem::zeroed::<x11_sys::XClientMessageEvent__bindgen_ty_1>();
+                msg.l = data;
+                msg
+            }
+        };
         x11_sys::XSendEvent(
             self.display,
-            source_window,
+            window,
             x11_sys::False as  std::os::raw::c_int,
             x11_sys::NoEventMask as  std::os::raw::c_long,
-            &mut x11_sys::XClientMessageEvent {
-                type_: x11_sys::ClientMessage as  std::os::raw::c_int,
-                serial: 0,
-                send_event: 0,
-                display: self.display,
-                window: source_window,
-                message_type: self.atoms.status,
-                format: 32,
-                data: {
-                    let mut data = mem::zeroed::<x11_sys::XClientMessageEvent__bindgen_ty_1>();
-                    data.l[0] = target_window as c_long;
-                    data.l[1] = if accepted {1 << 0} else {0};
-                    data.l[2] = 0;
-                    data.l[3] = 0;
-                    data.l[4] = if accepted {self.atoms.action_private} else {self.atoms.none} as c_long;
-                    data
-                }
-            } as *mut x11_sys::XClientMessageEvent as *mut x11_sys::XEvent
+            &mut xclient as *mut x11_sys::XClientMessageEvent as *mut x11_sys::XEvent
         );
         x11_sys::XFlush(self.display);
     }
     
-    // Requests that the selection representing the thing being dragged is converted to the
-    // appropriate data type (in our case, a URI list).
-    pub unsafe fn convert_selection(&self, target_window: x11_sys::Window) {
-        x11_sys::XConvertSelection(
+    // Source side
+    
+    /// Starts dragging the items out of our window, from here on the pointer belongs to the drag.
+    pub unsafe fn start_drag(&mut self, items: &[DragItem]) -> bool {
+        let (window, time) = self.last_press;
+        if window == 0 {
+            return false
+        }
+        let uri_list = drag_items_to_uri_list(items);
+        let text = drag_items_to_text(items);
+        let mut types = Vec::new();
+        if !uri_list.is_empty() {
+            types.push(self.atoms.uri_list);
+        }
+        if !text.is_empty() {
+            types.push(self.atoms.utf8_string);
+            types.push(self.atoms.text_plain);
+        }
+        if types.is_empty() {
+            return false
+        }
+        let grab = x11_sys::XGrabPointer(
             self.display,
-            self.atoms.selection,
-            self.atoms.uri_list,
-            self.atoms.selection,
-            target_window,
-            x11_sys::CurrentTime as x11_sys::Time,
+            window,
+            x11_sys::False as c_int,
+            (x11_sys::ButtonReleaseMask | x11_sys::PointerMotionMask) as c_uint,
+            x11_sys::GrabModeAsync as c_int,
+            x11_sys::GrabModeAsync as c_int,
+            0,
+            0,
+            time
         );
+        if grab != x11_sys::GrabSuccess as c_int {
+            return false
+        }
+        x11_sys::XSetSelectionOwner(self.display, self.atoms.selection, window, time);
+        x11_sys::XChangeProperty(
+            self.display,
+            window,
+            self.atoms.type_list,
+            4, // XA_ATOM
+            32,
+            x11_sys::PropModeReplace as c_int,
+            types.as_ptr() as *const std::os::raw::c_uchar,
+            types.len() as c_int
+        );
+        x11_sys::XFlush(self.display);
+        self.source = Some(DndSource {
+            window,
+            uri_list,
+            text,
+            types,
+            target: None,
+            accepted: false,
+            waiting_for_status: false,
+            pending_position: None,
+            dropped: false,
+        });
+        true
+    }
+    
+    /// Follows the pointer while dragging, telling the windows underneath about the drag.
+    pub unsafe fn source_motion(&mut self, x_root: c_int, y_root: c_int, time: x11_sys::Time) {
+        let target = self.find_target_window(x_root, y_root);
+        let Some(source) = &mut self.source else {
+            return
+        };
+        let source_window = source.window;
+        if source.target.map(| (window, _) | window) != target.map(| (window, _) | window) {
+            if let Some((old_target, _)) = source.target.take() {
+                self.send_client_message(old_target, self.atoms.leave, [source_window as c_long, 0, 0, 0, 0]);
+            }
+            let source = self.source.as_mut().unwrap();
+            source.accepted = false;
+            source.waiting_for_status = false;
+            source.pending_position = None;
+            if let Some((target_window, version)) = target {
+                source.target = Some((target_window, version));
+                let types = source.types.clone();
+                self.send_client_message(target_window, self.atoms.enter, [
+                    source_window as c_long,
+                    (version << 24) | if types.len() > 3 {1} else {0},
+                    types.first().copied().unwrap_or(0) as c_long,
+                    types.get(1).copied().unwrap_or(0) as c_long,
+                    types.get(2).copied().unwrap_or(0) as c_long,
+                ]);
+            }
+        }
+        let source = self.source.as_mut().unwrap();
+        if source.target.is_none() {
+            return
+        }
+        // one position in flight at a time, the latest one goes out when the status comes back
+        if source.waiting_for_status {
+            source.pending_position = Some((x_root, y_root, time));
+            return
+        }
+        self.send_position(x_root, y_root, time);
+    }
+    
+    unsafe fn send_position(&mut self, x_root: c_int, y_root: c_int, time: x11_sys::Time) {
+        let Some(source) = &mut self.source else {
+            return
+        };
+        let Some((target_window, _)) = source.target else {
+            return
+        };
+        source.waiting_for_status = true;
+        let source_window = source.window;
+        self.send_client_message(target_window, self.atoms.position, [
+            source_window as c_long,
+            0,
+            ((x_root as c_long & 0xffff) << 16) | (y_root as c_long & 0xffff),
+            time as c_long,
+            self.atoms.action_copy as c_long,
+        ]);
+    }
+    
+    /// Handles a XdndStatus event from the window we are dragging over.
+    pub unsafe fn handle_status_event(&mut self, event: &x11_sys::XClientMessageEvent) {
+        let Some(source) = &mut self.source else {
+            return
+        };
+        if source.target.map(| (window, _) | window) != Some(event.data.l[0] as x11_sys::Window) {
+            return
+        }
+        source.accepted = event.data.l[1] & (1 << 0) != 0;
+        source.waiting_for_status = false;
+        if let Some((x_root, y_root, time)) = source.pending_position.take() {
+            self.send_position(x_root, y_root, time);
+        }
+    }
+    
+    /// The button went up, returns true when the drag is over right away instead of waiting for XdndFinished.
+    pub unsafe fn source_release(&mut self, time: x11_sys::Time) -> bool {
+        x11_sys::XUngrabPointer(self.display, time);
+        let Some(source) = &mut self.source else {
+            return false
+        };
+        let source_window = source.window;
+        match source.target {
+            Some((target_window, _)) if source.accepted => {
+                source.dropped = true;
+                self.send_client_message(target_window, self.atoms.drop, [source_window as c_long, 0, time as c_long, 0, 0]);
+                false
+            }
+            Some((target_window, _)) => {
+                self.send_client_message(target_window, self.atoms.leave, [source_window as c_long, 0, 0, 0, 0]);
+                self.source = None;
+                true
+            }
+            None => {
+                self.source = None;
+                true
+            }
+        }
+    }
+    
+    /// Aborts the drag, for instance on escape.
+    pub unsafe fn cancel_drag(&mut self) {
+        x11_sys::XUngrabPointer(self.display, x11_sys::CurrentTime as x11_sys::Time);
+        if let Some(source) = self.source.take() {
+            if let Some((target_window, _)) = source.target {
+                if !source.dropped {
+                    self.send_client_message(target_window, self.atoms.leave, [source.window as c_long, 0, 0, 0, 0]);
+                }
+            }
+        }
+    }
+    
+    /// Handles a XdndFinished event, the target is done with our data.
+    pub fn handle_finished_event(&mut self, _event: &x11_sys::XClientMessageEvent) -> bool {
+        self.source.take().is_some()
+    }
+    
+    /// Serves the data of our drag to the target, returns false when the request is not about the drag.
+    pub unsafe fn handle_selection_request(&mut self, request: &x11_sys::XSelectionRequestEvent) -> bool {
+        if request.selection != self.atoms.selection {
+            return false
+        }
+        let mut response = x11_sys::XSelectionEvent {
+            type_: x11_sys::SelectionNotify as i32,
+            serial: 0,
+            send_event: 0,
+            display: self.display,
+            requestor: request.requestor,
+            selection: request.selection,
+            target: request.target,
+            time: request.time,
+            property: request.property,
+        };
+        let data = self.source.as_ref().and_then( | source | {
+            if request.target == self.atoms.uri_list && !source.uri_list.is_empty() {
+                Some(source.uri_list.as_bytes())
+            }
+            else if (request.target == self.atoms.utf8_string || request.target == self.atoms.text_plain) && !source.text.is_empty() {
+                Some(source.text.as_bytes())
+            }
+            else {
+                None
+            }
+        });
+        if let Some(data) = data {
+            x11_sys::XChangeProperty(
+                self.display,
+                request.requestor,
+                request.property,
+                request.target,
+                8,
+                x11_sys::PropModeReplace as c_int,
+                data.as_ptr(),
+                data.len() as c_int
+            );
+        }
+        else {
+            response.property = 0;
+        }
+        x11_sys::XSendEvent(self.display, request.requestor, 1, 0, &mut response as *mut _ as *mut x11_sys::XEvent);
+        x11_sys::XFlush(self.display);
+        true
+    }
+    
+    /// Finds the XDND aware window under the pointer and the protocol version to talk to it.
+    unsafe fn find_target_window(&self, x_root: c_int, y_root: c_int) -> Option<(x11_sys::Window, c_long)> {
+        let default_screen = x11_sys::XDefaultScreen(self.display);
+        let root_window = x11_sys::XRootWindow(self.display, default_screen);
+        let mut window = root_window;
+        // walk down from the root through the window manager frames to the client window
+        loop {
+            if window != root_window {
+                if let Some(version) = self.get_aware_version(window) {
+                    return Some((window, version.min(XDND_VERSION)))
+                }
+            }
+            let mut x = 0;
+            let mut y = 0;
+            let mut child = 0;
+            x11_sys::XTranslateCoordinates(self.display, root_window, window, x_root, y_root, &mut x, &mut y, &mut child);
+            if child == 0 {
+                return None
+            }
+            window = child;
+        }
+    }
+    
+    unsafe fn get_aware_version(&self, window: x11_sys::Window) -> Option<c_long> {
+        let mut actual_type = 0;
+        let mut actual_format = 0;
+        let mut nitems = 0;
+        let mut bytes_after = 0;
+        let mut prop = ptr::null_mut();
+        x11_sys::XGetWindowProperty(
+            self.display,
+            window,
+            self.atoms.aware,
+            0,
+            1,
+            x11_sys::False as c_int,
+            4, // XA_ATOM
+            &mut actual_type,
+            &mut actual_format,
+            &mut nitems,
+            &mut bytes_after,
+            &mut prop,
+        );
+        if prop.is_null() {
+            return None
+        }
+        let version = if nitems > 0 {Some(*(prop as *const c_ulong) as c_long)} else {None};
+        x11_sys::XFree(prop as *mut c_void);
+        version
     }
 }
 
+// the XDND protocol version we speak
+const XDND_VERSION: c_long = 5;
+
 pub struct DndAtoms {
+    pub action_copy: x11_sys::Atom,
+    pub action_link: x11_sys::Atom,
+    pub action_move: x11_sys::Atom,
     pub action_private: x11_sys::Atom,
     pub aware: x11_sys::Atom,
     pub drop: x11_sys::Atom,
     pub enter: x11_sys::Atom,
+    pub finished: x11_sys::Atom,
     pub leave: x11_sys::Atom,
     pub none: x11_sys::Atom,
     pub position: x11_sys::Atom,
@@ -761,15 +1436,21 @@ pub struct DndAtoms {
     pub status: x11_sys::Atom,
     pub type_list: x11_sys::Atom,
     pub uri_list: x11_sys::Atom,
+    pub utf8_string: x11_sys::Atom,
+    pub text_plain: x11_sys::Atom,
 }
 
 impl DndAtoms {
     pub unsafe fn new(display: *mut x11_sys::Display) -> DndAtoms {
         DndAtoms {
+            action_copy: x11_sys::XInternAtom(display, "XdndActionCopy\0".as_ptr() as *const _, 0),
+            action_link: x11_sys::XInternAtom(display, "XdndActionLink\0".as_ptr() as *const _, 0),
+            action_move: x11_sys::XInternAtom(display, "XdndActionMove\0".as_ptr() as *const _, 0),
             action_private: x11_sys::XInternAtom(display, "XdndActionPrivate\0".as_ptr() as *const _, 0),
             aware: x11_sys::XInternAtom(display, "XdndAware\0".as_ptr() as *const _, 0),
             drop: x11_sys::XInternAtom(display, "XdndDrop\0".as_ptr() as *const _, 0),
             enter: x11_sys::XInternAtom(display, "XdndEnter\0".as_ptr() as *const _, 0),
+            finished: x11_sys::XInternAtom(display, "XdndFinished\0".as_ptr() as *const _, 0),
             leave: x11_sys::XInternAtom(display, "XdndLeave\0".as_ptr() as *const _, 0),
             none: x11_sys::XInternAtom(display, "None\0".as_ptr() as *const _, 0),
             position: x11_sys::XInternAtom(display, "XdndPosition\0".as_ptr() as *const _, 0),
@@ -777,6 +1458,8 @@ impl DndAtoms {
             status: x11_sys::XInternAtom(display, "XdndStatus\0".as_ptr() as *const _, 0),
             type_list: x11_sys::XInternAtom(display, "XdndTypeList\0".as_ptr() as *const _, 0),
             uri_list: x11_sys::XInternAtom(display, "text/uri-list\0".as_ptr() as *const _, 0),
+            utf8_string: x11_sys::XInternAtom(display, "UTF8_STRING\0".as_ptr() as *const _, 0),
+            text_plain: x11_sys::XInternAtom(display, "text/plain\0".as_ptr() as *const _, 0),
         }
     }
 }
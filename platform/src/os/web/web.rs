@@ -23,6 +23,7 @@ use {
             Event,
             XRInput,
             TextClipboardEvent,
+            ClipboardReadEvent,
             TimerEvent,
             MouseDownEvent,
             MouseMoveEvent,
@@ -436,6 +437,11 @@ impl Cx {
                 },
                 CxOsOp::ShowClipboardActions(_) =>{
                 }
+                CxOsOp::CopyToClipboard(_, _) =>{
+                }
+                CxOsOp::ReadClipboard {request_id, selection, ..} => {
+                    self.call_event_handler(&Event::ClipboardRead(ClipboardReadEvent {request_id, selection, item: None}));
+                }
                 CxOsOp::SetCursor(cursor) => {
                     self.os.from_wasm(FromWasmSetMouseCursor::new(cursor));
                 },
@@ -11,6 +11,7 @@ use {
     crate::{
         log,
         error,
+        makepad_math::DVec2,
         windows::{
             core::HRESULT,
             core::PCWSTR,
@@ -50,6 +51,7 @@ use {
                         CS_VREDRAW,
                         CS_OWNDC,
                         IDI_WINLOGO,
+                        GetWindowRect,
                     },
                     HiDpi::{
                         PROCESS_DPI_AWARENESS,
@@ -74,6 +76,8 @@ use {
                     COLORREF,
                     S_OK,
                     HWND,
+                    POINT,
+                    RECT,
                     BOOL,
                     FARPROC,
                     DRAGDROP_S_DROP,
@@ -144,6 +148,12 @@ P1: IntoParam<IDropSource>,
     DoDragDrop(pdataobj.into_param().abi(), pdropsource.into_param().abi(), dwokeffects, pdweffect)
 }
 
+#[allow(non_snake_case)]
+unsafe fn GetCursorPos(lppoint: *mut POINT) -> BOOL {
+    ::windows_targets::link!("user32.dll" "system" fn GetCursorPos(lppoint: *mut POINT) -> BOOL);
+    GetCursorPos(lppoint)
+}
+
 pub struct Win32App {
     pub time_start: i64,
     pub time_freq: i64,
@@ -419,12 +429,20 @@ impl Win32App {
                         
                         get_win32_app_global().is_dragging_internal.replace(true);
                         let mut effect = DROPEFFECT(0);
-                        match unsafe {DoDragDrop(&data_object, &drop_source, DROPEFFECT_COPY | DROPEFFECT_MOVE, &mut effect)} {
+                        let result = unsafe {DoDragDrop(&data_object, &drop_source, DROPEFFECT_COPY | DROPEFFECT_MOVE, &mut effect)};
+                        match result {
                             DRAGDROP_S_DROP => {/*log!("DoDragDrop: succesful")*/},
                             DRAGDROP_S_CANCEL => {/*log!("DoDragDrop: canceled")*/},
                             _ => {log!("DoDragDrop: failed for some reason")},
                         }
                         get_win32_app_global().is_dragging_internal.replace(false);
+                        
+                        // nobody took the drag, tell the app when it ended outside of our windows
+                        if result == DRAGDROP_S_CANCEL || (result == DRAGDROP_S_DROP && effect == DROPEFFECT(0)) {
+                            if let Some(position) = Self::cursor_outside_windows() {
+                                Win32App::do_callback(Win32Event::DropOutside(DropOutsideEvent {position}));
+                            }
+                        }
                     }
                 },
                 _ => {
@@ -443,6 +461,21 @@ impl Win32App {
         }
     }
     
+    fn cursor_outside_windows() -> Option<DVec2> {
+        let mut point = POINT {x: 0, y: 0};
+        if unsafe {GetCursorPos(&mut point)} == FALSE {
+            return None
+        }
+        for hwnd in get_win32_app_global().all_windows.iter() {
+            let mut rect = RECT {left: 0, top: 0, bottom: 0, right: 0};
+            if unsafe {GetWindowRect(*hwnd, &mut rect)}.is_ok()
+                && point.x >= rect.left && point.x < rect.right && point.y >= rect.top && point.y < rect.bottom {
+                return None
+            }
+        }
+        Some(DVec2 {x: point.x as f64, y: point.y as f64})
+    }
+    
     pub fn start_signal_poll(&mut self) {
         let slot = self.get_free_timer_slot();
         let win32_id = unsafe {SetTimer(None, 0, 8 as u32, Some(Self::timer_proc))};
@@ -15,6 +15,7 @@ use {
             KeyEvent,
             DragEvent,
             DropEvent,
+            DropOutsideEvent,
             TextClipboardEvent,
             TimerEvent,
         },
@@ -42,6 +43,7 @@ pub enum Win32Event {
     TextInput(TextInputEvent),
     Drag(DragEvent),
     Drop(DropEvent),
+    DropOutside(DropOutsideEvent),
     DragEnd,
     KeyDown(KeyEvent),
     KeyUp(KeyEvent),
@@ -210,6 +210,9 @@ impl Cx {
                 self.call_event_handler(&Event::Drop(e));
                 self.drag_drop.cycle_drag();
             },
+            Win32Event::DropOutside(e) => {
+                self.call_event_handler(&Event::DropOutside(e));
+            }
             Win32Event::DragEnd => {
                 // send MouseUp
                 self.call_event_handler(&Event::MouseUp(MouseUpEvent {
@@ -340,6 +343,11 @@ impl Cx {
                 }
                 CxOsOp::ShowClipboardActions(_) => {
                 }
+                CxOsOp::CopyToClipboard(_, _) => {
+                }
+                CxOsOp::ReadClipboard {request_id, selection, ..} => {
+                    self.call_event_handler(&Event::ClipboardRead(ClipboardReadEvent {request_id, selection, item: None}));
+                }
                 CxOsOp::XrStartPresenting => {
                     //todo!()
                 },
@@ -65,6 +65,7 @@ impl LiveNew for WindowHandle {
         cxwindow.create_title = "Makepad".to_string();
         cxwindow.create_inner_size = None;
         cxwindow.create_position = None;
+        cxwindow.owner_window = None;
         cx.platform_ops.push(CxOsOp::CreateWindow(window.window_id()));
         window
     }
@@ -129,6 +130,11 @@ impl WindowHandle {
         cx.passes[pass.pass_id()].parent = CxPassParent::Window(self.window_id());
     }
     
+    /// Marks this window as belonging to `owner`, events for it are then let through by the owning window
+    pub fn set_owner(&self, cx: &mut Cx, owner: WindowId) {
+        cx.windows[self.window_id()].owner_window = Some(owner);
+    }
+    
     pub fn get_inner_size(&mut self, cx: &mut Cx) -> DVec2 {
         cx.windows[self.window_id()].get_inner_size()
     }
@@ -191,6 +197,7 @@ pub struct CxWindow {
     pub is_created: bool,
     pub window_geom: WindowGeom,
     pub main_pass_id: Option<PassId>,
+    pub owner_window: Option<WindowId>,
 }
 
 impl CxWindow {
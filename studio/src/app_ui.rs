@@ -10,6 +10,7 @@ live_design!{
     import makepad_studio::log_list::LogList;
     import makepad_studio::run_list::RunList;
     import makepad_studio::profiler::Profiler;
+    import makepad_widgets::designer::Designer;
     
     ICO_SEARCH = dep("crate://self/resources/icons/Icon_Search.svg")
 
@@ -123,7 +124,7 @@ live_design!{
             }
                 
             run_tabs = Tabs {
-                tabs: [run_first],
+                tabs: [run_first, design],
                 selected: 0
             }
                 
@@ -145,6 +146,12 @@ live_design!{
                 kind: RunFirst
             }
                 
+            design = Tab {
+                name: "Design"
+                closable: false,
+                kind: Designer
+            }
+                
             edit_first = Tab {
                 name: "Edit"
                 closable: false,
@@ -268,6 +275,7 @@ live_design!{
             StudioFileTree = <StudioFileTree> {}
             LogList = <LogList> {}
             Profiler = <Profiler> {}
+            Designer = <Designer> {file_name: "studio/src/app_ui.rs"}
         }}
     }
 }
@@ -417,8 +417,12 @@ pub fn build(sdk_dir: &Path, host_os: HostOs, package_name: Option<String>, app_
     let build_crate = get_build_crate_from_args(args)?;
     let underscore_build_crate = build_crate.replace('-', "_");
 
-    let java_url = package_name.unwrap_or_else(|| format!("dev.makepad.{underscore_build_crate}"));
-    let app_label = app_label.unwrap_or_else(|| format!("{underscore_build_crate}"));
+    let java_url = package_name
+        .or_else(|| get_crate_metadata(build_crate, "makepad-android-package-name"))
+        .unwrap_or_else(|| format!("dev.makepad.{underscore_build_crate}"));
+    let app_label = app_label
+        .or_else(|| get_crate_metadata(build_crate, "makepad-android-app-label"))
+        .unwrap_or_else(|| format!("{underscore_build_crate}"));
 
     rust_build(sdk_dir, host_os, args, android_targets)?;
     let build_paths = prepare_build(build_crate, &java_url, &app_label)?;
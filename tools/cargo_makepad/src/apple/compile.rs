@@ -211,7 +211,7 @@ pub fn build(org: &str, product: &str, args: &[String], apple_target: AppleTarge
 
 pub fn run_on_sim(apple_args: AppleArgs, args: &[String], apple_target: AppleTarget) -> Result<(), String> {
     if apple_args.org.is_none() || apple_args.app.is_none() {
-        return Err("Please set --org=org --app=app on the commandline inbetween ios and run-sim.".to_string());
+        return Err("Please set --org=org --app=app on the commandline inbetween ios and run-sim, or makepad-apple-org and makepad-apple-app in the package metadata.".to_string());
     }
     
     let result = build(&apple_args.org.unwrap_or("orgname".to_string()), &apple_args.app.unwrap_or("productname".to_string()), args, apple_target) ?;
@@ -455,7 +455,7 @@ pub struct AppleArgs {
 pub fn run_on_device(apple_args: AppleArgs, args: &[String], apple_target: AppleTarget) -> Result<(), String> {
     
     if apple_args.org.is_none() || apple_args.app.is_none() {
-        return Err("Please set --org=org --app=app on the commandline inbetween ios and run-device, these are the product name and organisation name from the xcode app you deployed to create the keys.".to_string());
+        return Err("Please set --org=org --app=app on the commandline inbetween ios and run-device (or makepad-apple-org and makepad-apple-app in the package metadata), these are the product name and organisation name from the xcode app you deployed to create the keys.".to_string());
     }
     let org = apple_args.org.unwrap();
     let app = apple_args.app.unwrap();
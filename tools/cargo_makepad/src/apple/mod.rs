@@ -1,6 +1,7 @@
 mod compile;
 mod sdk;
 use compile::*;
+use crate::utils::*;
 
 #[allow(dead_code)]
 #[allow(non_camel_case_types)]
@@ -122,6 +123,16 @@ pub fn handle_apple(args: &[String]) -> Result<(), String> {
         }
     }
     
+    // --org and --app default to the values in the package metadata of the crate
+    if let Ok(build_crate) = get_build_crate_from_args(&args[1..]) {
+        if org.is_none() {
+            org = get_crate_metadata(build_crate, "makepad-apple-org");
+        }
+        if app.is_none() {
+            app = get_crate_metadata(build_crate, "makepad-apple-app");
+        }
+    }
+    
     match args[0].as_ref() {
         "toolchain-install" | "install-toolchain"=>{
             let toolchains = vec![AppleTarget::sim_target(apple_os), AppleTarget::device_target(apple_os)];
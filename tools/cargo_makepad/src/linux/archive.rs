@@ -0,0 +1,146 @@
+// Minimal tar, gzip and ar writers, enough to assemble a .deb without external tools
+
+use std::{
+    fs,
+    path::Path,
+    time::SystemTime,
+};
+use makepad_miniz::compress_to_vec;
+
+fn mtime() -> u64 {
+    SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).map(| d | d.as_secs()).unwrap_or(0)
+}
+
+fn write_octal(field: &mut [u8], value: u64) {
+    // octal digits zero padded, terminated by a nul
+    let digits = format!("{:0width$o}", value, width = field.len() - 1);
+    field[..digits.len()].copy_from_slice(digits.as_bytes());
+}
+
+pub struct TarBuilder {
+    data: Vec<u8>,
+    mtime: u64,
+}
+
+impl TarBuilder {
+    pub fn new() -> Self {
+        Self {
+            data: Vec::new(),
+            mtime: mtime(),
+        }
+    }
+
+    fn add_entry(&mut self, path: &str, mode: u32, type_flag: u8, data: &[u8]) -> Result<(), String> {
+        let mut header = [0u8; 512];
+        // ustar splits long paths into a prefix and a name at a slash
+        let (prefix, name) = if path.len() <= 100 {
+            ("", path)
+        }
+        else {
+            let split = path[..path.len().min(156)].rfind('/')
+                .filter( | split | path.len() - split - 1 <= 100)
+                .ok_or_else( | | format!("Path too long for tar {}", path)) ?;
+            (&path[..split], &path[split + 1..])
+        };
+        header[0..name.len()].copy_from_slice(name.as_bytes());
+        write_octal(&mut header[100..108], mode as u64);
+        write_octal(&mut header[108..116], 0);
+        write_octal(&mut header[116..124], 0);
+        write_octal(&mut header[124..136], data.len() as u64);
+        write_octal(&mut header[136..148], self.mtime);
+        header[156] = type_flag;
+        header[257..263].copy_from_slice(b"ustar\0");
+        header[263..265].copy_from_slice(b"00");
+        header[265..269].copy_from_slice(b"root");
+        header[297..301].copy_from_slice(b"root");
+        header[345..345 + prefix.len()].copy_from_slice(prefix.as_bytes());
+        // the checksum is computed with the checksum field filled with spaces
+        header[148..156].copy_from_slice(b"        ");
+        let checksum: u64 = header.iter().map( | b | *b as u64).sum();
+        let checksum = format!("{:06o}\0 ", checksum);
+        header[148..156].copy_from_slice(checksum.as_bytes());
+
+        self.data.extend_from_slice(&header);
+        self.data.extend_from_slice(data);
+        self.data.resize(self.data.len().div_ceil(512) * 512, 0);
+        Ok(())
+    }
+
+    pub fn add_dir(&mut self, path: &str) -> Result<(), String> {
+        self.add_entry(&format!("{}/", path.trim_end_matches('/')), 0o755, b'5', &[])
+    }
+
+    pub fn add_file(&mut self, path: &str, mode: u32, data: &[u8]) -> Result<(), String> {
+        self.add_entry(path, mode, b'0', data)
+    }
+
+    pub fn add_dir_all(&mut self, source_dir: &Path, path: &str) -> Result<(), String> {
+        self.add_dir(path) ?;
+        let mut entries: Vec<_> = fs::read_dir(source_dir)
+            .map_err( | _e | format!("Unable to read source directory {:?}", source_dir)) ?
+            .flatten().map( | entry | entry.path()).collect();
+        entries.sort();
+        for source_path in entries {
+            let name = source_path.file_name().unwrap().to_string_lossy().to_string();
+            let dest_path = format!("{}/{}", path, name);
+            if source_path.is_dir() {
+                self.add_dir_all(&source_path, &dest_path) ?;
+            }
+            else {
+                let data = fs::read(&source_path)
+                    .map_err( | _e | format!("Cant open input file {:?}", source_path)) ?;
+                self.add_file(&dest_path, file_mode(&source_path), &data) ?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn finish(mut self) -> Vec<u8> {
+        self.data.extend_from_slice(&[0u8; 1024]);
+        self.data
+    }
+}
+
+#[cfg(unix)]
+fn file_mode(path: &Path) -> u32 {
+    use std::os::unix::fs::PermissionsExt;
+    fs::metadata(path).map( | meta | if meta.permissions().mode() & 0o111 != 0 {0o755} else {0o644}).unwrap_or(0o644)
+}
+
+#[cfg(not(unix))]
+fn file_mode(_path: &Path) -> u32 {
+    0o644
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xffff_ffffu32;
+    for byte in data {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {(crc >> 1) ^ 0xedb8_8320} else {crc >> 1};
+        }
+    }
+    !crc
+}
+
+pub fn gzip(data: &[u8]) -> Vec<u8> {
+    let mut out = vec![0x1f, 0x8b, 8, 0, 0, 0, 0, 0, 0, 3];
+    out.extend_from_slice(&compress_to_vec(data, 9));
+    out.extend_from_slice(&crc32(data).to_le_bytes());
+    out.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    out
+}
+
+pub fn ar(members: &[(&str, &[u8])]) -> Vec<u8> {
+    let mtime = mtime();
+    let mut out = b"!<arch>\n".to_vec();
+    for (name, data) in members {
+        let header = format!("{:<16}{:<12}{:<6}{:<6}{:<8}{:<10}`\n", name, mtime, 0, 0, 100644, data.len());
+        out.extend_from_slice(header.as_bytes());
+        out.extend_from_slice(data);
+        if data.len() & 1 == 1 {
+            out.push(b'\n');
+        }
+    }
+    out
+}
@@ -0,0 +1,72 @@
+mod archive;
+mod package;
+
+use crate::makepad_shell::*;
+
+pub struct LinuxPackageArgs {
+    pub package_name: Option<String>,
+    pub app_label: Option<String>,
+    pub version: Option<String>,
+    pub maintainer: Option<String>,
+    pub icon: Option<String>,
+}
+
+// the development packages needed to build makepad applications on debian/ubuntu
+const APT_GET_DEPS: [&str; 8] = [
+    "pkg-config",
+    "libx11-dev",
+    "libxcursor-dev",
+    "libxrandr-dev",
+    "libgl1-mesa-dev",
+    "libglx-dev",
+    "libasound2-dev",
+    "libpulse-dev",
+];
+
+pub fn handle_linux(mut args: &[String]) -> Result<(), String> {
+    let mut package_args = LinuxPackageArgs {
+        package_name: None,
+        app_label: None,
+        version: None,
+        maintainer: None,
+        icon: None,
+    };
+    // pull out options
+    for i in 0..args.len() {
+        let v = &args[i];
+        if let Some(opt) = v.strip_prefix("--package-name=") {
+            package_args.package_name = Some(opt.to_string());
+        }
+        else if let Some(opt) = v.strip_prefix("--app-label=") {
+            package_args.app_label = Some(opt.to_string());
+        }
+        else if let Some(opt) = v.strip_prefix("--version=") {
+            package_args.version = Some(opt.to_string());
+        }
+        else if let Some(opt) = v.strip_prefix("--maintainer=") {
+            package_args.maintainer = Some(opt.to_string());
+        }
+        else if let Some(opt) = v.strip_prefix("--icon=") {
+            package_args.icon = Some(opt.to_string());
+        }
+        else {
+            args = &args[i..];
+            break
+        }
+    }
+    if args.is_empty() {
+        return Err("Not enough arguments".into());
+    }
+    
+    match args[0].as_ref() {
+        "apt-get-install-makepad-deps" => {
+            let mut apt_args = vec!["apt-get", "install"];
+            apt_args.extend_from_slice(&APT_GET_DEPS);
+            shell_env(&[], &std::env::current_dir().unwrap(), "sudo", &apt_args)
+        }
+        "package" => {
+            package::package(package_args, &args[1..])
+        }
+        _ => Err(format!("{} is not a valid command or option", args[0]))
+    }
+}
@@ -0,0 +1,291 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+use makepad_toml_parser::*;
+use crate::makepad_shell::*;
+use crate::utils::*;
+use super::{LinuxPackageArgs, archive::*};
+
+// used when no --icon is given
+const DEFAULT_ICON_SVG: &str = include_str!("icon.svg");
+
+// runtime libraries the x11 backend links against
+const DEB_DEPENDS: &str = "libc6, libx11-6, libxcursor1, libxrandr2, libgl1, libglx0, libasound2, libpulse0";
+
+struct PackageInfo {
+    build_crate: String,
+    underscore_build_crate: String,
+    package_name: String,
+    app_label: String,
+    version: String,
+    maintainer: String,
+    icon: Option<PathBuf>,
+}
+
+impl PackageInfo {
+    // debian package names are lowercase and can't contain underscores
+    fn deb_name(&self) -> String {
+        self.build_crate.to_lowercase().replace('_', "-")
+    }
+
+    fn icon_file(&self) -> String {
+        let ext = match &self.icon {
+            Some(icon) => icon.extension().map( | ext | ext.to_string_lossy().to_string()).unwrap_or("png".to_string()),
+            None => "svg".to_string()
+        };
+        format!("{}.{}", self.package_name, ext)
+    }
+
+    fn icon_dir(&self) -> &'static str {
+        if self.icon_file().ends_with(".svg") {"usr/share/icons/hicolor/scalable/apps"} else {"usr/share/icons/hicolor/256x256/apps"}
+    }
+}
+
+fn desktop_file(info: &PackageInfo) -> String {
+    format!("[Desktop Entry]
+Type=Application
+Name={}
+Exec={}
+Icon={}
+Categories=Utility;
+Terminal=false
+",
+        info.app_label,
+        info.build_crate,
+        info.package_name,
+    )
+}
+
+fn app_run(info: &PackageInfo) -> String {
+    format!("#!/bin/sh
+HERE=\"$(dirname \"$(readlink -f \"$0\")\")\"
+exec \"$HERE/usr/bin/{}\" \"$@\"
+",
+        info.build_crate
+    )
+}
+
+fn deb_control(info: &PackageInfo, installed_size: u64) -> String {
+    format!("Package: {}
+Version: {}
+Architecture: {}
+Maintainer: {}
+Installed-Size: {}
+Depends: {}
+Section: misc
+Priority: optional
+Description: {}
+",
+        info.deb_name(),
+        info.version,
+        deb_arch(),
+        info.maintainer,
+        installed_size,
+        DEB_DEPENDS,
+        info.app_label,
+    )
+}
+
+fn flatpak_manifest(info: &PackageInfo) -> String {
+    format!(r#"{{
+    "app-id": "{0}",
+    "runtime": "org.freedesktop.Platform",
+    "runtime-version": "23.08",
+    "sdk": "org.freedesktop.Sdk",
+    "command": "{1}",
+    "finish-args": [
+        "--share=ipc",
+        "--socket=x11",
+        "--socket=pulseaudio",
+        "--device=dri"
+    ],
+    "modules": [
+        {{
+            "name": "{1}",
+            "buildsystem": "simple",
+            "build-commands": [
+                "mkdir -p /app",
+                "cp -r bin share /app/"
+            ],
+            "sources": [
+                {{
+                    "type": "dir",
+                    "path": "AppDir/usr"
+                }}
+            ]
+        }}
+    ]
+}}
+"#,
+        info.package_name,
+        info.build_crate,
+    )
+}
+
+fn deb_arch() -> &'static str {
+    match std::env::consts::ARCH {
+        "x86_64" => "amd64",
+        "aarch64" => "arm64",
+        "x86" => "i386",
+        "arm" => "armhf",
+        arch => arch
+    }
+}
+
+fn get_crate_version(build_crate: &str) -> Option<String> {
+    let crate_dir = get_crate_dir(build_crate).ok() ?;
+    let cargo_str = fs::read_to_string(crate_dir.join("Cargo.toml")).ok() ?;
+    let toml = parse_toml(&cargo_str).ok() ?;
+    if let Some(Toml::Str(version, _)) = toml.get("package.version") {
+        return Some(version.to_string())
+    }
+    None
+}
+
+fn rust_build(info: &PackageInfo, args: &[String]) -> Result<String, String> {
+    let cwd = std::env::current_dir().unwrap();
+    let mut args_out = vec!["build"];
+    for arg in args {
+        args_out.push(arg);
+    }
+    // packages ship optimized builds unless a profile is given explicitly
+    if !args.iter().any( | arg | arg == "--release" || arg.starts_with("--profile=")) {
+        args_out.push("--release");
+    }
+    // the executable lives in usr/bin and its resources in usr/share/<crate>
+    let package_dir = format!("../share/{}", info.underscore_build_crate);
+    shell_env(&[("MAKEPAD_PACKAGE_DIR", &package_dir)], &cwd, "cargo", &args_out) ?;
+
+    Ok(get_profile_from_args(&args_out.iter().map( | arg | arg.to_string()).collect::<Vec<_>>()))
+}
+
+fn build_app_dir(info: &PackageInfo, profile: &str, app_dir: &Path) -> Result<(), String> {
+    let cwd = std::env::current_dir().unwrap();
+
+    let src_bin = cwd.join(format!("target/{}/{}", profile, info.build_crate));
+    cp(&src_bin, &app_dir.join(format!("usr/bin/{}", info.build_crate)), true) ?;
+
+    // resources of the crate and its dependencies, laid out as the package root expects
+    let share_dir = app_dir.join(format!("usr/share/{}", info.underscore_build_crate));
+    let local_resources_path = get_crate_dir(&info.build_crate) ?.join("resources");
+    if local_resources_path.is_dir() {
+        cp_all(&local_resources_path, &share_dir.join(format!("{}/resources", info.underscore_build_crate)), false) ?;
+    }
+    for (name, resources_path) in get_crate_resources(&info.build_crate) {
+        cp_all(&resources_path, &share_dir.join(format!("{name}/resources")), false) ?;
+    }
+
+    let desktop_file = desktop_file(info);
+    let desktop_name = format!("{}.desktop", info.package_name);
+    write_text(&app_dir.join("usr/share/applications").join(&desktop_name), &desktop_file) ?;
+    write_text(&app_dir.join(&desktop_name), &desktop_file) ?;
+
+    let icon_file = info.icon_file();
+    if let Some(icon) = &info.icon {
+        cp(icon, &app_dir.join(info.icon_dir()).join(&icon_file), false) ?;
+    }
+    else {
+        write_text(&app_dir.join(info.icon_dir()).join(&icon_file), DEFAULT_ICON_SVG) ?;
+    }
+    cp(&app_dir.join(info.icon_dir()).join(&icon_file), &app_dir.join(&icon_file), false) ?;
+    cp(&app_dir.join(&icon_file), &app_dir.join(".DirIcon"), false) ?;
+
+    let app_run_file = app_dir.join("AppRun");
+    write_text(&app_run_file, &app_run(info)) ?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&app_run_file, PermissionsExt::from_mode(0o755))
+            .map_err( | _e | format!("Cant set exec permissions on {:?}", app_run_file)) ?;
+    }
+    Ok(())
+}
+
+fn dir_size(dir: &Path) -> u64 {
+    let mut size = 0;
+    if let Ok(entries) = fs::read_dir(dir) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                size += dir_size(&path);
+            }
+            else if let Ok(meta) = entry.metadata() {
+                size += meta.len();
+            }
+        }
+    }
+    size
+}
+
+fn build_deb(info: &PackageInfo, app_dir: &Path, out_dir: &Path) -> Result<PathBuf, String> {
+    let usr_dir = app_dir.join("usr");
+
+    let mut control = TarBuilder::new();
+    control.add_dir(".") ?;
+    control.add_file("./control", 0o644, deb_control(info, dir_size(&usr_dir).div_ceil(1024)).as_bytes()) ?;
+
+    let mut data = TarBuilder::new();
+    data.add_dir(".") ?;
+    data.add_dir_all(&usr_dir, "./usr") ?;
+
+    let control_tar = gzip(&control.finish());
+    let data_tar = gzip(&data.finish());
+    let deb = ar(&[
+        ("debian-binary", b"2.0\n"),
+        ("control.tar.gz", &control_tar),
+        ("data.tar.gz", &data_tar),
+    ]);
+
+    let dst_deb = out_dir.join(format!("{}_{}_{}.deb", info.deb_name(), info.version, deb_arch()));
+    fs::write(&dst_deb, deb).map_err( | _e | format!("Cant write output file {:?}", dst_deb)) ?;
+    Ok(dst_deb)
+}
+
+pub fn package(package_args: LinuxPackageArgs, args: &[String]) -> Result<(), String> {
+    let build_crate = get_build_crate_from_args(args) ?;
+    let underscore_build_crate = build_crate.replace('-', "_");
+
+    let info = PackageInfo {
+        build_crate: build_crate.to_string(),
+        package_name: package_args.package_name.unwrap_or_else( | | format!("dev.makepad.{underscore_build_crate}")),
+        app_label: package_args.app_label.unwrap_or_else( | | underscore_build_crate.clone()),
+        version: package_args.version.or_else( | | get_crate_version(build_crate)).unwrap_or("0.1.0".to_string()),
+        maintainer: package_args.maintainer.unwrap_or("Unknown <unknown@example.com>".to_string()),
+        icon: package_args.icon.map(PathBuf::from),
+        underscore_build_crate,
+    };
+
+    let profile = rust_build(&info, args) ?;
+
+    let cwd = std::env::current_dir().unwrap();
+    let out_dir = cwd.join(format!("target/makepad-linux-package/{}", info.underscore_build_crate));
+    let app_dir = out_dir.join("AppDir");
+    let _ = rmdir(&out_dir);
+    mkdir(&app_dir) ?;
+
+    println!("Building AppDir");
+    build_app_dir(&info, &profile, &app_dir) ?;
+
+    println!("Building deb package");
+    let dst_deb = build_deb(&info, &app_dir, &out_dir) ?;
+
+    let manifest_file = out_dir.join(format!("{}.json", info.package_name));
+    write_text(&manifest_file, &flatpak_manifest(&info)) ?;
+
+    println!("Linux packaging completed");
+    println!("    AppDir:           {}", app_dir.display());
+    println!("    deb:              {}", dst_deb.display());
+    println!("    Flatpak manifest: {}", manifest_file.display());
+
+    // turning the AppDir into a single file needs appimagetool, use it when it is installed
+    let app_image = out_dir.join(format!("{}-{}.AppImage", info.deb_name(), std::env::consts::ARCH));
+    match shell_env_cap(&[("ARCH", std::env::consts::ARCH)], &out_dir, "appimagetool", &[
+        app_dir.to_str().unwrap(),
+        app_image.to_str().unwrap(),
+    ]) {
+        Ok(_) => println!("    AppImage:         {}", app_image.display()),
+        Err(_) => println!("    appimagetool not found, run it on the AppDir to produce an AppImage")
+    }
+    Ok(())
+}
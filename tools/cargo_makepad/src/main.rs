@@ -1,13 +1,18 @@
 mod android;
 mod wasm;
+mod linux;
+mod new_project;
 mod utils;
 mod apple;
 mod check;
 use android::*;
 use wasm::*;
+use linux::*;
+use new_project::*;
 use apple::*;
 use check::*;
 pub use makepad_shell;
+pub use makepad_http;
 
 fn show_help(err: &str){
     if !err.is_empty(){
@@ -18,15 +23,24 @@ fn show_help(err: &str){
     println!();
     println!("Usage cargo makepad [commands]");
     println!();
+    println!("Project Commands:");
+    println!();
+    println!("    new <name> [--template=simple]               Create a new makepad application crate and add it to the workspace");
+    println!("                                                 templates: simple, list, dock, audio");
+    println!();
     println!("Wasm Commands:");
     println!();
     println!("    wasm install-toolchain                       Install the toolchain needed for wasm32 with rustup");
-    println!("    wasm build <cargo args>                      Build a wasm project");
+    println!("    wasm [options] build <cargo args>            Build a wasm project into target/makepad-wasm-app");
     println!("    wasm [options] run <cargo args>              Build and run a wasm project, starts a webserver at port 8080 by default");
+    println!("                                                 and rebuilds and reloads the page when the sources change");
     println!();
     println!("    [options] with its default value:");
     println!();
     println!("       --port=8080                               The port to run the wasm webserver");
+    println!("       --lan                                     Let other machines on the network reach the webserver, it only listens on 127.0.0.1 otherwise");
+    println!("       --strip                                   Strip the debug sections from the wasm file");
+    println!("       --brotli                                  Brotli compress the wasm file, needs the brotli command line tool");
     println!();
     println!("Apple iOS/TVOs Commands:");
     println!();
@@ -70,6 +84,17 @@ fn show_help(err: &str){
     println!("Linux commands:");
     println!();
     println!("    linux apt-get-install-makepad-deps           Call apt-get install with all dependencies needed for makepad.");
+    println!("    linux [options] package <cargo args>         Build a release binary and package it as an AppImage directory, a .deb");
+    println!("                                                 and a Flatpak manifest in target/makepad-linux-package");
+    println!("                                                 An AppImage file is also produced when appimagetool is installed");
+    println!();
+    println!("    [options] with its default value:");
+    println!();
+    println!("       --package-name=\"dev.makepad.crate\"      The reverse DNS application id used by the .desktop file and Flatpak");
+    println!("       --app-label=\"applabel\"                  The app label");
+    println!("       --version=\"0.1.0\"                       The package version, read from Cargo.toml by default");
+    println!("       --maintainer=\"Name <email>\"             The maintainer field of the .deb");
+    println!("       --icon=icon.png                           The application icon, png or svg");
     println!();
     println!();
     }
@@ -102,6 +127,12 @@ fn main() {
         "apple" => if let Err(e) = handle_apple(&args[1..]){
             println!("Got error: {}", e);
         }
+        "linux" => if let Err(e) = handle_linux(&args[1..]){
+            println!("Got error: {}", e);
+        }
+        "new" => if let Err(e) = handle_new(&args[1..]){
+            println!("Got error: {}", e);
+        }
         "check" => if let Err(e) = handle_check(&args[1..]){
             println!("Got error: {}", e);
         }
@@ -0,0 +1,145 @@
+use std::path::{Path, PathBuf};
+use crate::makepad_shell::*;
+use crate::utils::*;
+
+// used when the project isn't created inside a workspace that has makepad-widgets
+const MAKEPAD_WIDGETS_VERSION: &str = "0.6.0";
+
+const TEMPLATES: [(&str, &str); 4] = [
+    ("simple", include_str!("templates/app_simple.rs")),
+    ("list", include_str!("templates/app_list.rs")),
+    ("dock", include_str!("templates/app_dock.rs")),
+    ("audio", include_str!("templates/app_audio.rs")),
+];
+
+fn cargo_toml(name: &str, underscore_name: &str, widgets_dep: &str) -> String {
+    format!(r#"[package]
+name = "{name}"
+version = "0.1.0"
+edition = "2021"
+metadata.makepad-android-package-name = "dev.makepad.{underscore_name}"
+metadata.makepad-android-app-label = "{name}"
+metadata.makepad-apple-org = "makepad"
+metadata.makepad-apple-app = "{underscore_name}"
+
+[dependencies]
+makepad-widgets = {widgets_dep}
+"#)
+}
+
+fn lib_rs() -> String {
+    "pub use makepad_widgets;
+pub mod app;
+".to_string()
+}
+
+fn main_rs(underscore_name: &str) -> String {
+    format!("// this stub is necessary because some platforms require building
+// as dll (mobile / wasm) and some require to be built as executable
+// unfortunately cargo doesn't facilitate this without a main.rs stub
+fn main(){{
+    {underscore_name}::app::app_main()
+}}
+")
+}
+
+fn is_valid_crate_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with(|c: char| c.is_ascii_digit())
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+// the relative path from one absolute directory to another, with forward slashes as cargo likes them
+fn relative_path(from: &Path, to: &Path) -> String {
+    let from: Vec<_> = from.components().collect();
+    let to: Vec<_> = to.components().collect();
+    let common = from.iter().zip(to.iter()).take_while(|(a, b)| a == b).count();
+    let mut parts = vec!["..".to_string(); from.len() - common];
+    for component in &to[common..] {
+        parts.push(component.as_os_str().to_string_lossy().to_string());
+    }
+    if parts.is_empty() {
+        return ".".to_string()
+    }
+    parts.join("/")
+}
+
+fn find_workspace_manifest(dir: &Path) -> Option<PathBuf> {
+    for dir in dir.ancestors() {
+        let manifest = dir.join("Cargo.toml");
+        if let Ok(cargo_str) = std::fs::read_to_string(&manifest) {
+            if cargo_str.contains("[workspace]") || cargo_str.contains("workspace.members") {
+                return Some(manifest)
+            }
+        }
+    }
+    None
+}
+
+// inserts the new crate in front of the existing workspace members
+fn add_workspace_member(manifest: &Path, member: &str) -> Result<(), String> {
+    let cargo_str = std::fs::read_to_string(manifest).map_err(|_e| format!("Cant read {:?}", manifest)) ?;
+    let members = cargo_str.find("members").ok_or_else(|| format!("No workspace members in {:?}", manifest)) ?;
+    let open = cargo_str[members..].find('[').ok_or_else(|| format!("No workspace members in {:?}", manifest)) ? + members + 1;
+    let mut out = cargo_str[..open].to_string();
+    out.push_str(&format!("\n    \"{}\",", member));
+    out.push_str(&cargo_str[open..]);
+    write_text(manifest, &out)
+}
+
+pub fn handle_new(args: &[String]) -> Result<(), String> {
+    let mut path = None;
+    let mut template = "simple".to_string();
+    let mut i = 0;
+    while i < args.len() {
+        if let Some(opt) = args[i].strip_prefix("--template=") {
+            template = opt.to_string();
+        }
+        else if args[i] == "--template" && i + 1 < args.len() {
+            template = args[i + 1].clone();
+            i += 1;
+        }
+        else if path.is_none() {
+            path = Some(args[i].clone());
+        }
+        else {
+            return Err(format!("{} is not a valid option", args[i]));
+        }
+        i += 1;
+    }
+    let path = path.ok_or("Please provide a name for the new project") ?;
+    let app_rs = TEMPLATES.iter().find(|(name, _)| *name == template).map(|(_, app_rs)| *app_rs)
+        .ok_or_else(|| format!("{:?} is not a template, please use simple, list, dock or audio", template)) ?;
+
+    let cwd = std::env::current_dir().unwrap();
+    let crate_dir = cwd.join(&path);
+    let name = crate_dir.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+    if !is_valid_crate_name(&name) {
+        return Err(format!("{:?} is not a valid crate name", name));
+    }
+    if crate_dir.exists() {
+        return Err(format!("{:?} already exists", crate_dir));
+    }
+    let underscore_name = name.replace('-', "_");
+
+    // inside a workspace that has makepad-widgets, depend on it by path so the versions line up
+    let widgets_dep = match get_crate_dir("makepad-widgets") {
+        Ok(widgets_dir) => format!("{{ path = \"{}\", version = \"{}\" }}", relative_path(&crate_dir, &widgets_dir), MAKEPAD_WIDGETS_VERSION),
+        Err(_) => format!("\"{}\"", MAKEPAD_WIDGETS_VERSION)
+    };
+
+    write_text(&crate_dir.join("Cargo.toml"), &cargo_toml(&name, &underscore_name, &widgets_dep)) ?;
+    write_text(&crate_dir.join("src/lib.rs"), &lib_rs()) ?;
+    write_text(&crate_dir.join("src/main.rs"), &main_rs(&underscore_name)) ?;
+    write_text(&crate_dir.join("src/app.rs"), app_rs) ?;
+    mkdir(&crate_dir.join("resources")) ?;
+
+    println!("Created {} from the {} template in {}", name, template, crate_dir.display());
+    if let Some(manifest) = crate_dir.parent().and_then(find_workspace_manifest) {
+        let member = relative_path(manifest.parent().unwrap(), &crate_dir);
+        add_workspace_member(&manifest, &member) ?;
+        println!("Added {} to the workspace members in {}", member, manifest.display());
+    }
+    println!("Run it with: cargo run -p {}", name);
+    Ok(())
+}
@@ -0,0 +1,98 @@
+use makepad_widgets::*;
+use std::sync::{Arc, Mutex};
+
+live_design!{
+    import makepad_widgets::base::*;
+    import makepad_widgets::theme_desktop_dark::*;
+    
+    App = {{App}} {
+        ui: <Window>{
+            show_bg: true
+            width: Fill,
+            height: Fill
+            draw_bg: {color: #2}
+            
+            body = <View>{
+                flow: Down,
+                spacing: 20,
+                align: {
+                    x: 0.5,
+                    y: 0.5
+                },
+                play = <Button> {
+                    text: "Play"
+                }
+                frequency = <Slider> {
+                    width: 300,
+                    text: "Frequency"
+                    min: 110.0
+                    max: 880.0
+                }
+            }
+        }
+    }
+}
+
+app_main!(App);
+
+// shared between the ui and the audio thread
+#[derive(Default)]
+struct Tone {
+    playing: bool,
+    frequency: f64,
+    phase: f64,
+}
+
+#[derive(Live, LiveHook)]
+pub struct App {
+    #[live] ui: WidgetRef,
+    #[rust] tone: Arc<Mutex<Tone>>,
+}
+
+impl LiveRegister for App {
+    fn live_register(cx: &mut Cx) {
+        crate::makepad_widgets::live_design(cx);
+    }
+}
+
+impl MatchEvent for App {
+    fn handle_startup(&mut self, cx: &mut Cx) {
+        self.tone.lock().unwrap().frequency = 440.0;
+        self.ui.widget(id!(frequency)).set_text("440");
+        let tone = self.tone.clone();
+        cx.audio_output(0, move | _info, buffer | {
+            let mut tone = tone.lock().unwrap();
+            let step = tone.frequency / 48000.0;
+            for frame in 0..buffer.frame_count() {
+                let sample = if tone.playing {(tone.phase * std::f64::consts::TAU).sin() as f32 * 0.2} else {0.0};
+                tone.phase = (tone.phase + step).fract();
+                for channel in 0..buffer.channel_count() {
+                    buffer.channel_mut(channel)[frame] = sample;
+                }
+            }
+        });
+    }
+    
+    fn handle_audio_devices(&mut self, cx: &mut Cx, devices: &AudioDevicesEvent) {
+        cx.use_audio_outputs(&devices.default_output());
+    }
+    
+    fn handle_actions(&mut self, cx: &mut Cx, actions: &Actions) {
+        let play = self.ui.button(id!(play));
+        if play.clicked(&actions) {
+            let mut tone = self.tone.lock().unwrap();
+            tone.playing = !tone.playing;
+            play.set_text_and_redraw(cx, if tone.playing {"Stop"} else {"Play"});
+        }
+        if let Some(frequency) = self.ui.slider(id!(frequency)).slided(&actions) {
+            self.tone.lock().unwrap().frequency = frequency;
+        }
+    }
+}
+
+impl AppMain for App {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event) {
+        self.match_event(cx, event);
+        self.ui.handle_event(cx, event, &mut Scope::empty());
+    }
+}
@@ -0,0 +1,123 @@
+use makepad_widgets::*;
+
+live_design!{
+    import makepad_widgets::base::*;
+    import makepad_widgets::theme_desktop_dark::*;
+    
+    Panel = <RectView> {
+        width: Fill,
+        height: Fill,
+        flow: Down,
+        padding: 10,
+        draw_bg: {color: #2}
+        label = <Label> {
+            draw_text: {color: #f}
+            text: ""
+        }
+    }
+    
+    App = {{App}} {
+        ui: <Window>{
+            show_bg: true
+            width: Fill,
+            height: Fill
+            
+            body = {
+                dock = <Dock> {
+                    width: Fill,
+                    height: Fill
+                    
+                    root = Splitter {
+                        axis: Horizontal,
+                        align: FromA(250.0),
+                        a: side_tabs,
+                        b: split1
+                    }
+                    
+                    split1 = Splitter {
+                        axis: Vertical,
+                        align: FromB(200.0),
+                        a: main_tabs,
+                        b: bottom_tabs
+                    }
+                    
+                    side_tabs = Tabs {
+                        tabs: [explorer],
+                        selected: 0
+                    }
+                    
+                    main_tabs = Tabs {
+                        tabs: [editor, preview],
+                        selected: 0
+                    }
+                    
+                    bottom_tabs = Tabs {
+                        tabs: [log],
+                        selected: 0
+                    }
+                    
+                    explorer = Tab {
+                        name: "Explorer"
+                        closable: false,
+                        kind: Explorer
+                    }
+                    
+                    editor = Tab {
+                        name: "Editor"
+                        closable: false,
+                        kind: Editor
+                    }
+                    
+                    preview = Tab {
+                        name: "Preview"
+                        kind: Preview
+                    }
+                    
+                    log = Tab {
+                        name: "Log"
+                        closable: false,
+                        kind: Log
+                    }
+                    
+                    Explorer = <Panel> {label = {text: "Explorer"}}
+                    Editor = <Panel> {label = {text: "Editor"}}
+                    Preview = <Panel> {label = {text: "Preview"}}
+                    Log = <Panel> {label = {text: "Log"}}
+                }
+            }
+        }
+    }
+}
+
+app_main!(App);
+
+#[derive(Live, LiveHook)]
+pub struct App {
+    #[live] ui: WidgetRef,
+}
+
+impl LiveRegister for App {
+    fn live_register(cx: &mut Cx) {
+        crate::makepad_widgets::live_design(cx);
+    }
+}
+
+impl MatchEvent for App {
+    fn handle_actions(&mut self, cx: &mut Cx, actions: &Actions) {
+        let dock = self.ui.dock(id!(dock));
+        for action in actions {
+            if let Some(action) = action.as_widget_action() {
+                if let DockAction::TabCloseWasPressed(tab_id) = action.cast() {
+                    dock.close_tab(cx, tab_id);
+                }
+            }
+        }
+    }
+}
+
+impl AppMain for App {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event) {
+        self.match_event(cx, event);
+        self.ui.handle_event(cx, event, &mut Scope::empty());
+    }
+}
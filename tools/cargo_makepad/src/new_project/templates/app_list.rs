@@ -0,0 +1,111 @@
+use makepad_widgets::*;
+
+live_design!{
+    import makepad_widgets::base::*;
+    import makepad_widgets::theme_desktop_dark::*;
+    
+    ListItem = <View> {
+        width: Fill,
+        height: Fit,
+        padding: 10,
+        spacing: 10,
+        flow: Right,
+        align: {y: 0.5}
+        show_bg: true
+        draw_bg: {color: #3}
+        label = <Label> {
+            width: Fill,
+            draw_text: {color: #f}
+            text: ""
+        }
+        button = <Button> {
+            text: "Select"
+        }
+    }
+    
+    ItemList = {{ItemList}} {
+        list = <PortalList> {
+            width: Fill,
+            height: Fill,
+            ListItem = <ListItem> {}
+        }
+    }
+    
+    App = {{App}} {
+        ui: <Window>{
+            show_bg: true
+            width: Fill,
+            height: Fill
+            draw_bg: {color: #2}
+            
+            body = <View>{
+                flow: Down,
+                selected = <Label> {
+                    margin: 10,
+                    draw_text: {color: #f}
+                    text: "Nothing selected"
+                }
+                item_list = <ItemList> {}
+            }
+        }
+    }
+}
+
+app_main!(App);
+
+const ITEM_COUNT: usize = 1000;
+
+#[derive(Live, LiveHook, Widget)]
+struct ItemList {
+    #[deref] view: View
+}
+
+impl Widget for ItemList {
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        while let Some(item) = self.view.draw_walk(cx, scope, walk).step() {
+            if let Some(mut list) = item.as_portal_list().borrow_mut() {
+                list.set_item_range(cx, 0, ITEM_COUNT);
+                while let Some(item_id) = list.next_visible_item(cx) {
+                    let item = list.item(cx, item_id, live_id!(ListItem)).unwrap();
+                    item.label(id!(label)).set_text(&format!("Item {}", item_id));
+                    item.draw_all(cx, &mut Scope::empty());
+                }
+            }
+        }
+        DrawStep::done()
+    }
+    
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        self.view.handle_event(cx, event, scope)
+    }
+}
+
+#[derive(Live, LiveHook)]
+pub struct App {
+    #[live] ui: WidgetRef,
+}
+
+impl LiveRegister for App {
+    fn live_register(cx: &mut Cx) {
+        crate::makepad_widgets::live_design(cx);
+    }
+}
+
+impl MatchEvent for App {
+    fn handle_actions(&mut self, cx: &mut Cx, actions: &Actions) {
+        let lists = self.ui.portal_list_set(ids!(item_list.list));
+        for (item_id, item) in lists.items_with_actions(&actions) {
+            if item.button(id!(button)).clicked(&actions) {
+                let label = self.ui.label(id!(selected));
+                label.set_text_and_redraw(cx, &format!("Selected item {}", item_id));
+            }
+        }
+    }
+}
+
+impl AppMain for App {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event) {
+        self.match_event(cx, event);
+        self.ui.handle_event(cx, event, &mut Scope::empty());
+    }
+}
@@ -3,6 +3,7 @@ use std::{
     path::{Path, PathBuf},
 };
 use crate::makepad_shell::*;
+use makepad_toml_parser::*;
 
 pub fn extract_dependency_info(line: &str) -> Option<(String, String)> {
     let dependency_output_start = line.find(|c: char| c.is_alphanumeric())?;
@@ -43,6 +44,17 @@ pub fn get_crate_dir(build_crate: &str) -> Result<PathBuf, String> {
     }
 }
 
+// reads a package.metadata value from the Cargo.toml of the crate, e.g. makepad-apple-org
+pub fn get_crate_metadata(build_crate: &str, key: &str) -> Option<String> {
+    let crate_dir = get_crate_dir(build_crate).ok() ?;
+    let cargo_str = std::fs::read_to_string(crate_dir.join("Cargo.toml")).ok() ?;
+    let toml = parse_toml(&cargo_str).ok() ?;
+    if let Some(Toml::Str(value, _)) = toml.get(&format!("package.metadata.{}", key)) {
+        return Some(value.to_string())
+    }
+    None
+}
+
 pub fn get_crate_resources(build_crate: &str) -> HashSet<(String, PathBuf)> {
     let mut dependencies = HashSet::new();
     let cwd = std::env::current_dir().unwrap();
@@ -1,7 +1,79 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
 use crate::makepad_shell::*;
+use crate::utils::*;
+use super::{WasmConfig, serve, wasm_strip::*};
 
-pub fn build(args: &[String]) -> Result<(), String> {
-    
+// the web platform scripts index.html pulls in, served at the same paths as in the makepad repo
+const WEB_PLATFORM_FILES: [&str; 5] = ["web_gl.js", "web.js", "web_worker.js", "audio_worklet.js", "full_canvas.css"];
+
+fn index_html(build_crate: &str) -> String {
+    format!(r#"<!DOCTYPE html>
+<html>
+    <head>
+    <meta charset="utf-8">
+    <meta name="viewport" content="width=device-width, initial-scale=1.0, user-scalable=no">
+    <title>{build_crate}</title>
+    <script type='module'>
+        import {{WasmWebGL}} from "/makepad/platform/src/os/web/web_gl.js"
+        
+        const wasm = await WasmWebGL.fetch_and_instantiate_wasm(
+            "/{build_crate}.wasm"
+        );
+        
+        class MyWasmApp {{
+            constructor(wasm) {{
+                let canvas = document.getElementsByClassName('full_canvas')[0];
+                this.webgl = new WasmWebGL (wasm, this, canvas);
+            }}
+        }}
+        
+        let app = new MyWasmApp(wasm);
+    </script>
+    <script type='module'>
+        // reloads the page when cargo makepad wasm run has rebuilt the application
+        let was_connected = false;
+        function watch_reload() {{
+            let protocol = location.protocol == "https:" ? "wss://" : "ws://";
+            let socket = new WebSocket(protocol + location.host + "/$reload");
+            socket.onopen = _ => {{
+                // the server restarted, so whatever we are running is stale
+                if (was_connected) {{
+                    location.reload();
+                }}
+                was_connected = true;
+            }};
+            socket.onmessage = async e => {{
+                let msg = JSON.parse(await e.data.text());
+                if (msg.type == "build_start") {{
+                    document.title = "Rebuilding application...";
+                }}
+                if (msg.type == "file_change") {{
+                    location.reload();
+                }}
+            }};
+            socket.onclose = _ => setTimeout(watch_reload, 500);
+        }}
+        watch_reload();
+    </script>
+    <link rel='stylesheet' type='text/css' href='/makepad/platform/src/os/web/full_canvas.css'>
+    </head>
+    <body>
+        <canvas class='full_canvas'></canvas>
+        <div class='canvas_loader' >
+            <div style=''>
+                Loading..
+            </div>
+        </div>
+    </body>
+</html>
+"#)
+}
+
+fn rust_build(args: &[String]) -> Result<(), String> {
     let base_args = &[ 
         "run",
         "nightly",
@@ -24,13 +96,136 @@ pub fn build(args: &[String]) -> Result<(), String> {
     shell_env(&[
         ("RUSTFLAGS", "-C codegen-units=1 -C target-feature=+atomics,+bulk-memory,+mutable-globals -C link-arg=--export=__stack_pointer -C opt-level=z"),
         ("MAKEPAD", "lines"),
-    ], &cwd, "rustup", &args_out) ?;
+    ], &cwd, "rustup", &args_out)
+}
+
+fn build_app_dir(config: &WasmConfig, build_crate: &str, profile: &str) -> Result<PathBuf, String> {
+    let cwd = std::env::current_dir().unwrap();
+    let underscore_build_crate = build_crate.replace('-', "_");
+    let app_dir = cwd.join(format!("target/makepad-wasm-app/{profile}/{underscore_build_crate}"));
+    mkdir(&app_dir) ?;
+    
+    let platform_dir = get_crate_dir("makepad-platform") ?;
+    for file in WEB_PLATFORM_FILES {
+        cp(&platform_dir.join("src/os/web").join(file), &app_dir.join("makepad/platform/src/os/web").join(file), false) ?;
+    }
+    let wasm_bridge_dir = get_crate_dir("makepad-wasm-bridge") ?;
+    cp(&wasm_bridge_dir.join("src/wasm_bridge.js"), &app_dir.join("makepad/libs/wasm_bridge/src/wasm_bridge.js"), false) ?;
+    
+    let src_wasm = cwd.join(format!("target/wasm32-unknown-unknown/{profile}/{build_crate}.wasm"));
+    let mut wasm = fs::read(&src_wasm).map_err( | _e | format!("Cant open wasm file {:?}", src_wasm)) ?;
+    if config.strip {
+        wasm = wasm_strip_debug(&wasm).map_err( | _e | format!("Cant parse wasm file {:?}", src_wasm)) ?;
+    }
+    let wasm_file = format!("{build_crate}.wasm");
+    let dst_wasm = app_dir.join(&wasm_file);
+    fs::write(&dst_wasm, &wasm).map_err( | _e | format!("Cant write wasm file {:?}", dst_wasm)) ?;
+    println!("Wasm file size: {} kb", wasm.len() >> 10);
+    
+    let dst_brotli = app_dir.join(format!("{wasm_file}.br"));
+    let _ = rm(&dst_brotli);
+    if config.brotli {
+        shell_env_cap(&[], &app_dir, "brotli", &["-f", "-q", "11", &wasm_file])
+            .map_err( | e | format!("Brotli compression failed, is the brotli command line tool installed? {}", e)) ?;
+        if let Ok(meta) = fs::metadata(&dst_brotli) {
+            println!("Wasm file brotli compressed size: {} kb", meta.len() >> 10);
+        }
+    }
+    
+    write_text(&app_dir.join("index.html"), &index_html(build_crate)) ?;
+    Ok(app_dir)
+}
+
+pub fn build(config: &WasmConfig, args: &[String]) -> Result<PathBuf, String> {
+    let build_crate = get_build_crate_from_args(args) ?;
+    let profile = get_profile_from_args(args);
+    
+    rust_build(args) ?;
+    let app_dir = build_app_dir(config, build_crate, &profile) ?;
     
     println!("WebAssembly build completed");
     
-    Ok(())
+    Ok(app_dir)
+}
+
+// the local crates the build depends on, a change in any of these triggers a rebuild
+fn get_crate_local_dirs(build_crate: &str) -> Result<Vec<PathBuf>, String> {
+    let mut dirs = vec![get_crate_dir(build_crate) ?];
+    let cwd = std::env::current_dir().unwrap();
+    if let Ok(cargo_tree_output) = shell_env_cap(&[], &cwd, "cargo", &["tree", "-p", build_crate]) {
+        for line in cargo_tree_output.lines().skip(1) {
+            if let Some((_name, path)) = extract_dependency_info(line) {
+                let path = PathBuf::from(path);
+                if !dirs.contains(&path) {
+                    dirs.push(path);
+                }
+            }
+        }
+    }
+    Ok(dirs)
+}
+
+#[derive(Default, PartialEq)]
+struct WatchState {
+    file_count: usize,
+    last_modified: Option<SystemTime>,
+}
+
+impl WatchState {
+    fn scan(dirs: &[PathBuf]) -> Self {
+        let mut state = Self::default();
+        for dir in dirs {
+            state.scan_dir(dir);
+        }
+        state
+    }
+    
+    fn scan_dir(&mut self, dir: &Path) {
+        let entries = if let Ok(entries) = fs::read_dir(dir) {entries} else {return};
+        for entry in entries.flatten() {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            if name.starts_with('.') || name == "target" {
+                continue
+            }
+            let path = entry.path();
+            if path.is_dir() {
+                self.scan_dir(&path);
+            }
+            else if let Ok(modified) = entry.metadata().and_then( | meta | meta.modified()) {
+                self.file_count += 1;
+                if self.last_modified < Some(modified) {
+                    self.last_modified = Some(modified);
+                }
+            }
+        }
+    }
 }
 
-pub fn run(_args: &[String]) -> Result<(), String> {
-    return Err("Run is not implemented yet".into());
+pub fn run(config: &WasmConfig, args: &[String]) -> Result<(), String> {
+    let build_crate = get_build_crate_from_args(args) ?;
+    let app_dir = build(config, args) ?;
+    
+    let watch_dirs = get_crate_local_dirs(build_crate) ?;
+    let resource_dirs = watch_dirs.iter().map( | dir | dir.join("resources")).filter( | dir | dir.is_dir()).collect();
+    let reload = serve::start_server(config.port, config.lan, app_dir, resource_dirs) ?;
+    println!("Serving {} at http://127.0.0.1:{}/", build_crate, config.port);
+    if config.lan {
+        println!("The server also accepts connections from the local network on port {}", config.port);
+    }
+    
+    let mut watch_state = WatchState::scan(&watch_dirs);
+    loop {
+        std::thread::sleep(Duration::from_millis(500));
+        if WatchState::scan(&watch_dirs) == watch_state {
+            continue
+        }
+        println!("Source change detected, rebuilding {}", build_crate);
+        reload.send("build_start");
+        match build(config, args) {
+            Ok(_) => reload.send("file_change"),
+            Err(e) => println!("Got error: {}", e)
+        }
+        watch_state = WatchState::scan(&watch_dirs);
+    }
 }
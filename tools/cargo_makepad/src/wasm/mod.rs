@@ -1,7 +1,46 @@
 mod compile;
 mod sdk;
+mod serve;
+mod wasm_strip;
 
-pub fn handle_wasm(args: &[String]) -> Result<(), String> {
+pub struct WasmConfig {
+    pub port: u16,
+    // serve on every network interface instead of only this machine
+    pub lan: bool,
+    pub strip: bool,
+    pub brotli: bool,
+}
+
+pub fn handle_wasm(mut args: &[String]) -> Result<(), String> {
+    let mut config = WasmConfig {
+        port: 8080,
+        lan: false,
+        strip: false,
+        brotli: false,
+    };
+    // pull out options
+    for i in 0..args.len() {
+        let v = &args[i];
+        if let Some(opt) = v.strip_prefix("--port=") {
+            config.port = opt.parse::<u16>().map_err( | _ | format!("{:?} is not a valid port", opt)) ?;
+        }
+        else if v.trim() == "--lan" {
+            config.lan = true;
+        }
+        else if v.trim() == "--strip" {
+            config.strip = true;
+        }
+        else if v.trim() == "--brotli" {
+            config.brotli = true;
+        }
+        else {
+            args = &args[i..];
+            break
+        }
+    }
+    if args.is_empty() {
+        return Err("Not enough arguments".into());
+    }
     
     match args[0].as_ref() {
         "rustup-install-toolchain"=>{
@@ -11,11 +50,11 @@ pub fn handle_wasm(args: &[String]) -> Result<(), String> {
             sdk::rustup_toolchain_install()
         }
         "build" =>{
-            compile::build(&args[1..])?;
+            compile::build(&config, &args[1..])?;
             Ok(())
         }
         "run" =>{
-            compile::run(&args[1..])
+            compile::run(&config, &args[1..])
         }
         _ => Err(format!("{} is not a valid command or option", args[0]))
     }
@@ -0,0 +1,141 @@
+use std::{
+    collections::HashMap,
+    fs,
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    path::{Path, PathBuf},
+    sync::{mpsc, Arc, Mutex},
+};
+use crate::makepad_http::{server::*, utils::HttpServerHeaders};
+
+// the websockets of the pages that want to be told about a rebuild
+#[derive(Clone, Default)]
+pub struct ReloadSockets(Arc<Mutex<HashMap<u64, mpsc::Sender<Vec<u8>>>>>);
+
+impl ReloadSockets {
+    pub fn send(&self, msg_type: &str) {
+        let msg = format!("{{\"type\":\"{}\"}}", msg_type);
+        for sender in self.0.lock().unwrap().values() {
+            let _ = sender.send(msg.as_bytes().to_vec());
+        }
+    }
+}
+
+fn mime_type(path: &str) -> Option<&'static str> {
+    let ext = path.rsplit('.').next()?;
+    Some(match ext {
+        "html" => "text/html",
+        "wasm" => "application/wasm",
+        "css" => "text/css",
+        "js" => "text/javascript",
+        "json" => "application/json",
+        "ttf" => "application/ttf",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "svg" => "image/svg+xml",
+        "wav" => "audio/wav",
+        "mp3" => "audio/mpeg",
+        _ => "application/octet-stream"
+    })
+}
+
+// maps a request path onto the app dir, or onto a dependency resource requested by absolute path
+fn resolve_path(path: &str, app_dir: &Path, resource_dirs: &[PathBuf]) -> Option<PathBuf> {
+    if path.contains("..") || path.contains('\\') {
+        return None
+    }
+    if path == "/" {
+        return Some(app_dir.join("index.html"))
+    }
+    let local = app_dir.join(path.trim_start_matches('/'));
+    if local.is_file() {
+        return Some(local)
+    }
+    let abs_path = PathBuf::from(format!("/{}", path.strip_prefix("/makepad/")?.trim_start_matches('/')));
+    if resource_dirs.iter().any( | dir | abs_path.starts_with(dir)) && abs_path.is_file() {
+        return Some(abs_path)
+    }
+    None
+}
+
+fn not_found() -> HttpServerResponse {
+    HttpServerResponse {
+        header: "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_string(),
+        body: vec![]
+    }
+}
+
+fn get_response(headers: &HttpServerHeaders, app_dir: &Path, resource_dirs: &[PathBuf]) -> HttpServerResponse {
+    let file_path = if let Some(file_path) = resolve_path(&headers.path, app_dir, resource_dirs) {file_path} else {return not_found()};
+    let mime_type = mime_type(file_path.to_str().unwrap_or("")).unwrap_or("application/octet-stream");
+    
+    // serve the precompressed wasm when it is there and the browser takes it
+    let accepts_brotli = headers.accept_encoding.as_ref().is_some_and( | enc | enc.contains("br"));
+    let brotli_path = PathBuf::from(format!("{}.br", file_path.display()));
+    let has_brotli = mime_type == "application/wasm" && brotli_path.is_file();
+    let (body, encoding) = if accepts_brotli && has_brotli {
+        (fs::read(&brotli_path), "Content-Encoding: br\r\n")
+    }
+    else {
+        (fs::read(&file_path), "")
+    };
+    // the response depends on Accept-Encoding when there is a compressed variant
+    let vary = if has_brotli {"Vary: Accept-Encoding\r\n"} else {""};
+    let body = if let Ok(body) = body {body} else {return not_found()};
+    
+    // COOP/COEP make the page cross origin isolated, which is needed for SharedArrayBuffer and thus threads
+    let header = format!(
+        "HTTP/1.1 200 OK\r\n\
+            Content-Type: {}\r\n\
+            Cross-Origin-Embedder-Policy: require-corp\r\n\
+            Cross-Origin-Opener-Policy: same-origin\r\n\
+            {}{}\
+            Cache-Control: no-cache\r\n\
+            Content-Length: {}\r\n\
+            Connection: close\r\n\r\n",
+        mime_type,
+        encoding,
+        vary,
+        body.len()
+    );
+    HttpServerResponse {header, body}
+}
+
+pub fn start_server(port: u16, lan: bool, app_dir: PathBuf, resource_dirs: Vec<PathBuf>) -> Result<ReloadSockets, String> {
+    let ip = if lan {IpAddr::V4(Ipv4Addr::UNSPECIFIED)} else {IpAddr::V4(Ipv4Addr::LOCALHOST)};
+    let addr = SocketAddr::new(ip, port);
+    let (tx_request, rx_request) = mpsc::channel::<HttpServerRequest> ();
+    
+    if start_http_server(HttpServer {
+        listen_address: addr,
+        post_max_size: 1024 * 1024,
+        request: tx_request
+    }).is_none() {
+        return Err(format!("Cannot start the webserver on port {}", port));
+    }
+    
+    let reload = ReloadSockets::default();
+    let sockets = reload.clone();
+    std::thread::spawn(move || {
+        while let Ok(message) = rx_request.recv() {
+            match message {
+                HttpServerRequest::ConnectWebSocket {web_socket_id, response_sender, headers} => {
+                    if headers.path == "/$reload" {
+                        sockets.0.lock().unwrap().insert(web_socket_id, response_sender);
+                    }
+                },
+                HttpServerRequest::DisconnectWebSocket {web_socket_id} => {
+                    sockets.0.lock().unwrap().remove(&web_socket_id);
+                },
+                HttpServerRequest::BinaryMessage {..} => {}
+                HttpServerRequest::Get {headers, response_sender} => {
+                    let _ = response_sender.send(get_response(&headers, &app_dir, &resource_dirs));
+                }
+                HttpServerRequest::Post {response, ..} => {
+                    let _ = response.send(not_found());
+                }
+            }
+        }
+    });
+    Ok(reload)
+}
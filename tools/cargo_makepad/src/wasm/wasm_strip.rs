@@ -75,6 +75,7 @@ pub struct WasmSection{
     pub type_id: u8,
     pub start: usize,
     pub end: usize,
+    #[allow(dead_code)]
     pub name: String
 }
 
@@ -96,7 +97,8 @@ fn read_wasm_sections(buf:&[u8])->Result<Vec<WasmSection>,WasmParseError>{
             let start = reader.offset;
             if type_id == 0{
                 let name_len = reader.read_var_u32()? as usize;
-                if let Ok(name) = std::str::from_utf8(&reader.bytes[0..name_len]){
+                let name = reader.bytes.get(0..name_len).ok_or(WasmParseError)?;
+                if let Ok(name) = std::str::from_utf8(name){
                     sections.push(WasmSection{
                         start: offset,
                         type_id,
@@ -108,7 +110,7 @@ fn read_wasm_sections(buf:&[u8])->Result<Vec<WasmSection>,WasmParseError>{
                     return Err(WasmParseError);
                 }
                 let end = reader.offset;
-                reader.skip(payload_len - (end-start))?;
+                reader.skip(payload_len.checked_sub(end-start).ok_or(WasmParseError)?)?;
             }
             else{
                 sections.push(WasmSection{
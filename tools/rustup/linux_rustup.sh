@@ -1,5 +1,5 @@
 rustup install nightly
 rustup default nightly
 rustup update
-sudo apt install libxcursor-dev libx11-dev libgl1-mesa-dev libasound-dev libpulse-dev
+sudo apt install libxcursor-dev libxrandr-dev libx11-dev libgl1-mesa-dev libasound-dev libpulse-dev
 
@@ -2,6 +2,8 @@ use std::{
     fs,
     env
 };
+// shared with cargo makepad, which strips the wasm it builds the same way
+#[path = "../../cargo_makepad/src/wasm/wasm_strip.rs"]
 mod wasm_strip;
 use wasm_strip::*;
     
@@ -0,0 +1,524 @@
+use {
+    crate::{
+        makepad_draw::*,
+        widget::WidgetUid,
+    },
+    std::{
+        collections::{HashMap, HashSet},
+        fmt::Write,
+        sync::{Arc, Mutex},
+    }
+};
+
+// The accessibility tree is built while drawing: every WidgetRef opens a frame for its widget,
+// and when the widget is done drawing it describes itself with Widget::access_node.
+// Widgets without a node are transparent, their children end up with the closest ancestor that has one.
+// The tree is retained, parts that aren't redrawn (cached draw lists) keep their nodes.
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AccessRole {
+    Window,
+    Group,
+    Button,
+    CheckBox,
+    RadioButton,
+    Label,
+    Link,
+    TextInput,
+    Slider,
+    ComboBox,
+    List,
+    ListItem,
+    TabList,
+    Tab,
+    Tree,
+    TreeItem,
+    Image,
+    Menu,
+    MenuItem,
+    Dialog,
+    Tooltip,
+    Alert,
+}
+
+impl AccessRole {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Window => "window",
+            Self::Group => "group",
+            Self::Button => "button",
+            Self::CheckBox => "check box",
+            Self::RadioButton => "radio button",
+            Self::Label => "label",
+            Self::Link => "link",
+            Self::TextInput => "text input",
+            Self::Slider => "slider",
+            Self::ComboBox => "combo box",
+            Self::List => "list",
+            Self::ListItem => "list item",
+            Self::TabList => "tab list",
+            Self::Tab => "tab",
+            Self::Tree => "tree",
+            Self::TreeItem => "tree item",
+            Self::Image => "image",
+            Self::Menu => "menu",
+            Self::MenuItem => "menu item",
+            Self::Dialog => "dialog",
+            Self::Tooltip => "tooltip",
+            Self::Alert => "alert",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AccessAction {
+    Click,
+    Focus,
+    Increment,
+    Decrement,
+}
+
+impl AccessAction {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Click => "click",
+            Self::Focus => "focus",
+            Self::Increment => "increment",
+            Self::Decrement => "decrement",
+        }
+    }
+}
+
+// what assistive technology asks a widget to do, delivered through Widget::handle_access
+#[derive(Clone, Debug, PartialEq)]
+pub enum AccessRequest {
+    Action(AccessAction),
+    SetValue(f64),
+    SetText(String),
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum AccessValue {
+    #[default]
+    None,
+    Text(String),
+    Number {value: f64, min: f64, max: f64, step: f64},
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct AccessStates {
+    pub focusable: bool,
+    pub focused: bool,
+    pub disabled: bool,
+    pub editable: bool,
+    pub secret: bool,
+    pub selected: bool,
+    pub checked: Option<bool>,
+    pub expanded: Option<bool>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct AccessNode {
+    pub role: AccessRole,
+    pub label: String,
+    pub value: AccessValue,
+    pub states: AccessStates,
+    pub actions: Vec<AccessAction>,
+    // relative to the window, except for window nodes which have their position on screen
+    pub rect: Rect,
+    pub parent: Option<WidgetUid>,
+    pub children: Vec<WidgetUid>,
+}
+
+impl AccessNode {
+    pub fn new(role: AccessRole) -> Self {
+        Self {
+            role,
+            label: String::new(),
+            value: AccessValue::None,
+            states: AccessStates::default(),
+            actions: Vec::new(),
+            rect: Rect::default(),
+            parent: None,
+            children: Vec::new(),
+        }
+    }
+
+    pub fn with_label(mut self, label: &str) -> Self {
+        self.label = label.to_string();
+        self
+    }
+
+    pub fn with_value(mut self, value: AccessValue) -> Self {
+        self.value = value;
+        self
+    }
+
+    pub fn with_rect(mut self, rect: Rect) -> Self {
+        self.rect = rect;
+        self
+    }
+
+    pub fn with_action(mut self, action: AccessAction) -> Self {
+        self.actions.push(action);
+        self
+    }
+
+    pub fn with_focus(mut self, focused: bool) -> Self {
+        self.states.focusable = true;
+        self.states.focused = focused;
+        self
+    }
+
+    pub fn with_checked(mut self, checked: bool) -> Self {
+        self.states.checked = Some(checked);
+        self
+    }
+
+    pub fn with_states(mut self, states: AccessStates) -> Self {
+        self.states = states;
+        self
+    }
+
+    // node content without the tree links, used to see whether a widget itself changed
+    fn content_eq(&self, other: &AccessNode) -> bool {
+        self.role == other.role
+            && self.label == other.label
+            && self.value == other.value
+            && self.states == other.states
+            && self.actions == other.actions
+            && self.rect == other.rect
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AccessChange {
+    Added(WidgetUid),
+    Removed(WidgetUid),
+    // the node itself changed, label, value, states or rect
+    Changed(WidgetUid),
+    // the list of children changed
+    Reordered(WidgetUid),
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AccessTree {
+    nodes: HashMap<WidgetUid, AccessNode>,
+    roots: Vec<WidgetUid>,
+}
+
+impl AccessTree {
+    pub fn roots(&self) -> &[WidgetUid] {
+        &self.roots
+    }
+
+    pub fn node(&self, uid: WidgetUid) -> Option<&AccessNode> {
+        self.nodes.get(&uid)
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (WidgetUid, &AccessNode)> {
+        self.nodes.iter().map( | (uid, node) | (*uid, node))
+    }
+
+    // depth first, in drawing order
+    pub fn find(&self, f: impl Fn(&AccessNode) -> bool) -> Option<WidgetUid> {
+        fn find(tree: &AccessTree, uid: WidgetUid, f: &dyn Fn(&AccessNode) -> bool) -> Option<WidgetUid> {
+            let node = tree.nodes.get(&uid) ?;
+            if f(node) {
+                return Some(uid)
+            }
+            node.children.iter().find_map( | child | find(tree, *child, f))
+        }
+        self.roots.iter().find_map( | root | find(self, *root, &f))
+    }
+
+    pub fn find_by_label(&self, role: AccessRole, label: &str) -> Option<WidgetUid> {
+        self.find( | node | node.role == role && node.label == label)
+    }
+
+    pub fn focused(&self) -> Option<WidgetUid> {
+        self.find( | node | node.states.focused)
+    }
+
+    // a readable outline of the tree, one node per line
+    pub fn dump(&self) -> String {
+        fn dump(tree: &AccessTree, uid: WidgetUid, depth: usize, out: &mut String) {
+            let Some(node) = tree.nodes.get(&uid) else {return};
+            let _ = write!(out, "{}{} {:?}", "  ".repeat(depth), node.role.name(), node.label);
+            match &node.value {
+                AccessValue::None => (),
+                AccessValue::Text(text) => {let _ = write!(out, " value={:?}", text);}
+                AccessValue::Number {value, ..} => {let _ = write!(out, " value={}", value);}
+            }
+            let states = &node.states;
+            for (on, name) in [
+                (states.focusable, "focusable"),
+                (states.focused, "focused"),
+                (states.disabled, "disabled"),
+                (states.editable, "editable"),
+                (states.secret, "secret"),
+                (states.selected, "selected"),
+                (states.checked == Some(true), "checked"),
+                (states.expanded == Some(true), "expanded"),
+            ] {
+                if on {
+                    let _ = write!(out, " [{}]", name);
+                }
+            }
+            out.push('\n');
+            for child in &node.children {
+                dump(tree, *child, depth + 1, out);
+            }
+        }
+        let mut out = String::new();
+        for root in &self.roots {
+            dump(self, *root, 0, &mut out);
+        }
+        out
+    }
+
+    // what happened between old and self, parents of added and removed nodes are reported as reordered
+    pub fn diff(&self, old: &AccessTree) -> Vec<AccessChange> {
+        let mut changes = Vec::new();
+        for uid in old.nodes.keys() {
+            if !self.nodes.contains_key(uid) {
+                changes.push(AccessChange::Removed(*uid));
+            }
+        }
+        for (uid, node) in &self.nodes {
+            match old.nodes.get(uid) {
+                None => changes.push(AccessChange::Added(*uid)),
+                Some(old_node) => {
+                    if !node.content_eq(old_node) {
+                        changes.push(AccessChange::Changed(*uid));
+                    }
+                    if node.children != old_node.children {
+                        changes.push(AccessChange::Reordered(*uid));
+                    }
+                }
+            }
+        }
+        changes
+    }
+
+    fn remove_subtree(&mut self, uid: WidgetUid) {
+        if let Some(node) = self.nodes.remove(&uid) {
+            for child in node.children {
+                if self.nodes.get(&child).map( | c | c.parent == Some(uid)).unwrap_or(false) {
+                    self.remove_subtree(child);
+                }
+            }
+        }
+        self.roots.retain( | root | *root != uid);
+    }
+}
+
+struct AccessFrame {
+    uid: WidgetUid,
+    children: Vec<WidgetUid>,
+    keep: bool,
+}
+
+pub type AccessRequestQueue = Arc<Mutex<Vec<(WidgetUid, AccessRequest)>>>;
+
+#[derive(Default)]
+pub struct CxAccess {
+    tree: AccessTree,
+    stack: Vec<AccessFrame>,
+    // the children that widgets without a node of their own handed to their parent last time
+    spliced: HashMap<WidgetUid, Vec<WidgetUid>>,
+    seen: HashSet<WidgetUid>,
+    changed: bool,
+    requests: Vec<(WidgetUid, AccessRequest)>,
+    incoming: AccessRequestQueue,
+    #[cfg(target_os = "linux")]
+    bridge: Option<crate::atspi::AtspiBridge>,
+}
+
+impl CxAccess {
+    fn begin(&mut self, uid: WidgetUid) {
+        // a widget that returned a DrawStep is drawn again, its frame is still open
+        if self.stack.iter().any( | frame | frame.uid == uid) {
+            return
+        }
+        if self.stack.is_empty() {
+            self.seen.clear();
+        }
+        self.stack.push(AccessFrame {uid, children: Vec::new(), keep: false});
+    }
+
+    fn keep(&mut self, uid: WidgetUid) {
+        if let Some(frame) = self.stack.iter_mut().rev().find( | frame | frame.uid == uid) {
+            frame.keep = true;
+        }
+    }
+
+    fn previous_children(&self, uid: WidgetUid) -> Vec<WidgetUid> {
+        if let Some(node) = self.tree.nodes.get(&uid) {
+            return node.children.clone()
+        }
+        self.spliced.get(&uid).cloned().unwrap_or_default()
+    }
+
+    fn end(&mut self, uid: WidgetUid, node: Option<AccessNode>) {
+        let Some(index) = self.stack.iter().position( | frame | frame.uid == uid) else {return};
+        while self.stack.len() > index + 1 {
+            let inner = self.stack.last().unwrap().uid;
+            self.end(inner, None);
+        }
+        let frame = self.stack.pop().unwrap();
+        self.seen.insert(uid);
+
+        let old_children = self.previous_children(uid);
+        let mut children = frame.children;
+        if frame.keep {
+            let mut kept: Vec<WidgetUid> = old_children.iter().filter( | c | !children.contains(c)).cloned().collect();
+            kept.append(&mut children);
+            children = kept;
+            self.mark_seen(&children);
+        }
+
+        let handed_up = match node {
+            Some(mut node) => {
+                for child in &children {
+                    if let Some(child) = self.tree.nodes.get_mut(child) {
+                        child.parent = Some(uid);
+                    }
+                }
+                node.children = children.clone();
+                self.spliced.remove(&uid);
+                if let Some(old) = self.tree.nodes.get(&uid) {
+                    node.parent = old.parent;
+                    if *old != node {
+                        self.changed = true;
+                    }
+                }
+                else {
+                    self.changed = true;
+                }
+                self.tree.nodes.insert(uid, node);
+                vec![uid]
+            }
+            None => {
+                if self.tree.nodes.remove(&uid).is_some() {
+                    self.changed = true;
+                }
+                if children.is_empty() {
+                    self.spliced.remove(&uid);
+                }
+                else {
+                    self.spliced.insert(uid, children.clone());
+                }
+                children.clone()
+            }
+        };
+
+        for child in old_children {
+            if !children.contains(&child) && !self.seen.contains(&child) {
+                self.tree.remove_subtree(child);
+                self.changed = true;
+            }
+        }
+
+        if let Some(parent) = self.stack.last_mut() {
+            parent.children.extend(handed_up);
+        }
+        else {
+            for uid in handed_up {
+                if let Some(node) = self.tree.nodes.get_mut(&uid) {
+                    node.parent = None;
+                }
+                if !self.tree.roots.contains(&uid) {
+                    self.tree.roots.push(uid);
+                    self.changed = true;
+                }
+            }
+            self.end_pass();
+        }
+    }
+
+    fn mark_seen(&mut self, uids: &[WidgetUid]) {
+        for uid in uids {
+            if self.seen.insert(*uid) {
+                let children = self.previous_children(*uid);
+                self.mark_seen(&children);
+            }
+        }
+    }
+
+    fn end_pass(&mut self) {
+        let nodes = &self.tree.nodes;
+        let seen = &self.seen;
+        self.spliced.retain( | uid, children | seen.contains(uid) || children.iter().any( | c | nodes.contains_key(c)));
+        if !self.changed {
+            return
+        }
+        self.changed = false;
+        #[cfg(target_os = "linux")]
+        {
+            if self.bridge.is_none() {
+                self.bridge = Some(crate::atspi::AtspiBridge::start(self.incoming.clone()));
+            }
+            if let Some(bridge) = &self.bridge {
+                bridge.update(&self.tree);
+            }
+        }
+    }
+
+    fn take_request(&mut self, uid: WidgetUid) -> Option<AccessRequest> {
+        let tree = &self.tree;
+        self.requests.extend(self.incoming.lock().unwrap().drain(..).filter( | (uid, _) | tree.nodes.contains_key(uid)));
+        let index = self.requests.iter().position( | (r, _) | *r == uid) ?;
+        Some(self.requests.remove(index).1)
+    }
+}
+
+pub trait AccessCxExt {
+    fn begin_access(&mut self, uid: WidgetUid);
+    fn end_access(&mut self, uid: WidgetUid, node: Option<AccessNode>);
+    // call instead of drawing children when a cached draw list isn't redrawn
+    fn keep_access(&mut self, uid: WidgetUid);
+    fn access_tree(&mut self) -> &AccessTree;
+    // queues a request as if it came from a screen reader, it arrives with the next signal event
+    fn access_request(&mut self, uid: WidgetUid, request: AccessRequest);
+    fn take_access_request(&mut self, uid: WidgetUid) -> Option<AccessRequest>;
+}
+
+impl AccessCxExt for Cx {
+    fn begin_access(&mut self, uid: WidgetUid) {
+        self.global::<CxAccess>().begin(uid)
+    }
+
+    fn end_access(&mut self, uid: WidgetUid, node: Option<AccessNode>) {
+        self.global::<CxAccess>().end(uid, node)
+    }
+
+    fn keep_access(&mut self, uid: WidgetUid) {
+        self.global::<CxAccess>().keep(uid)
+    }
+
+    fn access_tree(&mut self) -> &AccessTree {
+        &self.global::<CxAccess>().tree
+    }
+
+    fn access_request(&mut self, uid: WidgetUid, request: AccessRequest) {
+        self.global::<CxAccess>().incoming.lock().unwrap().push((uid, request));
+        SignalToUI::set_ui_signal();
+    }
+
+    fn take_access_request(&mut self, uid: WidgetUid) -> Option<AccessRequest> {
+        let access = self.global::<CxAccess>();
+        if access.requests.is_empty() && access.incoming.lock().unwrap().is_empty() {
+            return None
+        }
+        access.take_request(uid)
+    }
+}
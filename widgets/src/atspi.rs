@@ -0,0 +1,986 @@
+use {
+    crate::{
+        accessibility::*,
+        makepad_draw::*,
+        widget::WidgetUid,
+    },
+    std::{
+        io::{Read, Write},
+        os::unix::net::UnixStream,
+        sync::{Arc, Mutex},
+    }
+};
+
+// Exports the accessibility tree to screen readers like Orca over AT-SPI.
+// AT-SPI runs on its own D-Bus bus, we speak just enough of the D-Bus wire protocol
+// to register with the registry and answer its questions from a thread.
+// Set NO_AT_BRIDGE=1 to switch it off, like with gtk.
+
+const ROOT_PATH: &str = "/org/a11y/atspi/accessible/root";
+const NODE_PATH: &str = "/org/a11y/atspi/accessible/";
+const NULL_PATH: &str = "/org/a11y/atspi/null";
+
+const METHOD_CALL: u8 = 1;
+const METHOD_RETURN: u8 = 2;
+const ERROR: u8 = 3;
+const SIGNAL: u8 = 4;
+
+#[derive(Default)]
+struct DbusWriter {
+    buf: Vec<u8>
+}
+
+impl DbusWriter {
+    fn pad(&mut self, align: usize) {
+        while !self.buf.len().is_multiple_of(align) {
+            self.buf.push(0);
+        }
+    }
+
+    fn u8(&mut self, v: u8) {
+        self.buf.push(v);
+    }
+
+    fn u32(&mut self, v: u32) {
+        self.pad(4);
+        self.buf.extend_from_slice(&v.to_le_bytes());
+    }
+
+    fn i32(&mut self, v: i32) {
+        self.u32(v as u32);
+    }
+
+    fn bool(&mut self, v: bool) {
+        self.u32(v as u32);
+    }
+
+    fn f64(&mut self, v: f64) {
+        self.pad(8);
+        self.buf.extend_from_slice(&v.to_le_bytes());
+    }
+
+    fn str(&mut self, v: &str) {
+        self.u32(v.len() as u32);
+        self.buf.extend_from_slice(v.as_bytes());
+        self.buf.push(0);
+    }
+
+    fn sig(&mut self, v: &str) {
+        self.u8(v.len() as u8);
+        self.buf.extend_from_slice(v.as_bytes());
+        self.buf.push(0);
+    }
+
+    fn array(&mut self, elem_align: usize, f: impl FnOnce(&mut Self)) {
+        self.u32(0);
+        let len_at = self.buf.len() - 4;
+        self.pad(elem_align);
+        let start = self.buf.len();
+        f(self);
+        let len = (self.buf.len() - start) as u32;
+        self.buf[len_at..len_at + 4].copy_from_slice(&len.to_le_bytes());
+    }
+
+    // an object reference, the (so) struct atspi uses everywhere
+    fn obj_ref(&mut self, bus: &str, path: &str) {
+        self.pad(8);
+        self.str(bus);
+        self.str(path);
+    }
+
+    fn variant(&mut self, sig: &str, f: impl FnOnce(&mut Self)) {
+        self.sig(sig);
+        f(self);
+    }
+}
+
+struct DbusReader<'a> {
+    buf: &'a [u8],
+    pos: usize
+}
+
+impl<'a> DbusReader<'a> {
+    fn pad(&mut self, align: usize) {
+        self.pos = self.pos.div_ceil(align) * align;
+    }
+
+    fn bytes(&mut self, len: usize) -> Option<&'a [u8]> {
+        let bytes = self.buf.get(self.pos..self.pos + len) ?;
+        self.pos += len;
+        Some(bytes)
+    }
+
+    fn u8(&mut self) -> Option<u8> {
+        Some(self.bytes(1)?[0])
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        self.pad(4);
+        Some(u32::from_le_bytes(self.bytes(4)?.try_into().ok()?))
+    }
+
+    fn i32(&mut self) -> Option<i32> {
+        self.u32().map( | v | v as i32)
+    }
+
+    fn f64(&mut self) -> Option<f64> {
+        self.pad(8);
+        Some(f64::from_le_bytes(self.bytes(8)?.try_into().ok()?))
+    }
+
+    fn str(&mut self) -> Option<&'a str> {
+        let len = self.u32()? as usize;
+        let s = std::str::from_utf8(self.bytes(len)?).ok() ?;
+        self.pos += 1;
+        Some(s)
+    }
+
+    fn sig(&mut self) -> Option<&'a str> {
+        let len = self.u8()? as usize;
+        let s = std::str::from_utf8(self.bytes(len)?).ok() ?;
+        self.pos += 1;
+        Some(s)
+    }
+}
+
+#[derive(Default)]
+struct DbusMessage {
+    msg_type: u8,
+    serial: u32,
+    path: String,
+    interface: String,
+    member: String,
+    reply_serial: Option<u32>,
+    sender: String,
+    data: Vec<u8>,
+    body: usize,
+}
+
+impl DbusMessage {
+    fn args(&self) -> DbusReader<'_> {
+        DbusReader {buf: &self.data, pos: self.body}
+    }
+
+    fn read(stream: &mut UnixStream) -> std::io::Result<DbusMessage> {
+        let invalid = | | std::io::Error::new(std::io::ErrorKind::InvalidData, "invalid dbus message");
+        let mut data = vec![0u8; 16];
+        stream.read_exact(&mut data) ?;
+        // we only ever talk to the local bus daemon, which uses our endianness
+        if data[0] != b'l' {
+            return Err(invalid())
+        }
+        let word = | at: usize | u32::from_le_bytes(data[at..at + 4].try_into().unwrap()) as usize;
+        let (body_len, serial, fields_len) = (word(4), word(8) as u32, word(12));
+        let body = (16 + fields_len).div_ceil(8) * 8;
+        data.resize(body + body_len, 0);
+        stream.read_exact(&mut data[16..]) ?;
+
+        let mut msg = DbusMessage {msg_type: data[1], serial, body, ..Default::default()};
+        let mut r = DbusReader {buf: &data, pos: 16};
+        while r.pos < 16 + fields_len {
+            r.pad(8);
+            let code = r.u8().ok_or_else(invalid) ?;
+            let sig = r.sig().ok_or_else(invalid) ?;
+            match sig {
+                "o" | "s" | "g" => {
+                    let v = if sig == "g" {r.sig()} else {r.str()};
+                    let v = v.ok_or_else(invalid)?.to_string();
+                    match code {
+                        1 => msg.path = v,
+                        2 => msg.interface = v,
+                        3 => msg.member = v,
+                        7 => msg.sender = v,
+                        _ => ()
+                    }
+                }
+                "u" => {
+                    let v = r.u32().ok_or_else(invalid) ?;
+                    if code == 5 {
+                        msg.reply_serial = Some(v);
+                    }
+                }
+                _ => return Err(invalid())
+            }
+        }
+        msg.data = data;
+        Ok(msg)
+    }
+}
+
+#[derive(Default)]
+struct DbusHeader<'a> {
+    msg_type: u8,
+    path: Option<&'a str>,
+    interface: Option<&'a str>,
+    member: Option<&'a str>,
+    error_name: Option<&'a str>,
+    reply_serial: Option<u32>,
+    destination: Option<&'a str>,
+    signature: &'a str,
+}
+
+struct DbusConnection {
+    stream: UnixStream,
+    serial: u32,
+}
+
+impl DbusConnection {
+    fn connect(address: &str) -> Option<Self> {
+        for address in address.split(';') {
+            let Some(params) = address.strip_prefix("unix:") else {continue};
+            let mut stream = None;
+            for param in params.split(',') {
+                if let Some(path) = param.strip_prefix("path=") {
+                    stream = UnixStream::connect(path).ok();
+                }
+                else if let Some(name) = param.strip_prefix("abstract=") {
+                    use std::os::linux::net::SocketAddrExt;
+                    stream = std::os::unix::net::SocketAddr::from_abstract_name(name.as_bytes()).ok()
+                        .and_then( | addr | UnixStream::connect_addr(&addr).ok());
+                }
+            }
+            if let Some(stream) = stream {
+                let mut conn = Self {stream, serial: 0};
+                if conn.auth().is_some() {
+                    return Some(conn)
+                }
+            }
+        }
+        None
+    }
+
+    fn auth(&mut self) -> Option<()> {
+        use std::os::unix::fs::MetadataExt;
+        let uid = std::fs::metadata("/proc/self").ok()?.uid().to_string();
+        let hex: String = uid.bytes().map( | b | format!("{:02x}", b)).collect();
+        self.stream.write_all(format!("\0AUTH EXTERNAL {}\r\n", hex).as_bytes()).ok() ?;
+        let mut line = Vec::new();
+        let mut byte = [0u8];
+        while !line.ends_with(b"\r\n") {
+            self.stream.read_exact(&mut byte).ok() ?;
+            line.push(byte[0]);
+        }
+        if !line.starts_with(b"OK") {
+            return None
+        }
+        self.stream.write_all(b"BEGIN\r\n").ok()
+    }
+
+    fn send(&mut self, header: &DbusHeader, body: &[u8]) -> u32 {
+        self.serial += 1;
+        let mut w = DbusWriter::default();
+        w.u8(b'l');
+        w.u8(header.msg_type);
+        w.u8(0);
+        w.u8(1);
+        w.u32(body.len() as u32);
+        w.u32(self.serial);
+        w.array(8, | w | {
+            let mut field = | code: u8, sig: &str, v: &str | {
+                w.pad(8);
+                w.u8(code);
+                w.sig(sig);
+                if sig == "g" {w.sig(v)} else {w.str(v)}
+            };
+            let strings = [
+                (1, "o", header.path),
+                (2, "s", header.interface),
+                (3, "s", header.member),
+                (4, "s", header.error_name),
+                (6, "s", header.destination),
+            ];
+            for (code, sig, v) in strings {
+                if let Some(v) = v {
+                    field(code, sig, v);
+                }
+            }
+            if !header.signature.is_empty() {
+                field(8, "g", header.signature);
+            }
+            if let Some(reply_serial) = header.reply_serial {
+                w.pad(8);
+                w.u8(5);
+                w.sig("u");
+                w.u32(reply_serial);
+            }
+        });
+        w.pad(8);
+        w.buf.extend_from_slice(body);
+        let _ = self.stream.write_all(&w.buf);
+        self.serial
+    }
+
+    // only used while setting up, before anyone knows about us and could call in
+    fn call(&mut self, dest: &str, path: &str, interface: &str, member: &str, signature: &str, body: &[u8]) -> Option<DbusMessage> {
+        let serial = self.send(&DbusHeader {
+            msg_type: METHOD_CALL,
+            path: Some(path),
+            interface: Some(interface),
+            member: Some(member),
+            destination: Some(dest),
+            signature,
+            ..Default::default()
+        }, body);
+        let mut reader = self.stream.try_clone().ok() ?;
+        loop {
+            let msg = DbusMessage::read(&mut reader).ok() ?;
+            if msg.reply_serial == Some(serial) {
+                return if msg.msg_type == METHOD_RETURN {Some(msg)} else {None}
+            }
+        }
+    }
+
+    fn hello(&mut self) -> Option<String> {
+        let reply = self.call("org.freedesktop.DBus", "/org/freedesktop/DBus", "org.freedesktop.DBus", "Hello", "", &[]) ?;
+        let name = reply.args().str()?.to_string();
+        Some(name)
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Target<'a> {
+    App,
+    Node(WidgetUid, &'a AccessNode)
+}
+
+struct BridgeState {
+    tree: AccessTree,
+    conn: Option<DbusConnection>,
+    unique_name: String,
+    desktop: (String, String),
+    embed_serial: u32,
+    requests: AccessRequestQueue,
+}
+
+type Reply = Result<(&'static str, DbusWriter), (&'static str, String)>;
+
+fn node_path(uid: WidgetUid) -> String {
+    format!("{}{}", NODE_PATH, uid.0)
+}
+
+fn atspi_role(node: &AccessNode) -> u32 {
+    match node.role {
+        AccessRole::Window => 23,
+        AccessRole::Group => 39,
+        AccessRole::Button => 43,
+        AccessRole::CheckBox => 7,
+        AccessRole::RadioButton => 44,
+        AccessRole::Label => 29,
+        AccessRole::Link => 88,
+        AccessRole::TextInput if node.states.secret => 40,
+        AccessRole::TextInput => 79,
+        AccessRole::Slider => 51,
+        AccessRole::ComboBox => 11,
+        AccessRole::List => 31,
+        AccessRole::ListItem => 32,
+        AccessRole::TabList => 38,
+        AccessRole::Tab => 37,
+        AccessRole::Tree => 65,
+        AccessRole::TreeItem => 91,
+        AccessRole::Image => 27,
+        AccessRole::Menu => 33,
+        AccessRole::MenuItem => 35,
+        AccessRole::Dialog => 16,
+        AccessRole::Tooltip => 64,
+        AccessRole::Alert => 2,
+    }
+}
+
+fn atspi_states(node: &AccessNode) -> u64 {
+    let s = &node.states;
+    let mut bits = 1 << 25 | 1 << 30; // showing, visible
+    let mut set = | on: bool, bit: u32 | if on {bits |= 1u64 << bit};
+    set(!s.disabled, 8); // enabled
+    set(!s.disabled, 24); // sensitive
+    set(s.focusable, 11);
+    set(s.focused, 12);
+    set(s.editable, 7);
+    set(s.editable, 26); // single line
+    set(s.checked.is_some(), 41); // checkable
+    set(s.checked == Some(true), 4);
+    set(s.expanded.is_some(), 9);
+    set(s.expanded == Some(true), 10);
+    set(s.selected, 23);
+    set(node.role == AccessRole::Window, 1); // active
+    bits
+}
+
+fn text_of(node: &AccessNode) -> Option<&str> {
+    match &node.value {
+        AccessValue::Text(text) => Some(text),
+        _ if node.role == AccessRole::Label => Some(&node.label),
+        _ => None
+    }
+}
+
+impl BridgeState {
+    fn target(&self, path: &str) -> Option<Target<'_>> {
+        if path == ROOT_PATH {
+            return Some(Target::App)
+        }
+        let uid = WidgetUid(path.strip_prefix(NODE_PATH)?.parse().ok()?);
+        self.tree.node(uid).map( | node | Target::Node(uid, node))
+    }
+
+    fn write_ref(&self, w: &mut DbusWriter, uid: Option<WidgetUid>) {
+        match uid {
+            Some(uid) => w.obj_ref(&self.unique_name, &node_path(uid)),
+            None => w.obj_ref(&self.unique_name, ROOT_PATH)
+        }
+    }
+
+    fn children(&self, target: Target) -> Vec<WidgetUid> {
+        match target {
+            Target::App => self.tree.roots().to_vec(),
+            Target::Node(_, node) => node.children.clone()
+        }
+    }
+
+    fn index_in_parent(&self, target: Target) -> i32 {
+        match target {
+            Target::App => -1,
+            Target::Node(uid, node) => {
+                let siblings = match node.parent.and_then( | p | self.tree.node(p)) {
+                    Some(parent) => &parent.children[..],
+                    None => self.tree.roots()
+                };
+                siblings.iter().position( | s | *s == uid).map( | i | i as i32).unwrap_or(-1)
+            }
+        }
+    }
+
+    fn interfaces(&self, target: Target) -> Vec<&'static str> {
+        let mut out = vec!["org.a11y.atspi.Accessible"];
+        match target {
+            Target::App => out.push("org.a11y.atspi.Application"),
+            Target::Node(_, node) => {
+                out.push("org.a11y.atspi.Component");
+                if !node.actions.is_empty() {
+                    out.push("org.a11y.atspi.Action");
+                }
+                if let AccessValue::Number {..} = node.value {
+                    out.push("org.a11y.atspi.Value");
+                }
+                if text_of(node).is_some() {
+                    out.push("org.a11y.atspi.Text");
+                }
+                if node.states.editable {
+                    out.push("org.a11y.atspi.EditableText");
+                }
+            }
+        }
+        out
+    }
+
+    // the window relative rect of a node moved into the requested coordinate space
+    fn extents(&self, target: Target, coord_type: u32) -> Rect {
+        let Target::Node(_, node) = target else {return Rect::default()};
+        if node.role == AccessRole::Window {
+            if coord_type == 0 {
+                return node.rect
+            }
+            return Rect {pos: DVec2::default(), size: node.rect.size}
+        }
+        let mut rect = node.rect;
+        match coord_type {
+            0 => {
+                let mut parent = node.parent;
+                while let Some(p) = parent.and_then( | p | self.tree.node(p)) {
+                    if p.role == AccessRole::Window {
+                        rect.pos += p.rect.pos;
+                        break;
+                    }
+                    parent = p.parent;
+                }
+            }
+            2 => if let Some(p) = node.parent.and_then( | p | self.tree.node(p)) {
+                if p.role != AccessRole::Window {
+                    rect.pos -= p.rect.pos;
+                }
+            }
+            _ => ()
+        }
+        // makepad works in logical pixels, on scaled screens these are off by the dpi factor
+        rect
+    }
+
+    fn property(&self, target: Target, interface: &str, name: &str, w: &mut DbusWriter) -> bool {
+        let node = if let Target::Node(_, node) = target {Some(node)} else {None};
+        match (interface, name, node) {
+            ("org.a11y.atspi.Accessible", "Name", _) => {
+                let name = match node {
+                    Some(node) => node.label.clone(),
+                    None => std::env::current_exe().ok()
+                        .and_then( | exe | exe.file_stem().map( | s | s.to_string_lossy().to_string()))
+                        .unwrap_or_default()
+                };
+                w.variant("s", | w | w.str(&name))
+            }
+            ("org.a11y.atspi.Accessible", "Description" | "HelpText" | "AccessibleId" | "Locale", _) => w.variant("s", | w | w.str("")),
+            ("org.a11y.atspi.Accessible", "ChildCount", _) => w.variant("i", | w | w.i32(self.children(target).len() as i32)),
+            ("org.a11y.atspi.Accessible", "Parent", None) => {
+                let (bus, path) = &self.desktop;
+                w.variant("(so)", | w | w.obj_ref(bus, path))
+            }
+            ("org.a11y.atspi.Accessible", "Parent", Some(node)) => w.variant("(so)", | w | self.write_ref(w, node.parent)),
+            ("org.a11y.atspi.Application", "ToolkitName", None) => w.variant("s", | w | w.str("makepad")),
+            ("org.a11y.atspi.Application", "Version", None) => w.variant("s", | w | w.str(env!("CARGO_PKG_VERSION"))),
+            ("org.a11y.atspi.Application", "AtspiVersion", None) => w.variant("s", | w | w.str("2.1")),
+            ("org.a11y.atspi.Application", "Id", None) => w.variant("i", | w | w.i32(0)),
+            ("org.a11y.atspi.Action", "NActions", Some(node)) => w.variant("i", | w | w.i32(node.actions.len() as i32)),
+            ("org.a11y.atspi.Value", _, Some(AccessNode {value: AccessValue::Number {value, min, max, step}, ..})) => {
+                let v = match name {
+                    "CurrentValue" => *value,
+                    "MinimumValue" => *min,
+                    "MaximumValue" => *max,
+                    "MinimumIncrement" => *step,
+                    _ => return false
+                };
+                w.variant("d", | w | w.f64(v))
+            }
+            ("org.a11y.atspi.Text", "CharacterCount", Some(node)) => w.variant("i", | w | w.i32(text_of(node).unwrap_or("").chars().count() as i32)),
+            ("org.a11y.atspi.Text", "CaretOffset", Some(_)) => w.variant("i", | w | w.i32(0)),
+            _ => return false
+        }
+        true
+    }
+
+    fn property_names(interface: &str) -> &'static [&'static str] {
+        match interface {
+            "org.a11y.atspi.Accessible" => &["Name", "Description", "Parent", "ChildCount", "Locale", "AccessibleId", "HelpText"],
+            "org.a11y.atspi.Application" => &["ToolkitName", "Version", "AtspiVersion", "Id"],
+            "org.a11y.atspi.Action" => &["NActions"],
+            "org.a11y.atspi.Value" => &["CurrentValue", "MinimumValue", "MaximumValue", "MinimumIncrement"],
+            "org.a11y.atspi.Text" => &["CharacterCount", "CaretOffset"],
+            _ => &[]
+        }
+    }
+
+    fn request(&self, uid: WidgetUid, request: AccessRequest) {
+        self.requests.lock().unwrap().push((uid, request));
+        SignalToUI::set_ui_signal();
+    }
+
+    fn handle_call(&self, msg: &DbusMessage) -> Reply {
+        let unknown_method = | | Err(("org.freedesktop.DBus.Error.UnknownMethod", format!("{}.{} is not supported", msg.interface, msg.member)));
+        let invalid_args = | | ("org.freedesktop.DBus.Error.InvalidArgs", "Invalid arguments".to_string());
+        let mut w = DbusWriter::default();
+        match (msg.interface.as_str(), msg.member.as_str()) {
+            ("org.freedesktop.DBus.Peer", "Ping") => return Ok(("", w)),
+            ("org.freedesktop.DBus.Introspectable", "Introspect") => {
+                w.str("<node/>");
+                return Ok(("s", w))
+            }
+            _ => ()
+        }
+        let Some(target) = self.target(&msg.path) else {
+            return Err(("org.freedesktop.DBus.Error.UnknownObject", format!("{} does not exist", msg.path)))
+        };
+        let node = if let Target::Node(uid, node) = target {Some((uid, node))} else {None};
+        let mut args = msg.args();
+        let sig = match (msg.interface.as_str(), msg.member.as_str()) {
+            ("org.freedesktop.DBus.Properties", "Get") => {
+                let (interface, name) = args.str().zip(args.str()).ok_or_else(invalid_args) ?;
+                if !self.property(target, interface, name, &mut w) {
+                    return Err(("org.freedesktop.DBus.Error.UnknownProperty", format!("{}.{} does not exist", interface, name)))
+                }
+                "v"
+            }
+            ("org.freedesktop.DBus.Properties", "GetAll") => {
+                let interface = args.str().ok_or_else(invalid_args) ?;
+                w.array(8, | w | {
+                    for name in Self::property_names(interface) {
+                        // not every node has every property of an interface
+                        if self.property(target, interface, name, &mut DbusWriter::default()) {
+                            w.pad(8);
+                            w.str(name);
+                            self.property(target, interface, name, w);
+                        }
+                    }
+                });
+                "a{sv}"
+            }
+            ("org.freedesktop.DBus.Properties", "Set") => {
+                let (interface, name) = args.str().zip(args.str()).ok_or_else(invalid_args) ?;
+                let sig = args.sig().ok_or_else(invalid_args) ?;
+                match (interface, name, sig, node) {
+                    ("org.a11y.atspi.Value", "CurrentValue", "d", Some((uid, _))) => {
+                        let value = args.f64().ok_or_else(invalid_args) ?;
+                        self.request(uid, AccessRequest::SetValue(value));
+                    }
+                    // the registry hands us an id, we don't need it
+                    ("org.a11y.atspi.Application", "Id", _, None) => (),
+                    _ => return Err(("org.freedesktop.DBus.Error.PropertyReadOnly", format!("{}.{} can't be set", interface, name)))
+                }
+                ""
+            }
+            ("org.a11y.atspi.Accessible", "GetChildAtIndex") => {
+                let index = args.i32().ok_or_else(invalid_args) ?;
+                match self.children(target).get(index as usize) {
+                    Some(child) => self.write_ref(&mut w, Some(*child)),
+                    None => w.obj_ref(&self.unique_name, NULL_PATH)
+                }
+                "(so)"
+            }
+            ("org.a11y.atspi.Accessible", "GetChildren") => {
+                let children = self.children(target);
+                w.array(8, | w | for child in children {
+                    self.write_ref(w, Some(child));
+                });
+                "a(so)"
+            }
+            ("org.a11y.atspi.Accessible", "GetIndexInParent") => {
+                w.i32(self.index_in_parent(target));
+                "i"
+            }
+            ("org.a11y.atspi.Accessible", "GetRelationSet") => {
+                w.array(8, | _ | ());
+                "a(ua(so))"
+            }
+            ("org.a11y.atspi.Accessible", "GetRole") => {
+                w.u32(node.map( | (_, node) | atspi_role(node)).unwrap_or(75));
+                "u"
+            }
+            ("org.a11y.atspi.Accessible", "GetRoleName" | "GetLocalizedRoleName") => {
+                w.str(node.map( | (_, node) | node.role.name()).unwrap_or("application"));
+                "s"
+            }
+            ("org.a11y.atspi.Accessible", "GetState") => {
+                let bits = node.map( | (_, node) | atspi_states(node)).unwrap_or(0);
+                w.array(4, | w | {
+                    w.u32(bits as u32);
+                    w.u32((bits >> 32) as u32);
+                });
+                "au"
+            }
+            ("org.a11y.atspi.Accessible", "GetAttributes") => {
+                w.array(8, | w | {
+                    w.pad(8);
+                    w.str("toolkit");
+                    w.str("makepad");
+                });
+                "a{ss}"
+            }
+            ("org.a11y.atspi.Accessible", "GetApplication") => {
+                self.write_ref(&mut w, None);
+                "(so)"
+            }
+            ("org.a11y.atspi.Accessible", "GetInterfaces") => {
+                let interfaces = self.interfaces(target);
+                w.array(4, | w | for interface in interfaces {
+                    w.str(interface);
+                });
+                "as"
+            }
+            ("org.a11y.atspi.Component", "GetExtents") => {
+                let rect = self.extents(target, args.u32().unwrap_or(0));
+                w.pad(8);
+                for v in [rect.pos.x, rect.pos.y, rect.size.x, rect.size.y] {
+                    w.i32(v as i32);
+                }
+                "(iiii)"
+            }
+            ("org.a11y.atspi.Component", "GetPosition") => {
+                let rect = self.extents(target, args.u32().unwrap_or(0));
+                w.i32(rect.pos.x as i32);
+                w.i32(rect.pos.y as i32);
+                "ii"
+            }
+            ("org.a11y.atspi.Component", "GetSize") => {
+                let rect = self.extents(target, 1);
+                w.i32(rect.size.x as i32);
+                w.i32(rect.size.y as i32);
+                "ii"
+            }
+            ("org.a11y.atspi.Component", "Contains") => {
+                let (x, y) = args.i32().zip(args.i32()).ok_or_else(invalid_args) ?;
+                let rect = self.extents(target, args.u32().unwrap_or(0));
+                w.bool(rect.contains(dvec2(x as f64, y as f64)));
+                "b"
+            }
+            ("org.a11y.atspi.Component", "GetAccessibleAtPoint") => {
+                let (x, y) = args.i32().zip(args.i32()).ok_or_else(invalid_args) ?;
+                let coord_type = args.u32().unwrap_or(0);
+                let pos = dvec2(x as f64, y as f64);
+                let hit = self.children(target).into_iter().rev().find( | child | {
+                    self.tree.node(*child).map( | c | self.extents(Target::Node(*child, c), coord_type).contains(pos)).unwrap_or(false)
+                });
+                match hit {
+                    Some(child) => self.write_ref(&mut w, Some(child)),
+                    None => w.obj_ref(&self.unique_name, NULL_PATH)
+                }
+                "(so)"
+            }
+            ("org.a11y.atspi.Component", "GetLayer") => {
+                w.u32(if matches!(node, Some((_, n)) if n.role == AccessRole::Window) {7} else {3});
+                "u"
+            }
+            ("org.a11y.atspi.Component", "GetAlpha") => {
+                w.f64(1.0);
+                "d"
+            }
+            ("org.a11y.atspi.Component", "GrabFocus") => {
+                let Some((uid, node)) = node else {return unknown_method()};
+                let focusable = node.states.focusable;
+                if focusable {
+                    self.request(uid, AccessRequest::Action(AccessAction::Focus));
+                }
+                w.bool(focusable);
+                "b"
+            }
+            ("org.a11y.atspi.Action", "GetActions") => {
+                let Some((_, node)) = node else {return unknown_method()};
+                w.array(8, | w | for action in &node.actions {
+                    w.pad(8);
+                    w.str(action.name());
+                    w.str(action.name());
+                    w.str("");
+                });
+                "a(sss)"
+            }
+            ("org.a11y.atspi.Action", "GetName" | "GetLocalizedName" | "GetDescription" | "GetKeyBinding") => {
+                let Some((_, node)) = node else {return unknown_method()};
+                let index = args.i32().ok_or_else(invalid_args) ?;
+                let name = node.actions.get(index as usize).map( | a | a.name()).unwrap_or("");
+                w.str(if msg.member == "GetKeyBinding" {""} else {name});
+                "s"
+            }
+            ("org.a11y.atspi.Action", "DoAction") => {
+                let Some((uid, node)) = node else {return unknown_method()};
+                let index = args.i32().ok_or_else(invalid_args) ?;
+                let action = node.actions.get(index as usize).copied();
+                if let Some(action) = action {
+                    self.request(uid, AccessRequest::Action(action));
+                }
+                w.bool(action.is_some());
+                "b"
+            }
+            ("org.a11y.atspi.Text", "GetText") => {
+                let Some((_, node)) = node else {return unknown_method()};
+                let (start, end) = args.i32().zip(args.i32()).ok_or_else(invalid_args) ?;
+                let text = text_of(node).unwrap_or("");
+                let end = if end < 0 {usize::MAX} else {end as usize};
+                let text: String = text.chars().skip(start.max(0) as usize).take(end.saturating_sub(start.max(0) as usize)).collect();
+                w.str(&text);
+                "s"
+            }
+            ("org.a11y.atspi.EditableText", "SetTextContents") => {
+                let Some((uid, _)) = node else {return unknown_method()};
+                let text = args.str().ok_or_else(invalid_args) ?;
+                self.request(uid, AccessRequest::SetText(text.to_string()));
+                w.bool(true);
+                "b"
+            }
+            _ => return unknown_method()
+        };
+        Ok((sig, w))
+    }
+
+    fn reply(&mut self, msg: &DbusMessage, reply: Reply) {
+        let Some(conn) = &mut self.conn else {return};
+        let (header, body) = match &reply {
+            Ok((sig, body)) => (DbusHeader {
+                msg_type: METHOD_RETURN,
+                reply_serial: Some(msg.serial),
+                destination: Some(&msg.sender),
+                signature: sig,
+                ..Default::default()
+            }, body.buf.clone()),
+            Err((name, text)) => {
+                let mut w = DbusWriter::default();
+                w.str(text);
+                (DbusHeader {
+                    msg_type: ERROR,
+                    error_name: Some(name),
+                    reply_serial: Some(msg.serial),
+                    destination: Some(&msg.sender),
+                    signature: "s",
+                    ..Default::default()
+                }, w.buf)
+            }
+        };
+        conn.send(&header, &body);
+    }
+
+    // atspi events carry a detail string and two detail numbers next to the value
+    fn emit(&mut self, path: &str, member: &str, (detail, detail1, detail2): (&str, i32, i32), sig: &str, value: impl FnOnce(&mut DbusWriter)) {
+        let Some(conn) = &mut self.conn else {return};
+        let mut w = DbusWriter::default();
+        w.str(detail);
+        w.i32(detail1);
+        w.i32(detail2);
+        w.variant(sig, value);
+        w.array(8, | _ | ());
+        conn.send(&DbusHeader {
+            msg_type: SIGNAL,
+            path: Some(path),
+            interface: Some("org.a11y.atspi.Event.Object"),
+            member: Some(member),
+            signature: "siiva{sv}",
+            ..Default::default()
+        }, &w.buf);
+    }
+
+    fn parent_path(parent: Option<WidgetUid>) -> String {
+        parent.map(node_path).unwrap_or(ROOT_PATH.to_string())
+    }
+
+    fn index_of(tree: &AccessTree, parent: Option<WidgetUid>, uid: WidgetUid) -> i32 {
+        let siblings = match parent.and_then( | p | tree.node(p)) {
+            Some(parent) => &parent.children[..],
+            None => tree.roots()
+        };
+        siblings.iter().position( | s | *s == uid).map( | i | i as i32).unwrap_or(-1)
+    }
+
+    fn emit_changes(&mut self, old: &AccessTree) {
+        let tree = std::mem::take(&mut self.tree);
+        let bus = self.unique_name.clone();
+        for change in tree.diff(old) {
+            match change {
+                AccessChange::Removed(uid) => {
+                    let parent = old.node(uid).unwrap().parent;
+                    // only report the top of a removed subtree
+                    if parent.is_some() && tree.node(parent.unwrap()).is_none() {
+                        continue;
+                    }
+                    let index = Self::index_of(old, parent, uid);
+                    self.emit(&Self::parent_path(parent), "ChildrenChanged", ("remove", index, 0), "(so)", | w | w.obj_ref(&bus, &node_path(uid)));
+                }
+                AccessChange::Added(uid) => {
+                    let parent = tree.node(uid).unwrap().parent;
+                    if parent.is_some() && old.node(parent.unwrap()).is_none() {
+                        continue;
+                    }
+                    let index = Self::index_of(&tree, parent, uid);
+                    self.emit(&Self::parent_path(parent), "ChildrenChanged", ("add", index, 0), "(so)", | w | w.obj_ref(&bus, &node_path(uid)));
+                }
+                AccessChange::Changed(uid) => {
+                    let (node, old_node) = (tree.node(uid).unwrap(), old.node(uid).unwrap());
+                    let path = node_path(uid);
+                    if node.label != old_node.label {
+                        self.emit(&path, "PropertyChange", ("accessible-name", 0, 0), "s", | w | w.str(&node.label));
+                    }
+                    if node.value != old_node.value {
+                        match (&node.value, &old_node.value) {
+                            (AccessValue::Text(text), old_value) => {
+                                if let AccessValue::Text(old_text) = old_value {
+                                    self.emit(&path, "TextChanged", ("delete", 0, old_text.chars().count() as i32), "s", | w | w.str(old_text));
+                                }
+                                self.emit(&path, "TextChanged", ("insert", 0, text.chars().count() as i32), "s", | w | w.str(text));
+                            }
+                            (AccessValue::Number {value, ..}, _) => {
+                                self.emit(&path, "PropertyChange", ("accessible-value", 0, 0), "d", | w | w.f64(*value));
+                            }
+                            _ => ()
+                        }
+                    }
+                    let (s, o) = (node.states, old_node.states);
+                    for (on, was, name) in [
+                        (s.focused, o.focused, "focused"),
+                        (s.checked == Some(true), o.checked == Some(true), "checked"),
+                        (s.expanded == Some(true), o.expanded == Some(true), "expanded"),
+                        (s.selected, o.selected, "selected"),
+                        (!s.disabled, !o.disabled, "enabled"),
+                    ] {
+                        if on != was {
+                            self.emit(&path, "StateChanged", (name, on as i32, 0), "i", | w | w.i32(0));
+                        }
+                    }
+                }
+                AccessChange::Reordered(_) => ()
+            }
+        }
+        self.tree = tree;
+    }
+}
+
+pub struct AtspiBridge {
+    state: Arc<Mutex<BridgeState>>
+}
+
+impl AtspiBridge {
+    pub fn start(requests: AccessRequestQueue) -> Self {
+        let state = Arc::new(Mutex::new(BridgeState {
+            tree: AccessTree::default(),
+            conn: None,
+            unique_name: String::new(),
+            desktop: ("org.a11y.atspi.Registry".to_string(), ROOT_PATH.to_string()),
+            embed_serial: 0,
+            requests,
+        }));
+        let thread_state = state.clone();
+        std::thread::spawn(move || {
+            Self::run(thread_state);
+        });
+        Self {state}
+    }
+
+    pub fn update(&self, tree: &AccessTree) {
+        let mut state = self.state.lock().unwrap();
+        let old = std::mem::replace(&mut state.tree, tree.clone());
+        if state.conn.is_some() {
+            state.emit_changes(&old);
+        }
+    }
+
+    fn a11y_bus_address() -> Option<String> {
+        if let Ok(address) = std::env::var("AT_SPI_BUS_ADDRESS") {
+            return Some(address)
+        }
+        let session_address = std::env::var("DBUS_SESSION_BUS_ADDRESS").ok()
+            .or_else( | | std::env::var("XDG_RUNTIME_DIR").ok().map( | dir | format!("unix:path={}/bus", dir))) ?;
+        let mut session = DbusConnection::connect(&session_address) ?;
+        session.hello() ?;
+        let reply = session.call("org.a11y.Bus", "/org/a11y/bus", "org.a11y.Bus", "GetAddress", "", &[]) ?;
+        let address = reply.args().str()?.to_string();
+        Some(address)
+    }
+
+    fn run(state: Arc<Mutex<BridgeState>>) -> Option<()> {
+        if std::env::var("NO_AT_BRIDGE").map( | v | v == "1").unwrap_or(false) {
+            return None
+        }
+        let address = Self::a11y_bus_address() ?;
+        let mut conn = DbusConnection::connect(&address) ?;
+        let unique_name = conn.hello() ?;
+        let mut reader = conn.stream.try_clone().ok() ?;
+
+        let mut body = DbusWriter::default();
+        body.obj_ref(&unique_name, ROOT_PATH);
+        let embed_serial = conn.send(&DbusHeader {
+            msg_type: METHOD_CALL,
+            path: Some(ROOT_PATH),
+            interface: Some("org.a11y.atspi.Socket"),
+            member: Some("Embed"),
+            destination: Some("org.a11y.atspi.Registry"),
+            signature: "(so)",
+            ..Default::default()
+        }, &body.buf);
+        {
+            let mut state = state.lock().unwrap();
+            state.unique_name = unique_name;
+            state.embed_serial = embed_serial;
+            state.conn = Some(conn);
+        }
+
+        loop {
+            let msg = DbusMessage::read(&mut reader).ok() ?;
+            let mut state = state.lock().unwrap();
+            match msg.msg_type {
+                METHOD_CALL => {
+                    let reply = state.handle_call(&msg);
+                    state.reply(&msg, reply);
+                }
+                METHOD_RETURN if msg.reply_serial == Some(state.embed_serial) => {
+                    let mut args = msg.args();
+                    args.pad(8);
+                    if let Some((bus, path)) = args.str().zip(args.str()) {
+                        state.desktop = (bus.to_string(), path.to_string());
+                    }
+                }
+                _ => ()
+            }
+        }
+    }
+}
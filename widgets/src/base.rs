@@ -15,11 +15,14 @@ live_design!{
     import crate::fold_header::FoldHeaderBase;
     import crate::image::ImageBase;
     import crate::rotated_image::RotatedImageBase;
+    import crate::svg::SvgBase;
+    import crate::view_3d::View3DBase;
     import crate::video::VideoBase;
     import crate::popup_menu::PopupMenuBase;
     import crate::label::LabelBase;
     import crate::link_label::LinkLabelBase;
     import crate::portal_list::PortalListBase;
+    import crate::data_grid::DataGridBase;
     import crate::flat_list::FlatListBase;
     import crate::scroll_bars::ScrollBarsBase;
     import crate::view::ViewBase;
@@ -42,6 +45,9 @@ live_design!{
     import crate::stack_navigation::StackNavigationViewBase;
     import crate::stack_navigation::StackNavigationBase;
     import crate::expandable_panel::ExpandablePanelBase;
+    import crate::modal::ModalBase;
+    import crate::tooltip::TooltipBase;
+    import crate::toast::ToastBase;
     import crate::keyboard_view::KeyboardViewBase;
     import crate::window_menu::WindowMenuBase;
     import crate::html::HtmlBase;
@@ -103,6 +109,44 @@ live_design!{
         }
     }
     
+    Svg = <SvgBase> {
+        width: Fit
+        height: Fit
+        
+        draw_svg: {
+            texture image: texture2d
+            instance opacity: 1.0
+            
+            fn pixel(self) -> vec4 {
+                let color = sample2d(self.image, self.pos).xyzw;
+                return Pal::premul(vec4(color.xyz, color.w * self.opacity))
+            }
+        }
+    }
+    
+    View3D = <View3DBase> {
+        width: Fill
+        height: Fill
+        clear_color: #0000
+        
+        draw_bg: {
+            texture image: texture2d
+            varying scale: vec2
+            varying shift: vec2
+            fn vertex(self) -> vec4 {
+                let dpi = self.dpi_factor;
+                let ceil_size = ceil(self.rect_size * dpi) / dpi
+                let floor_pos = floor(self.rect_pos * dpi) / dpi
+                self.scale = self.rect_size / ceil_size;
+                self.shift = (self.rect_pos - floor_pos) / ceil_size;
+                return self.clip_and_transform_vertex(self.rect_pos, self.rect_size)
+            }
+            fn pixel(self) -> vec4 {
+                return sample2d_rt(self.image, self.pos * self.scale + self.shift);
+            }
+        }
+    }
+    
     RotatedImage = <RotatedImageBase> {
         width: Fit
         height: Fit
@@ -683,10 +727,13 @@ live_design!{
     FoldHeaderBase = <FoldHeaderBase> {}
     ImageBase = <ImageBase> {}
     RotatedImageBase = <RotatedImageBase> {}
+    SvgBase = <SvgBase> {}
+    View3DBase = <View3DBase> {}
     VideoBase = <VideoBase> {}
     LabelBase = <LabelBase> {}
     LinkLabelBase = <LinkLabelBase> {}
     PortalListBase = <PortalListBase> {}
+    DataGridBase = <DataGridBase> {}
     FlatListBase = <FlatListBase>{}
     NavControlBase = <NavControlBase> {}
     PopupMenuBase = <PopupMenuBase> {}
@@ -707,4 +754,7 @@ live_design!{
     StackNavigationViewBase = <StackNavigationViewBase>{}
     StackNavigationBase = <StackNavigationBase>{}
     ExpandablePanelBase = <ExpandablePanelBase>{}
+    ModalBase = <ModalBase>{}
+    TooltipBase = <TooltipBase>{}
+    ToastBase = <ToastBase>{}
 }
@@ -1,4 +1,4 @@
-use crate::{makepad_derive_widget::*, makepad_draw::*, widget::*};
+use crate::{makepad_derive_widget::*, makepad_draw::*, widget::*, accessibility::*};
 live_design! {
     ButtonBase = {{Button}} {}
 }
@@ -35,12 +35,18 @@ pub struct Button {
 
     #[live(true)]
     grab_key_focus: bool,
+    #[live]
+    nav_order: NavOrder,
 
     #[live]
     pub text: RcStringMut,
 }
 
 impl Widget for Button {
+    fn widget_area(&self) -> Area {
+        self.draw_bg.area()
+    }
+    
     fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
         let uid = self.widget_uid();
         if self.animator_handle_event(cx, event).must_redraw() {
@@ -75,6 +81,11 @@ impl Widget for Button {
                     self.animator_play(cx, id!(hover.off));
                 }
             }
+            Hit::KeyDown(ke) if matches!(ke.key_code, KeyCode::ReturnKey | KeyCode::NumpadEnter | KeyCode::Space) => {
+                cx.widget_action(uid, &scope.path, ButtonAction::Pressed);
+                cx.widget_action(uid, &scope.path, ButtonAction::Clicked);
+                cx.widget_action(uid, &scope.path, ButtonAction::Released);
+            }
             _ => (),
         }
     }
@@ -85,6 +96,9 @@ impl Widget for Button {
             .draw_walk(cx, self.label_walk, Align::default(), self.text.as_ref());
         self.draw_icon.draw_walk(cx, self.icon_walk);
         self.draw_bg.end(cx);
+        if self.grab_key_focus {
+            cx.add_nav_stop_ordered(self.draw_bg.area(), NavRole::Button, Margin::default(), self.nav_order);
+        }
         DrawStep::done()
     }
 
@@ -95,6 +109,33 @@ impl Widget for Button {
     fn set_text(&mut self, v: &str) {
         self.text.as_mut_empty().push_str(v);
     }
+
+    fn access_node(&self, cx: &Cx) -> Option<AccessNode> {
+        let area = self.draw_bg.area();
+        let mut node = AccessNode::new(AccessRole::Button)
+            .with_label(self.text.as_ref())
+            .with_rect(area.rect(cx))
+            .with_action(AccessAction::Click);
+        if self.grab_key_focus {
+            node = node.with_focus(cx.has_key_focus(area));
+        }
+        Some(node)
+    }
+
+    fn handle_access(&mut self, cx: &mut Cx, request: &AccessRequest, scope: &mut Scope) {
+        match request {
+            AccessRequest::Action(AccessAction::Click) => {
+                let uid = self.widget_uid();
+                cx.widget_action(uid, &scope.path, ButtonAction::Pressed);
+                cx.widget_action(uid, &scope.path, ButtonAction::Clicked);
+                cx.widget_action(uid, &scope.path, ButtonAction::Released);
+            }
+            AccessRequest::Action(AccessAction::Focus) if self.grab_key_focus => {
+                cx.set_key_focus(self.draw_bg.area());
+            }
+            _ => ()
+        }
+    }
 }
 
 impl ButtonRef {
@@ -3,6 +3,7 @@ use {
         makepad_derive_widget::*,
         makepad_draw::*,
         widget::*,
+        accessibility::*,
     }
 };
 
@@ -49,6 +50,7 @@ pub struct CheckBox {
     #[live] text: RcStringMut,
     
     #[live] bind: String,
+    #[live] nav_order: NavOrder,
 }
 
 #[derive(Clone, Debug, DefaultNone)]
@@ -71,10 +73,20 @@ impl CheckBox {
         self.draw_text.draw_walk(cx, self.label_walk, self.label_align, self.text.as_ref());
         self.draw_icon.draw_walk(cx, self.icon_walk);
         self.draw_check.end(cx);
+        cx.add_nav_stop_ordered(self.draw_check.area(), NavRole::CheckBox, Margin::default(), self.nav_order);
+    }
+    
+    fn toggle(&mut self, cx: &mut Cx, scope: &mut Scope) {
+        let selected = !self.animator_in_state(cx, id!(selected.on));
+        self.animator_toggle(cx, selected, Animate::Yes, id!(selected.on), id!(selected.off));
+        cx.widget_action(self.widget_uid(), &scope.path, CheckBoxAction::Change(selected));
     }
 }
 
 impl Widget for CheckBox {
+    fn widget_area(&self) -> Area {
+        self.draw_check.area()
+    }
     
     fn widget_to_data(&self, _cx: &mut Cx, actions: &Actions, nodes: &mut LiveNodeVec, path: &[LiveId]) -> bool {
         match actions.find_widget_action_cast(self.widget_uid()) {
@@ -99,6 +111,12 @@ impl Widget for CheckBox {
         self.animator_handle_event(cx, event);
                 
         match event.hits(cx, self.draw_check.area()) {
+            Hit::KeyFocus(_) => {
+                self.animator_play(cx, id!(focus.on));
+            }
+            Hit::KeyFocusLost(_) => {
+                self.animator_play(cx, id!(focus.off));
+            }
             Hit::FingerHoverIn(_) => {
                 cx.set_cursor(MouseCursor::Hand);
                 self.animator_play(cx, id!(hover.on));
@@ -107,6 +125,7 @@ impl Widget for CheckBox {
                 self.animator_play(cx, id!(hover.off));
             },
             Hit::FingerDown(_fe) => {
+                cx.set_key_focus(self.draw_check.area());
                 if self.animator_in_state(cx, id!(selected.on)) {
                     self.animator_play(cx, id!(selected.off));
                     cx.widget_action(uid, &scope.path, CheckBoxAction::Change(false));
@@ -121,6 +140,9 @@ impl Widget for CheckBox {
             }
             Hit::FingerMove(_fe) => {
                                 
+            }
+            Hit::KeyDown(ke) if matches!(ke.key_code, KeyCode::ReturnKey | KeyCode::NumpadEnter | KeyCode::Space) => {
+                self.toggle(cx, scope);
             }
             _ => ()
         }
@@ -138,6 +160,20 @@ impl Widget for CheckBox {
     fn set_text(&mut self, v: &str) {
         self.text.as_mut_empty().push_str(v);
     }
+    
+    fn access_node(&self, cx: &Cx) -> Option<AccessNode> {
+        Some(AccessNode::new(AccessRole::CheckBox)
+            .with_label(self.text.as_ref())
+            .with_rect(self.draw_check.area().rect(cx))
+            .with_checked(self.animator_in_state(cx, id!(selected.on)))
+            .with_action(AccessAction::Click))
+    }
+    
+    fn handle_access(&mut self, cx: &mut Cx, request: &AccessRequest, scope: &mut Scope) {
+        if let AccessRequest::Action(AccessAction::Click) = request {
+            self.toggle(cx, scope);
+        }
+    }
 }
 
 impl CheckBoxRef {
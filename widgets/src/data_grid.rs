@@ -0,0 +1,857 @@
+use {
+    std::ops::Range,
+    crate::{
+        makepad_derive_widget::*,
+        makepad_draw::*,
+        widget::*,
+        text_input::{TextInputAction, TextInputWidgetRefExt},
+        scroll_bar::{ScrollBar, ScrollAxis, ScrollBarAction},
+    }
+};
+
+live_design!{
+    DrawGridCell = {{DrawGridCell}} {}
+    DrawSortIcon = {{DrawSortIcon}} {}
+    DataGridBase = {{DataGrid}} {}
+}
+
+/// The grid never owns its rows, it asks a source for the cells it is about to draw.
+/// Only the visible rows and columns are queried so the row count can be very large.
+pub trait DataGridSource {
+    fn row_count(&self) -> usize;
+    fn cell_text(&self, row: usize, column: LiveId, out: &mut String);
+}
+
+#[derive(Live, LiveHook, LiveRegister)]#[repr(C)]
+pub struct DrawGridCell {
+    #[deref] draw_super: DrawQuad,
+    #[live] is_even: f32,
+    #[live] selected: f32,
+    #[live] focussed: f32,
+    #[live] cursor: f32,
+}
+
+#[derive(Live, LiveHook, LiveRegister)]#[repr(C)]
+pub struct DrawSortIcon {
+    #[deref] draw_super: DrawQuad,
+    #[live] descending: f32,
+}
+
+#[derive(Clone, Debug, Live, LiveHook, LiveRegister)]
+#[live_ignore]
+pub struct DataGridColumn {
+    #[live] pub id: LiveId,
+    #[live] pub title: String,
+    #[live(100.0)] pub width: f64,
+    #[live(true)] pub sortable: bool,
+    #[live(true)] pub resizable: bool,
+    // overrides the horizontal part of the grid cell_layout align
+    #[live] pub align: Option<Align>,
+    // name of the editor template to instance for in place editing, empty is read only
+    #[live] pub editor: LiveId,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Live, LiveHook)]
+#[live_ignore]
+pub enum DataGridSelectionMode {
+    #[pick] Row,
+    Cell
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum DataGridSortOrder {
+    Ascending,
+    Descending
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DataGridSelection {
+    pub rows: Range<usize>,
+    pub columns: Vec<LiveId>,
+}
+
+#[derive(Clone, Debug, DefaultNone)]
+pub enum DataGridAction {
+    SelectionChanged(DataGridSelection),
+    CellEdited {row: usize, column: LiveId, text: String},
+    SortChanged {column: LiveId, order: DataGridSortOrder},
+    ColumnResized {column: LiveId, width: f64},
+    ColumnMoved {column: LiveId, index: usize},
+    None
+}
+
+#[derive(Clone, Copy)]
+enum GridDrag {
+    None,
+    Resize {column: usize, start_x: f64, start_width: f64},
+    Header {column: usize, target: Option<usize>},
+    Select,
+}
+
+struct CellEditor {
+    row: usize,
+    column: LiveId,
+    widget: WidgetRef,
+    // the text the edit was started by typing, otherwise the cell text is loaded on draw
+    typed: Option<String>,
+    needs_init: bool,
+}
+
+#[derive(Live, Widget)]
+pub struct DataGrid {
+    #[redraw] #[rust] area: Area,
+    #[walk] walk: Walk,
+    #[layout] layout: Layout,
+
+    #[live] draw_bg: DrawColor,
+    #[live] draw_cell: DrawGridCell,
+    #[live] draw_header: DrawGridCell,
+    #[live] draw_text: DrawText,
+    #[live] draw_header_text: DrawText,
+    #[live] draw_sort_icon: DrawSortIcon,
+    #[live] draw_drop: DrawColor,
+    #[live] cell_layout: Layout,
+
+    #[live] scroll_bar_x: ScrollBar,
+    #[live] scroll_bar_y: ScrollBar,
+
+    #[live] columns: Vec<DataGridColumn>,
+    #[live] frozen_columns: usize,
+    #[live(24.0)] row_height: f64,
+    #[live(26.0)] header_height: f64,
+    #[live(30.0)] min_column_width: f64,
+    #[live(4.0)] resize_handle_width: f64,
+    #[live(8.0)] sort_icon_size: f64,
+    #[live] selection_mode: DataGridSelectionMode,
+    #[live(true)] reorderable_columns: bool,
+    #[live] read_only: bool,
+
+    #[rust] draw_state: DrawStateWrap<()>,
+    #[rust] templates: ComponentMap<LiveId, LivePtr>,
+    #[rust] editor: Option<CellEditor>,
+    #[rust] row_count: usize,
+    #[rust] grid_scroll: DVec2,
+    #[rust] view_rect: Rect,
+    #[rust] cursor: Option<(usize, usize)>,
+    #[rust] anchor: (usize, usize),
+    #[rust] sort: Option<(LiveId, DataGridSortOrder)>,
+    #[rust(GridDrag::None)] drag: GridDrag,
+    #[rust] has_focus: bool,
+    #[rust] cell_text: String,
+}
+
+impl LiveHook for DataGrid {
+    fn before_apply(&mut self, _cx: &mut Cx, apply: &mut Apply, _index: usize, _nodes: &[LiveNode]) {
+        if let ApplyFrom::UpdateFromDoc {..} = apply.from {
+            self.templates.clear();
+        }
+    }
+
+    // the instanced childnodes are the editor templates
+    fn apply_value_instance(&mut self, cx: &mut Cx, apply: &mut Apply, index: usize, nodes: &[LiveNode]) -> usize {
+        let id = nodes[index].id;
+        match apply.from {
+            ApplyFrom::NewFromDoc {file_id} | ApplyFrom::UpdateFromDoc {file_id} => {
+                if nodes[index].origin.has_prop_type(LivePropType::Instance) {
+                    let live_ptr = cx.live_registry.borrow().file_id_index_to_live_ptr(file_id, index);
+                    self.templates.insert(id, live_ptr);
+                    if let Some(editor) = &mut self.editor {
+                        if self.columns.iter().any(|c| c.id == editor.column && c.editor == id) {
+                            editor.widget.apply(cx, apply, index, nodes);
+                        }
+                    }
+                }
+                else {
+                    cx.apply_error_no_matching_field(live_error_origin!(), index, nodes);
+                }
+            }
+            _ => ()
+        }
+        nodes.skip_node(index)
+    }
+}
+
+impl DataGrid {
+
+    fn begin(&mut self, cx: &mut Cx2d, walk: Walk) {
+        cx.begin_turtle(walk, self.layout);
+        self.view_rect = self.grid_rect(cx);
+    }
+
+    fn end(&mut self, cx: &mut Cx2d) {
+        let rect = self.view_rect;
+        let view_total = dvec2(self.total_width(), self.header_height + self.row_count as f64 * self.row_height);
+
+        if let GridDrag::Header {target: Some(target), ..} = self.drag {
+            let x = self.boundary_x(target).max(0.0).min(rect.size.x);
+            self.draw_drop.draw_abs(cx, Rect {
+                pos: dvec2(rect.pos.x + x - 1.0, rect.pos.y),
+                size: dvec2(2.0, rect.size.y)
+            });
+        }
+
+        let view_rect = Rect {pos: dvec2(0.0, 0.0), size: rect.size};
+        self.grid_scroll.x = self.scroll_bar_x.draw_scroll_bar(cx, ScrollAxis::Horizontal, view_rect, view_total);
+        self.grid_scroll.y = self.scroll_bar_y.draw_scroll_bar(cx, ScrollAxis::Vertical, view_rect, view_total);
+
+        cx.turtle_mut().set_used(rect.size.x, rect.size.y);
+        cx.end_turtle_with_area(&mut self.area);
+    }
+
+    fn grid_rect(&self, cx: &Cx2d) -> Rect {
+        let mut rect = cx.turtle().rect();
+        if rect.size.x.is_nan() {
+            rect.size.x = self.total_width();
+        }
+        if rect.size.y.is_nan() {
+            rect.size.y = self.header_height + self.row_count as f64 * self.row_height;
+        }
+        rect
+    }
+
+    /// Draws the visible cells, call this while the grid is stepping in its draw_walk
+    pub fn draw_source(&mut self, cx: &mut Cx2d, scope: &mut Scope, source: &dyn DataGridSource) {
+        self.row_count = source.row_count();
+        let rect = self.grid_rect(cx);
+        self.view_rect = rect;
+        self.clamp_cursor();
+
+        let total = dvec2(self.total_width(), self.header_height + self.row_count as f64 * self.row_height);
+        self.grid_scroll.x = self.grid_scroll.x.min(total.x - rect.size.x).max(0.0);
+        self.grid_scroll.y = self.grid_scroll.y.min(total.y - rect.size.y).max(0.0);
+
+        self.draw_bg.draw_abs(cx, rect);
+
+        let frozen_width = self.frozen_width().min(rect.size.x);
+        let body = Rect {
+            pos: rect.pos + dvec2(0.0, self.header_height),
+            size: dvec2(rect.size.x, (rect.size.y - self.header_height).max(0.0))
+        };
+        let first_row = (self.grid_scroll.y / self.row_height).floor().max(0.0) as usize;
+        let end_row = (((self.grid_scroll.y + body.size.y) / self.row_height).ceil() as usize).min(self.row_count);
+        let frozen = self.frozen_count();
+
+        // the scrolling columns go first so the frozen ones can clip them at their edge
+        for (columns, clip) in [
+            (frozen..self.columns.len(), Rect {
+                pos: body.pos + dvec2(frozen_width, 0.0),
+                size: dvec2(body.size.x - frozen_width, body.size.y)
+            }),
+            (0..frozen, Rect {pos: body.pos, size: dvec2(frozen_width, body.size.y)}),
+        ] {
+            cx.begin_turtle(Walk::abs_rect(clip), Layout::default());
+            for col in columns {
+                let cell_x = rect.pos.x + self.column_x(col);
+                let width = self.columns[col].width;
+                if cell_x + width < clip.pos.x || cell_x > clip.pos.x + clip.size.x {
+                    continue;
+                }
+                let layout = self.column_layout(col);
+                let column_id = self.columns[col].id;
+                for row in first_row..end_row {
+                    let cell = Rect {
+                        pos: dvec2(cell_x, body.pos.y + row as f64 * self.row_height - self.grid_scroll.y),
+                        size: dvec2(width, self.row_height)
+                    };
+                    if let Some(editor) = &self.editor {
+                        if editor.row == row && editor.column == column_id {
+                            continue;
+                        }
+                    }
+                    self.draw_cell.is_even = if row % 2 == 0 {1.0} else {0.0};
+                    self.draw_cell.selected = if self.is_selected(row, col) {1.0} else {0.0};
+                    self.draw_cell.cursor = if self.cursor == Some((row, col)) {1.0} else {0.0};
+                    self.draw_cell.focussed = if self.has_focus {1.0} else {0.0};
+
+                    let mut text = std::mem::take(&mut self.cell_text);
+                    text.clear();
+                    source.cell_text(row, column_id, &mut text);
+                    cx.begin_turtle(Walk::abs_rect(cell), layout);
+                    self.draw_cell.draw_abs(cx, cell);
+                    self.draw_text.draw_walk(cx, Walk::fit(), Align::default(), &text);
+                    cx.end_turtle();
+                    self.cell_text = text;
+                }
+                self.draw_editor(cx, scope, source, col, cell_x, body.pos.y);
+            }
+            cx.end_turtle();
+        }
+
+        for (columns, clip) in [
+            (frozen..self.columns.len(), Rect {
+                pos: rect.pos + dvec2(frozen_width, 0.0),
+                size: dvec2(rect.size.x - frozen_width, self.header_height)
+            }),
+            (0..frozen, Rect {pos: rect.pos, size: dvec2(frozen_width, self.header_height)}),
+        ] {
+            cx.begin_turtle(Walk::abs_rect(clip), Layout::default());
+            for col in columns {
+                let cell_x = rect.pos.x + self.column_x(col);
+                let width = self.columns[col].width;
+                if cell_x + width < clip.pos.x || cell_x > clip.pos.x + clip.size.x {
+                    continue;
+                }
+                let cell = Rect {
+                    pos: dvec2(cell_x, rect.pos.y),
+                    size: dvec2(width, self.header_height)
+                };
+                let mut layout = self.column_layout(col);
+                let sort = self.sort.filter(|(id, _)| *id == self.columns[col].id);
+                if sort.is_some() {
+                    layout.padding.right += self.sort_icon_size + self.cell_layout.padding.right;
+                }
+                self.draw_header.selected = match self.drag {
+                    GridDrag::Header {column, ..} if column == col => 1.0,
+                    _ => 0.0
+                };
+                cx.begin_turtle(Walk::abs_rect(cell), layout);
+                self.draw_header.draw_abs(cx, cell);
+                self.draw_header_text.draw_walk(cx, Walk::fit(), Align::default(), &self.columns[col].title);
+                if let Some((_, order)) = sort {
+                    let size = self.sort_icon_size;
+                    self.draw_sort_icon.descending = if order == DataGridSortOrder::Descending {1.0} else {0.0};
+                    self.draw_sort_icon.draw_abs(cx, Rect {
+                        pos: dvec2(
+                            cell.pos.x + cell.size.x - self.cell_layout.padding.right - size,
+                            cell.pos.y + 0.5 * (cell.size.y - size)
+                        ),
+                        size: dvec2(size, size)
+                    });
+                }
+                cx.end_turtle();
+            }
+            cx.end_turtle();
+        }
+    }
+
+    fn draw_editor(&mut self, cx: &mut Cx2d, scope: &mut Scope, source: &dyn DataGridSource, col: usize, cell_x: f64, body_y: f64) {
+        let column_id = self.columns[col].id;
+        let Some(editor) = &mut self.editor else {return};
+        if editor.column != column_id || editor.row >= self.row_count {
+            return
+        }
+        if editor.needs_init {
+            if let Some(mut text_input) = editor.widget.as_text_input().borrow_mut() {
+                if let Some(typed) = &editor.typed {
+                    text_input.set_text("");
+                    text_input.replace_text(typed);
+                }
+                else {
+                    let mut text = String::new();
+                    source.cell_text(editor.row, column_id, &mut text);
+                    text_input.set_text(&text);
+                    text_input.select_all();
+                }
+            }
+        }
+        let cell = Rect {
+            pos: dvec2(cell_x, body_y + editor.row as f64 * self.row_height - self.grid_scroll.y),
+            size: dvec2(self.columns[col].width, self.row_height)
+        };
+        while editor.widget.draw_walk(cx, scope, Walk::abs_rect(cell)).is_step() {}
+        // the editor only has an area to focus once it has been drawn
+        if editor.needs_init {
+            editor.needs_init = false;
+            if let Some(text_input) = editor.widget.as_text_input().borrow() {
+                text_input.set_key_focus(cx);
+            }
+        }
+    }
+
+    fn column_layout(&self, col: usize) -> Layout {
+        let mut layout = self.cell_layout;
+        if let Some(align) = self.columns[col].align {
+            layout.align.x = align.x;
+        }
+        layout
+    }
+
+    fn frozen_count(&self) -> usize {
+        self.frozen_columns.min(self.columns.len())
+    }
+
+    fn frozen_width(&self) -> f64 {
+        self.columns[..self.frozen_count()].iter().map( | c | c.width).sum()
+    }
+
+    fn total_width(&self) -> f64 {
+        self.columns.iter().map( | c | c.width).sum()
+    }
+
+    // left edge of a column relative to the grid, unfrozen columns are scrolled
+    fn column_x(&self, col: usize) -> f64 {
+        let x: f64 = self.columns[..col].iter().map( | c | c.width).sum();
+        if col < self.frozen_count() {x} else {x - self.grid_scroll.x}
+    }
+
+    // position of the drop marker when a column is inserted before `index`
+    fn boundary_x(&self, index: usize) -> f64 {
+        if index < self.columns.len() {
+            self.column_x(index)
+        }
+        else if let Some(last) = self.columns.len().checked_sub(1) {
+            self.column_x(last) + self.columns[last].width
+        }
+        else {
+            0.0
+        }
+    }
+
+    fn column_at(&self, x: f64) -> Option<usize> {
+        let frozen = self.frozen_count();
+        let range = if x < self.frozen_width() {0..frozen} else {frozen..self.columns.len()};
+        range.into_iter().find( | col | {
+            let left = self.column_x(*col);
+            x >= left && x < left + self.columns[*col].width
+        })
+    }
+
+    fn resize_handle_at(&self, x: f64) -> Option<usize> {
+        let frozen_width = self.frozen_width();
+        (0..self.columns.len()).rev().find( | col | {
+            let column = &self.columns[*col];
+            let right = self.column_x(*col) + column.width;
+            column.resizable
+                && (x - right).abs() <= self.resize_handle_width
+                && (*col < self.frozen_count() || right > frozen_width)
+        })
+    }
+
+    fn insert_index_at(&self, x: f64) -> usize {
+        (0..=self.columns.len()).min_by( | a, b | {
+            let da = (self.boundary_x(*a) - x).abs();
+            let db = (self.boundary_x(*b) - x).abs();
+            da.total_cmp(&db)
+        }).unwrap_or(0)
+    }
+
+    fn row_at(&self, y: f64) -> Option<usize> {
+        let y = y - self.header_height + self.grid_scroll.y;
+        if y < 0.0 {
+            return None
+        }
+        let row = (y / self.row_height) as usize;
+        if row < self.row_count {Some(row)} else {None}
+    }
+
+    fn body_height(&self) -> f64 {
+        (self.view_rect.size.y - self.header_height).max(0.0)
+    }
+
+    fn page_rows(&self) -> usize {
+        ((self.body_height() / self.row_height).floor() as usize).max(1)
+    }
+
+    fn clamp_cursor(&mut self) {
+        if self.row_count == 0 || self.columns.is_empty() {
+            self.cursor = None;
+            return
+        }
+        let last = (self.row_count - 1, self.columns.len() - 1);
+        if let Some(cursor) = &mut self.cursor {
+            cursor.0 = cursor.0.min(last.0);
+            cursor.1 = cursor.1.min(last.1);
+        }
+        self.anchor.0 = self.anchor.0.min(last.0);
+        self.anchor.1 = self.anchor.1.min(last.1);
+    }
+
+    fn is_selected(&self, row: usize, col: usize) -> bool {
+        let Some((cursor_row, cursor_col)) = self.cursor else {return false};
+        if row < cursor_row.min(self.anchor.0) || row > cursor_row.max(self.anchor.0) {
+            return false
+        }
+        match self.selection_mode {
+            DataGridSelectionMode::Row => true,
+            DataGridSelectionMode::Cell => col >= cursor_col.min(self.anchor.1) && col <= cursor_col.max(self.anchor.1)
+        }
+    }
+
+    pub fn selection(&self) -> DataGridSelection {
+        let Some((cursor_row, cursor_col)) = self.cursor else {return DataGridSelection::default()};
+        let rows = cursor_row.min(self.anchor.0)..cursor_row.max(self.anchor.0) + 1;
+        let columns = match self.selection_mode {
+            DataGridSelectionMode::Row => &self.columns[..],
+            DataGridSelectionMode::Cell => &self.columns[cursor_col.min(self.anchor.1)..=cursor_col.max(self.anchor.1)]
+        };
+        DataGridSelection {
+            rows,
+            columns: columns.iter().map( | c | c.id).collect()
+        }
+    }
+
+    pub fn cursor(&self) -> Option<(usize, LiveId)> {
+        self.cursor.map( | (row, col) | (row, self.columns[col].id))
+    }
+
+    pub fn sort(&self) -> Option<(LiveId, DataGridSortOrder)> {
+        self.sort
+    }
+
+    /// Sets the sort indicator without sending an action, for restoring a sorted view
+    pub fn set_sort(&mut self, cx: &mut Cx, sort: Option<(LiveId, DataGridSortOrder)>) {
+        self.sort = sort;
+        self.area.redraw(cx);
+    }
+
+    pub fn columns(&self) -> &[DataGridColumn] {
+        &self.columns
+    }
+
+    pub fn set_columns(&mut self, cx: &mut Cx, columns: Vec<DataGridColumn>) {
+        self.editor = None;
+        self.columns = columns;
+        self.clamp_cursor();
+        self.area.redraw(cx);
+    }
+
+    fn select_cell(&mut self, cx: &mut Cx, uid: WidgetUid, scope: &Scope, row: usize, col: usize, extend: bool) {
+        let old = self.selection();
+        if !extend {
+            self.anchor = (row, col);
+        }
+        self.cursor = Some((row, col));
+        self.scroll_to_cell(cx, row, col);
+        self.area.redraw(cx);
+        let new = self.selection();
+        if new != old {
+            cx.widget_action(uid, &scope.path, DataGridAction::SelectionChanged(new));
+        }
+    }
+
+    pub fn scroll_to_cell(&mut self, cx: &mut Cx, row: usize, col: usize) {
+        let mut scroll = self.grid_scroll;
+        let body = self.body_height();
+        let top = row as f64 * self.row_height;
+        if top < scroll.y {
+            scroll.y = top;
+        }
+        else if top + self.row_height > scroll.y + body {
+            scroll.y = top + self.row_height - body;
+        }
+        if col >= self.frozen_count() && col < self.columns.len() {
+            let left: f64 = self.columns[..col].iter().map( | c | c.width).sum();
+            let right = left + self.columns[col].width;
+            let frozen_width = self.frozen_width();
+            if left - scroll.x < frozen_width {
+                scroll.x = left - frozen_width;
+            }
+            else if right - scroll.x > self.view_rect.size.x {
+                scroll.x = right - self.view_rect.size.x;
+            }
+        }
+        if scroll != self.grid_scroll {
+            self.scroll_bar_x.set_scroll_pos_no_action(cx, scroll.x);
+            self.scroll_bar_y.set_scroll_pos_no_action(cx, scroll.y);
+            self.grid_scroll = dvec2(self.scroll_bar_x.get_scroll_pos(), self.scroll_bar_y.get_scroll_pos());
+            self.area.redraw(cx);
+        }
+    }
+
+    fn begin_edit(&mut self, cx: &mut Cx, row: usize, col: usize, typed: Option<String>) {
+        if self.read_only {
+            return
+        }
+        let column = &self.columns[col];
+        let Some(ptr) = self.templates.get(&column.editor) else {return};
+        self.editor = Some(CellEditor {
+            row,
+            column: column.id,
+            widget: WidgetRef::new_from_ptr(cx, Some(*ptr)),
+            typed,
+            needs_init: true,
+        });
+        self.area.redraw(cx);
+    }
+
+    fn handle_editor_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope, uid: WidgetUid) {
+        let Some(editor) = &self.editor else {return};
+        let widget = editor.widget.clone();
+        let actions = cx.capture_actions( | cx | widget.handle_event(cx, event, scope));
+        let mut result = None;
+        let mut focus_lost = false;
+        for action in &actions {
+            if let Some(action) = action.as_widget_action() {
+                match action.cast() {
+                    TextInputAction::Return(text) => result = result.or(Some(Some(text))),
+                    TextInputAction::Escape => result = result.or(Some(None)),
+                    TextInputAction::KeyFocusLost => focus_lost = true,
+                    _ => ()
+                }
+            }
+        }
+        if let Some(text) = result {
+            let editor = self.editor.take().unwrap();
+            if let Some(text) = text {
+                cx.widget_action(uid, &scope.path, DataGridAction::CellEdited {
+                    row: editor.row,
+                    column: editor.column,
+                    text
+                });
+            }
+            if !focus_lost {
+                cx.set_key_focus(self.area);
+            }
+            self.area.redraw(cx);
+        }
+    }
+
+    fn handle_key_down(&mut self, cx: &mut Cx, ke: &KeyEvent, uid: WidgetUid, scope: &Scope) {
+        if self.row_count == 0 || self.columns.is_empty() {
+            return
+        }
+        let (row, col) = self.cursor.unwrap_or((0, 0));
+        let last_row = self.row_count - 1;
+        let last_col = self.columns.len() - 1;
+        let page = self.page_rows();
+        let command = ke.modifiers.control || ke.modifiers.logo;
+        let target = match ke.key_code {
+            KeyCode::ArrowUp => (row.saturating_sub(1), col),
+            KeyCode::ArrowDown => ((row + 1).min(last_row), col),
+            KeyCode::ArrowLeft => (row, col.saturating_sub(1)),
+            KeyCode::ArrowRight => (row, (col + 1).min(last_col)),
+            KeyCode::PageUp => (row.saturating_sub(page), col),
+            KeyCode::PageDown => ((row + page).min(last_row), col),
+            KeyCode::Home if command => (0, col),
+            KeyCode::Home => (row, 0),
+            KeyCode::End if command => (last_row, col),
+            KeyCode::End => (row, last_col),
+            KeyCode::Tab if ke.modifiers.shift => (row, col.saturating_sub(1)),
+            KeyCode::Tab => (row, (col + 1).min(last_col)),
+            KeyCode::ReturnKey | KeyCode::F2 => {
+                if self.cursor.is_some() {
+                    self.begin_edit(cx, row, col, None);
+                }
+                return
+            }
+            KeyCode::KeyA if command => {
+                self.anchor = (0, 0);
+                self.select_cell(cx, uid, scope, last_row, last_col, true);
+                return
+            }
+            _ => return
+        };
+        let extend = ke.modifiers.shift && ke.key_code != KeyCode::Tab;
+        self.select_cell(cx, uid, scope, target.0, target.1, extend);
+    }
+
+    fn move_column(&mut self, cx: &mut Cx, uid: WidgetUid, scope: &Scope, from: usize, target: usize) {
+        let to = if target > from {target - 1} else {target};
+        if to == from {
+            return
+        }
+        let cursor = self.cursor.map( | (row, col) | (row, self.columns[col].id));
+        let anchor = (self.anchor.0, self.columns[self.anchor.1].id);
+        let column = self.columns.remove(from);
+        let id = column.id;
+        self.columns.insert(to, column);
+        let index_of = | columns: &[DataGridColumn], id | columns.iter().position( | c | c.id == id).unwrap_or(0);
+        self.cursor = cursor.map( | (row, id) | (row, index_of(&self.columns, id)));
+        self.anchor = (anchor.0, index_of(&self.columns, anchor.1));
+        cx.widget_action(uid, &scope.path, DataGridAction::ColumnMoved {column: id, index: to});
+    }
+}
+
+impl Widget for DataGrid {
+
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        let uid = self.widget_uid();
+
+        self.handle_editor_event(cx, event, scope, uid);
+
+        let area = self.area;
+        let mut scrolled = false;
+        for (scroll_bar, scroll) in [
+            (&mut self.scroll_bar_x, &mut self.grid_scroll.x),
+            (&mut self.scroll_bar_y, &mut self.grid_scroll.y)
+        ] {
+            let mut scroll_to = None;
+            let mut dispatch = | _cx: &mut Cx, action | {
+                if let ScrollBarAction::Scroll {scroll_pos, ..} = action {
+                    scroll_to = Some(scroll_pos);
+                }
+            };
+            scroll_bar.handle_event_with(cx, event, &mut dispatch);
+            scroll_bar.handle_scroll_event(cx, event, area, &mut dispatch);
+            if let Some(scroll_to) = scroll_to {
+                *scroll = scroll_to;
+                scrolled = true;
+            }
+        }
+        if scrolled {
+            self.area.redraw(cx);
+        }
+
+        match event.hits(cx, self.area) {
+            Hit::KeyFocus(_) => {
+                self.has_focus = true;
+                self.area.redraw(cx);
+            }
+            Hit::KeyFocusLost(_) => {
+                self.has_focus = false;
+                self.area.redraw(cx);
+            }
+            Hit::FingerHoverOver(fe) => {
+                let rel = fe.abs - self.view_rect.pos;
+                if rel.y < self.header_height && self.resize_handle_at(rel.x).is_some() {
+                    cx.set_cursor(MouseCursor::ColResize);
+                }
+                else {
+                    cx.set_cursor(MouseCursor::Default);
+                }
+            }
+            Hit::FingerDown(fe) => {
+                cx.set_key_focus(self.area);
+                let rel = fe.abs - self.view_rect.pos;
+                if rel.y < self.header_height {
+                    if let Some(column) = self.resize_handle_at(rel.x) {
+                        self.drag = GridDrag::Resize {column, start_x: fe.abs.x, start_width: self.columns[column].width};
+                    }
+                    else if let Some(column) = self.column_at(rel.x) {
+                        self.drag = GridDrag::Header {column, target: None};
+                        self.area.redraw(cx);
+                    }
+                }
+                else if let (Some(row), Some(col)) = (self.row_at(rel.y), self.column_at(rel.x)) {
+                    self.select_cell(cx, uid, scope, row, col, fe.modifiers.shift);
+                    if fe.tap_count == 2 {
+                        self.begin_edit(cx, row, col, None);
+                    }
+                    else {
+                        self.drag = GridDrag::Select;
+                    }
+                }
+            }
+            Hit::FingerMove(fe) => {
+                let rel = fe.abs - self.view_rect.pos;
+                match self.drag {
+                    GridDrag::Resize {column, start_x, start_width} => {
+                        cx.set_cursor(MouseCursor::ColResize);
+                        let width = (start_width + fe.abs.x - start_x).max(self.min_column_width);
+                        if width != self.columns[column].width {
+                            self.columns[column].width = width;
+                            self.area.redraw(cx);
+                        }
+                    }
+                    GridDrag::Header {column, ..} => {
+                        if self.reorderable_columns && (fe.abs.x - fe.abs_start.x).abs() > 4.0 {
+                            self.drag = GridDrag::Header {column, target: Some(self.insert_index_at(rel.x))};
+                            self.area.redraw(cx);
+                        }
+                    }
+                    GridDrag::Select => {
+                        let row = self.row_at(rel.y.max(self.header_height)).or(self.row_count.checked_sub(1));
+                        let col = self.column_at(rel.x);
+                        if let (Some(row), Some(col)) = (row, col) {
+                            if self.cursor != Some((row, col)) {
+                                self.select_cell(cx, uid, scope, row, col, true);
+                            }
+                        }
+                    }
+                    GridDrag::None => ()
+                }
+            }
+            Hit::FingerUp(fe) => {
+                match std::mem::replace(&mut self.drag, GridDrag::None) {
+                    GridDrag::Resize {column, ..} => {
+                        let column = &self.columns[column];
+                        cx.widget_action(uid, &scope.path, DataGridAction::ColumnResized {
+                            column: column.id,
+                            width: column.width
+                        });
+                    }
+                    GridDrag::Header {column, target: Some(target)} => {
+                        self.move_column(cx, uid, scope, column, target);
+                    }
+                    GridDrag::Header {column, target: None} => {
+                        let column = &self.columns[column];
+                        if fe.is_over && column.sortable {
+                            let order = match self.sort {
+                                Some((id, DataGridSortOrder::Ascending)) if id == column.id => DataGridSortOrder::Descending,
+                                _ => DataGridSortOrder::Ascending
+                            };
+                            self.sort = Some((column.id, order));
+                            cx.widget_action(uid, &scope.path, DataGridAction::SortChanged {column: column.id, order});
+                        }
+                    }
+                    GridDrag::Select | GridDrag::None => ()
+                }
+                self.area.redraw(cx);
+            }
+            Hit::KeyDown(ke) => {
+                self.handle_key_down(cx, &ke, uid, scope);
+            }
+            // typing on a cell starts editing it with what was typed
+            Hit::TextInput(te) if self.editor.is_none() && !te.input.is_empty() && !te.input.chars().any(char::is_control) => {
+                if let Some((row, col)) = self.cursor {
+                    self.begin_edit(cx, row, col, Some(te.input));
+                }
+            }
+            _ => ()
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, _scope: &mut Scope, walk: Walk) -> DrawStep {
+        if self.draw_state.begin(cx, ()) {
+            self.begin(cx, walk);
+            return DrawStep::make_step()
+        }
+        if self.draw_state.get().is_some() {
+            self.end(cx);
+            self.draw_state.end();
+        }
+        DrawStep::done()
+    }
+}
+
+impl DataGridRef {
+    pub fn selection(&self) -> DataGridSelection {
+        if let Some(inner) = self.borrow() {
+            inner.selection()
+        }
+        else {
+            DataGridSelection::default()
+        }
+    }
+
+    pub fn set_sort(&self, cx: &mut Cx, sort: Option<(LiveId, DataGridSortOrder)>) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_sort(cx, sort);
+        }
+    }
+
+    pub fn scroll_to_cell(&self, cx: &mut Cx, row: usize, col: usize) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.scroll_to_cell(cx, row, col);
+        }
+    }
+
+    fn find_action<T>(&self, actions: &Actions, f: impl Fn(DataGridAction) -> Option<T>) -> Option<T> {
+        let uid = self.widget_uid();
+        actions.iter()
+            .filter_map( | action | action.as_widget_action())
+            .filter( | action | action.widget_uid == uid)
+            .find_map( | action | f(action.cast()))
+    }
+
+    pub fn cell_edited(&self, actions: &Actions) -> Option<(usize, LiveId, String)> {
+        self.find_action(actions, | action | match action {
+            DataGridAction::CellEdited {row, column, text} => Some((row, column, text)),
+            _ => None
+        })
+    }
+
+    pub fn sort_changed(&self, actions: &Actions) -> Option<(LiveId, DataGridSortOrder)> {
+        self.find_action(actions, | action | match action {
+            DataGridAction::SortChanged {column, order} => Some((column, order)),
+            _ => None
+        })
+    }
+
+    pub fn selection_changed(&self, actions: &Actions) -> Option<DataGridSelection> {
+        self.find_action(actions, | action | match action {
+            DataGridAction::SelectionChanged(selection) => Some(selection),
+            _ => None
+        })
+    }
+}
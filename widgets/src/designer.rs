@@ -1,192 +1,726 @@
-use crate::{
-    makepad_derive_widget::*,
-    makepad_draw::*,
-    file_tree::*,
-    view::View,
-    widget::*,
+use {
+    std::fs,
+    crate::{
+        makepad_derive_widget::*,
+        makepad_draw::*,
+        makepad_platform::makepad_live_compiler::{LiveToken, LiveTokenId, TokenWithSpan, TextPos},
+        makepad_platform::makepad_live_tokenizer::Delim,
+        file_tree::*,
+        flat_list::*,
+        drop_down::*,
+        text_input::*,
+        view::View,
+        window::Window,
+        widget::*,
+        widget_match_event::*,
+    }
 };
 
 live_design!{
     import makepad_widgets::base::*
     import makepad_widgets::theme_desktop_dark::*
     import makepad_draw::shader::std::*
-    
+
     Designer = {{Designer}} {
-        has_view: true,
-        flow: Right
-        container: <RoundedView> {
-            draw_bg: {color: #3}
-            width: Fill, height: 400
-            flow: Down, spacing: 10, padding:10
-            <RoundedView>{
-                width: Fill, height: Fit
-                padding:5
-                draw_bg:{color:#5}
-                label = <Label> {text: "HI", draw_text:{color:#f}}
+        width: Fill, height: Fill
+        flow: Down
+        draw_selection: {
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                sdf.rect(1.0, 1.0, self.rect_size.x - 2.0, self.rect_size.y - 2.0);
+                sdf.stroke(#f80, 1.5);
+                return sdf.result
             }
         }
+        <RectView> {
+            width: Fill, height: Fit
+            padding: 5
+            draw_bg: {color: #3}
+            file = <DropDown> {width: 300}
+        }
         <Splitter> {
-            align: FromStart(300),
-            a: <View> {
-                outline = <FileTree> {
+            align: FromA(300.0),
+            a: <Splitter> {
+                axis: Vertical,
+                align: Weighted(0.5),
+                a: <View> {
+                    outline = <FileTree> {}
+                },
+                b: <View> {
+                    inspector = <FlatList> {
+                        width: Fill, height: Fill
+                        flow: Down
+                        drag_scrolling: false
+                        Property = <View> {
+                            width: Fill, height: Fit
+                            padding: {left: 5, right: 5}
+                            align: {y: 0.5}
+                            label = <Label> {width: 120, draw_text: {color: #a}}
+                            value = <TextInput> {width: Fill}
+                        }
+                        Message = <Label> {margin: 5, draw_text: {color: #8}}
+                    }
                 }
             },
-            b: <CachedScrollXY> {
-                dpi_factor: 1.5
+            b: <RectView> {
                 draw_bg: {color: #4}
-                width: Fill, height: Fill
-                flow: Down
+                design = <FlatList> {
+                    width: Fill, height: Fill
+                    flow: Down, spacing: 10, padding: 10
+                    drag_scrolling: false
+                    Title = <Label> {draw_text: {color: #f}}
+                }
             },
         }
     }
 }
 
-#[allow(dead_code)]
-enum OutlineNode {
-    Global {
-        uid: FileNodeId,
-        name: LiveId,
-        ptr: LivePtr
-    },
-    Component {
-        uid: FileNodeId,
-        name: LiveId,
-        class: LiveId,
-        prop_type: LivePropType,
-        ptr: LivePtr,
-        children: Vec<OutlineNode>
+struct OutlineNode {
+    uid: FileNodeId,
+    name: LiveId,
+    class: LiveId,
+    prop_type: LivePropType,
+    live_type: LiveType,
+    ptr: LivePtr,
+    children: Vec<OutlineNode>
+}
+
+impl OutlineNode {
+    fn title(&self) -> String {
+        // nameless children don't have their id in the string table
+        if self.name.as_string( | s | s.is_none()) {
+            format!("<{}>", self.class)
+        }
+        else if let LivePropType::Field = self.prop_type {
+            format!("{}: <{}>", self.name, self.class)
+        }
+        else {
+            format!("{} = <{}>", self.name, self.class)
+        }
+    }
+
+    fn find(&self, uid: FileNodeId) -> Option<&OutlineNode> {
+        if self.uid == uid {
+            return Some(self)
+        }
+        self.children.iter().find_map( | child | child.find(uid))
     }
+
+    fn find_path(&self, uid: FileNodeId, path: &mut Vec<FileNodeId>) -> bool {
+        path.push(self.uid);
+        if self.uid == uid || self.children.iter().any( | child | child.find_path(uid, path)) {
+            return true
+        }
+        path.pop();
+        false
+    }
+}
+
+struct InspectorProp {
+    id: LiveId,
+    path: Vec<LiveId>,
+    label: String,
+    value: String,
+    sync: bool,
 }
 
 #[derive(Live, Widget)]
 pub struct Designer {
-    #[live] container: Option<LivePtr>,
+    #[live] file_name: String,
+    #[live] draw_selection: DrawQuad,
+    #[rust] design_file: String,
+    #[rust] files: Vec<String>,
     #[rust] outline_nodes: Vec<OutlineNode>,
-    #[rust] components: ComponentMap<LivePtr, (WidgetRef, WidgetRef)>,
+    #[rust] selected: Option<FileNodeId>,
+    #[rust] props: Vec<InspectorProp>,
+    #[rust] components: ComponentMap<FileNodeId, WidgetRef>,
     #[deref] ui: View,
 }
 
 impl LiveHook for Designer {
-    
     fn after_new_from_doc(&mut self, cx: &mut Cx) {
-        // lets take the doc we need (app_mobile for instance)
+        self.design_file = self.file_name.clone();
+        self.reload(cx);
+    }
+}
+
+impl Designer {
+
+    /// Rebuilds the outline and the inspector from the live registry, called on startup,
+    /// when another file is picked and after every live reload
+    fn reload(&mut self, cx: &mut Cx) {
         let live_registry_rc = cx.live_registry.clone();
         let live_registry = &*live_registry_rc.borrow();
-        let file_id = live_registry.file_name_to_file_id("examples/ironfish/src/app_desktop.rs").unwrap();
-        // now we fetch the unexpanded nodes
-        // and build a list
-        let file = live_registry.file_id_to_file(file_id);
-        let nodes = &file.expanded.nodes;
-        // lets run over the file
-        fn recur_walk(live_registry: &LiveRegistry, base_ptr: LivePtr, mut index: usize, nodes: &[LiveNode], out: &mut Vec<OutlineNode>) -> usize {
+        let widget_registry = live_registry.components.get::<WidgetRegistry>();
+
+        // walks a live node tree and collects the widgets in it, looking through plain
+        // objects and non-widget classes so a window body or an App shows its content
+        fn recur_walk(live_registry: &LiveRegistry, widget_registry: &WidgetRegistry, base_ptr: LivePtr, parent_uid: LiveId, mut index: usize, nodes: &[LiveNode], out: &mut Vec<OutlineNode>) -> usize {
             while index < nodes.len() - 1 {
-                if let LiveValue::Class {class_parent, ..} = &nodes[index].value {
-                    // lets emit a class at our level
-                    let mut children = Vec::new();
-                    let name = nodes[index].id;
-                    let class = live_registry.ptr_to_node(class_parent.unwrap()).id;
-                    let ptr = base_ptr.with_index(index);
-                    index = recur_walk(live_registry, base_ptr, index + 1, nodes, &mut children);
-                    out.insert(0, OutlineNode::Component {
-                        uid: LiveId::unique().into(),
-                        name,
-                        prop_type: nodes[index].origin.prop_type(),
-                        class,
-                        ptr,
-                        children
-                    });
-                }
-                else if nodes[index].value.is_close() {
-                    return index + 1;
-                }
-                else {
-                    index = nodes.skip_node(index);
+                match &nodes[index].value {
+                    LiveValue::Class {live_type, class_parent} if widget_registry.map.contains_key(live_type) => {
+                        let name = nodes[index].id;
+                        let class = if let Some(class_parent) = class_parent {
+                            live_registry.ptr_to_node(*class_parent).id
+                        }
+                        else if let Some(info) = live_registry.live_type_infos.get(live_type) {
+                            info.type_name
+                        }
+                        else {
+                            LiveId(0)
+                        };
+                        let uid = parent_uid.id_append(name);
+                        let mut children = Vec::new();
+                        let next = recur_walk(live_registry, widget_registry, base_ptr, uid, index + 1, nodes, &mut children);
+                        out.push(OutlineNode {
+                            uid: uid.into(),
+                            name,
+                            class,
+                            prop_type: nodes[index].origin.prop_type(),
+                            live_type: *live_type,
+                            ptr: base_ptr.with_index(index),
+                            children
+                        });
+                        index = next;
+                    }
+                    LiveValue::Class {..} | LiveValue::Object => {
+                        let uid = parent_uid.id_append(nodes[index].id);
+                        index = recur_walk(live_registry, widget_registry, base_ptr, uid, index + 1, nodes, out);
+                    }
+                    LiveValue::Close => {
+                        return index + 1;
+                    }
+                    _ => {
+                        index = nodes.skip_node(index);
+                    }
                 }
             }
             index
         }
-        let base_ptr = live_registry.file_id_index_to_live_ptr(file_id, 0);
-        recur_walk(live_registry, base_ptr, 1, nodes, &mut self.outline_nodes);
+
+        // every file with a widget in it can be designed
+        let mut files = Vec::new();
+        for file in &live_registry.live_files {
+            let nodes = &file.expanded.nodes;
+            if nodes.len() > 1 && nodes.iter().any( | node | match &node.value {
+                LiveValue::Class {live_type, ..} => widget_registry.map.contains_key(live_type),
+                _ => false
+            }) {
+                files.push(file.file_name.clone());
+            }
+        }
+        files.sort();
+
+        if !files.contains(&self.design_file) {
+            self.design_file = files.first().cloned().unwrap_or_default();
+        }
+
+        let file_dropdown = self.ui.drop_down(id!(file));
+        file_dropdown.set_labels(files.clone());
+        if let Some(item) = files.iter().position( | file | *file == self.design_file) {
+            file_dropdown.set_selected_item(item);
+        }
+        self.files = files;
+
+        self.outline_nodes.clear();
+        if let Some(file_id) = live_registry.file_name_to_file_id(&self.design_file) {
+            let nodes = &live_registry.file_id_to_file(file_id).expanded.nodes;
+            if nodes.len() > 1 {
+                let base_ptr = live_registry.file_id_index_to_live_ptr(file_id, 0);
+                let root_uid = LiveId::from_str(&self.design_file);
+                recur_walk(live_registry, &widget_registry, base_ptr, root_uid, 1, nodes, &mut self.outline_nodes);
+            }
+        }
+
+        // the pointers moved, so the designed widgets are created again on the next draw
+        self.components.clear();
+        if let Some(selected) = self.selected {
+            if self.outline_nodes.iter().all( | node | node.find(selected).is_none()) {
+                self.selected = None;
+            }
+        }
+        drop(widget_registry);
+        self.update_inspector(live_registry);
+        self.ui.redraw(cx);
     }
-    // ok now we can iterate our top level components
-    // and instance them
-}
 
-impl Designer {
-    
-    fn draw_design(&mut self, cx: &mut Cx2d) {
-        // alrigh so. lets draw the designs
-        let mut count = 0;
-        for node in &self.outline_nodes {
-            if let OutlineNode::Component {ptr, name, class, ..} = node {
-                count += 1;
-                if count > 5{
-                    break;
+    fn selected_node(&self) -> Option<&OutlineNode> {
+        let selected = self.selected?;
+        self.outline_nodes.iter().find_map( | node | node.find(selected))
+    }
+
+    fn selected_root(&self) -> Option<&OutlineNode> {
+        if let Some(selected) = self.selected {
+            return self.outline_nodes.iter().find( | node | node.find(selected).is_some())
+        }
+        None
+    }
+
+    fn select(&mut self, cx: &mut Cx, uid: Option<FileNodeId>) {
+        self.selected = uid;
+        let live_registry_rc = cx.live_registry.clone();
+        self.update_inspector(&live_registry_rc.borrow());
+        self.ui.redraw(cx);
+    }
+
+    /// Lists the walk, layout and draw properties of the selected widget
+    fn update_inspector(&mut self, live_registry: &LiveRegistry) {
+        self.props.clear();
+        let Some(node) = self.selected_node() else {return};
+        let (nodes, index) = live_registry.ptr_to_nodes_index(node.ptr);
+
+        let mut props = Vec::new();
+        let mut push = | path: Vec<LiveId>, value: String | {
+            let label = path.iter().map( | id | id.to_string()).collect::<Vec<_>>().join(".");
+            props.push(InspectorProp {
+                id: LiveId::from_str(&label),
+                path,
+                label,
+                value,
+                sync: true
+            });
+        };
+
+        let walk_layout = [
+            live_id!(width),
+            live_id!(height),
+            live_id!(margin),
+            live_id!(padding),
+            live_id!(flow),
+            live_id!(spacing),
+            live_id!(align),
+            live_id!(clip_x),
+            live_id!(clip_y),
+        ];
+        for id in walk_layout {
+            match nodes.child_by_name(index, id.as_field()) {
+                Some(child) if nodes[child].value.is_object() => {
+                    let mut field = nodes.first_child(child);
+                    while let Some(field_index) = field {
+                        push(vec![id, nodes[field_index].id], live_value_to_string(nodes, field_index));
+                        field = nodes.next_child(field_index);
+                    }
+                }
+                Some(child) => push(vec![id], live_value_to_string(nodes, child)),
+                None => push(vec![id], String::new())
+            }
+        }
+
+        let mut child = nodes.first_child(index);
+        while let Some(child_index) = child {
+            let is_draw = nodes[child_index].id.as_string( | s | s.map_or(false, | s | s.starts_with("draw_")));
+            if is_draw && nodes[child_index].value.is_structy_type() {
+                let mut field = nodes.first_child(child_index);
+                while let Some(field_index) = field {
+                    match nodes[field_index].value {
+                        LiveValue::Bool(_) |
+                        LiveValue::Int64(_) |
+                        LiveValue::Float32(_) |
+                        LiveValue::Float64(_) |
+                        LiveValue::Color(_) |
+                        LiveValue::Vec2(_) |
+                        LiveValue::Vec3(_) |
+                        LiveValue::Vec4(_) => {
+                            push(vec![nodes[child_index].id, nodes[field_index].id], live_value_to_string(nodes, field_index));
+                        }
+                        _ => ()
+                    }
+                    field = nodes.next_child(field_index);
                 }
-                let container_ptr = self.container;
-                let (widget, container) = self.components.get_or_insert(cx, *ptr, | cx | {
-                    (
-                        WidgetRef::new_from_ptr(cx, Some(*ptr)),
-                        WidgetRef::new_from_ptr(cx, container_ptr),
-                    )
-                });
-                container.widget(id!(label)).set_text(&format!("{}=<{}>", name, class));
-                // lets draw this thing in a neat little container box with a title bar
-                while let Some(_) = container.draw(cx, &mut Scope::empty()).step() {
-                    widget.draw_all(cx, &mut Scope::empty());
+            }
+            child = nodes.next_child(child_index);
+        }
+        self.props = props;
+    }
+
+    /// Writes a property of the selected widget back into its Rust source and
+    /// hot reloads it, the same way an edit in the file would
+    fn write_prop(&mut self, cx: &mut Cx, path: &[LiveId], value: &str) {
+        let Some(node) = self.selected_node() else {return};
+        let ptr = node.ptr;
+        let live_registry_rc = cx.live_registry.clone();
+        let live_registry = live_registry_rc.borrow();
+        let file = live_registry.file_id_to_file(ptr.file_id);
+        let file_name = file.file_name.clone();
+        let Some(start) = file_name.find("src/") else {
+            error!("Designer: cannot find the source of {}", file_name);
+            return
+        };
+        let disk_path = format!("{}/{}", live_registry.file_id_to_cargo_manifest_path(ptr.file_id), &file_name[start..]);
+        let source = match fs::read_to_string(&disk_path) {
+            Ok(source) => source,
+            Err(err) => {
+                error!("Designer: cannot read {}: {}", disk_path, err);
+                return
+            }
+        };
+        let tokens = match LiveRegistry::tokenize_from_str_live_design(&source, TextPos::default(), ptr.file_id, None) {
+            Ok(tokens) => tokens,
+            Err(err) => {
+                error!("Designer: cannot tokenize {}: {}", disk_path, err);
+                return
+            }
+        };
+        // the token ids in the registry only mean something if the file didn't change underneath us
+        if tokens.len() != file.original.tokens.len() {
+            error!("Designer: {} changed on disk, wait for it to live reload", file_name);
+            return
+        }
+        let (nodes, index) = live_registry.ptr_to_nodes_index(ptr);
+        let Some((edit_start, edit_end, text)) = source_edit(&source, &tokens, ptr.file_id, nodes, index, path, value) else {
+            error!("Designer: the selected widget is not defined in {}", file_name);
+            return
+        };
+        drop(live_registry);
+
+        let mut new_source = source;
+        new_source.replace_range(edit_start..edit_end, &text);
+        if let Err(err) = fs::write(&disk_path, &new_source) {
+            error!("Designer: cannot write {}: {}", disk_path, err);
+            return
+        }
+        cx.send_live_file_change(&file_name, new_source);
+    }
+
+    fn pick(&self, cx: &Cx, abs: DVec2) -> Option<FileNodeId> {
+        let root = self.selected_root()?;
+        let widget = self.components.get(&root.uid)?;
+        // children draw on top of their parents and later siblings on top of earlier ones
+        // names repeat across the tree, so children are looked up by their full path
+        fn recur_pick(cx: &Cx, widget: &WidgetRef, node: &OutlineNode, path: &mut Vec<LiveId>, abs: DVec2, picked: &mut Option<FileNodeId>) {
+            for child in &node.children {
+                path.push(child.name);
+                if widget.widget(path).widget_area().clipped_rect(cx).contains(abs) {
+                    *picked = Some(child.uid);
                 }
+                recur_pick(cx, widget, child, path, abs, picked);
+                path.pop();
             }
         }
-        
+        let mut picked = None;
+        if widget.widget_area().clipped_rect(cx).contains(abs) {
+            picked = Some(root.uid);
+        }
+        recur_pick(cx, widget, root, &mut Vec::new(), abs, &mut picked);
+        picked
+    }
+
+    fn draw_design(&mut self, cx: &mut Cx2d, list: &mut FlatList) {
+        let title = list.item(cx, live_id!(title), live_id!(Title)).unwrap();
+        let Some(root) = self.selected_root() else {
+            title.set_text("Select a widget in the outline");
+            title.draw_all(cx, &mut Scope::empty());
+            return
+        };
+        let (uid, ptr) = (root.uid, root.ptr);
+        // a window would open its own OS window, and a designer would design itself forever
+        if root.live_type == LiveType::of::<Window>() || root.live_type == LiveType::of::<Designer>() {
+            title.set_text(&format!("{} can't be shown here", root.title()));
+            title.draw_all(cx, &mut Scope::empty());
+            return
+        }
+        title.set_text(&root.title());
+        title.draw_all(cx, &mut Scope::empty());
+
+        let selected = match self.selected {
+            Some(selected) if selected != uid => {
+                let mut path = Vec::new();
+                root.find_path(selected, &mut path);
+                Some(path.iter().skip(1).filter_map( | uid | root.find(*uid)).map( | node | node.name).collect::<Vec<_>>())
+            }
+            _ => None
+        };
+        let widget = self.components.get_or_insert(cx, uid, | cx | {
+            WidgetRef::new_from_ptr(cx, Some(ptr))
+        }).clone();
+        widget.draw_all(cx, &mut Scope::empty());
+
+        let area = if let Some(path) = selected {
+            widget.widget(&path).widget_area()
+        }
+        else {
+            widget.widget_area()
+        };
+        let rect = area.rect(cx);
+        if rect.size.x > 0.0 && rect.size.y > 0.0 {
+            self.draw_selection.draw_abs(cx, rect);
+        }
+    }
+
+    fn draw_inspector(&mut self, cx: &mut Cx2d, list: &mut FlatList) {
+        if self.props.is_empty() {
+            let message = list.item(cx, live_id!(message), live_id!(Message)).unwrap();
+            message.set_text("Select a widget to edit its properties");
+            message.draw_all(cx, &mut Scope::empty());
+            return
+        }
+        for prop in &mut self.props {
+            let item = list.item(cx, prop.id, live_id!(Property)).unwrap();
+            if prop.sync {
+                item.widget(id!(label)).set_text(&prop.label);
+                item.widget(id!(value)).set_text(&prop.value);
+                prop.sync = false;
+            }
+            item.draw_all(cx, &mut Scope::empty());
+        }
     }
-    
+
     fn draw_outline(&mut self, cx: &mut Cx2d, outline: &mut FileTree) {
         fn recur_walk(cx: &mut Cx2d, outline: &mut FileTree, children: &[OutlineNode]) {
             for child in children {
-                match child {
-                    OutlineNode::Global {..} => {}
-                    OutlineNode::Component {name, children, uid, class, prop_type, ..} => {
-                        if outline.begin_folder(cx, *uid, &if !name.is_unique(){
-                            if let LivePropType::Field = prop_type {
-                                format!("{}: <{}>", name, class)
-                            }
-                            else {
-                                format!("{}=<{}>", name, class)
-                            }
-                        }else {
-                            format!("<{}>", class)
-                        }).is_ok() {
-                            recur_walk(cx, outline, children);
-                            outline.end_folder();
-                        }
-                    }
+                if child.children.is_empty() {
+                    outline.file(cx, child.uid, &child.title());
+                }
+                else if outline.begin_folder(cx, child.uid, &child.title()).is_ok() {
+                    recur_walk(cx, outline, &child.children);
+                    outline.end_folder();
                 }
             }
         }
         recur_walk(cx, outline, &self.outline_nodes);
     }
-    
+}
+
+impl WidgetMatchEvent for Designer {
+    fn handle_actions(&mut self, cx: &mut Cx, actions: &Actions, _scope: &mut Scope) {
+        if let Some(item) = self.ui.drop_down(id!(file)).selected(actions) {
+            if let Some(file_name) = self.files.get(item) {
+                self.design_file = file_name.clone();
+                self.selected = None;
+                self.reload(cx);
+            }
+        }
+
+        let outline = self.ui.file_tree(id!(outline));
+        if let Some(uid) = outline.file_clicked(actions).or_else( || outline.folder_clicked(actions)) {
+            self.select(cx, Some(uid));
+        }
+
+        let inspector = self.ui.flat_list(id!(inspector));
+        for (item_id, item) in inspector.items_with_actions(actions) {
+            if let Some(value) = item.text_input(id!(value)).returned(actions) {
+                let edit = self.props.iter()
+                    .find( | prop | prop.id == item_id && prop.value != value)
+                    .map( | prop | prop.path.clone());
+                if let Some(path) = edit {
+                    self.write_prop(cx, &path, &value);
+                }
+            }
+        }
+    }
 }
 
 impl Widget for Designer {
-    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope){
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        if let Event::LiveEdit = event {
+            self.reload(cx);
+        }
+
+        // clicking a widget in the design selects it instead of using it
+        let design = self.ui.widget(id!(design));
+        if let Hit::FingerDown(fe) = event.hits(cx, design.widget_area()) {
+            if let Some(uid) = self.pick(cx, fe.abs) {
+                let outline = self.ui.file_tree(id!(outline));
+                let mut path = Vec::new();
+                if let Some(root) = self.selected_root() {
+                    root.find_path(uid, &mut path);
+                }
+                for ancestor in path.iter().filter( | ancestor | **ancestor != uid) {
+                    outline.set_folder_is_open(cx, *ancestor, true, Animate::Yes);
+                }
+                outline.set_selected_node(cx, uid, Animate::Yes);
+                self.select(cx, Some(uid));
+            }
+        }
+
+        self.widget_match_event(cx, event, scope);
         self.ui.handle_event(cx, event, scope);
-        for (component, container) in self.components.values_mut() {
-            component.handle_event(cx, event, scope);
-            container.handle_event(cx, event, scope);
+
+        // the designed widgets don't get input, only the events that keep them running
+        match event {
+            Event::MouseDown(_) | Event::MouseMove(_) | Event::MouseUp(_) | Event::MouseLeave(_) |
+            Event::TouchUpdate(_) | Event::Scroll(_) | Event::KeyDown(_) | Event::KeyUp(_) |
+            Event::TextInput(_) | Event::TextCopy(_) | Event::TextCut(_) | Event::Drag(_) | Event::Drop(_) => (),
+            _ => for component in self.components.values_mut() {
+                component.handle_event(cx, event, &mut Scope::empty());
+            }
         }
     }
-    
-    fn draw_walk(&mut self, cx: &mut Cx2d, scope:&mut Scope, _walk: Walk) -> DrawStep {
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
         let outline = self.ui.file_tree(id!(outline));
-        while let Some(next) = self.ui.draw(cx, scope).step() {
+        let inspector = self.ui.flat_list(id!(inspector));
+        let design = self.ui.flat_list(id!(design));
+        while let Some(next) = self.ui.draw_walk(cx, scope, walk).step() {
             if let Some(mut outline) = outline.has_widget(&next).borrow_mut() {
-                self.draw_outline(cx, &mut *outline);
+                self.draw_outline(cx, &mut outline);
             }
-            else if next == self.ui.widget(id!(design)) {
-                self.draw_design(cx);
+            else if let Some(mut list) = inspector.has_widget(&next).borrow_mut() {
+                self.draw_inspector(cx, &mut list);
+            }
+            else if let Some(mut list) = design.has_widget(&next).borrow_mut() {
+                self.draw_design(cx, &mut list);
             }
         }
         DrawStep::done()
     }
 }
+
+fn live_value_to_string(nodes: &[LiveNode], index: usize) -> String {
+    let children = | separator: &str, named: bool | {
+        let mut out = Vec::new();
+        let mut child = nodes.first_child(index);
+        while let Some(child_index) = child {
+            if named {
+                out.push(format!("{}: {}", nodes[child_index].id, live_value_to_string(nodes, child_index)));
+            }
+            else {
+                out.push(live_value_to_string(nodes, child_index));
+            }
+            child = nodes.next_child(child_index);
+        }
+        out.join(separator)
+    };
+    match &nodes[index].value {
+        LiveValue::Bool(v) => format!("{}", v),
+        LiveValue::Int64(v) => format!("{}", v),
+        LiveValue::Uint64(v) => format!("{}", v),
+        LiveValue::Float32(v) => format!("{}", v),
+        LiveValue::Float64(v) => format!("{}", v),
+        LiveValue::Color(v) => format!("#x{:08x}", v),
+        LiveValue::Vec2(v) => format!("vec2({}, {})", v.x, v.y),
+        LiveValue::Vec3(v) => format!("vec3({}, {}, {})", v.x, v.y, v.z),
+        LiveValue::Vec4(v) => format!("vec4({}, {}, {}, {})", v.x, v.y, v.z, v.w),
+        LiveValue::Id(id) | LiveValue::BareEnum(id) => format!("{}", id),
+        LiveValue::Str(s) => format!("{:?}", s),
+        LiveValue::String(s) => format!("{:?}", s.as_str()),
+        LiveValue::InlineString(s) => format!("{:?}", s.as_str()),
+        LiveValue::TupleEnum(variant) => format!("{}({})", variant, children(", ", false)),
+        LiveValue::NamedEnum(variant) => format!("{} {{{}}}", variant, children(", ", true)),
+        LiveValue::Object => format!("{{{}}}", children(", ", true)),
+        _ => String::new()
+    }
+}
+
+/// Works out where `path` below the node at `index` sits in the tokens of its live_design
+/// block, and returns the byte range in `source` to replace together with the replacement.
+/// Properties the node already spells out get their value replaced, the rest are inserted
+/// at the top of the innermost body that exists.
+fn source_edit(source: &str, tokens: &[TokenWithSpan], file_id: LiveFileId, nodes: &[LiveNode], index: usize, path: &[LiveId], value: &str) -> Option<(usize, usize, String)> {
+    let token_id = nodes[index].origin.token_id()?;
+    if token_id.file_id() != Some(file_id) {
+        return None
+    }
+    let open = body_open(tokens, token_id.token_index())?;
+    let close = matching_close(tokens, open)?;
+    let in_body = | token_id: LiveTokenId | {
+        token_id.file_id() == Some(file_id) && token_id.token_index() > open && token_id.token_index() < close
+    };
+
+    let mut body = open;
+    let mut parent = index;
+    for (depth, id) in path.iter().enumerate() {
+        let child = nodes.child_by_name(parent, id.as_field());
+        let token_id = child.and_then( | child | nodes[child].origin.token_id()).filter( | token_id | in_body(*token_id));
+        if let (Some(child), Some(token_id)) = (child, token_id) {
+            if depth == path.len() - 1 {
+                let (start, end) = value_range(tokens, token_id.token_index())?;
+                let start = text_pos_to_offset(source, tokens[start].span.start)?;
+                let end = text_pos_to_offset(source, tokens[end].span.end)?;
+                return Some((start, end, value.to_string()))
+            }
+            body = body_open(tokens, token_id.token_index())?;
+            parent = child;
+            continue
+        }
+
+        let mut text = format!("{}: {}", path[path.len() - 1], value);
+        for id in path[depth..path.len() - 1].iter().rev() {
+            text = format!("{}: {{{}}}", id, text);
+        }
+        let at = text_pos_to_offset(source, tokens[body].span.end)?;
+        let next = &tokens[body + 1];
+        if next.token.is_close() {
+            return Some((at, at, text))
+        }
+        if next.span.start.line == tokens[body].span.start.line {
+            return Some((at, at, format!("{}, ", text)))
+        }
+        // a body spread over lines gets the new property on its own line
+        let line_start = text_pos_to_offset(source, TextPos {line: next.span.start.line, column: 1})?;
+        let indent: String = source[line_start..].chars().take_while( | c | *c == ' ' || *c == '\t').collect();
+        return Some((at, at, format!("\n{}{}", indent, text)))
+    }
+    None
+}
+
+/// The token index of the `{` that opens the body of the property at `index`,
+/// skipping over `name = <Class>` and `{{RustType}}`
+fn body_open(tokens: &[TokenWithSpan], mut index: usize) -> Option<usize> {
+    while index < tokens.len() {
+        match &tokens[index].token {
+            LiveToken::Open(Delim::Brace) => {
+                if tokens.get(index + 1)?.token.is_open_delim(Delim::Brace) {
+                    index = matching_close(tokens, index)? + 1;
+                    continue
+                }
+                return Some(index)
+            }
+            LiveToken::Ident(_) | LiveToken::Punct(_) => index += 1,
+            _ => return None
+        }
+    }
+    None
+}
+
+fn matching_close(tokens: &[TokenWithSpan], open: usize) -> Option<usize> {
+    let mut depth = 0;
+    for (index, token) in tokens.iter().enumerate().skip(open) {
+        match token.token {
+            LiveToken::Open(_) => depth += 1,
+            LiveToken::Close(_) => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(index)
+                }
+            }
+            LiveToken::Eof => return None,
+            _ => ()
+        }
+    }
+    None
+}
+
+/// The first and last token of the value assigned to the property at `index`
+fn value_range(tokens: &[TokenWithSpan], index: usize) -> Option<(usize, usize)> {
+    let assign = tokens.get(index + 1)?;
+    if !assign.token.is_punct_id(live_id!(:)) && !assign.token.is_punct_id(live_id!(=)) {
+        return None
+    }
+    let start = index + 2;
+    let mut end = start;
+    if tokens.get(end)?.token.is_punct_id(live_id!(-)) {
+        end += 1;
+    }
+    match &tokens.get(end)?.token {
+        LiveToken::Open(_) => end = matching_close(tokens, end)?,
+        LiveToken::Ident(_) if tokens.get(end + 1)?.token.is_open_delim(Delim::Paren) => {
+            end = matching_close(tokens, end + 1)?
+        }
+        LiveToken::Punct(_) | LiveToken::Close(_) | LiveToken::Eof => return None,
+        _ => ()
+    }
+    Some((start, end))
+}
+
+/// Token spans count lines from 0 and columns in characters from 1
+fn text_pos_to_offset(source: &str, pos: TextPos) -> Option<usize> {
+    let mut offset = 0;
+    for _ in 0..pos.line {
+        offset += source[offset..].find('\n')? + 1;
+    }
+    let line = source[offset..].split('\n').next().unwrap_or("");
+    let line = line.strip_suffix('\r').unwrap_or(line);
+    let column = (pos.column as usize).saturating_sub(1);
+    Some(offset + line.char_indices().nth(column).map( | (i, _) | i).unwrap_or(line.len()))
+}
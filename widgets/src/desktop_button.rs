@@ -22,6 +22,10 @@ pub struct DesktopButton {
 }
 
 impl Widget for DesktopButton{
+    fn widget_area(&self) -> Area {
+        self.draw_bg.area()
+    }
+    
     fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
         let uid = self.widget_uid();
         self.animator_handle_event(cx, event);
@@ -53,6 +53,10 @@ pub struct Dock {
     #[live] tab_bar: Option<LivePtr>,
     #[live] splitter: Option<LivePtr>,
     
+    // dropping a tab outside of every window moves it into a new one. this relies on Event::DropOutside which
+    // only macOS, Windows and X11 send, Wayland doesn't tell where on screen a drag ended so tabs stay put there
+    #[live(true)] tear_off: bool,
+    
     #[rust] needs_save: bool,
     #[rust] area: Area,
     
@@ -64,6 +68,10 @@ pub struct Dock {
     #[rust] items: ComponentMap<LiveId, (LiveId, WidgetRef)>,
     #[rust] drop_state: Option<DropPosition>,
     #[rust] dock_item_iter_stack: Vec<(LiveId, usize)>,
+    
+    #[rust] windows: ComponentMap<LiveId, DockWindow>,
+    #[rust] drag_tab: Option<LiveId>,
+    #[rust] drag_window: Option<WindowId>,
 }
 
 impl WidgetNode for Dock{
@@ -125,6 +133,9 @@ impl<'a> Iterator for DockVisibleItemIterator<'a> {
                             return Some((item_id, widget.clone()))
                         }
                     }
+                    DockItem::Window {tabs, ..} => {
+                        self.stack.push((*tabs, 0));
+                    }
                 }
             }
         }
@@ -145,6 +156,40 @@ struct TabBarWrap {
     contents_rect: Rect
 }
 
+/// The OS window a floating part of the dock is drawn into
+struct DockWindow {
+    window: WindowHandle,
+    pass: Pass,
+    _depth_texture: Texture,
+    main_draw_list: DrawList2d,
+    drop_target_draw_list: DrawList2d,
+    area: Area,
+}
+
+impl DockWindow {
+    fn new(cx: &mut Cx, title: &str, position: DVec2, size: DVec2) -> Self {
+        let window = WindowHandle::new(cx);
+        let cxwindow = &mut cx.windows[window.window_id()];
+        cxwindow.create_title = title.to_string();
+        cxwindow.create_position = Some(position);
+        cxwindow.create_inner_size = Some(size);
+        let pass = Pass::new(cx);
+        window.set_pass(cx, &pass);
+        let depth_texture = Texture::new_with_format(cx, TextureFormat::DepthD32{
+            size:TextureSize::Auto
+        });
+        pass.set_depth_texture(cx, &depth_texture, PassClearDepth::ClearWith(1.0));
+        Self {
+            window,
+            pass,
+            _depth_texture: depth_texture,
+            main_draw_list: DrawList2d::new(cx),
+            drop_target_draw_list: DrawList2d::new(cx),
+            area: Area::Empty
+        }
+    }
+}
+
 #[derive(Copy, Debug, Clone)]
 enum DrawStackItem {
     Invalid,
@@ -154,7 +199,9 @@ enum DrawStackItem {
     Tabs {id: LiveId},
     TabLabel {id: LiveId, index: usize},
     Tab {id: LiveId},
-    TabContent {id: LiveId}
+    TabContent {id: LiveId},
+    Window {id: LiveId},
+    WindowEnd {id: LiveId}
 }
 
 impl DrawStackItem {
@@ -170,6 +217,9 @@ impl DrawStackItem {
             Some(DockItem::Tab {..}) => {
                 DrawStackItem::Tab {id}
             }
+            Some(DockItem::Window {..}) => {
+                DrawStackItem::Window {id}
+            }
         }
     }
 }
@@ -190,7 +240,8 @@ pub enum DockAction {
 pub struct DropPosition {
     part: DropPart,
     rect: Rect,
-    id: LiveId
+    id: LiveId,
+    window: Option<LiveId>
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -225,6 +276,12 @@ pub enum DockItem {
         name: String,
         closable: bool,
         kind: LiveId
+    },
+    #[live {tabs: LiveId(0), position: dvec2(100.0, 100.0), size: dvec2(800.0, 600.0)}]
+    Window {
+        tabs: LiveId,
+        position: DVec2,
+        size: DVec2
     }
 }
 
@@ -282,6 +339,14 @@ pub enum DockItemStore{
         name: String,
         closable: bool,
         kind: LiveIdStore
+    },
+    Window {
+        id: LiveIdStore,
+        tabs: LiveIdStore,
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64
     }
 }
 
@@ -357,39 +422,105 @@ impl Dock {
         
         if self.drop_target_draw_list.begin(cx, Walk::default()).is_redrawing() {
             if let Some(pos) = &self.drop_state {
-                self.drag_quad.draw_abs(cx, pos.rect);
+                if pos.window.is_none() {
+                    self.drag_quad.draw_abs(cx, pos.rect);
+                }
             }
             self.drop_target_draw_list.end(cx);
         }
         
-        self.tab_bars.retain_visible();
-        self.splitters.retain_visible();
+        // floating windows that didn't redraw this time still hold on to their tab bars and splitters
+        let dock_items = &self.dock_items;
+        self.tab_bars.retain_visible_and( | id, _ | dock_items.contains_key(id));
+        self.splitters.retain_visible_and( | id, _ | dock_items.contains_key(id));
         
         // lets draw the corners here
-        for splitter in self.splitters.values() {
+        let window_items = self.window_items();
+        for (splitter_id, splitter) in self.splitters.iter() {
+            if window_items.contains_key(splitter_id) {
+                continue;
+            }
             self.round_corner.draw_corners(cx, splitter.area_a().rect(cx));
             self.round_corner.draw_corners(cx, splitter.area_b().rect(cx));
         }
         self.round_corner.draw_corners(cx, cx.turtle().rect());
         
         cx.end_turtle_with_area(&mut self.area);
+        
+        // close the windows whose items are gone
+        let dock_items = &self.dock_items;
+        let mut closed = Vec::new();
+        for (window_id, dock_window) in self.windows.iter_mut() {
+            if !matches!(dock_items.get(window_id), Some(DockItem::Window {..})) {
+                dock_window.window.close(cx);
+                closed.push(*window_id);
+            }
+        }
+        for window_id in closed {
+            self.windows.remove(&window_id);
+        }
+    }
+    
+    /// Maps every dock item that lives in a floating window to the id of its window item
+    fn window_items(&self) -> HashMap<LiveId, LiveId> {
+        let mut out = HashMap::new();
+        let mut stack = Vec::new();
+        for (window_id, item) in &self.dock_items {
+            if let DockItem::Window {tabs, ..} = item {
+                stack.push(*tabs);
+                while let Some(id) = stack.pop() {
+                    out.insert(id, *window_id);
+                    match self.dock_items.get(&id) {
+                        Some(DockItem::Splitter {a, b, ..}) => {
+                            stack.push(*a);
+                            stack.push(*b);
+                        }
+                        Some(DockItem::Tabs {tabs, ..}) => {
+                            stack.extend(tabs.iter().cloned());
+                        }
+                        _ => ()
+                    }
+                }
+            }
+        }
+        out
+    }
+    
+    fn redraw_drop_targets(&mut self, cx: &mut Cx) {
+        self.drop_target_draw_list.redraw(cx);
+        for dock_window in self.windows.values() {
+            dock_window.drop_target_draw_list.redraw(cx);
+        }
+    }
+    
+    /// The floating window item an OS window belongs to
+    fn window_item_of(&self, window_id: WindowId) -> Option<LiveId> {
+        self.windows.iter().find( | (_, dock_window) | dock_window.window.window_id() == window_id).map( | (id, _) | *id)
     }
     
     fn find_drop_position(&self, cx: &Cx, abs: DVec2) -> Option<DropPosition> {
+        // only the tab bars in the window the drag is over count
+        let window = self.drag_window.and_then( | window_id | self.window_item_of(window_id));
+        let window_items = self.window_items();
         for (tab_bar_id, tab_bar) in self.tab_bars.iter() {
+            if window_items.get(tab_bar_id).cloned() != window {
+                continue;
+            }
             let rect = tab_bar.contents_rect;
             if let Some((tab_id, rect)) = tab_bar.tab_bar.is_over_tab(cx, abs) {
                 return Some(DropPosition {
                     part: DropPart::Tab,
                     id: tab_id,
-                    rect
+                    rect,
+                    window
                 })
             }
             else if let Some(rect) = tab_bar.tab_bar.is_over_tab_bar(cx, abs) {
                 return Some(DropPosition {
                     part: DropPart::TabBar,
                     id: *tab_bar_id,
-                    rect
+                    rect,
+                    window
                 })
             }
             else if rect.contains(abs) {
@@ -405,7 +536,8 @@ impl Dock {
                                 x: rect.size.x / 2.0,
                                 y: rect.size.y,
                             },
-                        }
+                        },
+                        window
                     })
                 } else if (bottom_right.x - abs.x) / rect.size.x < 0.1 {
                     return Some(DropPosition {
@@ -420,7 +552,8 @@ impl Dock {
                                 x: rect.size.x / 2.0,
                                 y: rect.size.y,
                             },
-                        }
+                        },
+                        window
                     })
                 } else if (abs.y - top_left.y) / rect.size.y < 0.1 {
                     return Some(DropPosition {
@@ -432,7 +565,8 @@ impl Dock {
                                 x: rect.size.x,
                                 y: rect.size.y / 2.0,
                             },
-                        }
+                        },
+                        window
                     })
                 } else if (bottom_right.y - abs.y) / rect.size.y < 0.1 {
                     return Some(DropPosition {
@@ -447,13 +581,15 @@ impl Dock {
                                 x: rect.size.x,
                                 y: rect.size.y / 2.0,
                             },
-                        }
+                        },
+                        window
                     })
                 } else {
                     return Some(DropPosition {
                         part: DropPart::Center,
                         id: *tab_bar_id,
-                        rect
+                        rect,
+                        window
                     })
                 }
             }
@@ -503,13 +639,162 @@ impl Dock {
                         kind: LiveIdStore(*kind)
                     });
                 }
+                DockItem::Window {
+                    tabs,
+                    position,
+                    size
+                }=>{
+                    out.push(DockItemStore::Window{
+                        id:LiveIdStore(*id),
+                        tabs:LiveIdStore(*tabs),
+                        x: position.x,
+                        y: position.y,
+                        width: size.x,
+                        height: size.y
+                    });
+                }
             }
         }
         out
     }
     
-    pub fn from_store_item(&mut self, _store:&[DockItemStore]){
+    /// Replaces the current layout with one previously produced by `to_store_items`.
+    /// Tabs whose template no longer exists are dropped, and splitters left with an
+    /// empty or missing side collapse into the other side. Content widgets of tabs
+    /// that survive with the same template are kept, so their state is preserved.
+    pub fn from_store_item(&mut self, cx: &mut Cx, store: &[DockItemStore]) {
+        let mut dock_items = HashMap::new();
+        for item in store {
+            match item {
+                DockItemStore::Splitter {id, axis, align, a, b} => {
+                    dock_items.insert(id.0, DockItem::Splitter {
+                        axis: *axis,
+                        align: *align,
+                        a: a.0,
+                        b: b.0
+                    });
+                }
+                DockItemStore::Tabs {id, tabs, selected, closable} => {
+                    dock_items.insert(id.0, DockItem::Tabs {
+                        tabs: tabs.iter().map( | v | v.0).collect(),
+                        selected: *selected,
+                        closable: *closable
+                    });
+                }
+                DockItemStore::Tab {id, name, closable, kind} => {
+                    if !self.templates.contains_key(&kind.0) {
+                        log!("Dock restore: template not found {}, dropping tab {}", kind.0, id.0);
+                        continue;
+                    }
+                    dock_items.insert(id.0, DockItem::Tab {
+                        name: name.clone(),
+                        closable: *closable,
+                        kind: kind.0
+                    });
+                }
+                DockItemStore::Window {id, tabs, x, y, width, height} => {
+                    dock_items.insert(id.0, DockItem::Window {
+                        tabs: tabs.0,
+                        position: dvec2(*x, *y),
+                        size: dvec2(*width, *height)
+                    });
+                }
+            }
+        }
+        if !dock_items.contains_key(&live_id!(root)) {
+            log!("Dock restore: stored layout has no root item, ignoring it");
+            return
+        }
+        
+        // tab bars can only point at tabs that made it through
+        let tab_ids: Vec<LiveId> = dock_items.iter().filter_map( | (id, item) | {
+            if let DockItem::Tab {..} = item {Some(*id)} else {None}
+        }).collect();
+        for item in dock_items.values_mut() {
+            if let DockItem::Tabs {tabs, selected, ..} = item {
+                tabs.retain( | v | tab_ids.contains(v));
+                if *selected >= tabs.len() {
+                    *selected = tabs.len().max(1) - 1;
+                }
+            }
+        }
+        
+        // collapse splitters that lost one of their sides
+        let is_dead = | dock_items: &HashMap<LiveId, DockItem>, id: LiveId | {
+            match dock_items.get(&id) {
+                None => true,
+                Some(DockItem::Tabs {tabs, closable, ..}) => *closable && tabs.len() == 0,
+                _ => false
+            }
+        };
+        loop {
+            let collapse = dock_items.iter().find_map( | (id, item) | {
+                if let DockItem::Splitter {a, b, ..} = item {
+                    if is_dead(&dock_items, *a) {
+                        return Some((*id, *a, *b))
+                    }
+                    if is_dead(&dock_items, *b) {
+                        return Some((*id, *b, *a))
+                    }
+                }
+                None
+            });
+            let Some((splitter_id, dead, alive)) = collapse else {break};
+            dock_items.remove(&splitter_id);
+            dock_items.remove(&dead);
+            if splitter_id == live_id!(root) {
+                if let Some(item) = dock_items.remove(&alive) {
+                    dock_items.insert(splitter_id, item);
+                }
+            }
+            else {
+                for item in dock_items.values_mut() {
+                    match item {
+                        DockItem::Splitter {a, b, ..} => {
+                            if *a == splitter_id {*a = alive}
+                            if *b == splitter_id {*b = alive}
+                        }
+                        DockItem::Window {tabs, ..} => {
+                            if *tabs == splitter_id {*tabs = alive}
+                        }
+                        _ => ()
+                    }
+                }
+            }
+        }
+        let alive: Vec<LiveId> = dock_items.keys().filter( | id | !is_dead(&dock_items, **id)).cloned().collect();
+        // and floating windows that have nothing left to show
+        dock_items.retain( | _, item | {
+            if let DockItem::Window {tabs, ..} = item {
+                return alive.contains(tabs)
+            }
+            true
+        });
+        if !dock_items.contains_key(&live_id!(root)) {
+            dock_items.insert(live_id!(root), DockItem::Tabs {
+                tabs: vec![],
+                selected: 0,
+                closable: false
+            });
+        }
+        
+        self.dock_items = dock_items;
+        self.drop_state = None;
         
+        // keep content widgets that are still in use, create the missing ones
+        let dock_items = &self.dock_items;
+        self.items.retain( | id, (kind, _) | {
+            matches!(dock_items.get(id), Some(DockItem::Tab {kind: k, ..}) if k == kind)
+        });
+        for tab_id in tab_ids {
+            if let Some(DockItem::Tab {kind, ..}) = self.dock_items.get(&tab_id) {
+                let kind = *kind;
+                self.item_or_create(cx, tab_id, kind);
+            }
+        }
+        
+        self.needs_save = false;
+        self.area.redraw(cx);
     }
     
     pub fn item(&mut self, entry_id: LiveId) -> Option<WidgetRef> {
@@ -559,6 +844,12 @@ impl Dock {
                         return
                     }
                 }
+                DockItem::Window {tabs, ..} => {
+                    if what_item == *tabs {
+                        *tabs = replace_item;
+                        return
+                    }
+                }
                 _ => ()
             }
         }
@@ -577,6 +868,17 @@ impl Dock {
     
     fn unsplit_tabs(&mut self, cx: &mut Cx, tabs_id: LiveId) {
         self.needs_save = true;        
+        // the last tabs of a floating window take the window with them
+        let window_id = self.dock_items.iter().find_map( | (window_id, item) | match item {
+            DockItem::Window {tabs, ..} if *tabs == tabs_id => Some(*window_id),
+            _ => None
+        });
+        if let Some(window_id) = window_id {
+            self.dock_items.remove(&window_id);
+            self.dock_items.remove(&tabs_id);
+            self.area.redraw(cx);
+            return
+        }
         for (splitter_id, item) in self.dock_items.iter_mut() {
             match *item {
                 DockItem::Splitter {a, b, ..} => {
@@ -684,6 +986,94 @@ impl Dock {
         None
     }
     
+    /// Moves a tab out of the dock into a new floating window at `position` in screen space
+    fn tear_off_tab(&mut self, cx: &mut Cx, tab_id: LiveId, position: DVec2) {
+        let Some(tabs_id) = self.find_tab_bar_of_tab(tab_id) else {
+            return
+        };
+        // a lone tab in a floating window is already torn off
+        let window_items = self.window_items();
+        if let Some(DockItem::Tabs {tabs, ..}) = self.dock_items.get(&tabs_id) {
+            if tabs.len() == 1 && window_items.contains_key(&tabs_id) {
+                return
+            }
+        }
+        let size = self.tab_bars.get(&tabs_id)
+            .map( | tab_bar | tab_bar.contents_rect.size)
+            .filter( | size | size.x > 0.0 && size.y > 0.0)
+            .unwrap_or(dvec2(800.0, 600.0));
+        self.close_tab(cx, tab_id, true);
+        let new_tabs = LiveId::unique();
+        self.dock_items.insert(new_tabs, DockItem::Tabs {
+            tabs: vec![tab_id],
+            selected: 0,
+            closable: true
+        });
+        self.dock_items.insert(LiveId::unique(), DockItem::Window {
+            tabs: new_tabs,
+            position,
+            size
+        });
+        self.needs_save = true;
+        self.area.redraw(cx);
+    }
+    
+    /// Puts the tabs of a floating window back into the first tab bar of the main dock
+    fn redock_window(&mut self, cx: &mut Cx, window_id: LiveId) {
+        let Some(DockItem::Window {tabs, ..}) = self.dock_items.remove(&window_id) else {
+            return
+        };
+        let mut tab_ids = Vec::new();
+        let mut stack = vec![tabs];
+        while let Some(id) = stack.pop() {
+            match self.dock_items.get(&id) {
+                Some(DockItem::Splitter {a, b, ..}) => {
+                    stack.push(*b);
+                    stack.push(*a);
+                }
+                Some(DockItem::Tabs {tabs, ..}) => {
+                    tab_ids.extend(tabs.iter().cloned());
+                }
+                Some(DockItem::Tab {..}) => {
+                    tab_ids.push(id);
+                    continue;
+                }
+                _ => ()
+            }
+            self.dock_items.remove(&id);
+        }
+        // walk the main dock depth first so the tabs end up somewhere predictable
+        let mut target = None;
+        let mut stack = vec![live_id!(root)];
+        while let Some(id) = stack.pop() {
+            match self.dock_items.get(&id) {
+                Some(DockItem::Splitter {a, b, ..}) => {
+                    stack.push(*b);
+                    stack.push(*a);
+                }
+                Some(DockItem::Tabs {..}) => {
+                    target = Some(id);
+                    break
+                }
+                _ => ()
+            }
+        }
+        if let Some(DockItem::Tabs {tabs, selected, ..}) = target.and_then( | id | self.dock_items.get_mut(&id)) {
+            tabs.extend(tab_ids.iter().cloned());
+            *selected = tabs.len().max(1) - 1;
+            if let Some(tab_bar) = target.and_then( | id | self.tab_bars.get(&id)) {
+                tab_bar.contents_draw_list.redraw(cx);
+            }
+        }
+        else {
+            for tab_id in tab_ids {
+                self.dock_items.remove(&tab_id);
+            }
+        }
+        self.needs_save = true;
+        self.area.redraw(cx);
+    }
+    
     fn check_drop_is_noop(&mut self, tab_id: LiveId, item_id: LiveId) -> bool {
         // ok so we have to find the tab id in our tab bars / tabs and remove it
         // if we are the last tab we need to remove a splitter
@@ -880,8 +1270,52 @@ impl Widget for Dock {
     fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope:&mut Scope) {
         // call handle on all tab bars, splitters,
         let uid = self.widget_uid();
+        
+        // events of our floating windows
+        match event {
+            Event::WindowClosed(e) => if let Some(window) = self.window_item_of(e.window_id) {
+                self.windows.remove(&window);
+                self.redock_window(cx, window);
+            }
+            Event::WindowGeomChange(e) => if let Some(window) = self.window_item_of(e.window_id) {
+                if let Some(DockItem::Window {position, size, ..}) = self.dock_items.get_mut(&window) {
+                    *position = e.new_geom.position;
+                    *size = e.new_geom.inner_size;
+                    self.needs_save = true;
+                }
+                if let Some(dock_window) = self.windows.get(&window) {
+                    dock_window.main_draw_list.redraw(cx);
+                }
+            }
+            Event::DropOutside(e) => if let Some(tab_id) = self.drag_tab {
+                if self.tear_off && !cx.os_type().is_single_window() {
+                    self.tear_off_tab(cx, tab_id, e.position);
+                }
+            }
+            _ => ()
+        }
+        
+        // input only goes to the part of the dock in the window it happened in
+        let window_items = self.window_items();
+        let target = event.window_id().map( | window_id | self.window_item_of(window_id));
+        let in_target = | id: &LiveId | target.map_or(true, | window | window_items.get(id).cloned() == window);
+        
+        if let Event::WindowDragQuery(e) = event {
+            // the empty part of a floating tab bar moves the window
+            if let Some(Some(_)) = target {
+                for (tabs_id, tab_bar) in self.tab_bars.iter() {
+                    if in_target(tabs_id) && tab_bar.tab_bar.is_over_tab_bar(cx, e.abs).is_some() && tab_bar.tab_bar.is_over_tab(cx, e.abs).is_none() {
+                        e.response.set(WindowDragQueryResponse::Caption);
+                    }
+                }
+            }
+        }
+        
         let dock_items = &mut self.dock_items;
         for (panel_id, splitter) in self.splitters.iter_mut() {
+            if !in_target(panel_id) {
+                continue;
+            }
             for action in cx.capture_actions(|cx| splitter.handle_event(cx, event, scope)) {
                 // alright so here we need to redraw the left/right area.. how?
                 
@@ -899,6 +1333,9 @@ impl Widget for Dock {
             };
         }
         for (panel_id, tab_bar) in self.tab_bars.iter_mut() {
+            if !in_target(panel_id) {
+                continue;
+            }
             let contents_view = &mut tab_bar.contents_draw_list;
             for action in cx.capture_actions(|cx| tab_bar.tab_bar.handle_event(cx, event, scope)) {
                 match action.as_widget_action().cast() {
@@ -924,6 +1361,9 @@ impl Widget for Dock {
             };
         }
         for (id,(_templ_id, item)) in self.items.iter_mut() {
+            if !in_target(id) {
+                continue;
+            }
             scope.with_id(*id, |scope|{
                item.handle_event(cx, event, scope);
             });
@@ -932,14 +1372,22 @@ impl Widget for Dock {
         if let Event::DragEnd = event {
             // end our possible dragstate
             self.drop_state = None;
-            self.drop_target_draw_list.redraw(cx);
+            self.drag_tab = None;
+            self.redraw_drop_targets(cx);
         }
         
         // alright lets manage the drag areas
-        match event.drag_hits(cx, self.area) {
+        let drag_area = match target {
+            Some(Some(window)) => self.windows.get(&window).map( | dock_window | dock_window.area).unwrap_or(Area::Empty),
+            _ => self.area
+        };
+        if let Event::Drag(_) | Event::Drop(_) = event {
+            self.drag_window = event.window_id();
+        }
+        match event.drag_hits(cx, drag_area) {
             DragHit::Drag(f) => {
                 self.drop_state = None;
-                self.drop_target_draw_list.redraw(cx);
+                self.redraw_drop_targets(cx);
                 match f.state {
                     DragState::In | DragState::Over => {
                         cx.widget_action(uid, &scope.path, DockAction::Drag(f.clone()))
@@ -949,7 +1397,7 @@ impl Widget for Dock {
             }
             DragHit::Drop(f) => {
                 self.drop_state = None;
-                self.drop_target_draw_list.redraw(cx);
+                self.redraw_drop_targets(cx);
                 cx.widget_action(uid, &scope.path, DockAction::Drop(f.clone()))
             }
             _ => {}
@@ -957,9 +1405,19 @@ impl Widget for Dock {
     }
     
     fn draw_walk(&mut self, cx: &mut Cx2d, scope:&mut Scope, walk: Walk) -> DrawStep {
-        if self.draw_state.begin_with(cx, &self.dock_items, | _, dock_items | {
+        if self.draw_state.begin_with(cx, &self.dock_items, | cx, dock_items | {
+            // the floating windows are drawn after the main dock
+            let mut stack = Vec::new();
+            if !cx.os_type().is_single_window() {
+                for (id, item) in dock_items {
+                    if let DockItem::Window {..} = item {
+                        stack.push(DrawStackItem::Window {id: *id});
+                    }
+                }
+            }
             let id = live_id!(root);
-            vec![DrawStackItem::from_dock_item(id, dock_items.get(&id))]
+            stack.push(DrawStackItem::from_dock_item(id, dock_items.get(&id)));
+            stack
         }) {
             self.begin(cx, walk);
         }
@@ -1061,6 +1519,49 @@ impl Widget for Dock {
                     }
                     else {panic!()}
                 }
+                Some(DrawStackItem::Window {id}) => {
+                    if let Some(DockItem::Window {tabs, position, size}) = self.dock_items.get(&id) {
+                        let title = match self.dock_items.get(tabs) {
+                            Some(DockItem::Tabs {tabs, selected, ..}) => match tabs.get(*selected).and_then( | tab | self.dock_items.get(tab)) {
+                                Some(DockItem::Tab {name, ..}) => name.clone(),
+                                _ => String::new()
+                            }
+                            _ => String::new()
+                        };
+                        let owner = cx.current_window_id();
+                        let dock_window = self.windows.get_or_insert(cx, id, | cx | {
+                            DockWindow::new(cx, &title, *position, *size)
+                        });
+                        if let Some(owner) = owner {
+                            dock_window.window.set_owner(cx, owner);
+                        }
+                        if cx.will_redraw(&mut dock_window.main_draw_list, Walk::default()) {
+                            cx.begin_pass(&dock_window.pass, None);
+                            dock_window.main_draw_list.begin_always(cx);
+                            cx.begin_pass_sized_turtle(self.layout);
+                            let size = cx.current_pass_size();
+                            self.padding_fill.draw_abs(cx, Rect {pos: DVec2::default(), size});
+                            dock_window.area = self.padding_fill.area();
+                            stack.push(DrawStackItem::WindowEnd {id});
+                            stack.push(DrawStackItem::from_dock_item(*tabs, self.dock_items.get(tabs)));
+                        }
+                    }
+                }
+                Some(DrawStackItem::WindowEnd {id}) => {
+                    let dock_window = self.windows.get_mut(&id).unwrap();
+                    if dock_window.drop_target_draw_list.begin(cx, Walk::default()).is_redrawing() {
+                        if let Some(pos) = &self.drop_state {
+                            if pos.window == Some(id) {
+                                self.drag_quad.draw_abs(cx, pos.rect);
+                            }
+                        }
+                        dock_window.drop_target_draw_list.end(cx);
+                    }
+                    self.round_corner.draw_corners(cx, cx.turtle().rect());
+                    cx.end_pass_sized_turtle();
+                    dock_window.main_draw_list.end(cx);
+                    cx.end_pass(&dock_window.pass);
+                }
                 Some(DrawStackItem::Invalid) => {}
                 None => {
                     break
@@ -1188,6 +1689,12 @@ impl DockRef {
         LiveId(0)
     }
         
+    pub fn from_store_item(&self, cx: &mut Cx, store: &[DockItemStore]) {
+        if let Some(mut dock) = self.borrow_mut() {
+            dock.from_store_item(cx, store);
+        }
+    }
+    
     pub fn needs_save(&self)->Option<Vec<DockItemStore>>{
         if let Some(mut dock) = self.borrow_mut() {
             if dock.needs_save{
@@ -1198,7 +1705,11 @@ impl DockRef {
         None
     }
     
-    pub fn tab_start_drag(&self, cx: &mut Cx, _tab_id: LiveId, item: DragItem) {
+    pub fn tab_start_drag(&self, cx: &mut Cx, tab_id: LiveId, item: DragItem) {
+        if let Some(mut dock) = self.borrow_mut() {
+            // remembered so the tab can be torn off when it's dropped outside our windows
+            dock.drag_tab = Some(tab_id);
+        }
         cx.start_dragging(vec![item]);
     }
 }
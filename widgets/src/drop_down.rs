@@ -32,6 +32,7 @@ pub struct DropDown {
     #[live] values: Vec<LiveValue>,
     
     #[live] popup_shift: DVec2,
+    #[live] nav_order: NavOrder,
     
     #[rust] is_open: bool,
     
@@ -117,7 +118,7 @@ impl DropDown {
         }
         self.draw_bg.end(cx);
         
-        cx.add_nav_stop(self.draw_bg.area(), NavRole::DropDown, Margin::default());
+        cx.add_nav_stop_ordered(self.draw_bg.area(), NavRole::DropDown, Margin::default(), self.nav_order);
         
         if self.is_open && self.popup_menu.is_some() {
             //cx.set_sweep_lock(self.draw_bg.area());
@@ -147,6 +148,9 @@ impl DropDown {
 }
 
 impl Widget for DropDown {
+    fn widget_area(&self) -> Area {
+        self.draw_bg.area()
+    }
     
     fn widget_to_data(&self, _cx: &mut Cx, actions: &Actions, nodes: &mut LiveNodeVec, path: &[LiveId]) -> bool {
         match actions.find_widget_action_cast(self.widget_uid()) {
@@ -237,6 +241,14 @@ impl Widget for DropDown {
                         self.draw_bg.redraw(cx);
                     }
                 },
+                KeyCode::ReturnKey | KeyCode::NumpadEnter | KeyCode::Space => {
+                    if self.is_open {
+                        self.set_closed(cx);
+                    }
+                    else {
+                        self.set_open(cx);
+                    }
+                }
                 _ => ()
             }
             Hit::FingerDown(_fe) => {
@@ -372,6 +372,20 @@ impl FileTree {
         }
     }
     
+    pub fn set_selected_node(&mut self, cx: &mut Cx, node_id: FileNodeId, animate: Animate) {
+        if let Some(last_selected) = self.selected_node_id {
+            if last_selected != node_id {
+                if let Some((tree_node, _)) = self.tree_nodes.get_mut(&last_selected) {
+                    tree_node.set_is_selected(cx, false, animate);
+                }
+            }
+        }
+        self.selected_node_id = Some(node_id);
+        if let Some((tree_node, _)) = self.tree_nodes.get_mut(&node_id) {
+            tree_node.set_is_selected(cx, true, animate);
+        }
+    }
+    
     pub fn start_dragging_file_node(
         &mut self,
         cx: &mut Cx,
@@ -390,6 +404,9 @@ impl FileTree {
 pub struct FileNodeId(pub LiveId);
 
 impl Widget for FileTree {
+    fn widget_area(&self) -> Area {
+        self.scroll_bars.area()
+    }
 
     fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
         let uid = self.widget_uid();
@@ -486,6 +503,28 @@ impl FileTreeRef{
     }
     
     
+    pub fn folder_clicked(&self, actions: &Actions) -> Option<FileNodeId> {
+        if let Some(item) = actions.find_widget_action(self.widget_uid()) {
+            if let FileTreeAction::FolderClicked(file_id) = item.cast() {
+                return Some(file_id)
+            }
+        }
+        None
+    }
+    
+    pub fn set_selected_node(&self, cx: &mut Cx, node_id: FileNodeId, animate: Animate) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_selected_node(cx, node_id, animate);
+            inner.redraw(cx);
+        }
+    }
+    
+    pub fn set_folder_is_open(&self, cx: &mut Cx, node_id: FileNodeId, is_open: bool, animate: Animate) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_folder_is_open(cx, node_id, is_open, animate);
+        }
+    }
+    
     pub fn file_start_drag(&self, cx: &mut Cx, _file_id: FileNodeId, item: DragItem) {
         cx.start_dragging(vec![item]);
     }
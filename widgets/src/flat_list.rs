@@ -136,6 +136,10 @@ impl FlatList {
 
 
 impl Widget for FlatList {
+    fn widget_area(&self) -> Area {
+        self.scroll_bars.area()
+    }
+    
     fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
 
         let uid = self.widget_uid();
@@ -50,6 +50,9 @@ impl FoldButton {
 }
 
 impl Widget for FoldButton {
+    fn widget_area(&self) -> Area {
+        self.draw_bg.area()
+    }
 
     fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope:&mut Scope) {
         let uid = self.widget_uid();
@@ -0,0 +1,338 @@
+use crate::image_animation::{AnimationFrame, FrameBlend, FrameDispose};
+use std::fmt;
+use std::rc::Rc;
+
+/// The errors that can occur while decoding a GIF stream.
+#[derive(Debug)]
+pub enum GifDecodeErrors {
+    /// The data does not start with `GIF87a` or `GIF89a`.
+    BadSignature,
+    /// The data ended in the middle of a block.
+    UnexpectedEof,
+    /// An unknown block introducer was found at the given offset.
+    UnknownBlock(usize),
+    /// The LZW image data referenced a code that is not in the dictionary.
+    InvalidLzwCode,
+}
+
+impl fmt::Display for GifDecodeErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+const MAX_LZW_CODES: usize = 4096;
+
+/// Incremental GIF decoder: every call to `next_frame` decodes exactly one image
+/// from the stream, so animations never have to be decoded up front.
+pub struct GifDecoder {
+    data: Rc<Vec<u8>>,
+    pos: usize,
+    first_block: usize,
+    pub width: usize,
+    pub height: usize,
+    global_palette: Vec<u32>,
+    /// Number of times to play the animation (NETSCAPE2.0 extension); `Some(0)` loops forever.
+    /// Only known once the extension has been read, which is normally before the first frame.
+    pub loop_count: Option<u16>,
+}
+
+impl GifDecoder {
+    pub fn new(data: Rc<Vec<u8>>) -> Result<Self, GifDecodeErrors> {
+        if data.len() < 13 || (&data[0..6] != b"GIF87a" && &data[0..6] != b"GIF89a") {
+            return Err(GifDecodeErrors::BadSignature)
+        }
+        let width = u16::from_le_bytes([data[6], data[7]]) as usize;
+        let height = u16::from_le_bytes([data[8], data[9]]) as usize;
+        let packed = data[10];
+        let mut pos = 13;
+        let mut global_palette = Vec::new();
+        if packed & 0x80 != 0 {
+            let size = 3 << ((packed & 0x07) + 1);
+            if data.len() < pos + size {
+                return Err(GifDecodeErrors::UnexpectedEof)
+            }
+            global_palette = read_palette(&data[pos..pos + size]);
+            pos += size;
+        }
+        Ok(Self {
+            data,
+            pos,
+            first_block: pos,
+            width,
+            height,
+            global_palette,
+            loop_count: None,
+        })
+    }
+
+    /// Starts decoding again from the first frame.
+    pub fn rewind(&mut self) {
+        self.pos = self.first_block;
+    }
+
+    /// Counts the images in the stream without decoding their pixel data.
+    pub fn frame_count(&self) -> usize {
+        let mut pos = self.first_block;
+        let mut count = 0;
+        let data = &self.data;
+        while pos < data.len() {
+            match data[pos] {
+                0x21 => {
+                    pos = skip_sub_blocks(data, pos + 2);
+                }
+                0x2c => {
+                    count += 1;
+                    if pos + 10 > data.len() {
+                        break;
+                    }
+                    let packed = data[pos + 9];
+                    pos += 10;
+                    if packed & 0x80 != 0 {
+                        pos += 3 << ((packed & 0x07) + 1);
+                    }
+                    pos = skip_sub_blocks(data, pos + 1);
+                }
+                _ => break
+            }
+        }
+        count
+    }
+
+    /// Decodes the next image, returning `None` at the end of the stream.
+    pub fn next_frame(&mut self) -> Result<Option<AnimationFrame>, GifDecodeErrors> {
+        let mut delay = 0.0;
+        let mut dispose = FrameDispose::None;
+        let mut transparent = None;
+        loop {
+            match self.byte()? {
+                0x21 => {
+                    let label = self.byte()?;
+                    match label {
+                        0xf9 => {
+                            let block = self.sub_block()?;
+                            if block.len() >= 4 {
+                                dispose = match (block[0] >> 2) & 0x07 {
+                                    2 => FrameDispose::Background,
+                                    3 => FrameDispose::Previous,
+                                    _ => FrameDispose::None
+                                };
+                                delay = u16::from_le_bytes([block[1], block[2]]) as f64 / 100.0;
+                                if block[0] & 0x01 != 0 {
+                                    transparent = Some(block[3] as usize);
+                                }
+                            }
+                            self.skip_sub_blocks()?;
+                        }
+                        0xff => {
+                            let block = self.sub_block()?;
+                            if block == b"NETSCAPE2.0" || block == b"ANIMEXTS1.0" {
+                                let sub = self.sub_block()?;
+                                if sub.len() >= 3 && sub[0] == 1 {
+                                    self.loop_count = Some(u16::from_le_bytes([sub[1], sub[2]]));
+                                }
+                            }
+                            self.skip_sub_blocks()?;
+                        }
+                        _ => self.skip_sub_blocks()?
+                    }
+                }
+                0x2c => {
+                    return self.read_image(delay, dispose, transparent).map(Some)
+                }
+                0x3b => return Ok(None),
+                _ => return Err(GifDecodeErrors::UnknownBlock(self.pos - 1))
+            }
+        }
+    }
+
+    fn read_image(&mut self, delay: f64, dispose: FrameDispose, transparent: Option<usize>) -> Result<AnimationFrame, GifDecodeErrors> {
+        let desc = self.bytes(9)?;
+        let x = u16::from_le_bytes([desc[0], desc[1]]) as usize;
+        let y = u16::from_le_bytes([desc[2], desc[3]]) as usize;
+        let width = u16::from_le_bytes([desc[4], desc[5]]) as usize;
+        let height = u16::from_le_bytes([desc[6], desc[7]]) as usize;
+        let packed = desc[8];
+        let local_palette = if packed & 0x80 != 0 {
+            let size = 3 << ((packed & 0x07) + 1);
+            Some(read_palette(self.bytes(size)?))
+        }
+        else {
+            None
+        };
+        let min_code_size = self.byte()?;
+        let mut lzw_data = Vec::new();
+        loop {
+            let block = self.sub_block()?;
+            if block.is_empty() {
+                break;
+            }
+            lzw_data.extend_from_slice(block);
+        }
+        let indices = lzw_decode(&lzw_data, min_code_size, width * height)?;
+
+        let palette = local_palette.as_ref().unwrap_or(&self.global_palette);
+        let mut data = vec![0u32; width * height];
+        let rows = interlaced_rows(height, packed & 0x40 != 0);
+        for (src_row, dst_row) in rows.into_iter().enumerate() {
+            for col in 0..width {
+                let Some(&index) = indices.get(src_row * width + col) else {break};
+                let index = index as usize;
+                if Some(index) == transparent {
+                    continue;
+                }
+                data[dst_row * width + col] = palette.get(index).copied().unwrap_or(0xff000000);
+            }
+        }
+        Ok(AnimationFrame {
+            x,
+            y,
+            width,
+            height,
+            data,
+            delay,
+            dispose,
+            blend: FrameBlend::Over,
+        })
+    }
+
+    fn byte(&mut self) -> Result<u8, GifDecodeErrors> {
+        let byte = *self.data.get(self.pos).ok_or(GifDecodeErrors::UnexpectedEof)?;
+        self.pos += 1;
+        Ok(byte)
+    }
+
+    fn bytes(&mut self, len: usize) -> Result<&[u8], GifDecodeErrors> {
+        if self.pos + len > self.data.len() {
+            return Err(GifDecodeErrors::UnexpectedEof)
+        }
+        self.pos += len;
+        Ok(&self.data[self.pos - len..self.pos])
+    }
+
+    fn sub_block(&mut self) -> Result<&[u8], GifDecodeErrors> {
+        let len = self.byte()? as usize;
+        self.bytes(len)
+    }
+
+    fn skip_sub_blocks(&mut self) -> Result<(), GifDecodeErrors> {
+        while !self.sub_block()?.is_empty() {}
+        Ok(())
+    }
+}
+
+fn read_palette(data: &[u8]) -> Vec<u32> {
+    data.chunks_exact(3).map( | c | 0xff000000 | ((c[0] as u32) << 16) | ((c[1] as u32) << 8) | (c[2] as u32)).collect()
+}
+
+fn skip_sub_blocks(data: &[u8], mut pos: usize) -> usize {
+    while pos < data.len() {
+        let len = data[pos] as usize;
+        pos += 1 + len;
+        if len == 0 {
+            break;
+        }
+    }
+    pos
+}
+
+/// Maps decoded row order to image rows, taking the 4-pass GIF interlacing into account.
+fn interlaced_rows(height: usize, interlaced: bool) -> Vec<usize> {
+    if !interlaced {
+        return (0..height).collect()
+    }
+    let mut rows = Vec::with_capacity(height);
+    for (start, step) in [(0, 8), (4, 8), (2, 4), (1, 2)] {
+        rows.extend((start..height).step_by(step));
+    }
+    rows
+}
+
+fn lzw_decode(data: &[u8], min_code_size: u8, max_len: usize) -> Result<Vec<u8>, GifDecodeErrors> {
+    let min_code_size = min_code_size.clamp(2, 11) as usize;
+    let clear_code = 1 << min_code_size;
+    let end_code = clear_code + 1;
+
+    // each dictionary entry is (prefix code, last byte, length)
+    let mut prefix = vec![0u16; MAX_LZW_CODES];
+    let mut suffix = vec![0u8; MAX_LZW_CODES];
+    let mut length = vec![0u16; MAX_LZW_CODES];
+    for i in 0..clear_code {
+        suffix[i] = i as u8;
+        length[i] = 1;
+    }
+
+    let mut out = Vec::with_capacity(max_len);
+    let mut code_size = min_code_size + 1;
+    let mut next_code = end_code + 1;
+    let mut prev: Option<usize> = None;
+    let mut bits = 0u32;
+    let mut bit_count = 0;
+    let mut bytes = data.iter();
+    let mut entry = Vec::new();
+
+    loop {
+        while bit_count < code_size {
+            let Some(&byte) = bytes.next() else {return Ok(out)};
+            bits |= (byte as u32) << bit_count;
+            bit_count += 8;
+        }
+        let code = (bits & ((1 << code_size) - 1)) as usize;
+        bits >>= code_size;
+        bit_count -= code_size;
+
+        if code == clear_code {
+            code_size = min_code_size + 1;
+            next_code = end_code + 1;
+            prev = None;
+            continue;
+        }
+        if code == end_code {
+            return Ok(out)
+        }
+        let Some(prev_code) = prev else {
+            if code >= clear_code {
+                return Err(GifDecodeErrors::InvalidLzwCode)
+            }
+            out.push(code as u8);
+            prev = Some(code);
+            continue;
+        };
+
+        // the special case where the code is the one we are about to add
+        let known = code < next_code;
+        if !known && code != next_code {
+            return Err(GifDecodeErrors::InvalidLzwCode)
+        }
+        entry.clear();
+        let mut walk = if known {code} else {prev_code};
+        loop {
+            entry.push(suffix[walk]);
+            if length[walk] <= 1 {
+                break;
+            }
+            walk = prefix[walk] as usize;
+        }
+        entry.reverse();
+        let first = entry[0];
+        if !known {
+            entry.push(first);
+        }
+        out.extend_from_slice(&entry);
+        if out.len() >= max_len {
+            out.truncate(max_len);
+            return Ok(out)
+        }
+
+        if next_code < MAX_LZW_CODES {
+            prefix[next_code] = prev_code as u16;
+            suffix[next_code] = first;
+            length[next_code] = length[prev_code] + 1;
+            next_code += 1;
+            if next_code == 1 << code_size && code_size < 12 {
+                code_size += 1;
+            }
+        }
+        prev = Some(code);
+    }
+}
@@ -35,6 +35,10 @@ impl LiveHook for Html{
 }
  
 impl Widget for Html {
+    fn widget_area(&self) -> Area {
+        self.text_flow.widget_area()
+    }
+    
     fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
         self.text_flow.handle_event(cx, event, scope);
     }
@@ -1,10 +1,13 @@
 use crate::{
     makepad_derive_widget::*,
     image_cache::*,
+    image_animation::ImageAnimation,
+    gif::GifDecodeErrors,
     makepad_draw::*,
     widget::*
 };
 use std::fmt;
+use std::rc::Rc;
 
 live_design!{
     ImageBase = {{Image}} {}
@@ -19,7 +22,23 @@ pub struct Image {
     #[live(1.0)] width_scale: f64,
     #[live] fit: ImageFit,
     #[live] source: LiveDependency,
+    /// Decode `source` on the image cache worker pool, downscaled to the drawn size.
+    #[live] async_load: bool,
+    /// Start playing animated PNGs and GIFs as soon as they are loaded.
+    #[live(true)] autoplay: bool,
     #[rust] texture: Option<Texture>,
+    #[rust] async_state: ImageAsyncState,
+    #[rust] animation: Option<ImageAnimation>,
+    #[rust] animation_path: String,
+    #[rust] next_frame: NextFrame,
+}
+
+#[derive(Default)]
+enum ImageAsyncState {
+    #[default]
+    Idle,
+    Pending(ImageCacheKey),
+    Done,
 }
 
 impl ImageCacheImpl for Image {
@@ -36,13 +55,60 @@ impl LiveHook for Image{
     fn after_apply(&mut self, cx: &mut Cx, _applyl: &mut Apply, _index: usize, _nodes: &[LiveNode]) {
         self.lazy_create_image_cache(cx);
         let source = self.source.clone();
-        if source.as_str().len()>0 {
-            let _ = self.load_image_dep_by_path(cx, source.as_str());
+        if self.async_load {
+            // the actual request is made on draw, when the display size is known
+            if let ImageAsyncState::Pending(key) = &self.async_state {
+                if key.path == source.as_str() {
+                    return
+                }
+            }
+            self.async_state = ImageAsyncState::Idle;
+        }
+        else if source.as_str().len()>0 {
+            if !self.load_animation_dep_by_path(cx, source.as_str()) {
+                self.animation = None;
+                let _ = self.load_image_dep_by_path(cx, source.as_str());
+            }
         }
     }
 }
 
 impl Widget for Image {
+    fn widget_area(&self) -> Area {
+        self.draw_bg.area()
+    }
+    
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, _scope: &mut Scope) {
+        if let Some(ne) = self.next_frame.is_event(event) {
+            if let Some(animation) = &mut self.animation {
+                if animation.handle_time(ne.time) {
+                    if let Some(texture) = &self.texture {
+                        animation.update_texture(cx, texture);
+                    }
+                    self.draw_bg.redraw(cx);
+                }
+                if animation.is_playing() {
+                    self.next_frame = cx.new_next_frame();
+                }
+            }
+        }
+        if let Event::Signal = event {
+            if let ImageAsyncState::Pending(key) = &self.async_state {
+                let key = key.clone();
+                match self.poll_async_image_load(cx, &key) {
+                    Ok(AsyncImageLoad::Pending(_)) => (),
+                    Ok(AsyncImageLoad::Ready) => {
+                        self.async_state = ImageAsyncState::Done;
+                        self.redraw(cx);
+                    }
+                    Err(_) => {
+                        self.async_state = ImageAsyncState::Done;
+                    }
+                }
+            }
+        }
+    }
+    
     fn draw_walk(&mut self, cx: &mut Cx2d, _scope: &mut Scope, walk: Walk) -> DrawStep {
         self.draw_walk(cx, walk)
     }
@@ -55,6 +121,9 @@ impl Image {
         // we change either nothing, or width or height
         let rect = cx.peek_walk_turtle(walk);
         let dpi = cx.current_dpi_factor();
+        if self.async_load {
+            self.request_async_load(cx, rect.size * dpi);
+        }
         let (width, height) = if let Some(image_texture) = &self.texture {
             self.draw_bg.draw_vars.set_texture(0, image_texture);
             let (width,height) = image_texture.get_format(cx).vec_width_height().unwrap_or((self.min_width as usize, self.min_height as usize));
@@ -99,6 +168,65 @@ impl Image {
         
         DrawStep::done()
     }
+    
+    /// Sets up frame-by-frame playback if `image_path` is an animated PNG or GIF.
+    fn load_animation_dep_by_path(&mut self, cx: &mut Cx, image_path: &str) -> bool {
+        if !image_path.ends_with(".png") && !image_path.ends_with(".apng") && !image_path.ends_with(".gif") {
+            return false
+        }
+        // keep playing when the same source is re-applied
+        if self.animation.is_some() && self.animation_path == image_path {
+            return true
+        }
+        if cx.get_global::<ImageCache>().is_animated(image_path) == Some(false) {
+            return false
+        }
+        let Ok(data) = cx.get_dependency(image_path) else {return false};
+        let animated = self.load_animation_from_data(cx, data);
+        cx.get_global::<ImageCache>().set_animated(image_path, animated);
+        if animated {
+            self.animation_path = image_path.to_string();
+        }
+        animated
+    }
+    
+    fn load_animation_from_data(&mut self, cx: &mut Cx, data: Rc<Vec<u8>>) -> bool {
+        match ImageAnimation::from_data(data) {
+            Ok(Some(mut animation)) => {
+                if !self.autoplay {
+                    animation.pause();
+                }
+                self.texture = Some(animation.new_texture(cx));
+                if animation.is_playing() {
+                    self.next_frame = cx.new_next_frame();
+                }
+                self.animation = Some(animation);
+                true
+            }
+            Ok(None) => false,
+            Err(err) => {
+                error!("Image: Cannot load animation {}", err);
+                false
+            }
+        }
+    }
+    
+    fn request_async_load(&mut self, cx: &mut Cx2d, size: DVec2) {
+        if !matches!(self.async_state, ImageAsyncState::Idle) || self.source.as_str().len() == 0 {
+            return
+        }
+        let max_size = if size.x >= 1.0 && size.y >= 1.0 {
+            Some((size.x.ceil() as usize, size.y.ceil() as usize))
+        }
+        else {
+            None
+        };
+        let source = self.source.clone();
+        self.async_state = match self.load_image_dep_by_path_async(cx, source.as_str(), max_size) {
+            Ok(AsyncImageLoad::Pending(key)) => ImageAsyncState::Pending(key),
+            Ok(AsyncImageLoad::Ready) | Err(_) => ImageAsyncState::Done
+        };
+    }
 }
 
 impl ImageRef {
@@ -129,8 +257,77 @@ impl ImageRef {
         }
     }
     
+    /// Decodes the body of an http `response` in the background and shows it once ready.
+    pub fn load_image_from_http_response(&self, cx: &mut Cx, url: &str, response: &HttpResponse) -> Result<(), ImageError> {
+        if let Some(mut inner) = self.borrow_mut() {
+            let load = inner.load_image_from_http_response_async(cx, url, response, None)?;
+            inner.async_state = match load {
+                AsyncImageLoad::Pending(key) => ImageAsyncState::Pending(key),
+                AsyncImageLoad::Ready => {
+                    inner.redraw(cx);
+                    ImageAsyncState::Done
+                }
+            };
+        }
+        Ok(())
+    }
+    
+    /// Loads a PNG, APNG, GIF or JPEG from encoded `data`, playing it if it is animated.
+    pub fn load_image_from_data(&self, cx: &mut Cx, data: &[u8]) -> Result<(), ImageError> {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.animation_path.clear();
+            if !inner.load_animation_from_data(cx, Rc::new(data.to_vec())) {
+                inner.animation = None;
+                let texture = ImageBuffer::from_data(data)?.into_new_texture(cx);
+                inner.texture = Some(texture);
+            }
+            inner.redraw(cx);
+        }
+        Ok(())
+    }
+    
+    /// Resumes (or starts) playing an animated image.
+    pub fn play_animation(&self, cx: &mut Cx) {
+        if let Some(mut inner) = self.borrow_mut() {
+            if let Some(animation) = &mut inner.animation {
+                if animation.is_finished() {
+                    let _ = animation.restart();
+                }
+                animation.play();
+                inner.next_frame = cx.new_next_frame();
+            }
+        }
+    }
+    
+    pub fn pause_animation(&self) {
+        if let Some(mut inner) = self.borrow_mut() {
+            if let Some(animation) = &mut inner.animation {
+                animation.pause();
+            }
+        }
+    }
+    
+    pub fn is_animation_playing(&self) -> bool {
+        if let Some(inner) = self.borrow() {
+            if let Some(animation) = &inner.animation {
+                return animation.is_playing()
+            }
+        }
+        false
+    }
+    
+    /// Overrides how many times the animation plays; 0 means forever.
+    pub fn set_animation_loop_count(&self, loop_count: u32) {
+        if let Some(mut inner) = self.borrow_mut() {
+            if let Some(animation) = &mut inner.animation {
+                animation.set_loop_count(loop_count);
+            }
+        }
+    }
+    
     pub fn set_texture(&self, texture: Option<Texture>) {
         if let Some(mut inner) = self.borrow_mut() {
+            inner.animation = None;
             inner.texture = texture
         }
     }
@@ -157,6 +354,10 @@ pub enum ImageError {
     PathNotFound(String),
     /// The image data could not be decoded as a PNG.
     PngDecode(PngDecodeErrors),
+    /// The image data could not be decoded as a GIF.
+    GifDecode(GifDecodeErrors),
+    /// Decoding on the image cache worker pool failed; the error is included as text.
+    DecodeFailed(String),
     /// The image data was in an unsupported format.
     /// Currently, only JPEG and PNG are supported.
     UnsupportedFormat,
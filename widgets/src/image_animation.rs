@@ -0,0 +1,371 @@
+use crate::{
+    makepad_draw::*,
+    gif::GifDecoder,
+    image_cache::ImageBuffer,
+    ImageError,
+};
+use makepad_zune_png::{PngDecoder, DisposeOp, BlendOp};
+use std::rc::Rc;
+
+/// What happens to a frame's area before the next frame is drawn.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FrameDispose {
+    None,
+    Background,
+    Previous,
+}
+
+/// How a frame is combined with the canvas below it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FrameBlend {
+    Source,
+    Over,
+}
+
+/// A single decoded frame of an animation, covering a sub-rectangle of the canvas.
+/// Pixels are packed like `ImageBuffer` data (`0xAARRGGBB`).
+pub struct AnimationFrame {
+    pub x: usize,
+    pub y: usize,
+    pub width: usize,
+    pub height: usize,
+    pub data: Vec<u32>,
+    /// Time in seconds to show this frame for.
+    pub delay: f64,
+    pub dispose: FrameDispose,
+    pub blend: FrameBlend,
+}
+
+enum FrameSource {
+    Apng(Box<PngDecoder<Rc<Vec<u8>>>>),
+    Gif(GifDecoder),
+}
+
+/// Plays an APNG or GIF by decoding one frame at a time and compositing it onto a canvas,
+/// which can then be uploaded into a texture.
+pub struct ImageAnimation {
+    // shared with the decoder, an APNG needs it again to rewind
+    data: Rc<Vec<u8>>,
+    source: FrameSource,
+    width: usize,
+    height: usize,
+    canvas: Vec<u32>,
+    previous: Vec<u32>,
+    dispose: Option<(FrameDispose, usize, usize, usize, usize)>,
+    frame_count: usize,
+    frame_index: usize,
+    loop_count: u32,
+    plays: u32,
+    delay: f64,
+    next_frame_time: Option<f64>,
+    playing: bool,
+    finished: bool,
+}
+
+impl ImageAnimation {
+    /// Returns `Ok(None)` if `data` is a valid but not animated (or unsupported) image.
+    pub fn from_data(data: Rc<Vec<u8>>) -> Result<Option<Self>, ImageError> {
+        let (source, width, height, frame_count, loop_count) = if data.starts_with(b"GIF8") {
+            let decoder = GifDecoder::new(data.clone()).map_err(ImageError::GifDecode)?;
+            let frame_count = decoder.frame_count();
+            let (width, height) = (decoder.width, decoder.height);
+            // the loop count is only known after reading up to the first frame
+            (FrameSource::Gif(decoder), width, height, frame_count, 0)
+        }
+        else if data.starts_with(&[0x89, b'P', b'N', b'G']) {
+            let mut decoder = PngDecoder::new(data.clone());
+            decoder.decode_headers().map_err(ImageError::PngDecode)?;
+            let Some(actl) = decoder.get_animation_info() else {return Ok(None)};
+            let (frame_count, loop_count) = (actl.num_frames as usize, actl.num_plays);
+            let (width, height) = decoder.get_dimensions().unwrap();
+            (FrameSource::Apng(Box::new(decoder)), width, height, frame_count, loop_count)
+        }
+        else {
+            return Ok(None)
+        };
+        if frame_count < 2 {
+            return Ok(None)
+        }
+        let mut animation = Self {
+            data,
+            source,
+            width,
+            height,
+            canvas: vec![0; width * height],
+            previous: Vec::new(),
+            dispose: None,
+            frame_count,
+            frame_index: 0,
+            loop_count,
+            plays: 0,
+            delay: 0.0,
+            next_frame_time: None,
+            playing: true,
+            finished: false,
+        };
+        animation.step()?;
+        if let FrameSource::Gif(decoder) = &animation.source {
+            animation.loop_count = decoder.loop_count.map( | v | v as u32).unwrap_or(1);
+        }
+        Ok(Some(animation))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The frames composited so far, packed like `ImageBuffer` data.
+    pub fn canvas(&self) -> &[u32] {
+        &self.canvas
+    }
+
+    pub fn frame_count(&self) -> usize {
+        self.frame_count
+    }
+
+    /// The index of the frame currently on the canvas.
+    pub fn frame_index(&self) -> usize {
+        self.frame_index.saturating_sub(1)
+    }
+
+    /// How many times the animation plays; 0 means forever.
+    pub fn loop_count(&self) -> u32 {
+        self.loop_count
+    }
+
+    pub fn set_loop_count(&mut self, loop_count: u32) {
+        self.loop_count = loop_count;
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.playing && !self.finished
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    pub fn play(&mut self) {
+        self.playing = true;
+        self.next_frame_time = None;
+    }
+
+    pub fn pause(&mut self) {
+        self.playing = false;
+    }
+
+    /// Rewinds to the first frame, resetting the play count.
+    pub fn restart(&mut self) -> Result<(), ImageError> {
+        self.rewind()?;
+        self.plays = 0;
+        self.finished = false;
+        self.next_frame_time = None;
+        self.canvas.iter_mut().for_each( | v | *v = 0);
+        self.dispose = None;
+        self.step()?;
+        Ok(())
+    }
+
+    /// Advances the animation to `time` (as in `NextFrameEvent::time`), returning
+    /// true if the canvas changed and should be uploaded.
+    pub fn handle_time(&mut self, time: f64) -> bool {
+        if !self.is_playing() {
+            return false
+        }
+        let next_frame_time = *self.next_frame_time.get_or_insert(time + self.delay);
+        if time < next_frame_time {
+            return false
+        }
+        // don't try to catch up on frames after a long stall
+        let mut next_frame_time = if time - next_frame_time > 1.0 {time} else {next_frame_time};
+        let mut changed = false;
+        while time >= next_frame_time && !self.finished {
+            match self.step() {
+                Ok(true) => changed = true,
+                Ok(false) => break,
+                Err(err) => {
+                    error!("ImageAnimation: frame decode failed {}", err);
+                    self.finished = true;
+                }
+            }
+            next_frame_time += self.delay.max(0.01);
+        }
+        self.next_frame_time = Some(next_frame_time);
+        changed
+    }
+
+    pub fn new_texture(&self, cx: &mut Cx) -> Texture {
+        Texture::new_with_format(cx, TextureFormat::VecBGRAu8_32 {
+            width: self.width,
+            height: self.height,
+            data: self.canvas.clone()
+        })
+    }
+
+    /// Uploads the current canvas into a texture created by `new_texture`.
+    pub fn update_texture(&self, cx: &mut Cx, texture: &Texture) {
+        let mut data = self.canvas.clone();
+        texture.swap_vec_u32(cx, &mut data);
+    }
+
+    fn rewind(&mut self) -> Result<(), ImageError> {
+        match &mut self.source {
+            FrameSource::Gif(decoder) => decoder.rewind(),
+            FrameSource::Apng(decoder) => {
+                let mut new_decoder = PngDecoder::new(self.data.clone());
+                new_decoder.decode_headers().map_err(ImageError::PngDecode)?;
+                **decoder = new_decoder;
+            }
+        }
+        self.frame_index = 0;
+        Ok(())
+    }
+
+    fn next_frame(&mut self) -> Result<Option<AnimationFrame>, ImageError> {
+        match &mut self.source {
+            FrameSource::Gif(decoder) => {
+                let frame = decoder.next_frame().map_err(ImageError::GifDecode)?;
+                // follow browsers in treating tiny delays as 'as fast as reasonable'
+                Ok(frame.map( | mut frame | {
+                    if frame.delay <= 0.01 {
+                        frame.delay = 0.1;
+                    }
+                    frame
+                }))
+            }
+            FrameSource::Apng(decoder) => {
+                if self.frame_index >= self.frame_count {
+                    return Ok(None)
+                }
+                let mut image = decoder.decode().map_err(ImageError::PngDecode)?;
+                // the default image isn't always part of the animation, nor of its frame count
+                if self.frame_index == 0 && decoder.get_last_frame_info().is_none() {
+                    image = decoder.decode().map_err(ImageError::PngDecode)?;
+                }
+                let info = decoder.get_last_frame_info().ok_or(ImageError::EmptyData)?;
+                let components = decoder.get_colorspace().unwrap().num_components();
+                let data = image.u8().ok_or(ImageError::EmptyData)?;
+                let len = info.width * info.height * components;
+                if data.len() < len || len == 0 {
+                    return Err(ImageError::EmptyData)
+                }
+                let buffer = ImageBuffer::new(&data[..len], info.width, info.height)?;
+                let delay_denom = if info.delay_denom == 0 {100.0} else {info.delay_denom as f64};
+                Ok(Some(AnimationFrame {
+                    x: info.x_offset,
+                    y: info.y_offset,
+                    width: info.width,
+                    height: info.height,
+                    data: buffer.data,
+                    delay: info.delay_num as f64 / delay_denom,
+                    dispose: match info.dispose_op {
+                        DisposeOp::None => FrameDispose::None,
+                        DisposeOp::Background => FrameDispose::Background,
+                        DisposeOp::Previous => FrameDispose::Previous,
+                    },
+                    blend: match info.blend_op {
+                        BlendOp::Source => FrameBlend::Source,
+                        BlendOp::Over => FrameBlend::Over,
+                    }
+                }))
+            }
+        }
+    }
+
+    /// Disposes the previous frame and composites the next one onto the canvas.
+    /// Returns false if the animation came to rest on its last frame.
+    fn step(&mut self) -> Result<bool, ImageError> {
+        let frame = match self.next_frame()? {
+            Some(frame) => frame,
+            None => {
+                self.plays += 1;
+                if self.loop_count != 0 && self.plays >= self.loop_count {
+                    self.finished = true;
+                    return Ok(false)
+                }
+                self.rewind()?;
+                match self.next_frame()? {
+                    Some(frame) => frame,
+                    None => {
+                        self.finished = true;
+                        return Ok(false)
+                    }
+                }
+            }
+        };
+        // the first frame of a loop starts from a clear canvas
+        if self.frame_index == 0 {
+            self.canvas.iter_mut().for_each( | v | *v = 0);
+            self.dispose = None;
+        }
+        if let Some((dispose, x, y, w, h)) = self.dispose.take() {
+            match dispose {
+                FrameDispose::None => (),
+                FrameDispose::Background => self.fill_rect(x, y, w, h, 0),
+                FrameDispose::Previous => if self.previous.len() == self.canvas.len() {
+                    std::mem::swap(&mut self.canvas, &mut self.previous);
+                }
+            }
+        }
+        if frame.dispose == FrameDispose::Previous {
+            self.previous.clone_from(&self.canvas);
+        }
+        self.blit(&frame);
+        self.dispose = Some((frame.dispose, frame.x, frame.y, frame.width, frame.height));
+        self.delay = frame.delay;
+        self.frame_index += 1;
+        Ok(true)
+    }
+
+    fn fill_rect(&mut self, x: usize, y: usize, w: usize, h: usize, value: u32) {
+        for row in y.min(self.height)..(y + h).min(self.height) {
+            let start = row * self.width;
+            self.canvas[start + x.min(self.width)..start + (x + w).min(self.width)].fill(value);
+        }
+    }
+
+    fn blit(&mut self, frame: &AnimationFrame) {
+        for row in 0..frame.height {
+            let cy = frame.y + row;
+            if cy >= self.height {
+                break;
+            }
+            for col in 0..frame.width {
+                let cx = frame.x + col;
+                if cx >= self.width {
+                    break;
+                }
+                let src = frame.data[row * frame.width + col];
+                let dst = &mut self.canvas[cy * self.width + cx];
+                *dst = match frame.blend {
+                    FrameBlend::Source => src,
+                    FrameBlend::Over => blend_over(src, *dst),
+                };
+            }
+        }
+    }
+}
+
+fn blend_over(src: u32, dst: u32) -> u32 {
+    let sa = src >> 24;
+    if sa == 255 {
+        return src
+    }
+    if sa == 0 {
+        return dst
+    }
+    let da = dst >> 24;
+    let sa = sa as f32 / 255.0;
+    let da = da as f32 / 255.0;
+    let oa = sa + da * (1.0 - sa);
+    let channel = | shift: u32 | {
+        let s = ((src >> shift) & 0xff) as f32;
+        let d = ((dst >> shift) & 0xff) as f32;
+        (((s * sa + d * da * (1.0 - sa)) / oa).round() as u32).min(255)
+    };
+    (((oa * 255.0).round() as u32) << 24) | (channel(16) << 16) | (channel(8) << 8) | channel(0)
+}
@@ -1,7 +1,10 @@
 use crate::{makepad_draw::*, ImageError};
 use std::collections::HashMap;
+use std::rc::Rc;
+use std::sync::Arc;
 use makepad_zune_jpeg::JpegDecoder;
 use makepad_zune_png::PngDecoder;
+use crate::gif::GifDecoder;
 
 pub use makepad_zune_png::error::PngDecodeErrors;
 pub use makepad_zune_jpeg::errors::DecodeErrors as JpgDecodeErrors;
@@ -96,6 +99,96 @@ impl ImageBuffer {
         }
     }
 
+    /// Decodes a PNG, JPEG or GIF by sniffing the magic bytes at the start of `data`.
+    pub fn from_data(data: &[u8]) -> Result<Self, ImageError> {
+        if data.starts_with(&[0x89, b'P', b'N', b'G']) {
+            Self::from_png(data)
+        }
+        else if data.starts_with(&[0xff, 0xd8]) {
+            Self::from_jpg(data)
+        }
+        else if data.starts_with(b"GIF8") {
+            Self::from_gif(data)
+        }
+        else {
+            error!("ImageBuffer::from_data: Image format not recognised");
+            Err(ImageError::UnsupportedFormat)
+        }
+    }
+    
+    /// Shrinks the image with a box filter so that it fits in `max_width` x `max_height`,
+    /// keeping the aspect ratio. Images that already fit are returned unchanged.
+    pub fn downscale_to_fit(self, max_width: usize, max_height: usize) -> Self {
+        if max_width == 0 || max_height == 0 || (self.width <= max_width && self.height <= max_height) {
+            return self
+        }
+        let scale = (max_width as f64 / self.width as f64).min(max_height as f64 / self.height as f64);
+        let width = ((self.width as f64 * scale).round() as usize).max(1);
+        let height = ((self.height as f64 * scale).round() as usize).max(1);
+        let mut data = Vec::with_capacity(width * height);
+        for y in 0..height {
+            let sy0 = y * self.height / height;
+            let sy1 = ((y + 1) * self.height / height).max(sy0 + 1);
+            for x in 0..width {
+                let sx0 = x * self.width / width;
+                let sx1 = ((x + 1) * self.width / width).max(sx0 + 1);
+                let mut acc = [0u32; 4];
+                for sy in sy0..sy1 {
+                    for sx in sx0..sx1 {
+                        let p = self.data[sy * self.width + sx];
+                        acc[0] += (p >> 24) & 0xff;
+                        acc[1] += (p >> 16) & 0xff;
+                        acc[2] += (p >> 8) & 0xff;
+                        acc[3] += p & 0xff;
+                    }
+                }
+                let n = ((sy1 - sy0) * (sx1 - sx0)) as u32;
+                data.push(((acc[0] / n) << 24) | ((acc[1] / n) << 16) | ((acc[2] / n) << 8) | (acc[3] / n));
+            }
+        }
+        ImageBuffer {
+            width,
+            height,
+            data
+        }
+    }
+    
+    /// The number of bytes this image occupies once uploaded as a texture.
+    pub fn byte_size(&self) -> usize {
+        self.data.len() * 4
+    }
+    
+    /// Decodes the first frame of a GIF. Use `ImageAnimation` to play all of them.
+    pub fn from_gif(
+        data: &[u8]
+    ) -> Result<Self, ImageError> {
+        let mut decoder = GifDecoder::new(Rc::new(data.to_vec())).map_err(ImageError::GifDecode)?;
+        match decoder.next_frame() {
+            Ok(Some(frame)) => {
+                let (width, height) = (decoder.width, decoder.height);
+                let mut data = vec![0u32; width * height];
+                for row in 0..frame.height.min(height.saturating_sub(frame.y)) {
+                    for col in 0..frame.width.min(width.saturating_sub(frame.x)) {
+                        data[(frame.y + row) * width + frame.x + col] = frame.data[row * frame.width + col];
+                    }
+                }
+                Ok(ImageBuffer {
+                    width,
+                    height,
+                    data
+                })
+            }
+            Ok(None) => {
+                error!("Error decoding GIF: no frames");
+                Err(ImageError::EmptyData)
+            }
+            Err(err) => {
+                error!("Error decoding GIF: {:?}", err);
+                Err(ImageError::GifDecode(err))
+            }
+        }
+    }
+
     pub fn from_jpg(
         data: &[u8]
     ) -> Result<Self, ImageError> {
@@ -114,15 +207,217 @@ impl ImageBuffer {
     }
 }
 
+/// Identifies an entry in the [`ImageCache`]: the source path or url, and the maximum
+/// size in pixels it was downscaled to (if any).
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct ImageCacheKey {
+    pub path: String,
+    pub max_size: Option<(usize, usize)>,
+}
+
+impl ImageCacheKey {
+    pub fn new(path: &str, max_size: Option<(usize, usize)>) -> Self {
+        Self {
+            path: path.to_string(),
+            max_size
+        }
+    }
+}
+
+/// The outcome of an asynchronous image load request.
+#[derive(Clone, Debug, PartialEq)]
+pub enum AsyncImageLoad {
+    /// The texture was available and has been set.
+    Ready,
+    /// The image is decoding on the worker pool; poll again with the key on `Event::Signal`.
+    Pending(ImageCacheKey),
+}
+
+enum ImageCacheEntry {
+    Loaded {
+        texture: Texture,
+        bytes: usize,
+        last_used: u64,
+    },
+    Loading,
+    Failed(String),
+}
+
+enum DecoderToUI {
+    Done(ImageCacheKey, ImageBuffer),
+    Error(ImageCacheKey, String),
+}
+
+/// Global texture cache shared by all image widgets. Decoded textures are kept up to
+/// `byte_budget` bytes, after which the least recently used ones are evicted.
 pub struct ImageCache {
-    map: HashMap<String, Texture>,
+    map: HashMap<ImageCacheKey, ImageCacheEntry>,
+    byte_budget: usize,
+    used_bytes: usize,
+    lru_counter: u64,
+    thread_pool: Option<TagThreadPool<ImageCacheKey >>,
+    to_ui: ToUIReceiver<DecoderToUI>,
+    // the encoded bytes of every async load that hasn't been handed to a widget yet, so
+    // an image evicted before anyone polled it can be decoded again
+    sources: HashMap<ImageCacheKey, Arc<Vec<u8>>>,
+    // whether the dependency at a path turned out to be an animation, so plain images
+    // don't get read and sniffed again every time they are applied
+    animated: HashMap<String, bool>,
 }
 
 impl ImageCache {
+    pub const DEFAULT_BYTE_BUDGET: usize = 128 * 1024 * 1024;
+    
     pub fn new() -> Self {
         Self {
             map: HashMap::new(),
+            byte_budget: Self::DEFAULT_BYTE_BUDGET,
+            used_bytes: 0,
+            lru_counter: 0,
+            thread_pool: None,
+            to_ui: ToUIReceiver::default(),
+            sources: HashMap::new(),
+            animated: HashMap::new(),
+        }
+    }
+    
+    pub fn byte_budget(&self) -> usize {
+        self.byte_budget
+    }
+    
+    pub fn used_bytes(&self) -> usize {
+        self.used_bytes
+    }
+    
+    /// Sets the maximum number of texture bytes the cache holds on to, evicting right away if needed.
+    pub fn set_byte_budget(&mut self, byte_budget: usize) {
+        self.byte_budget = byte_budget;
+        self.evict(None);
+    }
+    
+    /// Drops every cached texture. Textures still in use by widgets stay alive until they let go.
+    pub fn clear(&mut self) {
+        self.map.retain( | _, entry | matches!(entry, ImageCacheEntry::Loading));
+        let map = &self.map;
+        self.sources.retain( | key, _ | map.contains_key(key));
+        self.used_bytes = 0;
+        self.animated.clear();
+    }
+    
+    /// Whether the image at `path` is animated, if that has been found out before.
+    pub fn is_animated(&self, path: &str) -> Option<bool> {
+        self.animated.get(path).copied()
+    }
+    
+    pub fn set_animated(&mut self, path: &str, animated: bool) {
+        self.animated.insert(path.to_string(), animated);
+    }
+    
+    fn get(&mut self, key: &ImageCacheKey) -> Option<Texture> {
+        self.lru_counter += 1;
+        if let Some(ImageCacheEntry::Loaded {texture, last_used, ..}) = self.map.get_mut(key) {
+            *last_used = self.lru_counter;
+            return Some(texture.clone())
         }
+        None
+    }
+    
+    fn insert(&mut self, key: ImageCacheKey, texture: Texture, bytes: usize) {
+        self.lru_counter += 1;
+        if let Some(ImageCacheEntry::Loaded {bytes: old_bytes, ..}) = self.map.get(&key) {
+            self.used_bytes -= old_bytes;
+        }
+        self.used_bytes += bytes;
+        self.map.insert(key.clone(), ImageCacheEntry::Loaded {
+            texture,
+            bytes,
+            last_used: self.lru_counter
+        });
+        self.evict(Some(&key));
+    }
+    
+    fn evict(&mut self, keep: Option<&ImageCacheKey>) {
+        while self.used_bytes > self.byte_budget {
+            let oldest = self.map.iter().filter_map( | (key, entry) | match entry {
+                ImageCacheEntry::Loaded {last_used, ..} if Some(key) != keep => Some((key, *last_used)),
+                _ => None
+            }).min_by_key( | (_, last_used) | *last_used).map( | (key, _) | key.clone());
+            
+            let Some(oldest) = oldest else {break};
+            if let Some(ImageCacheEntry::Loaded {bytes, ..}) = self.map.remove(&oldest) {
+                self.used_bytes -= bytes;
+            }
+        }
+    }
+    
+    // shares the bytes of another load of the same path that is still in flight
+    fn shared_source(&self, path: &str) -> Option<Arc<Vec<u8>>> {
+        self.sources.iter().find( | (key, _) | key.path == path).map( | (_, data) | data.clone())
+    }
+    
+    fn dependency_source(cx: &mut Cx, path: &str) -> Result<Arc<Vec<u8>>, String> {
+        if let Some(data) = cx.get_global::<ImageCache>().shared_source(path) {
+            return Ok(data)
+        }
+        cx.get_dependency(path).map( | data | Arc::new(data.to_vec()))
+    }
+    
+    // whether a load for `key` has to start a decode, failed images are tried again
+    fn needs_decode(&self, key: &ImageCacheKey) -> bool {
+        matches!(self.map.get(key), None | Some(ImageCacheEntry::Failed(_)))
+    }
+    
+    fn decode_async(cx: &mut Cx, key: ImageCacheKey, data: Arc<Vec<u8>>) {
+        if cx.get_global::<ImageCache>().thread_pool.is_none() {
+            let use_cores = cx.cpu_cores().max(3) - 2;
+            let thread_pool = TagThreadPool::new(cx, use_cores);
+            cx.get_global::<ImageCache>().thread_pool = Some(thread_pool);
+        }
+        let cache = cx.get_global::<ImageCache>();
+        cache.map.insert(key.clone(), ImageCacheEntry::Loading);
+        cache.sources.insert(key.clone(), data.clone());
+        let to_ui = cache.to_ui.sender();
+        cache.thread_pool.as_ref().unwrap().execute_rev(key, move | key | {
+            match ImageBuffer::from_data(&data) {
+                Ok(image_buffer) => {
+                    let image_buffer = if let Some((max_width, max_height)) = key.max_size {
+                        image_buffer.downscale_to_fit(max_width, max_height)
+                    }
+                    else {
+                        image_buffer
+                    };
+                    let _ = to_ui.send(DecoderToUI::Done(key, image_buffer));
+                }
+                Err(err) => {
+                    let _ = to_ui.send(DecoderToUI::Error(key, err.to_string()));
+                }
+            }
+        });
+    }
+    
+    /// Uploads images that finished decoding on the worker pool. Returns true if any arrived.
+    pub fn handle_decoded_images(cx: &mut Cx) -> bool {
+        if !cx.has_global::<ImageCache>() {
+            return false
+        }
+        let mut updates = false;
+        while let Ok(msg) = cx.get_global::<ImageCache>().to_ui.try_recv() {
+            match msg {
+                DecoderToUI::Done(key, image_buffer) => {
+                    let bytes = image_buffer.byte_size();
+                    let texture = image_buffer.into_new_texture(cx);
+                    cx.get_global::<ImageCache>().insert(key, texture, bytes);
+                }
+                DecoderToUI::Error(key, err) => {
+                    error!("ImageCache: Cannot decode image {}: {}", key.path, err);
+                    let cache = cx.get_global::<ImageCache>();
+                    cache.sources.remove(&key);
+                    cache.map.insert(key, ImageCacheEntry::Failed(err));
+                }
+            }
+            updates = true;
+        }
+        updates
     }
 }
 
@@ -167,42 +462,36 @@ pub trait ImageCacheImpl {
         cx: &mut Cx,
         image_path: &str,
     ) -> Result<(), ImageError> {
-        if let Some(texture) = cx.get_global::<ImageCache>().map.get(image_path){
-            self.set_texture(Some(texture.clone()));
+        let key = ImageCacheKey::new(image_path, None);
+        if let Some(texture) = cx.get_global::<ImageCache>().get(&key){
+            self.set_texture(Some(texture));
             Ok(())
         }
         else{
             match cx.get_dependency(image_path) {
                 Ok(data) => {
-                    if image_path.ends_with(".jpg") {
-                        match ImageBuffer::from_jpg(&*data){
-                            Ok(data)=>{
-                                let texture = data.into_new_texture(cx);
-                                cx.get_global::<ImageCache>().map.insert(image_path.to_string(), texture.clone());
-                                self.set_texture(Some(texture));
-                                Ok(())
-                            }
-                            Err(err)=>{
-                                error!("load_image_dep_by_path: Cannot load jpeg image from path: {} {}", image_path, err);
-                                Err(err)
-                            }
-                        }
+                    let image_buffer = if image_path.ends_with(".jpg") {
+                        ImageBuffer::from_jpg(&*data)
                     } else if image_path.ends_with(".png") {
-                        match ImageBuffer::from_png(&*data){
-                            Ok(data)=>{
-                                let texture = data.into_new_texture(cx);
-                                cx.get_global::<ImageCache>().map.insert(image_path.to_string(), texture.clone());
-                                self.set_texture(Some(texture));
-                                Ok(())
-                            }
-                            Err(err)=>{
-                                error!("load_image_dep_by_path: Cannot load png image from path: {} {}", image_path, err);
-                                Err(err)
-                            }
-                        }
+                        ImageBuffer::from_png(&*data)
+                    } else if image_path.ends_with(".gif") {
+                        ImageBuffer::from_gif(&*data)
                     } else {
                         error!("load_image_dep_by_path: Image format not supported {}", image_path);
-                        Err(ImageError::UnsupportedFormat)
+                        return Err(ImageError::UnsupportedFormat)
+                    };
+                    match image_buffer {
+                        Ok(data)=>{
+                            let bytes = data.byte_size();
+                            let texture = data.into_new_texture(cx);
+                            cx.get_global::<ImageCache>().insert(key, texture.clone(), bytes);
+                            self.set_texture(Some(texture));
+                            Ok(())
+                        }
+                        Err(err)=>{
+                            error!("load_image_dep_by_path: Cannot load image from path: {} {}", image_path, err);
+                            Err(err)
+                        }
                     }
                 }
                 Err(err) => {
@@ -212,4 +501,80 @@ pub trait ImageCacheImpl {
             }
         }
     }
+    
+    /// Like `load_image_dep_by_path`, but decodes on the image cache worker pool and
+    /// optionally downscales to `max_size` pixels. Until the image is ready the texture
+    /// is left alone, so callers can keep showing a placeholder.
+    fn load_image_dep_by_path_async(
+        &mut self,
+        cx: &mut Cx,
+        image_path: &str,
+        max_size: Option<(usize, usize)>,
+    ) -> Result<AsyncImageLoad, ImageError> {
+        self.lazy_create_image_cache(cx);
+        let key = ImageCacheKey::new(image_path, max_size);
+        if !cx.get_global::<ImageCache>().needs_decode(&key) {
+            return self.poll_async_image_load(cx, &key)
+        }
+        match ImageCache::dependency_source(cx, image_path) {
+            Ok(data) => {
+                ImageCache::decode_async(cx, key.clone(), data);
+                Ok(AsyncImageLoad::Pending(key))
+            }
+            Err(err) => {
+                error!("load_image_dep_by_path_async: Resource not found {} {}", image_path, err);
+                Err(ImageError::PathNotFound(image_path.to_string()))
+            }
+        }
+    }
+    
+    /// Decodes the body of an http `response` on the image cache worker pool, caching
+    /// the texture under `url`. The image format is detected from the data itself.
+    fn load_image_from_http_response_async(
+        &mut self,
+        cx: &mut Cx,
+        url: &str,
+        response: &HttpResponse,
+        max_size: Option<(usize, usize)>,
+    ) -> Result<AsyncImageLoad, ImageError> {
+        self.lazy_create_image_cache(cx);
+        let key = ImageCacheKey::new(url, max_size);
+        if !cx.get_global::<ImageCache>().needs_decode(&key) {
+            return self.poll_async_image_load(cx, &key)
+        }
+        match response.get_body() {
+            Some(body) if response.status_code == 200 && !body.is_empty() => {
+                ImageCache::decode_async(cx, key.clone(), Arc::new(body.clone()));
+                Ok(AsyncImageLoad::Pending(key))
+            }
+            _ => {
+                error!("load_image_from_http_response_async: No image in response for {} (status {})", url, response.status_code);
+                Err(ImageError::EmptyData)
+            }
+        }
+    }
+    
+    /// Checks on an image requested with one of the async loaders. Call this on
+    /// `Event::Signal`; it sets the texture once decoding has finished.
+    fn poll_async_image_load(&mut self, cx: &mut Cx, key: &ImageCacheKey) -> Result<AsyncImageLoad, ImageError> {
+        ImageCache::handle_decoded_images(cx);
+        let cache = cx.get_global::<ImageCache>();
+        if let Some(texture) = cache.get(key) {
+            cache.sources.remove(key);
+            self.set_texture(Some(texture));
+            return Ok(AsyncImageLoad::Ready)
+        }
+        match cache.map.get(key) {
+            Some(ImageCacheEntry::Failed(err)) => Err(ImageError::DecodeFailed(err.clone())),
+            Some(_) => Ok(AsyncImageLoad::Pending(key.clone())),
+            // evicted before anyone picked it up, decode it again
+            None => match cache.sources.get(key).cloned() {
+                Some(data) => {
+                    ImageCache::decode_async(cx, key.clone(), data);
+                    Ok(AsyncImageLoad::Pending(key.clone()))
+                }
+                None => Err(ImageError::DecodeFailed(format!("{} is not being loaded", key.path)))
+            }
+        }
+    }
 }
@@ -2,7 +2,8 @@ use {
     crate::{
         makepad_derive_widget::*,
         makepad_draw::*,
-        widget::*
+        widget::*,
+        accessibility::*,
     }
 };
 
@@ -21,6 +22,9 @@ pub struct Label {
 } 
 
 impl Widget for Label {
+    fn widget_area(&self) -> Area {
+        self.draw_text.area()
+    }
 
     fn draw_walk(&mut self, cx: &mut Cx2d, _scope: &mut Scope, walk:Walk)->DrawStep{
         self.draw_text.draw_walk(cx, walk.with_add_padding(self.padding), self.align, self.text.as_ref());
@@ -34,4 +38,10 @@ impl Widget for Label {
     fn set_text(&mut self, v:&str){
         self.text.as_mut_empty().push_str(v);
     }
+    
+    fn access_node(&self, cx: &Cx) -> Option<AccessNode> {
+        Some(AccessNode::new(AccessRole::Label)
+            .with_label(self.text.as_ref())
+            .with_rect(self.draw_text.area().rect(cx)))
+    }
 }
@@ -8,6 +8,8 @@ pub use makepad_derive_widget::*;
 pub mod button;
 pub mod label;
 pub mod image;
+pub mod svg;
+pub mod view_3d;
 pub mod link_label;
 pub mod drop_down;
 pub mod popup_menu;
@@ -28,8 +30,12 @@ pub mod tab;
 pub mod tab_bar;
 pub mod tab_close_button;
 pub mod portal_list;
+pub mod data_grid;
 pub mod stack_navigation;
 pub mod expandable_panel;
+pub mod modal;
+pub mod tooltip;
+pub mod toast;
 pub mod desktop_button;
 pub mod window;
 pub mod scroll_shadow;
@@ -55,6 +61,9 @@ pub mod nav_control;
 
 pub mod view;
 pub mod widget;
+pub mod accessibility;
+#[cfg(target_os = "linux")]
+mod atspi;
 pub mod widget_match_event;
 
 pub mod touch_gesture;
@@ -65,12 +74,17 @@ pub mod data_binding;
 mod base;
 mod theme_desktop_dark;
 pub mod image_cache;
+pub mod image_animation;
+pub mod gif;
+pub mod svg_document;
 
 pub use crate::{
     data_binding::{DataBindingStore, DataBindingMap},
     button::*,
     view::*,
     image::*,
+    svg::*,
+    view_3d::*,
     label::*,
     slider::*,
     check_box::*,
@@ -80,6 +94,7 @@ pub use crate::{
     text_input::*,
     link_label::*,
     portal_list::*,
+    data_grid::*,
     flat_list::*,
     page_flip::*,
     slide_panel::*,
@@ -87,6 +102,9 @@ pub use crate::{
     dock::*,
     stack_navigation::*,
     expandable_panel::*,
+    modal::*,
+    tooltip::*,
+    toast::*,
     window::*,
     tab::TabClosable,
     scroll_bars::{ScrollBars},
@@ -94,6 +112,7 @@ pub use crate::{
     scroll_bar::{ScrollBar},
     slides_view::{SlidesView},
     widget_match_event::WidgetMatchEvent,
+    accessibility::*,
     widget::{
         WidgetSet,
         WidgetSetIterator,
@@ -132,6 +151,8 @@ pub fn live_design(cx: &mut Cx) {
     crate::nav_control::live_design(cx);
     crate::image::live_design(cx);
     crate::rotated_image::live_design(cx);
+    crate::svg::live_design(cx);
+    crate::view_3d::live_design(cx);
     crate::video::live_design(cx);
     crate::view::live_design(cx);
     crate::fold_button::live_design(cx);
@@ -151,6 +172,7 @@ pub fn live_design(cx: &mut Cx) {
     crate::multi_window::live_design(cx);
     crate::designer::live_design(cx);
     crate::portal_list::live_design(cx);
+    crate::data_grid::live_design(cx);
     crate::flat_list::live_design(cx);
     crate::slide_panel::live_design(cx);
     crate::tab::live_design(cx);
@@ -164,6 +186,9 @@ pub fn live_design(cx: &mut Cx) {
     crate::vectorline::live_design(cx);
     crate::stack_navigation::live_design(cx);
     crate::expandable_panel::live_design(cx);
+    crate::modal::live_design(cx);
+    crate::tooltip::live_design(cx);
+    crate::toast::live_design(cx);
     crate::html::live_design(cx);
     crate::text_flow::live_design(cx);
 }
@@ -3,6 +3,7 @@ use {
         makepad_derive_widget::*,
         widget::*,
         makepad_draw::*,
+        accessibility::*,
         button::{Button, ButtonAction}
     }
 };
@@ -17,6 +18,10 @@ pub struct LinkLabel {
 }
 
 impl Widget for LinkLabel {
+    fn widget_area(&self) -> Area {
+        self.button.widget_area()
+    }
+    
     fn handle_event(
         &mut self,
         cx: &mut Cx,
@@ -37,6 +42,14 @@ impl Widget for LinkLabel {
     fn set_text(&mut self, v:&str){
         self.button.set_text(v);
     }
+    
+    fn access_node(&self, cx: &Cx) -> Option<AccessNode> {
+        self.button.access_node(cx).map( | node | AccessNode {role: AccessRole::Link, ..node})
+    }
+    
+    fn handle_access(&mut self, cx: &mut Cx, request: &AccessRequest, scope: &mut Scope) {
+        self.button.handle_access(cx, request, scope)
+    }
 }
 
 impl LinkLabelRef {
@@ -0,0 +1,156 @@
+use crate::{
+    makepad_derive_widget::*,
+    makepad_draw::*,
+    widget::*,
+    view::View,
+};
+
+live_design!{
+    ModalBase = {{Modal}} {}
+}
+
+#[derive(Clone, Debug, DefaultNone)]
+pub enum ModalAction {
+    None,
+    Opened,
+    /// Closed from code with the result the dialog ended with
+    Closed(LiveId),
+    /// Closed by the user with escape or a click on the backdrop
+    Dismissed,
+}
+
+#[derive(Clone)]
+enum DrawState {
+    Drawing,
+}
+
+/// A dialog that draws its content centered over a backdrop in the modal layer of the
+/// overlay stack. While it is the top modal it keeps the finger input and tab order to itself
+#[derive(Live, LiveHook, Widget)]
+pub struct Modal {
+    #[deref] view: View,
+    #[live] draw_list: DrawList2d,
+    #[live] draw_backdrop: DrawColor,
+    #[live(true)] dismiss_on_backdrop: bool,
+    #[live(true)] dismiss_on_escape: bool,
+    // where the modal sits in its parent, redrawing it brings the overlay back
+    #[redraw] #[rust] area: Area,
+    #[rust] draw_state: DrawStateWrap<DrawState>,
+    #[rust] is_open: bool,
+}
+
+impl Modal {
+    pub fn open(&mut self, cx: &mut Cx) {
+        if self.is_open {
+            return
+        }
+        self.is_open = true;
+        // tab starts over inside the dialog
+        cx.set_key_focus(Area::Empty);
+        cx.widget_action(self.widget_uid(), &HeapLiveIdPath::default(), ModalAction::Opened);
+        self.area.redraw(cx);
+    }
+
+    fn close_with(&mut self, cx: &mut Cx, action: ModalAction) {
+        if !self.is_open {
+            return
+        }
+        self.is_open = false;
+        cx.set_key_focus(Area::Empty);
+        cx.widget_action(self.widget_uid(), &HeapLiveIdPath::default(), action);
+        self.area.redraw(cx);
+        self.draw_list.redraw(cx);
+    }
+
+    pub fn close(&mut self, cx: &mut Cx, result: LiveId) {
+        self.close_with(cx, ModalAction::Closed(result));
+    }
+}
+
+impl Widget for Modal {
+    fn widget_area(&self) -> Area {
+        self.draw_backdrop.area()
+    }
+
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        if !self.is_open {
+            return
+        }
+        self.view.handle_event(cx, event, scope);
+
+        // a modal opened on top of this one gets the keys and the clicks
+        if !cx.global::<CxOverlayStack>().is_top(self.draw_list.draw_list_id()) {
+            return
+        }
+        if let Event::KeyDown(ke) = event {
+            if ke.key_code == KeyCode::Escape && self.dismiss_on_escape {
+                self.close_with(cx, ModalAction::Dismissed);
+                return
+            }
+        }
+        if let Hit::FingerUp(fe) = event.hits(cx, self.draw_backdrop.area()) {
+            if fe.is_over && self.dismiss_on_backdrop && !self.view.widget_area().clipped_rect(cx).contains(fe.abs) {
+                self.close_with(cx, ModalAction::Dismissed);
+            }
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        if self.draw_state.begin(cx, DrawState::Drawing) {
+            // takes no room in the parent, the dialog itself lives in the overlay
+            cx.walk_turtle_with_area(&mut self.area, Walk::empty().with_abs_pos(DVec2::default()));
+            if !self.is_open {
+                self.draw_state.end();
+                return DrawStep::done()
+            }
+            self.draw_list.begin_overlay_layer(cx, OverlayLayer::Modal);
+            cx.begin_pass_sized_turtle(Layout::flow_down());
+            self.draw_backdrop.begin(cx, Walk::fill(), Layout {
+                align: Align {x: 0.5, y: 0.5},
+                ..Layout::default()
+            });
+        }
+        if let Some(DrawState::Drawing) = self.draw_state.get() {
+            self.view.draw_walk(cx, scope, walk)?;
+            self.draw_state.end();
+            self.draw_backdrop.end(cx);
+            cx.end_pass_sized_turtle();
+            self.draw_list.end(cx);
+        }
+        DrawStep::done()
+    }
+}
+
+impl ModalRef {
+    pub fn open(&self, cx: &mut Cx) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.open(cx);
+        }
+    }
+
+    pub fn close(&self, cx: &mut Cx, result: LiveId) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.close(cx, result);
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        if let Some(inner) = self.borrow() {
+            inner.is_open
+        }
+        else {
+            false
+        }
+    }
+
+    pub fn closed(&self, actions: &Actions) -> Option<LiveId> {
+        if let ModalAction::Closed(result) = actions.find_widget_action_cast(self.widget_uid()) {
+            return Some(result)
+        }
+        None
+    }
+
+    pub fn dismissed(&self, actions: &Actions) -> bool {
+        matches!(actions.find_widget_action_cast(self.widget_uid()), ModalAction::Dismissed)
+    }
+}
@@ -3,7 +3,7 @@ use crate::makepad_draw::*;
 
 live_design!{
     import makepad_draw::shader::std::*;
-    
+
     NavControlBase = {{NavControl}} {}
 }
 
@@ -12,11 +12,13 @@ pub struct NavControl {
     #[live] draw_list: DrawList2d,
     #[live] draw_focus: DrawQuad,
     #[live] draw_text: DrawText,
-    #[rust] _recent_focus: Area,
+    #[live] focus_margin: Margin,
+    // the focus ring only shows once the keyboard moved the focus, not after a click
+    #[rust] show_focus: bool,
 }
 
 impl NavControl {
-    
+
     pub fn send_trigger_to_scroll_stack(cx: &mut Cx, stack:Vec<Area>){
         let mut prev_area = None;
         for next_area in stack{
@@ -29,55 +31,122 @@ impl NavControl {
             prev_area = Some(next_area);
         }
     }
-    
+
+    // runs of radio buttons form a group that tab treats as one stop and arrows move through
+    fn nav_groups(stops: &[(NavStop, Vec<Area>)]) -> Vec<usize> {
+        let mut groups: Vec<usize> = Vec::with_capacity(stops.len());
+        for (index, (stop, _)) in stops.iter().enumerate() {
+            if index > 0 && stop.role == NavRole::RadioButton && stops[index - 1].0.role == NavRole::RadioButton {
+                groups.push(groups[index - 1]);
+            }
+            else {
+                groups.push(index);
+            }
+        }
+        groups
+    }
+
+    fn focus_stop(&mut self, cx: &mut Cx, stop: (NavStop, Vec<Area>)) {
+        let (stop, scroll_stack) = stop;
+        Self::send_trigger_to_scroll_stack(cx, scroll_stack);
+        cx.set_key_focus(stop.area);
+        self.show_focus = true;
+        self.draw_list.redraw(cx);
+    }
+
+    fn focus_tab(&mut self, cx: &mut Cx, root: DrawListId, backward: bool) {
+        let mut stops = Cx2d::collect_nav_stops(cx, root);
+        if stops.is_empty() {
+            return
+        }
+        let current = stops.iter().position( | (stop, _) | cx.has_key_focus(stop.area));
+        // something that isn't a stop has the focus, like a code editor that wants tab itself
+        if current.is_none() && !cx.has_key_focus(Area::Empty) {
+            return
+        }
+        let groups = Self::nav_groups(&stops);
+        let len = stops.len();
+        let next = match current {
+            None => if backward {len - 1} else {0},
+            Some(current) => {
+                let mut next = current;
+                loop {
+                    next = if backward {(next + len - 1) % len} else {(next + 1) % len};
+                    if next == current || groups[next] != groups[current] {
+                        break
+                    }
+                }
+                next
+            }
+        };
+        // entering a group always lands on its first stop
+        let next = groups.iter().position( | group | *group == groups[next]).unwrap();
+        self.focus_stop(cx, stops.swap_remove(next));
+    }
+
+    fn focus_in_group(&mut self, cx: &mut Cx, root: DrawListId, backward: bool) {
+        let mut stops = Cx2d::collect_nav_stops(cx, root);
+        let Some(current) = stops.iter().position( | (stop, _) | cx.has_key_focus(stop.area)) else {
+            return
+        };
+        let groups = Self::nav_groups(&stops);
+        let group: Vec<usize> = (0..stops.len()).filter( | index | groups[*index] == groups[current]).collect();
+        if group.len() < 2 {
+            return
+        }
+        let pos = group.iter().position( | index | *index == current).unwrap();
+        let next = if backward {group[(pos + group.len() - 1) % group.len()]} else {group[(pos + 1) % group.len()]};
+        // moving through a group selects what it lands on, like radio buttons do everywhere
+        cx.send_trigger(stops[next].0.area, Trigger {
+            id: live_id!(nav_select),
+            from: stops[current].0.area
+        });
+        self.focus_stop(cx, stops.swap_remove(next));
+    }
+
     pub fn handle_event(&mut self, cx: &mut Cx, event: &Event, root: DrawListId) {
         match event {
+            Event::KeyFocus(_) | Event::KeyFocusLost(_) => {
+                self.draw_list.redraw(cx);
+            }
+            Event::MouseDown(_) | Event::TouchUpdate(_) => if self.show_focus {
+                self.show_focus = false;
+                self.draw_list.redraw(cx);
+            }
             Event::KeyDown(ke) => match ke.key_code {
-                KeyCode::Tab => {
-                    if ke.modifiers.shift {
-                        let mut prev_area = Area::Empty;
-                        if let Some((prev_area, scroll_stack)) = Cx2d::iterate_nav_stops(cx, root, | cx, stop | {
-                            if cx.has_key_focus(stop.area) {
-                                return Some(prev_area);
-                            }
-                            prev_area = stop.area;
-                            None
-                        }) {
-                            if !prev_area.is_empty() {
-                                Self::send_trigger_to_scroll_stack(cx, scroll_stack);
-                                cx.set_key_focus(prev_area);
-                            }
-                        }
-                    }
-                    else {
-                        let mut next_stop = false;
-                        if let Some((next_area, scroll_stack)) = Cx2d::iterate_nav_stops(cx, root, | cx, stop | {
-                            if next_stop {
-                                return Some(stop.area)
-                            }
-                            if cx.has_key_focus(stop.area) {
-                                next_stop = true;
-                            }
-                            None
-                        }) {
-                            Self::send_trigger_to_scroll_stack(cx, scroll_stack);
-                            cx.set_key_focus(next_area);
-                        }
-                    }
+                KeyCode::Tab if !ke.modifiers.control && !ke.modifiers.logo && !ke.modifiers.alt => {
+                    self.focus_tab(cx, root, ke.modifiers.shift);
+                }
+                KeyCode::ArrowUp | KeyCode::ArrowLeft => {
+                    self.focus_in_group(cx, root, true);
+                }
+                KeyCode::ArrowDown | KeyCode::ArrowRight => {
+                    self.focus_in_group(cx, root, false);
                 }
                 _ => ()
             },
             _ => ()
         }
     }
-    
-    pub fn draw(&mut self, cx: &mut Cx2d) {
-        if !self.draw_list.begin(cx, Walk::default()).is_redrawing() {
-            return
+
+    pub fn draw(&mut self, cx: &mut Cx2d, root: DrawListId) {
+        self.draw_list.begin_overlay_last(cx);
+        if self.show_focus {
+            let stops = Cx2d::collect_nav_stops(cx, root);
+            if let Some((stop, _)) = stops.iter().find( | (stop, _) | cx.has_key_focus(stop.area)) {
+                let rect = stop.area.clipped_rect(cx);
+                let margin = Margin {
+                    left: stop.margin.left + self.focus_margin.left,
+                    top: stop.margin.top + self.focus_margin.top,
+                    right: stop.margin.right + self.focus_margin.right,
+                    bottom: stop.margin.bottom + self.focus_margin.bottom,
+                };
+                self.draw_focus.draw_abs(cx, Rect {
+                    pos: rect.pos - margin.left_top(),
+                    size: rect.size + margin.size()
+                });
+            }
         }
-        
         self.draw_list.end(cx);
     }
 }
-
-
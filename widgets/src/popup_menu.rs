@@ -139,7 +139,7 @@ impl PopupMenu {
     }
     
     pub fn begin(&mut self, cx: &mut Cx2d) {
-        self.draw_list.begin_overlay_reuse(cx);
+        self.draw_list.begin_overlay_layer(cx, OverlayLayer::Popup);
         
         cx.begin_pass_sized_turtle(Layout::flow_down());
         
@@ -508,6 +508,9 @@ impl PortalList {
 
 
 impl Widget for PortalList {
+    fn widget_area(&self) -> Area {
+        self.area
+    }
 
     fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
         let uid = self.widget_uid();
@@ -2,6 +2,7 @@ use crate::{
         makepad_derive_widget::*,
         makepad_draw::*,
         widget::*, View,
+        accessibility::*,
     };
 
 live_design!{
@@ -55,6 +56,7 @@ pub struct RadioButton {
     #[live] label: String,
     
     #[live] bind: String,
+    #[live] nav_order: NavOrder,
 }
 
 #[derive(Clone, Debug, DefaultNone)]
@@ -74,11 +76,22 @@ impl RadioButton {
         self.draw_icon.draw_walk(cx, self.icon_walk);
         self.draw_text.draw_walk(cx, self.label_walk, self.label_align, &self.label);
         self.draw_radio.end(cx);
+        cx.add_nav_stop_ordered(self.draw_radio.area(), NavRole::RadioButton, Margin::default(), self.nav_order);
+    }
+    
+    fn select(&mut self, cx: &mut Cx, scope: &mut Scope) {
+        if self.animator_in_state(cx, id!(selected.off)) {
+            self.animator_play(cx, id!(selected.on));
+            cx.widget_action(self.widget_uid(), &scope.path, RadioButtonAction::Clicked);
+        }
     }
 
 }
 
 impl Widget for RadioButtonGroup {
+    fn widget_area(&self) -> Area {
+        self.frame.widget_area()
+    }
     
     fn handle_event(&mut self, cx: &mut Cx, event: &Event, _scope: &mut Scope) {
         //let uid = self.widget_uid();
@@ -93,12 +106,44 @@ impl Widget for RadioButtonGroup {
 }
 
 impl Widget for RadioButton {
+    fn widget_area(&self) -> Area {
+        self.draw_radio.area()
+    }
+    
+    fn access_node(&self, cx: &Cx) -> Option<AccessNode> {
+        Some(AccessNode::new(AccessRole::RadioButton)
+            .with_label(&self.label)
+            .with_rect(self.draw_radio.area().rect(cx))
+            .with_checked(self.animator_in_state(cx, id!(selected.on)))
+            .with_action(AccessAction::Click))
+    }
+    
+    fn handle_access(&mut self, cx: &mut Cx, request: &AccessRequest, scope: &mut Scope) {
+        if let AccessRequest::Action(AccessAction::Click) = request {
+            self.select(cx, scope);
+        }
+    }
     
     fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
         let uid = self.widget_uid();
         self.animator_handle_event(cx, event);
+        
+        // the nav control moved the focus here with the arrow keys
+        if let Event::Trigger(te) = event {
+            if let Some(triggers) = te.triggers.get(&self.draw_radio.area()) {
+                if triggers.iter().any( | t | t.id == live_id!(nav_select)) {
+                    self.select(cx, scope);
+                }
+            }
+        }
                 
         match event.hits(cx, self.draw_radio.area()) {
+            Hit::KeyFocus(_) => {
+                self.animator_play(cx, id!(focus.on));
+            }
+            Hit::KeyFocusLost(_) => {
+                self.animator_play(cx, id!(focus.off));
+            }
             Hit::FingerHoverIn(_) => {
                 cx.set_cursor(MouseCursor::Hand);
                 self.animator_play(cx, id!(hover.on));
@@ -108,6 +153,7 @@ impl Widget for RadioButton {
                 self.animator_play(cx, id!(hover.off));
             },
             Hit::FingerDown(_fe) => {
+                cx.set_key_focus(self.draw_radio.area());
                 if self.animator_in_state(cx, id!(selected.off)) {
                     self.animator_play(cx, id!(selected.on));
                     cx.widget_action(uid, &scope.path, RadioButtonAction::Clicked);
@@ -118,6 +164,9 @@ impl Widget for RadioButton {
             }
             Hit::FingerMove(_fe) => {
                                 
+            }
+            Hit::KeyDown(ke) if matches!(ke.key_code, KeyCode::ReturnKey | KeyCode::NumpadEnter | KeyCode::Space) => {
+                self.select(cx, scope);
             }
             _ => ()
         }
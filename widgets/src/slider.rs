@@ -3,6 +3,7 @@ use {
         makepad_derive_widget::*,
         makepad_draw::*,
         widget::*,
+        accessibility::*,
         text_input::{TextInput, TextInputAction}
     }
 };
@@ -100,6 +101,11 @@ impl Slider {
         self.text_input.select_all();
     }
     
+    // without a step, screen readers and the arrow keys move the slider a hundredth of its range
+    fn key_step(&self) -> f64 {
+        if self.step != 0.0 {self.step} else {(self.max - self.min) / 100.0}
+    }
+    
     pub fn update_text_input_and_redraw(&mut self, cx: &mut Cx) {
         self.update_text_input();
         self.text_input.redraw(cx);
@@ -122,11 +128,30 @@ impl Slider {
 
 
 impl Widget for Slider {
+    fn widget_area(&self) -> Area {
+        self.draw_slider.area()
+    }
 
     fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope:&mut Scope) {
         let uid = self.widget_uid();
         self.animator_handle_event(cx, event);
         
+        // up and down step the value while the text field has the keyboard
+        if let Event::KeyDown(ke) = event {
+            if cx.has_key_focus(self.text_input.widget_area()) {
+                let step = match ke.key_code {
+                    KeyCode::ArrowUp => self.key_step(),
+                    KeyCode::ArrowDown => -self.key_step(),
+                    _ => 0.0
+                };
+                if step != 0.0 && self.set_internal((self.to_external() + step).max(self.min).min(self.max)) {
+                    self.update_text_input_and_redraw(cx);
+                    self.redraw(cx);
+                    cx.widget_action(uid, &scope.path, SliderAction::Slide(self.to_external()));
+                }
+            }
+        }
+        
         for action in cx.capture_actions(|cx| self.text_input.handle_event(cx, event, scope)) {
             match action.as_widget_action().cast() {
                 TextInputAction::KeyFocus => {
@@ -231,7 +256,35 @@ impl Widget for Slider {
             self.update_text_input()
         }
     }
-        
+    
+    fn access_node(&self, cx: &Cx) -> Option<AccessNode> {
+        Some(AccessNode::new(AccessRole::Slider)
+            .with_label(&self.text)
+            .with_value(AccessValue::Number {
+                value: self.to_external(),
+                min: self.min,
+                max: self.max,
+                step: self.step
+            })
+            .with_rect(self.draw_slider.area().rect(cx))
+            .with_action(AccessAction::Increment)
+            .with_action(AccessAction::Decrement))
+    }
+    
+    fn handle_access(&mut self, cx: &mut Cx, request: &AccessRequest, scope: &mut Scope) {
+        let step = self.key_step();
+        let value = match request {
+            AccessRequest::SetValue(v) => *v,
+            AccessRequest::Action(AccessAction::Increment) => self.to_external() + step,
+            AccessRequest::Action(AccessAction::Decrement) => self.to_external() - step,
+            _ => return
+        };
+        if self.set_internal(value.max(self.min).min(self.max)) {
+            self.update_text_input_and_redraw(cx);
+            self.redraw(cx);
+            cx.widget_action(self.widget_uid(), &scope.path, SliderAction::Slide(self.to_external()));
+        }
+    }
 }
 
 impl SliderRef{
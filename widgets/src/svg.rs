@@ -0,0 +1,200 @@
+use crate::{
+    makepad_derive_widget::*,
+    makepad_draw::*,
+    svg_document::SvgDocument,
+    widget::*
+};
+use std::{collections::HashMap, rc::Rc};
+
+live_design!{
+    SvgBase = {{Svg}} {}
+}
+
+/// Rasterized textures kept around, after which the least recently drawn ones are dropped.
+const MAX_CACHED_TEXTURES: usize = 256;
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+struct SvgTextureKey {
+    source: String,
+    width: usize,
+    height: usize,
+    color: [u32; 4],
+}
+
+/// Parsed documents by source path, and their rasterizations per pixel size.
+#[derive(Default)]
+struct SvgCache {
+    documents: HashMap<String, Rc<SvgDocument>>,
+    textures: HashMap<SvgTextureKey, SvgTexture>,
+    lru_counter: u64,
+}
+
+struct SvgTexture {
+    texture: Texture,
+    last_used: u64,
+}
+
+impl SvgCache {
+    fn get(&mut self, key: &SvgTextureKey) -> Option<Texture> {
+        self.lru_counter += 1;
+        let entry = self.textures.get_mut(key)?;
+        entry.last_used = self.lru_counter;
+        Some(entry.texture.clone())
+    }
+    
+    fn insert(&mut self, key: SvgTextureKey, texture: Texture) {
+        if self.textures.len() >= MAX_CACHED_TEXTURES {
+            let oldest = self.textures.iter().min_by_key( | (_, entry) | entry.last_used).map( | (key, _) | key.clone());
+            if let Some(oldest) = oldest {
+                self.textures.remove(&oldest);
+            }
+        }
+        self.lru_counter += 1;
+        self.textures.insert(key, SvgTexture {texture, last_used: self.lru_counter});
+    }
+}
+
+/// Draws an SVG document, rasterized on the CPU at the exact pixel size it is drawn at,
+/// so it stays sharp at any size and dpi factor.
+#[derive(Live, Widget)]
+pub struct Svg {
+    #[walk] walk: Walk,
+    #[redraw] #[live] draw_svg: DrawQuad,
+    #[live] source: LiveDependency,
+    /// The color used for `currentColor` fills and strokes, for tinting icons.
+    #[live(vec4(0.0, 0.0, 0.0, 1.0))] color: Vec4,
+    #[rust] document: Option<Rc<SvgDocument>>,
+    #[rust] document_key: String,
+    #[rust] texture: Option<Texture>,
+}
+
+impl LiveHook for Svg {
+    fn after_apply(&mut self, cx: &mut Cx, _apply: &mut Apply, _index: usize, _nodes: &[LiveNode]) {
+        let source = self.source.clone();
+        if !source.as_str().is_empty() && self.document_key != source.as_str() {
+            if let Err(err) = self.load_svg_dep_by_path(cx, source.as_str()) {
+                error!("Svg: cannot load {}: {}", source.as_str(), err);
+            }
+        }
+    }
+}
+
+impl Widget for Svg {
+    fn draw_walk(&mut self, cx: &mut Cx2d, _scope: &mut Scope, walk: Walk) -> DrawStep {
+        self.draw_walk(cx, walk)
+    }
+}
+
+impl Svg {
+    pub fn draw_walk(&mut self, cx: &mut Cx2d, mut walk: Walk) -> DrawStep {
+        let Some(document) = self.document.clone() else {
+            self.draw_svg.draw_vars.empty_texture(0);
+            self.draw_svg.draw_walk(cx, walk);
+            return DrawStep::done()
+        };
+        // Fit sizes follow the document, keeping its aspect ratio
+        let size = document.size();
+        match (walk.width.is_fit(), walk.height.is_fit()) {
+            (true, true) => {
+                walk.width = Size::Fixed(size.x);
+                walk.height = Size::Fixed(size.y);
+            }
+            (true, false) => {
+                let rect = cx.peek_walk_turtle(walk);
+                walk.width = Size::Fixed(rect.size.y * size.x / size.y);
+            }
+            (false, true) => {
+                let rect = cx.peek_walk_turtle(walk);
+                walk.height = Size::Fixed(rect.size.x * size.y / size.x);
+            }
+            (false, false) => ()
+        }
+        let rect = cx.peek_walk_turtle(walk);
+        let dpi = cx.current_dpi_factor();
+        let width = (rect.size.x * dpi).round().max(1.0) as usize;
+        let height = (rect.size.y * dpi).round().max(1.0) as usize;
+        let key = SvgTextureKey {
+            source: self.document_key.clone(),
+            width,
+            height,
+            color: [self.color.x.to_bits(), self.color.y.to_bits(), self.color.z.to_bits(), self.color.w.to_bits()],
+        };
+        let texture = match cx.get_global::<SvgCache>().get(&key) {
+            Some(texture) => texture,
+            None => {
+                let texture = Texture::new_with_format(cx, TextureFormat::VecBGRAu8_32 {
+                    width,
+                    height,
+                    data: document.rasterize(width, height, self.color)
+                });
+                cx.get_global::<SvgCache>().insert(key, texture.clone());
+                texture
+            }
+        };
+        self.draw_svg.draw_vars.set_texture(0, &texture);
+        self.texture = Some(texture);
+        self.draw_svg.draw_walk(cx, walk);
+        DrawStep::done()
+    }
+
+    fn lazy_create_svg_cache(cx: &mut Cx) {
+        if !cx.has_global::<SvgCache>() {
+            cx.set_global(SvgCache::default());
+        }
+    }
+
+    /// Loads the SVG document at `svg_path`, sharing the parsed document with other widgets.
+    pub fn load_svg_dep_by_path(&mut self, cx: &mut Cx, svg_path: &str) -> Result<(), String> {
+        Self::lazy_create_svg_cache(cx);
+        if let Some(document) = cx.get_global::<SvgCache>().documents.get(svg_path) {
+            self.document = Some(document.clone());
+            self.document_key = svg_path.to_string();
+            return Ok(())
+        }
+        let data = cx.get_dependency(svg_path)?;
+        let source = std::str::from_utf8(&data).map_err( | e | e.to_string())?;
+        let document = Rc::new(SvgDocument::parse(source)?);
+        cx.get_global::<SvgCache>().documents.insert(svg_path.to_string(), document.clone());
+        self.document = Some(document);
+        self.document_key = svg_path.to_string();
+        Ok(())
+    }
+
+    /// Parses and shows SVG `source` text, which is not shared with other widgets.
+    pub fn load_svg_from_str(&mut self, cx: &mut Cx, source: &str) -> Result<(), String> {
+        Self::lazy_create_svg_cache(cx);
+        let document = SvgDocument::parse(source)?;
+        self.document = Some(Rc::new(document));
+        // an unique key per loaded text, so stale rasterizations are never reused
+        self.document_key = format!("<inline {}>", LiveId::from_str(source).0);
+        Ok(())
+    }
+}
+
+impl SvgRef {
+    /// Loads the SVG document at the given `svg_path` into this `SvgRef`.
+    pub fn load_svg_dep_by_path(&self, cx: &mut Cx, svg_path: &str) -> Result<(), String> {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.load_svg_dep_by_path(cx, svg_path)?;
+            inner.redraw(cx);
+        }
+        Ok(())
+    }
+
+    /// Parses SVG `source` text and shows it in this `SvgRef`.
+    pub fn load_svg_from_str(&self, cx: &mut Cx, source: &str) -> Result<(), String> {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.load_svg_from_str(cx, source)?;
+            inner.redraw(cx);
+        }
+        Ok(())
+    }
+
+    /// Sets the color used for `currentColor` in the document.
+    pub fn set_color(&self, cx: &mut Cx, color: Vec4) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.color = color;
+            inner.redraw(cx);
+        }
+    }
+}
@@ -0,0 +1,867 @@
+use {
+    std::collections::HashMap,
+    crate::{
+        makepad_draw::*,
+        makepad_draw::icon_atlas::parse_svg_path,
+        makepad_html::{parse_html, HtmlNode},
+        makepad_draw::makepad_vector::{
+            geometry::{AffineTransformation, LinearTransformation, Point, Transform, Transformation, Trapezoid, Vector},
+            internal_iter::ExtendFromInternalIterator,
+            path::{LinePathCommand, PathCommand, PathIterator},
+            trapezoidator::Trapezoidator,
+        },
+    }
+};
+
+/// A parsed SVG document, reduced to a flat list of filled and stroked shapes.
+///
+/// Supports `path`, `rect`, `circle`, `ellipse`, `line`, `polyline` and `polygon`,
+/// nested `g` groups with transforms, fills and strokes with opacity, and
+/// linear/radial gradients. Fills use the nonzero winding rule and strokes use
+/// round joins and caps.
+#[derive(Default)]
+pub struct SvgDocument {
+    pub view_box: Option<Rect>,
+    pub width: Option<f64>,
+    pub height: Option<f64>,
+    pub shapes: Vec<SvgShape>,
+    pub gradients: HashMap<String, SvgGradient>,
+}
+
+pub struct SvgShape {
+    pub path: Vec<PathCommand>,
+    pub transform: AffineTransformation,
+    pub fill: Option<SvgPaint>,
+    pub fill_opacity: f64,
+    pub stroke: Option<SvgPaint>,
+    pub stroke_opacity: f64,
+    pub stroke_width: f64,
+    pub opacity: f64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum SvgPaint {
+    Color(Vec4),
+    /// `currentColor`, supplied at rasterization time.
+    CurrentColor,
+    /// A reference to a gradient by id, as in `url(#id)`.
+    Url(String),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SvgGradientKind {
+    Linear {x1: f64, y1: f64, x2: f64, y2: f64},
+    Radial {cx: f64, cy: f64, r: f64},
+}
+
+#[derive(Clone, Debug)]
+pub struct SvgGradient {
+    pub kind: SvgGradientKind,
+    /// True for `gradientUnits="userSpaceOnUse"`, otherwise coordinates are relative to the shape bounds.
+    pub user_space: bool,
+    pub transform: AffineTransformation,
+    pub stops: Vec<(f64, Vec4)>,
+    href: Option<String>,
+}
+
+#[derive(Clone)]
+struct SvgStyle {
+    fill: Option<SvgPaint>,
+    fill_opacity: f64,
+    stroke: Option<SvgPaint>,
+    stroke_opacity: f64,
+    stroke_width: f64,
+    opacity: f64,
+    transform: AffineTransformation,
+    hidden: bool,
+}
+
+impl Default for SvgStyle {
+    fn default() -> Self {
+        Self {
+            fill: Some(SvgPaint::Color(vec4(0.0, 0.0, 0.0, 1.0))),
+            fill_opacity: 1.0,
+            stroke: None,
+            stroke_opacity: 1.0,
+            stroke_width: 1.0,
+            opacity: 1.0,
+            transform: AffineTransformation::identity(),
+            hidden: false,
+        }
+    }
+}
+
+impl SvgDocument {
+    pub fn parse(source: &str) -> Result<Self, String> {
+        let source = strip_prolog(source);
+        let mut errors = Some(Vec::new());
+        let html = parse_html(&source, &mut errors);
+
+        let mut doc = SvgDocument::default();
+        let mut stack = vec![SvgStyle::default()];
+        let mut current_gradient: Option<String> = None;
+        let mut found_svg = false;
+        let nodes = &html.nodes;
+        let mut i = 0;
+        while i < nodes.len() {
+            match &nodes[i] {
+                HtmlNode::OpenTag {lc: tag, ..} => {
+                    let mut attrs = Vec::new();
+                    while let Some(HtmlNode::Attribute {lc, start, end, ..}) = nodes.get(i + 1) {
+                        attrs.push((*lc, &html.decoded[*start..*end]));
+                        i += 1;
+                    }
+                    let attrs = SvgAttrs::new(attrs);
+                    let mut style = stack.last().cloned().unwrap_or_default();
+                    style.opacity = 1.0;
+                    attrs.apply_style(&mut style, &stack.last().cloned().unwrap_or_default());
+
+                    match *tag {
+                        live_id!(svg) if !found_svg => {
+                            found_svg = true;
+                            doc.width = attrs.get("width").and_then(parse_length);
+                            doc.height = attrs.get("height").and_then(parse_length);
+                            doc.view_box = attrs.get("viewBox").and_then( | v | {
+                                let n = parse_numbers(v);
+                                if n.len() == 4 && n[2] > 0.0 && n[3] > 0.0 {
+                                    Some(Rect {pos: dvec2(n[0], n[1]), size: dvec2(n[2], n[3])})
+                                }
+                                else {
+                                    None
+                                }
+                            });
+                        }
+                        live_id!(defs) | live_id!(clippath) | live_id!(mask) | live_id!(symbol) |
+                        live_id!(pattern) | live_id!(marker) | live_id!(title) | live_id!(desc) | live_id!(style) => {
+                            style.hidden = true;
+                        }
+                        live_id!(lineargradient) | live_id!(radialgradient) => {
+                            let id = attrs.get("id").unwrap_or("").to_string();
+                            let kind = if *tag == live_id!(lineargradient) {
+                                SvgGradientKind::Linear {
+                                    x1: attrs.get_fraction("x1", 0.0),
+                                    y1: attrs.get_fraction("y1", 0.0),
+                                    x2: attrs.get_fraction("x2", 1.0),
+                                    y2: attrs.get_fraction("y2", 0.0),
+                                }
+                            }
+                            else {
+                                SvgGradientKind::Radial {
+                                    cx: attrs.get_fraction("cx", 0.5),
+                                    cy: attrs.get_fraction("cy", 0.5),
+                                    r: attrs.get_fraction("r", 0.5),
+                                }
+                            };
+                            doc.gradients.insert(id.clone(), SvgGradient {
+                                kind,
+                                user_space: attrs.get("gradientUnits") == Some("userSpaceOnUse"),
+                                transform: attrs.get("gradientTransform").map(parse_transform).unwrap_or(AffineTransformation::identity()),
+                                stops: Vec::new(),
+                                href: attrs.get("href").or(attrs.get("xlink:href")).map( | v | v.trim_start_matches('#').to_string()),
+                            });
+                            current_gradient = Some(id);
+                            style.hidden = true;
+                        }
+                        live_id!(stop) => if let Some(gradient) = current_gradient.as_ref().and_then( | id | doc.gradients.get_mut(id)) {
+                            let offset = attrs.get_fraction("offset", 0.0).clamp(0.0, 1.0);
+                            let mut color = attrs.get_style("stop-color").and_then(parse_color).unwrap_or(vec4(0.0, 0.0, 0.0, 1.0));
+                            color.w *= attrs.get_style("stop-opacity").and_then(parse_number).unwrap_or(1.0) as f32;
+                            // offsets can never go backwards
+                            let offset = gradient.stops.last().map( | v | v.0.max(offset)).unwrap_or(offset);
+                            gradient.stops.push((offset, color));
+                        }
+                        _ => if !style.hidden {
+                            if let Some(path) = attrs.shape_path(*tag) {
+                                doc.shapes.push(SvgShape {
+                                    path,
+                                    transform: style.transform,
+                                    fill: style.fill.clone(),
+                                    fill_opacity: style.fill_opacity,
+                                    stroke: style.stroke.clone(),
+                                    stroke_opacity: style.stroke_opacity,
+                                    stroke_width: style.stroke_width,
+                                    opacity: style.opacity,
+                                });
+                            }
+                        }
+                    }
+                    stack.push(style);
+                }
+                HtmlNode::CloseTag {lc: tag, ..} => {
+                    if *tag == live_id!(lineargradient) || *tag == live_id!(radialgradient) {
+                        current_gradient = None;
+                    }
+                    if stack.len() > 1 {
+                        stack.pop();
+                    }
+                }
+                _ => ()
+            }
+            i += 1;
+        }
+        if !found_svg {
+            return Err("SvgDocument: no <svg> element found".to_string())
+        }
+        doc.resolve_gradient_hrefs();
+        Ok(doc)
+    }
+
+    fn resolve_gradient_hrefs(&mut self) {
+        let ids: Vec<String> = self.gradients.keys().cloned().collect();
+        for id in ids {
+            let mut href = self.gradients[&id].href.clone();
+            // follow the chain (bounded, in case of cycles) to the first gradient with stops
+            for _ in 0..8 {
+                if !self.gradients[&id].stops.is_empty() {
+                    break;
+                }
+                let Some(parent) = href.as_ref().and_then( | h | self.gradients.get(h)) else {break};
+                let (stops, next) = (parent.stops.clone(), parent.href.clone());
+                self.gradients.get_mut(&id).unwrap().stops = stops;
+                href = next;
+            }
+        }
+    }
+
+    /// The intrinsic size in pixels, from `width`/`height` or else the `viewBox`.
+    pub fn size(&self) -> DVec2 {
+        let vb = self.view_box.map( | v | v.size).unwrap_or(dvec2(100.0, 100.0));
+        match (self.width, self.height) {
+            (Some(w), Some(h)) => dvec2(w, h),
+            (Some(w), None) => dvec2(w, w * vb.y / vb.x),
+            (None, Some(h)) => dvec2(h * vb.x / vb.y, h),
+            (None, None) => vb
+        }
+    }
+
+    /// Renders the document into a `width` x `height` pixel buffer, packed like `ImageBuffer`
+    /// data (`0xAARRGGBB`, not premultiplied). The view box is fitted centered, keeping its aspect.
+    pub fn rasterize(&self, width: usize, height: usize, current_color: Vec4) -> Vec<u32> {
+        let mut raster = SvgRaster::new(width, height);
+        let view_box = self.view_box.unwrap_or(Rect {pos: dvec2(0.0, 0.0), size: self.size()});
+        let scale = (width as f64 / view_box.size.x).min(height as f64 / view_box.size.y);
+        let offset = dvec2(
+            (width as f64 - view_box.size.x * scale) * 0.5 - view_box.pos.x * scale,
+            (height as f64 - view_box.size.y * scale) * 0.5 - view_box.pos.y * scale
+        );
+        let view = AffineTransformation::uniform_scaling(scale).translate(Vector::new(offset.x, offset.y));
+
+        for shape in &self.shapes {
+            let transform = compose(&view, &shape.transform);
+            let shape_scale = transform.xy.x.length().max(transform.xy.y.length()).max(1e-6);
+            let lines = linearize(&shape.path, 0.25 / shape_scale);
+            let Some(bounds) = line_bounds(&lines) else {continue};
+            let Some(inverse) = invert(&transform) else {continue};
+            let device: Vec<LinePathCommand> = lines.iter().map( | cmd | cmd.transform(&transform)).collect();
+
+            if let Some(fill) = &shape.fill {
+                let paint = self.resolve_paint(fill, current_color, bounds, inverse);
+                let opacity = shape.opacity * shape.fill_opacity;
+                raster.fill(close_subpaths(&device).into_iter(), &paint, opacity);
+            }
+            if let Some(stroke) = &shape.stroke {
+                let stroke_width = shape.stroke_width * shape_scale;
+                if stroke_width > 0.0 {
+                    let paint = self.resolve_paint(stroke, current_color, bounds, inverse);
+                    let opacity = shape.opacity * shape.stroke_opacity;
+                    raster.fill(stroke_outline(&device, stroke_width).into_iter(), &paint, opacity);
+                }
+            }
+        }
+        raster.into_pixels()
+    }
+
+    fn resolve_paint(&self, paint: &SvgPaint, current_color: Vec4, bounds: Rect, inverse: AffineTransformation) -> RasterPaint {
+        match paint {
+            SvgPaint::Color(color) => RasterPaint::Solid(*color),
+            SvgPaint::CurrentColor => RasterPaint::Solid(current_color),
+            SvgPaint::Url(id) => match self.gradients.get(id) {
+                Some(gradient) if !gradient.stops.is_empty() => {
+                    // device -> user space -> gradient space
+                    let mut to_gradient = inverse;
+                    if !gradient.user_space {
+                        let size = dvec2(bounds.size.x.max(1e-6), bounds.size.y.max(1e-6));
+                        let to_bbox = AffineTransformation::translation(Vector::new(-bounds.pos.x, -bounds.pos.y))
+                            .scale(Vector::new(1.0 / size.x, 1.0 / size.y));
+                        to_gradient = compose(&to_bbox, &to_gradient);
+                    }
+                    if let Some(gradient_inverse) = invert(&gradient.transform) {
+                        to_gradient = compose(&gradient_inverse, &to_gradient);
+                    }
+                    RasterPaint::Gradient {
+                        kind: gradient.kind,
+                        to_gradient,
+                        stops: gradient.stops.clone()
+                    }
+                }
+                // a gradient without stops paints nothing
+                Some(_) => RasterPaint::Solid(vec4(0.0, 0.0, 0.0, 0.0)),
+                None => RasterPaint::Solid(vec4(0.0, 0.0, 0.0, 1.0)),
+            }
+        }
+    }
+}
+
+struct SvgAttrs<'a> {
+    attrs: Vec<(LiveId, &'a str)>,
+    style: Vec<(LiveId, &'a str)>,
+}
+
+impl<'a> SvgAttrs<'a> {
+    fn new(attrs: Vec<(LiveId, &'a str)>) -> Self {
+        let mut style = Vec::new();
+        if let Some((_, css)) = attrs.iter().find( | (id, _) | *id == live_id!(style)) {
+            for decl in css.split(';') {
+                if let Some((name, value)) = decl.split_once(':') {
+                    style.push((LiveId::from_str_lc(name.trim()), value.trim()));
+                }
+            }
+        }
+        Self {attrs, style}
+    }
+
+    fn get(&self, name: &str) -> Option<&'a str> {
+        let id = LiveId::from_str_lc(name);
+        self.attrs.iter().find( | (lc, _) | *lc == id).map( | (_, v) | v.trim())
+    }
+
+    /// Looks up a presentation property, where the `style` attribute wins over attributes.
+    fn get_style(&self, name: &str) -> Option<&'a str> {
+        let id = LiveId::from_str_lc(name);
+        self.style.iter().rev().find( | (lc, _) | *lc == id).map( | (_, v) | *v).or_else( || self.get(name))
+    }
+
+    fn get_number(&self, name: &str) -> f64 {
+        self.get(name).and_then(parse_length).unwrap_or(0.0)
+    }
+
+    /// A number that may also be written as a percentage.
+    fn get_fraction(&self, name: &str, default: f64) -> f64 {
+        match self.get_style(name) {
+            Some(v) if v.ends_with('%') => parse_number(&v[..v.len() - 1]).map( | v | v / 100.0).unwrap_or(default),
+            Some(v) => parse_length(v).unwrap_or(default),
+            None => default
+        }
+    }
+
+    fn apply_style(&self, style: &mut SvgStyle, parent: &SvgStyle) {
+        let paint = | value: &str, inherited: &Option<SvgPaint> | -> Option<SvgPaint> {
+            match value {
+                "none" | "transparent" => None,
+                "inherit" => inherited.clone(),
+                "currentColor" => Some(SvgPaint::CurrentColor),
+                v if v.starts_with("url(") => {
+                    let id = v[4..].split(')').next().unwrap_or("").trim().trim_matches(|c| c == '\'' || c == '"');
+                    Some(SvgPaint::Url(id.trim_start_matches('#').to_string()))
+                }
+                v => parse_color(v).map(SvgPaint::Color)
+            }
+        };
+        if let Some(v) = self.get_style("fill") {
+            style.fill = paint(v, &parent.fill);
+        }
+        if let Some(v) = self.get_style("stroke") {
+            style.stroke = paint(v, &parent.stroke);
+        }
+        if let Some(v) = self.get_style("stroke-width").and_then(parse_length) {
+            style.stroke_width = v;
+        }
+        if let Some(v) = self.get_style("fill-opacity").and_then(parse_number) {
+            style.fill_opacity = v.clamp(0.0, 1.0);
+        }
+        if let Some(v) = self.get_style("stroke-opacity").and_then(parse_number) {
+            style.stroke_opacity = v.clamp(0.0, 1.0);
+        }
+        // opacity is not inherited, but group opacity still applies to everything inside
+        style.opacity = parent.opacity * self.get_style("opacity").and_then(parse_number).unwrap_or(1.0).clamp(0.0, 1.0);
+        if self.get_style("display") == Some("none") || self.get_style("visibility") == Some("hidden") {
+            style.hidden = true;
+        }
+        if let Some(v) = self.get("transform") {
+            style.transform = compose(&parent.transform, &parse_transform(v));
+        }
+    }
+
+    fn shape_path(&self, tag: LiveId) -> Option<Vec<PathCommand>> {
+        let mut path = Vec::new();
+        match tag {
+            live_id!(path) => return self.get("d").and_then( | d | parse_svg_path(d.as_bytes()).map_err( | e | error!("SvgDocument: {}", e)).ok()),
+            live_id!(rect) => {
+                let (x, y, w, h) = (self.get_number("x"), self.get_number("y"), self.get_number("width"), self.get_number("height"));
+                if w <= 0.0 || h <= 0.0 {
+                    return None
+                }
+                let rx = self.get("rx").and_then(parse_length);
+                let ry = self.get("ry").and_then(parse_length);
+                let rx = rx.or(ry).unwrap_or(0.0).min(w * 0.5);
+                let ry = ry.or(Some(rx)).unwrap_or(0.0).min(h * 0.5);
+                if rx > 0.0 && ry > 0.0 {
+                    let r = Point::new(rx, ry);
+                    path.push(PathCommand::MoveTo(Point::new(x + rx, y)));
+                    path.push(PathCommand::LineTo(Point::new(x + w - rx, y)));
+                    path.push(PathCommand::ArcTo(Point::new(x + w, y + ry), r, 0.0, false, true));
+                    path.push(PathCommand::LineTo(Point::new(x + w, y + h - ry)));
+                    path.push(PathCommand::ArcTo(Point::new(x + w - rx, y + h), r, 0.0, false, true));
+                    path.push(PathCommand::LineTo(Point::new(x + rx, y + h)));
+                    path.push(PathCommand::ArcTo(Point::new(x, y + h - ry), r, 0.0, false, true));
+                    path.push(PathCommand::LineTo(Point::new(x, y + ry)));
+                    path.push(PathCommand::ArcTo(Point::new(x + rx, y), r, 0.0, false, true));
+                }
+                else {
+                    path.push(PathCommand::MoveTo(Point::new(x, y)));
+                    path.push(PathCommand::LineTo(Point::new(x + w, y)));
+                    path.push(PathCommand::LineTo(Point::new(x + w, y + h)));
+                    path.push(PathCommand::LineTo(Point::new(x, y + h)));
+                }
+                path.push(PathCommand::Close);
+            }
+            live_id!(circle) | live_id!(ellipse) => {
+                let (cx, cy) = (self.get_number("cx"), self.get_number("cy"));
+                let (rx, ry) = if tag == live_id!(circle) {
+                    let r = self.get_number("r");
+                    (r, r)
+                }
+                else {
+                    (self.get_number("rx"), self.get_number("ry"))
+                };
+                if rx <= 0.0 || ry <= 0.0 {
+                    return None
+                }
+                let r = Point::new(rx, ry);
+                path.push(PathCommand::MoveTo(Point::new(cx + rx, cy)));
+                path.push(PathCommand::ArcTo(Point::new(cx - rx, cy), r, 0.0, false, true));
+                path.push(PathCommand::ArcTo(Point::new(cx + rx, cy), r, 0.0, false, true));
+                path.push(PathCommand::Close);
+            }
+            live_id!(line) => {
+                path.push(PathCommand::MoveTo(Point::new(self.get_number("x1"), self.get_number("y1"))));
+                path.push(PathCommand::LineTo(Point::new(self.get_number("x2"), self.get_number("y2"))));
+            }
+            live_id!(polyline) | live_id!(polygon) => {
+                let points = parse_numbers(self.get("points")?);
+                for (i, p) in points.chunks_exact(2).enumerate() {
+                    let p = Point::new(p[0], p[1]);
+                    path.push(if i == 0 {PathCommand::MoveTo(p)} else {PathCommand::LineTo(p)});
+                }
+                if tag == live_id!(polygon) {
+                    path.push(PathCommand::Close);
+                }
+            }
+            _ => return None
+        }
+        Some(path)
+    }
+}
+
+/// Removes the `<?xml ?>` prolog, doctype and CDATA sections, which the html parser doesn't know about.
+fn strip_prolog(source: &str) -> String {
+    let mut out = String::with_capacity(source.len());
+    let mut rest = source;
+    while let Some(start) = rest.find("<?").into_iter().chain(rest.find("<!D")).chain(rest.find("<![")).min() {
+        out.push_str(&rest[..start]);
+        let end = if rest[start..].starts_with("<![") {
+            rest[start..].find("]]>").map( | e | e + 3)
+        }
+        else {
+            rest[start..].find('>').map( | e | e + 1)
+        };
+        match end {
+            Some(end) => rest = &rest[start + end..],
+            None => {
+                rest = "";
+                break;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+fn parse_number(v: &str) -> Option<f64> {
+    v.trim().parse::<f64>().ok()
+}
+
+fn parse_length(v: &str) -> Option<f64> {
+    let v = v.trim();
+    let v = v.strip_suffix("px").unwrap_or(v);
+    if v.ends_with('%') {
+        return None
+    }
+    parse_number(v)
+}
+
+fn parse_numbers(v: &str) -> Vec<f64> {
+    v.split( | c: char | c == ',' || c.is_whitespace()).filter( | s | !s.is_empty()).filter_map(parse_number).collect()
+}
+
+fn parse_color(v: &str) -> Option<Vec4> {
+    let v = v.trim();
+    if let Some(hex) = v.strip_prefix('#') {
+        let digit = | i: usize | u8::from_str_radix(&hex[i..i + 1], 16).ok();
+        let byte = | i: usize | u8::from_str_radix(&hex[i..i + 2], 16).ok();
+        let (r, g, b) = match hex.len() {
+            3 => (digit(0)? * 17, digit(1)? * 17, digit(2)? * 17),
+            6 => (byte(0)?, byte(2)?, byte(4)?),
+            _ => return None
+        };
+        return Some(vec4(r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0, 1.0))
+    }
+    if let Some(args) = v.strip_prefix("rgb(").or_else( || v.strip_prefix("rgba(")) {
+        let parts: Vec<&str> = args.trim_end_matches(')').split(',').map( | s | s.trim()).collect();
+        if parts.len() < 3 {
+            return None
+        }
+        let channel = | s: &str | -> Option<f32> {
+            match s.strip_suffix('%') {
+                Some(p) => p.parse::<f32>().ok().map( | v | v / 100.0),
+                None => s.parse::<f32>().ok().map( | v | v / 255.0)
+            }
+        };
+        let a = parts.get(3).and_then( | a | a.parse::<f32>().ok()).unwrap_or(1.0);
+        return Some(vec4(channel(parts[0])?, channel(parts[1])?, channel(parts[2])?, a))
+    }
+    let rgb = match v.to_ascii_lowercase().as_str() {
+        "black" => 0x000000,
+        "white" => 0xffffff,
+        "red" => 0xff0000,
+        "green" => 0x008000,
+        "lime" => 0x00ff00,
+        "blue" => 0x0000ff,
+        "yellow" => 0xffff00,
+        "cyan" | "aqua" => 0x00ffff,
+        "magenta" | "fuchsia" => 0xff00ff,
+        "gray" | "grey" => 0x808080,
+        "silver" => 0xc0c0c0,
+        "maroon" => 0x800000,
+        "olive" => 0x808000,
+        "purple" => 0x800080,
+        "teal" => 0x008080,
+        "navy" => 0x000080,
+        "orange" => 0xffa500,
+        _ => return None
+    };
+    Some(vec4(
+        ((rgb >> 16) & 0xff) as f32 / 255.0,
+        ((rgb >> 8) & 0xff) as f32 / 255.0,
+        (rgb & 0xff) as f32 / 255.0,
+        1.0
+    ))
+}
+
+fn parse_transform(v: &str) -> AffineTransformation {
+    let mut result = AffineTransformation::identity();
+    for part in v.split(')') {
+        let Some((name, args)) = part.split_once('(') else {continue};
+        let n = parse_numbers(args);
+        let arg = | i: usize, default: f64 | n.get(i).copied().unwrap_or(default);
+        let t = match name.trim().trim_start_matches(',').trim() {
+            "matrix" if n.len() == 6 => matrix(n[0], n[1], n[2], n[3], n[4], n[5]),
+            "translate" => matrix(1.0, 0.0, 0.0, 1.0, arg(0, 0.0), arg(1, 0.0)),
+            "scale" => matrix(arg(0, 1.0), 0.0, 0.0, arg(1, arg(0, 1.0)), 0.0, 0.0),
+            "rotate" => {
+                let (s, c) = arg(0, 0.0).to_radians().sin_cos();
+                let (cx, cy) = (arg(1, 0.0), arg(2, 0.0));
+                let rotate = matrix(c, s, -s, c, 0.0, 0.0);
+                compose(&compose(&matrix(1.0, 0.0, 0.0, 1.0, cx, cy), &rotate), &matrix(1.0, 0.0, 0.0, 1.0, -cx, -cy))
+            }
+            "skewX" => matrix(1.0, 0.0, arg(0, 0.0).to_radians().tan(), 1.0, 0.0, 0.0),
+            "skewY" => matrix(1.0, arg(0, 0.0).to_radians().tan(), 0.0, 1.0, 0.0, 0.0),
+            _ => continue
+        };
+        result = compose(&result, &t);
+    }
+    result
+}
+
+/// The SVG `matrix(a b c d e f)` as an affine transformation.
+fn matrix(a: f64, b: f64, c: f64, d: f64, e: f64, f: f64) -> AffineTransformation {
+    AffineTransformation::new(LinearTransformation::new(Vector::new(a, b), Vector::new(c, d)), Vector::new(e, f))
+}
+
+/// Returns the transformation that applies `inner` first, then `outer`.
+fn compose(outer: &AffineTransformation, inner: &AffineTransformation) -> AffineTransformation {
+    AffineTransformation::new(
+        outer.xy.compose(inner.xy),
+        outer.xy.transform_vector(inner.z) + outer.z
+    )
+}
+
+fn invert(t: &AffineTransformation) -> Option<AffineTransformation> {
+    let (a, b, c, d) = (t.xy.x.x, t.xy.x.y, t.xy.y.x, t.xy.y.y);
+    let det = a * d - b * c;
+    if det.abs() < 1e-12 {
+        return None
+    }
+    let (ia, ib, ic, id) = (d / det, -b / det, -c / det, a / det);
+    let (e, f) = (t.z.x, t.z.y);
+    Some(matrix(ia, ib, ic, id, -(ia * e + ic * f), -(ib * e + id * f)))
+}
+
+fn linearize(path: &[PathCommand], epsilon: f64) -> Vec<LinePathCommand> {
+    let mut lines = Vec::new();
+    lines.extend_from_internal_iter(path.iter().cloned().linearize(epsilon));
+    lines
+}
+
+fn line_bounds(lines: &[LinePathCommand]) -> Option<Rect> {
+    let mut min = dvec2(f64::INFINITY, f64::INFINITY);
+    let mut max = dvec2(-f64::INFINITY, -f64::INFINITY);
+    for cmd in lines {
+        if let LinePathCommand::MoveTo(p) | LinePathCommand::LineTo(p) = cmd {
+            min = dvec2(min.x.min(p.x), min.y.min(p.y));
+            max = dvec2(max.x.max(p.x), max.y.max(p.y));
+        }
+    }
+    if min.x > max.x {
+        return None
+    }
+    Some(Rect {pos: min, size: max - min})
+}
+
+/// Makes every subpath explicitly closed and started with a move, as the trapezoidator expects.
+fn close_subpaths(lines: &[LinePathCommand]) -> Vec<LinePathCommand> {
+    let mut out = Vec::with_capacity(lines.len() + 1);
+    let mut start = None;
+    let mut open = false;
+    for cmd in lines {
+        match *cmd {
+            LinePathCommand::MoveTo(p) => {
+                if open {
+                    out.push(LinePathCommand::Close);
+                }
+                out.push(LinePathCommand::MoveTo(p));
+                start = Some(p);
+                open = false;
+            }
+            LinePathCommand::LineTo(p) => {
+                let Some(s) = start else {continue};
+                if !open {
+                    if !matches!(out.last(), Some(LinePathCommand::MoveTo(_))) {
+                        out.push(LinePathCommand::MoveTo(s));
+                    }
+                    open = true;
+                }
+                out.push(LinePathCommand::LineTo(p));
+            }
+            LinePathCommand::Close => if open {
+                out.push(LinePathCommand::Close);
+                open = false;
+            }
+        }
+    }
+    if open {
+        out.push(LinePathCommand::Close);
+    }
+    out
+}
+
+/// Builds the outline of a stroke as a union of segment quads and round joins. All
+/// polygons share the same orientation, so the nonzero fill rule merges them.
+fn stroke_outline(lines: &[LinePathCommand], width: f64) -> Vec<LinePathCommand> {
+    let half = width * 0.5;
+    let mut out = Vec::new();
+    let mut start = None;
+    let mut last: Option<Point> = None;
+    let join_steps = ((half * 2.0).ceil() as usize).clamp(8, 64);
+    let join = | out: &mut Vec<LinePathCommand>, p: Point | {
+        for i in 0..join_steps {
+            let angle = -(i as f64) / join_steps as f64 * std::f64::consts::TAU;
+            let q = Point::new(p.x + angle.cos() * half, p.y + angle.sin() * half);
+            out.push(if i == 0 {LinePathCommand::MoveTo(q)} else {LinePathCommand::LineTo(q)});
+        }
+        out.push(LinePathCommand::Close);
+    };
+    let segment = | out: &mut Vec<LinePathCommand>, p0: Point, p1: Point | {
+        let d = p1 - p0;
+        let len = d.length();
+        if len < 1e-9 {
+            return
+        }
+        let n = Vector::new(-d.y / len * half, d.x / len * half);
+        out.push(LinePathCommand::MoveTo(p0 + n));
+        out.push(LinePathCommand::LineTo(p1 + n));
+        out.push(LinePathCommand::LineTo(p1 - n));
+        out.push(LinePathCommand::LineTo(p0 - n));
+        out.push(LinePathCommand::Close);
+    };
+    for cmd in lines {
+        match *cmd {
+            LinePathCommand::MoveTo(p) => {
+                join(&mut out, p);
+                start = Some(p);
+                last = Some(p);
+            }
+            LinePathCommand::LineTo(p) => if let Some(l) = last {
+                segment(&mut out, l, p);
+                join(&mut out, p);
+                last = Some(p);
+            }
+            LinePathCommand::Close => if let (Some(l), Some(s)) = (last, start) {
+                segment(&mut out, l, s);
+                last = Some(s);
+            }
+        }
+    }
+    out
+}
+
+enum RasterPaint {
+    Solid(Vec4),
+    Gradient {
+        kind: SvgGradientKind,
+        to_gradient: AffineTransformation,
+        stops: Vec<(f64, Vec4)>,
+    }
+}
+
+impl RasterPaint {
+    fn color_at(&self, x: f64, y: f64) -> Vec4 {
+        match self {
+            RasterPaint::Solid(color) => *color,
+            RasterPaint::Gradient {kind, to_gradient, stops} => {
+                let p = to_gradient.transform_point(Point::new(x, y));
+                let t = match *kind {
+                    SvgGradientKind::Linear {x1, y1, x2, y2} => {
+                        let (dx, dy) = (x2 - x1, y2 - y1);
+                        let len2 = dx * dx + dy * dy;
+                        if len2 <= 0.0 {0.0} else {((p.x - x1) * dx + (p.y - y1) * dy) / len2}
+                    }
+                    SvgGradientKind::Radial {cx, cy, r} => {
+                        if r <= 0.0 {1.0} else {((p.x - cx).powi(2) + (p.y - cy).powi(2)).sqrt() / r}
+                    }
+                };
+                let t = t.clamp(0.0, 1.0);
+                let first = stops[0];
+                if t <= first.0 {
+                    return first.1
+                }
+                for pair in stops.windows(2) {
+                    let ((t0, c0), (t1, c1)) = (pair[0], pair[1]);
+                    if t <= t1 {
+                        let f = if t1 > t0 {((t - t0) / (t1 - t0)) as f32} else {1.0};
+                        return c0 + (c1 - c0) * f
+                    }
+                }
+                stops[stops.len() - 1].1
+            }
+        }
+    }
+}
+
+/// A premultiplied RGBA float canvas that trapezoids are composited onto with exact area coverage.
+struct SvgRaster {
+    width: usize,
+    height: usize,
+    pixels: Vec<Vec4>,
+    coverage: Vec<f32>,
+    trapezoidator: Trapezoidator,
+}
+
+impl SvgRaster {
+    fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![Vec4::default(); width * height],
+            coverage: vec![0.0; width * height],
+            trapezoidator: Trapezoidator::new(),
+        }
+    }
+
+    fn fill<I: Iterator<Item = LinePathCommand>>(&mut self, lines: I, paint: &RasterPaint, opacity: f64) {
+        let mut trapezoids: Vec<Trapezoid> = Vec::new();
+        match self.trapezoidator.trapezoidate(lines) {
+            Some(trapezoidate) => trapezoids.extend_from_internal_iter(trapezoidate),
+            None => return
+        }
+        if trapezoids.is_empty() {
+            return
+        }
+        // accumulate coverage; trapezoids of one path never overlap
+        let (mut x0, mut y0, mut x1, mut y1) = (self.width, self.height, 0, 0);
+        for t in &trapezoids {
+            let px0 = (t.xs[0].floor().max(0.0) as usize).min(self.width);
+            let px1 = (t.xs[1].ceil().max(0.0) as usize).min(self.width);
+            let py0 = (t.ys[0].min(t.ys[1]).floor().max(0.0) as usize).min(self.height);
+            let py1 = (t.ys[2].max(t.ys[3]).ceil().max(0.0) as usize).min(self.height);
+            x0 = x0.min(px0);
+            y0 = y0.min(py0);
+            x1 = x1.max(px1);
+            y1 = y1.max(py1);
+            for py in py0..py1 {
+                for px in px0..px1 {
+                    let p_min = vec2(px as f32, py as f32);
+                    let p_max = p_min + vec2(1.0, 1.0);
+                    let a0 = clamped_right_trapezoid_area(vec2(t.xs[0], t.ys[0]), vec2(t.xs[1], t.ys[1]), p_min, p_max);
+                    let a1 = clamped_right_trapezoid_area(vec2(t.xs[0], t.ys[2]), vec2(t.xs[1], t.ys[3]), p_min, p_max);
+                    self.coverage[py * self.width + px] += a0 - a1;
+                }
+            }
+        }
+        for py in y0..y1 {
+            for px in x0..x1 {
+                let index = py * self.width + px;
+                let coverage = self.coverage[index].clamp(0.0, 1.0);
+                self.coverage[index] = 0.0;
+                if coverage <= 0.0 {
+                    continue;
+                }
+                let color = paint.color_at(px as f64 + 0.5, py as f64 + 0.5);
+                let alpha = color.w * coverage * opacity as f32;
+                let src = vec4(color.x * alpha, color.y * alpha, color.z * alpha, alpha);
+                let dst = self.pixels[index];
+                self.pixels[index] = src + dst * (1.0 - alpha);
+            }
+        }
+    }
+
+    fn into_pixels(self) -> Vec<u32> {
+        self.pixels.iter().map( | p | {
+            if p.w <= 0.0 {
+                return 0
+            }
+            let channel = | v: f32 | ((v / p.w).clamp(0.0, 1.0) * 255.0).round() as u32;
+            let a = (p.w.clamp(0.0, 1.0) * 255.0).round() as u32;
+            (a << 24) | (channel(p.x) << 16) | (channel(p.y) << 8) | channel(p.z)
+        }).collect()
+    }
+}
+
+/// Area of the pixel box below the line p0-p1, same as `compute_clamped_right_trapezoid_area` in `DrawTrapezoidVector`.
+fn clamped_right_trapezoid_area(mut p0: Vec2, mut p1: Vec2, p_min: Vec2, p_max: Vec2) -> f32 {
+    fn at_x(p0: Vec2, p1: Vec2, x: f32) -> Vec2 {
+        vec2(x, p0.y + (p1.y - p0.y) * (x - p0.x) / (p1.x - p0.x))
+    }
+    fn at_y(p0: Vec2, p1: Vec2, y: f32) -> Vec2 {
+        vec2(p0.x + (p1.x - p0.x) * (y - p0.y) / (p1.y - p0.y), y)
+    }
+    let x0 = p0.x.clamp(p_min.x, p_max.x);
+    let x1 = p1.x.clamp(p_min.x, p_max.x);
+    if p0.x < p_min.x && p_min.x < p1.x {
+        p0 = at_x(p0, p1, p_min.x);
+    }
+    if p0.x < p_max.x && p_max.x < p1.x {
+        p1 = at_x(p0, p1, p_max.x);
+    }
+    if p0.y < p_min.y && p_min.y < p1.y {
+        p0 = at_y(p0, p1, p_min.y);
+    }
+    if p1.y < p_min.y && p_min.y < p0.y {
+        p1 = at_y(p1, p0, p_min.y);
+    }
+    if p0.y < p_max.y && p_max.y < p1.y {
+        p1 = at_y(p0, p1, p_max.y);
+    }
+    if p1.y < p_max.y && p_max.y < p0.y {
+        p0 = at_y(p1, p0, p_max.y);
+    }
+    let clamp = | p: Vec2 | vec2(p.x.clamp(p_min.x, p_max.x), p.y.clamp(p_min.y, p_max.y));
+    p0 = clamp(p0);
+    p1 = clamp(p1);
+    let h0 = p_max.y - p0.y;
+    let h1 = p_max.y - p1.y;
+    let a0 = (p0.x - x0) * h0;
+    let a1 = (p1.x - p0.x) * (h0 + h1) * 0.5;
+    let a2 = (x1 - p1.x) * h1;
+    a0 + a1 + a2
+}
@@ -106,6 +106,10 @@ enum DrawState {
 }
 
 impl Widget for TextFlow {
+    fn widget_area(&self) -> Area {
+        self.area
+    }
+    
     fn draw_walk(&mut self, cx: &mut Cx2d, _scope: &mut Scope, walk:Walk)->DrawStep{
         //self.draw_text.draw_walk(cx, walk.with_add_padding(self.padding), self.align, self.text.as_ref());
         if self.draw_state.begin(cx, DrawState::Begin) {
@@ -3,6 +3,7 @@ use {
         makepad_derive_widget::*,
         makepad_draw::*,
         widget::*,
+        accessibility::*,
     }
 };
 
@@ -29,6 +30,12 @@ pub enum UndoGroup {
 }
 
 
+#[derive(Clone, Copy)]
+struct CaretBox {
+    pos: DVec2,
+    width: f64,
+}
+
 #[derive(Live, LiveHook, LiveRegister)]
 #[repr(C)]
 pub struct DrawLabel {
@@ -61,10 +68,19 @@ pub struct TextInput {
     #[live] on_focus_select_all: bool,
     #[live] pub read_only: bool,
     
+    // multi line mode: return inserts a newline, the text scrolls inside the box
+    #[live] multi_line: bool,
+    #[live(true)] word_wrap: bool,
+    #[live] max_length: Option<usize>,
+    #[live] max_lines: Option<usize>,
+    // only send Change on return / focus loss instead of on every keystroke
+    #[live] change_on_commit: bool,
+    
     //#[live] label_walk: Walk,
     
     #[live] pub text: String,
     #[live] ascii_only: bool,
+    #[live] nav_order: NavOrder,
     #[rust] double_tap_start: Option<(usize, usize)>,
     #[rust] undo_id: u64,
     
@@ -72,24 +88,55 @@ pub struct TextInput {
     #[rust] undo_stack: Vec<UndoItem>,
     #[rust] redo_stack: Vec<UndoItem>,
     #[rust] cursor_tail: usize,
-    #[rust] cursor_head: usize
+    #[rust] cursor_head: usize,
+    // the x position up/down movement tries to stay at
+    #[rust] cursor_x: Option<f64>,
+    // caret position and glyph width for every char index, plus one for the end
+    #[rust] carets: Vec<CaretBox>,
+    #[rust] text_scroll: DVec2,
+    #[rust] view_size: DVec2,
+    #[rust] scrolled_head: Option<usize>,
+    #[rust] change_pending: bool,
+    // uncommitted input method composition, drawn underlined at the cursor
+    #[rust] preedit: TextPreeditEvent,
+    #[rust] primary_selection: (usize, usize),
+    #[rust(LiveId::unique())] primary_paste_id: LiveId,
 }
 
 impl Widget for TextInput {
+    fn widget_area(&self) -> Area {
+        self.draw_bg.area()
+    }
+    
     fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
         let uid = self.widget_uid();
         if self.animator_handle_event(cx, event).must_redraw() {
             self.draw_bg.redraw(cx);
         }
+        if let Event::ClipboardRead(cr) = event {
+            if cr.request_id == self.primary_paste_id && !self.read_only {
+                let text = cr.item.as_ref().and_then( | item | item.as_text()).unwrap_or_default();
+                let mut input = String::new();
+                self.filter_input(&text, Some(&mut input));
+                if !input.is_empty() {
+                    self.undo_id += 1;
+                    self.create_undo(UndoGroup::TextInput(self.undo_id));
+                    if self.change(cx, &input){self.push_change_action(uid, scope, cx)}
+                }
+            }
+        }
         match event.hits(cx, self.draw_bg.area()) {
             Hit::KeyFocusLost(_) => {
                 self.animator_play(cx, id!(focus.off));
+                self.preedit = TextPreeditEvent::default();
                 cx.hide_text_ime();
+                self.commit_change(uid, scope, cx);
                 cx.widget_action(uid, &scope.path, TextInputAction::Return(self.text.clone()));
                 cx.widget_action(uid, &scope.path, TextInputAction::KeyFocusLost);
             }
             Hit::KeyFocus(_) => {
                 self.undo_id += 1;
+                self.scrolled_head = None;
                 self.animator_play(cx, id!(focus.on));
                 // select all
                 if self.on_focus_select_all {
@@ -104,6 +151,8 @@ impl Widget for TextInput {
                 if input.len() == 0 {
                     return
                 }
+                self.cursor_x = None;
+                self.preedit = TextPreeditEvent::default();
                 let last_undo = self.last_undo.take();
                 if te.replace_last {
                     self.undo_id += 1;
@@ -121,6 +170,10 @@ impl Widget for TextInput {
                 }
                 if self.change(cx, &input){self.push_change_action(uid, scope, cx)}
             }
+            Hit::TextPreedit(tp) if !self.read_only => {
+                self.preedit = tp;
+                self.draw_bg.redraw(cx);
+            }
             Hit::TextCopy(ce) => {
                 self.undo_id += 1;
                 *ce.response.borrow_mut() = Some(self.selected_text());
@@ -133,121 +186,134 @@ impl Widget for TextInput {
                     if self.change(cx, ""){self.push_change_action(uid, scope, cx)}
                 }
             }
-            Hit::KeyDown(ke) => match ke.key_code {
-                                
-                KeyCode::Tab => {
-                    // dispatch_action(cx, self, TextInputAction::Tab(key.mod_shift));
+            Hit::KeyDown(ke) => {
+                if !matches!(ke.key_code, KeyCode::ArrowUp | KeyCode::ArrowDown | KeyCode::PageUp | KeyCode::PageDown) {
+                    self.cursor_x = None;
                 }
-                KeyCode::ReturnKey => {
-                    cx.hide_text_ime();
-                    cx.widget_action(uid, &scope.path, TextInputAction::Return(self.text.clone()));
-                },
-                KeyCode::Escape => {
-                    cx.widget_action(uid, &scope.path, TextInputAction::Escape);
-                },
-                KeyCode::KeyZ if ke.modifiers.logo || ke.modifiers.shift => {
-                    if self.read_only {
-                        return
-                    }
-                    self.undo_id += 1;
-                    if ke.modifiers.shift {
-                        self.redo();
-                    }
-                    else {
-                        self.undo();
-                    }
-                    self.push_change_action(uid, scope, cx);
-                    self.draw_bg.redraw(cx);
-                }
-                KeyCode::KeyA if ke.modifiers.logo || ke.modifiers.control => {
-                    self.undo_id += 1;
-                    self.cursor_tail = 0;
-                    self.cursor_head = self.text.chars().count();
-                    self.draw_bg.redraw(cx);
-                }
-                KeyCode::ArrowLeft => if !ke.modifiers.logo {
-                                        
-                    self.undo_id += 1;
-                    if self.cursor_head>0 {
-                        self.cursor_head -= 1;
+                match ke.key_code {
+                                
+                    KeyCode::Tab => {
+                        // dispatch_action(cx, self, TextInputAction::Tab(key.mod_shift));
                     }
-                    if !ke.modifiers.shift {
-                        self.cursor_tail = self.cursor_head;
+                    KeyCode::ReturnKey if self.multi_line && !ke.modifiers.logo && !ke.modifiers.control => {
+                        self.undo_id += 1;
+                        self.create_undo(UndoGroup::TextInput(self.undo_id));
+                        if self.change(cx, "\n"){self.push_change_action(uid, scope, cx)}
                     }
-                    self.draw_bg.redraw(cx);
-                },
-                KeyCode::ArrowRight => if !ke.modifiers.logo {
-                    self.undo_id += 1;
-                    if self.cursor_head < self.text.chars().count() {
-                        self.cursor_head += 1;
+                    KeyCode::ReturnKey => {
+                        cx.hide_text_ime();
+                        self.commit_change(uid, scope, cx);
+                        cx.widget_action(uid, &scope.path, TextInputAction::Return(self.text.clone()));
+                    },
+                    KeyCode::Escape => {
+                        cx.widget_action(uid, &scope.path, TextInputAction::Escape);
+                    },
+                    KeyCode::KeyZ if ke.modifiers.logo || ke.modifiers.shift => {
+                        if self.read_only {
+                            return
+                        }
+                        self.undo_id += 1;
+                        if ke.modifiers.shift {
+                            self.redo();
+                        }
+                        else {
+                            self.undo();
+                        }
+                        self.push_change_action(uid, scope, cx);
+                        self.draw_bg.redraw(cx);
                     }
-                    if !ke.modifiers.shift {
-                        self.cursor_tail = self.cursor_head;
+                    KeyCode::KeyA if ke.modifiers.logo || ke.modifiers.control => {
+                        self.undo_id += 1;
+                        self.cursor_tail = 0;
+                        self.cursor_head = self.text.chars().count();
+                        self.draw_bg.redraw(cx);
                     }
-                    self.draw_bg.redraw(cx);
-                }
-                KeyCode::ArrowDown => if !ke.modifiers.logo {
-                    self.undo_id += 1;
-                    // we need to figure out what is below our current cursor
-                    if let Some(pos) = self.draw_text.get_cursor_pos(cx, 0.0, self.cursor_head) {
-                        if let Some(pos) = self.draw_text.closest_offset(cx, dvec2(pos.x, pos.y + self.draw_text.get_line_spacing() * 1.5)) {
-                            self.cursor_head = pos;
-                            if !ke.modifiers.shift {
-                                self.cursor_tail = self.cursor_head;
-                            }
-                            self.draw_bg.redraw(cx);
+                    KeyCode::ArrowLeft => if !ke.modifiers.logo {
+                                        
+                        self.undo_id += 1;
+                        if self.cursor_head>0 {
+                            self.cursor_head -= 1;
                         }
-                    }
-                },
-                KeyCode::ArrowUp => if !ke.modifiers.logo {
-                    self.undo_id += 1;
-                    // we need to figure out what is below our current cursor
-                    if let Some(pos) = self.draw_text.get_cursor_pos(cx, 0.0, self.cursor_head) {
-                        if let Some(pos) = self.draw_text.closest_offset(cx, dvec2(pos.x, pos.y - self.draw_text.get_line_spacing() * 0.5)) {
-                            self.cursor_head = pos;
-                            if !ke.modifiers.shift {
-                                self.cursor_tail = self.cursor_head;
-                            }
-                            self.draw_bg.redraw(cx);
+                        if !ke.modifiers.shift {
+                            self.cursor_tail = self.cursor_head;
+                        }
+                        self.draw_bg.redraw(cx);
+                    },
+                    KeyCode::ArrowRight => if !ke.modifiers.logo {
+                        self.undo_id += 1;
+                        if self.cursor_head < self.text.chars().count() {
+                            self.cursor_head += 1;
+                        }
+                        if !ke.modifiers.shift {
+                            self.cursor_tail = self.cursor_head;
                         }
+                        self.draw_bg.redraw(cx);
                     }
-                },
-                KeyCode::Home => if !ke.modifiers.logo {
-                    self.undo_id += 1;
-                    self.cursor_head = 0;
-                    if !ke.modifiers.shift {
-                        self.cursor_tail = self.cursor_head;
+                    KeyCode::ArrowDown => {
+                        self.undo_id += 1;
+                        let head = if ke.modifiers.logo {self.text.chars().count()} else {self.vertical_index(self.cursor_head, 1.0)};
+                        self.move_head(cx, head, ke.modifiers.shift);
+                    },
+                    KeyCode::ArrowUp => {
+                        self.undo_id += 1;
+                        let head = if ke.modifiers.logo {0} else {self.vertical_index(self.cursor_head, -1.0)};
+                        self.move_head(cx, head, ke.modifiers.shift);
+                    },
+                    KeyCode::PageDown if self.multi_line => {
+                        self.undo_id += 1;
+                        let head = self.vertical_index(self.cursor_head, self.page_lines());
+                        self.move_head(cx, head, ke.modifiers.shift);
+                    },
+                    KeyCode::PageUp if self.multi_line => {
+                        self.undo_id += 1;
+                        let head = self.vertical_index(self.cursor_head, -self.page_lines());
+                        self.move_head(cx, head, ke.modifiers.shift);
+                    },
+                    KeyCode::Home if !ke.modifiers.logo || self.multi_line => {
+                        self.undo_id += 1;
+                        let head = if self.multi_line && !ke.modifiers.logo && !ke.modifiers.control {
+                            self.line_range(self.cursor_head).0
+                        }
+                        else {
+                            0
+                        };
+                        self.move_head(cx, head, ke.modifiers.shift);
                     }
-                    self.draw_bg.redraw(cx);
-                }
-                KeyCode::End => if !ke.modifiers.logo {
-                    self.undo_id += 1;
-                    self.cursor_head = self.text.chars().count();
-                                        
-                    if !ke.modifiers.shift {
-                        self.cursor_tail = self.cursor_head;
+                    KeyCode::End if !ke.modifiers.logo || self.multi_line => {
+                        self.undo_id += 1;
+                        let head = if self.multi_line && !ke.modifiers.logo && !ke.modifiers.control {
+                            self.line_range(self.cursor_head).1
+                        }
+                        else {
+                            self.text.chars().count()
+                        };
+                        self.move_head(cx, head, ke.modifiers.shift);
                     }
-                    self.draw_bg.redraw(cx);
-                }
-                KeyCode::Backspace => {
-                    self.create_undo(UndoGroup::Backspace(self.undo_id));
-                    if self.cursor_head == self.cursor_tail {
-                        if self.cursor_tail > 0 {
-                            self.cursor_tail -= 1;
+                    KeyCode::Backspace => {
+                        self.create_undo(UndoGroup::Backspace(self.undo_id));
+                        if self.cursor_head == self.cursor_tail {
+                            if self.cursor_tail > 0 {
+                                self.cursor_tail -= 1;
+                            }
                         }
+                        if self.change(cx, ""){self.push_change_action(uid, scope, cx)}
                     }
-                    if self.change(cx, ""){self.push_change_action(uid, scope, cx)}
-                }
-                KeyCode::Delete => {
-                    self.create_undo(UndoGroup::Delete(self.undo_id));
-                    if self.cursor_head == self.cursor_tail {
-                        if self.cursor_head < self.text.chars().count() {
-                            self.cursor_head += 1;
+                    KeyCode::Delete => {
+                        self.create_undo(UndoGroup::Delete(self.undo_id));
+                        if self.cursor_head == self.cursor_tail {
+                            if self.cursor_head < self.text.chars().count() {
+                                self.cursor_head += 1;
+                            }
                         }
+                        if self.change(cx, ""){self.push_change_action(uid, scope, cx)}
                     }
-                    if self.change(cx, ""){self.push_change_action(uid, scope, cx)}
+                    _ => ()
                 }
-                _ => ()
+                self.update_primary_selection(cx);
+            }
+            Hit::FingerScroll(fe) if self.multi_line => {
+                self.text_scroll += fe.scroll;
+                self.draw_bg.redraw(cx);
             }
             Hit::FingerHoverIn(_) => {
                 cx.set_cursor(MouseCursor::Text);
@@ -256,12 +322,24 @@ impl Widget for TextInput {
             Hit::FingerHoverOut(_) => {
                 self.animator_play(cx, id!(hover.off));
             },
+            Hit::FingerDown(fe) if fe.device.mouse_button() == Some(2) => {
+                // middle click pastes the primary selection where it lands
+                self.set_key_focus(cx);
+                self.cursor_x = None;
+                if let Some(pos) = self.closest_index(fe.abs) {
+                    self.cursor_head = pos.min(self.text.chars().count());
+                    self.cursor_tail = self.cursor_head;
+                    self.draw_bg.redraw(cx);
+                }
+                cx.read_clipboard(self.primary_paste_id, ClipboardSelection::Primary, &[ClipboardItem::TEXT]);
+            }
             Hit::FingerDown(fe) => {
                 cx.set_cursor(MouseCursor::Text);
                 self.set_key_focus(cx);
+                self.cursor_x = None;
                 // ok so we need to calculate where we put the cursor down.
                 //elf.
-                if let Some(pos) = self.draw_text.closest_offset(cx, fe.abs) {
+                if let Some(pos) = self.closest_index(fe.abs) {
                     //log!("{} {}", pos, fe.abs);
                     let pos = pos.min(self.text.chars().count());
                     if fe.tap_count == 1 {
@@ -286,7 +364,7 @@ impl Widget for TextInput {
             },
             Hit::FingerUp(fe) => {
                 self.double_tap_start = None;
-                if let Some(pos) = self.draw_text.closest_offset(cx, fe.abs) {
+                if let Some(pos) = self.closest_index(fe.abs) {
                     let pos = pos.min(self.text.chars().count());
                     if !fe.modifiers.shift && fe.tap_count == 1 && fe.was_tap() {
                         self.cursor_head = pos;
@@ -297,6 +375,7 @@ impl Widget for TextInput {
                 if fe.was_long_press() {
                     cx.show_clipboard_actions(self.selected_text());
                 }
+                self.update_primary_selection(cx);
                 if fe.is_over && fe.device.has_hovers() {
                     self.animator_play(cx, id!(hover.on));
                 }
@@ -304,8 +383,8 @@ impl Widget for TextInput {
                     self.animator_play(cx, id!(hover.off));
                 }
             }
-            Hit::FingerMove(fe) => {
-                if let Some(pos) = self.draw_text.closest_offset(cx, fe.abs) {
+            Hit::FingerMove(fe) if fe.device.mouse_button() != Some(2) => {
+                if let Some(pos) = self.closest_index(fe.abs) {
                     let pos = pos.min(self.text.chars().count());
                     if fe.tap_count == 2 {
                         let (head, tail) = self.double_tap_start.unwrap();
@@ -320,7 +399,7 @@ impl Widget for TextInput {
                         self.draw_bg.redraw(cx);
                     }
                     else if fe.tap_count == 1 {
-                        if let Some(pos_start) = self.draw_text.closest_offset(cx, fe.abs_start) {
+                        if let Some(pos_start) = self.closest_index(fe.abs_start) {
                             let pos_start = pos_start.min(self.text.chars().count());
                                                         
                             self.cursor_head = pos_start;
@@ -350,6 +429,39 @@ impl Widget for TextInput {
     fn set_text(&mut self, v: &str) {
         self.filter_input(&v, None);
     }
+    
+    fn access_node(&self, cx: &Cx) -> Option<AccessNode> {
+        let area = self.draw_bg.area();
+        // the empty message is the closest thing to a name we have
+        let text = if self.secret {String::new()} else {self.text.clone()};
+        Some(AccessNode::new(AccessRole::TextInput)
+            .with_label(&self.empty_message)
+            .with_value(AccessValue::Text(text))
+            .with_rect(area.rect(cx))
+            .with_states(AccessStates {
+                focusable: true,
+                focused: cx.has_key_focus(area),
+                editable: !self.read_only,
+                secret: self.secret,
+                ..Default::default()
+            })
+            .with_action(AccessAction::Focus))
+    }
+    
+    fn handle_access(&mut self, cx: &mut Cx, request: &AccessRequest, scope: &mut Scope) {
+        match request {
+            AccessRequest::Action(AccessAction::Focus) => self.set_key_focus(cx),
+            AccessRequest::SetText(text) if !self.read_only => {
+                self.filter_input(text, None);
+                self.select_all();
+                self.draw_bg.redraw(cx);
+                let uid = self.widget_uid();
+                self.push_change_action(uid, scope, cx);
+                self.commit_change(uid, scope, cx);
+            }
+            _ => ()
+        }
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, DefaultNone)]
@@ -373,6 +485,19 @@ impl TextInput {
         }
     }
     
+    /// Hands a changed selection to the primary selection, the one middle click pastes on X11
+    fn update_primary_selection(&mut self, cx: &mut Cx) {
+        let selection = self.sorted_cursor();
+        if selection == self.primary_selection || self.secret {
+            return
+        }
+        self.primary_selection = selection;
+        if selection.0 == selection.1 {
+            return
+        }
+        cx.copy_to_clipboard(ClipboardSelection::Primary, vec![ClipboardItem::text(&self.selected_text())]);
+    }
+    
     pub fn selected_text(&mut self) -> String {
         let mut ret = String::new();
         let (left, right) = self.sorted_cursor();
@@ -503,19 +628,236 @@ impl TextInput {
         }
     }
     
-    pub fn push_change_action(&self, uid:WidgetUid, scope:&Scope, cx: &mut Cx){
+    pub fn push_change_action(&mut self, uid:WidgetUid, scope:&Scope, cx: &mut Cx){
+        if self.change_on_commit {
+            self.change_pending = true;
+            return
+        }
         cx.widget_action(uid, &scope.path, TextInputAction::Change(self.text.clone()));
     }
     
+    fn commit_change(&mut self, uid:WidgetUid, scope:&Scope, cx: &mut Cx){
+        if self.change_pending {
+            self.change_pending = false;
+            cx.widget_action(uid, &scope.path, TextInputAction::Change(self.text.clone()));
+        }
+    }
+    
     pub fn change(&mut self, cx: &mut Cx, s: &str)->bool{
         if self.read_only {
             return false
         }
-        self.replace_text(s);
+        let input = self.clip_to_limits(s);
+        if input.is_empty() && !s.is_empty() && self.cursor_head == self.cursor_tail {
+            return false
+        }
+        self.replace_text(&input);
         self.draw_bg.redraw(cx);
         true
     }
     
+    // cuts the input off where it would run over max_length or max_lines
+    fn clip_to_limits(&self, input: &str) -> String {
+        let (left, right) = self.sorted_cursor();
+        let mut kept_chars = 0;
+        let mut kept_lines = 1;
+        for (i, c) in self.text.chars().enumerate() {
+            if i < left || i >= right {
+                kept_chars += 1;
+                if c == '\n' {
+                    kept_lines += 1;
+                }
+            }
+        }
+        let mut chars_left = self.max_length.map( | max | max.saturating_sub(kept_chars));
+        let mut lines_left = self.max_lines.map( | max | max.saturating_sub(kept_lines));
+        let mut output = String::new();
+        for c in input.chars() {
+            if chars_left == Some(0) {
+                break;
+            }
+            if c == '\n' {
+                if lines_left == Some(0) {
+                    break;
+                }
+                lines_left = lines_left.map( | l | l - 1);
+            }
+            chars_left = chars_left.map( | l | l - 1);
+            output.push(c);
+        }
+        output
+    }
+    
+    fn move_head(&mut self, cx: &mut Cx, head: usize, select: bool) {
+        self.cursor_head = head;
+        if !select {
+            self.cursor_tail = self.cursor_head;
+        }
+        self.draw_bg.redraw(cx);
+    }
+    
+    fn caret(&self, index: usize) -> Option<CaretBox> {
+        self.carets.get(index).or(self.carets.last()).cloned()
+    }
+    
+    fn same_line(&self, a: f64, b: f64) -> bool {
+        (a - b).abs() < 0.5 * self.draw_text.get_line_spacing()
+    }
+    
+    // the char index closest to a point, picking the visual line first
+    fn closest_index(&self, abs: DVec2) -> Option<usize> {
+        let line_spacing = self.draw_text.get_line_spacing();
+        let top_drop = self.draw_text.get_font_size() * 0.2;
+        let mut line_y = None;
+        for caret in &self.carets {
+            let dist = (abs.y - (caret.pos.y - top_drop + 0.5 * line_spacing)).abs();
+            if line_y.is_none_or( | (best, _) | dist < best) {
+                line_y = Some((dist, caret.pos.y));
+            }
+        }
+        let (_, line_y) = line_y?;
+        let mut closest = None;
+        for (i, caret) in self.carets.iter().enumerate() {
+            if !self.same_line(caret.pos.y, line_y) {
+                continue;
+            }
+            let dist = (abs.x - caret.pos.x).abs();
+            if closest.is_none_or( | (best, _) | dist < best) {
+                closest = Some((dist, i));
+            }
+        }
+        closest.map( | (_, i) | i.min(self.text.chars().count()))
+    }
+    
+    // the char index a number of visual lines up or down from index
+    fn vertical_index(&mut self, index: usize, lines: f64) -> usize {
+        let len = self.text.chars().count();
+        let caret = if let Some(caret) = self.caret(index) {caret} else {return index};
+        let x = *self.cursor_x.get_or_insert(caret.pos.x);
+        let line_spacing = self.draw_text.get_line_spacing();
+        let target_y = caret.pos.y + lines * line_spacing;
+        // the line nearest to the target that is past our own one, so paging clamps to the last line
+        let mut line_y = None;
+        for other in &self.carets {
+            let past = if lines > 0.0 {
+                other.pos.y > caret.pos.y + 0.5 * line_spacing
+            }
+            else {
+                other.pos.y < caret.pos.y - 0.5 * line_spacing
+            };
+            let dist = (other.pos.y - target_y).abs();
+            if past && line_y.is_none_or( | (best, _) | dist < best) {
+                line_y = Some((dist, other.pos.y));
+            }
+        }
+        let (_, line_y) = if let Some(line_y) = line_y {line_y} else {
+            return if lines > 0.0 {len} else {0}
+        };
+        let mut closest = None;
+        for (i, other) in self.carets.iter().enumerate() {
+            let dist = (x - other.pos.x).abs();
+            if self.same_line(other.pos.y, line_y) && closest.is_none_or( | (best, _) | dist < best) {
+                closest = Some((dist, i));
+            }
+        }
+        closest.map_or(index, | (_, i) | i.min(len))
+    }
+    
+    fn page_lines(&self) -> f64 {
+        (self.view_size.y / self.draw_text.get_line_spacing()).floor().max(1.0)
+    }
+    
+    // the first and last char index on the visual line of index
+    fn line_range(&self, index: usize) -> (usize, usize) {
+        let len = self.text.chars().count();
+        let index = index.min(len);
+        let caret = if let Some(caret) = self.caret(index) {caret} else {return (0, len)};
+        let mut start = index;
+        while start > 0 && self.same_line(self.carets[start - 1].pos.y, caret.pos.y) {
+            start -= 1;
+        }
+        let mut end = index;
+        while end < len && self.same_line(self.carets[end + 1].pos.y, caret.pos.y) {
+            end += 1;
+        }
+        (start, end)
+    }
+    
+    // the text as drawn: secret chars masked and the IME composition in place of the selection
+    fn shown_text(&self) -> String {
+        let (left, right) = self.sorted_cursor();
+        let mut shown = String::new();
+        for (i, c) in self.text.chars().enumerate() {
+            if i == left {
+                shown.push_str(&self.preedit.text);
+            }
+            if self.preedit.text.is_empty() || i < left || i >= right {
+                shown.push(c);
+            }
+        }
+        if left >= self.text.chars().count() {
+            shown.push_str(&self.preedit.text);
+        }
+        if self.secret {
+            shown = shown.chars().map( | c | if c == '\n' {c} else {'*'}).collect();
+        }
+        shown
+    }
+    
+    fn update_carets(&mut self, cx: &Cx, origin: DVec2, text: &str) {
+        self.carets.clear();
+        if text.is_empty() {
+            // park the caret at the start of the empty message
+            let pos = self.draw_text.get_cursor_pos(cx, 0.0, 0).unwrap_or(origin);
+            self.carets.push(CaretBox {pos, width: 0.0});
+            return
+        }
+        // newlines don't produce glyphs, so walk the chars and keep our own pen
+        let line_spacing = self.draw_text.get_line_spacing();
+        let glyph_count = self.draw_text.get_char_count(cx);
+        let mut pen = origin;
+        let mut glyph = 0;
+        for c in text.chars() {
+            if c == '\n' {
+                self.carets.push(CaretBox {pos: pen, width: 0.0});
+                pen = dvec2(origin.x, pen.y + line_spacing);
+            }
+            else if glyph < glyph_count {
+                let left = self.draw_text.get_cursor_pos(cx, 0.0, glyph).unwrap_or(pen);
+                let right = self.draw_text.get_cursor_pos(cx, 1.0, glyph).unwrap_or(left);
+                self.carets.push(CaretBox {pos: left, width: right.x - left.x});
+                pen = right;
+                glyph += 1;
+            }
+            else {
+                self.carets.push(CaretBox {pos: pen, width: 0.0});
+            }
+        }
+        self.carets.push(CaretBox {pos: pen, width: 0.0});
+    }
+    
+    fn selection_rects(&self, start: usize, end: usize, shift: DVec2, pad: DVec2) -> Vec<Rect> {
+        let line_spacing = self.draw_text.get_line_spacing();
+        // give selected newlines some width so empty lines show up
+        let newline_width = self.draw_text.get_font_size() * 0.5;
+        let mut rects: Vec<Rect> = Vec::new();
+        for caret in self.carets.iter().take(end).skip(start) {
+            let width = if caret.width > 0.0 {caret.width} else {newline_width};
+            if let Some(rect) = rects.last_mut() {
+                if self.same_line(rect.pos.y, caret.pos.y) {
+                    rect.size.x = rect.size.x.max(caret.pos.x + width - rect.pos.x);
+                    continue;
+                }
+            }
+            rects.push(Rect {pos: caret.pos, size: dvec2(width, line_spacing)});
+        }
+        for rect in &mut rects {
+            rect.pos += shift;
+            rect.size += pad;
+        }
+        rects
+    }
+    
     pub fn set_key_focus(&self, cx: &mut Cx) {
         cx.set_key_focus(self.draw_bg.area());
     }
@@ -530,7 +872,7 @@ impl TextInput {
         output.clear();
         if self.ascii_only {
             for c in input.as_bytes() {
-                if *c>31 && *c<127 {
+                if (*c>31 && *c<127) || (self.multi_line && *c == b'\n') {
                     output.push(*c as char);
                 }
             }
@@ -547,38 +889,55 @@ impl TextInput {
                 }
             }
         }
+        else if self.multi_line {
+            output.push_str(&input.replace("\r\n", "\n").replace('\r', "\n"));
+        }
         else {
             output.push_str(input);
         }
     }
     
     pub fn draw_walk_text_input(&mut self, cx: &mut Cx2d, walk: Walk) {
-        
-        self.draw_bg.begin(cx, walk, self.layout);
+        let mut layout = self.layout;
+        if self.multi_line {
+            layout.scroll = self.text_scroll;
+            layout.clip_x = true;
+            layout.clip_y = true;
+            self.draw_text.wrap = if self.word_wrap {TextWrap::Word} else {TextWrap::Line};
+        }
+        self.draw_bg.begin(cx, walk, layout);
         let turtle_rect = cx.turtle().rect();
+        // the padded rect moves with the scroll, we want the visible one
+        let view_rect = cx.turtle().padded_rect();
+        let view_rect = Rect {pos: view_rect.pos + layout.scroll, size: view_rect.size};
         
         // this makes sure selection goes behind the text
         self.draw_select.append_to_draw_call(cx);
         
-        if self.text.len() == 0 {
+        let text_walk = if !self.multi_line {
+            Walk::size(self.walk.width, self.walk.height)
+        }
+        else if self.word_wrap && !self.walk.width.is_fit() {
+            Walk::size(Size::Fill, Size::Fit)
+        }
+        else {
+            Walk::size(Size::Fit, Size::Fit)
+        };
+        let origin = cx.turtle().pos();
+        let shown = self.shown_text();
+        if shown.is_empty() {
             self.draw_text.is_empty = 1.0;
-            self.draw_text.draw_walk(cx, Walk::size(self.walk.width, self.walk.height), self.label_align, &self.empty_message);
+            self.draw_text.draw_walk(cx, text_walk, self.label_align, &self.empty_message);
         }
         else {
             self.draw_text.is_empty = 0.0;
-            if self.secret {
-                self.draw_text.draw_walk(cx, Walk::size(
-                    self.walk.width,
-                    self.walk.height
-                ), self.label_align, &"*".repeat(self.text.len()));
-            }
-            else {
-                self.draw_text.draw_walk(cx, Walk::size(
-                    self.walk.width,
-                    self.walk.height
-                ), self.label_align, &self.text);
-            }
+            self.draw_text.draw_walk(cx, text_walk, self.label_align, &shown);
         }
+        self.update_carets(cx, origin, &shown);
+        
+        let (left, _) = self.sorted_cursor();
+        let preedit_len = self.preedit.text.chars().count();
+        let head_index = if preedit_len > 0 {left + self.preedit.cursor.min(preedit_len)} else {self.cursor_head};
         
         let mut turtle = cx.turtle().padded_rect_used();
         turtle.pos.y -= self.cursor_margin_top;
@@ -586,10 +945,14 @@ impl TextInput {
         // move the IME
         let line_spacing = self.draw_text.get_line_spacing();
         let top_drop = self.draw_text.get_font_size() * 0.2;
-        let head = self.draw_text.get_cursor_pos(cx, 0.0, self.cursor_head)
+        let head = self.caret(head_index).map(| caret | caret.pos)
             .unwrap_or(dvec2(turtle.pos.x, 0.0));
         
-        if !self.read_only && self.cursor_head == self.cursor_tail {
+        if self.multi_line {
+            self.scroll_into_view(cx, view_rect, origin, head - dvec2(0.0, top_drop));
+        }
+        
+        if !self.read_only && (self.cursor_head == self.cursor_tail || preedit_len > 0) {
             self.draw_cursor.draw_abs(cx, Rect {
                 pos: dvec2(head.x - 0.5 * self.cursor_size, head.y - top_drop),
                 size: dvec2(self.cursor_size, line_spacing)
@@ -598,12 +961,22 @@ impl TextInput {
         
         // draw selection rects
         
-        if self.cursor_head != self.cursor_tail {
+        if preedit_len > 0 {
+            // underline the composition
+            let thickness = (0.5 * self.cursor_size).max(1.0);
+            for rect in self.selection_rects(left, left + preedit_len, dvec2(0.0, -top_drop), dvec2(0.0, 0.0)) {
+                self.draw_cursor.draw_abs(cx, Rect {
+                    pos: dvec2(rect.pos.x, rect.pos.y + rect.size.y - thickness),
+                    size: dvec2(rect.size.x, thickness)
+                });
+            }
+        }
+        else if self.cursor_head != self.cursor_tail {
             let top_drop = self.draw_text.get_font_size() * 0.3;
             let bottom_drop = self.draw_text.get_font_size() * 0.1;
             
             let (start, end) = self.sorted_cursor();
-            let rects = self.draw_text.get_selection_rects(cx, start, end, dvec2(0.0, -top_drop), dvec2(0.0, bottom_drop));
+            let rects = self.selection_rects(start, end, dvec2(0.0, -top_drop), dvec2(0.0, bottom_drop));
             for rect in rects {
                 self.draw_select.draw_abs(cx, rect);
             }
@@ -612,19 +985,54 @@ impl TextInput {
         
         if  cx.has_key_focus(self.draw_bg.area()) {
             // ok so. if we have the IME we should inject a tracking point
-            let ime_x = self.draw_text.get_cursor_pos(cx, 0.5, self.cursor_head)
-                .unwrap_or(dvec2(turtle.pos.x, 0.0)).x;
+            let ime = self.caret(head_index)
+                .map( | caret | caret.pos + dvec2(caret.width * 0.5, 0.0))
+                .unwrap_or(dvec2(turtle.pos.x, 0.0));
             
             if self.numeric_only {
                 cx.hide_text_ime();
             }
             else {
-                let ime_abs = dvec2(ime_x, turtle.pos.y);
+                // the candidate window goes just below the caret line
+                let ime_y = if self.multi_line {ime.y} else {turtle.pos.y};
+                let ime_abs = dvec2(ime.x, ime_y - top_drop + line_spacing);
                 cx.show_text_ime(self.draw_bg.area(), ime_abs - turtle_rect.pos);
             }
         }
         
-        cx.add_nav_stop(self.draw_bg.area(), NavRole::TextInput, Margin::default())
+        cx.add_nav_stop_ordered(self.draw_bg.area(), NavRole::TextInput, Margin::default(), self.nav_order)
+    }
+    
+    // keeps the scroll inside the text and, when the caret moved, the caret inside the view
+    fn scroll_into_view(&mut self, cx: &mut Cx2d, view_rect: Rect, origin: DVec2, caret: DVec2) {
+        let line_spacing = self.draw_text.get_line_spacing();
+        self.view_size = view_rect.size;
+        let old_scroll = self.text_scroll;
+        if self.scrolled_head != Some(self.cursor_head) {
+            self.scrolled_head = Some(self.cursor_head);
+            // the caret position already has the scroll applied
+            let nudge = | start: f64, size: f64, view_start: f64, view_size: f64 | {
+                if view_size.is_nan() || start < view_start {
+                    (start - view_start).min(0.0)
+                }
+                else {
+                    (start + size - (view_start + view_size)).max(0.0)
+                }
+            };
+            self.text_scroll.x += nudge(caret.x, self.cursor_size, view_rect.pos.x, view_rect.size.x);
+            self.text_scroll.y += nudge(caret.y, line_spacing, view_rect.pos.y, view_rect.size.y);
+        }
+        let mut content = dvec2(0.0, 0.0);
+        for caret in &self.carets {
+            content.x = content.x.max(caret.pos.x + caret.width - origin.x);
+            content.y = content.y.max(caret.pos.y + line_spacing - origin.y);
+        }
+        let max = content - view_rect.size;
+        self.text_scroll.x = if max.x.is_nan() {0.0} else {self.text_scroll.x.min(max.x).max(0.0)};
+        self.text_scroll.y = if max.y.is_nan() {0.0} else {self.text_scroll.y.min(max.y).max(0.0)};
+        if self.text_scroll != old_scroll {
+            self.draw_bg.redraw(cx);
+        }
     }
 }
 
@@ -636,4 +1044,10 @@ impl TextInputRef {
         None
     }
     
+    pub fn returned(&self, actions: &Actions) -> Option<String> {
+        if let TextInputAction::Return(val) = actions.find_widget_action_cast(self.widget_uid()) {
+            return Some(val);
+        }
+        None
+    }
 }
@@ -67,6 +67,7 @@ live_design! {
     const THEME_COLOR_HIGH = #C00
     const THEME_COLOR_MID = #FA0
     const THEME_COLOR_LOW = #8A0
+    const THEME_COLOR_FOCUS = #5AF
 
     // RELATIVE =DEFS
     //    42, =78, 117
@@ -687,9 +688,14 @@ live_design! {
     }
 
     NavControl = <NavControlBase> {
+        focus_margin: 2.0
         draw_focus: {
+            uniform color: (THEME_COLOR_FOCUS)
             fn pixel(self) -> vec4 {
-                return #000f
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                sdf.box(1.0, 1.0, self.rect_size.x - 2.0, self.rect_size.y - 2.0, 3.0);
+                sdf.stroke(self.color, 1.5);
+                return sdf.result
             }
         }
         draw_text: {
@@ -2110,6 +2116,102 @@ live_design! {
         }
     }
 
+    TextArea = <TextInput> {
+        multi_line: true,
+        empty_message: "",
+        width: Fill,
+        height: 100,
+    }
+
+    DataGrid = <DataGridBase> {
+        width: Fill,
+        height: Fill,
+        row_height: (THEME_DATA_ITEM_HEIGHT),
+        header_height: 26.0,
+        cell_layout: {
+            align: {y: 0.5}
+            padding: {left: 6.0, right: 6.0}
+        }
+
+        scroll_bar_x: <ScrollBar> {}
+        scroll_bar_y: <ScrollBar> {}
+
+        draw_bg: {color: (THEME_COLOR_BG_EDITOR)}
+
+        draw_cell: {
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                sdf.rect(0., 0., self.rect_size.x, self.rect_size.y);
+                sdf.fill(mix(
+                    mix(
+                        THEME_COLOR_BG_EDITOR,
+                        THEME_COLOR_BG_ODD,
+                        self.is_even
+                    ),
+                    mix(
+                        THEME_COLOR_BG_UNFOCUSSED,
+                        THEME_COLOR_BG_SELECTED,
+                        self.focussed
+                    ),
+                    self.selected
+                ));
+                // grid lines on the right and bottom edge
+                sdf.rect(self.rect_size.x - 1., 0., 1., self.rect_size.y);
+                sdf.rect(0., self.rect_size.y - 1., self.rect_size.x, 1.);
+                sdf.fill(THEME_COLOR_BG_HEADER);
+                sdf.box(1., 1., self.rect_size.x - 3., self.rect_size.y - 3., 1.);
+                sdf.stroke(mix(#0000, THEME_COLOR_TEXT_SELECTED, self.cursor * self.focussed), 1.);
+                return sdf.result;
+            }
+        }
+
+        draw_header: {
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                sdf.rect(0., 0., self.rect_size.x, self.rect_size.y);
+                sdf.fill(mix(THEME_COLOR_BG_HEADER, THEME_COLOR_CONTROL_PRESSED, self.selected));
+                sdf.rect(self.rect_size.x - 1., 3., 1., self.rect_size.y - 6.);
+                sdf.fill(THEME_COLOR_BG_EDITOR);
+                return sdf.result;
+            }
+        }
+
+        draw_text: {
+            color: (THEME_COLOR_TEXT_DEFAULT),
+            text_style: <THEME_FONT_DATA> {}
+        }
+
+        draw_header_text: {
+            color: (THEME_COLOR_TEXT_DEFAULT),
+            text_style: <THEME_FONT_BOLD> {}
+        }
+
+        draw_sort_icon: {
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                let w = self.rect_size.x;
+                let h = self.rect_size.y;
+                let top = mix(0.2 * h, 0.8 * h, self.descending);
+                let bottom = mix(0.8 * h, 0.2 * h, self.descending);
+                sdf.move_to(0., bottom);
+                sdf.line_to(0.5 * w, top);
+                sdf.line_to(w, bottom);
+                sdf.close_path();
+                return sdf.fill(THEME_COLOR_TEXT_DEFAULT);
+            }
+        }
+
+        draw_drop: {color: (THEME_COLOR_DRAG_QUAD)}
+
+        TextEditor = <TextInput> {
+            width: Fill,
+            height: Fill,
+            empty_message: "",
+            padding: {left: 6.0, right: 6.0, top: 0.0, bottom: 0.0},
+            label_align: {y: 0.5},
+        }
+    }
+
     Slider = <SliderBase> {
         min: 0.0,
         max: 1.0,
@@ -2426,4 +2528,74 @@ live_design! {
     }
 
     // StackView DSL end
+
+    Modal = <ModalBase> {
+        width: 400, height: Fit
+        flow: Down
+        padding: 20, spacing: 10
+        show_bg: true
+
+        draw_backdrop: {color: #0008}
+
+        draw_bg: {
+            instance radius: 4.0
+            color: (THEME_COLOR_BG_HEADER)
+
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size)
+                sdf.box(0.0, 0.0, self.rect_size.x, self.rect_size.y, self.radius)
+                sdf.fill(self.color)
+                return sdf.result;
+            }
+        }
+    }
+
+    Tooltip = <TooltipBase> {
+        width: Fit, height: Fit
+        offset: vec2(12.0, 16.0)
+        tip_layout: {padding: {left: 8, top: 5, right: 8, bottom: 5}}
+
+        draw_tip: {
+            instance color: #0
+            instance radius: 3.0
+
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size)
+                sdf.box(0.0, 0.0, self.rect_size.x, self.rect_size.y, self.radius)
+                sdf.fill(self.color)
+                return sdf.result;
+            }
+        }
+
+        draw_text: {
+            color: (THEME_COLOR_TEXT_HOVER)
+            text_style: <THEME_FONT_LABEL> {}
+        }
+    }
+
+    Toast = <ToastBase> {
+        flow: Down
+        align: {x: 1.0, y: 1.0}
+        padding: 20, spacing: 10
+        toast_walk: {width: 300, height: Fit}
+        toast_layout: {padding: 10}
+
+        draw_bg: {
+            instance color: (THEME_COLOR_BG_HEADER)
+            instance radius: 4.0
+
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size)
+                sdf.box(0.0, 0.0, self.rect_size.x, self.rect_size.y, self.radius)
+                sdf.fill(self.color)
+                return sdf.result;
+            }
+        }
+
+        draw_text: {
+            color: (THEME_COLOR_TEXT_HOVER)
+            text_style: <THEME_FONT_LABEL> {}
+            wrap: Word
+        }
+    }
 }
@@ -0,0 +1,153 @@
+use crate::{
+    makepad_derive_widget::*,
+    makepad_draw::*,
+    widget::*,
+};
+
+live_design!{
+    ToastBase = {{Toast}} {}
+}
+
+#[derive(Clone, Debug, DefaultNone)]
+pub enum ToastAction {
+    None,
+    /// A toast was clicked away before it timed out
+    Clicked(LiveId),
+}
+
+struct ToastItem {
+    id: LiveId,
+    text: String,
+    // runs from the moment the toast first shows, not from when it was queued
+    timer: Option<Timer>,
+    area: Area,
+}
+
+/// A queue of notifications stacked in a corner of the window, each goes away after
+/// `duration` seconds or when clicked. At most `max_visible` show at a time
+#[derive(Live, LiveHook, Widget)]
+pub struct Toast {
+    #[walk] walk: Walk,
+    #[live] draw_list: DrawList2d,
+    #[live] draw_bg: DrawQuad,
+    #[live] draw_text: DrawText,
+    // how the stack of toasts sits in the window
+    #[layout] layout: Layout,
+    #[live] toast_walk: Walk,
+    #[live] toast_layout: Layout,
+    #[live(4.0)] duration: f64,
+    #[live(5usize)] max_visible: usize,
+    // where the toasts are anchored in their parent, redrawing it brings the overlay back
+    #[redraw] #[rust] area: Area,
+    #[rust] toasts: Vec<ToastItem>,
+    #[rust] next_id: u64,
+}
+
+impl Toast {
+    /// Queues a notification and returns the id its `ToastAction` will carry
+    pub fn push(&mut self, cx: &mut Cx, text: &str) -> LiveId {
+        self.next_id += 1;
+        let id = LiveId(self.next_id);
+        self.toasts.push(ToastItem {
+            id,
+            text: text.to_string(),
+            timer: None,
+            area: Area::Empty,
+        });
+        self.area.redraw(cx);
+        id
+    }
+
+    pub fn clear(&mut self, cx: &mut Cx) {
+        for toast in self.toasts.drain(..) {
+            if let Some(timer) = toast.timer {
+                cx.stop_timer(timer);
+            }
+        }
+        self.area.redraw(cx);
+        self.draw_list.redraw(cx);
+    }
+
+    fn remove(&mut self, cx: &mut Cx, index: usize) {
+        let toast = self.toasts.remove(index);
+        if let Some(timer) = toast.timer {
+            cx.stop_timer(timer);
+        }
+        self.area.redraw(cx);
+        self.draw_list.redraw(cx);
+    }
+}
+
+impl Widget for Toast {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        let uid = self.widget_uid();
+        if let Some(index) = self.toasts.iter().position( | toast | toast.timer.map_or(false, | timer | timer.is_event(event).is_some())) {
+            self.remove(cx, index);
+        }
+        let mut clicked = None;
+        for (index, toast) in self.toasts.iter().enumerate() {
+            match event.hits(cx, toast.area) {
+                Hit::FingerHoverIn(_) => {
+                    cx.set_cursor(MouseCursor::Hand);
+                }
+                Hit::FingerUp(fe) if fe.is_over => {
+                    clicked = Some(index);
+                }
+                _ => ()
+            }
+        }
+        if let Some(index) = clicked {
+            let id = self.toasts[index].id;
+            self.remove(cx, index);
+            cx.widget_action(uid, &scope.path, ToastAction::Clicked(id));
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, _scope: &mut Scope, _walk: Walk) -> DrawStep {
+        // takes no room in the parent, the toasts live in the overlay
+        cx.walk_turtle_with_area(&mut self.area, Walk::empty().with_abs_pos(DVec2::default()));
+        if self.toasts.is_empty() {
+            return DrawStep::done()
+        }
+        self.draw_list.begin_overlay_layer(cx, OverlayLayer::Toast);
+        cx.begin_pass_sized_turtle(self.layout);
+        // the oldest toasts show first, the rest wait their turn
+        for toast in self.toasts.iter_mut().take(self.max_visible) {
+            if toast.timer.is_none() {
+                toast.timer = Some(cx.start_timeout(self.duration));
+            }
+            self.draw_bg.begin(cx, self.toast_walk, self.toast_layout);
+            self.draw_text.draw_walk(cx, Walk::fit(), Align::default(), &toast.text);
+            self.draw_bg.end(cx);
+            toast.area = self.draw_bg.area();
+        }
+        for toast in self.toasts.iter_mut().skip(self.max_visible) {
+            toast.area = Area::Empty;
+        }
+        cx.end_pass_sized_turtle();
+        self.draw_list.end(cx);
+        DrawStep::done()
+    }
+}
+
+impl ToastRef {
+    pub fn push(&self, cx: &mut Cx, text: &str) -> Option<LiveId> {
+        if let Some(mut inner) = self.borrow_mut() {
+            return Some(inner.push(cx, text))
+        }
+        None
+    }
+
+    pub fn clear(&self, cx: &mut Cx) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.clear(cx);
+        }
+    }
+
+    pub fn clicked(&self, actions: &Actions) -> Option<LiveId> {
+        if let ToastAction::Clicked(id) = actions.find_widget_action_cast(self.widget_uid()) {
+            return Some(id)
+        }
+        None
+    }
+}
@@ -0,0 +1,124 @@
+use crate::{
+    makepad_derive_widget::*,
+    makepad_draw::*,
+    widget::*,
+    view::View,
+};
+
+live_design!{
+    TooltipBase = {{Tooltip}} {}
+}
+
+/// Wraps its content and shows `text` next to the mouse once it rests on it for `delay` seconds
+#[derive(Live, LiveHook, Widget)]
+pub struct Tooltip {
+    #[deref] view: View,
+    #[live] draw_list: DrawList2d,
+    #[live] draw_tip: DrawQuad,
+    #[live] draw_text: DrawText,
+    #[live] tip_layout: Layout,
+    #[live] text: String,
+    #[live(0.5)] delay: f64,
+    #[live] offset: DVec2,
+    #[rust] timer: Timer,
+    #[rust] mouse_pos: Option<DVec2>,
+    #[rust] visible: bool,
+}
+
+impl Tooltip {
+    fn hide(&mut self, cx: &mut Cx) {
+        cx.stop_timer(self.timer);
+        self.mouse_pos = None;
+        if self.visible {
+            self.visible = false;
+            self.view.redraw(cx);
+            self.draw_list.redraw(cx);
+        }
+    }
+
+    fn draw_tip(&mut self, cx: &mut Cx2d, mouse_pos: DVec2) {
+        self.draw_list.begin_overlay_layer(cx, OverlayLayer::Tooltip);
+        cx.begin_pass_sized_turtle(Layout::flow_down());
+        self.draw_tip.begin(cx, Walk::fit().with_abs_pos(mouse_pos + self.offset), self.tip_layout);
+        self.draw_text.draw_walk(cx, Walk::fit(), Align::default(), &self.text);
+        self.draw_tip.end(cx);
+
+        // keep the whole tip inside the window
+        let rect = self.draw_tip.area().rect(cx);
+        let pass_size = cx.current_pass_size();
+        let mut shift = DVec2::default();
+        if rect.pos.x + rect.size.x > pass_size.x {
+            shift.x = (pass_size.x - rect.size.x).max(0.0) - rect.pos.x;
+        }
+        if rect.pos.y + rect.size.y > pass_size.y {
+            // flip above the mouse instead of covering what it points at
+            shift.y = (mouse_pos.y - self.offset.y - rect.size.y).max(0.0) - rect.pos.y;
+        }
+        let range = cx.get_turtle_align_range();
+        cx.shift_align_range(&range, shift);
+
+        cx.end_pass_sized_turtle();
+        self.draw_list.end(cx);
+    }
+}
+
+impl Widget for Tooltip {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        self.view.handle_event(cx, event, scope);
+
+        if self.timer.is_event(event).is_some() && self.mouse_pos.is_some() {
+            self.visible = true;
+            self.view.redraw(cx);
+        }
+        // hovers go to the content, so the tooltip looks at the mouse itself
+        match event {
+            Event::MouseMove(e) => {
+                if self.view.widget_area().clipped_rect(cx).contains(e.abs) {
+                    if !self.visible {
+                        cx.stop_timer(self.timer);
+                        self.timer = cx.start_timeout(self.delay);
+                    }
+                    self.mouse_pos = Some(e.abs);
+                }
+                else if self.mouse_pos.is_some() {
+                    self.hide(cx);
+                }
+            }
+            Event::MouseDown(_) | Event::MouseLeave(_) | Event::Scroll(_) | Event::KeyDown(_) => {
+                if self.mouse_pos.is_some() {
+                    self.hide(cx);
+                }
+            }
+            _ => ()
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        self.view.draw_walk(cx, scope, walk)?;
+        if self.visible && !self.text.is_empty() {
+            if let Some(mouse_pos) = self.mouse_pos {
+                self.draw_tip(cx, mouse_pos);
+            }
+        }
+        DrawStep::done()
+    }
+
+    fn text(&self) -> String {
+        self.text.clone()
+    }
+
+    fn set_text(&mut self, v: &str) {
+        self.text = v.to_string();
+    }
+}
+
+impl TooltipRef {
+    pub fn set_tooltip(&self, cx: &mut Cx, text: &str) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.text = text.to_string();
+            if inner.visible {
+                inner.view.redraw(cx);
+            }
+        }
+    }
+}
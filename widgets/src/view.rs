@@ -1,5 +1,5 @@
 use {
-    crate::{makepad_derive_widget::*, makepad_draw::*, scroll_bars::ScrollBars, widget::*},
+    crate::{makepad_derive_widget::*, makepad_draw::*, scroll_bars::ScrollBars, widget::*, accessibility::AccessCxExt},
     std::collections::hash_map::HashMap,
 };
 
@@ -561,6 +561,10 @@ impl WidgetNode for View {
 }
 
 impl Widget for View {
+    fn widget_area(&self) -> Area {
+        self.area
+    }
+    
     fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
         let uid = self.widget_uid();
         if self.animator_handle_event(cx, event).must_redraw() {
@@ -705,6 +709,7 @@ impl Widget for View {
                                 );
                             }
                         }
+                        cx.keep_access(self.widget_uid());
                         return DrawStep::done();
                     }
                     // lets start a pass
@@ -743,6 +748,7 @@ impl Widget for View {
                         .is_not_redrawing()
                     {
                         cx.walk_turtle_with_area(&mut self.area, walk);
+                        cx.keep_access(self.widget_uid());
                         return DrawStep::done();
                     }
                 }
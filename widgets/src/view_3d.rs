@@ -0,0 +1,359 @@
+use crate::{
+    makepad_derive_widget::*,
+    makepad_draw::*,
+    makepad_draw::mesh::{MeshScene, MeshError},
+    image_cache::ImageBuffer,
+    widget::*
+};
+
+live_design!{
+    View3DBase = {{View3D}} {}
+}
+
+/// A camera orbiting around `target`; angles are in degrees.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OrbitCamera {
+    pub target: Vec3,
+    pub yaw: f32,
+    pub pitch: f32,
+    pub distance: f32,
+}
+
+impl Default for OrbitCamera {
+    fn default() -> Self {
+        Self {
+            target: vec3(0.0, 0.0, 0.0),
+            yaw: 30.0,
+            pitch: 20.0,
+            distance: 5.0,
+        }
+    }
+}
+
+impl OrbitCamera {
+    pub fn eye(&self) -> Vec3 {
+        let (yaw, pitch) = (self.yaw.to_radians(), self.pitch.to_radians());
+        self.target + vec3(pitch.cos() * yaw.sin(), pitch.sin(), pitch.cos() * yaw.cos()) * self.distance
+    }
+
+    pub fn view_matrix(&self) -> Mat4 {
+        Mat4::look_at(self.eye(), self.target, vec3(0.0, 1.0, 0.0))
+    }
+
+    /// Moves the target in the view plane by a distance in screen pixels.
+    fn pan(&mut self, delta: DVec2, viewport_height: f64, fov_y: f32) {
+        let view = self.view_matrix();
+        let right = vec3(view.v[0], view.v[4], view.v[8]);
+        let up = vec3(view.v[1], view.v[5], view.v[9]);
+        // world units per pixel at the target distance
+        let scale = 2.0 * self.distance * (fov_y.to_radians() * 0.5).tan() / viewport_height.max(1.0) as f32;
+        self.target = self.target - right * (delta.x as f32 * scale) + up * (delta.y as f32 * scale);
+    }
+}
+
+struct View3DPass {
+    pass: Pass,
+    draw_list: DrawList2d,
+    color_texture: Texture,
+    depth_texture: Texture,
+}
+
+struct GpuPrimitive {
+    geometry: Geometry,
+    material: Option<usize>,
+}
+
+/// The loaded scene uploaded to the gpu.
+struct GpuScene {
+    scene: MeshScene,
+    meshes: Vec<Vec<GpuPrimitive>>,
+    textures: Vec<Option<Texture>>,
+    radius: f32,
+    center: Vec3,
+}
+
+/// Shows a glTF or OBJ model with an orbit camera: drag to rotate, drag with the right
+/// mouse button (or shift) to pan, scroll or pinch to zoom, and double tap to reset.
+///
+/// The scene is rendered into its own depth-tested pass, and every mesh used by more than
+/// one node is drawn with a single instanced draw call.
+#[derive(Live, Widget)]
+pub struct View3D {
+    #[walk] walk: Walk,
+    #[redraw] #[live] draw_bg: DrawQuad,
+    #[live] draw_mesh: DrawMesh,
+    #[live] source: LiveDependency,
+    #[live] clear_color: Vec4,
+    #[live(45.0)] fov_y: f32,
+    /// Degrees of rotation per pixel dragged.
+    #[live(0.4)] rotate_speed: f32,
+    #[live(true)] interactive: bool,
+    #[rust] camera: OrbitCamera,
+    #[rust] gpu_scene: Option<GpuScene>,
+    #[rust] loaded_source: String,
+    #[rust] render_pass: Option<View3DPass>,
+    #[rust] touches: Vec<(DigitId, DVec2)>,
+    #[rust] last_drag: Option<DVec2>,
+}
+
+#[derive(Clone, Debug, DefaultNone)]
+pub enum View3DAction {
+    CameraChanged(OrbitCamera),
+    None
+}
+
+impl LiveHook for View3D {
+    fn after_apply(&mut self, cx: &mut Cx, _apply: &mut Apply, _index: usize, _nodes: &[LiveNode]) {
+        let source = self.source.clone();
+        if source.as_str().len() > 0 && source.as_str() != self.loaded_source {
+            if let Err(err) = self.load_mesh_dep_by_path(cx, source.as_str()) {
+                error!("View3D: cannot load {}: {}", source.as_str(), err);
+            }
+        }
+    }
+}
+
+impl Widget for View3D {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        if !self.interactive {
+            return
+        }
+        let uid = self.widget_uid();
+        let camera = self.camera;
+        let rect = self.draw_bg.area().rect(cx);
+        match event.hits(cx, self.draw_bg.area()) {
+            Hit::FingerDown(fe) => {
+                if fe.tap_count == 2 {
+                    self.reset_camera();
+                }
+                self.touches.retain( | (id, _) | *id != fe.digit_id);
+                self.touches.push((fe.digit_id, fe.abs));
+                self.last_drag = Some(fe.abs);
+            }
+            Hit::FingerMove(fe) => {
+                if self.touches.len() >= 2 {
+                    // pinch to zoom, move both fingers to pan
+                    let (before_mid, before_dist) = touch_span(&self.touches);
+                    if let Some(touch) = self.touches.iter_mut().find( | (id, _) | *id == fe.digit_id) {
+                        touch.1 = fe.abs;
+                    }
+                    let (after_mid, after_dist) = touch_span(&self.touches);
+                    if after_dist > 1.0 && before_dist > 1.0 {
+                        self.zoom((before_dist / after_dist) as f32);
+                    }
+                    self.camera.pan(after_mid - before_mid, rect.size.y, self.fov_y);
+                }
+                else if let Some(last) = self.last_drag {
+                    let delta = fe.abs - last;
+                    let pan = fe.modifiers.shift || fe.device.mouse_button().map_or(false, | b | b != 0);
+                    if pan {
+                        self.camera.pan(delta, rect.size.y, self.fov_y);
+                    }
+                    else {
+                        self.camera.yaw -= delta.x as f32 * self.rotate_speed;
+                        self.camera.pitch = (self.camera.pitch + delta.y as f32 * self.rotate_speed).clamp(-89.0, 89.0);
+                    }
+                }
+                self.last_drag = Some(fe.abs);
+            }
+            Hit::FingerUp(fe) => {
+                self.touches.retain( | (id, _) | *id != fe.digit_id);
+                self.last_drag = self.touches.last().map( | (_, pos) | *pos);
+            }
+            Hit::FingerScroll(fe) => {
+                self.zoom((1.0 + fe.scroll.y * 0.001).clamp(0.5, 2.0) as f32);
+            }
+            _ => ()
+        }
+        if self.camera != camera {
+            cx.widget_action(uid, &scope.path, View3DAction::CameraChanged(self.camera));
+            self.redraw(cx);
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, _scope: &mut Scope, walk: Walk) -> DrawStep {
+        self.draw_walk(cx, walk)
+    }
+}
+
+fn touch_span(touches: &[(DigitId, DVec2)]) -> (DVec2, f64) {
+    let (a, b) = (touches[0].1, touches[1].1);
+    ((a + b) * 0.5, (a - b).length())
+}
+
+impl View3D {
+    pub fn draw_walk(&mut self, cx: &mut Cx2d, walk: Walk) -> DrawStep {
+        let Some(gpu_scene) = &self.gpu_scene else {
+            self.draw_bg.draw_vars.empty_texture(0);
+            self.draw_bg.draw_walk(cx, walk);
+            return DrawStep::done()
+        };
+        if self.render_pass.is_none() {
+            let render_pass = View3DPass {
+                pass: Pass::new_with_name(cx, "View3D"),
+                draw_list: DrawList2d::new(cx),
+                color_texture: Texture::new_with_format(cx, TextureFormat::RenderBGRAu8 {
+                    size: TextureSize::Auto
+                }),
+                depth_texture: Texture::new_with_format(cx, TextureFormat::DepthD32 {
+                    size: TextureSize::Auto
+                }),
+            };
+            render_pass.pass.set_depth_texture(cx, &render_pass.depth_texture, PassClearDepth::ClearWith(1.0));
+            self.render_pass = Some(render_pass);
+        }
+        let render_pass = self.render_pass.as_mut().unwrap();
+        render_pass.pass.clear_color_textures(cx);
+        render_pass.pass.add_color_texture(cx, &render_pass.color_texture, PassClearColor::ClearWith(self.clear_color));
+
+        let radius = gpu_scene.radius.max(1e-3);
+        render_pass.pass.set_matrix_mode(cx, PassMatrixMode::Projection {
+            fov_y: self.fov_y,
+            near: (self.camera.distance - radius * 2.0).max(self.camera.distance * 0.01).max(1e-3),
+            far: self.camera.distance + radius * 4.0,
+            cam: self.camera.view_matrix(),
+        });
+
+        cx.make_child_pass(&render_pass.pass);
+        cx.begin_pass(&render_pass.pass, None);
+        render_pass.draw_list.begin_always(cx);
+
+        self.draw_mesh.set_camera_pos(cx, self.camera.eye());
+        for instance in &gpu_scene.scene.instances {
+            let Some(primitives) = gpu_scene.meshes.get(instance.mesh) else {continue};
+            self.draw_mesh.set_transform(&instance.transform);
+            for primitive in primitives {
+                let material = primitive.material.and_then( | m | gpu_scene.scene.materials.get(m)).cloned().unwrap_or_default();
+                self.draw_mesh.base_color = material.base_color;
+                self.draw_mesh.metallic = material.metallic;
+                self.draw_mesh.roughness = material.roughness;
+                self.draw_mesh.emissive = material.emissive;
+                let texture = material.base_color_texture.and_then( | t | gpu_scene.textures.get(t)).and_then( | t | t.as_ref());
+                self.draw_mesh.set_texture(texture);
+                self.draw_mesh.draw(cx, &primitive.geometry);
+            }
+        }
+
+        render_pass.draw_list.end(cx);
+        cx.end_pass(&render_pass.pass);
+
+        self.draw_bg.draw_vars.set_texture(0, &render_pass.color_texture);
+        self.draw_bg.draw_walk(cx, walk);
+        cx.set_pass_area(&render_pass.pass, self.draw_bg.area());
+        DrawStep::done()
+    }
+
+    fn zoom(&mut self, factor: f32) {
+        let radius = self.gpu_scene.as_ref().map_or(1.0, | s | s.radius.max(1e-3));
+        self.camera.distance = (self.camera.distance * factor).clamp(radius * 0.05, radius * 50.0);
+    }
+
+    /// Points the camera at the center of the scene, far enough back to see all of it.
+    pub fn reset_camera(&mut self) {
+        let (center, radius) = self.gpu_scene.as_ref().map_or((vec3(0.0, 0.0, 0.0), 1.0), | s | (s.center, s.radius));
+        self.camera = OrbitCamera {
+            target: center,
+            distance: radius.max(1e-3) / (self.fov_y.to_radians() * 0.5).sin() * 1.1,
+            ..OrbitCamera::default()
+        };
+    }
+
+    pub fn camera(&self) -> OrbitCamera {
+        self.camera
+    }
+
+    pub fn set_camera(&mut self, cx: &mut Cx, camera: OrbitCamera) {
+        self.camera = camera;
+        self.redraw(cx);
+    }
+
+    /// Loads a model from a live dependency. Files it references are looked up as
+    /// dependencies next to it, so a `.gltf` with external buffers needs those declared
+    /// as well; `.glb` files and embedded `data:` buffers are self contained.
+    pub fn load_mesh_dep_by_path(&mut self, cx: &mut Cx, path: &str) -> Result<(), MeshError> {
+        let data = cx.get_dependency(path).map_err(MeshError::MissingData)?;
+        let dir = path.rfind('/').map( | i | &path[..i + 1]).unwrap_or("");
+        let scene = MeshScene::from_data(&data, &mut | file | {
+            cx.get_dependency(&format!("{}{}", dir, file)).ok().map( | d | d.to_vec())
+        })?;
+        self.set_scene(cx, scene);
+        self.loaded_source = path.to_string();
+        Ok(())
+    }
+
+    /// Loads a model from `.obj`, `.gltf` or `.glb` data; it must not reference other files.
+    pub fn load_mesh_from_data(&mut self, cx: &mut Cx, data: &[u8]) -> Result<(), MeshError> {
+        let scene = MeshScene::from_data(data, &mut | _ | None)?;
+        self.set_scene(cx, scene);
+        Ok(())
+    }
+
+    /// Uploads `scene` and resets the camera to show all of it.
+    pub fn set_scene(&mut self, cx: &mut Cx, scene: MeshScene) {
+        let meshes = scene.meshes.iter().map( | mesh | {
+            mesh.primitives.iter().map( | primitive | {
+                let geometry = Geometry::new(cx);
+                primitive.geometry.clone().to_geometry(cx, &geometry);
+                GpuPrimitive {geometry, material: primitive.material}
+            }).collect()
+        }).collect();
+        let textures = scene.images.iter().map( | image | {
+            match ImageBuffer::from_data(&image.data) {
+                Ok(buffer) => Some(buffer.into_new_texture(cx)),
+                Err(err) => {
+                    error!("View3D: cannot decode image {}: {}", image.name, err);
+                    None
+                }
+            }
+        }).collect();
+        let (min, max) = scene.bounds().unwrap_or((vec3(-1.0, -1.0, -1.0), vec3(1.0, 1.0, 1.0)));
+        self.gpu_scene = Some(GpuScene {
+            scene,
+            meshes,
+            textures,
+            center: (min + max) * 0.5,
+            radius: (max - min).dot(max - min).sqrt() * 0.5,
+        });
+        self.reset_camera();
+        self.redraw(cx);
+    }
+}
+
+impl View3DRef {
+    pub fn load_mesh_dep_by_path(&self, cx: &mut Cx, path: &str) -> Result<(), MeshError> {
+        if let Some(mut inner) = self.borrow_mut() {
+            return inner.load_mesh_dep_by_path(cx, path)
+        }
+        Ok(())
+    }
+
+    pub fn load_mesh_from_data(&self, cx: &mut Cx, data: &[u8]) -> Result<(), MeshError> {
+        if let Some(mut inner) = self.borrow_mut() {
+            return inner.load_mesh_from_data(cx, data)
+        }
+        Ok(())
+    }
+
+    pub fn set_scene(&self, cx: &mut Cx, scene: MeshScene) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_scene(cx, scene);
+        }
+    }
+
+    pub fn camera(&self) -> Option<OrbitCamera> {
+        self.borrow().map( | inner | inner.camera())
+    }
+
+    pub fn set_camera(&self, cx: &mut Cx, camera: OrbitCamera) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_camera(cx, camera);
+        }
+    }
+
+    pub fn camera_changed(&self, actions: &Actions) -> Option<OrbitCamera> {
+        if let View3DAction::CameraChanged(camera) = actions.find_widget_action(self.widget_uid()).cast() {
+            return Some(camera)
+        }
+        None
+    }
+}
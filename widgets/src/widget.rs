@@ -1,5 +1,8 @@
 use {
-    crate::makepad_draw::*,
+    crate::{
+        makepad_draw::*,
+        accessibility::*,
+    },
     std::fmt::{Formatter, Debug, Error},
     std::collections::BTreeMap,
     std::any::TypeId,
@@ -16,7 +19,7 @@ pub enum WidgetCache {
     Clear
 }
 
-#[derive(Clone, Debug, Copy, PartialEq)]
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Hash)]
 pub struct WidgetUid(pub u64);
 
 pub trait WidgetDesign {
@@ -49,6 +52,11 @@ pub trait Widget: WidgetNode {
     // fn widget_uid(&self)->WidgetUid;
     fn widget_uid(&self) -> WidgetUid {return WidgetUid(self as *const _ as *const () as u64)}
     
+    // the area the widget last drew, used to outline and pick widgets in the designer
+    fn widget_area(&self) -> Area {
+        Area::Empty
+    }
+    
     fn widget_to_data(&self, _cx: &mut Cx, _actions: &Actions, _nodes: &mut LiveNodeVec, _path: &[LiveId]) -> bool {false}
     fn data_to_widget(&mut self, _cx: &mut Cx, _nodes: &[LiveNode], _path: &[LiveId]) {}
     
@@ -82,6 +90,15 @@ pub trait Widget: WidgetNode {
         self.set_text(v);
         self.redraw(cx);
     }
+    
+    // describes the widget to assistive technology, called when it is done drawing
+    fn access_node(&self, _cx: &Cx) -> Option<AccessNode> {
+        None
+    }
+    
+    // a screen reader asking the widget to do something, like clicking it
+    fn handle_access(&mut self, _cx: &mut Cx, _request: &AccessRequest, _scope: &mut Scope) {
+    }
     /*
     fn create_child(
         &mut self,
@@ -374,7 +391,16 @@ impl WidgetRef {
             // if we're in a draw event, do taht here
             if let Event::Draw(e) = event{
                 let cx = &mut Cx2d::new(cx, e);
-                return inner.widget.draw_all(cx, scope);
+                let uid = inner.widget.widget_uid();
+                cx.begin_access(uid);
+                inner.widget.draw_all(cx, scope);
+                let node = inner.widget.access_node(cx);
+                return cx.end_access(uid, node);
+            }
+            if let Event::Signal = event {
+                if let Some(request) = cx.take_access_request(inner.widget.widget_uid()) {
+                    inner.widget.handle_access(cx, &request, scope);
+                }
             }
             return inner.widget.handle_event(cx, event, scope)
         }
@@ -387,6 +413,13 @@ impl WidgetRef {
         WidgetUid(0)
     }
     
+    pub fn widget_area(&self) -> Area {
+        if let Some(inner) = self.0.borrow().as_ref() {
+            return inner.widget.widget_area()
+        }
+        Area::Empty
+    }
+    
     pub fn widget_to_data(&self, cx: &mut Cx, actions: &Actions, nodes: &mut LiveNodeVec, path: &[LiveId]) -> bool {
         if let Some(inner) = self.0.borrow_mut().as_mut() {
             return inner.widget.widget_to_data(cx, actions, nodes, path);
@@ -427,24 +460,32 @@ impl WidgetRef {
     
     pub fn draw_walk(&self, cx: &mut Cx2d, scope:&mut Scope, walk: Walk) -> DrawStep {
         if let Some(inner) = self.0.borrow_mut().as_mut() {
-           if let Some(nd) = inner.widget.draw_walk(cx, scope, walk).step() {
+            let uid = inner.widget.widget_uid();
+            cx.begin_access(uid);
+            if let Some(nd) = inner.widget.draw_walk(cx, scope, walk).step() {
                 if nd.is_empty() {
                     return DrawStep::make_step_here(self.clone())
                 }
                 return DrawStep::make_step_here(nd);
             }
+            let node = inner.widget.access_node(cx);
+            cx.end_access(uid, node);
         }
         DrawStep::done()
     }
     
     pub fn draw(&mut self, cx: &mut Cx2d, scope: &mut Scope) -> DrawStep{
         if let Some(inner) = self.0.borrow_mut().as_mut() {
-        if let Some(nd) = inner.widget.draw(cx, scope).step() {
+            let uid = inner.widget.widget_uid();
+            cx.begin_access(uid);
+            if let Some(nd) = inner.widget.draw(cx, scope).step() {
                 if nd.is_empty() {
                     return DrawStep::make_step_here(self.clone())
                 }
                 return DrawStep::make_step_here(nd);
             }
+            let node = inner.widget.access_node(cx);
+            cx.end_access(uid, node);
         }
         DrawStep::done()
     }
@@ -472,7 +513,11 @@ impl WidgetRef {
     
     pub fn draw_all(&self, cx: &mut Cx2d, scope:&mut Scope) {
         if let Some(inner) = self.0.borrow_mut().as_mut() {
-            return inner.widget.draw_all(cx, scope)
+            let uid = inner.widget.widget_uid();
+            cx.begin_access(uid);
+            inner.widget.draw_all(cx, scope);
+            let node = inner.widget.access_node(cx);
+            cx.end_access(uid, node);
         }
     }
     
@@ -7,6 +7,7 @@ use crate::{
     button::*,
     view::*,
     widget::*,
+    accessibility::*,
 };
 
 live_design!{
@@ -86,6 +87,11 @@ impl LiveHook for Window {
                 //draw_bg: {color: (THEME_COLOR_BG_APP)}  
                 // self.frame.get_view(id!(caption_bar)).set_visible(false);
             }
+            OsType::LinuxWindow(params) if params.custom_window_chrome => {
+                // wayland leaves the decorations to the client, so we use the same ones as on windows
+                self.view(id!(caption_bar)).set_visible(true);
+                self.view(id!(windows_buttons)).set_visible(true);
+            }
             OsType::LinuxWindow(_) |
             OsType::LinuxDirect |
             OsType::Android(_) => {
@@ -114,6 +120,12 @@ pub enum WindowAction {
 }
 
 impl Window {
+    
+    // an open modal keeps tab navigation inside itself
+    fn nav_root(&self, cx: &mut Cx) -> DrawListId {
+        cx.global::<CxOverlayStack>().top(self.overlay.draw_list_id(), OverlayLayer::Modal)
+            .unwrap_or(self.main_draw_list.draw_list_id())
+    }
 
     pub fn begin(&mut self, cx: &mut Cx2d) -> Redrawing {
 
@@ -135,6 +147,8 @@ impl Window {
     pub fn end(&mut self, cx: &mut Cx2d) {
         //while self.frame.draw_widget_continue(cx).is_not_done() {}
         self.debug_view.draw(cx);
+        let nav_root = self.nav_root(cx);
+        self.nav_control.draw(cx, nav_root);
         
         // lets draw our cursor
         if let OsType::LinuxDirect = cx.os_type() {
@@ -185,7 +199,8 @@ impl Widget for Window {
             self.performance_view.handle_widget(cx, event);
         }
         
-        self.nav_control.handle_event(cx, event, self.main_draw_list.draw_list_id());
+        let nav_root = self.nav_root(cx);
+        self.nav_control.handle_event(cx, event, nav_root);
         self.overlay.handle_event(cx, event);
         if self.demo_next_frame.is_event(event).is_some(){
             if self.demo{
@@ -193,6 +208,15 @@ impl Widget for Window {
             }
             cx.repaint_pass_and_child_passes(self.pass.pass_id());
         }
+        // windows we own, like the floating panels of a dock, are drawn and handled by our children
+        if let Some(window_id) = event.window_id() {
+            let own_window_id = self.window.window_id();
+            if window_id != own_window_id && cx.window_is_owned_by(window_id, own_window_id) {
+                self.view.handle_event(cx, event, scope);
+                return
+            }
+        }
+        
         let is_for_other_window = match event {
             Event::WindowCloseRequested(ev) => ev.window_id != self.window.window_id(),
             Event::WindowClosed(ev) => {
@@ -248,6 +272,8 @@ impl Widget for Window {
             Event::MouseMove(ev) => ev.window_id != self.window.window_id(),
             Event::MouseUp(ev) => ev.window_id != self.window.window_id(),
             Event::Scroll(ev) => ev.window_id != self.window.window_id(),
+            Event::Drag(ev) => ev.window_id != self.window.window_id(),
+            Event::Drop(ev) => ev.window_id != self.window.window_id(),
             _ => false
         };
         
@@ -297,9 +323,15 @@ impl Widget for Window {
     }
     
     fn draw_walk(&mut self, cx: &mut Cx2d, scope:&mut Scope, walk: Walk) -> DrawStep {
+        // windows in a MultiWindow aren't drawn through a WidgetRef, so they open their own access node
+        let uid = self.widget_uid();
         if self.draw_state.begin(cx, DrawState::Drawing) {
+            cx.begin_access(uid);
             if self.begin(cx).is_not_redrawing() {
                 self.draw_state.end();
+                cx.keep_access(uid);
+                let node = self.access_node(cx);
+                cx.end_access(uid, node);
                 return DrawStep::done();
             }
         }
@@ -308,8 +340,18 @@ impl Widget for Window {
             self.view.draw_walk(cx, scope, walk)?;
             self.draw_state.end();
             self.end(cx);
+            let node = self.access_node(cx);
+            cx.end_access(uid, node);
         }
         
         DrawStep::done()
     }
+    
+    fn access_node(&self, cx: &Cx) -> Option<AccessNode> {
+        let window = &cx.windows[self.window.window_id()];
+        let geom = &window.window_geom;
+        Some(AccessNode::new(AccessRole::Window)
+            .with_label(&window.create_title)
+            .with_rect(Rect {pos: geom.position, size: geom.inner_size}))
+    }
 }
@@ -0,0 +1,154 @@
+// Draws a small widget tree without a window on screen and checks the accessibility tree
+// that drawing leaves behind, and how it follows the widgets when they change.
+
+use makepad_widgets::*;
+
+live_design!{
+    import makepad_widgets::base::*;
+    import makepad_widgets::theme_desktop_dark::*;
+
+    App = {{App}} {
+        ui: <Window> {
+            window: {title: "Settings", inner_size: vec2(400, 300)}
+            body = <View> {
+                flow: Down
+                title = <Label> {text: "Network"}
+                // a plain view has no node, its children end up with the window
+                row = <View> {
+                    height: Fit
+                    save = <Button> {text: "Save"}
+                    wifi = <CheckBox> {text: "Wifi"}
+                }
+                volume = <Slider> {text: "Volume", min: 2.0, max: 10.0, step: 1.0}
+            }
+        }
+    }
+}
+
+#[derive(Live, LiveHook)]
+pub struct App {
+    #[live] ui: WidgetRef,
+}
+
+impl LiveRegister for App {}
+
+fn new_app() -> (Cx, App) {
+    let mut cx = Cx::new(Box::new( | _, _ | {}));
+    makepad_widgets::live_design(&mut cx);
+    crate::live_design(&mut cx);
+    // expands the live files and loads the fonts, like the platform does on startup
+    cx.init_cx_os();
+    let app = App::new_main(&mut cx);
+    (cx, app)
+}
+
+fn draw(cx: &mut Cx, app: &App) {
+    let draw_event = DrawEvent {redraw_all: true, ..Default::default()};
+    app.ui.handle_event(cx, &Event::Draw(draw_event), &mut Scope::empty());
+}
+
+fn uid(app: &App, path: &[LiveId]) -> WidgetUid {
+    app.ui.widget(path).widget_uid()
+}
+
+#[test]
+fn tree_structure() {
+    let (mut cx, app) = new_app();
+    draw(&mut cx, &app);
+    let tree = cx.access_tree().clone();
+
+    assert_eq!(tree.dump(), "\
+window \"Settings\"
+  label \"Network\"
+  button \"Save\" [focusable]
+  check box \"Wifi\"
+  slider \"Volume\" value=2
+");
+    assert_eq!(tree.roots(), &[app.ui.widget_uid()]);
+    assert_eq!(tree.len(), 5);
+
+    // every node hangs off the window, in drawing order
+    let window = tree.node(app.ui.widget_uid()).unwrap();
+    let children = [id!(title), id!(save), id!(wifi), id!(volume)].map( | path | uid(&app, path));
+    assert_eq!(window.children, children);
+    for child in children {
+        assert_eq!(tree.node(child).unwrap().parent, Some(app.ui.widget_uid()));
+    }
+    // views without a node don't show up
+    assert!(tree.node(uid(&app, id!(body))).is_none());
+    assert!(tree.node(uid(&app, id!(row))).is_none());
+
+    let save = tree.node(uid(&app, id!(save))).unwrap();
+    assert_eq!(save.actions, vec![AccessAction::Click]);
+    assert!(save.rect.size.x > 0.0 && save.rect.size.y > 0.0);
+    let wifi = tree.node(uid(&app, id!(wifi))).unwrap();
+    assert_eq!(wifi.states.checked, Some(false));
+    let volume = tree.node(uid(&app, id!(volume))).unwrap();
+    assert_eq!(volume.value, AccessValue::Number {value: 2.0, min: 2.0, max: 10.0, step: 1.0});
+    assert!(volume.rect.pos.y > save.rect.pos.y);
+
+    assert_eq!(tree.find_by_label(AccessRole::Button, "Save"), Some(uid(&app, id!(save))));
+    assert_eq!(tree.find_by_label(AccessRole::Button, "Cancel"), None);
+}
+
+#[test]
+fn tree_follows_changes() {
+    let (mut cx, app) = new_app();
+    draw(&mut cx, &app);
+    let before = cx.access_tree().clone();
+
+    // redrawing the same widgets changes nothing
+    draw(&mut cx, &app);
+    assert_eq!(cx.access_tree().diff(&before), vec![]);
+
+    let title = uid(&app, id!(title));
+    let save = uid(&app, id!(save));
+    let wifi = uid(&app, id!(wifi));
+    let window = app.ui.widget_uid();
+    app.ui.label(id!(title)).set_text("Wireless");
+    app.ui.view(id!(row)).set_visible(false);
+    draw(&mut cx, &app);
+    let tree = cx.access_tree().clone();
+
+    let changes = tree.diff(&before);
+    for change in [
+        AccessChange::Changed(title),
+        AccessChange::Removed(save),
+        AccessChange::Removed(wifi),
+        AccessChange::Reordered(window),
+    ] {
+        assert!(changes.contains(&change), "{:?} missing from {:?}", change, changes);
+    }
+    assert!(!changes.contains(&AccessChange::Changed(window)), "{:?}", changes);
+    assert_eq!(tree.dump(), "\
+window \"Settings\"
+  label \"Wireless\"
+  slider \"Volume\" value=2
+");
+
+    // showing the row again puts its widgets back where they were
+    app.ui.view(id!(row)).set_visible(true);
+    draw(&mut cx, &app);
+    let changes = cx.access_tree().diff(&tree);
+    assert!(changes.contains(&AccessChange::Added(save)), "{:?}", changes);
+    assert!(changes.contains(&AccessChange::Added(wifi)), "{:?}", changes);
+    assert_eq!(cx.access_tree().node(window).unwrap().children, [title, save, wifi, uid(&app, id!(volume))]);
+}
+
+#[test]
+fn requests_reach_the_widget() {
+    let (mut cx, app) = new_app();
+    draw(&mut cx, &app);
+    let wifi = uid(&app, id!(wifi));
+    let volume = uid(&app, id!(volume));
+
+    cx.access_request(wifi, AccessRequest::Action(AccessAction::Click));
+    cx.access_request(volume, AccessRequest::Action(AccessAction::Increment));
+    app.ui.handle_event(&mut cx, &Event::Signal, &mut Scope::empty());
+    draw(&mut cx, &app);
+
+    let tree = cx.access_tree();
+    assert_eq!(tree.node(wifi).unwrap().states.checked, Some(true));
+    assert_eq!(tree.node(volume).unwrap().value, AccessValue::Number {value: 3.0, min: 2.0, max: 10.0, step: 1.0});
+    assert_eq!(cx.take_access_request(wifi), None);
+}
@@ -0,0 +1,115 @@
+// Plays small APNGs built by hand, with and without the default image in the animation.
+
+use {
+    std::rc::Rc,
+    makepad_widgets::image_animation::ImageAnimation,
+};
+
+const RED: [u8; 4] = [255, 0, 0, 255];
+const GREEN: [u8; 4] = [0, 255, 0, 255];
+const BLUE: [u8; 4] = [0, 0, 255, 255];
+
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = 0xffff_ffffu32;
+    for byte in bytes {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {(crc >> 1) ^ 0xedb8_8320} else {crc >> 1};
+        }
+    }
+    !crc
+}
+
+// a zlib stream with a single stored block holding one 1x1 RGBA scanline
+fn zlib_pixel(pixel: [u8; 4]) -> Vec<u8> {
+    let scanline = [0, pixel[0], pixel[1], pixel[2], pixel[3]];
+    let mut out = vec![0x78, 0x01, 0x01, 5, 0, !5, 0xff];
+    out.extend_from_slice(&scanline);
+    let (mut a, mut b) = (1u32, 0u32);
+    for byte in scanline {
+        a = (a + byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    out.extend_from_slice(&((b << 16) | a).to_be_bytes());
+    out
+}
+
+fn chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend_from_slice(kind);
+    png.extend_from_slice(data);
+    let crc = crc32(&png[start..]);
+    png.extend_from_slice(&crc.to_be_bytes());
+}
+
+fn fctl(seq: u32) -> Vec<u8> {
+    let mut data = Vec::new();
+    for v in [seq, 1, 1, 0, 0] {
+        data.extend_from_slice(&v.to_be_bytes());
+    }
+    // 1/10th of a second, no dispose, source blend
+    data.extend_from_slice(&[0, 1, 0, 10, 0, 0]);
+    data
+}
+
+fn fdat(seq: u32, pixel: [u8; 4]) -> Vec<u8> {
+    let mut data = seq.to_be_bytes().to_vec();
+    data.extend_from_slice(&zlib_pixel(pixel));
+    data
+}
+
+// a 1x1 APNG showing `frames`, starting with `default` as the IDAT image when it isn't one of them
+fn apng(default: Option<[u8; 4]>, frames: &[[u8; 4]]) -> Rc<Vec<u8>> {
+    let mut png = vec![0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a];
+    chunk(&mut png, b"IHDR", &[0, 0, 0, 1, 0, 0, 0, 1, 8, 6, 0, 0, 0]);
+    let mut actl = (frames.len() as u32).to_be_bytes().to_vec();
+    actl.extend_from_slice(&0u32.to_be_bytes());
+    chunk(&mut png, b"acTL", &actl);
+    let mut seq = 0;
+    let mut frames = frames.iter();
+    match default {
+        Some(pixel) => chunk(&mut png, b"IDAT", &zlib_pixel(pixel)),
+        None => {
+            chunk(&mut png, b"fcTL", &fctl(seq));
+            chunk(&mut png, b"IDAT", &zlib_pixel(*frames.next().unwrap()));
+            seq += 1;
+        }
+    }
+    for pixel in frames {
+        chunk(&mut png, b"fcTL", &fctl(seq));
+        chunk(&mut png, b"fdAT", &fdat(seq + 1, *pixel));
+        seq += 2;
+    }
+    chunk(&mut png, b"IEND", &[]);
+    Rc::new(png)
+}
+
+fn argb(pixel: [u8; 4]) -> u32 {
+    u32::from_be_bytes([pixel[3], pixel[0], pixel[1], pixel[2]])
+}
+
+// the canvas at the start and after each of the following `steps` frames
+fn play(animation: &mut ImageAnimation, steps: usize) -> Vec<u32> {
+    let mut shown = vec![animation.canvas()[0]];
+    assert!(!animation.handle_time(0.0));
+    for step in 1..=steps {
+        assert!(animation.handle_time(step as f64 * 0.1 + 0.001));
+        shown.push(animation.canvas()[0]);
+    }
+    shown
+}
+
+#[test]
+fn default_image_in_animation() {
+    let mut animation = ImageAnimation::from_data(apng(None, &[RED, GREEN, BLUE])).unwrap().unwrap();
+    assert_eq!(animation.frame_count(), 3);
+    assert_eq!(play(&mut animation, 4), [RED, GREEN, BLUE, RED, GREEN].map(argb));
+}
+
+#[test]
+fn default_image_not_in_animation() {
+    let mut animation = ImageAnimation::from_data(apng(Some(RED), &[GREEN, BLUE])).unwrap().unwrap();
+    assert_eq!(animation.frame_count(), 2);
+    assert_eq!(play(&mut animation, 4), [GREEN, BLUE, GREEN, BLUE, GREEN].map(argb));
+}
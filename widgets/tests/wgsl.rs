@@ -0,0 +1,151 @@
+// Compiles every draw shader used by the widgets in this crate to WGSL and compares
+// the output with the golden files in tests/wgsl. Run with MAKEPAD_BLESS=1 to update them.
+// Generated names embed live file and node indices, which move with any edit to a
+// live_design, so they are renamed after the module and node path they come from.
+
+use {
+    std::{
+        collections::BTreeMap,
+        path::Path,
+    },
+    makepad_widgets::*,
+    makepad_widgets::makepad_platform::makepad_shader_compiler::generate_wgsl,
+};
+
+fn collect_root_classes(cx: &Cx) -> Vec<(LiveId, LivePtr)> {
+    let live_registry_rc = cx.live_registry.clone();
+    let live_registry = live_registry_rc.borrow();
+    let mut classes = Vec::new();
+    for (file_index, file) in live_registry.live_files.iter().enumerate() {
+        if file.module_id.0 != live_id!(makepad_widgets) {
+            continue
+        }
+        let file_id = LiveFileId::new(file_index);
+        let base_ptr = live_registry.file_id_index_to_live_ptr(file_id, 0);
+        let nodes = &file.expanded.nodes;
+        let mut index = 1;
+        while index < nodes.len().saturating_sub(1) {
+            if let LiveValue::Class {..} = &nodes[index].value {
+                classes.push((nodes[index].id, base_ptr.with_index(index)));
+            }
+            index = nodes.skip_node(index);
+        }
+    }
+    classes
+}
+
+// the module and node path of the live node at `node_index`, like
+// `makepad_draw__shader__std__Sdf2d`, leaving out the node itself
+fn live_node_path(cx: &Cx, file_index: usize, node_index: usize) -> Option<Vec<String>> {
+    let live_registry = cx.live_registry.borrow();
+    let file = live_registry.live_files.get(file_index)?;
+    let nodes = &file.expanded.nodes;
+    nodes.get(node_index)?;
+    let mut path = vec![node_index];
+    while *path.last().unwrap() > 0 {
+        path.push(nodes.parent(*path.last().unwrap())?);
+    }
+    let mut names = vec![file.module_id.to_string().replace("::", "__")];
+    // skip the document root
+    names.extend(path.iter().rev().skip(1).map( | index | nodes[*index].id.to_string()));
+    Some(names)
+}
+
+// renames `fn_<file>_<node>`, `struct_<file>_<node>` and `live_<file>_<node>`
+fn normalize_identifiers(cx: &Cx, wgsl: &str) -> String {
+    let mut out = String::with_capacity(wgsl.len());
+    let mut rest = wgsl;
+    while let Some(start) = rest.find( | c: char | c.is_ascii_alphanumeric() || c == '_') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let end = rest.find( | c: char | !c.is_ascii_alphanumeric() && c != '_').unwrap_or(rest.len());
+        let (ident, tail) = rest.split_at(end);
+        out.push_str(&normalize_identifier(cx, ident));
+        rest = tail;
+    }
+    out.push_str(rest);
+    out
+}
+
+fn normalize_identifier(cx: &Cx, ident: &str) -> String {
+    let Some((prefix, rest)) = ident.split_once('_') else {return ident.to_string()};
+    if !matches!(prefix, "fn" | "struct" | "live") {
+        return ident.to_string()
+    }
+    let mut parts = rest.splitn(3, '_');
+    let (Some(file_index), Some(node_index)) = (parts.next(), parts.next()) else {return ident.to_string()};
+    let (Ok(file_index), Ok(node_index)) = (file_index.parse::<usize>(), node_index.parse::<usize>()) else {
+        return ident.to_string()
+    };
+    let Some(mut names) = live_node_path(cx, file_index, node_index) else {return ident.to_string()};
+    match parts.next() {
+        // functions already end in their own name
+        Some(suffix) => {
+            names.pop();
+            format!("{}_{}_{}", prefix, names.join("__"), suffix)
+        }
+        None => format!("{}_{}", prefix, names.join("__"))
+    }
+}
+
+fn generate_widget_shaders() -> BTreeMap<String, String> {
+    let mut cx = Cx::new(Box::new( | _, _ | {}));
+    makepad_widgets::live_design(&mut cx);
+    cx.live_expand();
+
+    let mut shaders = BTreeMap::new();
+    for (class_id, class_ptr) in collect_root_classes(&cx) {
+        let first_new_shader = cx.draw_shaders.shaders.len();
+        let _widget = WidgetRef::new_from_ptr(&mut cx, Some(class_ptr));
+        for draw_shader_id in first_new_shader..cx.draw_shaders.shaders.len() {
+            let draw_shader_ptr = *cx.draw_shaders.compile_set.iter().find( | ptr | {
+                cx.draw_shaders.ptr_to_item.get(ptr).map(| item | item.draw_shader_id) == Some(draw_shader_id)
+            }).unwrap();
+            let shader = &cx.draw_shaders.shaders[draw_shader_id];
+            let draw_shader_def = cx.shader_registry.draw_shader_defs.get(&draw_shader_ptr).unwrap();
+            let wgsl = generate_wgsl::generate_shader(draw_shader_def, &shader.mapping.const_table, &cx.shader_registry);
+            let wgsl = normalize_identifiers(&cx, &wgsl);
+
+            let mut name = format!("{}.{}", class_id, shader.class_prop);
+            let mut counter = 1;
+            while shaders.contains_key(&name) {
+                counter += 1;
+                name = format!("{}.{}_{}", class_id, shader.class_prop, counter);
+            }
+            shaders.insert(name, wgsl);
+        }
+    }
+    shaders
+}
+
+#[test]
+fn wgsl_golden() {
+    let golden_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/wgsl");
+    let shaders = generate_widget_shaders();
+    assert!(!shaders.is_empty(), "no widget shaders were compiled");
+
+    if std::env::var("MAKEPAD_BLESS").is_ok() {
+        let _ = std::fs::remove_dir_all(&golden_dir);
+        std::fs::create_dir_all(&golden_dir).unwrap();
+        for (name, wgsl) in &shaders {
+            std::fs::write(golden_dir.join(format!("{}.wgsl", name)), wgsl).unwrap();
+        }
+        return
+    }
+
+    let mut failures = Vec::new();
+    for (name, wgsl) in &shaders {
+        match std::fs::read_to_string(golden_dir.join(format!("{}.wgsl", name))) {
+            Ok(golden) if golden == *wgsl => (),
+            Ok(_) => failures.push(format!("{} differs from its golden file", name)),
+            Err(_) => failures.push(format!("{} has no golden file", name)),
+        }
+    }
+    for entry in std::fs::read_dir(&golden_dir).unwrap() {
+        let file_name = entry.unwrap().file_name().into_string().unwrap();
+        if !shaders.contains_key(file_name.trim_end_matches(".wgsl")) {
+            failures.push(format!("{} is no longer generated", file_name));
+        }
+    }
+    assert!(failures.is_empty(), "WGSL output changed, rerun with MAKEPAD_BLESS=1 if intended:\n{}", failures.join("\n"));
+}
@@ -0,0 +1,127 @@
+@group(0) @binding(0) var<uniform> const_table: array<vec4<f32>, 2>;
+@group(0) @binding(1) var<uniform> draw_table: array<vec4<f32>, 1>;
+@group(0) @binding(2) var<uniform> pass_table: array<vec4<f32>, 13>;
+@group(0) @binding(3) var<uniform> user_table: array<vec4<f32>, 1>;
+@group(0) @binding(4) var<uniform> view_table: array<vec4<f32>, 4>;
+@group(0) @binding(5) var ds_image: texture_2d<f32>;
+@group(0) @binding(6) var default_sampler: sampler;
+
+var<private> ds_camera_projection: mat4x4<f32>;
+var<private> ds_camera_view: mat4x4<f32>;
+var<private> ds_camera_inv: mat4x4<f32>;
+var<private> ds_dpi_factor: f32;
+var<private> ds_dpi_dilate: f32;
+var<private> ds_time: f32;
+var<private> ds_view_transform: mat4x4<f32>;
+var<private> ds_draw_zbias: f32;
+var<private> ds_rect_pos: vec2<f32>;
+var<private> ds_rect_size: vec2<f32>;
+var<private> ds_draw_clip: vec4<f32>;
+var<private> ds_draw_depth: f32;
+var<private> ds_0: f32;
+var<private> ds_color: vec4<f32>;
+var<private> ds_geom_pos: vec2<f32>;
+var<private> ds_pos: vec2<f32>;
+var<private> ds_marked: f32;
+var<private> ds_scale: vec2<f32>;
+var<private> ds_shift: vec2<f32>;
+
+fn sample2d_rt(tex: texture_2d<f32>, pos: vec2<f32>) -> vec4<f32> {
+    return textureSampleLevel(tex, default_sampler, pos, 0.0);
+}
+
+fn fn_makepad_widgets__base__CachedView__draw_bg_pixel() -> vec4<f32> {
+    return (sample2d_rt(ds_image, ((ds_pos * ds_scale) + ds_shift)) + vec4<f32>(ds_marked, const_table[0].y, const_table[0].z, const_table[0].w));
+}
+
+fn fn_makepad_widgets__base__CachedView__draw_bg_clip_and_transform_vertex(p_var_rect_pos_0: vec2<f32>, p_var_rect_size_0: vec2<f32>) -> vec4<f32> {
+    var var_rect_pos_0 = p_var_rect_pos_0;
+    var var_rect_size_0 = p_var_rect_size_0;
+    var var_clipped_0: vec2<f32> = clamp(((ds_geom_pos * var_rect_size_0) + var_rect_pos_0), ds_draw_clip.xy, ds_draw_clip.zw);
+    ds_pos = ((var_clipped_0 - var_rect_pos_0) / var_rect_size_0);
+    return (ds_camera_projection * (ds_camera_view * (ds_view_transform * vec4<f32>(var_clipped_0.x, var_clipped_0.y, (ds_draw_depth + ds_draw_zbias), const_table[0].x))));
+}
+
+fn fn_makepad_widgets__base__CachedView__draw_bg_vertex() -> vec4<f32> {
+    var var_dpi_0: f32 = ds_dpi_factor;
+    var var_ceil_size_0: vec2<f32> = (ceil((ds_rect_size * var_dpi_0)) / var_dpi_0);
+    var var_floor_pos_0: vec2<f32> = (floor((ds_rect_pos * var_dpi_0)) / var_dpi_0);
+    ds_scale = (ds_rect_size / var_ceil_size_0);
+    ds_shift = ((ds_rect_pos - var_floor_pos_0) / var_ceil_size_0);
+    return fn_makepad_widgets__base__CachedView__draw_bg_clip_and_transform_vertex (ds_rect_pos, ds_rect_size);
+}
+
+struct VertexInput {
+    @location(0) packed_geometry_0: vec2<f32>,
+    @location(1) packed_instance_0: vec4<f32>,
+    @location(2) packed_instance_1: vec4<f32>,
+    @location(3) packed_instance_2: vec4<f32>,
+    @location(4) packed_instance_3: vec2<f32>,
+}
+
+struct Varyings {
+    @builtin(position) position: vec4<f32>,
+    @location(0) packed_varying_0: vec4<f32>,
+    @location(1) packed_varying_1: vec2<f32>,
+}
+
+@vertex
+fn vertex_main(input: VertexInput) -> Varyings {
+    ds_draw_zbias = draw_table[0].x;
+    ds_camera_projection = mat4x4<f32>(pass_table[0].x, pass_table[0].y, pass_table[0].z, pass_table[0].w, pass_table[1].x, pass_table[1].y, pass_table[1].z, pass_table[1].w, pass_table[2].x, pass_table[2].y, pass_table[2].z, pass_table[2].w, pass_table[3].x, pass_table[3].y, pass_table[3].z, pass_table[3].w);
+    ds_camera_view = mat4x4<f32>(pass_table[4].x, pass_table[4].y, pass_table[4].z, pass_table[4].w, pass_table[5].x, pass_table[5].y, pass_table[5].z, pass_table[5].w, pass_table[6].x, pass_table[6].y, pass_table[6].z, pass_table[6].w, pass_table[7].x, pass_table[7].y, pass_table[7].z, pass_table[7].w);
+    ds_camera_inv = mat4x4<f32>(pass_table[8].x, pass_table[8].y, pass_table[8].z, pass_table[8].w, pass_table[9].x, pass_table[9].y, pass_table[9].z, pass_table[9].w, pass_table[10].x, pass_table[10].y, pass_table[10].z, pass_table[10].w, pass_table[11].x, pass_table[11].y, pass_table[11].z, pass_table[11].w);
+    ds_dpi_factor = pass_table[12].x;
+    ds_dpi_dilate = pass_table[12].y;
+    ds_time = pass_table[12].z;
+    ds_marked = user_table[0].x;
+    ds_view_transform = mat4x4<f32>(view_table[0].x, view_table[0].y, view_table[0].z, view_table[0].w, view_table[1].x, view_table[1].y, view_table[1].z, view_table[1].w, view_table[2].x, view_table[2].y, view_table[2].z, view_table[2].w, view_table[3].x, view_table[3].y, view_table[3].z, view_table[3].w);
+    ds_rect_pos.x = input.packed_instance_0.x;
+    ds_rect_pos.y = input.packed_instance_0.y;
+    ds_rect_size.x = input.packed_instance_0.z;
+    ds_rect_size.y = input.packed_instance_0.w;
+    ds_draw_clip.x = input.packed_instance_1.x;
+    ds_draw_clip.y = input.packed_instance_1.y;
+    ds_draw_clip.z = input.packed_instance_1.z;
+    ds_draw_clip.w = input.packed_instance_1.w;
+    ds_draw_depth = input.packed_instance_2.x;
+    ds_0 = input.packed_instance_2.y;
+    ds_color.x = input.packed_instance_2.z;
+    ds_color.y = input.packed_instance_2.w;
+    ds_color.z = input.packed_instance_3.x;
+    ds_color.w = input.packed_instance_3.y;
+    ds_geom_pos.x = input.packed_geometry_0.x;
+    ds_geom_pos.y = input.packed_geometry_0.y;
+
+    var output: Varyings;
+    output.position = fn_makepad_widgets__base__CachedView__draw_bg_vertex();
+
+    output.packed_varying_0.x = ds_pos.x;
+    output.packed_varying_0.y = ds_pos.y;
+    output.packed_varying_0.z = ds_scale.x;
+    output.packed_varying_0.w = ds_scale.y;
+    output.packed_varying_1.x = ds_shift.x;
+    output.packed_varying_1.y = ds_shift.y;
+    return output;
+}
+
+@fragment
+fn fragment_main(input: Varyings) -> @location(0) vec4<f32> {
+    ds_draw_zbias = draw_table[0].x;
+    ds_camera_projection = mat4x4<f32>(pass_table[0].x, pass_table[0].y, pass_table[0].z, pass_table[0].w, pass_table[1].x, pass_table[1].y, pass_table[1].z, pass_table[1].w, pass_table[2].x, pass_table[2].y, pass_table[2].z, pass_table[2].w, pass_table[3].x, pass_table[3].y, pass_table[3].z, pass_table[3].w);
+    ds_camera_view = mat4x4<f32>(pass_table[4].x, pass_table[4].y, pass_table[4].z, pass_table[4].w, pass_table[5].x, pass_table[5].y, pass_table[5].z, pass_table[5].w, pass_table[6].x, pass_table[6].y, pass_table[6].z, pass_table[6].w, pass_table[7].x, pass_table[7].y, pass_table[7].z, pass_table[7].w);
+    ds_camera_inv = mat4x4<f32>(pass_table[8].x, pass_table[8].y, pass_table[8].z, pass_table[8].w, pass_table[9].x, pass_table[9].y, pass_table[9].z, pass_table[9].w, pass_table[10].x, pass_table[10].y, pass_table[10].z, pass_table[10].w, pass_table[11].x, pass_table[11].y, pass_table[11].z, pass_table[11].w);
+    ds_dpi_factor = pass_table[12].x;
+    ds_dpi_dilate = pass_table[12].y;
+    ds_time = pass_table[12].z;
+    ds_marked = user_table[0].x;
+    ds_view_transform = mat4x4<f32>(view_table[0].x, view_table[0].y, view_table[0].z, view_table[0].w, view_table[1].x, view_table[1].y, view_table[1].z, view_table[1].w, view_table[2].x, view_table[2].y, view_table[2].z, view_table[2].w, view_table[3].x, view_table[3].y, view_table[3].z, view_table[3].w);
+    ds_pos.x = input.packed_varying_0.x;
+    ds_pos.y = input.packed_varying_0.y;
+    ds_scale.x = input.packed_varying_0.z;
+    ds_scale.y = input.packed_varying_0.w;
+    ds_shift.x = input.packed_varying_1.x;
+    ds_shift.y = input.packed_varying_1.y;
+
+    return fn_makepad_widgets__base__CachedView__draw_bg_pixel();
+}
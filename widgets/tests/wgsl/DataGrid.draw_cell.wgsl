@@ -0,0 +1,253 @@
+@group(0) @binding(0) var<uniform> const_table: array<vec4<f32>, 15>;
+@group(0) @binding(1) var<uniform> live_table: array<vec4<f32>, 6>;
+@group(0) @binding(2) var<uniform> draw_table: array<vec4<f32>, 1>;
+@group(0) @binding(3) var<uniform> pass_table: array<vec4<f32>, 13>;
+@group(0) @binding(4) var<uniform> view_table: array<vec4<f32>, 4>;
+
+var<private> live_makepad_widgets__theme_desktop_dark__THEME_COLOR_BG_HEADER: vec4<f32>;
+var<private> live_makepad_widgets__theme_desktop_dark__THEME_COLOR_BG_EDITOR: vec4<f32>;
+var<private> live_makepad_widgets__theme_desktop_dark__THEME_COLOR_BG_ODD: vec4<f32>;
+var<private> live_makepad_widgets__theme_desktop_dark__THEME_COLOR_BG_SELECTED: vec4<f32>;
+var<private> live_makepad_widgets__theme_desktop_dark__THEME_COLOR_BG_UNFOCUSSED: vec4<f32>;
+var<private> live_makepad_widgets__theme_desktop_dark__THEME_COLOR_TEXT_SELECTED: vec4<f32>;
+var<private> ds_camera_projection: mat4x4<f32>;
+var<private> ds_camera_view: mat4x4<f32>;
+var<private> ds_camera_inv: mat4x4<f32>;
+var<private> ds_dpi_factor: f32;
+var<private> ds_dpi_dilate: f32;
+var<private> ds_time: f32;
+var<private> ds_view_transform: mat4x4<f32>;
+var<private> ds_draw_zbias: f32;
+var<private> ds_rect_pos: vec2<f32>;
+var<private> ds_rect_size: vec2<f32>;
+var<private> ds_draw_clip: vec4<f32>;
+var<private> ds_draw_depth: f32;
+var<private> ds_0: f32;
+var<private> ds_is_even: f32;
+var<private> ds_selected: f32;
+var<private> ds_focussed: f32;
+var<private> ds_cursor: f32;
+var<private> ds_geom_pos: vec2<f32>;
+var<private> ds_pos: vec2<f32>;
+
+struct struct_makepad_draw__shader__std__Sdf2d {
+    f_pos: vec2<f32>,
+    f_result: vec4<f32>,
+    f_last_pos: vec2<f32>,
+    f_start_pos: vec2<f32>,
+    f_shape: f32,
+    f_clip: f32,
+    f_has_clip: f32,
+    f_old_shape: f32,
+    f_blur: f32,
+    f_aa: f32,
+    f_scale_factor: f32,
+    f_dist: f32,
+}
+
+fn fn_makepad_draw__shader__std__Sdf2d_rect(var_self_0: ptr<function, struct_makepad_draw__shader__std__Sdf2d>, p_var_x_0: f32, p_var_y_0: f32, p_var_w_0: f32, p_var_h_0: f32) {
+    var var_x_0 = p_var_x_0;
+    var var_y_0 = p_var_y_0;
+    var var_w_0 = p_var_w_0;
+    var var_h_0 = p_var_h_0;
+    var var_s_0: vec2<f32> = (vec2<f32>(var_w_0, var_h_0) * const_table[13].z);
+    var var_d_0: vec2<f32> = (abs(((vec2<f32>(var_x_0, var_y_0) - (*var_self_0).f_pos) + var_s_0)) - var_s_0);
+    var var_dm_0: vec2<f32> = min(var_d_0, vec2<f32>(const_table[13].w, const_table[14].x));
+    (*var_self_0).f_dist = (max(var_dm_0.x, var_dm_0.y) + length(max(var_d_0, vec2<f32>(const_table[14].y, const_table[14].z))));
+    (*var_self_0).f_old_shape = (*var_self_0).f_shape;
+    (*var_self_0).f_shape = min((*var_self_0).f_shape, (*var_self_0).f_dist);
+}
+
+fn fn_makepad_draw__shader__std__Sdf2d_box(var_self_0: ptr<function, struct_makepad_draw__shader__std__Sdf2d>, p_var_x_0: f32, p_var_y_0: f32, p_var_w_0: f32, p_var_h_0: f32, p_var_r_0: f32) {
+    var var_x_0 = p_var_x_0;
+    var var_y_0 = p_var_y_0;
+    var var_w_0 = p_var_w_0;
+    var var_h_0 = p_var_h_0;
+    var var_r_0 = p_var_r_0;
+    var var_p_0: vec2<f32> = ((*var_self_0).f_pos - vec2<f32>(var_x_0, var_y_0));
+    var var_size_0: vec2<f32> = vec2<f32>((const_table[11].w * var_w_0), (const_table[12].x * var_h_0));
+    var var_bp_0: vec2<f32> = max((abs((var_p_0 - var_size_0.xy)) - (var_size_0.xy - vec2<f32>((const_table[12].y * var_r_0), (const_table[12].z * var_r_0)).xy)), vec2<f32>(const_table[12].w, const_table[13].x));
+    (*var_self_0).f_dist = ((length(var_bp_0) - (const_table[13].y * var_r_0)) / (*var_self_0).f_scale_factor);
+    (*var_self_0).f_old_shape = (*var_self_0).f_shape;
+    (*var_self_0).f_shape = min((*var_self_0).f_shape, (*var_self_0).f_dist);
+}
+
+fn fn_makepad_draw__shader__std__Sdf2d_calc_blur(var_self_0: ptr<function, struct_makepad_draw__shader__std__Sdf2d>, p_var_w_0: f32) -> f32 {
+    var var_w_0 = p_var_w_0;
+    var var_wa_0: f32 = clamp((-var_w_0 * (*var_self_0).f_aa), const_table[10].y, const_table[10].z);
+    var var_wb_0: f32 = const_table[10].w;
+    if((*var_self_0).f_blur > const_table[11].x) {
+            var_wb_0 = clamp((-var_w_0 / (*var_self_0).f_blur), const_table[11].y, const_table[11].z);
+    }
+
+    return (var_wa_0 * var_wb_0);
+}
+
+fn fn_makepad_draw__shader__std__Sdf2d_stroke_keep(var_self_0: ptr<function, struct_makepad_draw__shader__std__Sdf2d>, p_var_color_0: vec4<f32>, p_var_width_0: f32) -> vec4<f32> {
+    var var_color_0 = p_var_color_0;
+    var var_width_0 = p_var_width_0;
+    var var_f_0: f32 = fn_makepad_draw__shader__std__Sdf2d_calc_blur (&(*var_self_0), (abs((*var_self_0).f_shape) - (var_width_0 / (*var_self_0).f_scale_factor)));
+    var var_source_0: vec4<f32> = vec4<f32>((var_color_0.rgb * var_color_0.a), var_color_0.a);
+    var var_dest_0: vec4<f32> = (*var_self_0).f_result;
+    (*var_self_0).f_result = ((var_source_0 * var_f_0) + (var_dest_0 * (const_table[10].x - (var_source_0.a * var_f_0))));
+    return (*var_self_0).f_result;
+}
+
+fn fn_makepad_draw__shader__std__Sdf2d_stroke(var_self_0: ptr<function, struct_makepad_draw__shader__std__Sdf2d>, p_var_color_0: vec4<f32>, p_var_width_0: f32) -> vec4<f32> {
+    var var_color_0 = p_var_color_0;
+    var var_width_0 = p_var_width_0;
+    fn_makepad_draw__shader__std__Sdf2d_stroke_keep (&(*var_self_0), var_color_0, var_width_0);
+    (*var_self_0).f_shape = const_table[9].y;    (*var_self_0).f_old_shape = (*var_self_0).f_shape;
+    (*var_self_0).f_clip = -const_table[9].z;
+    (*var_self_0).f_has_clip = const_table[9].w;
+    return (*var_self_0).f_result;
+}
+
+fn fn_makepad_draw__shader__std__Sdf2d_fill_keep_premul(var_self_0: ptr<function, struct_makepad_draw__shader__std__Sdf2d>, p_var_source_0: vec4<f32>) -> vec4<f32> {
+    var var_source_0 = p_var_source_0;
+    var var_f_0: f32 = fn_makepad_draw__shader__std__Sdf2d_calc_blur (&(*var_self_0), (*var_self_0).f_shape);
+    (*var_self_0).f_result = ((var_source_0 * var_f_0) + ((*var_self_0).f_result * (const_table[8].y - (var_source_0.a * var_f_0))));
+    if((*var_self_0).f_has_clip > const_table[8].z) {
+            var var_f2_0: f32 = (const_table[8].w - fn_makepad_draw__shader__std__Sdf2d_calc_blur (&(*var_self_0), -(*var_self_0).f_clip));
+        (*var_self_0).f_result = ((var_source_0 * var_f2_0) + ((*var_self_0).f_result * (const_table[9].x - (var_source_0.a * var_f2_0))));
+    }
+
+    return (*var_self_0).f_result;
+}
+
+fn fn_makepad_draw__shader__std__Sdf2d_fill_premul(var_self_0: ptr<function, struct_makepad_draw__shader__std__Sdf2d>, p_var_color_0: vec4<f32>) -> vec4<f32> {
+    var var_color_0 = p_var_color_0;
+    fn_makepad_draw__shader__std__Sdf2d_fill_keep_premul (&(*var_self_0), var_color_0);
+    (*var_self_0).f_shape = const_table[7].z;    (*var_self_0).f_old_shape = (*var_self_0).f_shape;
+    (*var_self_0).f_clip = -const_table[7].w;
+    (*var_self_0).f_has_clip = const_table[8].x;
+    return (*var_self_0).f_result;
+}
+
+fn fn_makepad_draw__shader__std__Sdf2d_fill(var_self_0: ptr<function, struct_makepad_draw__shader__std__Sdf2d>, p_var_color_0: vec4<f32>) -> vec4<f32> {
+    var var_color_0 = p_var_color_0;
+    return fn_makepad_draw__shader__std__Sdf2d_fill_premul (&(*var_self_0), vec4<f32>((var_color_0.rgb * var_color_0.a), var_color_0.a));
+}
+
+fn fn_makepad_draw__shader__std__Sdf2d_antialias(p_var_p_0: vec2<f32>) -> f32 {
+    var var_p_0 = p_var_p_0;
+    return (const_table[7].y / length(vec2<f32>(length(dpdx(var_p_0)), length(dpdy(var_p_0)))));
+}
+
+fn fn_makepad_draw__shader__std__Sdf2d_viewport(p_var_pos_0: vec2<f32>) -> struct_makepad_draw__shader__std__Sdf2d {
+    var var_pos_0 = p_var_pos_0;
+    return struct_makepad_draw__shader__std__Sdf2d(var_pos_0,vec4<f32>(const_table[4].w),vec2<f32>(const_table[5].x),vec2<f32>(const_table[5].y),const_table[5].z,-const_table[5].w,const_table[6].x,const_table[6].y,const_table[6].z,fn_makepad_draw__shader__std__Sdf2d_antialias (var_pos_0),const_table[6].w,const_table[7].x);
+}
+
+fn fn_makepad_widgets__theme_desktop_dark__DataGrid__draw_cell_pixel() -> vec4<f32> {
+    var var_sdf_0: struct_makepad_draw__shader__std__Sdf2d = fn_makepad_draw__shader__std__Sdf2d_viewport ((ds_pos * ds_rect_size));
+    fn_makepad_draw__shader__std__Sdf2d_rect (&var_sdf_0, const_table[0].y, const_table[0].z, ds_rect_size.x, ds_rect_size.y);
+    fn_makepad_draw__shader__std__Sdf2d_fill (&var_sdf_0, mix(mix(live_makepad_widgets__theme_desktop_dark__THEME_COLOR_BG_EDITOR, live_makepad_widgets__theme_desktop_dark__THEME_COLOR_BG_ODD, ds_is_even), mix(live_makepad_widgets__theme_desktop_dark__THEME_COLOR_BG_UNFOCUSSED, live_makepad_widgets__theme_desktop_dark__THEME_COLOR_BG_SELECTED, ds_focussed), ds_selected));
+    fn_makepad_draw__shader__std__Sdf2d_rect (&var_sdf_0, (ds_rect_size.x - const_table[0].w), const_table[1].x, const_table[1].y, ds_rect_size.y);
+    fn_makepad_draw__shader__std__Sdf2d_rect (&var_sdf_0, const_table[1].z, (ds_rect_size.y - const_table[1].w), ds_rect_size.x, const_table[2].x);
+    fn_makepad_draw__shader__std__Sdf2d_fill (&var_sdf_0, live_makepad_widgets__theme_desktop_dark__THEME_COLOR_BG_HEADER);
+    fn_makepad_draw__shader__std__Sdf2d_box (&var_sdf_0, const_table[2].y, const_table[2].z, (ds_rect_size.x - const_table[2].w), (ds_rect_size.y - const_table[3].x), const_table[3].y);
+    fn_makepad_draw__shader__std__Sdf2d_stroke (&var_sdf_0, mix(vec4<f32>(const_table[3].z, const_table[3].w, const_table[4].x, const_table[4].y), live_makepad_widgets__theme_desktop_dark__THEME_COLOR_TEXT_SELECTED, (ds_cursor * ds_focussed)), const_table[4].z);
+    return var_sdf_0.f_result;
+}
+
+fn fn_makepad_widgets__theme_desktop_dark__DataGrid__draw_cell_clip_and_transform_vertex(p_var_rect_pos_0: vec2<f32>, p_var_rect_size_0: vec2<f32>) -> vec4<f32> {
+    var var_rect_pos_0 = p_var_rect_pos_0;
+    var var_rect_size_0 = p_var_rect_size_0;
+    var var_clipped_0: vec2<f32> = clamp(((ds_geom_pos * var_rect_size_0) + var_rect_pos_0), ds_draw_clip.xy, ds_draw_clip.zw);
+    ds_pos = ((var_clipped_0 - var_rect_pos_0) / var_rect_size_0);
+    return (ds_camera_projection * (ds_camera_view * (ds_view_transform * vec4<f32>(var_clipped_0.x, var_clipped_0.y, (ds_draw_depth + ds_draw_zbias), const_table[0].x))));
+}
+
+fn fn_makepad_widgets__theme_desktop_dark__DataGrid__draw_cell_vertex() -> vec4<f32> {
+    return fn_makepad_widgets__theme_desktop_dark__DataGrid__draw_cell_clip_and_transform_vertex (ds_rect_pos, ds_rect_size);
+}
+
+struct VertexInput {
+    @location(0) packed_geometry_0: vec2<f32>,
+    @location(1) packed_instance_0: vec4<f32>,
+    @location(2) packed_instance_1: vec4<f32>,
+    @location(3) packed_instance_2: vec4<f32>,
+    @location(4) packed_instance_3: vec2<f32>,
+}
+
+struct Varyings {
+    @builtin(position) position: vec4<f32>,
+    @location(0) packed_varying_0: vec4<f32>,
+    @location(1) packed_varying_1: vec4<f32>,
+}
+
+@vertex
+fn vertex_main(input: VertexInput) -> Varyings {
+    ds_draw_zbias = draw_table[0].x;
+    ds_camera_projection = mat4x4<f32>(pass_table[0].x, pass_table[0].y, pass_table[0].z, pass_table[0].w, pass_table[1].x, pass_table[1].y, pass_table[1].z, pass_table[1].w, pass_table[2].x, pass_table[2].y, pass_table[2].z, pass_table[2].w, pass_table[3].x, pass_table[3].y, pass_table[3].z, pass_table[3].w);
+    ds_camera_view = mat4x4<f32>(pass_table[4].x, pass_table[4].y, pass_table[4].z, pass_table[4].w, pass_table[5].x, pass_table[5].y, pass_table[5].z, pass_table[5].w, pass_table[6].x, pass_table[6].y, pass_table[6].z, pass_table[6].w, pass_table[7].x, pass_table[7].y, pass_table[7].z, pass_table[7].w);
+    ds_camera_inv = mat4x4<f32>(pass_table[8].x, pass_table[8].y, pass_table[8].z, pass_table[8].w, pass_table[9].x, pass_table[9].y, pass_table[9].z, pass_table[9].w, pass_table[10].x, pass_table[10].y, pass_table[10].z, pass_table[10].w, pass_table[11].x, pass_table[11].y, pass_table[11].z, pass_table[11].w);
+    ds_dpi_factor = pass_table[12].x;
+    ds_dpi_dilate = pass_table[12].y;
+    ds_time = pass_table[12].z;
+    ds_view_transform = mat4x4<f32>(view_table[0].x, view_table[0].y, view_table[0].z, view_table[0].w, view_table[1].x, view_table[1].y, view_table[1].z, view_table[1].w, view_table[2].x, view_table[2].y, view_table[2].z, view_table[2].w, view_table[3].x, view_table[3].y, view_table[3].z, view_table[3].w);
+    live_makepad_widgets__theme_desktop_dark__THEME_COLOR_BG_HEADER = vec4<f32>(live_table[0].x, live_table[0].y, live_table[0].z, live_table[0].w);
+    live_makepad_widgets__theme_desktop_dark__THEME_COLOR_BG_EDITOR = vec4<f32>(live_table[1].x, live_table[1].y, live_table[1].z, live_table[1].w);
+    live_makepad_widgets__theme_desktop_dark__THEME_COLOR_BG_ODD = vec4<f32>(live_table[2].x, live_table[2].y, live_table[2].z, live_table[2].w);
+    live_makepad_widgets__theme_desktop_dark__THEME_COLOR_BG_SELECTED = vec4<f32>(live_table[3].x, live_table[3].y, live_table[3].z, live_table[3].w);
+    live_makepad_widgets__theme_desktop_dark__THEME_COLOR_BG_UNFOCUSSED = vec4<f32>(live_table[4].x, live_table[4].y, live_table[4].z, live_table[4].w);
+    live_makepad_widgets__theme_desktop_dark__THEME_COLOR_TEXT_SELECTED = vec4<f32>(live_table[5].x, live_table[5].y, live_table[5].z, live_table[5].w);
+    ds_rect_pos.x = input.packed_instance_0.x;
+    ds_rect_pos.y = input.packed_instance_0.y;
+    ds_rect_size.x = input.packed_instance_0.z;
+    ds_rect_size.y = input.packed_instance_0.w;
+    ds_draw_clip.x = input.packed_instance_1.x;
+    ds_draw_clip.y = input.packed_instance_1.y;
+    ds_draw_clip.z = input.packed_instance_1.z;
+    ds_draw_clip.w = input.packed_instance_1.w;
+    ds_draw_depth = input.packed_instance_2.x;
+    ds_0 = input.packed_instance_2.y;
+    ds_is_even = input.packed_instance_2.z;
+    ds_selected = input.packed_instance_2.w;
+    ds_focussed = input.packed_instance_3.x;
+    ds_cursor = input.packed_instance_3.y;
+    ds_geom_pos.x = input.packed_geometry_0.x;
+    ds_geom_pos.y = input.packed_geometry_0.y;
+
+    var output: Varyings;
+    output.position = fn_makepad_widgets__theme_desktop_dark__DataGrid__draw_cell_vertex();
+
+    output.packed_varying_0.x = ds_rect_size.x;
+    output.packed_varying_0.y = ds_rect_size.y;
+    output.packed_varying_0.z = ds_is_even;
+    output.packed_varying_0.w = ds_selected;
+    output.packed_varying_1.x = ds_focussed;
+    output.packed_varying_1.y = ds_cursor;
+    output.packed_varying_1.z = ds_pos.x;
+    output.packed_varying_1.w = ds_pos.y;
+    return output;
+}
+
+@fragment
+fn fragment_main(input: Varyings) -> @location(0) vec4<f32> {
+    ds_draw_zbias = draw_table[0].x;
+    ds_camera_projection = mat4x4<f32>(pass_table[0].x, pass_table[0].y, pass_table[0].z, pass_table[0].w, pass_table[1].x, pass_table[1].y, pass_table[1].z, pass_table[1].w, pass_table[2].x, pass_table[2].y, pass_table[2].z, pass_table[2].w, pass_table[3].x, pass_table[3].y, pass_table[3].z, pass_table[3].w);
+    ds_camera_view = mat4x4<f32>(pass_table[4].x, pass_table[4].y, pass_table[4].z, pass_table[4].w, pass_table[5].x, pass_table[5].y, pass_table[5].z, pass_table[5].w, pass_table[6].x, pass_table[6].y, pass_table[6].z, pass_table[6].w, pass_table[7].x, pass_table[7].y, pass_table[7].z, pass_table[7].w);
+    ds_camera_inv = mat4x4<f32>(pass_table[8].x, pass_table[8].y, pass_table[8].z, pass_table[8].w, pass_table[9].x, pass_table[9].y, pass_table[9].z, pass_table[9].w, pass_table[10].x, pass_table[10].y, pass_table[10].z, pass_table[10].w, pass_table[11].x, pass_table[11].y, pass_table[11].z, pass_table[11].w);
+    ds_dpi_factor = pass_table[12].x;
+    ds_dpi_dilate = pass_table[12].y;
+    ds_time = pass_table[12].z;
+    ds_view_transform = mat4x4<f32>(view_table[0].x, view_table[0].y, view_table[0].z, view_table[0].w, view_table[1].x, view_table[1].y, view_table[1].z, view_table[1].w, view_table[2].x, view_table[2].y, view_table[2].z, view_table[2].w, view_table[3].x, view_table[3].y, view_table[3].z, view_table[3].w);
+    live_makepad_widgets__theme_desktop_dark__THEME_COLOR_BG_HEADER = vec4<f32>(live_table[0].x, live_table[0].y, live_table[0].z, live_table[0].w);
+    live_makepad_widgets__theme_desktop_dark__THEME_COLOR_BG_EDITOR = vec4<f32>(live_table[1].x, live_table[1].y, live_table[1].z, live_table[1].w);
+    live_makepad_widgets__theme_desktop_dark__THEME_COLOR_BG_ODD = vec4<f32>(live_table[2].x, live_table[2].y, live_table[2].z, live_table[2].w);
+    live_makepad_widgets__theme_desktop_dark__THEME_COLOR_BG_SELECTED = vec4<f32>(live_table[3].x, live_table[3].y, live_table[3].z, live_table[3].w);
+    live_makepad_widgets__theme_desktop_dark__THEME_COLOR_BG_UNFOCUSSED = vec4<f32>(live_table[4].x, live_table[4].y, live_table[4].z, live_table[4].w);
+    live_makepad_widgets__theme_desktop_dark__THEME_COLOR_TEXT_SELECTED = vec4<f32>(live_table[5].x, live_table[5].y, live_table[5].z, live_table[5].w);
+    ds_rect_size.x = input.packed_varying_0.x;
+    ds_rect_size.y = input.packed_varying_0.y;
+    ds_is_even = input.packed_varying_0.z;
+    ds_selected = input.packed_varying_0.w;
+    ds_focussed = input.packed_varying_1.x;
+    ds_cursor = input.packed_varying_1.y;
+    ds_pos.x = input.packed_varying_1.z;
+    ds_pos.y = input.packed_varying_1.w;
+
+    return fn_makepad_widgets__theme_desktop_dark__DataGrid__draw_cell_pixel();
+}
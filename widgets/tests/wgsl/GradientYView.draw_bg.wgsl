@@ -0,0 +1,144 @@
+@group(0) @binding(0) var<uniform> const_table: array<vec4<f32>, 2>;
+@group(0) @binding(1) var<uniform> draw_table: array<vec4<f32>, 1>;
+@group(0) @binding(2) var<uniform> pass_table: array<vec4<f32>, 13>;
+@group(0) @binding(3) var<uniform> view_table: array<vec4<f32>, 4>;
+
+var<private> ds_camera_projection: mat4x4<f32>;
+var<private> ds_camera_view: mat4x4<f32>;
+var<private> ds_camera_inv: mat4x4<f32>;
+var<private> ds_dpi_factor: f32;
+var<private> ds_dpi_dilate: f32;
+var<private> ds_time: f32;
+var<private> ds_view_transform: mat4x4<f32>;
+var<private> ds_draw_zbias: f32;
+var<private> ds_color2: vec4<f32>;
+var<private> ds_dither: f32;
+var<private> ds_rect_pos: vec2<f32>;
+var<private> ds_rect_size: vec2<f32>;
+var<private> ds_draw_clip: vec4<f32>;
+var<private> ds_draw_depth: f32;
+var<private> ds_0: f32;
+var<private> ds_color: vec4<f32>;
+var<private> ds_geom_pos: vec2<f32>;
+var<private> ds_pos: vec2<f32>;
+
+fn fn_makepad_draw__shader__std__Math_random_2d(p_var_v_0: vec2<f32>) -> f32 {
+    var var_v_0 = p_var_v_0;
+    return fract((sin(dot(var_v_0.xy, vec2<f32>(const_table[0].z, const_table[0].w))) * const_table[1].x));
+}
+
+fn fn_makepad_widgets__base__GradientYView__draw_bg_get_color() -> vec4<f32> {
+    var var_dither_0: f32 = ((fn_makepad_draw__shader__std__Math_random_2d (ds_pos.xy) * const_table[0].y) * ds_dither);
+    return mix(ds_color, ds_color2, (ds_pos.y + var_dither_0));
+}
+
+fn fn_makepad_draw__shader__std__Pal_premul(p_var_v_0: vec4<f32>) -> vec4<f32> {
+    var var_v_0 = p_var_v_0;
+    return vec4<f32>((var_v_0.x * var_v_0.w), (var_v_0.y * var_v_0.w), (var_v_0.z * var_v_0.w), var_v_0.w);
+}
+
+fn fn_makepad_widgets__base__GradientYView__draw_bg_pixel() -> vec4<f32> {
+    return fn_makepad_draw__shader__std__Pal_premul (fn_makepad_widgets__base__GradientYView__draw_bg_get_color ());
+}
+
+fn fn_makepad_widgets__base__GradientYView__draw_bg_clip_and_transform_vertex(p_var_rect_pos_0: vec2<f32>, p_var_rect_size_0: vec2<f32>) -> vec4<f32> {
+    var var_rect_pos_0 = p_var_rect_pos_0;
+    var var_rect_size_0 = p_var_rect_size_0;
+    var var_clipped_0: vec2<f32> = clamp(((ds_geom_pos * var_rect_size_0) + var_rect_pos_0), ds_draw_clip.xy, ds_draw_clip.zw);
+    ds_pos = ((var_clipped_0 - var_rect_pos_0) / var_rect_size_0);
+    return (ds_camera_projection * (ds_camera_view * (ds_view_transform * vec4<f32>(var_clipped_0.x, var_clipped_0.y, (ds_draw_depth + ds_draw_zbias), const_table[0].x))));
+}
+
+fn fn_makepad_widgets__base__GradientYView__draw_bg_vertex() -> vec4<f32> {
+    return fn_makepad_widgets__base__GradientYView__draw_bg_clip_and_transform_vertex (ds_rect_pos, ds_rect_size);
+}
+
+struct VertexInput {
+    @location(0) packed_geometry_0: vec2<f32>,
+    @location(1) packed_instance_0: vec4<f32>,
+    @location(2) packed_instance_1: vec4<f32>,
+    @location(3) packed_instance_2: vec4<f32>,
+    @location(4) packed_instance_3: vec4<f32>,
+    @location(5) packed_instance_4: vec3<f32>,
+}
+
+struct Varyings {
+    @builtin(position) position: vec4<f32>,
+    @location(0) packed_varying_0: vec4<f32>,
+    @location(1) packed_varying_1: vec4<f32>,
+    @location(2) packed_varying_2: vec3<f32>,
+}
+
+@vertex
+fn vertex_main(input: VertexInput) -> Varyings {
+    ds_draw_zbias = draw_table[0].x;
+    ds_camera_projection = mat4x4<f32>(pass_table[0].x, pass_table[0].y, pass_table[0].z, pass_table[0].w, pass_table[1].x, pass_table[1].y, pass_table[1].z, pass_table[1].w, pass_table[2].x, pass_table[2].y, pass_table[2].z, pass_table[2].w, pass_table[3].x, pass_table[3].y, pass_table[3].z, pass_table[3].w);
+    ds_camera_view = mat4x4<f32>(pass_table[4].x, pass_table[4].y, pass_table[4].z, pass_table[4].w, pass_table[5].x, pass_table[5].y, pass_table[5].z, pass_table[5].w, pass_table[6].x, pass_table[6].y, pass_table[6].z, pass_table[6].w, pass_table[7].x, pass_table[7].y, pass_table[7].z, pass_table[7].w);
+    ds_camera_inv = mat4x4<f32>(pass_table[8].x, pass_table[8].y, pass_table[8].z, pass_table[8].w, pass_table[9].x, pass_table[9].y, pass_table[9].z, pass_table[9].w, pass_table[10].x, pass_table[10].y, pass_table[10].z, pass_table[10].w, pass_table[11].x, pass_table[11].y, pass_table[11].z, pass_table[11].w);
+    ds_dpi_factor = pass_table[12].x;
+    ds_dpi_dilate = pass_table[12].y;
+    ds_time = pass_table[12].z;
+    ds_view_transform = mat4x4<f32>(view_table[0].x, view_table[0].y, view_table[0].z, view_table[0].w, view_table[1].x, view_table[1].y, view_table[1].z, view_table[1].w, view_table[2].x, view_table[2].y, view_table[2].z, view_table[2].w, view_table[3].x, view_table[3].y, view_table[3].z, view_table[3].w);
+    ds_color2.x = input.packed_instance_0.x;
+    ds_color2.y = input.packed_instance_0.y;
+    ds_color2.z = input.packed_instance_0.z;
+    ds_color2.w = input.packed_instance_0.w;
+    ds_dither = input.packed_instance_1.x;
+    ds_rect_pos.x = input.packed_instance_1.y;
+    ds_rect_pos.y = input.packed_instance_1.z;
+    ds_rect_size.x = input.packed_instance_1.w;
+    ds_rect_size.y = input.packed_instance_2.x;
+    ds_draw_clip.x = input.packed_instance_2.y;
+    ds_draw_clip.y = input.packed_instance_2.z;
+    ds_draw_clip.z = input.packed_instance_2.w;
+    ds_draw_clip.w = input.packed_instance_3.x;
+    ds_draw_depth = input.packed_instance_3.y;
+    ds_0 = input.packed_instance_3.z;
+    ds_color.x = input.packed_instance_3.w;
+    ds_color.y = input.packed_instance_4.x;
+    ds_color.z = input.packed_instance_4.y;
+    ds_color.w = input.packed_instance_4.z;
+    ds_geom_pos.x = input.packed_geometry_0.x;
+    ds_geom_pos.y = input.packed_geometry_0.y;
+
+    var output: Varyings;
+    output.position = fn_makepad_widgets__base__GradientYView__draw_bg_vertex();
+
+    output.packed_varying_0.x = ds_color2.x;
+    output.packed_varying_0.y = ds_color2.y;
+    output.packed_varying_0.z = ds_color2.z;
+    output.packed_varying_0.w = ds_color2.w;
+    output.packed_varying_1.x = ds_dither;
+    output.packed_varying_1.y = ds_color.x;
+    output.packed_varying_1.z = ds_color.y;
+    output.packed_varying_1.w = ds_color.z;
+    output.packed_varying_2.x = ds_color.w;
+    output.packed_varying_2.y = ds_pos.x;
+    output.packed_varying_2.z = ds_pos.y;
+    return output;
+}
+
+@fragment
+fn fragment_main(input: Varyings) -> @location(0) vec4<f32> {
+    ds_draw_zbias = draw_table[0].x;
+    ds_camera_projection = mat4x4<f32>(pass_table[0].x, pass_table[0].y, pass_table[0].z, pass_table[0].w, pass_table[1].x, pass_table[1].y, pass_table[1].z, pass_table[1].w, pass_table[2].x, pass_table[2].y, pass_table[2].z, pass_table[2].w, pass_table[3].x, pass_table[3].y, pass_table[3].z, pass_table[3].w);
+    ds_camera_view = mat4x4<f32>(pass_table[4].x, pass_table[4].y, pass_table[4].z, pass_table[4].w, pass_table[5].x, pass_table[5].y, pass_table[5].z, pass_table[5].w, pass_table[6].x, pass_table[6].y, pass_table[6].z, pass_table[6].w, pass_table[7].x, pass_table[7].y, pass_table[7].z, pass_table[7].w);
+    ds_camera_inv = mat4x4<f32>(pass_table[8].x, pass_table[8].y, pass_table[8].z, pass_table[8].w, pass_table[9].x, pass_table[9].y, pass_table[9].z, pass_table[9].w, pass_table[10].x, pass_table[10].y, pass_table[10].z, pass_table[10].w, pass_table[11].x, pass_table[11].y, pass_table[11].z, pass_table[11].w);
+    ds_dpi_factor = pass_table[12].x;
+    ds_dpi_dilate = pass_table[12].y;
+    ds_time = pass_table[12].z;
+    ds_view_transform = mat4x4<f32>(view_table[0].x, view_table[0].y, view_table[0].z, view_table[0].w, view_table[1].x, view_table[1].y, view_table[1].z, view_table[1].w, view_table[2].x, view_table[2].y, view_table[2].z, view_table[2].w, view_table[3].x, view_table[3].y, view_table[3].z, view_table[3].w);
+    ds_color2.x = input.packed_varying_0.x;
+    ds_color2.y = input.packed_varying_0.y;
+    ds_color2.z = input.packed_varying_0.z;
+    ds_color2.w = input.packed_varying_0.w;
+    ds_dither = input.packed_varying_1.x;
+    ds_color.x = input.packed_varying_1.y;
+    ds_color.y = input.packed_varying_1.z;
+    ds_color.z = input.packed_varying_1.w;
+    ds_color.w = input.packed_varying_2.x;
+    ds_pos.x = input.packed_varying_2.y;
+    ds_pos.y = input.packed_varying_2.z;
+
+    return fn_makepad_widgets__base__GradientYView__draw_bg_pixel();
+}
@@ -0,0 +1,135 @@
+@group(0) @binding(0) var<uniform> const_table: array<vec4<f32>, 1>;
+@group(0) @binding(1) var<uniform> draw_table: array<vec4<f32>, 1>;
+@group(0) @binding(2) var<uniform> pass_table: array<vec4<f32>, 13>;
+@group(0) @binding(3) var<uniform> view_table: array<vec4<f32>, 4>;
+@group(0) @binding(4) var ds_image: texture_2d<f32>;
+@group(0) @binding(5) var default_sampler: sampler;
+
+var<private> ds_camera_projection: mat4x4<f32>;
+var<private> ds_camera_view: mat4x4<f32>;
+var<private> ds_camera_inv: mat4x4<f32>;
+var<private> ds_dpi_factor: f32;
+var<private> ds_dpi_dilate: f32;
+var<private> ds_time: f32;
+var<private> ds_view_transform: mat4x4<f32>;
+var<private> ds_draw_zbias: f32;
+var<private> ds_opacity: f32;
+var<private> ds_image_scale: vec2<f32>;
+var<private> ds_image_pan: vec2<f32>;
+var<private> ds_rect_pos: vec2<f32>;
+var<private> ds_rect_size: vec2<f32>;
+var<private> ds_draw_clip: vec4<f32>;
+var<private> ds_draw_depth: f32;
+var<private> ds_geom_pos: vec2<f32>;
+var<private> ds_pos: vec2<f32>;
+
+fn sample2d(tex: texture_2d<f32>, pos: vec2<f32>) -> vec4<f32> {
+    return textureSampleLevel(tex, default_sampler, pos, 0.0);
+}
+
+fn fn_makepad_widgets__base__Image__draw_bg_get_color_scale_pan(p_var_scale_0: vec2<f32>, p_var_pan_0: vec2<f32>) -> vec4<f32> {
+    var var_scale_0 = p_var_scale_0;
+    var var_pan_0 = p_var_pan_0;
+    return sample2d(ds_image, ((ds_pos * var_scale_0) + var_pan_0)).xyzw;
+}
+
+fn fn_makepad_widgets__base__Image__draw_bg_get_color() -> vec4<f32> {
+    return fn_makepad_widgets__base__Image__draw_bg_get_color_scale_pan (ds_image_scale, ds_image_pan);
+}
+
+fn fn_makepad_draw__shader__std__Pal_premul(p_var_v_0: vec4<f32>) -> vec4<f32> {
+    var var_v_0 = p_var_v_0;
+    return vec4<f32>((var_v_0.x * var_v_0.w), (var_v_0.y * var_v_0.w), (var_v_0.z * var_v_0.w), var_v_0.w);
+}
+
+fn fn_makepad_widgets__base__Image__draw_bg_pixel() -> vec4<f32> {
+    var var_color_0: vec4<f32> = fn_makepad_widgets__base__Image__draw_bg_get_color ();
+    return fn_makepad_draw__shader__std__Pal_premul (vec4<f32>(var_color_0.xyz, (var_color_0.w * ds_opacity)));
+}
+
+fn fn_makepad_widgets__base__Image__draw_bg_clip_and_transform_vertex(p_var_rect_pos_0: vec2<f32>, p_var_rect_size_0: vec2<f32>) -> vec4<f32> {
+    var var_rect_pos_0 = p_var_rect_pos_0;
+    var var_rect_size_0 = p_var_rect_size_0;
+    var var_clipped_0: vec2<f32> = clamp(((ds_geom_pos * var_rect_size_0) + var_rect_pos_0), ds_draw_clip.xy, ds_draw_clip.zw);
+    ds_pos = ((var_clipped_0 - var_rect_pos_0) / var_rect_size_0);
+    return (ds_camera_projection * (ds_camera_view * (ds_view_transform * vec4<f32>(var_clipped_0.x, var_clipped_0.y, (ds_draw_depth + ds_draw_zbias), const_table[0].x))));
+}
+
+fn fn_makepad_widgets__base__Image__draw_bg_vertex() -> vec4<f32> {
+    return fn_makepad_widgets__base__Image__draw_bg_clip_and_transform_vertex (ds_rect_pos, ds_rect_size);
+}
+
+struct VertexInput {
+    @location(0) packed_geometry_0: vec2<f32>,
+    @location(1) packed_instance_0: vec4<f32>,
+    @location(2) packed_instance_1: vec4<f32>,
+    @location(3) packed_instance_2: vec4<f32>,
+    @location(4) packed_instance_3: vec2<f32>,
+}
+
+struct Varyings {
+    @builtin(position) position: vec4<f32>,
+    @location(0) packed_varying_0: vec4<f32>,
+    @location(1) packed_varying_1: vec3<f32>,
+}
+
+@vertex
+fn vertex_main(input: VertexInput) -> Varyings {
+    ds_draw_zbias = draw_table[0].x;
+    ds_camera_projection = mat4x4<f32>(pass_table[0].x, pass_table[0].y, pass_table[0].z, pass_table[0].w, pass_table[1].x, pass_table[1].y, pass_table[1].z, pass_table[1].w, pass_table[2].x, pass_table[2].y, pass_table[2].z, pass_table[2].w, pass_table[3].x, pass_table[3].y, pass_table[3].z, pass_table[3].w);
+    ds_camera_view = mat4x4<f32>(pass_table[4].x, pass_table[4].y, pass_table[4].z, pass_table[4].w, pass_table[5].x, pass_table[5].y, pass_table[5].z, pass_table[5].w, pass_table[6].x, pass_table[6].y, pass_table[6].z, pass_table[6].w, pass_table[7].x, pass_table[7].y, pass_table[7].z, pass_table[7].w);
+    ds_camera_inv = mat4x4<f32>(pass_table[8].x, pass_table[8].y, pass_table[8].z, pass_table[8].w, pass_table[9].x, pass_table[9].y, pass_table[9].z, pass_table[9].w, pass_table[10].x, pass_table[10].y, pass_table[10].z, pass_table[10].w, pass_table[11].x, pass_table[11].y, pass_table[11].z, pass_table[11].w);
+    ds_dpi_factor = pass_table[12].x;
+    ds_dpi_dilate = pass_table[12].y;
+    ds_time = pass_table[12].z;
+    ds_view_transform = mat4x4<f32>(view_table[0].x, view_table[0].y, view_table[0].z, view_table[0].w, view_table[1].x, view_table[1].y, view_table[1].z, view_table[1].w, view_table[2].x, view_table[2].y, view_table[2].z, view_table[2].w, view_table[3].x, view_table[3].y, view_table[3].z, view_table[3].w);
+    ds_opacity = input.packed_instance_0.x;
+    ds_image_scale.x = input.packed_instance_0.y;
+    ds_image_scale.y = input.packed_instance_0.z;
+    ds_image_pan.x = input.packed_instance_0.w;
+    ds_image_pan.y = input.packed_instance_1.x;
+    ds_rect_pos.x = input.packed_instance_1.y;
+    ds_rect_pos.y = input.packed_instance_1.z;
+    ds_rect_size.x = input.packed_instance_1.w;
+    ds_rect_size.y = input.packed_instance_2.x;
+    ds_draw_clip.x = input.packed_instance_2.y;
+    ds_draw_clip.y = input.packed_instance_2.z;
+    ds_draw_clip.z = input.packed_instance_2.w;
+    ds_draw_clip.w = input.packed_instance_3.x;
+    ds_draw_depth = input.packed_instance_3.y;
+    ds_geom_pos.x = input.packed_geometry_0.x;
+    ds_geom_pos.y = input.packed_geometry_0.y;
+
+    var output: Varyings;
+    output.position = fn_makepad_widgets__base__Image__draw_bg_vertex();
+
+    output.packed_varying_0.x = ds_opacity;
+    output.packed_varying_0.y = ds_image_scale.x;
+    output.packed_varying_0.z = ds_image_scale.y;
+    output.packed_varying_0.w = ds_image_pan.x;
+    output.packed_varying_1.x = ds_image_pan.y;
+    output.packed_varying_1.y = ds_pos.x;
+    output.packed_varying_1.z = ds_pos.y;
+    return output;
+}
+
+@fragment
+fn fragment_main(input: Varyings) -> @location(0) vec4<f32> {
+    ds_draw_zbias = draw_table[0].x;
+    ds_camera_projection = mat4x4<f32>(pass_table[0].x, pass_table[0].y, pass_table[0].z, pass_table[0].w, pass_table[1].x, pass_table[1].y, pass_table[1].z, pass_table[1].w, pass_table[2].x, pass_table[2].y, pass_table[2].z, pass_table[2].w, pass_table[3].x, pass_table[3].y, pass_table[3].z, pass_table[3].w);
+    ds_camera_view = mat4x4<f32>(pass_table[4].x, pass_table[4].y, pass_table[4].z, pass_table[4].w, pass_table[5].x, pass_table[5].y, pass_table[5].z, pass_table[5].w, pass_table[6].x, pass_table[6].y, pass_table[6].z, pass_table[6].w, pass_table[7].x, pass_table[7].y, pass_table[7].z, pass_table[7].w);
+    ds_camera_inv = mat4x4<f32>(pass_table[8].x, pass_table[8].y, pass_table[8].z, pass_table[8].w, pass_table[9].x, pass_table[9].y, pass_table[9].z, pass_table[9].w, pass_table[10].x, pass_table[10].y, pass_table[10].z, pass_table[10].w, pass_table[11].x, pass_table[11].y, pass_table[11].z, pass_table[11].w);
+    ds_dpi_factor = pass_table[12].x;
+    ds_dpi_dilate = pass_table[12].y;
+    ds_time = pass_table[12].z;
+    ds_view_transform = mat4x4<f32>(view_table[0].x, view_table[0].y, view_table[0].z, view_table[0].w, view_table[1].x, view_table[1].y, view_table[1].z, view_table[1].w, view_table[2].x, view_table[2].y, view_table[2].z, view_table[2].w, view_table[3].x, view_table[3].y, view_table[3].z, view_table[3].w);
+    ds_opacity = input.packed_varying_0.x;
+    ds_image_scale.x = input.packed_varying_0.y;
+    ds_image_scale.y = input.packed_varying_0.z;
+    ds_image_pan.x = input.packed_varying_0.w;
+    ds_image_pan.y = input.packed_varying_1.x;
+    ds_pos.x = input.packed_varying_1.y;
+    ds_pos.y = input.packed_varying_1.z;
+
+    return fn_makepad_widgets__base__Image__draw_bg_pixel();
+}
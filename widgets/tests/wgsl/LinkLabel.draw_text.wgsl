@@ -0,0 +1,196 @@
+@group(0) @binding(0) var<uniform> const_table: array<vec4<f32>, 3>;
+@group(0) @binding(1) var<uniform> live_table: array<vec4<f32>, 2>;
+@group(0) @binding(2) var<uniform> draw_table: array<vec4<f32>, 1>;
+@group(0) @binding(3) var<uniform> pass_table: array<vec4<f32>, 13>;
+@group(0) @binding(4) var<uniform> user_table: array<vec4<f32>, 1>;
+@group(0) @binding(5) var<uniform> view_table: array<vec4<f32>, 4>;
+@group(0) @binding(6) var ds_tex: texture_2d<f32>;
+@group(0) @binding(7) var default_sampler: sampler;
+
+var<private> live_makepad_widgets__theme_desktop_dark__THEME_COLOR_TEXT_DEFAULT: vec4<f32>;
+var<private> live_makepad_widgets__theme_desktop_dark__THEME_COLOR_TEXT_META: vec4<f32>;
+var<private> ds_camera_projection: mat4x4<f32>;
+var<private> ds_camera_view: mat4x4<f32>;
+var<private> ds_camera_inv: mat4x4<f32>;
+var<private> ds_dpi_factor: f32;
+var<private> ds_dpi_dilate: f32;
+var<private> ds_time: f32;
+var<private> ds_view_transform: mat4x4<f32>;
+var<private> ds_draw_zbias: f32;
+var<private> ds_pressed: f32;
+var<private> ds_hover: f32;
+var<private> ds_color: vec4<f32>;
+var<private> ds_font_t1: vec2<f32>;
+var<private> ds_font_t2: vec2<f32>;
+var<private> ds_rect_pos: vec2<f32>;
+var<private> ds_rect_size: vec2<f32>;
+var<private> ds_draw_clip: vec4<f32>;
+var<private> ds_char_depth: f32;
+var<private> ds_delta: vec2<f32>;
+var<private> ds_shader_font_size: f32;
+var<private> ds_advance: f32;
+var<private> ds_geom_pos: vec2<f32>;
+var<private> ds_brightness: f32;
+var<private> ds_curve: f32;
+var<private> ds_sdf_radius: f32;
+var<private> ds_sdf_cutoff: f32;
+var<private> ds_tex_coord1: vec2<f32>;
+var<private> ds_tex_coord2: vec2<f32>;
+var<private> ds_tex_coord3: vec2<f32>;
+var<private> ds_clipped: vec2<f32>;
+var<private> ds_pos: vec2<f32>;
+
+fn sample2d(tex: texture_2d<f32>, pos: vec2<f32>) -> vec4<f32> {
+    return textureSampleLevel(tex, default_sampler, pos, 0.0);
+}
+
+fn fn_makepad_widgets__theme_desktop_dark__LinkLabel__draw_text_blend_color(p_var_incol_0: vec4<f32>) -> vec4<f32> {
+    var var_incol_0 = p_var_incol_0;
+    return var_incol_0;
+}
+
+fn fn_makepad_widgets__theme_desktop_dark__LinkLabel__draw_text_get_color() -> vec4<f32> {
+    return mix(mix(live_makepad_widgets__theme_desktop_dark__THEME_COLOR_TEXT_META, live_makepad_widgets__theme_desktop_dark__THEME_COLOR_TEXT_DEFAULT, ds_hover), live_makepad_widgets__theme_desktop_dark__THEME_COLOR_TEXT_META, ds_pressed);
+}
+
+fn fn_makepad_widgets__theme_desktop_dark__LinkLabel__draw_text_sample_color(p_var_scale_0: f32, p_var_pos_0: vec2<f32>) -> vec4<f32> {
+    var var_scale_0 = p_var_scale_0;
+    var var_pos_0 = p_var_pos_0;
+    var var_s_0: f32 = sample2d(ds_tex, var_pos_0).x;
+    if(ds_sdf_radius != const_table[1].y) {
+            var var_texel_coords_0: vec2<f32> = (var_pos_0.xy * const_table[1].z);
+        var_s_0 = clamp(((((var_s_0 - (const_table[1].w - ds_sdf_cutoff)) * ds_sdf_radius) / var_scale_0) + const_table[2].x), const_table[2].y, const_table[2].z);
+    }else{
+            var_s_0 = pow(var_s_0, ds_curve);
+    }
+
+    var var_col_0: vec4<f32> = fn_makepad_widgets__theme_desktop_dark__LinkLabel__draw_text_get_color ();
+    return fn_makepad_widgets__theme_desktop_dark__LinkLabel__draw_text_blend_color (vec4<f32>((((var_s_0 * var_col_0.rgb) * ds_brightness) * var_col_0.a), (var_s_0 * var_col_0.a)));
+}
+
+fn fn_makepad_widgets__theme_desktop_dark__LinkLabel__draw_text_pixel() -> vec4<f32> {
+    var var_texel_coords_0: vec2<f32> = ds_tex_coord1.xy;
+    var var_dxt_0: f32 = length(dpdx(var_texel_coords_0));
+    var var_dyt_0: f32 = length(dpdy(var_texel_coords_0));
+    var var_scale_0: f32 = (((var_dxt_0 + var_dyt_0) * const_table[0].w) * const_table[1].x);
+    return fn_makepad_widgets__theme_desktop_dark__LinkLabel__draw_text_sample_color (var_scale_0, ds_tex_coord1.xy);
+}
+
+fn fn_makepad_widgets__theme_desktop_dark__LinkLabel__draw_text_vertex() -> vec4<f32> {
+    var var_min_pos_0: vec2<f32> = vec2<f32>(ds_rect_pos.x, ds_rect_pos.y);
+    var var_max_pos_0: vec2<f32> = vec2<f32>((ds_rect_pos.x + ds_rect_size.x), (ds_rect_pos.y - ds_rect_size.y));
+    ds_clipped = clamp(mix(var_min_pos_0, var_max_pos_0, ds_geom_pos), ds_draw_clip.xy, ds_draw_clip.zw);
+    var var_normalized_0: vec2<f32> = ((ds_clipped - var_min_pos_0) / vec2<f32>(ds_rect_size.x, -ds_rect_size.y));
+    ds_tex_coord1 = mix(vec2<f32>(ds_font_t1.x, (const_table[0].x - ds_font_t1.y)), vec2<f32>(ds_font_t2.x, (const_table[0].y - ds_font_t2.y)), var_normalized_0.xy);
+    ds_pos = var_normalized_0;
+    return (ds_camera_projection * (ds_camera_view * (ds_view_transform * vec4<f32>(ds_clipped.x, ds_clipped.y, (ds_char_depth + ds_draw_zbias), const_table[0].z))));
+}
+
+struct VertexInput {
+    @location(0) packed_geometry_0: vec2<f32>,
+    @location(1) packed_instance_0: vec4<f32>,
+    @location(2) packed_instance_1: vec4<f32>,
+    @location(3) packed_instance_2: vec4<f32>,
+    @location(4) packed_instance_3: vec4<f32>,
+    @location(5) packed_instance_4: vec4<f32>,
+    @location(6) packed_instance_5: vec3<f32>,
+}
+
+struct Varyings {
+    @builtin(position) position: vec4<f32>,
+    @location(0) packed_varying_0: vec4<f32>,
+    @location(1) packed_varying_1: vec4<f32>,
+    @location(2) packed_varying_2: vec4<f32>,
+}
+
+@vertex
+fn vertex_main(input: VertexInput) -> Varyings {
+    ds_draw_zbias = draw_table[0].x;
+    ds_camera_projection = mat4x4<f32>(pass_table[0].x, pass_table[0].y, pass_table[0].z, pass_table[0].w, pass_table[1].x, pass_table[1].y, pass_table[1].z, pass_table[1].w, pass_table[2].x, pass_table[2].y, pass_table[2].z, pass_table[2].w, pass_table[3].x, pass_table[3].y, pass_table[3].z, pass_table[3].w);
+    ds_camera_view = mat4x4<f32>(pass_table[4].x, pass_table[4].y, pass_table[4].z, pass_table[4].w, pass_table[5].x, pass_table[5].y, pass_table[5].z, pass_table[5].w, pass_table[6].x, pass_table[6].y, pass_table[6].z, pass_table[6].w, pass_table[7].x, pass_table[7].y, pass_table[7].z, pass_table[7].w);
+    ds_camera_inv = mat4x4<f32>(pass_table[8].x, pass_table[8].y, pass_table[8].z, pass_table[8].w, pass_table[9].x, pass_table[9].y, pass_table[9].z, pass_table[9].w, pass_table[10].x, pass_table[10].y, pass_table[10].z, pass_table[10].w, pass_table[11].x, pass_table[11].y, pass_table[11].z, pass_table[11].w);
+    ds_dpi_factor = pass_table[12].x;
+    ds_dpi_dilate = pass_table[12].y;
+    ds_time = pass_table[12].z;
+    ds_brightness = user_table[0].x;
+    ds_curve = user_table[0].y;
+    ds_sdf_radius = user_table[0].z;
+    ds_sdf_cutoff = user_table[0].w;
+    ds_view_transform = mat4x4<f32>(view_table[0].x, view_table[0].y, view_table[0].z, view_table[0].w, view_table[1].x, view_table[1].y, view_table[1].z, view_table[1].w, view_table[2].x, view_table[2].y, view_table[2].z, view_table[2].w, view_table[3].x, view_table[3].y, view_table[3].z, view_table[3].w);
+    live_makepad_widgets__theme_desktop_dark__THEME_COLOR_TEXT_DEFAULT = vec4<f32>(live_table[0].x, live_table[0].y, live_table[0].z, live_table[0].w);
+    live_makepad_widgets__theme_desktop_dark__THEME_COLOR_TEXT_META = vec4<f32>(live_table[1].x, live_table[1].y, live_table[1].z, live_table[1].w);
+    ds_pressed = input.packed_instance_0.x;
+    ds_hover = input.packed_instance_0.y;
+    ds_color.x = input.packed_instance_0.z;
+    ds_color.y = input.packed_instance_0.w;
+    ds_color.z = input.packed_instance_1.x;
+    ds_color.w = input.packed_instance_1.y;
+    ds_font_t1.x = input.packed_instance_1.z;
+    ds_font_t1.y = input.packed_instance_1.w;
+    ds_font_t2.x = input.packed_instance_2.x;
+    ds_font_t2.y = input.packed_instance_2.y;
+    ds_rect_pos.x = input.packed_instance_2.z;
+    ds_rect_pos.y = input.packed_instance_2.w;
+    ds_rect_size.x = input.packed_instance_3.x;
+    ds_rect_size.y = input.packed_instance_3.y;
+    ds_draw_clip.x = input.packed_instance_3.z;
+    ds_draw_clip.y = input.packed_instance_3.w;
+    ds_draw_clip.z = input.packed_instance_4.x;
+    ds_draw_clip.w = input.packed_instance_4.y;
+    ds_char_depth = input.packed_instance_4.z;
+    ds_delta.x = input.packed_instance_4.w;
+    ds_delta.y = input.packed_instance_5.x;
+    ds_shader_font_size = input.packed_instance_5.y;
+    ds_advance = input.packed_instance_5.z;
+    ds_geom_pos.x = input.packed_geometry_0.x;
+    ds_geom_pos.y = input.packed_geometry_0.y;
+
+    var output: Varyings;
+    output.position = fn_makepad_widgets__theme_desktop_dark__LinkLabel__draw_text_vertex();
+
+    output.packed_varying_0.x = ds_pressed;
+    output.packed_varying_0.y = ds_hover;
+    output.packed_varying_0.z = ds_tex_coord1.x;
+    output.packed_varying_0.w = ds_tex_coord1.y;
+    output.packed_varying_1.x = ds_tex_coord2.x;
+    output.packed_varying_1.y = ds_tex_coord2.y;
+    output.packed_varying_1.z = ds_tex_coord3.x;
+    output.packed_varying_1.w = ds_tex_coord3.y;
+    output.packed_varying_2.x = ds_clipped.x;
+    output.packed_varying_2.y = ds_clipped.y;
+    output.packed_varying_2.z = ds_pos.x;
+    output.packed_varying_2.w = ds_pos.y;
+    return output;
+}
+
+@fragment
+fn fragment_main(input: Varyings) -> @location(0) vec4<f32> {
+    ds_draw_zbias = draw_table[0].x;
+    ds_camera_projection = mat4x4<f32>(pass_table[0].x, pass_table[0].y, pass_table[0].z, pass_table[0].w, pass_table[1].x, pass_table[1].y, pass_table[1].z, pass_table[1].w, pass_table[2].x, pass_table[2].y, pass_table[2].z, pass_table[2].w, pass_table[3].x, pass_table[3].y, pass_table[3].z, pass_table[3].w);
+    ds_camera_view = mat4x4<f32>(pass_table[4].x, pass_table[4].y, pass_table[4].z, pass_table[4].w, pass_table[5].x, pass_table[5].y, pass_table[5].z, pass_table[5].w, pass_table[6].x, pass_table[6].y, pass_table[6].z, pass_table[6].w, pass_table[7].x, pass_table[7].y, pass_table[7].z, pass_table[7].w);
+    ds_camera_inv = mat4x4<f32>(pass_table[8].x, pass_table[8].y, pass_table[8].z, pass_table[8].w, pass_table[9].x, pass_table[9].y, pass_table[9].z, pass_table[9].w, pass_table[10].x, pass_table[10].y, pass_table[10].z, pass_table[10].w, pass_table[11].x, pass_table[11].y, pass_table[11].z, pass_table[11].w);
+    ds_dpi_factor = pass_table[12].x;
+    ds_dpi_dilate = pass_table[12].y;
+    ds_time = pass_table[12].z;
+    ds_brightness = user_table[0].x;
+    ds_curve = user_table[0].y;
+    ds_sdf_radius = user_table[0].z;
+    ds_sdf_cutoff = user_table[0].w;
+    ds_view_transform = mat4x4<f32>(view_table[0].x, view_table[0].y, view_table[0].z, view_table[0].w, view_table[1].x, view_table[1].y, view_table[1].z, view_table[1].w, view_table[2].x, view_table[2].y, view_table[2].z, view_table[2].w, view_table[3].x, view_table[3].y, view_table[3].z, view_table[3].w);
+    live_makepad_widgets__theme_desktop_dark__THEME_COLOR_TEXT_DEFAULT = vec4<f32>(live_table[0].x, live_table[0].y, live_table[0].z, live_table[0].w);
+    live_makepad_widgets__theme_desktop_dark__THEME_COLOR_TEXT_META = vec4<f32>(live_table[1].x, live_table[1].y, live_table[1].z, live_table[1].w);
+    ds_pressed = input.packed_varying_0.x;
+    ds_hover = input.packed_varying_0.y;
+    ds_tex_coord1.x = input.packed_varying_0.z;
+    ds_tex_coord1.y = input.packed_varying_0.w;
+    ds_tex_coord2.x = input.packed_varying_1.x;
+    ds_tex_coord2.y = input.packed_varying_1.y;
+    ds_tex_coord3.x = input.packed_varying_1.z;
+    ds_tex_coord3.y = input.packed_varying_1.w;
+    ds_clipped.x = input.packed_varying_2.x;
+    ds_clipped.y = input.packed_varying_2.y;
+    ds_pos.x = input.packed_varying_2.z;
+    ds_pos.y = input.packed_varying_2.w;
+
+    return fn_makepad_widgets__theme_desktop_dark__LinkLabel__draw_text_pixel();
+}
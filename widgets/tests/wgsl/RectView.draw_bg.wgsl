@@ -0,0 +1,250 @@
+@group(0) @binding(0) var<uniform> const_table: array<vec4<f32>, 9>;
+@group(0) @binding(1) var<uniform> draw_table: array<vec4<f32>, 1>;
+@group(0) @binding(2) var<uniform> pass_table: array<vec4<f32>, 13>;
+@group(0) @binding(3) var<uniform> view_table: array<vec4<f32>, 4>;
+
+var<private> ds_camera_projection: mat4x4<f32>;
+var<private> ds_camera_view: mat4x4<f32>;
+var<private> ds_camera_inv: mat4x4<f32>;
+var<private> ds_dpi_factor: f32;
+var<private> ds_dpi_dilate: f32;
+var<private> ds_time: f32;
+var<private> ds_view_transform: mat4x4<f32>;
+var<private> ds_draw_zbias: f32;
+var<private> ds_border_width: f32;
+var<private> ds_border_color: vec4<f32>;
+var<private> ds_inset: vec4<f32>;
+var<private> ds_rect_pos: vec2<f32>;
+var<private> ds_rect_size: vec2<f32>;
+var<private> ds_draw_clip: vec4<f32>;
+var<private> ds_draw_depth: f32;
+var<private> ds_0: f32;
+var<private> ds_color: vec4<f32>;
+var<private> ds_geom_pos: vec2<f32>;
+var<private> ds_pos: vec2<f32>;
+
+struct struct_makepad_draw__shader__std__Sdf2d {
+    f_pos: vec2<f32>,
+    f_result: vec4<f32>,
+    f_last_pos: vec2<f32>,
+    f_start_pos: vec2<f32>,
+    f_shape: f32,
+    f_clip: f32,
+    f_has_clip: f32,
+    f_old_shape: f32,
+    f_blur: f32,
+    f_aa: f32,
+    f_scale_factor: f32,
+    f_dist: f32,
+}
+
+fn fn_makepad_widgets__base__RectView__draw_bg_get_border_color() -> vec4<f32> {
+    return ds_border_color;
+}
+
+fn fn_makepad_widgets__base__RectView__draw_bg_get_color() -> vec4<f32> {
+    return ds_color;
+}
+
+fn fn_makepad_draw__shader__std__Sdf2d_rect(var_self_0: ptr<function, struct_makepad_draw__shader__std__Sdf2d>, p_var_x_0: f32, p_var_y_0: f32, p_var_w_0: f32, p_var_h_0: f32) {
+    var var_x_0 = p_var_x_0;
+    var var_y_0 = p_var_y_0;
+    var var_w_0 = p_var_w_0;
+    var var_h_0 = p_var_h_0;
+    var var_s_0: vec2<f32> = (vec2<f32>(var_w_0, var_h_0) * const_table[7].y);
+    var var_d_0: vec2<f32> = (abs(((vec2<f32>(var_x_0, var_y_0) - (*var_self_0).f_pos) + var_s_0)) - var_s_0);
+    var var_dm_0: vec2<f32> = min(var_d_0, vec2<f32>(const_table[7].z, const_table[7].w));
+    (*var_self_0).f_dist = (max(var_dm_0.x, var_dm_0.y) + length(max(var_d_0, vec2<f32>(const_table[8].x, const_table[8].y))));
+    (*var_self_0).f_old_shape = (*var_self_0).f_shape;
+    (*var_self_0).f_shape = min((*var_self_0).f_shape, (*var_self_0).f_dist);
+}
+
+fn fn_makepad_draw__shader__std__Sdf2d_calc_blur(var_self_0: ptr<function, struct_makepad_draw__shader__std__Sdf2d>, p_var_w_0: f32) -> f32 {
+    var var_w_0 = p_var_w_0;
+    var var_wa_0: f32 = clamp((-var_w_0 * (*var_self_0).f_aa), const_table[5].w, const_table[6].x);
+    var var_wb_0: f32 = const_table[6].y;
+    if((*var_self_0).f_blur > const_table[6].z) {
+            var_wb_0 = clamp((-var_w_0 / (*var_self_0).f_blur), const_table[6].w, const_table[7].x);
+    }
+
+    return (var_wa_0 * var_wb_0);
+}
+
+fn fn_makepad_draw__shader__std__Sdf2d_stroke_keep(var_self_0: ptr<function, struct_makepad_draw__shader__std__Sdf2d>, p_var_color_0: vec4<f32>, p_var_width_0: f32) -> vec4<f32> {
+    var var_color_0 = p_var_color_0;
+    var var_width_0 = p_var_width_0;
+    var var_f_0: f32 = fn_makepad_draw__shader__std__Sdf2d_calc_blur (&(*var_self_0), (abs((*var_self_0).f_shape) - (var_width_0 / (*var_self_0).f_scale_factor)));
+    var var_source_0: vec4<f32> = vec4<f32>((var_color_0.rgb * var_color_0.a), var_color_0.a);
+    var var_dest_0: vec4<f32> = (*var_self_0).f_result;
+    (*var_self_0).f_result = ((var_source_0 * var_f_0) + (var_dest_0 * (const_table[5].z - (var_source_0.a * var_f_0))));
+    return (*var_self_0).f_result;
+}
+
+fn fn_makepad_draw__shader__std__Sdf2d_stroke(var_self_0: ptr<function, struct_makepad_draw__shader__std__Sdf2d>, p_var_color_0: vec4<f32>, p_var_width_0: f32) -> vec4<f32> {
+    var var_color_0 = p_var_color_0;
+    var var_width_0 = p_var_width_0;
+    fn_makepad_draw__shader__std__Sdf2d_stroke_keep (&(*var_self_0), var_color_0, var_width_0);
+    (*var_self_0).f_shape = const_table[4].w;    (*var_self_0).f_old_shape = (*var_self_0).f_shape;
+    (*var_self_0).f_clip = -const_table[5].x;
+    (*var_self_0).f_has_clip = const_table[5].y;
+    return (*var_self_0).f_result;
+}
+
+fn fn_makepad_draw__shader__std__Sdf2d_fill_keep_premul(var_self_0: ptr<function, struct_makepad_draw__shader__std__Sdf2d>, p_var_source_0: vec4<f32>) -> vec4<f32> {
+    var var_source_0 = p_var_source_0;
+    var var_f_0: f32 = fn_makepad_draw__shader__std__Sdf2d_calc_blur (&(*var_self_0), (*var_self_0).f_shape);
+    (*var_self_0).f_result = ((var_source_0 * var_f_0) + ((*var_self_0).f_result * (const_table[3].w - (var_source_0.a * var_f_0))));
+    if((*var_self_0).f_has_clip > const_table[4].x) {
+            var var_f2_0: f32 = (const_table[4].y - fn_makepad_draw__shader__std__Sdf2d_calc_blur (&(*var_self_0), -(*var_self_0).f_clip));
+        (*var_self_0).f_result = ((var_source_0 * var_f2_0) + ((*var_self_0).f_result * (const_table[4].z - (var_source_0.a * var_f2_0))));
+    }
+
+    return (*var_self_0).f_result;
+}
+
+fn fn_makepad_draw__shader__std__Sdf2d_fill_keep(var_self_0: ptr<function, struct_makepad_draw__shader__std__Sdf2d>, p_var_color_0: vec4<f32>) -> vec4<f32> {
+    var var_color_0 = p_var_color_0;
+    return fn_makepad_draw__shader__std__Sdf2d_fill_keep_premul (&(*var_self_0), vec4<f32>((var_color_0.rgb * var_color_0.a), var_color_0.a));
+}
+
+fn fn_makepad_draw__shader__std__Sdf2d_antialias(p_var_p_0: vec2<f32>) -> f32 {
+    var var_p_0 = p_var_p_0;
+    return (const_table[3].z / length(vec2<f32>(length(dpdx(var_p_0)), length(dpdy(var_p_0)))));
+}
+
+fn fn_makepad_draw__shader__std__Sdf2d_viewport(p_var_pos_0: vec2<f32>) -> struct_makepad_draw__shader__std__Sdf2d {
+    var var_pos_0 = p_var_pos_0;
+    return struct_makepad_draw__shader__std__Sdf2d(var_pos_0,vec4<f32>(const_table[1].x),vec2<f32>(const_table[1].y),vec2<f32>(const_table[1].z),const_table[1].w,-const_table[2].x,const_table[2].y,const_table[2].z,const_table[2].w,fn_makepad_draw__shader__std__Sdf2d_antialias (var_pos_0),const_table[3].x,const_table[3].y);
+}
+
+fn fn_makepad_widgets__base__RectView__draw_bg_pixel() -> vec4<f32> {
+    var var_sdf_0: struct_makepad_draw__shader__std__Sdf2d = fn_makepad_draw__shader__std__Sdf2d_viewport ((ds_pos * ds_rect_size));
+    fn_makepad_draw__shader__std__Sdf2d_rect (&var_sdf_0, (ds_inset.x + ds_border_width), (ds_inset.y + ds_border_width), (ds_rect_size.x - ((ds_inset.x + ds_inset.z) + (ds_border_width * const_table[0].y))), (ds_rect_size.y - ((ds_inset.y + ds_inset.w) + (ds_border_width * const_table[0].z))));
+    fn_makepad_draw__shader__std__Sdf2d_fill_keep (&var_sdf_0, fn_makepad_widgets__base__RectView__draw_bg_get_color ());
+    if(ds_border_width > const_table[0].w) {
+            fn_makepad_draw__shader__std__Sdf2d_stroke (&var_sdf_0, fn_makepad_widgets__base__RectView__draw_bg_get_border_color (), ds_border_width);
+    }
+
+    return var_sdf_0.f_result;
+}
+
+fn fn_makepad_widgets__base__RectView__draw_bg_clip_and_transform_vertex(p_var_rect_pos_0: vec2<f32>, p_var_rect_size_0: vec2<f32>) -> vec4<f32> {
+    var var_rect_pos_0 = p_var_rect_pos_0;
+    var var_rect_size_0 = p_var_rect_size_0;
+    var var_clipped_0: vec2<f32> = clamp(((ds_geom_pos * var_rect_size_0) + var_rect_pos_0), ds_draw_clip.xy, ds_draw_clip.zw);
+    ds_pos = ((var_clipped_0 - var_rect_pos_0) / var_rect_size_0);
+    return (ds_camera_projection * (ds_camera_view * (ds_view_transform * vec4<f32>(var_clipped_0.x, var_clipped_0.y, (ds_draw_depth + ds_draw_zbias), const_table[0].x))));
+}
+
+fn fn_makepad_widgets__base__RectView__draw_bg_vertex() -> vec4<f32> {
+    return fn_makepad_widgets__base__RectView__draw_bg_clip_and_transform_vertex (ds_rect_pos, ds_rect_size);
+}
+
+struct VertexInput {
+    @location(0) packed_geometry_0: vec2<f32>,
+    @location(1) packed_instance_0: vec4<f32>,
+    @location(2) packed_instance_1: vec4<f32>,
+    @location(3) packed_instance_2: vec4<f32>,
+    @location(4) packed_instance_3: vec4<f32>,
+    @location(5) packed_instance_4: vec4<f32>,
+    @location(6) packed_instance_5: vec3<f32>,
+}
+
+struct Varyings {
+    @builtin(position) position: vec4<f32>,
+    @location(0) packed_varying_0: vec4<f32>,
+    @location(1) packed_varying_1: vec4<f32>,
+    @location(2) packed_varying_2: vec4<f32>,
+    @location(3) packed_varying_3: vec4<f32>,
+    @location(4) packed_varying_4: f32,
+}
+
+@vertex
+fn vertex_main(input: VertexInput) -> Varyings {
+    ds_draw_zbias = draw_table[0].x;
+    ds_camera_projection = mat4x4<f32>(pass_table[0].x, pass_table[0].y, pass_table[0].z, pass_table[0].w, pass_table[1].x, pass_table[1].y, pass_table[1].z, pass_table[1].w, pass_table[2].x, pass_table[2].y, pass_table[2].z, pass_table[2].w, pass_table[3].x, pass_table[3].y, pass_table[3].z, pass_table[3].w);
+    ds_camera_view = mat4x4<f32>(pass_table[4].x, pass_table[4].y, pass_table[4].z, pass_table[4].w, pass_table[5].x, pass_table[5].y, pass_table[5].z, pass_table[5].w, pass_table[6].x, pass_table[6].y, pass_table[6].z, pass_table[6].w, pass_table[7].x, pass_table[7].y, pass_table[7].z, pass_table[7].w);
+    ds_camera_inv = mat4x4<f32>(pass_table[8].x, pass_table[8].y, pass_table[8].z, pass_table[8].w, pass_table[9].x, pass_table[9].y, pass_table[9].z, pass_table[9].w, pass_table[10].x, pass_table[10].y, pass_table[10].z, pass_table[10].w, pass_table[11].x, pass_table[11].y, pass_table[11].z, pass_table[11].w);
+    ds_dpi_factor = pass_table[12].x;
+    ds_dpi_dilate = pass_table[12].y;
+    ds_time = pass_table[12].z;
+    ds_view_transform = mat4x4<f32>(view_table[0].x, view_table[0].y, view_table[0].z, view_table[0].w, view_table[1].x, view_table[1].y, view_table[1].z, view_table[1].w, view_table[2].x, view_table[2].y, view_table[2].z, view_table[2].w, view_table[3].x, view_table[3].y, view_table[3].z, view_table[3].w);
+    ds_border_width = input.packed_instance_0.x;
+    ds_border_color.x = input.packed_instance_0.y;
+    ds_border_color.y = input.packed_instance_0.z;
+    ds_border_color.z = input.packed_instance_0.w;
+    ds_border_color.w = input.packed_instance_1.x;
+    ds_inset.x = input.packed_instance_1.y;
+    ds_inset.y = input.packed_instance_1.z;
+    ds_inset.z = input.packed_instance_1.w;
+    ds_inset.w = input.packed_instance_2.x;
+    ds_rect_pos.x = input.packed_instance_2.y;
+    ds_rect_pos.y = input.packed_instance_2.z;
+    ds_rect_size.x = input.packed_instance_2.w;
+    ds_rect_size.y = input.packed_instance_3.x;
+    ds_draw_clip.x = input.packed_instance_3.y;
+    ds_draw_clip.y = input.packed_instance_3.z;
+    ds_draw_clip.z = input.packed_instance_3.w;
+    ds_draw_clip.w = input.packed_instance_4.x;
+    ds_draw_depth = input.packed_instance_4.y;
+    ds_0 = input.packed_instance_4.z;
+    ds_color.x = input.packed_instance_4.w;
+    ds_color.y = input.packed_instance_5.x;
+    ds_color.z = input.packed_instance_5.y;
+    ds_color.w = input.packed_instance_5.z;
+    ds_geom_pos.x = input.packed_geometry_0.x;
+    ds_geom_pos.y = input.packed_geometry_0.y;
+
+    var output: Varyings;
+    output.position = fn_makepad_widgets__base__RectView__draw_bg_vertex();
+
+    output.packed_varying_0.x = ds_border_width;
+    output.packed_varying_0.y = ds_border_color.x;
+    output.packed_varying_0.z = ds_border_color.y;
+    output.packed_varying_0.w = ds_border_color.z;
+    output.packed_varying_1.x = ds_border_color.w;
+    output.packed_varying_1.y = ds_inset.x;
+    output.packed_varying_1.z = ds_inset.y;
+    output.packed_varying_1.w = ds_inset.z;
+    output.packed_varying_2.x = ds_inset.w;
+    output.packed_varying_2.y = ds_rect_size.x;
+    output.packed_varying_2.z = ds_rect_size.y;
+    output.packed_varying_2.w = ds_color.x;
+    output.packed_varying_3.x = ds_color.y;
+    output.packed_varying_3.y = ds_color.z;
+    output.packed_varying_3.z = ds_color.w;
+    output.packed_varying_3.w = ds_pos.x;
+    output.packed_varying_4 = ds_pos.y;
+    return output;
+}
+
+@fragment
+fn fragment_main(input: Varyings) -> @location(0) vec4<f32> {
+    ds_draw_zbias = draw_table[0].x;
+    ds_camera_projection = mat4x4<f32>(pass_table[0].x, pass_table[0].y, pass_table[0].z, pass_table[0].w, pass_table[1].x, pass_table[1].y, pass_table[1].z, pass_table[1].w, pass_table[2].x, pass_table[2].y, pass_table[2].z, pass_table[2].w, pass_table[3].x, pass_table[3].y, pass_table[3].z, pass_table[3].w);
+    ds_camera_view = mat4x4<f32>(pass_table[4].x, pass_table[4].y, pass_table[4].z, pass_table[4].w, pass_table[5].x, pass_table[5].y, pass_table[5].z, pass_table[5].w, pass_table[6].x, pass_table[6].y, pass_table[6].z, pass_table[6].w, pass_table[7].x, pass_table[7].y, pass_table[7].z, pass_table[7].w);
+    ds_camera_inv = mat4x4<f32>(pass_table[8].x, pass_table[8].y, pass_table[8].z, pass_table[8].w, pass_table[9].x, pass_table[9].y, pass_table[9].z, pass_table[9].w, pass_table[10].x, pass_table[10].y, pass_table[10].z, pass_table[10].w, pass_table[11].x, pass_table[11].y, pass_table[11].z, pass_table[11].w);
+    ds_dpi_factor = pass_table[12].x;
+    ds_dpi_dilate = pass_table[12].y;
+    ds_time = pass_table[12].z;
+    ds_view_transform = mat4x4<f32>(view_table[0].x, view_table[0].y, view_table[0].z, view_table[0].w, view_table[1].x, view_table[1].y, view_table[1].z, view_table[1].w, view_table[2].x, view_table[2].y, view_table[2].z, view_table[2].w, view_table[3].x, view_table[3].y, view_table[3].z, view_table[3].w);
+    ds_border_width = input.packed_varying_0.x;
+    ds_border_color.x = input.packed_varying_0.y;
+    ds_border_color.y = input.packed_varying_0.z;
+    ds_border_color.z = input.packed_varying_0.w;
+    ds_border_color.w = input.packed_varying_1.x;
+    ds_inset.x = input.packed_varying_1.y;
+    ds_inset.y = input.packed_varying_1.z;
+    ds_inset.z = input.packed_varying_1.w;
+    ds_inset.w = input.packed_varying_2.x;
+    ds_rect_size.x = input.packed_varying_2.y;
+    ds_rect_size.y = input.packed_varying_2.z;
+    ds_color.x = input.packed_varying_2.w;
+    ds_color.y = input.packed_varying_3.x;
+    ds_color.z = input.packed_varying_3.y;
+    ds_color.w = input.packed_varying_3.z;
+    ds_pos.x = input.packed_varying_3.w;
+    ds_pos.y = input.packed_varying_4;
+
+    return fn_makepad_widgets__base__RectView__draw_bg_pixel();
+}
@@ -0,0 +1,256 @@
+@group(0) @binding(0) var<uniform> const_table: array<vec4<f32>, 13>;
+@group(0) @binding(1) var<uniform> draw_table: array<vec4<f32>, 1>;
+@group(0) @binding(2) var<uniform> pass_table: array<vec4<f32>, 13>;
+@group(0) @binding(3) var<uniform> view_table: array<vec4<f32>, 4>;
+@group(0) @binding(4) var ds_image: texture_2d<f32>;
+@group(0) @binding(5) var default_sampler: sampler;
+
+var<private> ds_camera_projection: mat4x4<f32>;
+var<private> ds_camera_view: mat4x4<f32>;
+var<private> ds_camera_inv: mat4x4<f32>;
+var<private> ds_dpi_factor: f32;
+var<private> ds_dpi_dilate: f32;
+var<private> ds_time: f32;
+var<private> ds_view_transform: mat4x4<f32>;
+var<private> ds_draw_zbias: f32;
+var<private> ds_rotation: f32;
+var<private> ds_opacity: f32;
+var<private> ds_scale: f32;
+var<private> ds_rect_pos: vec2<f32>;
+var<private> ds_rect_size: vec2<f32>;
+var<private> ds_draw_clip: vec4<f32>;
+var<private> ds_draw_depth: f32;
+var<private> ds_0: f32;
+var<private> ds_color: vec4<f32>;
+var<private> ds_geom_pos: vec2<f32>;
+var<private> ds_pos: vec2<f32>;
+
+struct struct_makepad_draw__shader__std__Sdf2d {
+    f_pos: vec2<f32>,
+    f_result: vec4<f32>,
+    f_last_pos: vec2<f32>,
+    f_start_pos: vec2<f32>,
+    f_shape: f32,
+    f_clip: f32,
+    f_has_clip: f32,
+    f_old_shape: f32,
+    f_blur: f32,
+    f_aa: f32,
+    f_scale_factor: f32,
+    f_dist: f32,
+}
+
+fn sample2d(tex: texture_2d<f32>, pos: vec2<f32>) -> vec4<f32> {
+    return textureSampleLevel(tex, default_sampler, pos, 0.0);
+}
+
+fn fn_makepad_widgets__base__RotatedImage__draw_bg_rotate_2d_from_center(p_var_coord_0: vec2<f32>, p_var_a_0: f32, p_var_size_0: vec2<f32>) -> vec2<f32> {
+    var var_coord_0 = p_var_coord_0;
+    var var_a_0 = p_var_a_0;
+    var var_size_0 = p_var_size_0;
+    var var_cos_a_0: f32 = cos(-var_a_0);
+    var var_sin_a_0: f32 = sin(-var_a_0);
+    var var_centered_coord_0: vec2<f32> = (var_coord_0 - vec2<f32>(const_table[11].z, const_table[11].w));
+    var var_denorm_coord_0: vec2<f32> = vec2<f32>(var_centered_coord_0.x, ((var_centered_coord_0.y * var_size_0.y) / var_size_0.x));
+    var var_demorm_rotated_0: vec2<f32> = vec2<f32>(((var_denorm_coord_0.x * var_cos_a_0) - (var_denorm_coord_0.y * var_sin_a_0)), ((var_denorm_coord_0.x * var_sin_a_0) + (var_denorm_coord_0.y * var_cos_a_0)));
+    var var_rotated_0: vec2<f32> = vec2<f32>(var_demorm_rotated_0.x, ((var_demorm_rotated_0.y * var_size_0.x) / var_size_0.y));
+    return (var_rotated_0 + vec2<f32>(const_table[12].x, const_table[12].y));
+}
+
+fn fn_makepad_widgets__base__RotatedImage__draw_bg_rotation_vertex_expansion(p_var_rotation_0: f32, p_var_w_0: f32, p_var_h_0: f32) -> vec2<f32> {
+    var var_rotation_0 = p_var_rotation_0;
+    var var_w_0 = p_var_w_0;
+    var var_h_0 = p_var_h_0;
+    var var_horizontal_expansion_0: f32 = ((((abs(cos(var_rotation_0)) * var_w_0) + (abs(sin(var_rotation_0)) * var_h_0)) / var_w_0) - const_table[11].x);
+    var var_vertical_expansion_0: f32 = ((((abs(sin(var_rotation_0)) * var_w_0) + (abs(cos(var_rotation_0)) * var_h_0)) / var_h_0) - const_table[11].y);
+    return vec2<f32>(var_horizontal_expansion_0, var_vertical_expansion_0);
+}
+
+fn fn_makepad_widgets__base__RotatedImage__draw_bg_get_color() -> vec4<f32> {
+    var var_rot_padding_0: vec2<f32> = (fn_makepad_widgets__base__RotatedImage__draw_bg_rotation_vertex_expansion (ds_rotation, ds_rect_size.x, ds_rect_size.y) / const_table[10].x);
+    var var_current_pos_0: vec2<f32> = (ds_pos.xy - var_rot_padding_0);
+    var var_original_pos_0: vec2<f32> = fn_makepad_widgets__base__RotatedImage__draw_bg_rotate_2d_from_center (var_current_pos_0, ds_rotation, ds_rect_size);
+    var var_scaled_pos_0: vec2<f32> = (var_original_pos_0 / ds_scale);
+    var var_color_0: vec4<f32> = sample2d(ds_image, var_scaled_pos_0).xyzw;
+    var var_faded_color_0: vec4<f32> = (var_color_0 * vec4<f32>(const_table[10].y, const_table[10].z, const_table[10].w, ds_opacity));
+    return var_faded_color_0;
+}
+
+fn fn_makepad_draw__shader__std__Sdf2d_box(var_self_0: ptr<function, struct_makepad_draw__shader__std__Sdf2d>, p_var_x_0: f32, p_var_y_0: f32, p_var_w_0: f32, p_var_h_0: f32, p_var_r_0: f32) {
+    var var_x_0 = p_var_x_0;
+    var var_y_0 = p_var_y_0;
+    var var_w_0 = p_var_w_0;
+    var var_h_0 = p_var_h_0;
+    var var_r_0 = p_var_r_0;
+    var var_p_0: vec2<f32> = ((*var_self_0).f_pos - vec2<f32>(var_x_0, var_y_0));
+    var var_size_0: vec2<f32> = vec2<f32>((const_table[8].y * var_w_0), (const_table[8].z * var_h_0));
+    var var_bp_0: vec2<f32> = max((abs((var_p_0 - var_size_0.xy)) - (var_size_0.xy - vec2<f32>((const_table[8].w * var_r_0), (const_table[9].x * var_r_0)).xy)), vec2<f32>(const_table[9].y, const_table[9].z));
+    (*var_self_0).f_dist = ((length(var_bp_0) - (const_table[9].w * var_r_0)) / (*var_self_0).f_scale_factor);
+    (*var_self_0).f_old_shape = (*var_self_0).f_shape;
+    (*var_self_0).f_shape = min((*var_self_0).f_shape, (*var_self_0).f_dist);
+}
+
+fn fn_makepad_draw__shader__std__Sdf2d_calc_blur(var_self_0: ptr<function, struct_makepad_draw__shader__std__Sdf2d>, p_var_w_0: f32) -> f32 {
+    var var_w_0 = p_var_w_0;
+    var var_wa_0: f32 = clamp((-var_w_0 * (*var_self_0).f_aa), const_table[6].w, const_table[7].x);
+    var var_wb_0: f32 = const_table[7].y;
+    if((*var_self_0).f_blur > const_table[7].z) {
+            var_wb_0 = clamp((-var_w_0 / (*var_self_0).f_blur), const_table[7].w, const_table[8].x);
+    }
+
+    return (var_wa_0 * var_wb_0);
+}
+
+fn fn_makepad_draw__shader__std__Sdf2d_fill_keep_premul(var_self_0: ptr<function, struct_makepad_draw__shader__std__Sdf2d>, p_var_source_0: vec4<f32>) -> vec4<f32> {
+    var var_source_0 = p_var_source_0;
+    var var_f_0: f32 = fn_makepad_draw__shader__std__Sdf2d_calc_blur (&(*var_self_0), (*var_self_0).f_shape);
+    (*var_self_0).f_result = ((var_source_0 * var_f_0) + ((*var_self_0).f_result * (const_table[5].w - (var_source_0.a * var_f_0))));
+    if((*var_self_0).f_has_clip > const_table[6].x) {
+            var var_f2_0: f32 = (const_table[6].y - fn_makepad_draw__shader__std__Sdf2d_calc_blur (&(*var_self_0), -(*var_self_0).f_clip));
+        (*var_self_0).f_result = ((var_source_0 * var_f2_0) + ((*var_self_0).f_result * (const_table[6].z - (var_source_0.a * var_f2_0))));
+    }
+
+    return (*var_self_0).f_result;
+}
+
+fn fn_makepad_draw__shader__std__Sdf2d_fill_premul(var_self_0: ptr<function, struct_makepad_draw__shader__std__Sdf2d>, p_var_color_0: vec4<f32>) -> vec4<f32> {
+    var var_color_0 = p_var_color_0;
+    fn_makepad_draw__shader__std__Sdf2d_fill_keep_premul (&(*var_self_0), var_color_0);
+    (*var_self_0).f_shape = const_table[5].x;    (*var_self_0).f_old_shape = (*var_self_0).f_shape;
+    (*var_self_0).f_clip = -const_table[5].y;
+    (*var_self_0).f_has_clip = const_table[5].z;
+    return (*var_self_0).f_result;
+}
+
+fn fn_makepad_draw__shader__std__Sdf2d_rotate(var_self_0: ptr<function, struct_makepad_draw__shader__std__Sdf2d>, p_var_a_0: f32, p_var_x_0: f32, p_var_y_0: f32) {
+    var var_a_0 = p_var_a_0;
+    var var_x_0 = p_var_x_0;
+    var var_y_0 = p_var_y_0;
+    var var_ca_0: f32 = cos(-var_a_0);
+    var var_sa_0: f32 = sin(-var_a_0);
+    var var_p_0: vec2<f32> = ((*var_self_0).f_pos - vec2<f32>(var_x_0, var_y_0));
+    (*var_self_0).f_pos = (vec2<f32>(((var_p_0.x * var_ca_0) - (var_p_0.y * var_sa_0)), ((var_p_0.x * var_sa_0) + (var_p_0.y * var_ca_0))) + vec2<f32>(var_x_0, var_y_0));
+}
+
+fn fn_makepad_draw__shader__std__Sdf2d_translate(var_self_0: ptr<function, struct_makepad_draw__shader__std__Sdf2d>, p_var_x_0: f32, p_var_y_0: f32) -> vec2<f32> {
+    var var_x_0 = p_var_x_0;
+    var var_y_0 = p_var_y_0;
+    (*var_self_0).f_pos -= vec2<f32>(var_x_0, var_y_0);
+    return (*var_self_0).f_pos;
+}
+
+fn fn_makepad_draw__shader__std__Sdf2d_antialias(p_var_p_0: vec2<f32>) -> f32 {
+    var var_p_0 = p_var_p_0;
+    return (const_table[4].w / length(vec2<f32>(length(dpdx(var_p_0)), length(dpdy(var_p_0)))));
+}
+
+fn fn_makepad_draw__shader__std__Sdf2d_viewport(p_var_pos_0: vec2<f32>) -> struct_makepad_draw__shader__std__Sdf2d {
+    var var_pos_0 = p_var_pos_0;
+    return struct_makepad_draw__shader__std__Sdf2d(var_pos_0,vec4<f32>(const_table[2].y),vec2<f32>(const_table[2].z),vec2<f32>(const_table[2].w),const_table[3].x,-const_table[3].y,const_table[3].z,const_table[3].w,const_table[4].x,fn_makepad_draw__shader__std__Sdf2d_antialias (var_pos_0),const_table[4].y,const_table[4].z);
+}
+
+fn fn_makepad_draw__shader__std__Pal_premul(p_var_v_0: vec4<f32>) -> vec4<f32> {
+    var var_v_0 = p_var_v_0;
+    return vec4<f32>((var_v_0.x * var_v_0.w), (var_v_0.y * var_v_0.w), (var_v_0.z * var_v_0.w), var_v_0.w);
+}
+
+fn fn_makepad_widgets__base__RotatedImage__draw_bg_pixel() -> vec4<f32> {
+    var var_rot_expansion_0: vec2<f32> = fn_makepad_widgets__base__RotatedImage__draw_bg_rotation_vertex_expansion (ds_rotation, ds_rect_size.x, ds_rect_size.y);
+    var var_sdf_0: struct_makepad_draw__shader__std__Sdf2d = fn_makepad_draw__shader__std__Sdf2d_viewport ((ds_pos * ds_rect_size));
+    var var_translation_offset_0: vec2<f32> = vec2<f32>(((ds_rect_size.x * var_rot_expansion_0.x) / const_table[0].w), (((ds_rect_size.y * ds_scale) * var_rot_expansion_0.y) / const_table[1].x));
+    fn_makepad_draw__shader__std__Sdf2d_translate (&var_sdf_0, var_translation_offset_0.x, var_translation_offset_0.y);
+    var var_center_0: vec2<f32> = (ds_rect_size * const_table[1].y);
+    fn_makepad_draw__shader__std__Sdf2d_rotate (&var_sdf_0, ds_rotation, var_center_0.x, var_center_0.y);
+    var var_scaled_size_0: vec2<f32> = (ds_rect_size * ds_scale);
+    fn_makepad_draw__shader__std__Sdf2d_box (&var_sdf_0, const_table[1].z, const_table[1].w, var_scaled_size_0.x, var_scaled_size_0.y, const_table[2].x);
+    fn_makepad_draw__shader__std__Sdf2d_fill_premul (&var_sdf_0, fn_makepad_draw__shader__std__Pal_premul (fn_makepad_widgets__base__RotatedImage__draw_bg_get_color ()));
+    return var_sdf_0.f_result;
+}
+
+fn fn_makepad_widgets__base__RotatedImage__draw_bg_vertex() -> vec4<f32> {
+    var var_rot_expansion_0: vec2<f32> = fn_makepad_widgets__base__RotatedImage__draw_bg_rotation_vertex_expansion (ds_rotation, ds_rect_size.x, ds_rect_size.y);
+    var var_adjusted_pos_0: vec2<f32> = vec2<f32>((ds_rect_pos.x - ((ds_rect_size.x * var_rot_expansion_0.x) / const_table[0].x)), (ds_rect_pos.y - ((ds_rect_size.y * var_rot_expansion_0.y) / const_table[0].y)));
+    var var_expanded_size_0: vec2<f32> = vec2<f32>((ds_rect_size.x * (ds_scale + var_rot_expansion_0.x)), (ds_rect_size.y * (ds_scale + var_rot_expansion_0.y)));
+    var var_clipped_0: vec2<f32> = clamp(((ds_geom_pos * var_expanded_size_0) + var_adjusted_pos_0), ds_draw_clip.xy, ds_draw_clip.zw);
+    ds_pos = ((var_clipped_0 - var_adjusted_pos_0) / ds_rect_size);
+    return (ds_camera_projection * (ds_camera_view * (ds_view_transform * vec4<f32>(var_clipped_0.x, var_clipped_0.y, (ds_draw_depth + ds_draw_zbias), const_table[0].z))));
+}
+
+struct VertexInput {
+    @location(0) packed_geometry_0: vec2<f32>,
+    @location(1) packed_instance_0: vec4<f32>,
+    @location(2) packed_instance_1: vec4<f32>,
+    @location(3) packed_instance_2: vec4<f32>,
+    @location(4) packed_instance_3: vec4<f32>,
+    @location(5) packed_instance_4: f32,
+}
+
+struct Varyings {
+    @builtin(position) position: vec4<f32>,
+    @location(0) packed_varying_0: vec4<f32>,
+    @location(1) packed_varying_1: vec3<f32>,
+}
+
+@vertex
+fn vertex_main(input: VertexInput) -> Varyings {
+    ds_draw_zbias = draw_table[0].x;
+    ds_camera_projection = mat4x4<f32>(pass_table[0].x, pass_table[0].y, pass_table[0].z, pass_table[0].w, pass_table[1].x, pass_table[1].y, pass_table[1].z, pass_table[1].w, pass_table[2].x, pass_table[2].y, pass_table[2].z, pass_table[2].w, pass_table[3].x, pass_table[3].y, pass_table[3].z, pass_table[3].w);
+    ds_camera_view = mat4x4<f32>(pass_table[4].x, pass_table[4].y, pass_table[4].z, pass_table[4].w, pass_table[5].x, pass_table[5].y, pass_table[5].z, pass_table[5].w, pass_table[6].x, pass_table[6].y, pass_table[6].z, pass_table[6].w, pass_table[7].x, pass_table[7].y, pass_table[7].z, pass_table[7].w);
+    ds_camera_inv = mat4x4<f32>(pass_table[8].x, pass_table[8].y, pass_table[8].z, pass_table[8].w, pass_table[9].x, pass_table[9].y, pass_table[9].z, pass_table[9].w, pass_table[10].x, pass_table[10].y, pass_table[10].z, pass_table[10].w, pass_table[11].x, pass_table[11].y, pass_table[11].z, pass_table[11].w);
+    ds_dpi_factor = pass_table[12].x;
+    ds_dpi_dilate = pass_table[12].y;
+    ds_time = pass_table[12].z;
+    ds_view_transform = mat4x4<f32>(view_table[0].x, view_table[0].y, view_table[0].z, view_table[0].w, view_table[1].x, view_table[1].y, view_table[1].z, view_table[1].w, view_table[2].x, view_table[2].y, view_table[2].z, view_table[2].w, view_table[3].x, view_table[3].y, view_table[3].z, view_table[3].w);
+    ds_rotation = input.packed_instance_0.x;
+    ds_opacity = input.packed_instance_0.y;
+    ds_scale = input.packed_instance_0.z;
+    ds_rect_pos.x = input.packed_instance_0.w;
+    ds_rect_pos.y = input.packed_instance_1.x;
+    ds_rect_size.x = input.packed_instance_1.y;
+    ds_rect_size.y = input.packed_instance_1.z;
+    ds_draw_clip.x = input.packed_instance_1.w;
+    ds_draw_clip.y = input.packed_instance_2.x;
+    ds_draw_clip.z = input.packed_instance_2.y;
+    ds_draw_clip.w = input.packed_instance_2.z;
+    ds_draw_depth = input.packed_instance_2.w;
+    ds_0 = input.packed_instance_3.x;
+    ds_color.x = input.packed_instance_3.y;
+    ds_color.y = input.packed_instance_3.z;
+    ds_color.z = input.packed_instance_3.w;
+    ds_color.w = input.packed_instance_4;
+    ds_geom_pos.x = input.packed_geometry_0.x;
+    ds_geom_pos.y = input.packed_geometry_0.y;
+
+    var output: Varyings;
+    output.position = fn_makepad_widgets__base__RotatedImage__draw_bg_vertex();
+
+    output.packed_varying_0.x = ds_rotation;
+    output.packed_varying_0.y = ds_opacity;
+    output.packed_varying_0.z = ds_scale;
+    output.packed_varying_0.w = ds_rect_size.x;
+    output.packed_varying_1.x = ds_rect_size.y;
+    output.packed_varying_1.y = ds_pos.x;
+    output.packed_varying_1.z = ds_pos.y;
+    return output;
+}
+
+@fragment
+fn fragment_main(input: Varyings) -> @location(0) vec4<f32> {
+    ds_draw_zbias = draw_table[0].x;
+    ds_camera_projection = mat4x4<f32>(pass_table[0].x, pass_table[0].y, pass_table[0].z, pass_table[0].w, pass_table[1].x, pass_table[1].y, pass_table[1].z, pass_table[1].w, pass_table[2].x, pass_table[2].y, pass_table[2].z, pass_table[2].w, pass_table[3].x, pass_table[3].y, pass_table[3].z, pass_table[3].w);
+    ds_camera_view = mat4x4<f32>(pass_table[4].x, pass_table[4].y, pass_table[4].z, pass_table[4].w, pass_table[5].x, pass_table[5].y, pass_table[5].z, pass_table[5].w, pass_table[6].x, pass_table[6].y, pass_table[6].z, pass_table[6].w, pass_table[7].x, pass_table[7].y, pass_table[7].z, pass_table[7].w);
+    ds_camera_inv = mat4x4<f32>(pass_table[8].x, pass_table[8].y, pass_table[8].z, pass_table[8].w, pass_table[9].x, pass_table[9].y, pass_table[9].z, pass_table[9].w, pass_table[10].x, pass_table[10].y, pass_table[10].z, pass_table[10].w, pass_table[11].x, pass_table[11].y, pass_table[11].z, pass_table[11].w);
+    ds_dpi_factor = pass_table[12].x;
+    ds_dpi_dilate = pass_table[12].y;
+    ds_time = pass_table[12].z;
+    ds_view_transform = mat4x4<f32>(view_table[0].x, view_table[0].y, view_table[0].z, view_table[0].w, view_table[1].x, view_table[1].y, view_table[1].z, view_table[1].w, view_table[2].x, view_table[2].y, view_table[2].z, view_table[2].w, view_table[3].x, view_table[3].y, view_table[3].z, view_table[3].w);
+    ds_rotation = input.packed_varying_0.x;
+    ds_opacity = input.packed_varying_0.y;
+    ds_scale = input.packed_varying_0.z;
+    ds_rect_size.x = input.packed_varying_0.w;
+    ds_rect_size.y = input.packed_varying_1.x;
+    ds_pos.x = input.packed_varying_1.y;
+    ds_pos.y = input.packed_varying_1.z;
+
+    return fn_makepad_widgets__base__RotatedImage__draw_bg_pixel();
+}
@@ -0,0 +1,219 @@
+@group(0) @binding(0) var<uniform> const_table: array<vec4<f32>, 21>;
+@group(0) @binding(1) var<uniform> draw_table: array<vec4<f32>, 1>;
+@group(0) @binding(2) var<uniform> pass_table: array<vec4<f32>, 13>;
+@group(0) @binding(3) var<uniform> view_table: array<vec4<f32>, 4>;
+
+var<private> ds_camera_projection: mat4x4<f32>;
+var<private> ds_camera_view: mat4x4<f32>;
+var<private> ds_camera_inv: mat4x4<f32>;
+var<private> ds_dpi_factor: f32;
+var<private> ds_dpi_dilate: f32;
+var<private> ds_time: f32;
+var<private> ds_view_transform: mat4x4<f32>;
+var<private> ds_draw_zbias: f32;
+var<private> ds_hover: f32;
+var<private> ds_focus: f32;
+var<private> ds_drag: f32;
+var<private> ds_rect_pos: vec2<f32>;
+var<private> ds_rect_size: vec2<f32>;
+var<private> ds_draw_clip: vec4<f32>;
+var<private> ds_draw_depth: f32;
+var<private> ds_0: f32;
+var<private> ds_slide_pos: f32;
+var<private> ds_slider_type: f32;
+var<private> ds_geom_pos: vec2<f32>;
+var<private> ds_pos: vec2<f32>;
+
+struct struct_makepad_draw__shader__std__Sdf2d {
+    f_pos: vec2<f32>,
+    f_result: vec4<f32>,
+    f_last_pos: vec2<f32>,
+    f_start_pos: vec2<f32>,
+    f_shape: f32,
+    f_clip: f32,
+    f_has_clip: f32,
+    f_old_shape: f32,
+    f_blur: f32,
+    f_aa: f32,
+    f_scale_factor: f32,
+    f_dist: f32,
+}
+
+fn fn_makepad_draw__shader__std__Sdf2d_rect(var_self_0: ptr<function, struct_makepad_draw__shader__std__Sdf2d>, p_var_x_0: f32, p_var_y_0: f32, p_var_w_0: f32, p_var_h_0: f32) {
+    var var_x_0 = p_var_x_0;
+    var var_y_0 = p_var_y_0;
+    var var_w_0 = p_var_w_0;
+    var var_h_0 = p_var_h_0;
+    var var_s_0: vec2<f32> = (vec2<f32>(var_w_0, var_h_0) * const_table[19].y);
+    var var_d_0: vec2<f32> = (abs(((vec2<f32>(var_x_0, var_y_0) - (*var_self_0).f_pos) + var_s_0)) - var_s_0);
+    var var_dm_0: vec2<f32> = min(var_d_0, vec2<f32>(const_table[19].z, const_table[19].w));
+    (*var_self_0).f_dist = (max(var_dm_0.x, var_dm_0.y) + length(max(var_d_0, vec2<f32>(const_table[20].x, const_table[20].y))));
+    (*var_self_0).f_old_shape = (*var_self_0).f_shape;
+    (*var_self_0).f_shape = min((*var_self_0).f_shape, (*var_self_0).f_dist);
+}
+
+fn fn_makepad_draw__shader__std__Sdf2d_calc_blur(var_self_0: ptr<function, struct_makepad_draw__shader__std__Sdf2d>, p_var_w_0: f32) -> f32 {
+    var var_w_0 = p_var_w_0;
+    var var_wa_0: f32 = clamp((-var_w_0 * (*var_self_0).f_aa), const_table[17].w, const_table[18].x);
+    var var_wb_0: f32 = const_table[18].y;
+    if((*var_self_0).f_blur > const_table[18].z) {
+            var_wb_0 = clamp((-var_w_0 / (*var_self_0).f_blur), const_table[18].w, const_table[19].x);
+    }
+
+    return (var_wa_0 * var_wb_0);
+}
+
+fn fn_makepad_draw__shader__std__Sdf2d_fill_keep_premul(var_self_0: ptr<function, struct_makepad_draw__shader__std__Sdf2d>, p_var_source_0: vec4<f32>) -> vec4<f32> {
+    var var_source_0 = p_var_source_0;
+    var var_f_0: f32 = fn_makepad_draw__shader__std__Sdf2d_calc_blur (&(*var_self_0), (*var_self_0).f_shape);
+    (*var_self_0).f_result = ((var_source_0 * var_f_0) + ((*var_self_0).f_result * (const_table[16].w - (var_source_0.a * var_f_0))));
+    if((*var_self_0).f_has_clip > const_table[17].x) {
+            var var_f2_0: f32 = (const_table[17].y - fn_makepad_draw__shader__std__Sdf2d_calc_blur (&(*var_self_0), -(*var_self_0).f_clip));
+        (*var_self_0).f_result = ((var_source_0 * var_f2_0) + ((*var_self_0).f_result * (const_table[17].z - (var_source_0.a * var_f2_0))));
+    }
+
+    return (*var_self_0).f_result;
+}
+
+fn fn_makepad_draw__shader__std__Sdf2d_fill_premul(var_self_0: ptr<function, struct_makepad_draw__shader__std__Sdf2d>, p_var_color_0: vec4<f32>) -> vec4<f32> {
+    var var_color_0 = p_var_color_0;
+    fn_makepad_draw__shader__std__Sdf2d_fill_keep_premul (&(*var_self_0), var_color_0);
+    (*var_self_0).f_shape = const_table[16].x;    (*var_self_0).f_old_shape = (*var_self_0).f_shape;
+    (*var_self_0).f_clip = -const_table[16].y;
+    (*var_self_0).f_has_clip = const_table[16].z;
+    return (*var_self_0).f_result;
+}
+
+fn fn_makepad_draw__shader__std__Sdf2d_fill(var_self_0: ptr<function, struct_makepad_draw__shader__std__Sdf2d>, p_var_color_0: vec4<f32>) -> vec4<f32> {
+    var var_color_0 = p_var_color_0;
+    return fn_makepad_draw__shader__std__Sdf2d_fill_premul (&(*var_self_0), vec4<f32>((var_color_0.rgb * var_color_0.a), var_color_0.a));
+}
+
+fn fn_makepad_draw__shader__std__Sdf2d_antialias(p_var_p_0: vec2<f32>) -> f32 {
+    var var_p_0 = p_var_p_0;
+    return (const_table[15].w / length(vec2<f32>(length(dpdx(var_p_0)), length(dpdy(var_p_0)))));
+}
+
+fn fn_makepad_draw__shader__std__Sdf2d_viewport(p_var_pos_0: vec2<f32>) -> struct_makepad_draw__shader__std__Sdf2d {
+    var var_pos_0 = p_var_pos_0;
+    return struct_makepad_draw__shader__std__Sdf2d(var_pos_0,vec4<f32>(const_table[13].y),vec2<f32>(const_table[13].z),vec2<f32>(const_table[13].w),const_table[14].x,-const_table[14].y,const_table[14].z,const_table[14].w,const_table[15].x,fn_makepad_draw__shader__std__Sdf2d_antialias (var_pos_0),const_table[15].y,const_table[15].z);
+}
+
+fn fn_makepad_widgets__theme_desktop_dark__Slider__draw_slider_pixel() -> vec4<f32> {
+    var var_slider_height_0: f32 = const_table[0].y;
+    var var_nub_size_0: f32 = mix(const_table[0].z, const_table[0].w, ds_hover);
+    var var_nubbg_size_0: f32 = const_table[1].x;
+    var var_sdf_0: struct_makepad_draw__shader__std__Sdf2d = fn_makepad_draw__shader__std__Sdf2d_viewport ((ds_pos * ds_rect_size));
+    var var_slider_bg_color_0: vec4<f32> = mix(vec4<f32>(const_table[1].y, const_table[1].z, const_table[1].w, const_table[2].x), vec4<f32>(const_table[2].y, const_table[2].z, const_table[2].w, const_table[3].x), ds_focus);
+    var var_slider_color_0: vec4<f32> = mix(mix(vec4<f32>(const_table[3].y, const_table[3].z, const_table[3].w, const_table[4].x), vec4<f32>(const_table[4].y, const_table[4].z, const_table[4].w, const_table[5].x), ds_hover), vec4<f32>(const_table[5].y, const_table[5].z, const_table[5].w, const_table[6].x), ds_focus);
+    var var_nub_color_0: vec4<f32> = mix(mix(vec4<f32>(const_table[6].y, const_table[6].z, const_table[6].w, const_table[7].x), vec4<f32>(const_table[7].y, const_table[7].z, const_table[7].w, const_table[8].x), ds_hover), mix(vec4<f32>(const_table[8].y, const_table[8].z, const_table[8].w, const_table[9].x), vec4<f32>(const_table[9].y, const_table[9].z, const_table[9].w, const_table[10].x), ds_drag), ds_focus);
+    var var_nubbg_color_0: vec4<f32> = mix(vec4<f32>(const_table[10].y, const_table[10].z, const_table[10].w, const_table[11].x), vec4<f32>(const_table[11].y, const_table[11].z, const_table[11].w, const_table[12].x), ds_drag);
+    if(abs(ds_slider_type - 1.0)<0.5){
+            fn_makepad_draw__shader__std__Sdf2d_rect (&var_sdf_0, const_table[12].y, (ds_rect_size.y - var_slider_height_0), ds_rect_size.x, var_slider_height_0);
+        fn_makepad_draw__shader__std__Sdf2d_fill (&var_sdf_0, var_slider_bg_color_0);
+        fn_makepad_draw__shader__std__Sdf2d_rect (&var_sdf_0, const_table[12].z, (ds_rect_size.y - var_slider_height_0), ((ds_slide_pos * (ds_rect_size.x - var_nub_size_0)) + var_nub_size_0), var_slider_height_0);
+        fn_makepad_draw__shader__std__Sdf2d_fill (&var_sdf_0, var_slider_color_0);
+        var var_nubbg_x_0: f32 = (((ds_slide_pos * (ds_rect_size.x - var_nub_size_0)) - (var_nubbg_size_0 * const_table[12].w)) + (const_table[13].x * var_nub_size_0));
+        fn_makepad_draw__shader__std__Sdf2d_rect (&var_sdf_0, var_nubbg_x_0, (ds_rect_size.y - var_slider_height_0), var_nubbg_size_0, var_slider_height_0);
+        fn_makepad_draw__shader__std__Sdf2d_fill (&var_sdf_0, var_nubbg_color_0);
+        var var_nub_x_0: f32 = (ds_slide_pos * (ds_rect_size.x - var_nub_size_0));
+        fn_makepad_draw__shader__std__Sdf2d_rect (&var_sdf_0, var_nub_x_0, (ds_rect_size.y - var_slider_height_0), var_nub_size_0, var_slider_height_0);
+        fn_makepad_draw__shader__std__Sdf2d_fill (&var_sdf_0, var_nub_color_0);
+    }else  if(abs(ds_slider_type - 2.0)<0.5){
+    }else  if(abs(ds_slider_type - 3.0)<0.5){
+    }
+    return var_sdf_0.f_result;
+}
+
+fn fn_makepad_widgets__theme_desktop_dark__Slider__draw_slider_clip_and_transform_vertex(p_var_rect_pos_0: vec2<f32>, p_var_rect_size_0: vec2<f32>) -> vec4<f32> {
+    var var_rect_pos_0 = p_var_rect_pos_0;
+    var var_rect_size_0 = p_var_rect_size_0;
+    var var_clipped_0: vec2<f32> = clamp(((ds_geom_pos * var_rect_size_0) + var_rect_pos_0), ds_draw_clip.xy, ds_draw_clip.zw);
+    ds_pos = ((var_clipped_0 - var_rect_pos_0) / var_rect_size_0);
+    return (ds_camera_projection * (ds_camera_view * (ds_view_transform * vec4<f32>(var_clipped_0.x, var_clipped_0.y, (ds_draw_depth + ds_draw_zbias), const_table[0].x))));
+}
+
+fn fn_makepad_widgets__theme_desktop_dark__Slider__draw_slider_vertex() -> vec4<f32> {
+    return fn_makepad_widgets__theme_desktop_dark__Slider__draw_slider_clip_and_transform_vertex (ds_rect_pos, ds_rect_size);
+}
+
+struct VertexInput {
+    @location(0) packed_geometry_0: vec2<f32>,
+    @location(1) packed_instance_0: vec4<f32>,
+    @location(2) packed_instance_1: vec4<f32>,
+    @location(3) packed_instance_2: vec4<f32>,
+    @location(4) packed_instance_3: vec3<f32>,
+}
+
+struct Varyings {
+    @builtin(position) position: vec4<f32>,
+    @location(0) packed_varying_0: vec4<f32>,
+    @location(1) packed_varying_1: vec4<f32>,
+    @location(2) packed_varying_2: f32,
+}
+
+@vertex
+fn vertex_main(input: VertexInput) -> Varyings {
+    ds_draw_zbias = draw_table[0].x;
+    ds_camera_projection = mat4x4<f32>(pass_table[0].x, pass_table[0].y, pass_table[0].z, pass_table[0].w, pass_table[1].x, pass_table[1].y, pass_table[1].z, pass_table[1].w, pass_table[2].x, pass_table[2].y, pass_table[2].z, pass_table[2].w, pass_table[3].x, pass_table[3].y, pass_table[3].z, pass_table[3].w);
+    ds_camera_view = mat4x4<f32>(pass_table[4].x, pass_table[4].y, pass_table[4].z, pass_table[4].w, pass_table[5].x, pass_table[5].y, pass_table[5].z, pass_table[5].w, pass_table[6].x, pass_table[6].y, pass_table[6].z, pass_table[6].w, pass_table[7].x, pass_table[7].y, pass_table[7].z, pass_table[7].w);
+    ds_camera_inv = mat4x4<f32>(pass_table[8].x, pass_table[8].y, pass_table[8].z, pass_table[8].w, pass_table[9].x, pass_table[9].y, pass_table[9].z, pass_table[9].w, pass_table[10].x, pass_table[10].y, pass_table[10].z, pass_table[10].w, pass_table[11].x, pass_table[11].y, pass_table[11].z, pass_table[11].w);
+    ds_dpi_factor = pass_table[12].x;
+    ds_dpi_dilate = pass_table[12].y;
+    ds_time = pass_table[12].z;
+    ds_view_transform = mat4x4<f32>(view_table[0].x, view_table[0].y, view_table[0].z, view_table[0].w, view_table[1].x, view_table[1].y, view_table[1].z, view_table[1].w, view_table[2].x, view_table[2].y, view_table[2].z, view_table[2].w, view_table[3].x, view_table[3].y, view_table[3].z, view_table[3].w);
+    ds_hover = input.packed_instance_0.x;
+    ds_focus = input.packed_instance_0.y;
+    ds_drag = input.packed_instance_0.z;
+    ds_rect_pos.x = input.packed_instance_0.w;
+    ds_rect_pos.y = input.packed_instance_1.x;
+    ds_rect_size.x = input.packed_instance_1.y;
+    ds_rect_size.y = input.packed_instance_1.z;
+    ds_draw_clip.x = input.packed_instance_1.w;
+    ds_draw_clip.y = input.packed_instance_2.x;
+    ds_draw_clip.z = input.packed_instance_2.y;
+    ds_draw_clip.w = input.packed_instance_2.z;
+    ds_draw_depth = input.packed_instance_2.w;
+    ds_0 = input.packed_instance_3.x;
+    ds_slide_pos = input.packed_instance_3.y;
+    ds_slider_type = input.packed_instance_3.z;
+    ds_geom_pos.x = input.packed_geometry_0.x;
+    ds_geom_pos.y = input.packed_geometry_0.y;
+
+    var output: Varyings;
+    output.position = fn_makepad_widgets__theme_desktop_dark__Slider__draw_slider_vertex();
+
+    output.packed_varying_0.x = ds_hover;
+    output.packed_varying_0.y = ds_focus;
+    output.packed_varying_0.z = ds_drag;
+    output.packed_varying_0.w = ds_rect_size.x;
+    output.packed_varying_1.x = ds_rect_size.y;
+    output.packed_varying_1.y = ds_slide_pos;
+    output.packed_varying_1.z = ds_slider_type;
+    output.packed_varying_1.w = ds_pos.x;
+    output.packed_varying_2 = ds_pos.y;
+    return output;
+}
+
+@fragment
+fn fragment_main(input: Varyings) -> @location(0) vec4<f32> {
+    ds_draw_zbias = draw_table[0].x;
+    ds_camera_projection = mat4x4<f32>(pass_table[0].x, pass_table[0].y, pass_table[0].z, pass_table[0].w, pass_table[1].x, pass_table[1].y, pass_table[1].z, pass_table[1].w, pass_table[2].x, pass_table[2].y, pass_table[2].z, pass_table[2].w, pass_table[3].x, pass_table[3].y, pass_table[3].z, pass_table[3].w);
+    ds_camera_view = mat4x4<f32>(pass_table[4].x, pass_table[4].y, pass_table[4].z, pass_table[4].w, pass_table[5].x, pass_table[5].y, pass_table[5].z, pass_table[5].w, pass_table[6].x, pass_table[6].y, pass_table[6].z, pass_table[6].w, pass_table[7].x, pass_table[7].y, pass_table[7].z, pass_table[7].w);
+    ds_camera_inv = mat4x4<f32>(pass_table[8].x, pass_table[8].y, pass_table[8].z, pass_table[8].w, pass_table[9].x, pass_table[9].y, pass_table[9].z, pass_table[9].w, pass_table[10].x, pass_table[10].y, pass_table[10].z, pass_table[10].w, pass_table[11].x, pass_table[11].y, pass_table[11].z, pass_table[11].w);
+    ds_dpi_factor = pass_table[12].x;
+    ds_dpi_dilate = pass_table[12].y;
+    ds_time = pass_table[12].z;
+    ds_view_transform = mat4x4<f32>(view_table[0].x, view_table[0].y, view_table[0].z, view_table[0].w, view_table[1].x, view_table[1].y, view_table[1].z, view_table[1].w, view_table[2].x, view_table[2].y, view_table[2].z, view_table[2].w, view_table[3].x, view_table[3].y, view_table[3].z, view_table[3].w);
+    ds_hover = input.packed_varying_0.x;
+    ds_focus = input.packed_varying_0.y;
+    ds_drag = input.packed_varying_0.z;
+    ds_rect_size.x = input.packed_varying_0.w;
+    ds_rect_size.y = input.packed_varying_1.x;
+    ds_slide_pos = input.packed_varying_1.y;
+    ds_slider_type = input.packed_varying_1.z;
+    ds_pos.x = input.packed_varying_1.w;
+    ds_pos.y = input.packed_varying_2;
+
+    return fn_makepad_widgets__theme_desktop_dark__Slider__draw_slider_pixel();
+}
@@ -0,0 +1,235 @@
+@group(0) @binding(0) var<uniform> const_table: array<vec4<f32>, 9>;
+@group(0) @binding(1) var<uniform> draw_table: array<vec4<f32>, 1>;
+@group(0) @binding(2) var<uniform> pass_table: array<vec4<f32>, 13>;
+@group(0) @binding(3) var<uniform> user_table: array<vec4<f32>, 4>;
+@group(0) @binding(4) var<uniform> view_table: array<vec4<f32>, 4>;
+@group(0) @binding(5) var ds_base_color_texture: texture_2d<f32>;
+@group(0) @binding(6) var default_sampler: sampler;
+
+var<private> ds_camera_projection: mat4x4<f32>;
+var<private> ds_camera_view: mat4x4<f32>;
+var<private> ds_camera_inv: mat4x4<f32>;
+var<private> ds_dpi_factor: f32;
+var<private> ds_dpi_dilate: f32;
+var<private> ds_time: f32;
+var<private> ds_view_transform: mat4x4<f32>;
+var<private> ds_draw_zbias: f32;
+var<private> ds_transform_x: vec4<f32>;
+var<private> ds_transform_y: vec4<f32>;
+var<private> ds_transform_z: vec4<f32>;
+var<private> ds_transform_w: vec4<f32>;
+var<private> ds_base_color: vec4<f32>;
+var<private> ds_metallic: f32;
+var<private> ds_roughness: f32;
+var<private> ds_emissive: vec3<f32>;
+var<private> ds_has_texture: f32;
+var<private> ds_geom_pos: vec3<f32>;
+var<private> ds_99b3cae7d88b5bea: f32;
+var<private> ds_geom_normal: vec3<f32>;
+var<private> ds_geom_uv: vec2<f32>;
+var<private> ds_camera_pos: vec3<f32>;
+var<private> ds_light_dir: vec3<f32>;
+var<private> ds_light_color: vec3<f32>;
+var<private> ds_ambient_sky: vec3<f32>;
+var<private> ds_ambient_ground: vec3<f32>;
+var<private> ds_world_pos: vec3<f32>;
+var<private> ds_world_normal: vec3<f32>;
+var<private> ds_uv: vec2<f32>;
+
+fn sample2d(tex: texture_2d<f32>, pos: vec2<f32>) -> vec4<f32> {
+    return textureSampleLevel(tex, default_sampler, pos, 0.0);
+}
+
+fn fn_makepad_widgets__base__View3D__draw_mesh_shade(p_var_albedo_0: vec3<f32>) -> vec3<f32> {
+    var var_albedo_0 = p_var_albedo_0;
+    var var_n_0: vec3<f32> = normalize(ds_world_normal);
+    var var_v_0: vec3<f32> = normalize((ds_camera_pos - ds_world_pos));
+    if(dot(var_n_0, var_v_0) < const_table[0].y) {
+            var_n_0 = -var_n_0;
+    }
+
+    var var_l_0: vec3<f32> = normalize(ds_light_dir);
+    var var_h_0: vec3<f32> = normalize((var_l_0 + var_v_0));
+    var var_n_dot_l_0: f32 = max(dot(var_n_0, var_l_0), const_table[0].z);
+    var var_n_dot_v_0: f32 = max(dot(var_n_0, var_v_0), const_table[0].w);
+    var var_n_dot_h_0: f32 = max(dot(var_n_0, var_h_0), const_table[1].x);
+    var var_v_dot_h_0: f32 = max(dot(var_v_0, var_h_0), const_table[1].y);
+    var var_rough_0: f32 = clamp(ds_roughness, const_table[1].z, const_table[1].w);
+    var var_a_0: f32 = (var_rough_0 * var_rough_0);
+    var var_a2_0: f32 = (var_a_0 * var_a_0);
+    var var_d_0: f32 = (((var_n_dot_h_0 * var_n_dot_h_0) * (var_a2_0 - const_table[2].x)) + const_table[2].y);
+    var var_distribution_0: f32 = (var_a2_0 / ((const_table[2].z * var_d_0) * var_d_0));
+    var var_k_0: f32 = (((var_rough_0 + const_table[2].w) * (var_rough_0 + const_table[3].x)) / const_table[3].y);
+    var var_geometry_0: f32 = ((var_n_dot_l_0 / ((var_n_dot_l_0 * (const_table[3].z - var_k_0)) + var_k_0)) * (var_n_dot_v_0 / ((var_n_dot_v_0 * (const_table[3].w - var_k_0)) + var_k_0)));
+    var var_f0_0: vec3<f32> = mix(vec3<f32>(const_table[4].x, const_table[4].y, const_table[4].z), var_albedo_0, ds_metallic);
+    var var_fresnel_0: vec3<f32> = (var_f0_0 + ((vec3<f32>(const_table[4].w, const_table[5].x, const_table[5].y) - var_f0_0) * pow((const_table[5].z - var_v_dot_h_0), const_table[5].w)));
+    var var_specular_0: vec3<f32> = (var_fresnel_0 * ((var_distribution_0 * var_geometry_0) / ((const_table[6].x * var_n_dot_v_0) * max(var_n_dot_l_0, const_table[6].y))));
+    var var_diffuse_0: vec3<f32> = ((((vec3<f32>(const_table[6].z, const_table[6].w, const_table[7].x) - var_fresnel_0) * (const_table[7].y - ds_metallic)) * var_albedo_0) / const_table[7].z);
+    var var_ambient_0: vec3<f32> = (mix(ds_ambient_ground, ds_ambient_sky, ((var_n_0.y * const_table[7].w) + const_table[8].x)) * var_albedo_0);
+    return ((var_ambient_0 + ((((var_diffuse_0 + var_specular_0) * ds_light_color) * var_n_dot_l_0) * const_table[8].y)) + ds_emissive);
+}
+
+fn fn_makepad_widgets__base__View3D__draw_mesh_get_base_color() -> vec4<f32> {
+    if(ds_has_texture > const_table[0].x) {
+            return (ds_base_color * sample2d(ds_base_color_texture, ds_uv));
+    }
+
+    return ds_base_color;
+}
+
+fn fn_makepad_draw__shader__std__Pal_premul(p_var_v_0: vec4<f32>) -> vec4<f32> {
+    var var_v_0 = p_var_v_0;
+    return vec4<f32>((var_v_0.x * var_v_0.w), (var_v_0.y * var_v_0.w), (var_v_0.z * var_v_0.w), var_v_0.w);
+}
+
+fn fn_makepad_widgets__base__View3D__draw_mesh_pixel() -> vec4<f32> {
+    var var_color_0: vec4<f32> = fn_makepad_widgets__base__View3D__draw_mesh_get_base_color ();
+    return fn_makepad_draw__shader__std__Pal_premul (vec4<f32>(fn_makepad_widgets__base__View3D__draw_mesh_shade (var_color_0.xyz), var_color_0.w));
+}
+
+fn fn_makepad_widgets__base__View3D__draw_mesh_transform_point(p_var_p_0: vec3<f32>) -> vec4<f32> {
+    var var_p_0 = p_var_p_0;
+    return ((((ds_transform_x * var_p_0.x) + (ds_transform_y * var_p_0.y)) + (ds_transform_z * var_p_0.z)) + ds_transform_w);
+}
+
+fn fn_makepad_widgets__base__View3D__draw_mesh_vertex() -> vec4<f32> {
+    var var_world_0: vec4<f32> = fn_makepad_widgets__base__View3D__draw_mesh_transform_point (ds_geom_pos);
+    ds_world_pos = var_world_0.xyz;
+    ds_world_normal = (((ds_transform_x * ds_geom_normal.x) + (ds_transform_y * ds_geom_normal.y)) + (ds_transform_z * ds_geom_normal.z)).xyz;
+    ds_uv = ds_geom_uv;
+    return (ds_camera_projection * (ds_camera_view * var_world_0));
+}
+
+struct VertexInput {
+    @location(0) packed_geometry_0: vec4<f32>,
+    @location(1) packed_geometry_1: vec4<f32>,
+    @location(2) packed_geometry_2: f32,
+    @location(3) packed_instance_0: vec4<f32>,
+    @location(4) packed_instance_1: vec4<f32>,
+    @location(5) packed_instance_2: vec4<f32>,
+    @location(6) packed_instance_3: vec4<f32>,
+    @location(7) packed_instance_4: vec4<f32>,
+    @location(8) packed_instance_5: vec4<f32>,
+    @location(9) packed_instance_6: vec2<f32>,
+}
+
+struct Varyings {
+    @builtin(position) position: vec4<f32>,
+    @location(0) packed_varying_0: vec4<f32>,
+    @location(1) packed_varying_1: vec4<f32>,
+    @location(2) packed_varying_2: vec4<f32>,
+    @location(3) packed_varying_3: vec4<f32>,
+    @location(4) packed_varying_4: vec2<f32>,
+}
+
+@vertex
+fn vertex_main(input: VertexInput) -> Varyings {
+    ds_draw_zbias = draw_table[0].x;
+    ds_camera_projection = mat4x4<f32>(pass_table[0].x, pass_table[0].y, pass_table[0].z, pass_table[0].w, pass_table[1].x, pass_table[1].y, pass_table[1].z, pass_table[1].w, pass_table[2].x, pass_table[2].y, pass_table[2].z, pass_table[2].w, pass_table[3].x, pass_table[3].y, pass_table[3].z, pass_table[3].w);
+    ds_camera_view = mat4x4<f32>(pass_table[4].x, pass_table[4].y, pass_table[4].z, pass_table[4].w, pass_table[5].x, pass_table[5].y, pass_table[5].z, pass_table[5].w, pass_table[6].x, pass_table[6].y, pass_table[6].z, pass_table[6].w, pass_table[7].x, pass_table[7].y, pass_table[7].z, pass_table[7].w);
+    ds_camera_inv = mat4x4<f32>(pass_table[8].x, pass_table[8].y, pass_table[8].z, pass_table[8].w, pass_table[9].x, pass_table[9].y, pass_table[9].z, pass_table[9].w, pass_table[10].x, pass_table[10].y, pass_table[10].z, pass_table[10].w, pass_table[11].x, pass_table[11].y, pass_table[11].z, pass_table[11].w);
+    ds_dpi_factor = pass_table[12].x;
+    ds_dpi_dilate = pass_table[12].y;
+    ds_time = pass_table[12].z;
+    ds_camera_pos = vec3<f32>(user_table[0].x, user_table[0].y, user_table[0].z);
+    ds_light_dir = vec3<f32>(user_table[0].w, user_table[1].x, user_table[1].y);
+    ds_light_color = vec3<f32>(user_table[1].z, user_table[1].w, user_table[2].x);
+    ds_ambient_sky = vec3<f32>(user_table[2].y, user_table[2].z, user_table[2].w);
+    ds_ambient_ground = vec3<f32>(user_table[3].x, user_table[3].y, user_table[3].z);
+    ds_view_transform = mat4x4<f32>(view_table[0].x, view_table[0].y, view_table[0].z, view_table[0].w, view_table[1].x, view_table[1].y, view_table[1].z, view_table[1].w, view_table[2].x, view_table[2].y, view_table[2].z, view_table[2].w, view_table[3].x, view_table[3].y, view_table[3].z, view_table[3].w);
+    ds_transform_x.x = input.packed_instance_0.x;
+    ds_transform_x.y = input.packed_instance_0.y;
+    ds_transform_x.z = input.packed_instance_0.z;
+    ds_transform_x.w = input.packed_instance_0.w;
+    ds_transform_y.x = input.packed_instance_1.x;
+    ds_transform_y.y = input.packed_instance_1.y;
+    ds_transform_y.z = input.packed_instance_1.z;
+    ds_transform_y.w = input.packed_instance_1.w;
+    ds_transform_z.x = input.packed_instance_2.x;
+    ds_transform_z.y = input.packed_instance_2.y;
+    ds_transform_z.z = input.packed_instance_2.z;
+    ds_transform_z.w = input.packed_instance_2.w;
+    ds_transform_w.x = input.packed_instance_3.x;
+    ds_transform_w.y = input.packed_instance_3.y;
+    ds_transform_w.z = input.packed_instance_3.z;
+    ds_transform_w.w = input.packed_instance_3.w;
+    ds_base_color.x = input.packed_instance_4.x;
+    ds_base_color.y = input.packed_instance_4.y;
+    ds_base_color.z = input.packed_instance_4.z;
+    ds_base_color.w = input.packed_instance_4.w;
+    ds_metallic = input.packed_instance_5.x;
+    ds_roughness = input.packed_instance_5.y;
+    ds_emissive.x = input.packed_instance_5.z;
+    ds_emissive.y = input.packed_instance_5.w;
+    ds_emissive.z = input.packed_instance_6.x;
+    ds_has_texture = input.packed_instance_6.y;
+    ds_geom_pos.x = input.packed_geometry_0.x;
+    ds_geom_pos.y = input.packed_geometry_0.y;
+    ds_geom_pos.z = input.packed_geometry_0.z;
+    ds_99b3cae7d88b5bea = input.packed_geometry_0.w;
+    ds_geom_normal.x = input.packed_geometry_1.x;
+    ds_geom_normal.y = input.packed_geometry_1.y;
+    ds_geom_normal.z = input.packed_geometry_1.z;
+    ds_geom_uv.x = input.packed_geometry_1.w;
+    ds_geom_uv.y = input.packed_geometry_2;
+
+    var output: Varyings;
+    output.position = fn_makepad_widgets__base__View3D__draw_mesh_vertex();
+
+    output.packed_varying_0.x = ds_base_color.x;
+    output.packed_varying_0.y = ds_base_color.y;
+    output.packed_varying_0.z = ds_base_color.z;
+    output.packed_varying_0.w = ds_base_color.w;
+    output.packed_varying_1.x = ds_metallic;
+    output.packed_varying_1.y = ds_roughness;
+    output.packed_varying_1.z = ds_emissive.x;
+    output.packed_varying_1.w = ds_emissive.y;
+    output.packed_varying_2.x = ds_emissive.z;
+    output.packed_varying_2.y = ds_has_texture;
+    output.packed_varying_2.z = ds_world_pos.x;
+    output.packed_varying_2.w = ds_world_pos.y;
+    output.packed_varying_3.x = ds_world_pos.z;
+    output.packed_varying_3.y = ds_world_normal.x;
+    output.packed_varying_3.z = ds_world_normal.y;
+    output.packed_varying_3.w = ds_world_normal.z;
+    output.packed_varying_4.x = ds_uv.x;
+    output.packed_varying_4.y = ds_uv.y;
+    return output;
+}
+
+@fragment
+fn fragment_main(input: Varyings) -> @location(0) vec4<f32> {
+    ds_draw_zbias = draw_table[0].x;
+    ds_camera_projection = mat4x4<f32>(pass_table[0].x, pass_table[0].y, pass_table[0].z, pass_table[0].w, pass_table[1].x, pass_table[1].y, pass_table[1].z, pass_table[1].w, pass_table[2].x, pass_table[2].y, pass_table[2].z, pass_table[2].w, pass_table[3].x, pass_table[3].y, pass_table[3].z, pass_table[3].w);
+    ds_camera_view = mat4x4<f32>(pass_table[4].x, pass_table[4].y, pass_table[4].z, pass_table[4].w, pass_table[5].x, pass_table[5].y, pass_table[5].z, pass_table[5].w, pass_table[6].x, pass_table[6].y, pass_table[6].z, pass_table[6].w, pass_table[7].x, pass_table[7].y, pass_table[7].z, pass_table[7].w);
+    ds_camera_inv = mat4x4<f32>(pass_table[8].x, pass_table[8].y, pass_table[8].z, pass_table[8].w, pass_table[9].x, pass_table[9].y, pass_table[9].z, pass_table[9].w, pass_table[10].x, pass_table[10].y, pass_table[10].z, pass_table[10].w, pass_table[11].x, pass_table[11].y, pass_table[11].z, pass_table[11].w);
+    ds_dpi_factor = pass_table[12].x;
+    ds_dpi_dilate = pass_table[12].y;
+    ds_time = pass_table[12].z;
+    ds_camera_pos = vec3<f32>(user_table[0].x, user_table[0].y, user_table[0].z);
+    ds_light_dir = vec3<f32>(user_table[0].w, user_table[1].x, user_table[1].y);
+    ds_light_color = vec3<f32>(user_table[1].z, user_table[1].w, user_table[2].x);
+    ds_ambient_sky = vec3<f32>(user_table[2].y, user_table[2].z, user_table[2].w);
+    ds_ambient_ground = vec3<f32>(user_table[3].x, user_table[3].y, user_table[3].z);
+    ds_view_transform = mat4x4<f32>(view_table[0].x, view_table[0].y, view_table[0].z, view_table[0].w, view_table[1].x, view_table[1].y, view_table[1].z, view_table[1].w, view_table[2].x, view_table[2].y, view_table[2].z, view_table[2].w, view_table[3].x, view_table[3].y, view_table[3].z, view_table[3].w);
+    ds_base_color.x = input.packed_varying_0.x;
+    ds_base_color.y = input.packed_varying_0.y;
+    ds_base_color.z = input.packed_varying_0.z;
+    ds_base_color.w = input.packed_varying_0.w;
+    ds_metallic = input.packed_varying_1.x;
+    ds_roughness = input.packed_varying_1.y;
+    ds_emissive.x = input.packed_varying_1.z;
+    ds_emissive.y = input.packed_varying_1.w;
+    ds_emissive.z = input.packed_varying_2.x;
+    ds_has_texture = input.packed_varying_2.y;
+    ds_world_pos.x = input.packed_varying_2.z;
+    ds_world_pos.y = input.packed_varying_2.w;
+    ds_world_pos.z = input.packed_varying_3.x;
+    ds_world_normal.x = input.packed_varying_3.y;
+    ds_world_normal.y = input.packed_varying_3.z;
+    ds_world_normal.z = input.packed_varying_3.w;
+    ds_uv.x = input.packed_varying_4.x;
+    ds_uv.y = input.packed_varying_4.y;
+
+    return fn_makepad_widgets__base__View3D__draw_mesh_pixel();
+}